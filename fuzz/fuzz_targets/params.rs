@@ -0,0 +1,25 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use relayx::params;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(address) = params::address(value) {
+        assert_eq!(params::address(&format!("{:#x}", address)), Ok(address));
+    }
+    if let Ok(id) = params::chain_id(value) {
+        assert_eq!(id.to_string(), value);
+    }
+    if let Ok(hash) = params::hash(value) {
+        assert_eq!(params::hash(&format!("{:#x}", hash)), Ok(hash));
+    }
+    if let Ok(quantity) = params::quantity(value) {
+        assert_eq!(params::quantity(&format!("{:#x}", quantity)), Ok(quantity));
+    }
+    if let Ok(bytes) = params::hex_bytes(value) {
+        assert_eq!(bytes.to_string(), value.to_ascii_lowercase());
+    }
+});
@@ -0,0 +1,117 @@
+//! Fee attestations: quotes signed by the relayer key as EIP-712 typed data.
+//!
+//! A wallet contract that trusts the relayer's address can recover the signer of a
+//! [`FeeQuote`] on-chain and refuse to pay a fee other than the one quoted, or pay one after
+//! the quote's expiry. The typed data is
+//!
+//! ```text
+//! EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)
+//! FeeQuote(address wallet,address token,uint256 fee,uint256 expiry,uint256 chainId)
+//! ```
+//!
+//! with the domain named `Relayx`, version `1`, and the paying wallet as verifying contract.
+//! The zero address as `token` stands for the native currency.
+
+use alloy::primitives::{keccak256, Address, B256, U256};
+
+use crate::signer::RelayerSigner;
+
+/// Domain name wallets check attestations against
+pub const DOMAIN_NAME: &str = "Relayx";
+/// Domain version wallets check attestations against
+pub const DOMAIN_VERSION: &str = "1";
+
+const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const FEE_QUOTE_TYPE: &str =
+    "FeeQuote(address wallet,address token,uint256 fee,uint256 expiry,uint256 chainId)";
+
+/// A quoted fee as the relayer attests to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeQuote {
+    /// Wallet that pays the fee; also the domain's verifying contract
+    pub wallet: Address,
+    pub token: Address,
+    /// Fee in the token's smallest unit
+    pub fee: U256,
+    /// Unix time after which the quote no longer holds
+    pub expiry: u64,
+    pub chain_id: u64,
+}
+
+impl FeeQuote {
+    /// EIP-712 digest the relayer signs
+    pub fn hash(&self) -> B256 {
+        keccak256(self.encode_for_signing())
+    }
+
+    /// `0x1901 || domainSeparator || structHash`, the preimage of [`FeeQuote::hash`]
+    pub fn encode_for_signing(&self) -> Vec<u8> {
+        let chain_id = U256::from(self.chain_id).to_be_bytes::<32>();
+
+        let mut domain = keccak256(DOMAIN_TYPE).to_vec();
+        domain.extend_from_slice(keccak256(DOMAIN_NAME).as_slice());
+        domain.extend_from_slice(keccak256(DOMAIN_VERSION).as_slice());
+        domain.extend_from_slice(&chain_id);
+        domain.extend_from_slice(self.wallet.into_word().as_slice());
+
+        let mut message = keccak256(FEE_QUOTE_TYPE).to_vec();
+        message.extend_from_slice(self.wallet.into_word().as_slice());
+        message.extend_from_slice(self.token.into_word().as_slice());
+        message.extend_from_slice(&self.fee.to_be_bytes::<32>());
+        message.extend_from_slice(&U256::from(self.expiry).to_be_bytes::<32>());
+        message.extend_from_slice(&chain_id);
+
+        let mut digest = vec![0x19, 0x01];
+        digest.extend_from_slice(keccak256(domain).as_slice());
+        digest.extend_from_slice(keccak256(message).as_slice());
+        digest
+    }
+
+    /// 65-byte `r || s || v` signature of [`FeeQuote::hash`] by `signer`, hex-encoded
+    pub async fn sign(&self, signer: &RelayerSigner) -> Result<String, String> {
+        let signature = signer
+            .sign_keccak(&self.encode_for_signing())
+            .await
+            .map_err(|e| format!("Failed to sign fee quote: {}", e))?;
+        Ok(format!("0x{}", hex::encode(signature.as_bytes())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{primitives::PrimitiveSignature, signers::local::PrivateKeySigner};
+
+    #[tokio::test]
+    async fn test_signed_quote_recovers_to_the_relayer() {
+        let signer = PrivateKeySigner::random();
+        let quote = FeeQuote {
+            wallet: Address::repeat_byte(0x11),
+            token: Address::repeat_byte(0x22),
+            fee: U256::from(21_000),
+            expiry: 1_755_917_874,
+            chain_id: 8453,
+        };
+
+        let signature = quote.sign(&signer.clone().into()).await.unwrap();
+        let bytes = hex::decode(signature.trim_start_matches("0x")).unwrap();
+        assert_eq!(bytes.len(), 65);
+        let recovered = PrimitiveSignature::from_bytes_and_parity(&bytes[..64], bytes[64] == 28)
+            .recover_address_from_prehash(&quote.hash())
+            .unwrap();
+        assert_eq!(recovered, signer.address());
+
+        // Every attested field is bound by the signature
+        let other_fee = FeeQuote {
+            fee: U256::from(21_001),
+            ..quote.clone()
+        };
+        let other_chain = FeeQuote {
+            chain_id: 1,
+            ..quote.clone()
+        };
+        assert_ne!(other_fee.hash(), quote.hash());
+        assert_ne!(other_chain.hash(), quote.hash());
+    }
+}
@@ -0,0 +1,295 @@
+//! Delivery of final request state to per-request callback URLs.
+//!
+//! A `relayer_sendTransaction` call may register a `callbackUrl`. Once the request completes
+//! or fails, [`deliver`] POSTs the payload to that URL, signed with the relayer's callback
+//! secret, and retries with exponential backoff until the receiver answers with a 2xx status.
+//! Every attempt is recorded in storage so `relayer_getStatus` can report delivery state.
+//! Deliveries a restart interrupted are resumed when the server starts again.
+//!
+//! Unless [`HostPolicy::AllowPrivate`] is configured, callbacks only reach public hosts: URLs
+//! naming a loopback, private or link-local address are refused, and so are hosts whose names
+//! resolve to one at delivery time.
+//!
+//! Receivers verify a delivery by computing HMAC-SHA256 over `"{timestamp}.{body}"`, keyed
+//! with the shared secret, and comparing it with the [`SIGNATURE_HEADER`] value.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use url::{Host, Url};
+use uuid::Uuid;
+
+use crate::storage::Storage;
+use crate::types::CallbackStatus;
+
+/// Header carrying `sha256=<hex HMAC>` of the timestamp and body
+pub const SIGNATURE_HEADER: &str = "X-Relayx-Signature";
+/// Header carrying the Unix time (seconds) the delivery was signed at
+pub const TIMESTAMP_HEADER: &str = "X-Relayx-Timestamp";
+
+/// Attempts made before a delivery is marked failed
+pub const MAX_ATTEMPTS: u32 = 5;
+/// Wait before the first retry; doubles after every failed attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+/// Bound on a single attempt, connection included
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hosts callbacks may be delivered to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostPolicy {
+    /// Only hosts with public addresses
+    PublicOnly,
+    /// Any host, the relayer's own network included
+    AllowPrivate,
+}
+
+/// Whether `ip` can only be reached from inside the relayer's network: loopback, private,
+/// link-local, shared (CGNAT), unspecified and unique local addresses, and IPv4 ones mapped
+/// into IPv6
+fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || first == 0
+                || (first == 100 && second & 0xc0 == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_internal(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+/// Resolver for deliveries to public hosts only. Names are checked when a connection is
+/// made, so one cannot be pointed at an internal address after its URL was accepted.
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(resolve_public(name.as_str().to_string()))
+    }
+}
+
+/// Addresses of `host`, or an error when any of them is internal
+async fn resolve_public(
+    host: String,
+) -> Result<reqwest::dns::Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+    if let Some(addr) = addrs.iter().find(|addr| is_internal(addr.ip())) {
+        return Err(format!("{} resolves to internal address {}", host, addr.ip()).into());
+    }
+    Ok(Box::new(addrs.into_iter()))
+}
+
+/// Shared HTTP client for `policy`; redirects are not followed so a delivery only reaches
+/// the registered URL
+fn http_client(policy: HostPolicy) -> &'static reqwest::Client {
+    static PUBLIC: OnceLock<reqwest::Client> = OnceLock::new();
+    static ANY: OnceLock<reqwest::Client> = OnceLock::new();
+    let builder = || {
+        reqwest::Client::builder()
+            .timeout(ATTEMPT_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+    };
+    match policy {
+        // Not defaulted on failure, since a default client would resolve any host
+        HostPolicy::PublicOnly => PUBLIC.get_or_init(|| {
+            builder()
+                .dns_resolver(Arc::new(PublicResolver))
+                .build()
+                .expect("callback HTTP client")
+        }),
+        HostPolicy::AllowPrivate => ANY.get_or_init(|| builder().build().unwrap_or_default()),
+    }
+}
+
+/// Check that a callback URL is an absolute http(s) URL whose host `policy` allows. Host
+/// names are resolved, and checked again, when the callback is delivered.
+pub fn validate_url(url: &str, policy: HostPolicy) -> Result<(), String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid callbackUrl: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("callbackUrl must be an http or https URL".to_string());
+    }
+    if policy == HostPolicy::PublicOnly {
+        let internal = match parsed.host() {
+            Some(Host::Ipv4(ip)) => is_internal(IpAddr::V4(ip)),
+            Some(Host::Ipv6(ip)) => is_internal(IpAddr::V6(ip)),
+            Some(Host::Domain(name)) => {
+                let name = name.trim_end_matches('.').to_ascii_lowercase();
+                name == "localhost" || name.ends_with(".localhost")
+            }
+            None => false,
+        };
+        if internal {
+            return Err("callbackUrl must point to a public host".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// `sha256=<hex>` signature of a delivery body signed at `timestamp`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// POST `payload` to the callback registered for `request_id`, retrying until it is accepted
+/// or [`MAX_ATTEMPTS`] have failed. Does nothing when no callback is pending. A delivery
+/// resumed after a restart keeps its attempt count and backoff.
+pub async fn deliver(
+    storage: &Storage,
+    request_id: Uuid,
+    secret: &str,
+    policy: HostPolicy,
+    payload: &serde_json::Value,
+) {
+    let mut callback = match storage.get_callback(request_id).await {
+        Ok(Some(callback)) if callback.status == CallbackStatus::Pending => callback,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::error!("Failed to load callback for {}: {}", request_id, e);
+            return;
+        }
+    };
+    let body = match serde_json::to_vec(payload) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to serialize callback for {}: {}", request_id, e);
+            return;
+        }
+    };
+
+    let mut delay = RETRY_BASE_DELAY * 2u32.pow(callback.attempts.saturating_sub(1));
+    while callback.attempts < MAX_ATTEMPTS {
+        if callback.attempts > 0 {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        let outcome = post(&callback.url, secret, policy, &body).await;
+        callback.attempts += 1;
+        callback.last_attempt_at = Some(Utc::now());
+        match outcome {
+            Ok(()) => {
+                callback.status = CallbackStatus::Delivered;
+                callback.delivered_at = callback.last_attempt_at;
+                callback.last_error = None;
+                tracing::info!(
+                    "Callback for {} delivered after {} attempt(s)",
+                    request_id,
+                    callback.attempts
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Callback attempt {} for {} failed: {}",
+                    callback.attempts,
+                    request_id,
+                    e
+                );
+                callback.last_error = Some(e);
+                if callback.attempts >= MAX_ATTEMPTS {
+                    callback.status = CallbackStatus::Failed;
+                }
+            }
+        }
+        if let Err(e) = storage.update_callback(request_id, &callback).await {
+            tracing::error!("Failed to record callback for {}: {}", request_id, e);
+        }
+        if callback.status != CallbackStatus::Pending {
+            return;
+        }
+    }
+}
+
+/// One signed delivery attempt; any non-2xx answer is a failure
+async fn post(url: &str, secret: &str, policy: HostPolicy, body: &[u8]) -> Result<(), String> {
+    validate_url(url, policy)?;
+    let timestamp = Utc::now().timestamp();
+    let response = http_client(policy)
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, sign(secret, timestamp, body))
+        .body(body.to_vec())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status().as_u16()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_is_hmac_sha256_of_timestamp_and_body() {
+        // HMAC-SHA256("key", "1700000000.{}"), computed independently
+        let expected = "sha256=9d713ed406bb7076d4123f0dc2c39d2df5c654ed4b0cd56b52c8b4c940bd63ae";
+        assert_eq!(sign("key", 1_700_000_000, b"{}"), expected);
+        assert_ne!(sign("other", 1_700_000_000, b"{}"), expected);
+        assert_ne!(sign("key", 1_700_000_001, b"{}"), expected);
+    }
+
+    #[test]
+    fn test_validate_url_requires_http_scheme_and_host() {
+        let public = HostPolicy::PublicOnly;
+        assert!(validate_url("https://example.com/hooks/relay", public).is_ok());
+        assert!(validate_url("http://93.184.216.34/hook", public).is_ok());
+        assert!(validate_url("ftp://example.com", public).is_err());
+        assert!(validate_url("not a url", public).is_err());
+    }
+
+    #[test]
+    fn test_validate_url_refuses_internal_hosts_unless_allowed() {
+        for url in [
+            "http://127.0.0.1:8080",
+            "http://localhost/hook",
+            "http://api.localhost/hook",
+            "http://10.0.0.5/hook",
+            "http://172.16.1.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(
+                validate_url(url, HostPolicy::PublicOnly).is_err(),
+                "{} accepted",
+                url
+            );
+            assert!(validate_url(url, HostPolicy::AllowPrivate).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_names_resolving_to_internal_addresses_are_refused() {
+        let err = resolve_public("localhost".to_string()).await.err().unwrap();
+        assert!(err.to_string().contains("internal address"));
+    }
+}
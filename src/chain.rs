@@ -0,0 +1,1769 @@
+//! Chain access for the relay pipeline.
+//!
+//! Every node interaction (gas price, balance, call, estimate, send, receipt) goes
+//! through the [`ChainClient`] trait. [`AlloyChainClient`] talks to the configured RPC
+//! endpoints through cached per-endpoint providers, over HTTP or, for `ws://` and `wss://`
+//! URLs, a persistent [`WsTransport`]; [`MockChainClient`] answers from memory so the
+//! pipeline can be exercised without a node.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    network::{Ethereum, EthereumWallet},
+    primitives::{keccak256, Address, Bytes, B256, U256, U64},
+    providers::{
+        fillers::{FillProvider, JoinFill, RecommendedFillers, WalletFiller},
+        Identity, Provider, ProviderBuilder, RootProvider,
+    },
+    rpc::{
+        client::{BatchRequest, ClientBuilder, RpcClient},
+        types::{FeeHistory, TransactionReceipt, TransactionRequest},
+    },
+    transports::BoxTransport,
+};
+use async_trait::async_trait;
+use url::Url;
+
+use crate::{
+    signer::RelayerSigner,
+    types::{ReceiptOutcome, SandboxSettings},
+    ws::WsTransport,
+};
+
+/// Outcome of a mined transaction
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TxReceipt {
+    /// `true` when execution succeeded, `false` when it reverted
+    pub success: bool,
+    pub block_number: Option<u64>,
+    pub block_hash: Option<B256>,
+    /// Gas used by this transaction alone
+    pub gas_used: u128,
+    /// Price paid per gas: the gas price of a legacy transaction, or base fee plus tip
+    pub effective_gas_price: u128,
+    /// Blob gas used, for blob transactions
+    pub blob_gas_used: Option<u128>,
+    /// Price paid per blob gas, for blob transactions
+    pub blob_gas_price: Option<u128>,
+    pub logs: Vec<TxLog>,
+}
+
+/// Event emitted by a mined transaction
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TxLog {
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+}
+
+/// Outcome of executing a call against the latest block without broadcasting it
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CallSimulation {
+    /// `false` when the call reverted
+    pub success: bool,
+    /// Return data, or the revert data when the call reverted
+    pub output: Bytes,
+    pub gas_used: u64,
+    /// Logs the call would emit; empty when it reverted
+    pub logs: Vec<TxLog>,
+}
+
+/// Sender, recipient and native value of a broadcast transaction
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TxTransfer {
+    pub from: Address,
+    /// `None` for contract creations
+    pub to: Option<Address>,
+    pub value: U256,
+}
+
+impl From<TransactionReceipt> for TxReceipt {
+    fn from(receipt: TransactionReceipt) -> Self {
+        Self {
+            success: receipt.status(),
+            block_number: receipt.block_number,
+            block_hash: receipt.block_hash,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            blob_gas_used: receipt.blob_gas_used,
+            blob_gas_price: receipt.blob_gas_price,
+            logs: receipt
+                .inner
+                .logs()
+                .iter()
+                .map(|log| TxLog {
+                    address: log.inner.address,
+                    topics: log.inner.data.topics().to_vec(),
+                    data: log.inner.data.data.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Node operations used by the relayer. Each call names the RPC endpoint it targets so
+/// one client can serve every configured chain.
+#[async_trait]
+pub trait ChainClient: Send + Sync {
+    /// Current gas price in wei
+    async fn gas_price(&self, rpc_url: &str) -> Result<u128, String>;
+
+    /// Current blob base fee in wei (`eth_blobBaseFee`); fails on chains without EIP-4844
+    async fn blob_base_fee(&self, rpc_url: &str) -> Result<u128, String>;
+
+    /// `baseFeePerGas` of the latest block, `None` on chains without EIP-1559
+    async fn base_fee(&self, rpc_url: &str) -> Result<Option<u128>, String>;
+
+    /// Native balance of `address` in wei
+    async fn balance(&self, rpc_url: &str, address: Address) -> Result<U256, String>;
+
+    /// Deployed bytecode of `address`, empty for accounts without code
+    async fn code(&self, rpc_url: &str, address: Address) -> Result<Bytes, String>;
+
+    /// Value of storage slot `slot` of `address`
+    async fn storage_at(&self, rpc_url: &str, address: Address, slot: U256)
+        -> Result<U256, String>;
+
+    /// Execute a read-only `eth_call` sending `value` wei and return the raw output
+    async fn call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Bytes, String>;
+
+    /// Gas needed to execute `input` against `to` while sending `value` wei
+    async fn estimate_gas(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<u64, String>;
+
+    /// Execute `input` against `to` sending `value` wei, reporting the logs it would emit and
+    /// its revert data instead of failing when it reverts (`eth_simulateV1`)
+    async fn simulate_call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<CallSimulation, String>;
+
+    /// Number of transactions sent from `address` (its next nonce)
+    async fn transaction_count(&self, rpc_url: &str, address: Address) -> Result<u64, String>;
+
+    /// Next nonce of `address` counting the node's executable pending transactions. Equal to
+    /// [`ChainClient::transaction_count`] when nothing from `address` is waiting to be mined,
+    /// or only transactions queued behind a missing nonce are.
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String>;
+
+    /// Sign `tx` with `signer` and broadcast it, returning the transaction hash
+    async fn send_transaction(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String>;
+
+    /// Broadcast an already signed EIP-2718 transaction, returning its hash
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String>;
+
+    /// Receipt for `hash`, or `None` while the transaction is not yet mined
+    async fn receipt(&self, rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String>;
+
+    /// Receipts for several hashes, in the same order. Clients that can should send the
+    /// lookups as one JSON-RPC batch; the default makes one call per hash.
+    async fn receipts(
+        &self,
+        rpc_url: &str,
+        hashes: &[B256],
+    ) -> Result<Vec<Option<TxReceipt>>, String> {
+        let mut receipts = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            receipts.push(self.receipt(rpc_url, *hash).await?);
+        }
+        Ok(receipts)
+    }
+
+    /// Sender, recipient and value of transaction `hash`, or `None` when the node does not
+    /// know it
+    async fn transfer(&self, rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String>;
+
+    /// Revert data of the mined transaction `hash`, recovered by replaying it with `eth_call`
+    /// at `block_number`, the block it was mined in. Empty when the replay does not revert or
+    /// the node returns no data with the revert.
+    async fn replay_transaction(
+        &self,
+        rpc_url: &str,
+        hash: B256,
+        block_number: u64,
+    ) -> Result<Bytes, String>;
+
+    async fn block_number(&self, rpc_url: &str) -> Result<u64, String>;
+
+    /// `eth_feeHistory` over the latest `block_count` blocks with the given reward percentiles
+    async fn fee_history(
+        &self,
+        rpc_url: &str,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String>;
+
+    /// Chain id reported by the endpoint
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String>;
+
+    /// Wait until the endpoint announces a new block and return its number. Only endpoints
+    /// that push blocks (WebSocket) announce them; for others this never resolves, so
+    /// callers race it against a timer.
+    async fn next_block(&self, _rpc_url: &str) -> u64 {
+        std::future::pending().await
+    }
+}
+
+/// Read-only provider over HTTP or WebSocket
+type ReadProvider = RootProvider<BoxTransport>;
+
+/// Provider with gas, nonce, chain-id and wallet fillers, used for broadcasting
+type WalletProvider = FillProvider<
+    JoinFill<
+        JoinFill<Identity, <Ethereum as RecommendedFillers>::RecommendedFillers>,
+        WalletFiller<EthereumWallet>,
+    >,
+    ReadProvider,
+    BoxTransport,
+    Ethereum,
+>;
+
+/// Providers built so far, keyed by RPC URL (and signer for the wallet variant)
+#[derive(Default)]
+struct ProviderCache {
+    read_only: Mutex<HashMap<String, ReadProvider>>,
+    wallet: Mutex<HashMap<(String, Address), WalletProvider>>,
+    /// Connections of `ws://` and `wss://` endpoints
+    websockets: Mutex<HashMap<String, WsTransport>>,
+}
+
+/// [`ChainClient`] backed by alloy providers.
+///
+/// Providers are built once per endpoint and reused by every caller, so handlers and
+/// the monitor share connection pools instead of dialing the node on each request.
+/// WebSocket endpoints keep one connection that also announces new blocks.
+/// Clones share the same cache.
+#[derive(Default, Clone)]
+pub struct AlloyChainClient {
+    providers: Arc<ProviderCache>,
+}
+
+fn parse_url(rpc_url: &str) -> Result<Url, String> {
+    Url::parse(rpc_url).map_err(|e| format!("Invalid RPC URL: {}", e))
+}
+
+impl AlloyChainClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read-only provider for `rpc_url`, built on first use
+    fn provider(&self, rpc_url: &str) -> Result<ReadProvider, String> {
+        let mut cache = self
+            .providers
+            .read_only
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(provider) = cache.get(rpc_url) {
+            return Ok(provider.clone());
+        }
+        let url = parse_url(rpc_url)?;
+        let client = match self.websocket(rpc_url)? {
+            Some(transport) => RpcClient::new(transport, false).boxed(),
+            None => ClientBuilder::default().hyper_http(url).boxed(),
+        };
+        let provider = ProviderBuilder::new().on_client(client);
+        cache.insert(rpc_url.to_string(), provider.clone());
+        Ok(provider)
+    }
+
+    /// Connection to `rpc_url` when it is a WebSocket endpoint, dialed on first use
+    fn websocket(&self, rpc_url: &str) -> Result<Option<WsTransport>, String> {
+        let url = parse_url(rpc_url)?;
+        if !matches!(url.scheme(), "ws" | "wss") {
+            return Ok(None);
+        }
+        let transport = self
+            .providers
+            .websockets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(rpc_url.to_string())
+            .or_insert_with(|| WsTransport::connect(url))
+            .clone();
+        Ok(Some(transport))
+    }
+
+    /// Wallet-filled provider for `rpc_url` signing as `signer`, layered over the cached
+    /// read-only provider so both variants share one connection
+    fn wallet_provider(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+    ) -> Result<WalletProvider, String> {
+        let key = (rpc_url.to_string(), signer.address());
+        if let Some(provider) = self
+            .providers
+            .wallet
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+        {
+            return Ok(provider.clone());
+        }
+        let provider = ProviderBuilder::new()
+            .with_recommended_fillers()
+            .wallet(EthereumWallet::from(signer.clone()))
+            .on_provider(self.provider(rpc_url)?);
+        self.providers
+            .wallet
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key)
+            .or_insert(provider.clone());
+        Ok(provider)
+    }
+}
+
+impl fmt::Debug for AlloyChainClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let endpoints = self
+            .providers
+            .read_only
+            .lock()
+            .map(|cache| cache.len())
+            .unwrap_or_default();
+        f.debug_struct("AlloyChainClient")
+            .field("cached_endpoints", &endpoints)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ChainClient for AlloyChainClient {
+    async fn gas_price(&self, rpc_url: &str) -> Result<u128, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_gas_price()
+            .await
+            .map_err(|e| format!("Failed to fetch gas price: {}", e))
+    }
+
+    async fn blob_base_fee(&self, rpc_url: &str) -> Result<u128, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_blob_base_fee()
+            .await
+            .map_err(|e| format!("Failed to fetch blob base fee: {}", e))
+    }
+
+    async fn base_fee(&self, rpc_url: &str) -> Result<Option<u128>, String> {
+        let provider = self.provider(rpc_url)?;
+        let block = provider
+            .get_block_by_number(BlockNumberOrTag::Latest, false.into())
+            .await
+            .map_err(|e| format!("Failed to fetch latest block: {}", e))?
+            .ok_or_else(|| "Node returned no latest block".to_string())?;
+        Ok(block.header.base_fee_per_gas.map(u128::from))
+    }
+
+    async fn balance(&self, rpc_url: &str, address: Address) -> Result<U256, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_balance(address)
+            .await
+            .map_err(|e| format!("Failed to fetch balance: {}", e))
+    }
+
+    async fn code(&self, rpc_url: &str, address: Address) -> Result<Bytes, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_code_at(address)
+            .await
+            .map_err(|e| format!("Failed to fetch code: {}", e))
+    }
+
+    async fn storage_at(
+        &self,
+        rpc_url: &str,
+        address: Address,
+        slot: U256,
+    ) -> Result<U256, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_storage_at(address, slot)
+            .await
+            .map_err(|e| format!("Failed to fetch storage: {}", e))
+    }
+
+    async fn call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Bytes, String> {
+        let provider = self.provider(rpc_url)?;
+        let tx = TransactionRequest::default()
+            .to(to)
+            .input(input.into())
+            .value(value);
+        provider
+            .call(&tx)
+            .await
+            .map_err(|e| format!("eth_call failed: {}", e))
+    }
+
+    async fn estimate_gas(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<u64, String> {
+        let provider = self.provider(rpc_url)?;
+        let tx = TransactionRequest::default()
+            .to(to)
+            .input(input.into())
+            .value(value);
+        provider
+            .estimate_gas(&tx)
+            .await
+            .map_err(|e| format!("Gas estimation failed: {}", e))
+    }
+
+    async fn simulate_call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<CallSimulation, String> {
+        let provider = self.provider(rpc_url)?;
+        let payload = serde_json::json!({
+            "blockStateCalls": [{ "calls": [{ "to": to, "input": input, "value": value }] }],
+        });
+        let blocks: Vec<SimulatedBlock> = provider
+            .client()
+            .request("eth_simulateV1", (payload, BlockNumberOrTag::Latest))
+            .await
+            .map_err(|e| format!("eth_simulateV1 failed: {}", e))?;
+        let call = blocks
+            .into_iter()
+            .flat_map(|block| block.calls)
+            .next()
+            .ok_or_else(|| "eth_simulateV1 returned no call result".to_string())?;
+        Ok(CallSimulation {
+            success: call.status.to::<u64>() == 1,
+            output: call.return_data,
+            gas_used: call.gas_used.to(),
+            logs: call
+                .logs
+                .into_iter()
+                .map(|log| TxLog {
+                    address: log.address,
+                    topics: log.topics,
+                    data: log.data,
+                })
+                .collect(),
+        })
+    }
+
+    async fn transaction_count(&self, rpc_url: &str, address: Address) -> Result<u64, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_transaction_count(address)
+            .await
+            .map_err(|e| format!("Failed to get nonce: {}", e))
+    }
+
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_transaction_count(address)
+            .pending()
+            .await
+            .map_err(|e| format!("Failed to get pending nonce: {}", e))
+    }
+
+    async fn send_transaction(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String> {
+        let provider = self.wallet_provider(rpc_url, signer)?;
+        provider
+            .send_transaction(tx)
+            .await
+            .map(|pending| *pending.tx_hash())
+            .map_err(|e| format!("Failed to send transaction: {}", e))
+    }
+
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .send_raw_transaction(&raw)
+            .await
+            .map(|pending| *pending.tx_hash())
+            .map_err(|e| format!("Failed to send raw transaction: {}", e))
+    }
+
+    async fn receipt(&self, rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_transaction_receipt(hash)
+            .await
+            .map(|receipt| receipt.map(TxReceipt::from))
+            .map_err(|e| format!("Failed to fetch receipt: {}", e))
+    }
+
+    async fn receipts(
+        &self,
+        rpc_url: &str,
+        hashes: &[B256],
+    ) -> Result<Vec<Option<TxReceipt>>, String> {
+        let provider = self.provider(rpc_url)?;
+        let mut batch = BatchRequest::new(provider.client());
+        let waiters = hashes
+            .iter()
+            .map(|hash| {
+                batch.add_call::<_, Option<TransactionReceipt>>(
+                    "eth_getTransactionReceipt",
+                    &(hash,),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to build receipt batch: {}", e))?;
+
+        // Endpoints without batch support reject the whole request; ask one hash at a time
+        if let Err(e) = batch.send().await {
+            tracing::debug!(
+                "Batched receipt lookup failed on {}, retrying singly: {}",
+                rpc_url,
+                e
+            );
+            let mut receipts = Vec::with_capacity(hashes.len());
+            for hash in hashes {
+                receipts.push(self.receipt(rpc_url, *hash).await?);
+            }
+            return Ok(receipts);
+        }
+
+        let mut receipts = Vec::with_capacity(waiters.len());
+        for waiter in waiters {
+            let receipt = waiter
+                .await
+                .map_err(|e| format!("Failed to fetch receipt: {}", e))?;
+            receipts.push(receipt.map(TxReceipt::from));
+        }
+        Ok(receipts)
+    }
+
+    async fn transfer(&self, rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String> {
+        let provider = self.provider(rpc_url)?;
+        // Only three fields are needed, so skip decoding the full typed transaction
+        provider
+            .client()
+            .request("eth_getTransactionByHash", (hash,))
+            .await
+            .map_err(|e| format!("Failed to fetch transaction: {}", e))
+    }
+
+    async fn replay_transaction(
+        &self,
+        rpc_url: &str,
+        hash: B256,
+        block_number: u64,
+    ) -> Result<Bytes, String> {
+        let provider = self.provider(rpc_url)?;
+        let tx: Option<serde_json::Value> = provider
+            .client()
+            .request("eth_getTransactionByHash", (hash,))
+            .await
+            .map_err(|e| format!("Failed to fetch transaction: {}", e))?;
+        let tx = tx.ok_or_else(|| format!("Transaction 0x{:x} not found", hash))?;
+        let call = serde_json::json!({
+            "from": tx["from"],
+            "to": tx["to"],
+            "input": tx["input"],
+            "value": tx["value"],
+            "gas": tx["gas"],
+        });
+        let replayed: Result<Bytes, _> = provider
+            .client()
+            .request("eth_call", (call, BlockNumberOrTag::Number(block_number)))
+            .await;
+        match replayed {
+            Ok(_) => Ok(Bytes::new()),
+            Err(e) => match e.as_error_resp() {
+                Some(payload) => Ok(payload.as_revert_data().unwrap_or_default()),
+                None => Err(format!("eth_call failed: {}", e)),
+            },
+        }
+    }
+
+    async fn block_number(&self, rpc_url: &str) -> Result<u64, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_block_number()
+            .await
+            .map_err(|e| format!("Failed to fetch block number: {}", e))
+    }
+
+    async fn fee_history(
+        &self,
+        rpc_url: &str,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_fee_history(block_count, BlockNumberOrTag::Latest, reward_percentiles)
+            .await
+            .map_err(|e| format!("Failed to fetch fee history: {}", e))
+    }
+
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String> {
+        let provider = self.provider(rpc_url)?;
+        provider
+            .get_chain_id()
+            .await
+            .map_err(|e| format!("Failed to fetch chain id: {}", e))
+    }
+
+    async fn next_block(&self, rpc_url: &str) -> u64 {
+        match self.websocket(rpc_url) {
+            Ok(Some(transport)) => transport.next_head().await,
+            _ => std::future::pending().await,
+        }
+    }
+}
+
+/// One block of an `eth_simulateV1` result
+#[derive(Debug, serde::Deserialize)]
+struct SimulatedBlock {
+    calls: Vec<SimulatedCall>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulatedCall {
+    status: U64,
+    return_data: Bytes,
+    gas_used: U64,
+    #[serde(default)]
+    logs: Vec<SimulatedLog>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SimulatedLog {
+    address: Address,
+    topics: Vec<B256>,
+    data: Bytes,
+}
+
+#[derive(Debug)]
+struct MockState {
+    gas_price: u128,
+    /// `None` models a chain without EIP-4844
+    blob_base_fee: Option<u128>,
+    base_fee: Option<u128>,
+    balance: U256,
+    block_number: u64,
+    fee_history: FeeHistory,
+    call_results: HashMap<Address, Bytes>,
+    /// Outputs for one exact calldata, taking precedence over `call_results`
+    input_call_results: HashMap<(Address, Bytes), Bytes>,
+    revert_reason: Option<String>,
+    /// Revert data returned when a mined transaction is replayed
+    replay_revert: Bytes,
+    gas_estimate: u64,
+    /// Target and value of every gas estimate, in order
+    estimated: Vec<(Address, U256)>,
+    receipt_success: bool,
+    /// Logs carried by every receipt and successful simulation
+    receipt_logs: Vec<TxLog>,
+    mined: bool,
+    /// Hashes mined even while `mined` is off
+    mined_hashes: HashSet<B256>,
+    /// Mined transactions the relayer did not send, such as deposits
+    transfers: HashMap<B256, TxTransfer>,
+    chain_ids: HashMap<String, u64>,
+    sent: Vec<TransactionRequest>,
+    /// Hash handed out for each entry of `sent`
+    sent_hashes: Vec<B256>,
+    /// Hash sent transactions by their contents instead of by position
+    content_hashes: bool,
+    /// Outcomes of transactions to these targets, overriding `mined` and `receipt_success`
+    receipt_outcomes: HashMap<Address, ReceiptOutcome>,
+    /// Signed transactions broadcast as-is, with the endpoint they went to
+    sent_raw: Vec<(String, Bytes)>,
+    /// Transaction counts of other senders; any other address reports the relayer's
+    transaction_counts: HashMap<Address, u64>,
+    /// Nonces of sent transactions evicted by [`MockChainClient::drop_nonce`]
+    dropped: BTreeSet<u64>,
+    /// Sent transactions that filled a dropped nonce rather than adding a new one
+    refilled: usize,
+    latency: Duration,
+}
+
+/// In-memory [`ChainClient`] with configurable answers.
+///
+/// Defaults: 20 gwei gas price, legacy blocks without a base fee, no blob support, unlimited balance, empty call output and
+/// fee history, 150k gas estimate, and successful receipts for every transaction it has "sent". Sent
+/// transactions are recorded and can be inspected with [`MockChainClient::sent_transactions`].
+#[derive(Debug)]
+pub struct MockChainClient {
+    state: Mutex<MockState>,
+}
+
+impl Default for MockChainClient {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(MockState {
+                gas_price: 20_000_000_000,
+                blob_base_fee: None,
+                base_fee: None,
+                balance: U256::MAX,
+                block_number: 1,
+                fee_history: FeeHistory::default(),
+                call_results: HashMap::new(),
+                input_call_results: HashMap::new(),
+                revert_reason: None,
+                replay_revert: Bytes::new(),
+                gas_estimate: 150_000,
+                estimated: Vec::new(),
+                receipt_success: true,
+                receipt_logs: Vec::new(),
+                mined: true,
+                mined_hashes: HashSet::new(),
+                transfers: HashMap::new(),
+                chain_ids: HashMap::new(),
+                sent: Vec::new(),
+                sent_hashes: Vec::new(),
+                content_hashes: false,
+                receipt_outcomes: HashMap::new(),
+                sent_raw: Vec::new(),
+                transaction_counts: HashMap::new(),
+                dropped: BTreeSet::new(),
+                refilled: 0,
+                latency: Duration::ZERO,
+            }),
+        }
+    }
+}
+
+impl MockChainClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mock backing sandbox mode: answers after `chain_latency`, hashes transactions by their
+    /// contents so the same relay always gets the same hash, and settles relays as scripted
+    pub fn sandbox(settings: &SandboxSettings) -> Self {
+        let mock = Self::default()
+            .with_latency(settings.chain_latency)
+            .with_content_hashes();
+        {
+            let mut state = mock.state();
+            match settings.default_receipt {
+                ReceiptOutcome::Success => {}
+                ReceiptOutcome::Reverted => state.receipt_success = false,
+                ReceiptOutcome::Pending => state.mined = false,
+            }
+            state.receipt_outcomes = settings.receipts.clone();
+        }
+        mock
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait out the configured latency before answering
+    async fn delay(&self) {
+        let latency = self.state().latency;
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+    }
+
+    pub fn with_gas_price(self, wei: u128) -> Self {
+        self.state().gas_price = wei;
+        self
+    }
+
+    /// Blob base fee reported by `eth_blobBaseFee`; without it the chain has no blobs
+    pub fn with_blob_base_fee(self, wei: u128) -> Self {
+        self.state().blob_base_fee = Some(wei);
+        self
+    }
+
+    /// Base fee of the latest block; without it the chain looks pre-London
+    pub fn with_base_fee(self, wei: u128) -> Self {
+        self.state().base_fee = Some(wei);
+        self
+    }
+
+    pub fn with_balance(self, wei: U256) -> Self {
+        self.state().balance = wei;
+        self
+    }
+
+    pub fn with_block_number(self, block_number: u64) -> Self {
+        self.state().block_number = block_number;
+        self
+    }
+
+    /// Answer for every `eth_feeHistory` call
+    pub fn with_fee_history(self, history: FeeHistory) -> Self {
+        self.state().fee_history = history;
+        self
+    }
+
+    pub fn with_gas_estimate(self, gas: u64) -> Self {
+        self.state().gas_estimate = gas;
+        self
+    }
+
+    /// Output returned by `eth_call` against `to`
+    pub fn with_call_result(self, to: Address, output: Bytes) -> Self {
+        self.state().call_results.insert(to, output);
+        self
+    }
+
+    /// Output returned by `eth_call` against `to` with exactly `input` as calldata
+    pub fn with_call_result_for_input(self, to: Address, input: Bytes, output: Bytes) -> Self {
+        self.state().input_call_results.insert((to, input), output);
+        self
+    }
+
+    /// Make every `eth_call`, gas estimate and simulation revert with `reason`
+    pub fn with_revert(self, reason: impl Into<String>) -> Self {
+        self.state().revert_reason = Some(reason.into());
+        self
+    }
+
+    /// Mark the receipts of sent transactions as reverted
+    pub fn with_failed_receipts(self) -> Self {
+        self.state().receipt_success = false;
+        self
+    }
+
+    /// Revert data returned when a mined transaction is replayed
+    pub fn with_replay_revert(self, data: Bytes) -> Self {
+        self.state().replay_revert = data;
+        self
+    }
+
+    /// Emit `logs` in the receipt of every mined transaction
+    pub fn with_receipt_logs(self, logs: Vec<TxLog>) -> Self {
+        self.state().receipt_logs = logs;
+        self
+    }
+
+    /// Settle transactions sent to `to` with `outcome`, whatever the default
+    pub fn with_receipt_outcome(self, to: Address, outcome: ReceiptOutcome) -> Self {
+        self.state().receipt_outcomes.insert(to, outcome);
+        self
+    }
+
+    /// Hash each sent transaction from its contents rather than its position, so identical
+    /// transactions get identical hashes across runs
+    pub fn with_content_hashes(self) -> Self {
+        self.state().content_hashes = true;
+        self
+    }
+
+    /// Leave sent transactions unmined, so no receipt is ever found for them
+    pub fn with_unmined_transactions(self) -> Self {
+        self.state().mined = false;
+        self
+    }
+
+    /// A mined transaction sent by someone else, such as a deposit to the fee collector
+    pub fn with_transfer(self, hash: B256, transfer: TxTransfer) -> Self {
+        self.state().transfers.insert(hash, transfer);
+        self
+    }
+
+    /// Chain id reported for `rpc_url`; endpoints without one fail the lookup
+    pub fn with_chain_id(self, rpc_url: impl Into<String>, chain_id: u64) -> Self {
+        self.state().chain_ids.insert(rpc_url.into(), chain_id);
+        self
+    }
+
+    /// Transaction count reported for `address`, such as the sender of a raw transaction
+    pub fn with_transaction_count(self, address: Address, count: u64) -> Self {
+        self.state().transaction_counts.insert(address, count);
+        self
+    }
+
+    /// Delay every answer by `latency`, like a slow node
+    pub fn with_latency(self, latency: Duration) -> Self {
+        self.state().latency = latency;
+        self
+    }
+
+    /// Change the gas price reported from now on
+    pub fn set_gas_price(&self, wei: u128) {
+        self.state().gas_price = wei;
+    }
+
+    /// Change the output returned by `eth_call` against `to` from now on
+    pub fn set_call_result(&self, to: Address, output: Bytes) {
+        self.state().call_results.insert(to, output);
+    }
+
+    /// Mine the sent transaction `hash` even when transactions are left unmined
+    pub fn mine(&self, hash: B256) {
+        self.state().mined_hashes.insert(hash);
+    }
+
+    /// Forget the sent transaction with `nonce` as if the mempool had evicted it: the
+    /// sender's transaction count stalls at the lowest dropped nonce until a new
+    /// transaction with that nonce is sent
+    pub fn drop_nonce(&self, nonce: u64) {
+        self.state().dropped.insert(nonce);
+    }
+
+    /// Transactions broadcast so far, in order
+    pub fn sent_transactions(&self) -> Vec<TransactionRequest> {
+        self.state().sent.clone()
+    }
+
+    /// Change the transaction count reported for `address` from now on
+    pub fn set_transaction_count(&self, address: Address, count: u64) {
+        self.state().transaction_counts.insert(address, count);
+    }
+
+    /// Signed transactions broadcast so far, in order, with the RPC URL each was sent to
+    pub fn sent_raw_transactions(&self) -> Vec<(String, Bytes)> {
+        self.state().sent_raw.clone()
+    }
+
+    /// Target and value of every gas estimate so far, in order
+    pub fn gas_estimates(&self) -> Vec<(Address, U256)> {
+        self.state().estimated.clone()
+    }
+
+    /// Position of the sent transaction with `hash`
+    fn sent_index(state: &MockState, hash: B256) -> Option<usize> {
+        state.sent_hashes.iter().position(|sent| *sent == hash)
+    }
+}
+
+#[async_trait]
+impl ChainClient for MockChainClient {
+    async fn gas_price(&self, _rpc_url: &str) -> Result<u128, String> {
+        self.delay().await;
+        Ok(self.state().gas_price)
+    }
+
+    async fn blob_base_fee(&self, _rpc_url: &str) -> Result<u128, String> {
+        self.delay().await;
+        self.state()
+            .blob_base_fee
+            .ok_or_else(|| "Failed to fetch blob base fee: method not found".to_string())
+    }
+
+    async fn base_fee(&self, _rpc_url: &str) -> Result<Option<u128>, String> {
+        self.delay().await;
+        Ok(self.state().base_fee)
+    }
+
+    async fn balance(&self, _rpc_url: &str, _address: Address) -> Result<U256, String> {
+        self.delay().await;
+        Ok(self.state().balance)
+    }
+
+    async fn code(&self, _rpc_url: &str, _address: Address) -> Result<Bytes, String> {
+        self.delay().await;
+        Ok(Bytes::new())
+    }
+
+    async fn storage_at(
+        &self,
+        _rpc_url: &str,
+        _address: Address,
+        _slot: U256,
+    ) -> Result<U256, String> {
+        self.delay().await;
+        Ok(U256::ZERO)
+    }
+
+    async fn call(
+        &self,
+        _rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        _value: U256,
+    ) -> Result<Bytes, String> {
+        self.delay().await;
+        let state = self.state();
+        if let Some(reason) = &state.revert_reason {
+            return Err(format!("eth_call failed: execution reverted: {}", reason));
+        }
+        Ok(state
+            .input_call_results
+            .get(&(to, input))
+            .or_else(|| state.call_results.get(&to))
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn estimate_gas(
+        &self,
+        _rpc_url: &str,
+        to: Address,
+        _input: Bytes,
+        value: U256,
+    ) -> Result<u64, String> {
+        self.delay().await;
+        let mut state = self.state();
+        state.estimated.push((to, value));
+        match &state.revert_reason {
+            Some(reason) => Err(format!(
+                "Gas estimation failed: execution reverted: {}",
+                reason
+            )),
+            None => Ok(state.gas_estimate),
+        }
+    }
+
+    async fn simulate_call(
+        &self,
+        _rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        _value: U256,
+    ) -> Result<CallSimulation, String> {
+        self.delay().await;
+        let state = self.state();
+        Ok(match &state.revert_reason {
+            // Reverts with `Error(string)`, as `require(false, reason)` does
+            Some(reason) => {
+                let mut output = vec![0x08, 0xc3, 0x79, 0xa0];
+                output.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+                output.extend_from_slice(&U256::from(reason.len()).to_be_bytes::<32>());
+                output.extend_from_slice(reason.as_bytes());
+                output.resize(68 + reason.len().next_multiple_of(32), 0);
+                CallSimulation {
+                    success: false,
+                    output: output.into(),
+                    gas_used: state.gas_estimate,
+                    logs: Vec::new(),
+                }
+            }
+            None => CallSimulation {
+                success: true,
+                output: state
+                    .input_call_results
+                    .get(&(to, input))
+                    .or_else(|| state.call_results.get(&to))
+                    .cloned()
+                    .unwrap_or_default(),
+                gas_used: state.gas_estimate,
+                logs: state.receipt_logs.clone(),
+            },
+        })
+    }
+
+    async fn transaction_count(&self, _rpc_url: &str, address: Address) -> Result<u64, String> {
+        self.delay().await;
+        let state = self.state();
+        if let Some(&count) = state.transaction_counts.get(&address) {
+            return Ok(count);
+        }
+        Ok(match state.dropped.first() {
+            Some(&nonce) => nonce,
+            None => (state.sent.len() - state.refilled) as u64,
+        })
+    }
+
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String> {
+        self.transaction_count(rpc_url, address).await
+    }
+
+    async fn send_transaction(
+        &self,
+        _rpc_url: &str,
+        _signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String> {
+        self.delay().await;
+        let mut state = self.state();
+        if tx.nonce.is_some_and(|nonce| state.dropped.remove(&nonce)) {
+            state.refilled += 1;
+        }
+        let hash = if state.content_hashes {
+            keccak256(serde_json::to_vec(&tx).unwrap_or_default())
+        } else {
+            B256::from(U256::from(state.sent.len() + 1))
+        };
+        state.sent.push(tx);
+        state.sent_hashes.push(hash);
+        Ok(hash)
+    }
+
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String> {
+        self.delay().await;
+        let hash = keccak256(&raw);
+        self.state().sent_raw.push((rpc_url.to_string(), raw));
+        Ok(hash)
+    }
+
+    async fn receipt(&self, _rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String> {
+        self.delay().await;
+        let state = self.state();
+        let sent = Self::sent_index(&state, hash);
+        let sent_raw = state.sent_raw.iter().any(|(_, raw)| keccak256(raw) == hash);
+        let outcome = sent
+            .and_then(|i| state.sent[i].to.and_then(|kind| kind.to().copied()))
+            .and_then(|to| state.receipt_outcomes.get(&to).copied());
+        let mined = match outcome {
+            Some(outcome) => outcome != ReceiptOutcome::Pending,
+            None => state.mined,
+        } || state.mined_hashes.contains(&hash);
+        let success = match outcome {
+            Some(outcome) => outcome != ReceiptOutcome::Reverted,
+            None => state.receipt_success,
+        };
+        let external = state.transfers.contains_key(&hash);
+        // Each blob costs 2^17 blob gas
+        let blob_gas = sent
+            .and_then(|i| state.sent[i].blob_versioned_hashes.as_ref())
+            .map(|hashes| hashes.len() as u128 * 131_072);
+        // Sent transactions pay their own price; anything else the current one
+        let effective_gas_price = sent
+            .and_then(|i| state.sent[i].gas_price.or(state.sent[i].max_fee_per_gas))
+            .unwrap_or(state.gas_price);
+        Ok(
+            ((sent.is_some() || sent_raw) && mined || external).then(|| TxReceipt {
+                success,
+                block_number: Some(state.block_number),
+                block_hash: Some(B256::from(U256::from(state.block_number))),
+                gas_used: u128::from(state.gas_estimate),
+                effective_gas_price,
+                blob_gas_used: blob_gas,
+                blob_gas_price: blob_gas.and(state.blob_base_fee),
+                logs: state.receipt_logs.clone(),
+            }),
+        )
+    }
+
+    async fn transfer(&self, _rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String> {
+        self.delay().await;
+        let state = self.state();
+        if let Some(transfer) = state.transfers.get(&hash) {
+            return Ok(Some(transfer.clone()));
+        }
+        Ok(Self::sent_index(&state, hash).map(|i| {
+            let tx = &state.sent[i];
+            TxTransfer {
+                from: tx.from.unwrap_or_default(),
+                to: tx.to.and_then(|kind| kind.to().copied()),
+                value: tx.value.unwrap_or_default(),
+            }
+        }))
+    }
+
+    async fn replay_transaction(
+        &self,
+        _rpc_url: &str,
+        hash: B256,
+        _block_number: u64,
+    ) -> Result<Bytes, String> {
+        self.delay().await;
+        let state = self.state();
+        let sent = Self::sent_index(&state, hash).is_some()
+            || state.sent_raw.iter().any(|(_, raw)| keccak256(raw) == hash);
+        if !sent {
+            return Err(format!("Transaction 0x{:x} not found", hash));
+        }
+        Ok(state.replay_revert.clone())
+    }
+
+    async fn block_number(&self, _rpc_url: &str) -> Result<u64, String> {
+        self.delay().await;
+        Ok(self.state().block_number)
+    }
+
+    async fn fee_history(
+        &self,
+        _rpc_url: &str,
+        _block_count: u64,
+        _reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String> {
+        self.delay().await;
+        Ok(self.state().fee_history.clone())
+    }
+
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String> {
+        self.delay().await;
+        self.state()
+            .chain_ids
+            .get(rpc_url)
+            .copied()
+            .ok_or_else(|| format!("No chain id mocked for {}", rpc_url))
+    }
+}
+
+/// [`ChainClient`] that fails any call the wrapped client does not answer within `timeout`
+struct TimeoutChainClient {
+    inner: Arc<dyn ChainClient>,
+    timeout: Duration,
+}
+
+impl TimeoutChainClient {
+    async fn limit<T>(
+        &self,
+        operation: &str,
+        call: impl std::future::Future<Output = Result<T, String>>,
+    ) -> Result<T, String> {
+        tokio::time::timeout(self.timeout, call)
+            .await
+            .unwrap_or_else(|_| Err(format!("{} timed out after {:?}", operation, self.timeout)))
+    }
+}
+
+#[async_trait]
+impl ChainClient for TimeoutChainClient {
+    async fn gas_price(&self, rpc_url: &str) -> Result<u128, String> {
+        self.limit("eth_gasPrice", self.inner.gas_price(rpc_url))
+            .await
+    }
+
+    async fn blob_base_fee(&self, rpc_url: &str) -> Result<u128, String> {
+        self.limit("eth_blobBaseFee", self.inner.blob_base_fee(rpc_url))
+            .await
+    }
+
+    async fn base_fee(&self, rpc_url: &str) -> Result<Option<u128>, String> {
+        self.limit("eth_getBlockByNumber", self.inner.base_fee(rpc_url))
+            .await
+    }
+
+    async fn balance(&self, rpc_url: &str, address: Address) -> Result<U256, String> {
+        self.limit("eth_getBalance", self.inner.balance(rpc_url, address))
+            .await
+    }
+
+    async fn code(&self, rpc_url: &str, address: Address) -> Result<Bytes, String> {
+        self.limit("eth_getCode", self.inner.code(rpc_url, address))
+            .await
+    }
+
+    async fn storage_at(
+        &self,
+        rpc_url: &str,
+        address: Address,
+        slot: U256,
+    ) -> Result<U256, String> {
+        self.limit(
+            "eth_getStorageAt",
+            self.inner.storage_at(rpc_url, address, slot),
+        )
+        .await
+    }
+
+    async fn call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Bytes, String> {
+        self.limit("eth_call", self.inner.call(rpc_url, to, input, value))
+            .await
+    }
+
+    async fn estimate_gas(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<u64, String> {
+        self.limit(
+            "eth_estimateGas",
+            self.inner.estimate_gas(rpc_url, to, input, value),
+        )
+        .await
+    }
+
+    async fn simulate_call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<CallSimulation, String> {
+        self.limit(
+            "eth_simulateV1",
+            self.inner.simulate_call(rpc_url, to, input, value),
+        )
+        .await
+    }
+
+    async fn transaction_count(&self, rpc_url: &str, address: Address) -> Result<u64, String> {
+        self.limit(
+            "eth_getTransactionCount",
+            self.inner.transaction_count(rpc_url, address),
+        )
+        .await
+    }
+
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String> {
+        self.limit(
+            "eth_getTransactionCount",
+            self.inner.pending_transaction_count(rpc_url, address),
+        )
+        .await
+    }
+
+    async fn send_transaction(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String> {
+        self.limit(
+            "eth_sendRawTransaction",
+            self.inner.send_transaction(rpc_url, signer, tx),
+        )
+        .await
+    }
+
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String> {
+        self.limit(
+            "eth_sendRawTransaction",
+            self.inner.send_raw_transaction(rpc_url, raw),
+        )
+        .await
+    }
+
+    async fn receipt(&self, rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String> {
+        self.limit(
+            "eth_getTransactionReceipt",
+            self.inner.receipt(rpc_url, hash),
+        )
+        .await
+    }
+
+    async fn receipts(
+        &self,
+        rpc_url: &str,
+        hashes: &[B256],
+    ) -> Result<Vec<Option<TxReceipt>>, String> {
+        self.limit(
+            "eth_getTransactionReceipt batch",
+            self.inner.receipts(rpc_url, hashes),
+        )
+        .await
+    }
+
+    async fn transfer(&self, rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String> {
+        self.limit(
+            "eth_getTransactionByHash",
+            self.inner.transfer(rpc_url, hash),
+        )
+        .await
+    }
+
+    async fn replay_transaction(
+        &self,
+        rpc_url: &str,
+        hash: B256,
+        block_number: u64,
+    ) -> Result<Bytes, String> {
+        self.limit(
+            "eth_call replay",
+            self.inner.replay_transaction(rpc_url, hash, block_number),
+        )
+        .await
+    }
+
+    async fn block_number(&self, rpc_url: &str) -> Result<u64, String> {
+        self.limit("eth_blockNumber", self.inner.block_number(rpc_url))
+            .await
+    }
+
+    async fn fee_history(
+        &self,
+        rpc_url: &str,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String> {
+        self.limit(
+            "eth_feeHistory",
+            self.inner
+                .fee_history(rpc_url, block_count, reward_percentiles),
+        )
+        .await
+    }
+
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String> {
+        self.limit("eth_chainId", self.inner.chain_id(rpc_url))
+            .await
+    }
+
+    // Waiting for a block is not a call, so it is not limited
+    async fn next_block(&self, rpc_url: &str) -> u64 {
+        self.inner.next_block(rpc_url).await
+    }
+}
+
+/// Recently fetched gas prices per RPC URL, with the instant each one goes stale
+type GasPriceCache = Mutex<HashMap<String, (Instant, u128)>>;
+
+/// Next nonce to hand out per RPC URL and sender; `None` until synced from the node
+type NonceSlot = Arc<tokio::sync::Mutex<Option<u64>>>;
+
+/// Shared handle to the [`ChainClient`] a [`crate::Config`] uses.
+///
+/// Defaults to [`AlloyChainClient`]; sandbox mode swaps in [`MockChainClient::sandbox`] so
+/// the service can run without reachable nodes.
+#[derive(Clone)]
+pub struct ChainBackend {
+    client: Arc<dyn ChainClient>,
+    gas_prices: Arc<GasPriceCache>,
+    nonces: Arc<Mutex<HashMap<(String, Address), NonceSlot>>>,
+}
+
+impl ChainBackend {
+    pub fn new(client: impl ChainClient + 'static) -> Self {
+        Self::from_arc(Arc::new(client))
+    }
+
+    pub fn from_arc(client: Arc<dyn ChainClient>) -> Self {
+        Self {
+            client,
+            gas_prices: Arc::default(),
+            nonces: Arc::default(),
+        }
+    }
+
+    /// Fail every chain call that takes longer than `timeout`; a zero `timeout` leaves
+    /// calls unbounded
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        if timeout.is_zero() {
+            return self;
+        }
+        Self {
+            client: Arc::new(TimeoutChainClient {
+                inner: self.client,
+                timeout,
+            }),
+            ..self
+        }
+    }
+
+    /// Append every chain call and its answer to the fixture at `path`; see
+    /// [`crate::recording`]
+    pub fn recorded(self, path: &std::path::Path) -> std::io::Result<Self> {
+        Ok(Self {
+            client: Arc::new(crate::recording::RecordingChainClient::new(
+                self.client,
+                path,
+            )?),
+            ..self
+        })
+    }
+
+    /// Run `eth_call`, `eth_estimateGas` and `eth_simulateV1` in an embedded EVM instead of on
+    /// the node; see [`crate::evm`]
+    #[cfg(feature = "local-evm")]
+    pub fn with_local_evm(self, settings: crate::types::LocalEvmSettings) -> Self {
+        Self {
+            client: Arc::new(crate::evm::LocalEvmClient::new(self.client, settings)),
+            ..self
+        }
+    }
+
+    /// Gas price for `rpc_url`, reusing a previous answer for up to `ttl`. Each entry
+    /// lives an extra random 0-20% of `ttl` so chains fetched together do not all go
+    /// stale at once. A zero `ttl` always asks the node.
+    pub async fn cached_gas_price(&self, rpc_url: &str, ttl: Duration) -> Result<u128, String> {
+        let now = Instant::now();
+        if let Some((_, gas_price)) = self
+            .gas_prices
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(rpc_url)
+            .filter(|(stale_at, _)| !ttl.is_zero() && now < *stale_at)
+        {
+            return Ok(*gas_price);
+        }
+
+        let gas_price = self.client.gas_price(rpc_url).await?;
+        if !ttl.is_zero() {
+            let jitter = ttl.mul_f64((uuid::Uuid::new_v4().as_u128() % 1000) as f64 / 5000.0);
+            self.gas_prices
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(rpc_url.to_string(), (now + ttl + jitter, gas_price));
+        }
+        Ok(gas_price)
+    }
+
+    fn nonce_slot(&self, rpc_url: &str, sender: Address) -> NonceSlot {
+        self.nonces
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry((rpc_url.to_string(), sender))
+            .or_default()
+            .clone()
+    }
+
+    /// Reserve the next nonce for `sender` on `rpc_url`. Only the first reservation (and
+    /// the first after [`ChainBackend::resync_nonce`]) asks the node, for its pending count
+    /// so transactions still in the mempool are not reused; concurrent callers are
+    /// serialized so each gets a distinct nonce.
+    pub async fn reserve_nonce(&self, rpc_url: &str, sender: Address) -> Result<u64, String> {
+        let slot = self.nonce_slot(rpc_url, sender);
+        let mut next = slot.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => {
+                self.client
+                    .pending_transaction_count(rpc_url, sender)
+                    .await?
+            }
+        };
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Next nonce [`ChainBackend::reserve_nonce`] would hand out for `sender`, or `None` when
+    /// nothing has been reserved since startup or the last resync
+    pub async fn tracked_nonce(&self, rpc_url: &str, sender: Address) -> Option<u64> {
+        let slot = self
+            .nonces
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&(rpc_url.to_string(), sender))
+            .cloned()?;
+        let next = *slot.lock().await;
+        next
+    }
+
+    /// Forget the tracked nonce for `sender` so the next reservation reads the node's
+    /// pending count again. Call after a send fails, since the reserved nonce may be unused
+    /// or stale.
+    pub async fn resync_nonce(&self, rpc_url: &str, sender: Address) {
+        *self.nonce_slot(rpc_url, sender).lock().await = None;
+    }
+}
+
+impl Default for ChainBackend {
+    fn default() -> Self {
+        Self::new(AlloyChainClient::new())
+    }
+}
+
+impl Deref for ChainBackend {
+    type Target = dyn ChainClient;
+
+    fn deref(&self) -> &Self::Target {
+        self.client.as_ref()
+    }
+}
+
+impl fmt::Debug for ChainBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChainBackend")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+
+    #[tokio::test]
+    async fn test_mock_receipts_follow_sent_transactions() {
+        let mock = MockChainClient::new().with_failed_receipts();
+        let signer = RelayerSigner::from(PrivateKeySigner::random());
+        let unknown = B256::repeat_byte(0xab);
+        assert_eq!(mock.receipt("mock", unknown).await.unwrap(), None);
+
+        let hash = mock
+            .send_transaction("mock", &signer, TransactionRequest::default())
+            .await
+            .unwrap();
+        let receipt = mock.receipt("mock", hash).await.unwrap().unwrap();
+        assert!(!receipt.success);
+        assert_eq!(
+            mock.receipts("mock", &[unknown, hash]).await.unwrap(),
+            vec![None, Some(receipt)]
+        );
+        assert_eq!(
+            mock.transaction_count("mock", signer.address())
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(mock.chain_id("mock").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_hashes_by_content_and_scripts_receipts() {
+        let reverting = Address::repeat_byte(0xde);
+        let stuck = Address::repeat_byte(0x51);
+        let settings = SandboxSettings {
+            receipts: [
+                (reverting, ReceiptOutcome::Reverted),
+                (stuck, ReceiptOutcome::Pending),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let signer = RelayerSigner::from(PrivateKeySigner::random());
+        let tx = |to: Address| TransactionRequest::default().to(to).nonce(0);
+
+        let mut hashes = Vec::new();
+        for _ in 0..2 {
+            let mock = MockChainClient::sandbox(&settings);
+            let ok = mock
+                .send_transaction("mock", &signer, tx(Address::repeat_byte(0x01)))
+                .await
+                .unwrap();
+            let reverted = mock
+                .send_transaction("mock", &signer, tx(reverting))
+                .await
+                .unwrap();
+            let pending = mock
+                .send_transaction("mock", &signer, tx(stuck))
+                .await
+                .unwrap();
+            assert!(mock.receipt("mock", ok).await.unwrap().unwrap().success);
+            assert!(
+                !mock
+                    .receipt("mock", reverted)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .success
+            );
+            assert_eq!(mock.receipt("mock", pending).await.unwrap(), None);
+            hashes.push(ok);
+        }
+        assert_eq!(hashes[0], hashes[1]);
+    }
+
+    #[tokio::test]
+    async fn test_gas_price_cache_expires_after_ttl() {
+        let mock = Arc::new(MockChainClient::new().with_gas_price(7));
+        let backend = ChainBackend::from_arc(mock.clone());
+        let ttl = Duration::from_millis(50);
+        assert_eq!(backend.cached_gas_price("mock", ttl).await.unwrap(), 7);
+
+        mock.set_gas_price(9);
+        assert_eq!(backend.cached_gas_price("mock", ttl).await.unwrap(), 7);
+        assert_eq!(
+            backend
+                .cached_gas_price("mock", Duration::ZERO)
+                .await
+                .unwrap(),
+            9
+        );
+
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert_eq!(backend.cached_gas_price("mock", ttl).await.unwrap(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_backend_timeout_fails_slow_calls() {
+        let mock = MockChainClient::new().with_latency(Duration::from_millis(200));
+        let backend = ChainBackend::new(mock).with_timeout(Duration::from_millis(20));
+        let err = backend.block_number("mock").await.unwrap_err();
+        assert!(err.contains("eth_blockNumber timed out"), "{}", err);
+
+        let backend = ChainBackend::new(MockChainClient::new()).with_timeout(Duration::ZERO);
+        assert_eq!(backend.block_number("mock").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_nonces_are_reserved_locally_until_resync() {
+        let mock = Arc::new(MockChainClient::new());
+        let backend = ChainBackend::from_arc(mock.clone());
+        let signer = RelayerSigner::from(PrivateKeySigner::random());
+        let sender = signer.address();
+
+        let (a, b) = tokio::join!(
+            backend.reserve_nonce("mock", sender),
+            backend.reserve_nonce("mock", sender)
+        );
+        let mut reserved = vec![a.unwrap(), b.unwrap()];
+        reserved.sort();
+        assert_eq!(reserved, vec![0, 1]);
+        assert_eq!(backend.reserve_nonce("other", sender).await.unwrap(), 0);
+
+        // The node only learns of one transaction; a resync picks its count back up
+        mock.send_transaction("mock", &signer, TransactionRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(backend.reserve_nonce("mock", sender).await.unwrap(), 2);
+        backend.resync_nonce("mock", sender).await;
+        assert_eq!(backend.reserve_nonce("mock", sender).await.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_alloy_providers_are_cached_per_endpoint_and_signer() {
+        let client = AlloyChainClient::new();
+        let signer = RelayerSigner::from(PrivateKeySigner::random());
+        client.provider("http://localhost:8545").unwrap();
+        client.provider("http://localhost:8545").unwrap();
+        client
+            .wallet_provider("http://localhost:8545", &signer)
+            .unwrap();
+        client
+            .wallet_provider(
+                "http://localhost:8545",
+                &RelayerSigner::from(PrivateKeySigner::random()),
+            )
+            .unwrap();
+        assert!(client.provider("not a url").is_err());
+
+        // Clones share the cache
+        let shared = client.clone();
+        shared
+            .wallet_provider("http://localhost:8545", &signer)
+            .unwrap();
+        assert_eq!(shared.providers.read_only.lock().unwrap().len(), 1);
+        assert_eq!(shared.providers.wallet.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,809 @@
+//! Command-line interface: `relayx serve` plus operator subcommands that inspect or
+//! adjust requests either through a running instance (`--url`) or by opening the
+//! RocksDB database directly (`--db-path`).
+
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand};
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    encryption::StorageKeyArgs,
+    export::{export_accounting, parse_bound, ExportFormat},
+    preflight::check_config,
+    rpc::build_status_result,
+    storage::{Storage, TenantScope},
+    types::{NonceHealing, RelayerRequest, RequestStatus, StatusCodes},
+};
+
+/// Error message recorded on requests cancelled through `relayx cancel`
+pub const CANCELLED_BY_OPERATOR: &str = "cancelled by operator";
+
+#[derive(Parser, Debug)]
+#[command(name = "relayx")]
+#[command(about = "A modular relayer service with JSON-RPC endpoints")]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Server options, used when no subcommand is given (same as `relayx serve`)
+    #[command(flatten)]
+    pub config: Config,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run the JSON-RPC relayer service
+    Serve(Box<Config>),
+    /// Show the relayer_getStatus entry for a request
+    Status {
+        /// Request id returned by relayer_sendTransaction, or a transaction hash it broadcast
+        id: String,
+        #[command(flatten)]
+        target: Target,
+    },
+    /// List stored requests, optionally filtered by status or target address
+    List {
+        /// Only show requests in this state: scheduled, pending, processing, completed, failed
+        #[arg(long, value_parser = parse_status)]
+        status: Option<RequestStatus>,
+        /// Only show requests sent to this address, read from the target index
+        #[arg(long)]
+        to: Option<String>,
+        /// Only show requests of this tenant; `--to` reads its partition of the target index
+        #[arg(long)]
+        tenant: Option<String>,
+        /// Maximum number of requests to print
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Admin token of the relayer at `--url`
+        #[arg(long, env = "RELAYX_ADMIN_TOKEN")]
+        admin_token: Option<String>,
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Rebroadcast a request with bumped gas: right away through a running relayer, or by
+    /// handing it back to the monitor when editing the database
+    Resubmit {
+        /// Request id to resubmit
+        id: String,
+        /// API key of the tenant the request belongs to, for the relayer at `--url`
+        #[arg(long, env = "RELAYX_API_KEY")]
+        api_key: Option<String>,
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Stop tracking a pending request and mark it failed; through a running relayer its
+    /// broadcast transaction is replaced and its gas tank debit returned
+    Cancel {
+        /// Request id to cancel
+        id: String,
+        /// Admin token of the relayer at `--url`
+        #[arg(long, env = "RELAYX_ADMIN_TOKEN")]
+        admin_token: Option<String>,
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Export mined requests with their gas cost and collected fee for accounting
+    Export {
+        /// Include requests completed at or after this RFC 3339 time or YYYY-MM-DD date
+        #[arg(long, value_parser = parse_bound)]
+        from: Option<DateTime<Utc>>,
+        /// Include requests completed before this RFC 3339 time or YYYY-MM-DD date
+        #[arg(long, value_parser = parse_bound)]
+        to: Option<DateTime<Utc>>,
+        /// Output format; only csv is supported
+        #[arg(long, default_value = "csv")]
+        format: ExportFormat,
+        /// File to write the export to instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        db: DbArgs,
+    },
+    /// Move requests finished longer ago than a threshold to the archive column family
+    Archive {
+        /// Archive requests that completed or failed more than this many days ago
+        #[arg(long, default_value_t = 30)]
+        older_than_days: u32,
+        #[command(flatten)]
+        db: DbArgs,
+    },
+    /// List nonce gaps the monitor filled with self-transfers, newest first
+    Healings {
+        /// Maximum number of healings to print
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        #[command(flatten)]
+        db: DbArgs,
+    },
+    /// Validate the configuration and the chains it points at without serving
+    CheckConfig {
+        /// Only check the configuration itself; do not contact the chains' nodes
+        #[arg(long)]
+        offline: bool,
+        #[command(flatten)]
+        config: Box<Config>,
+    },
+    /// Fire sendTransaction/getStatus/getFeeData at fixed rates against a running relayer and
+    /// report latency percentiles and error rates
+    #[cfg(feature = "client")]
+    Loadtest(LoadtestArgs),
+}
+
+#[cfg(feature = "client")]
+#[derive(Args, Debug)]
+pub struct LoadtestArgs {
+    /// URL of the relayer to load
+    #[arg(long, env = "RELAYX_URL")]
+    pub url: String,
+    /// How long to keep sending, in seconds
+    #[arg(long, default_value_t = 30)]
+    pub duration_secs: u64,
+    /// relayer_sendTransaction calls per second (0 to skip)
+    #[arg(long, default_value_t = 5.0)]
+    pub send_rate: f64,
+    /// relayer_getStatus calls per second (0 to skip)
+    #[arg(long, default_value_t = 20.0)]
+    pub status_rate: f64,
+    /// relayer_getFeeData calls per second (0 to skip)
+    #[arg(long, default_value_t = 20.0)]
+    pub fee_data_rate: f64,
+    /// Calls allowed to be outstanding at once; further calls are counted as dropped
+    #[arg(long, default_value_t = 512)]
+    pub max_in_flight: usize,
+    /// Chain the canned requests target
+    #[arg(long, default_value = "1")]
+    pub chain_id: String,
+    /// Fee token asked about by relayer_getFeeData (the zero address for the native currency)
+    #[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
+    pub token: String,
+    /// JSON file with the relayer_sendTransaction request to send instead of the canned
+    /// sponsored dry run
+    #[arg(long)]
+    pub request: Option<PathBuf>,
+    /// API key sent with every call, for a relayer serving several tenants
+    #[arg(long, env = "RELAYX_API_KEY")]
+    pub api_key: Option<String>,
+}
+
+/// Where a subcommand reads or changes requests: a running relayer or its database
+#[derive(Args, Debug)]
+pub struct Target {
+    /// URL of a running relayer; when set the request is made over JSON-RPC
+    #[arg(long, env = "RELAYX_URL")]
+    pub url: Option<String>,
+
+    #[command(flatten)]
+    pub db: DbArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct DbArgs {
+    /// Database path for RocksDB storage (the server must not be holding it open)
+    #[arg(long, default_value = "./relayx_db")]
+    pub db_path: PathBuf,
+
+    /// Key the server encrypts stored request data with, needed to read it
+    #[command(flatten)]
+    pub encryption: StorageKeyArgs,
+}
+
+impl DbArgs {
+    fn open(&self) -> Result<Storage> {
+        let storage = Storage::new(&self.db_path)?;
+        Ok(match self.encryption.resolve()? {
+            Some(key) => storage.with_storage_key(&key),
+            None => storage,
+        })
+    }
+}
+
+pub fn parse_status(s: &str) -> Result<RequestStatus, String> {
+    match s.to_lowercase().as_str() {
+        "scheduled" => Ok(RequestStatus::Scheduled),
+        "pending" => Ok(RequestStatus::Pending),
+        "processing" => Ok(RequestStatus::Processing),
+        "completed" => Ok(RequestStatus::Completed),
+        "failed" => Ok(RequestStatus::Failed),
+        other => Err(format!(
+            "unknown status '{}' (expected scheduled, pending, processing, completed or failed)",
+            other
+        )),
+    }
+}
+
+/// Run an operator subcommand. `Command::Serve` is handled by the binary itself.
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Serve(_) => bail!("serve is handled by the binary entry point"),
+        Command::Status { id, target } => status(&id, &target).await,
+        Command::List {
+            status,
+            to,
+            tenant,
+            limit,
+            admin_token,
+            target,
+        } => list(status, to, tenant, limit, admin_token, &target).await,
+        Command::Resubmit {
+            id,
+            api_key,
+            target,
+        } => resubmit(&id, api_key, &target).await,
+        Command::Cancel {
+            id,
+            admin_token,
+            target,
+        } => cancel(&id, admin_token, &target).await,
+        Command::Export {
+            from,
+            to,
+            format,
+            output,
+            db,
+        } => {
+            let storage = db.open()?;
+            match output {
+                Some(path) => {
+                    let mut file = BufWriter::new(File::create(&path)?);
+                    let rows = export_accounting(&storage, from, to, format, &mut file).await?;
+                    eprintln!("Exported {} request(s) to {}", rows, path.display());
+                }
+                None => {
+                    export_accounting(&storage, from, to, format, &mut std::io::stdout().lock())
+                        .await?;
+                }
+            }
+            Ok(())
+        }
+        Command::Archive {
+            older_than_days,
+            db,
+        } => {
+            let storage = db.open()?;
+            let cutoff = Utc::now() - chrono::Duration::days(older_than_days.into());
+            let archived = storage.archive_requests(cutoff).await?;
+            println!(
+                "Archived {} request(s) finished before {}",
+                archived,
+                cutoff.to_rfc3339()
+            );
+            Ok(())
+        }
+        Command::Healings { limit, db } => {
+            let storage = db.open()?;
+            for healing in storage.get_nonce_healings(limit).await? {
+                println!("{}", format_healing_line(&healing));
+            }
+            Ok(())
+        }
+        #[cfg(feature = "client")]
+        Command::Loadtest(args) => loadtest(args).await,
+        Command::CheckConfig { offline, config } => {
+            let report = check_config(&config, !offline).await;
+            println!("{}", report);
+            if report.errors() > 0 {
+                bail!("configuration has {} error(s)", report.errors());
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn status(id: &str, target: &Target) -> Result<()> {
+    let result = match &target.url {
+        #[cfg(feature = "client")]
+        Some(url) => crate::client::RelayxClient::new(url.clone())
+            .get_status(&[id.to_string()])
+            .await?
+            .result
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("no status returned for {}", id))?,
+        #[cfg(not(feature = "client"))]
+        Some(_) => bail!("--url requires relayx to be built with the `client` feature"),
+        None => {
+            let storage = target.db.open()?;
+            build_status_result(&storage, id, &StatusCodes::default(), TenantScope::All).await
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "client"), allow(unused_variables))]
+async fn list(
+    status: Option<RequestStatus>,
+    to: Option<String>,
+    tenant: Option<String>,
+    limit: usize,
+    admin_token: Option<String>,
+    target: &Target,
+) -> Result<()> {
+    let requests = match &target.url {
+        #[cfg(feature = "client")]
+        Some(url) => {
+            let request = crate::types::ListRequestsRequest {
+                admin_token: admin_token.ok_or_else(|| anyhow!("--url requires --admin-token"))?,
+                status: status.map(|s| format!("{:?}", s).to_lowercase()),
+                to,
+                tenant,
+                limit: Some(limit),
+            };
+            crate::client::RelayxClient::new(url.clone())
+                .list_requests(&request)
+                .await?
+                .requests
+        }
+        #[cfg(not(feature = "client"))]
+        Some(_) => bail!("--url requires relayx to be built with the `client` feature"),
+        None => {
+            let storage = target.db.open()?;
+            list_requests(&storage, status, to.as_deref(), tenant.as_deref(), limit).await?
+        }
+    };
+    for req in requests {
+        println!("{}", format_request_line(&req));
+    }
+    Ok(())
+}
+
+/// Through a running relayer the request is replaced right away with
+/// `relayer_resendTransaction`; in the database it is handed back to the monitor
+#[cfg_attr(not(feature = "client"), allow(unused_variables))]
+async fn resubmit(id: &str, api_key: Option<String>, target: &Target) -> Result<()> {
+    match &target.url {
+        #[cfg(feature = "client")]
+        Some(url) => {
+            let mut client = crate::client::RelayxClient::new(url.clone());
+            if let Some(api_key) = api_key {
+                client = client.with_api_key(api_key);
+            }
+            let response = client.resend_transaction(id, None).await?;
+            println!("Request {} rebroadcast with bumped gas", response.id);
+        }
+        #[cfg(not(feature = "client"))]
+        Some(_) => bail!("--url requires relayx to be built with the `client` feature"),
+        None => {
+            let storage = target.db.open()?;
+            let req = resubmit_request(&storage, parse_id(id)?).await?;
+            println!(
+                "Request {} queued for resubmission (last tx {})",
+                req.id,
+                req.transaction_hash.unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Through a running relayer (`admin_cancelRequest`) a broadcast transaction is withdrawn by
+/// a self-transfer at its nonce, the callback is delivered and the gas tank debit returned.
+/// Editing the database can only stop tracking the request; its callback is delivered when
+/// the server next starts.
+#[cfg_attr(not(feature = "client"), allow(unused_variables))]
+async fn cancel(id: &str, admin_token: Option<String>, target: &Target) -> Result<()> {
+    let (id, refunded) = match &target.url {
+        #[cfg(feature = "client")]
+        Some(url) => {
+            let request = crate::types::CancelRequestRequest {
+                admin_token: admin_token.ok_or_else(|| anyhow!("--url requires --admin-token"))?,
+                id: id.to_string(),
+            };
+            let response = crate::client::RelayxClient::new(url.clone())
+                .cancel_request(&request)
+                .await?;
+            if let Some(tx_hash) = &response.replacement_hash {
+                println!("Replaced its pending transaction with {}", tx_hash);
+            }
+            (response.id, response.refunded_wei)
+        }
+        #[cfg(not(feature = "client"))]
+        Some(_) => bail!("--url requires relayx to be built with the `client` feature"),
+        None => {
+            let storage = target.db.open()?;
+            let (req, refunded) = cancel_request(&storage, parse_id(id)?, false).await?;
+            if let Some(tx_hash) = &req.transaction_hash {
+                eprintln!(
+                    "{} may still be mined; cancel through --url to replace it",
+                    tx_hash
+                );
+            }
+            (req.id.to_string(), refunded.map(|wei| wei.to_string()))
+        }
+    };
+    match refunded {
+        Some(wei) => println!(
+            "Request {} cancelled; refunded {} wei to its gas tank",
+            id, wei
+        ),
+        None => println!("Request {} cancelled", id),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+async fn loadtest(args: LoadtestArgs) -> Result<()> {
+    use crate::{
+        client::RelayxClient,
+        loadtest::{run_load_test, LoadTestPlan},
+        types::{ExchangeRateRequest, SendTransactionRequest},
+    };
+
+    let send_request: SendTransactionRequest = match &args.request {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("invalid request in {}: {}", path.display(), e))?,
+        None => serde_json::from_value(serde_json::json!({
+            "to": "0x0000000000000000000000000000000000000001",
+            "data": "0x00",
+            "capabilities": {
+                "payment": { "type": "sponsored", "token": "", "data": "" },
+                "dryRun": true
+            },
+            "chainId": args.chain_id,
+            "authorizationList": ""
+        }))?,
+    };
+    let plan = LoadTestPlan {
+        duration: std::time::Duration::from_secs(args.duration_secs),
+        send_rate: args.send_rate,
+        status_rate: args.status_rate,
+        fee_data_rate: args.fee_data_rate,
+        max_in_flight: args.max_in_flight,
+        send_request,
+        fee_data_request: ExchangeRateRequest {
+            token: args.token,
+            chain_id: args.chain_id,
+        },
+    };
+    let mut client = RelayxClient::new(args.url);
+    if let Some(api_key) = args.api_key {
+        client = client.with_api_key(api_key);
+    }
+    eprintln!(
+        "Loading {} for {}s ({} send/s, {} status/s, {} feeData/s)",
+        client.url(),
+        args.duration_secs,
+        plan.send_rate,
+        plan.status_rate,
+        plan.fee_data_rate
+    );
+    let report = run_load_test(std::sync::Arc::new(client), &plan).await;
+    println!("{}", report);
+    Ok(())
+}
+
+fn parse_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| anyhow!("invalid request id: {}", id))
+}
+
+/// Stored requests matching `status` and sent to `to`, newest first, optionally only those of
+/// `tenant`. A target address is looked up through the index rather than by scanning every
+/// request; the index is partitioned by tenant, so without one it lists untenanted requests.
+pub async fn list_requests(
+    storage: &Storage,
+    status: Option<RequestStatus>,
+    to: Option<&str>,
+    tenant: Option<&str>,
+    limit: usize,
+) -> Result<Vec<RelayerRequest>> {
+    let matches_status = |r: &RelayerRequest| status.as_ref().is_none_or(|s| &r.status == s);
+    if let Some(to) = to {
+        let (requests, _) = storage
+            .get_requests_by_wallet(tenant, to, None, limit, matches_status)
+            .await?;
+        return Ok(requests);
+    }
+    let scope = match tenant {
+        Some(tenant) => TenantScope::Tenant(Some(tenant)),
+        None => TenantScope::All,
+    };
+    let mut requests: Vec<RelayerRequest> = storage
+        .get_requests(scope, None)
+        .await?
+        .into_iter()
+        .filter(matches_status)
+        .collect();
+    requests.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+    requests.truncate(limit);
+    Ok(requests)
+}
+
+fn format_request_line(req: &RelayerRequest) -> String {
+    format!(
+        "{}  chain={}  status={:?}  tx={}  created={}{}",
+        req.id,
+        req.chain_id,
+        req.status,
+        req.transaction_hash.as_deref().unwrap_or("-"),
+        req.created_at.to_rfc3339(),
+        req.error_message
+            .as_deref()
+            .map(|e| format!("  error={}", e))
+            .unwrap_or_default()
+    )
+}
+
+fn format_healing_line(healing: &NonceHealing) -> String {
+    format!(
+        "{}  chain={}  relayer={}  nonce={}  tx={}  gasPrice={}",
+        healing.healed_at.to_rfc3339(),
+        healing.chain_id,
+        healing.relayer,
+        healing.nonce,
+        healing.transaction_hash,
+        healing.gas_price
+    )
+}
+
+/// Move a broadcast request back to Processing so the background monitor bumps
+/// gas and rebroadcasts it on its next pass
+pub async fn resubmit_request(storage: &Storage, id: Uuid) -> Result<RelayerRequest> {
+    let req = storage
+        .get_request(TenantScope::All, id)
+        .await?
+        .ok_or_else(|| anyhow!("request {} not found", id))?;
+    if req.status == RequestStatus::Completed {
+        bail!("request {} is already completed", id);
+    }
+    if req.transaction_hash.is_none() {
+        bail!(
+            "request {} was never broadcast; submit it again with relayer_sendTransaction",
+            id
+        );
+    }
+    storage
+        .update_request_status(id, RequestStatus::Processing, None)
+        .await?;
+    storage
+        .get_request(TenantScope::All, id)
+        .await?
+        .ok_or_else(|| anyhow!("request {} not found", id))
+}
+
+/// Whether a request in `status` is still tracked by the scheduler or monitor
+pub fn is_cancellable(status: &RequestStatus) -> bool {
+    matches!(
+        status,
+        RequestStatus::Scheduled | RequestStatus::Pending | RequestStatus::Processing
+    )
+}
+
+/// Mark a scheduled/pending/processing request failed so the scheduler and monitor stop
+/// tracking it, leaving its callback pending for the server to deliver.
+///
+/// The gas tank debit it took is returned when its call can no longer run: nothing was
+/// broadcast for it, or `withdrawn` says its transaction's nonce was taken by a replacement.
+/// Otherwise the broadcast transaction may still be mined and the debit is kept. Returns the
+/// cancelled request and the refunded amount.
+pub async fn cancel_request(
+    storage: &Storage,
+    id: Uuid,
+    withdrawn: bool,
+) -> Result<(RelayerRequest, Option<u128>)> {
+    let req = storage
+        .get_request(TenantScope::All, id)
+        .await?
+        .ok_or_else(|| anyhow!("request {} not found", id))?;
+    if !is_cancellable(&req.status) {
+        bail!("request {} is {:?} and cannot be cancelled", id, req.status);
+    }
+    storage
+        .update_request_status(
+            id,
+            RequestStatus::Failed,
+            Some(CANCELLED_BY_OPERATOR.to_string()),
+        )
+        .await?;
+    let refunded = if withdrawn || req.transaction_hash.is_none() {
+        storage.refund_gas_tank(id).await?
+    } else {
+        None
+    };
+    let req = storage
+        .get_request(TenantScope::All, id)
+        .await?
+        .ok_or_else(|| anyhow!("request {} not found", id))?;
+    Ok((req, refunded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::tempdir;
+
+    fn request(status: RequestStatus, tx_hash: Option<&str>) -> RelayerRequest {
+        RelayerRequest {
+            id: Uuid::new_v4(),
+            from_address: "0x0000000000000000000000000000000000000001".to_string(),
+            to_address: "0x0000000000000000000000000000000000000002".to_string(),
+            amount: "0".to_string(),
+            gas_limit: 21000,
+            gas_price: "0x1".to_string(),
+            data: Some("0x".to_string()),
+            nonce: 0,
+            chain_id: 1,
+            transaction_hash: tx_hash.map(str::to_string),
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Default::default(),
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_subcommands_and_default_serve() {
+        let cli = Cli::try_parse_from(["relayx", "--http-port", "5000"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.config.http_port, 5000);
+
+        let cli = Cli::try_parse_from(["relayx", "list", "--status", "failed"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::List {
+                status: Some(RequestStatus::Failed),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["relayx", "list", "--status", "bogus"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "relayx",
+            "cancel",
+            "6f1c0000-0000-0000-0000-000000000000",
+            "--url",
+            "http://localhost:4937",
+            "--admin-token",
+            "secret",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cancel {
+                admin_token: Some(_),
+                target: Target { url: Some(_), .. },
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_and_cancel_transitions() {
+        let dir = tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+
+        let failed = request(RequestStatus::Failed, Some("0xabc"));
+        let unsent = request(RequestStatus::Failed, None);
+        let done = request(RequestStatus::Completed, Some("0xdef"));
+        for r in [&failed, &unsent, &done] {
+            storage.create_request(r.clone()).await.unwrap();
+        }
+
+        let resubmitted = resubmit_request(&storage, failed.id).await.unwrap();
+        assert_eq!(resubmitted.status, RequestStatus::Processing);
+        assert!(resubmitted.error_message.is_none());
+        assert!(resubmit_request(&storage, unsent.id).await.is_err());
+        assert!(resubmit_request(&storage, done.id).await.is_err());
+
+        let (cancelled, refunded) = cancel_request(&storage, failed.id, false).await.unwrap();
+        assert_eq!(cancelled.status, RequestStatus::Failed);
+        assert_eq!(
+            cancelled.error_message.as_deref(),
+            Some(CANCELLED_BY_OPERATOR)
+        );
+        assert_eq!(refunded, None);
+        assert!(cancel_request(&storage, done.id, false).await.is_err());
+
+        let failed_list = list_requests(&storage, Some(RequestStatus::Failed), None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(failed_list.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_refunds_gas_tank_only_when_the_call_cannot_run() {
+        let dir = tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+        let wallet = "0x00000000000000000000000000000000000000aa";
+        storage
+            .credit_gas_tank_deposit(&crate::types::GasTankDeposit {
+                chain_id: "1".to_string(),
+                wallet: wallet.to_string(),
+                transaction_hash: "0xd1".to_string(),
+                amount: "1000".to_string(),
+                credited_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let in_flight = request(RequestStatus::Processing, Some("0xabc"));
+        let withdrawn = request(RequestStatus::Processing, Some("0xdef"));
+        for (r, authorization) in [(&in_flight, "0x01"), (&withdrawn, "0x02")] {
+            storage.create_request(r.clone()).await.unwrap();
+            storage
+                .debit_gas_tank("1", wallet, 300, authorization, r.id)
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        // A broadcast transaction that was not replaced may still be mined
+        let (_, refunded) = cancel_request(&storage, in_flight.id, false).await.unwrap();
+        assert_eq!(refunded, None);
+        assert_eq!(
+            storage.get_gas_tank_balance("1", wallet).await.unwrap(),
+            400
+        );
+
+        let (_, refunded) = cancel_request(&storage, withdrawn.id, true).await.unwrap();
+        assert_eq!(refunded, Some(300));
+        assert_eq!(
+            storage.get_gas_tank_balance("1", wallet).await.unwrap(),
+            700
+        );
+        assert!(!storage
+            .is_gas_tank_authorization_spent("1", "0x02")
+            .await
+            .unwrap());
+        // The debit is returned once
+        assert_eq!(storage.refund_gas_tank(withdrawn.id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_by_target_uses_index() {
+        let dir = tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+
+        let target = "0x00000000000000000000000000000000000000AA";
+        let mut older = request(RequestStatus::Completed, Some("0x01"));
+        older.to_address = target.to_string();
+        older.created_at = Utc::now() - chrono::Duration::seconds(10);
+        let mut newer = request(RequestStatus::Failed, Some("0x02"));
+        newer.to_address = target.to_lowercase();
+        let elsewhere = request(RequestStatus::Completed, Some("0x03"));
+        for r in [&older, &newer, &elsewhere] {
+            storage.create_request(r.clone()).await.unwrap();
+        }
+
+        let ids = |requests: Vec<RelayerRequest>| requests.iter().map(|r| r.id).collect::<Vec<_>>();
+        let all = list_requests(&storage, None, Some(target), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(ids(all), vec![newer.id, older.id]);
+        let completed = list_requests(
+            &storage,
+            Some(RequestStatus::Completed),
+            Some(target),
+            None,
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids(completed), vec![older.id]);
+        let first = list_requests(&storage, None, Some(target), None, 1)
+            .await
+            .unwrap();
+        assert_eq!(ids(first), vec![newer.id]);
+    }
+}
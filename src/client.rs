@@ -0,0 +1,567 @@
+//! Typed async client for the RelayX JSON-RPC API.
+//!
+//! Wraps the wire types from [`crate::types`] so integrators can call a running relayer
+//! without hand-rolling request/response structs:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), relayx::client::ClientError> {
+//! use relayx::client::RelayxClient;
+//!
+//! let client = RelayxClient::new("http://127.0.0.1:4937");
+//! let status = client.get_status(&["6f1c…".to_string()]).await?;
+//! println!("{:?}", status.result);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::types::{
+    CancelRequestRequest, CancelRequestResponse, CreateRecurringJobRequest, EstimateFeeRequest,
+    EstimateFeeResponse, ExchangeRateRequest, ExchangeRateResponse, FeeDataRequest,
+    FeeDataResponse, GetBundleStatusRequest, GetBundleStatusResponse, GetCapabilitiesRequest,
+    GetCapabilitiesResponse, GetFeeHistoryRequest, GetFeeHistoryResponse, GetGasTankBalanceRequest,
+    GetGasTankBalanceResponse, GetGasTankDepositsRequest, GetGasTankDepositsResponse,
+    GetQueueStatsRequest, GetQueueStatsResponse, GetReceiptRequest, GetReceiptResponse,
+    GetSponsorshipAllowanceRequest, GetSponsorshipAllowanceResponse, GetStatusRequest,
+    GetStatusResponse, GetSupportedChainsResponse, GetTransactionsByWalletRequest,
+    GetTransactionsByWalletResponse, GetVersionResponse, HealthResponse, ListRecurringJobsRequest,
+    ListRecurringJobsResponse, ListRequestsRequest, ListRequestsResponse, LivenessResponse,
+    PauseRecurringJobRequest, QuoteRequest, QuoteResponse, ReadinessResponse, RecurringJob,
+    ResendTransactionOptions, ResendTransactionRequest, ResendTransactionResponse,
+    SendCallsRequest, SendCallsResponse, SendRawTransactionRequest,
+    SendTransactionMultichainRequest, SendTransactionMultichainResponse, SendTransactionRequest,
+    SendTransactionResponse, SimulateRequest, SimulateResponse, StatusKind, StatusResult,
+    SubmitGasTankDepositRequest, SubmitGasTankDepositResponse,
+};
+
+/// Errors returned by [`RelayxClient`]
+#[derive(Debug)]
+pub enum ClientError {
+    /// The HTTP request could not be sent or the body could not be read
+    Transport(reqwest::Error),
+    /// The relayer answered with a JSON-RPC error object
+    Rpc {
+        code: i64,
+        message: String,
+        data: Option<Value>,
+    },
+    /// The response did not match the expected shape
+    Decode(String),
+    /// `wait_for_completion` gave up before the request reached a final state
+    Timeout { id: String, last_status: u16 },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(e) => write!(f, "transport error: {}", e),
+            ClientError::Rpc { code, message, .. } => {
+                write!(f, "relayer error {}: {}", code, message)
+            }
+            ClientError::Decode(msg) => write!(f, "invalid response: {}", msg),
+            ClientError::Timeout { id, last_status } => write!(
+                f,
+                "timed out waiting for request {} (last status {})",
+                id, last_status
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Transport(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Transport(e)
+    }
+}
+
+/// Async client for a RelayX instance
+#[derive(Debug)]
+pub struct RelayxClient {
+    http: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+    next_id: AtomicU64,
+}
+
+impl RelayxClient {
+    /// Create a client for the relayer listening at `url` (e.g. `http://127.0.0.1:4937`)
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_http_client(url, reqwest::Client::new())
+    }
+
+    /// Create a client that reuses a preconfigured `reqwest::Client` (timeouts, proxies, TLS)
+    pub fn with_http_client(url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            http,
+            url: url.into(),
+            api_key: None,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Send `api_key` with every call that takes one, for a relayer serving several tenants.
+    /// Requests that set their own `api_key` keep it.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Endpoint this client talks to
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Submit a single-chain transaction (`relayer_sendTransaction`)
+    pub async fn send_transaction(
+        &self,
+        request: &SendTransactionRequest,
+    ) -> Result<SendTransactionResponse, ClientError> {
+        self.call("relayer_sendTransaction", json!([self.keyed(request)]))
+            .await
+    }
+
+    /// Submit a cross-chain bundle (`relayer_sendTransactionMultichain`)
+    pub async fn send_transaction_multichain(
+        &self,
+        request: &SendTransactionMultichainRequest,
+    ) -> Result<SendTransactionMultichainResponse, ClientError> {
+        self.call(
+            "relayer_sendTransactionMultichain",
+            json!([self.keyed(request)]),
+        )
+        .await
+    }
+
+    /// Relay a batch of calls through Multicall3 (`relayer_sendCalls`)
+    pub async fn send_calls(
+        &self,
+        request: &SendCallsRequest,
+    ) -> Result<SendCallsResponse, ClientError> {
+        self.call("relayer_sendCalls", json!([self.keyed(request)]))
+            .await
+    }
+
+    /// Broadcast and track a transaction signed by its sender (`relayer_sendRawTransaction`)
+    pub async fn send_raw_transaction(
+        &self,
+        request: &SendRawTransactionRequest,
+    ) -> Result<SendTransactionResponse, ClientError> {
+        self.call("relayer_sendRawTransaction", json!([self.keyed(request)]))
+            .await
+    }
+
+    /// Fetch the status of one or more request ids (`relayer_getStatus`)
+    pub async fn get_status(&self, ids: &[String]) -> Result<GetStatusResponse, ClientError> {
+        let request = GetStatusRequest {
+            ids: ids.to_vec(),
+            api_key: None,
+        };
+        self.call("relayer_getStatus", self.keyed(&request)).await
+    }
+
+    /// Fetch the aggregate status of a multichain bundle (`relayer_getBundleStatus`)
+    pub async fn get_bundle_status(
+        &self,
+        bundle_id: &str,
+    ) -> Result<GetBundleStatusResponse, ClientError> {
+        let request = GetBundleStatusRequest {
+            bundle_id: bundle_id.to_string(),
+            api_key: None,
+        };
+        self.call("relayer_getBundleStatus", self.keyed(&request))
+            .await
+    }
+
+    /// Token/gas exchange rate for a chain (`relayer_getExchangeRate`)
+    pub async fn get_exchange_rate(
+        &self,
+        request: &ExchangeRateRequest,
+    ) -> Result<ExchangeRateResponse, ClientError> {
+        self.call("relayer_getExchangeRate", json!([request])).await
+    }
+
+    /// Fee quote for a transaction (`relayer_getQuote`)
+    pub async fn get_quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, ClientError> {
+        self.call("relayer_getQuote", json!([request])).await
+    }
+
+    /// Full fee breakdown for a transaction paid in a given token (`relayer_estimateFee`)
+    pub async fn estimate_fee(
+        &self,
+        request: &EstimateFeeRequest,
+    ) -> Result<EstimateFeeResponse, ClientError> {
+        self.call("relayer_estimateFee", json!([request])).await
+    }
+
+    /// Pre-flight simulation of a relay call with its fee quote (`relayer_simulate`)
+    pub async fn simulate(
+        &self,
+        request: &SimulateRequest,
+    ) -> Result<SimulateResponse, ClientError> {
+        self.call("relayer_simulate", json!([request])).await
+    }
+
+    /// Relay history of a wallet, one page at a time (`relayer_getTransactionsByWallet`)
+    pub async fn get_transactions_by_wallet(
+        &self,
+        request: &GetTransactionsByWalletRequest,
+    ) -> Result<GetTransactionsByWalletResponse, ClientError> {
+        self.call(
+            "relayer_getTransactionsByWallet",
+            json!([self.keyed(request)]),
+        )
+        .await
+    }
+
+    /// Rebroadcast an in-flight request now with a bumped gas price
+    /// (`relayer_resendTransaction`)
+    pub async fn resend_transaction(
+        &self,
+        id: &str,
+        options: Option<ResendTransactionOptions>,
+    ) -> Result<ResendTransactionResponse, ClientError> {
+        let options = match options {
+            None if self.api_key.is_some() => Some(ResendTransactionOptions::default()),
+            options => options,
+        };
+        let request = ResendTransactionRequest(id.to_string(), options);
+        let mut params = json!(request);
+        if let Some(options) = params.get_mut(1).filter(|options| options.is_object()) {
+            *options = self.keyed(&*options);
+        }
+        self.call("relayer_resendTransaction", params).await
+    }
+
+    /// Stored requests matching a status, target address or tenant, newest first
+    /// (`admin_listRequests`)
+    pub async fn list_requests(
+        &self,
+        request: &ListRequestsRequest,
+    ) -> Result<ListRequestsResponse, ClientError> {
+        self.call("admin_listRequests", json!([request])).await
+    }
+
+    /// Stop tracking a request, replacing its pending transaction, and return its gas tank
+    /// debit (`admin_cancelRequest`)
+    pub async fn cancel_request(
+        &self,
+        request: &CancelRequestRequest,
+    ) -> Result<CancelRequestResponse, ClientError> {
+        self.call("admin_cancelRequest", json!([request])).await
+    }
+
+    /// Every transaction broadcast for a request, by request id or any of its transaction
+    /// hashes, with the receipt of the one that mined (`relayer_getReceipt`)
+    pub async fn get_receipt(&self, id: &str) -> Result<GetReceiptResponse, ClientError> {
+        let request = GetReceiptRequest {
+            id: id.to_string(),
+            api_key: None,
+        };
+        self.call("relayer_getReceipt", self.keyed(&request)).await
+    }
+
+    /// Credit a mined deposit to the sender's gas tank (`relayer_submitGasTankDeposit`)
+    pub async fn submit_gas_tank_deposit(
+        &self,
+        chain_id: &str,
+        transaction_hash: &str,
+    ) -> Result<SubmitGasTankDepositResponse, ClientError> {
+        let request = SubmitGasTankDepositRequest {
+            chain_id: chain_id.to_string(),
+            transaction_hash: transaction_hash.to_string(),
+        };
+        self.call("relayer_submitGasTankDeposit", json!([request]))
+            .await
+    }
+
+    /// Prepaid gas balance of a wallet on a chain (`relayer_getGasTankBalance`)
+    pub async fn get_gas_tank_balance(
+        &self,
+        wallet: &str,
+        chain_id: &str,
+    ) -> Result<GetGasTankBalanceResponse, ClientError> {
+        let request = GetGasTankBalanceRequest {
+            wallet: wallet.to_string(),
+            chain_id: chain_id.to_string(),
+        };
+        self.call("relayer_getGasTankBalance", json!([request]))
+            .await
+    }
+
+    /// Deposits credited to a wallet's gas tank, newest first (`relayer_getGasTankDeposits`)
+    pub async fn get_gas_tank_deposits(
+        &self,
+        request: &GetGasTankDepositsRequest,
+    ) -> Result<GetGasTankDepositsResponse, ClientError> {
+        self.call("relayer_getGasTankDeposits", json!([request]))
+            .await
+    }
+
+    /// Sponsored gas allowance left to the project owning `api_key` this month
+    /// (`relayer_getSponsorshipAllowance`)
+    pub async fn get_sponsorship_allowance(
+        &self,
+        api_key: &str,
+    ) -> Result<GetSponsorshipAllowanceResponse, ClientError> {
+        let request = GetSponsorshipAllowanceRequest {
+            api_key: api_key.to_string(),
+        };
+        self.call("relayer_getSponsorshipAllowance", json!([request]))
+            .await
+    }
+
+    /// Store a request template the relayer relays on every interval
+    /// (`relayer_createRecurringJob`)
+    pub async fn create_recurring_job(
+        &self,
+        request: &CreateRecurringJobRequest,
+    ) -> Result<RecurringJob, ClientError> {
+        let mut request = json!(request);
+        if let Some(template) = request.get_mut("request") {
+            *template = self.keyed(&*template);
+        }
+        self.call("relayer_createRecurringJob", json!([request]))
+            .await
+    }
+
+    /// Pause a recurring job, or resume it with `paused = false` (`relayer_pauseRecurringJob`)
+    pub async fn pause_recurring_job(
+        &self,
+        id: &str,
+        paused: bool,
+    ) -> Result<RecurringJob, ClientError> {
+        let request = PauseRecurringJobRequest {
+            id: id.to_string(),
+            paused: Some(paused),
+            api_key: None,
+        };
+        self.call("relayer_pauseRecurringJob", json!([self.keyed(&request)]))
+            .await
+    }
+
+    /// Recurring jobs relaying to `wallet` (`relayer_listRecurringJobs`)
+    pub async fn list_recurring_jobs(
+        &self,
+        wallet: &str,
+    ) -> Result<ListRecurringJobsResponse, ClientError> {
+        let request = ListRecurringJobsRequest {
+            wallet: wallet.to_string(),
+            api_key: None,
+        };
+        self.call("relayer_listRecurringJobs", json!([self.keyed(&request)]))
+            .await
+    }
+
+    /// Gas prices and token rates sampled by the relayer (`relayer_getFeeHistory`)
+    pub async fn get_fee_history(
+        &self,
+        request: &GetFeeHistoryRequest,
+    ) -> Result<GetFeeHistoryResponse, ClientError> {
+        self.call("relayer_getFeeHistory", json!([request])).await
+    }
+
+    /// Per-chain queue depth and throughput over a recent window (`relayer_getQueueStats`)
+    pub async fn get_queue_stats(
+        &self,
+        request: &GetQueueStatsRequest,
+    ) -> Result<GetQueueStatsResponse, ClientError> {
+        self.call("relayer_getQueueStats", json!([self.keyed(request)]))
+            .await
+    }
+
+    /// Current fee data for a chain and token (`relayer_getFeeData`)
+    pub async fn get_fee_data(
+        &self,
+        request: &FeeDataRequest,
+    ) -> Result<FeeDataResponse, ClientError> {
+        self.call("relayer_getFeeData", json!([request])).await
+    }
+
+    /// Supported payment options, optionally restricted to one chain (`relayer_getCapabilities`)
+    pub async fn get_capabilities(
+        &self,
+        chain_id: Option<&str>,
+    ) -> Result<GetCapabilitiesResponse, ClientError> {
+        let params = match chain_id {
+            Some(chain_id) => json!([GetCapabilitiesRequest {
+                chain_id: Some(chain_id.to_string()),
+            }]),
+            None => json!([]),
+        };
+        self.call("relayer_getCapabilities", params).await
+    }
+
+    /// Chains served by the relayer (`relayer_getSupportedChains`)
+    pub async fn get_supported_chains(&self) -> Result<GetSupportedChainsResponse, ClientError> {
+        self.call("relayer_getSupportedChains", json!([])).await
+    }
+
+    /// Build information, spec versions and supported features (`relayer_getVersion`)
+    pub async fn get_version(&self) -> Result<GetVersionResponse, ClientError> {
+        self.call("relayer_getVersion", json!([])).await
+    }
+
+    /// Service health (`health_check`)
+    pub async fn health_check(&self) -> Result<HealthResponse, ClientError> {
+        self.call("health_check", json!([])).await
+    }
+
+    /// Whether the relayer process is up (`health_live`)
+    pub async fn health_live(&self) -> Result<LivenessResponse, ClientError> {
+        self.call("health_live", json!([])).await
+    }
+
+    /// Whether the relayer can accept relays (`health_ready`); fails with
+    /// [`NOT_READY`](crate::errors::NOT_READY) naming the failing checks otherwise
+    pub async fn health_ready(&self) -> Result<ReadinessResponse, ClientError> {
+        self.call("health_ready", json!([])).await
+    }
+
+    /// Poll `relayer_getStatus` until the request is no longer pending and return its final
+    /// status entry. Confirmed, failed and unknown ids all end the wait; callers inspect
+    /// `statusName` (or `status`) to tell them apart.
+    pub async fn wait_for_completion(
+        &self,
+        id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<StatusResult, ClientError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let response = self.get_status(&[id.to_string()]).await?;
+            let status = response.result.into_iter().next().ok_or_else(|| {
+                ClientError::Decode(format!("no status entry returned for {}", id))
+            })?;
+
+            // Relayers that predate `statusName` always report pending as 201
+            let pending = match status.status_name {
+                Some(kind) => kind == StatusKind::Pending,
+                None => status.status == 201,
+            };
+            if !pending {
+                return Ok(status);
+            }
+            if Instant::now() + poll_interval > deadline {
+                return Err(ClientError::Timeout {
+                    id: id.to_string(),
+                    last_status: status.status,
+                });
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// `params` as JSON with the client's API key added when they carry none
+    fn keyed(&self, params: &impl Serialize) -> Value {
+        let mut value = json!(params);
+        if let (Some(api_key), Some(fields)) = (&self.api_key, value.as_object_mut()) {
+            fields
+                .entry("apiKey")
+                .or_insert_with(|| Value::String(api_key.clone()));
+        }
+        value
+    }
+
+    /// Issue a raw JSON-RPC call and decode its `result` into `T`
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, ClientError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let body = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": id,
+        });
+
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        decode_response(response)
+    }
+}
+
+/// Split a JSON-RPC envelope into its typed `result` or a `ClientError::Rpc`
+fn decode_response<T: DeserializeOwned>(mut response: Value) -> Result<T, ClientError> {
+    if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+        return Err(ClientError::Rpc {
+            code: error.get("code").and_then(Value::as_i64).unwrap_or(0),
+            message: error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            data: error.get("data").cloned(),
+        });
+    }
+
+    let result = response
+        .get_mut("result")
+        .map(Value::take)
+        .ok_or_else(|| ClientError::Decode("missing result".to_string()))?;
+    serde_json::from_value(result).map_err(|e| ClientError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_response_maps_rpc_error() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -4202, "message": "Unsupported Payment Token" },
+            "id": 1
+        });
+        match decode_response::<GetStatusResponse>(response) {
+            Err(ClientError::Rpc { code, message, .. }) => {
+                assert_eq!(code, -4202);
+                assert_eq!(message, "Unsupported Payment Token");
+            }
+            other => panic!("expected rpc error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_response_parses_result() {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "result": { "result": [{
+                "version": "2.0.0",
+                "id": "abc",
+                "status": 404,
+                "receipts": [],
+                "resubmissions": [],
+                "offchainFailure": [],
+                "onchainFailure": []
+            }]},
+            "id": 1
+        });
+        let status: GetStatusResponse = decode_response(response).unwrap();
+        assert_eq!(status.result.len(), 1);
+        assert_eq!(status.result[0].status, 404);
+    }
+}
@@ -1,8 +1,42 @@
-use std::{fs, path::PathBuf, sync::OnceLock};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+    sync::{OnceLock, RwLock},
+};
 
+use alloy::signers::local::PrivateKeySigner;
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    callback::HostPolicy,
+    chain::ChainBackend,
+    encryption::{read_owner_only, SecretSlot, StorageKeyArgs},
+    registry::{self, RegistryChain},
+    signer::RelayerSigner,
+    types::{
+        ArchivePolicy, ChainRegistration, DexQuoter, DuplicatePolicy, FeeBounds, FeePayment,
+        GasOracle, GasPriceStrategy, LocalEvmSettings, RebalancePolicy, ReceiptOutcome,
+        RecordingMode, RemoteSignerSettings, ResubmissionPolicy, RpcRecording, SandboxFailure,
+        SandboxSettings, SentrySettings, SimulationFailureMode, Speed, SpeedLane,
+        SponsorshipProject, StatusCodes, StatusKind, StorageTuning, Tenant, TokenInfo, TxType,
+    },
+};
+
+fn local_signer(key: &str) -> Result<RelayerSigner, String> {
+    key.parse::<PrivateKeySigner>()
+        .map(RelayerSigner::from)
+        // The parse error is not passed on so that no part of the key ends up in a log
+        .map_err(|_| "private key does not parse as a secp256k1 key".to_string())
+}
+
+/// Chains registered at runtime through the admin API, layered over the JSON config
+fn runtime_chains() -> &'static RwLock<BTreeMap<String, ChainRegistration>> {
+    static RUNTIME_CHAINS: OnceLock<RwLock<BTreeMap<String, ChainRegistration>>> = OnceLock::new();
+    RUNTIME_CHAINS.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
 #[derive(Parser, Debug, Clone, Serialize, Deserialize)]
 #[command(name = "relayx")]
 #[command(about = "A modular relayer service with JSON-RPC endpoints")]
@@ -60,6 +94,15 @@ pub struct Config {
     #[arg(long = "relayer-private-key", env = "RELAYX_PRIVATE_KEY")]
     pub relayer_private_key: Option<String>,
 
+    /// File holding the relayer private key, readable by its owner only (mode 0600). Keeps
+    /// the key out of process listings and environment dumps
+    #[arg(
+        long = "relayer-private-key-file",
+        env = "RELAYX_PRIVATE_KEY_FILE",
+        conflicts_with = "relayer_private_key"
+    )]
+    pub relayer_private_key_file: Option<PathBuf>,
+
     /// Disable transaction simulation (use default gas limit instead)
     #[arg(long = "disable-simulation", env = "RELAYX_DISABLE_SIMULATION")]
     pub disable_simulation: bool,
@@ -67,6 +110,45 @@ pub struct Config {
     /// Sentry DSN for error tracking (optional)
     #[arg(long = "sentry-dsn", env = "SENTRY_DSN")]
     pub sentry_dsn: Option<String>,
+
+    /// Shared secret required by admin_* methods; admin API is disabled when unset
+    #[arg(long = "admin-token", env = "RELAYX_ADMIN_TOKEN")]
+    pub admin_token: Option<String>,
+
+    /// Key used to sign callback deliveries (HMAC-SHA256); callbacks are refused when unset
+    #[arg(long = "callback-secret", env = "RELAYX_CALLBACK_SECRET")]
+    pub callback_secret: Option<String>,
+
+    /// Let callbacks reach loopback, private and link-local hosts, for receivers on the
+    /// relayer's own network
+    #[arg(
+        long = "callback-allow-private-hosts",
+        env = "RELAYX_CALLBACK_ALLOW_PRIVATE_HOSTS"
+    )]
+    pub callback_allow_private_hosts: bool,
+
+    /// How long a fetched gas price is reused, in milliseconds (0 disables the cache)
+    #[arg(
+        long = "gas-price-ttl-ms",
+        env = "RELAYX_GAS_PRICE_TTL_MS",
+        default_value_t = 3000
+    )]
+    pub gas_price_ttl_ms: u64,
+
+    /// Key encrypting stored request data at rest
+    #[command(flatten)]
+    #[serde(skip)]
+    pub encryption: StorageKeyArgs,
+
+    /// Node access used for every chain interaction
+    #[arg(skip)]
+    #[serde(skip)]
+    pub chain: ChainBackend,
+
+    /// Key read from `relayer_private_key_file` by [`Config::load_relayer_key_file`]
+    #[arg(skip)]
+    #[serde(skip)]
+    pub relayer_key_from_file: SecretSlot,
 }
 
 impl Config {
@@ -112,6 +194,9 @@ impl Config {
     /// 1) { "1": "https://mainnet.example", "137": "https://polygon.example" }
     /// 2) { "rpcs": { "1": "https://mainnet.example" } }
     pub fn rpc_url_for_chain(&self, chain_id: &str) -> Option<String> {
+        if let Some(chain) = self.runtime_chain(chain_id) {
+            return Some(chain.rpc_url);
+        }
         let root = self.get_json_config()?;
         // Try { "rpcs": { chainId: url } }
         if let Some(url) = root
@@ -125,7 +210,87 @@ impl Config {
         if let Some(url) = root.get(chain_id).and_then(|v| v.as_str()) {
             return Some(url.to_string());
         }
-        None
+        // Fall back to the registry's public endpoint for a bootstrapped chain
+        self.bootstrapped_chain(chain_id)
+            .and_then(|chain| chain.public_rpc_url().map(str::to_string))
+    }
+
+    /// Returns the WebSocket endpoint a chain is watched through: `wsUrls[chainId]`, or the
+    /// chain's RPC URL when that is itself `ws://` or `wss://`.
+    /// Expects JSON structure: { "wsUrls": { "1": "wss://mainnet.example/ws" } }
+    pub fn ws_url_for_chain(&self, chain_id: &str) -> Option<String> {
+        if let Some(url) = self
+            .get_json_config()
+            .and_then(|root| root.get("wsUrls"))
+            .and_then(|m| m.get(chain_id))
+            .and_then(|v| v.as_str())
+        {
+            return Some(url.to_string());
+        }
+        self.rpc_url_for_chain(chain_id)
+            .filter(|url| url.starts_with("ws://") || url.starts_with("wss://"))
+    }
+
+    /// Endpoint for the monitor's frequent receipt lookups: the chain's WebSocket endpoint
+    /// when it has one, so one-shot calls keep using the RPC URL
+    pub fn monitor_url_for_chain(&self, chain_id: &str) -> Option<String> {
+        self.ws_url_for_chain(chain_id)
+            .or_else(|| self.rpc_url_for_chain(chain_id))
+    }
+
+    /// Returns the chain registry's entry for a chain, when `chainRegistry` is configured.
+    /// Expects JSON structure: { "chainRegistry": { "path": "/etc/relayx/chains.json",
+    /// "chains": [1, 137] } }; without `path` the embedded registry is used.
+    pub fn registry_chain(&self, chain_id: &str) -> Option<RegistryChain> {
+        static REGISTRY: OnceLock<Vec<RegistryChain>> = OnceLock::new();
+        let entry = self.get_json_config()?.get("chainRegistry")?;
+        let chains = REGISTRY.get_or_init(|| match entry.get("path").and_then(|v| v.as_str()) {
+            Some(path) => registry::load(path.as_ref()).unwrap_or_else(|e| {
+                tracing::error!("Chain registry not loaded: {:#}", e);
+                Vec::new()
+            }),
+            None => registry::embedded(),
+        });
+        let chain_id: u64 = chain_id.parse().ok()?;
+        chains
+            .iter()
+            .find(|chain| chain.chain_id == chain_id)
+            .cloned()
+    }
+
+    /// Returns the chain IDs listed under `chainRegistry.chains`, served with the registry's
+    /// public RPC endpoint unless config.json gives their RPC URL
+    pub fn bootstrapped_chain_ids(&self) -> Vec<u64> {
+        self.get_json_config()
+            .and_then(|v| v.get("chainRegistry"))
+            .and_then(|v| v.get("chains"))
+            .and_then(|v| v.as_array())
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_u64().or_else(|| id.as_str()?.parse().ok()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Registry entry of a chain listed under `chainRegistry.chains`
+    fn bootstrapped_chain(&self, chain_id: &str) -> Option<RegistryChain> {
+        let id: u64 = chain_id.parse().ok()?;
+        if !self.bootstrapped_chain_ids().contains(&id) {
+            return None;
+        }
+        self.registry_chain(chain_id)
+    }
+
+    /// Returns the fee collector configured for one chain, if any; callers fall back to the
+    /// global `feeCollector`. Expects JSON structure: { "feeCollectors": { "137": "0x..." } }
+    pub fn chain_fee_collector(&self, chain_id: &str) -> Option<String> {
+        self.get_json_config()?
+            .get("feeCollectors")
+            .and_then(|m| m.get(chain_id))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
     }
 
     /// Returns the configured fee collector address if present in the JSON file.
@@ -141,19 +306,246 @@ impl Config {
     /// Returns Chainlink native token/USD aggregator address for a chain
     /// Expects JSON structure: { "chainlink": { "nativeUsd": { "1": "0x..." } } }
     pub fn chainlink_native_usd(&self, chain_id: &str) -> Option<String> {
+        if let Some(feed) = self
+            .runtime_chain(chain_id)
+            .and_then(|chain| chain.native_usd_feed)
+        {
+            return Some(feed);
+        }
         let root = self.get_json_config()?;
         root.get("chainlink")
             .and_then(|c| c.get("nativeUsd"))
             .and_then(|m| m.get(chain_id))
+            .or_else(|| {
+                root.get("nativeTokens")
+                    .and_then(|m| m.get(chain_id))
+                    .and_then(|t| t.get("usdFeed"))
+            })
             .and_then(|v| v.as_str())
             .map(|s| s.to_string())
     }
 
+    /// Returns the DEX quoter that prices fee tokens without a Chainlink feed, or `None` when
+    /// the chain accepts only oracle-priced tokens.
+    /// Expects JSON structure: { "dexQuoter": { "1": { "quoter": "0x...", "wrappedNative":
+    /// "0x...", "feeTier": 3000, "probeAmount": 100000000000000000, "maxPriceImpactBps": 100 } } }
+    pub fn dex_quoter(&self, chain_id: &str) -> Option<DexQuoter> {
+        if let Some(quoter) = self
+            .runtime_chain(chain_id)
+            .and_then(|chain| chain.dex_quoter)
+        {
+            return Some(quoter);
+        }
+        let entry = self.get_json_config()?.get("dexQuoter")?.get(chain_id)?;
+        match serde_json::from_value(entry.clone()) {
+            Ok(quoter) => Some(quoter),
+            Err(e) => {
+                tracing::warn!("Ignoring dexQuoter entry for chain {}: {}", chain_id, e);
+                None
+            }
+        }
+    }
+
+    /// Returns the transaction type forced for a chain, or `None` to detect it from the
+    /// chain's latest block.
+    /// Expects JSON structure: { "txType": { "56": "legacy", "1": "eip1559" } }
+    pub fn tx_type_override(&self, chain_id: &str) -> Option<TxType> {
+        if let Some(model) = self.runtime_chain(chain_id).and_then(|chain| chain.tx_type) {
+            return Some(model);
+        }
+        let entry = self.get_json_config()?.get("txType")?.get(chain_id)?;
+        match serde_json::from_value(entry.clone()) {
+            Ok(model) => Some(model),
+            Err(e) => {
+                tracing::warn!("Ignoring txType entry for chain {}: {}", chain_id, e);
+                None
+            }
+        }
+    }
+
+    /// Returns the call ERC20 quotes on a chain ask the wallet to pay the fee with; `transfer`
+    /// unless configured.
+    /// Expects JSON structure: { "feePayment": { "137": "approve" } }
+    pub fn fee_payment(&self, chain_id: &str) -> FeePayment {
+        let Some(entry) = self
+            .get_json_config()
+            .and_then(|v| v.get("feePayment"))
+            .and_then(|m| m.get(chain_id))
+        else {
+            return FeePayment::default();
+        };
+        serde_json::from_value(entry.clone()).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring feePayment entry for chain {}: {}", chain_id, e);
+            FeePayment::default()
+        })
+    }
+
+    /// Returns how far, in basis points, a payment may fall short of the fee re-priced when it
+    /// is verified, so oracle moves since the quote do not fail it; 0 (exact) unless configured
+    /// and at most 10000.
+    /// Expects JSON structure: { "rateSlippageBps": { "default": 200, "137": 300 } }
+    pub fn rate_slippage_bps(&self, chain_id: u64) -> u64 {
+        let Some(slippage) = self
+            .get_json_config()
+            .and_then(|v| v.get("rateSlippageBps"))
+        else {
+            return 0;
+        };
+        slippage
+            .get(chain_id.to_string())
+            .or_else(|| slippage.get("default"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            .min(10_000)
+    }
+
+    /// Returns how far below its charged price, in percent of it, a mined relay's gas cost must
+    /// come in for the difference to be recorded as an overpayment; 20 unless configured.
+    /// Expects JSON structure: { "refunds": { "thresholdPercent": 20 } }
+    pub fn refund_threshold_percent(&self) -> u64 {
+        self.get_json_config()
+            .and_then(|v| v.get("refunds")?.get("thresholdPercent")?.as_u64())
+            .unwrap_or(20)
+            .min(100)
+    }
+
+    /// Returns the endpoint private-mempool raw transactions are broadcast through on a chain,
+    /// if it has one.
+    /// Expects JSON structure: { "privateRpcs": { "1": "https://rpc.flashbots.net/fast" } }
+    pub fn private_rpc_url(&self, chain_id: &str) -> Option<String> {
+        if let Some(url) = self
+            .runtime_chain(chain_id)
+            .and_then(|chain| chain.private_rpc_url)
+        {
+            return Some(url);
+        }
+        self.get_json_config()?
+            .get("privateRpcs")?
+            .get(chain_id)?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    /// Returns the event ABIs receipt logs emitted by `contract` on a chain are decoded with.
+    /// Expects JSON structure:
+    /// { "eventAbis": { "1": { "0xA0b8...eB48": [{ "type": "event", "name": "Transfer", ... }] } } };
+    /// non-event items are ignored, so a contract's full ABI may be given.
+    pub fn event_abis(&self, chain_id: &str, contract: &str) -> Vec<alloy::json_abi::Event> {
+        if let Some(chain) = self.runtime_chain(chain_id) {
+            if let Some(events) = chain.event_abis.get(&contract.to_lowercase()) {
+                return events.clone();
+            }
+        }
+        let Some(contracts) = self
+            .get_json_config()
+            .and_then(|config| config.get("eventAbis")?.get(chain_id)?.as_object())
+        else {
+            return Vec::new();
+        };
+        let Some((_, items)) = contracts
+            .iter()
+            .find(|(address, _)| address.eq_ignore_ascii_case(contract))
+        else {
+            return Vec::new();
+        };
+        match serde_json::from_value::<alloy::json_abi::JsonAbi>(items.clone()) {
+            Ok(abi) => abi.events().cloned().collect(),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring eventAbis entry for {} on chain {}: {}",
+                    contract,
+                    chain_id,
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the Multicall3 deployment `relayer_sendCalls` batches through on a chain,
+    /// defaulting to the canonical address.
+    /// Expects JSON structure: { "multicall3": { "324": "0xF9cda624FBC7e059355ce98a31693d299FACd963" } }
+    pub fn multicall3_address(&self, chain_id: &str) -> String {
+        self.get_json_config()
+            .and_then(|config| {
+                config
+                    .get("multicall3")?
+                    .get(chain_id)?
+                    .as_str()
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| crate::multicall::MULTICALL3_ADDRESS.to_string())
+    }
+
+    /// Whether fees on a chain can be paid in `token`: any oracle-priced token, or any token
+    /// at all when the chain has a DEX quoter (liquidity is checked when pricing)
+    pub fn accepts_payment_token(&self, chain_id: &str, token: &str) -> bool {
+        self.get_supported_tokens()
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(token))
+            || self.dex_quoter(chain_id).is_some()
+    }
+
+    /// Returns metadata for the chain's native gas token (always at the zero address).
+    /// Expects JSON structure:
+    /// { "nativeTokens": { "137": { "symbol": "POL", "name": "Polygon", "decimals": 18,
+    /// "usdFeed": "0x..." } } }. Falls back to the chain registry, then to well-known gas
+    /// tokens, then to ETH.
+    pub fn native_token_info(&self, chain_id: &str) -> TokenInfo {
+        let (symbol, name, decimals) = match chain_id {
+            "137" | "80002" => ("POL", "Polygon", 18),
+            "100" | "10200" => ("xDAI", "xDAI", 18),
+            "56" | "97" => ("BNB", "BNB", 18),
+            "43114" | "43113" => ("AVAX", "Avalanche", 18),
+            "250" => ("FTM", "Fantom", 18),
+            "42220" => ("CELO", "Celo", 18),
+            _ => ("ETH", "Ethereum", 18),
+        };
+        let mut info = TokenInfo {
+            decimals,
+            address: "0x0000000000000000000000000000000000000000".to_string(),
+            symbol: Some(symbol.to_string()),
+            name: Some(name.to_string()),
+        };
+        if let Some(chain) = self.registry_chain(chain_id) {
+            info.symbol = Some(chain.native_currency.symbol);
+            info.name = Some(chain.native_currency.name);
+            info.decimals = chain.native_currency.decimals;
+        }
+
+        if let Some(entry) = self
+            .get_json_config()
+            .and_then(|v| v.get("nativeTokens"))
+            .and_then(|m| m.get(chain_id))
+        {
+            if let Some(symbol) = entry.get("symbol").and_then(|v| v.as_str()) {
+                info.symbol = Some(symbol.to_string());
+            }
+            if let Some(name) = entry.get("name").and_then(|v| v.as_str()) {
+                info.name = Some(name.to_string());
+            }
+            if let Some(decimals) = entry
+                .get("decimals")
+                .and_then(|v| v.as_u64())
+                .and_then(|d| u8::try_from(d).ok())
+            {
+                info.decimals = decimals;
+            }
+        }
+        info
+    }
+
     /// Returns Chainlink token/USD aggregator address for a given chain and token address
     /// Expects JSON structure: { "chainlink": { "tokenUsd": { "1": { "0xToken": "0xFeed" } } } }
     pub fn chainlink_token_usd(&self, chain_id: &str, token_address: &str) -> Option<String> {
-        let root = self.get_json_config()?;
         let token_lc = token_address.to_lowercase();
+        if let Some(feed) = self
+            .runtime_chain(chain_id)
+            .and_then(|chain| chain.tokens.get(&token_lc).cloned())
+        {
+            return Some(feed);
+        }
+        let root = self.get_json_config()?;
         root.get("chainlink")
             .and_then(|c| c.get("tokenUsd"))
             .and_then(|chains| chains.get(chain_id))
@@ -212,7 +604,22 @@ impl Config {
             .map(|s| s.to_string())
     }
 
-    /// Returns the relayer private key from CLI/env/config, if provided.
+    /// Read the key file given by `--relayer-private-key-file` into memory. Called once at
+    /// startup; fails when the file is missing or accessible by group or others.
+    pub fn load_relayer_key_file(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.relayer_private_key_file {
+            self.relayer_key_from_file
+                .set(read_owner_only(path, "relayer key file")?);
+        }
+        Ok(())
+    }
+
+    /// Zero the key read from `--relayer-private-key-file`; relays can no longer be signed
+    pub fn wipe_relayer_key(&self) {
+        self.relayer_key_from_file.wipe();
+    }
+
+    /// Returns the relayer private key from CLI/key file/env/config, if provided.
     pub fn get_relayer_private_key(&self) -> Option<String> {
         if let Some(cli_key) = self
             .relayer_private_key
@@ -223,6 +630,11 @@ impl Config {
             return Some(cli_key);
         }
 
+        // A configured key file is authoritative: no fallback once it has been wiped
+        if self.relayer_private_key_file.is_some() {
+            return self.relayer_key_from_file.get();
+        }
+
         if let Ok(env_key) = std::env::var("RELAYX_PRIVATE_KEY") {
             if !env_key.is_empty() {
                 return Some(env_key);
@@ -239,15 +651,21 @@ impl Config {
     /// Returns all supported ERC20 token addresses from the chainlink configuration.
     /// This extracts tokens from the chainlink.tokenUsd configuration across all chains.
     pub fn get_supported_tokens(&self) -> Vec<String> {
-        let root = match self.get_json_config() {
-            Some(config) => config,
-            None => return Vec::new(),
-        };
-
-        let mut tokens = Vec::new();
+        let mut tokens: Vec<String> = runtime_chains()
+            .read()
+            .map(|chains| {
+                chains
+                    .values()
+                    .flat_map(|chain| chain.tokens.keys().cloned())
+                    .collect()
+            })
+            .unwrap_or_default();
 
         // Extract tokens from chainlink.tokenUsd configuration
-        if let Some(chainlink) = root.get("chainlink") {
+        if let Some(chainlink) = self
+            .get_json_config()
+            .and_then(|root| root.get("chainlink"))
+        {
             if let Some(token_usd) = chainlink.get("tokenUsd") {
                 if let Some(token_map) = token_usd.as_object() {
                     for (_chain_id, chain_tokens) in token_map {
@@ -267,6 +685,816 @@ impl Config {
         tokens
     }
 
+    /// Returns the chain IDs that have an RPC URL configured, in ascending order.
+    /// Reads both the `rpcs` map and numeric top-level keys of the flat format.
+    pub fn configured_chain_ids(&self) -> Vec<u64> {
+        let mut chain_ids: Vec<u64> = runtime_chains()
+            .read()
+            .map(|chains| chains.keys().filter_map(|k| k.parse().ok()).collect())
+            .unwrap_or_default();
+
+        let root = self.get_json_config();
+        let maps = [root.and_then(|r| r.get("rpcs")), root];
+        for map in maps.into_iter().flatten().filter_map(|m| m.as_object()) {
+            for (key, value) in map {
+                if !value.is_string() {
+                    continue;
+                }
+                if let Ok(chain_id) = key.parse::<u64>() {
+                    chain_ids.push(chain_id);
+                }
+            }
+        }
+        chain_ids.extend(
+            self.bootstrapped_chain_ids()
+                .into_iter()
+                .filter(|id| self.rpc_url_for_chain(&id.to_string()).is_some()),
+        );
+
+        chain_ids.sort_unstable();
+        chain_ids.dedup();
+        chain_ids
+    }
+
+    /// Returns the ERC20 payment tokens configured for a single chain via `chainlink.tokenUsd`.
+    pub fn get_supported_tokens_for_chain(&self, chain_id: &str) -> Vec<String> {
+        if let Some(chain) = self.runtime_chain(chain_id) {
+            return chain.tokens.keys().cloned().collect();
+        }
+        let mut tokens: Vec<String> = self
+            .get_json_config()
+            .and_then(|root| root.get("chainlink"))
+            .and_then(|c| c.get("tokenUsd"))
+            .and_then(|chains| chains.get(chain_id))
+            .and_then(|m| m.as_object())
+            .map(|m| m.keys().cloned().collect())
+            .unwrap_or_default();
+        tokens.sort();
+        tokens
+    }
+
+    /// Returns the number of confirmations required before a request on the chain is
+    /// considered final. Expects JSON structure: { "confirmations": { "1": 12 } }, defaults to 1.
+    pub fn confirmation_depth(&self, chain_id: &str) -> u64 {
+        self.get_json_config()
+            .and_then(|v| v.get("confirmations"))
+            .and_then(|m| m.get(chain_id))
+            .and_then(|n| n.as_u64())
+            .unwrap_or(1)
+    }
+
+    /// Returns the average block time of a chain in seconds.
+    /// Expects JSON structure: { "blockTimes": { "1": 12, "42161": 0.25 } }; well-known chains
+    /// have defaults and anything else assumes 12 seconds.
+    pub fn block_time_seconds(&self, chain_id: &str) -> f64 {
+        let configured = self
+            .get_json_config()
+            .and_then(|v| v.get("blockTimes"))
+            .and_then(|m| m.get(chain_id))
+            .and_then(|v| v.as_f64())
+            .filter(|t| *t > 0.0);
+        if let Some(block_time) = configured {
+            return block_time;
+        }
+
+        match chain_id {
+            "10" | "8453" | "84532" | "11155420" | "7777777" | "34443" | "137" | "43114" => 2.0,
+            "42161" | "42170" | "421614" => 0.25,
+            "56" => 3.0,
+            "100" => 5.0,
+            _ => 12.0,
+        }
+    }
+
+    /// Returns the L2 data fee model for a chain: "optimism", "arbitrum", or None.
+    /// Expects JSON structure: { "feeModels": { "10": "optimism" } }; well-known OP-stack and
+    /// Arbitrum chains default to their model, and "none" disables it.
+    pub fn fee_model(&self, chain_id: &str) -> Option<String> {
+        let configured = self
+            .get_json_config()
+            .and_then(|v| v.get("feeModels"))
+            .and_then(|m| m.get(chain_id))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_lowercase());
+        if let Some(model) = configured {
+            return if model == "none" { None } else { Some(model) };
+        }
+
+        match chain_id {
+            "10" | "8453" | "84532" | "11155420" | "7777777" | "34443" => {
+                Some("optimism".to_string())
+            }
+            "42161" | "42170" | "421614" => Some("arbitrum".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Returns the smart account implementations supported on a chain.
+    /// Expects JSON structure: { "walletImplementations": { "1": ["0x..."] } }, or a flat array
+    /// applying to every chain.
+    pub fn wallet_implementations(&self, chain_id: &str) -> Vec<String> {
+        let entry = match self
+            .get_json_config()
+            .and_then(|v| v.get("walletImplementations"))
+        {
+            Some(entry) => entry,
+            None => return Vec::new(),
+        };
+        let list = if entry.is_array() {
+            Some(entry)
+        } else {
+            entry.get(chain_id)
+        };
+        list.and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the runtime registration for a chain, if one was added through the admin API
+    pub fn runtime_chain(&self, chain_id: &str) -> Option<ChainRegistration> {
+        runtime_chains().read().ok()?.get(chain_id).cloned()
+    }
+
+    /// Register (or replace) a chain at runtime. Token addresses are normalised to lowercase.
+    pub fn register_runtime_chain(&self, mut chain: ChainRegistration) {
+        chain.tokens = chain
+            .tokens
+            .into_iter()
+            .map(|(token, feed)| (token.to_lowercase(), feed))
+            .collect();
+        chain.event_abis = chain
+            .event_abis
+            .into_iter()
+            .map(|(contract, events)| (contract.to_lowercase(), events))
+            .collect();
+        if let Ok(mut chains) = runtime_chains().write() {
+            chains.insert(chain.chain_id.clone(), chain);
+        }
+    }
+
+    /// Remove a chain registered at runtime, returning the previous registration
+    pub fn unregister_runtime_chain(&self, chain_id: &str) -> Option<ChainRegistration> {
+        runtime_chains().write().ok()?.remove(chain_id)
+    }
+
+    /// Whether the chain has an RPC URL in the JSON config file or is bootstrapped from the
+    /// chain registry (as opposed to a runtime registration)
+    pub fn is_file_configured_chain(&self, chain_id: &str) -> bool {
+        self.get_json_config().is_some_and(|root| {
+            root.get("rpcs")
+                .and_then(|m| m.get(chain_id))
+                .or_else(|| root.get(chain_id))
+                .is_some_and(|v| v.is_string())
+        }) || self.bootstrapped_chain(chain_id).is_some()
+    }
+
+    /// Returns per-method caps on in-flight calls, applied on top of `max_concurrent_requests`.
+    /// Expects JSON structure: { "methodConcurrency": { "relayer_sendTransaction": 20 } }.
+    pub fn method_concurrency_limits(&self) -> BTreeMap<String, usize> {
+        self.get_json_config()
+            .and_then(|v| v.get("methodConcurrency"))
+            .and_then(|v| v.as_object())
+            .map(|limits| {
+                limits
+                    .iter()
+                    .filter_map(|(method, limit)| {
+                        Some((method.clone(), usize::try_from(limit.as_u64()?).ok()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the RPC methods turned off on this deployment; calls to them fail with "method
+    /// not found". Expects JSON structure: { "disabledMethods": ["relayer_getQuote"] }.
+    pub fn disabled_methods(&self) -> BTreeSet<String> {
+        self.get_json_config()
+            .and_then(|v| v.get("disabledMethods"))
+            .and_then(|v| v.as_array())
+            .map(|methods| {
+                methods
+                    .iter()
+                    .filter_map(|method| method.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether `method` is served, i.e. not listed in `disabledMethods`
+    pub fn is_method_enabled(&self, method: &str) -> bool {
+        !self.disabled_methods().contains(method)
+    }
+
+    /// Returns the largest calldata, in bytes, accepted for a relayed transaction.
+    /// Expects JSON structure: { "limits": { "maxCalldataBytes": 131072 } }, defaults to 128 KiB.
+    pub fn max_calldata_bytes(&self) -> u64 {
+        self.get_json_config()
+            .and_then(|v| v.get("limits"))
+            .and_then(|m| m.get("maxCalldataBytes"))
+            .and_then(|n| n.as_u64())
+            .unwrap_or(131_072)
+    }
+
+    /// Returns the most gas a relayed transaction may use.
+    /// Expects JSON structure: { "limits": { "maxGas": 15000000 } }, defaults to 15,000,000.
+    pub fn max_gas(&self) -> u64 {
+        self.get_json_config()
+            .and_then(|v| v.get("limits"))
+            .and_then(|m| m.get("maxGas"))
+            .and_then(|n| n.as_u64())
+            .unwrap_or(15_000_000)
+    }
+
+    /// Returns the API keys accepted for sponsored relays; sponsorship is open when empty.
+    /// Expects JSON structure: { "sponsorship": { "apiKeys": ["..."] } }.
+    pub fn sponsorship_api_keys(&self) -> Vec<String> {
+        self.get_json_config()
+            .and_then(|v| v.get("sponsorship"))
+            .and_then(|m| m.get("apiKeys"))
+            .and_then(|v| v.as_array())
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns how a speed lane prices gas on a chain.
+    /// Expects JSON structure: { "speedLanes": { "137": { "fast": { "percentile": 95,
+    /// "bumpPercent": 50 } } } }; a "default" entry applies to chains without their own, and
+    /// unset fields keep the built-in lane (slow: 25th percentile, 10% bumps; standard:
+    /// `eth_gasPrice`, 20% bumps; fast: 90th percentile, 40% bumps).
+    pub fn speed_lane(&self, chain_id: &str, speed: Speed) -> SpeedLane {
+        let (name, mut lane) = match speed {
+            Speed::Slow => (
+                "slow",
+                SpeedLane {
+                    percentile: Some(25.0),
+                    bump_percent: 10,
+                },
+            ),
+            Speed::Standard => (
+                "standard",
+                SpeedLane {
+                    percentile: None,
+                    bump_percent: 20,
+                },
+            ),
+            Speed::Fast => (
+                "fast",
+                SpeedLane {
+                    percentile: Some(90.0),
+                    bump_percent: 40,
+                },
+            ),
+        };
+        let lanes = self.get_json_config().and_then(|v| v.get("speedLanes"));
+        let configured = lanes
+            .and_then(|m| m.get(chain_id).or_else(|| m.get("default")))
+            .and_then(|m| m.get(name));
+        if let Some(configured) = configured {
+            if let Some(percentile) = configured
+                .get("percentile")
+                .and_then(|v| v.as_f64())
+                .filter(|p| (0.0..=100.0).contains(p))
+            {
+                lane.percentile = Some(percentile);
+            }
+            if let Some(bump) = configured.get("bumpPercent").and_then(|v| v.as_u64()) {
+                lane.bump_percent = bump;
+            }
+        }
+        lane
+    }
+
+    /// Returns where a chain's gas price comes from and how it is bounded.
+    /// Expects JSON structure: { "gasOracle": { "137": { "strategy": "polygonGasStation",
+    /// "floorGwei": 30, "ceilingGwei": 1000 }, "default": { "strategy": "feeHistory",
+    /// "percentile": 60 } } }. Strategies are "node" (`eth_gasPrice`, the default),
+    /// "feeHistory" (next base fee plus the `percentile` priority fee, default 50) and
+    /// "polygonGasStation" (`gasStationUrl`, known for Polygon and Amoy). A ceiling below the
+    /// floor is ignored.
+    pub fn gas_oracle(&self, chain_id: &str) -> GasOracle {
+        let default_station = match chain_id {
+            "137" => Some("https://gasstation.polygon.technology/v2"),
+            "80002" => Some("https://gasstation.polygon.technology/amoy"),
+            _ => None,
+        };
+        let mut oracle = GasOracle {
+            strategy: GasPriceStrategy::Node,
+            percentile: 50.0,
+            gas_station_url: default_station.map(|url| url.to_string()),
+            floor_wei: None,
+            ceiling_wei: None,
+        };
+        let Some(entry) = self
+            .get_json_config()
+            .and_then(|v| v.get("gasOracle"))
+            .and_then(|m| m.get(chain_id).or_else(|| m.get("default")))
+        else {
+            return oracle;
+        };
+
+        if let Some(strategy) = entry.get("strategy") {
+            match serde_json::from_value(strategy.clone()) {
+                Ok(strategy) => oracle.strategy = strategy,
+                Err(_) => tracing::warn!(
+                    "Ignoring unknown gas price strategy {} for chain {}",
+                    strategy,
+                    chain_id
+                ),
+            }
+        }
+        if let Some(percentile) = entry
+            .get("percentile")
+            .and_then(|v| v.as_f64())
+            .filter(|p| (0.0..=100.0).contains(p))
+        {
+            oracle.percentile = percentile;
+        }
+        if let Some(url) = entry.get("gasStationUrl").and_then(|v| v.as_str()) {
+            oracle.gas_station_url = Some(url.to_string());
+        }
+        let gwei = |field: &str| {
+            entry
+                .get(field)
+                .and_then(|v| v.as_f64())
+                .filter(|g| *g >= 0.0)
+                .map(|g| (g * 1e9) as u128)
+        };
+        oracle.floor_wei = gwei("floorGwei");
+        oracle.ceiling_wei = gwei("ceilingGwei");
+        if let (Some(floor), Some(ceiling)) = (oracle.floor_wei, oracle.ceiling_wei) {
+            if ceiling < floor {
+                tracing::warn!(
+                    "Ignoring gas price ceiling for chain {}: ceilingGwei is below floorGwei",
+                    chain_id
+                );
+                oracle.ceiling_wei = None;
+            }
+        }
+        oracle
+    }
+
+    /// Returns the balance targets for rebalancing native funds between a chain's keys, or
+    /// `None` when the chain is not rebalanced.
+    /// Expects JSON structure: { "rebalancer": { "chains": { "137": { "keyRefs":
+    /// ["RELAYX_TREASURY_KEY"], "minBalance": "200000000000000000", "targetBalance":
+    /// "1000000000000000000" } } } }; a "default" entry applies to chains without their own.
+    /// Balances are in wei, as decimal strings or numbers; `targetBalance` below `minBalance`
+    /// disables the entry.
+    pub fn rebalance_policy(&self, chain_id: &str) -> Option<RebalancePolicy> {
+        let chains = self.get_json_config()?.get("rebalancer")?.get("chains")?;
+        let entry = chains.get(chain_id).or_else(|| chains.get("default"))?;
+        let wei = |field: &str| -> Option<u128> {
+            match entry.get(field)? {
+                serde_json::Value::String(s) => s.parse().ok(),
+                value => value.as_u64().map(u128::from),
+            }
+        };
+        let min_balance = wei("minBalance")?;
+        let target_balance = wei("targetBalance")?;
+        if target_balance < min_balance {
+            tracing::warn!(
+                "Ignoring rebalancer entry for chain {}: targetBalance is below minBalance",
+                chain_id
+            );
+            return None;
+        }
+        let key_refs = entry
+            .get("keyRefs")
+            .and_then(|v| v.as_array())
+            .map(|refs| {
+                refs.iter()
+                    .filter_map(|r| r.as_str())
+                    .map(|r| r.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(RebalancePolicy {
+            key_refs,
+            min_balance,
+            target_balance,
+        })
+    }
+
+    /// How often the rebalancer checks key balances, from `rebalancer.intervalSeconds`
+    /// (default 300)
+    pub fn rebalance_interval(&self) -> std::time::Duration {
+        let seconds = self
+            .get_json_config()
+            .and_then(|v| v.get("rebalancer"))
+            .and_then(|v| v.get("intervalSeconds"))
+            .and_then(|v| v.as_u64())
+            .filter(|s| *s > 0)
+            .unwrap_or(300);
+        std::time::Duration::from_secs(seconds)
+    }
+
+    /// Returns the sponsorship projects, each with the monthly gas allowance of its tier.
+    /// Expects JSON structure: { "sponsorship": { "tiers": { "pro": { "monthlyGas": 50000000 } },
+    /// "projects": { "acme": { "apiKey": "...", "tier": "pro" } } } }. A tier without
+    /// `monthlyGas` is unlimited; a project whose tier is not defined gets no allowance.
+    pub fn sponsorship_projects(&self) -> Vec<SponsorshipProject> {
+        let Some(sponsorship) = self.get_json_config().and_then(|v| v.get("sponsorship")) else {
+            return Vec::new();
+        };
+        let Some(projects) = sponsorship.get("projects").and_then(|v| v.as_object()) else {
+            return Vec::new();
+        };
+        projects
+            .iter()
+            .filter_map(|(id, entry)| {
+                let api_key = entry.get("apiKey").and_then(|v| v.as_str())?;
+                if api_key.is_empty() {
+                    return None;
+                }
+                let tier = entry
+                    .get("tier")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let monthly_gas = match sponsorship.get("tiers").and_then(|t| t.get(&tier)) {
+                    Some(limits) => limits.get("monthlyGas").and_then(|n| n.as_u64()),
+                    None => Some(0),
+                };
+                Some(SponsorshipProject {
+                    id: id.clone(),
+                    api_key: api_key.to_string(),
+                    tier,
+                    monthly_gas,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the tenants sharing this instance, each identified by its API key. Expects JSON
+    /// structure: { "tenants": { "acme": { "apiKey": "..." } } }. Entries without a key are
+    /// skipped; with no tenants the relayer serves a single tenant and needs no key.
+    pub fn tenants(&self) -> Vec<Tenant> {
+        let Some(tenants) = self
+            .get_json_config()
+            .and_then(|v| v.get("tenants"))
+            .and_then(|v| v.as_object())
+        else {
+            return Vec::new();
+        };
+        tenants
+            .iter()
+            .filter_map(|(id, entry)| {
+                let api_key = entry.get("apiKey").and_then(|v| v.as_str())?;
+                if api_key.is_empty() {
+                    return None;
+                }
+                Some(Tenant {
+                    id: id.clone(),
+                    api_key: api_key.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the address gas tank deposits must be sent to, if it differs from the fee
+    /// collector. Expects JSON structure: { "gasTank": { "depositAddress": "0x..." } }.
+    pub fn gas_tank_deposit_address(&self) -> Option<String> {
+        self.get_json_config()?
+            .get("gasTank")
+            .and_then(|m| m.get("depositAddress"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    /// Returns the RocksDB tuning, with defaults for anything not set.
+    /// Expects JSON structure: { "rocksdb": { "blockCacheMb": 256, "writeBufferMb": 128,
+    /// "compression": "lz4", "maxOpenFiles": -1, "statistics": true } }; compression is one of
+    /// none, snappy, lz4 or zstd.
+    pub fn storage_tuning(&self) -> StorageTuning {
+        let mut tuning = StorageTuning::default();
+        let Some(entry) = self.get_json_config().and_then(|v| v.get("rocksdb")) else {
+            return tuning;
+        };
+        let megabytes = |key: &str| {
+            entry
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .and_then(|mb| usize::try_from(mb).ok()?.checked_mul(1024 * 1024))
+        };
+        if let Some(bytes) = megabytes("blockCacheMb") {
+            tuning.block_cache_bytes = bytes;
+        }
+        if let Some(bytes) = megabytes("writeBufferMb") {
+            tuning.write_buffer_bytes = bytes;
+        }
+        if let Some(compression) = entry.get("compression") {
+            match serde_json::from_value(compression.clone()) {
+                Ok(compression) => tuning.compression = compression,
+                Err(_) => tracing::warn!("Ignoring unknown RocksDB compression {}", compression),
+            }
+        }
+        if let Some(files) = entry
+            .get("maxOpenFiles")
+            .and_then(|v| v.as_i64())
+            .and_then(|n| i32::try_from(n).ok())
+        {
+            tuning.max_open_files = files;
+        }
+        if let Some(statistics) = entry.get("statistics").and_then(|v| v.as_bool()) {
+            tuning.statistics = statistics;
+        }
+        tuning
+    }
+
+    /// Returns the gas limit a relay is sent with when it is not simulated, either because
+    /// simulation is disabled or because it failed and `simulation.onFailure` falls back.
+    /// Expects JSON structure: { "simulation": { "fallbackGasLimit": { "default": 150000,
+    /// "42161": 3000000 } } }, defaults to 150,000.
+    pub fn fallback_gas_limit(&self, chain_id: u64) -> u64 {
+        let Some(limits) = self
+            .get_json_config()
+            .and_then(|v| v.get("simulation"))
+            .and_then(|v| v.get("fallbackGasLimit"))
+        else {
+            return 150_000;
+        };
+        limits
+            .get(chain_id.to_string())
+            .or_else(|| limits.get("default"))
+            .and_then(|v| v.as_u64())
+            .filter(|gas| *gas > 0)
+            .unwrap_or(150_000)
+    }
+
+    /// Returns what a relay does when its simulation fails.
+    /// Expects JSON structure: { "simulation": { "onFailure": "fallbackWithCap",
+    /// "fallbackCapPerHour": 20 } }; `onFailure` is `reject` (the default), `fallback` or
+    /// `fallbackWithCap` (cap defaults to 10).
+    pub fn simulation_failure_mode(&self) -> SimulationFailureMode {
+        let Some(entry) = self.get_json_config().and_then(|v| v.get("simulation")) else {
+            return SimulationFailureMode::Reject;
+        };
+        match entry.get("onFailure").and_then(|v| v.as_str()) {
+            Some("fallback") => SimulationFailureMode::Fallback,
+            Some("fallbackWithCap") => SimulationFailureMode::FallbackWithCap(
+                entry
+                    .get("fallbackCapPerHour")
+                    .and_then(|v| v.as_u64())
+                    .and_then(|n| u32::try_from(n).ok())
+                    .unwrap_or(10),
+            ),
+            Some("reject") | None => SimulationFailureMode::Reject,
+            Some(other) => {
+                tracing::warn!("Unknown simulation.onFailure '{}', rejecting", other);
+                SimulationFailureMode::Reject
+            }
+        }
+    }
+
+    /// Returns the range fees quoted in `token` on a chain are held to, if configured; the
+    /// native token is the zero address.
+    /// Expects JSON structure: { "feeBounds": { "8453": { "0xToken": { "min": 10000,
+    /// "max": 50000000, "onExceed": "reject" } } } }; `onExceed` is `clamp` (the default) or
+    /// `reject`.
+    pub fn fee_bounds(&self, chain_id: &str, token: &str) -> Option<FeeBounds> {
+        let config = self.get_json_config()?;
+        let (_, entry) = config
+            .get("feeBounds")?
+            .get(chain_id)?
+            .as_object()?
+            .iter()
+            .find(|(address, _)| address.eq_ignore_ascii_case(token))?;
+        let bounds = FeeBounds {
+            min: entry.get("min").and_then(|v| v.as_u64()),
+            max: entry.get("max").and_then(|v| v.as_u64()),
+            reject: match entry.get("onExceed").and_then(|v| v.as_str()) {
+                Some("reject") => true,
+                Some("clamp") | None => false,
+                Some(other) => {
+                    tracing::warn!("Unknown feeBounds onExceed '{}', clamping", other);
+                    false
+                }
+            },
+        };
+        (bounds.min.is_some() || bounds.max.is_some()).then_some(bounds)
+    }
+
+    /// Returns the limits on resubmitting an unmined request.
+    /// Expects JSON structure: { "resubmission": { "maxAttempts": 50, "keepLast": 10 } }
+    pub fn resubmission_policy(&self) -> ResubmissionPolicy {
+        let mut policy = ResubmissionPolicy::default();
+        let Some(entry) = self.get_json_config().and_then(|v| v.get("resubmission")) else {
+            return policy;
+        };
+        if let Some(max) = entry
+            .get("maxAttempts")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| u32::try_from(n).ok())
+        {
+            policy.max_attempts = max;
+        }
+        if let Some(keep) = entry
+            .get("keepLast")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| usize::try_from(n).ok())
+        {
+            policy.keep_last = keep;
+        }
+        policy
+    }
+
+    /// Returns the numeric codes `relayer_getStatus` reports for each request state.
+    /// Expects JSON structure: { "statusCodes": { "scheme": "eip5792", "notFound": 410 } };
+    /// `scheme` (`http`, the default, or `eip5792`) picks the codes and keys named after the
+    /// `statusName` values override single ones.
+    pub fn status_codes(&self) -> StatusCodes {
+        let Some(entry) = self.get_json_config().and_then(|v| v.get("statusCodes")) else {
+            return StatusCodes::default();
+        };
+        let mut codes = match entry.get("scheme").and_then(|v| v.as_str()) {
+            Some(name) => StatusCodes::scheme(name).unwrap_or_else(|| {
+                tracing::warn!("Ignoring unknown statusCodes scheme {}", name);
+                StatusCodes::default()
+            }),
+            None => StatusCodes::default(),
+        };
+        for kind in StatusKind::ALL {
+            if let Some(code) = entry
+                .get(kind.name())
+                .and_then(|v| v.as_u64())
+                .and_then(|n| u16::try_from(n).ok())
+            {
+                codes.set(kind, code);
+            }
+        }
+        codes
+    }
+
+    /// Returns when finished requests are archived, or `None` to keep them all in the hot
+    /// set. Expects JSON structure: { "archive": { "afterDays": 30, "intervalSeconds": 3600 } }
+    pub fn archive_policy(&self) -> Option<ArchivePolicy> {
+        let entry = self.get_json_config()?.get("archive")?;
+        let mut policy = ArchivePolicy::default();
+        if let Some(days) = entry
+            .get("afterDays")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| u32::try_from(n).ok())
+        {
+            policy.after_days = days;
+        }
+        if let Some(seconds) = entry
+            .get("intervalSeconds")
+            .and_then(|v| v.as_u64())
+            .filter(|s| *s > 0)
+        {
+            policy.interval_seconds = seconds;
+        }
+        Some(policy)
+    }
+
+    /// Returns how repeated submissions of the same relay are detected, if enabled.
+    /// Expects JSON structure: { "duplicates": { "windowSeconds": 10, "onDuplicate": "reject" } };
+    /// `onDuplicate` is `reject` or `returnExisting`.
+    pub fn duplicate_policy(&self) -> Option<DuplicatePolicy> {
+        let entry = self.get_json_config()?.get("duplicates")?;
+        let mut policy = DuplicatePolicy::default();
+        if let Some(seconds) = entry
+            .get("windowSeconds")
+            .and_then(|v| v.as_u64())
+            .filter(|s| *s > 0)
+        {
+            policy.window_seconds = seconds;
+        }
+        match entry.get("onDuplicate").and_then(|v| v.as_str()) {
+            Some("returnExisting") => policy.return_existing = true,
+            Some("reject") | None => {}
+            Some(other) => tracing::warn!("Unknown duplicates.onDuplicate '{}', rejecting", other),
+        }
+        Some(policy)
+    }
+
+    /// Returns the wallet ABI file to use instead of the embedded `resources/abi.json`.
+    /// Expects JSON structure: { "walletAbiPath": "/etc/relayx/wallet-abi.json" }; the file may
+    /// be a compiler artifact with an `abi` field or a bare ABI array.
+    pub fn wallet_abi_path(&self) -> Option<PathBuf> {
+        self.get_json_config()?
+            .get("walletAbiPath")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Hosts callbacks may be delivered to: public ones unless
+    /// `--callback-allow-private-hosts` is set
+    pub fn callback_host_policy(&self) -> HostPolicy {
+        if self.callback_allow_private_hosts {
+            HostPolicy::AllowPrivate
+        } else {
+            HostPolicy::PublicOnly
+        }
+    }
+
+    /// Returns the admin API token from CLI/env/config, if provided.
+    pub fn get_admin_token(&self) -> Option<String> {
+        if let Some(token) = self.admin_token.as_ref().filter(|s| !s.is_empty()) {
+            return Some(token.clone());
+        }
+        self.get_json_config()
+            .and_then(|v| v.get("adminToken"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    /// Returns the relayer private key for a chain. A runtime registration may point at a
+    /// dedicated key via `relayerKeyRef` (an environment variable name); otherwise the global
+    /// relayer key is used.
+    pub fn get_relayer_private_key_for_chain(&self, chain_id: &str) -> Option<String> {
+        if let Some(key_ref) = self
+            .runtime_chain(chain_id)
+            .and_then(|chain| chain.relayer_key_ref)
+        {
+            if let Ok(key) = std::env::var(&key_ref) {
+                if !key.is_empty() {
+                    return Some(key);
+                }
+            }
+            tracing::warn!(
+                "Relayer key reference {} for chain {} is not set, using default key",
+                key_ref,
+                chain_id
+            );
+        }
+        self.get_relayer_private_key()
+    }
+
+    /// Returns the remote signer a key is held by, if any: `default` for the relayer key, or
+    /// the environment variable name a `relayerKeyRef` or rebalancer `keyRefs` entry uses.
+    /// Expects JSON structure: { "remoteSigners": { "default": { "url":
+    /// "https://web3signer:9000", "publicKey": "0x04...", "clientCert": "/etc/relayx/client.pem",
+    /// "clientKey": "/etc/relayx/client.key", "caCert": "/etc/relayx/ca.pem", "timeoutMs": 5000 } } }
+    pub fn remote_signer(&self, name: &str) -> Option<RemoteSignerSettings> {
+        let entry = self
+            .get_json_config()?
+            .get("remoteSigners")?
+            .get(name)?
+            .clone();
+        serde_json::from_value(entry)
+            .inspect_err(|e| tracing::warn!("Ignoring remoteSigners entry {}: {}", name, e))
+            .ok()
+    }
+
+    /// Returns the signer of the relayer key: its remote signer when one is configured under
+    /// `default`, else the private key. `None` when neither is set.
+    pub fn relayer_signer(&self) -> Option<Result<RelayerSigner, String>> {
+        if let Some(settings) = self.remote_signer("default") {
+            return Some(RelayerSigner::remote(&settings));
+        }
+        self.get_relayer_private_key().map(|key| local_signer(&key))
+    }
+
+    /// Returns the signer of a chain's relayer key, following its `relayerKeyRef` like
+    /// [`Config::get_relayer_private_key_for_chain`]
+    pub fn relayer_signer_for_chain(
+        &self,
+        chain_id: &str,
+    ) -> Option<Result<RelayerSigner, String>> {
+        if let Some(key_ref) = self
+            .runtime_chain(chain_id)
+            .and_then(|chain| chain.relayer_key_ref)
+        {
+            if let Some(signer) = self.signer_for_key_ref(&key_ref) {
+                return Some(signer);
+            }
+            tracing::warn!(
+                "Relayer key reference {} for chain {} is not set, using default key",
+                key_ref,
+                chain_id
+            );
+        }
+        self.relayer_signer()
+    }
+
+    /// Returns the signer of the key an environment variable name refers to: its remote
+    /// signer when one is configured under that name, else the private key the variable
+    /// holds. `None` when neither is set.
+    pub fn signer_for_key_ref(&self, key_ref: &str) -> Option<Result<RelayerSigner, String>> {
+        if let Some(settings) = self.remote_signer(key_ref) {
+            return Some(RelayerSigner::remote(&settings));
+        }
+        std::env::var(key_ref)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(|key| local_signer(&key))
+    }
+
     /// Check if a chain ID is supported by checking if it has an RPC URL configured
     pub fn is_chain_supported(&self, chain_id: u64) -> bool {
         self.rpc_url_for_chain(&chain_id.to_string()).is_some()
@@ -283,6 +1511,15 @@ impl Config {
             .unwrap_or_else(|| self.log_level.clone())
     }
 
+    /// Effective gas price cache lifetime from config.json (`gasPriceTtlMs`) or CLI/env
+    pub fn gas_price_ttl(&self) -> std::time::Duration {
+        let millis = self
+            .get_json_config()
+            .and_then(|v| v.get("gasPriceTtlMs").and_then(|n| n.as_u64()))
+            .unwrap_or(self.gas_price_ttl_ms);
+        std::time::Duration::from_millis(millis)
+    }
+
     /// Check if simulation is disabled (from config.json or CLI/env)
     pub fn is_simulation_disabled(&self) -> bool {
         if self.disable_simulation {
@@ -308,12 +1545,172 @@ impl Config {
         self.get_json_config().and_then(|v| {
             v.get("sentryDsn")
                 .or_else(|| v.get("sentry_dsn"))
+                .or_else(|| v.get("sentry").and_then(|s| s.get("dsn")))
                 .and_then(|s| s.as_str())
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string())
         })
     }
 
+    /// Returns the sandbox settings when sandbox mode is on, i.e. when a `sandbox` section is
+    /// present without `"enabled": false`.
+    /// Expects JSON structure: { "sandbox": { "chainLatencyMs": 100, "latencyMs": { "default": 50,
+    /// "relayer_sendTransaction": 800 }, "failures": { "relayer_getQuote": { "code": -32005,
+    /// "message": "Relayer Busy" } }, "receipts": { "default": "success",
+    /// "0xWallet": "reverted" } } }; receipt outcomes are `success`, `reverted` or `pending`.
+    pub fn sandbox_settings(&self) -> Option<SandboxSettings> {
+        let entry = self.get_json_config()?.get("sandbox")?;
+        if entry.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
+            return None;
+        }
+        let millis = |v: &serde_json::Value| v.as_u64().map(std::time::Duration::from_millis);
+        let mut settings = SandboxSettings::default();
+        if let Some(latency) = entry.get("chainLatencyMs").and_then(millis) {
+            settings.chain_latency = latency;
+        }
+        if let Some(latencies) = entry.get("latencyMs").and_then(|v| v.as_object()) {
+            for (method, latency) in latencies {
+                let Some(latency) = millis(latency) else {
+                    continue;
+                };
+                match method.as_str() {
+                    "default" => settings.default_latency = latency,
+                    _ => {
+                        settings.method_latency.insert(method.clone(), latency);
+                    }
+                }
+            }
+        }
+        if let Some(failures) = entry.get("failures").and_then(|v| v.as_object()) {
+            for (method, failure) in failures {
+                settings.failures.insert(
+                    method.clone(),
+                    SandboxFailure {
+                        code: failure
+                            .get("code")
+                            .and_then(|v| v.as_i64())
+                            .unwrap_or(-32603),
+                        message: failure
+                            .get("message")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("Sandbox failure")
+                            .to_string(),
+                    },
+                );
+            }
+        }
+        if let Some(receipts) = entry.get("receipts").and_then(|v| v.as_object()) {
+            for (target, outcome) in receipts {
+                let Ok(outcome) = serde_json::from_value::<ReceiptOutcome>(outcome.clone()) else {
+                    tracing::warn!(
+                        "Ignoring sandbox receipt outcome {} for {}",
+                        outcome,
+                        target
+                    );
+                    continue;
+                };
+                if target == "default" {
+                    settings.default_receipt = outcome;
+                    continue;
+                }
+                match target.parse() {
+                    Ok(address) => {
+                        settings.receipts.insert(address, outcome);
+                    }
+                    Err(e) => tracing::warn!("Ignoring sandbox receipt for {}: {}", target, e),
+                }
+            }
+        }
+        Some(settings)
+    }
+
+    /// Returns where upstream chain traffic is recorded to or replayed from, if anywhere.
+    /// Expects JSON structure: { "rpcRecording": { "mode": "record", "path": "fixtures/base.jsonl" } };
+    /// `mode` is `record` or `replay`.
+    pub fn rpc_recording(&self) -> Option<RpcRecording> {
+        let entry = self.get_json_config()?.get("rpcRecording")?;
+        let path = entry.get("path").and_then(|v| v.as_str())?;
+        let mode = match serde_json::from_value::<RecordingMode>(entry.get("mode")?.clone()) {
+            Ok(mode) => mode,
+            Err(e) => {
+                tracing::warn!("Ignoring rpcRecording: invalid mode: {}", e);
+                return None;
+            }
+        };
+        Some(RpcRecording {
+            mode,
+            path: path.into(),
+        })
+    }
+
+    /// Returns the local EVM settings when in-process simulation is on, i.e. when a
+    /// `localSimulation` section is present without `"enabled": false`.
+    /// Expects JSON structure: { "localSimulation": { "fork": true, "gasLimit": 30000000,
+    /// "accounts": { "0xWallet": { "balance": "0xde0b6b3a7640000", "nonce": 1, "code": "0x60...",
+    /// "storage": { "0x0": "0x1" } } } } }
+    pub fn local_evm_settings(&self) -> Option<LocalEvmSettings> {
+        let entry = self.get_json_config()?.get("localSimulation")?;
+        if entry.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
+            return None;
+        }
+        let mut settings = LocalEvmSettings::default();
+        if let Some(fork) = entry.get("fork").and_then(|v| v.as_bool()) {
+            settings.fork = fork;
+        }
+        if let Some(gas_limit) = entry.get("gasLimit").and_then(|v| v.as_u64()) {
+            settings.gas_limit = gas_limit;
+        }
+        if let Some(accounts) = entry.get("accounts").and_then(|v| v.as_object()) {
+            for (address, account) in accounts {
+                let parsed = address.parse().map_err(|e| format!("{}", e)).and_then(|a| {
+                    serde_json::from_value(account.clone())
+                        .map(|account| (a, account))
+                        .map_err(|e| e.to_string())
+                });
+                match parsed {
+                    Ok((address, account)) => {
+                        settings.accounts.insert(address, account);
+                    }
+                    Err(e) => tracing::warn!("Ignoring local EVM account {}: {}", address, e),
+                }
+            }
+        }
+        Some(settings)
+    }
+
+    /// Returns the Sentry client settings, with defaults for anything not set.
+    /// Expects JSON structure: { "sentry": { "environment": "production", "sampleRate": 0.5,
+    /// "tracesSampleRate": 0.05, "attachRequestContext": true, "scrubFields": ["email"] } };
+    /// sample rates are clamped to 0..1 and `scrubFields` are masked on top of the defaults.
+    pub fn sentry_settings(&self) -> SentrySettings {
+        let mut settings = SentrySettings::default();
+        let Some(entry) = self.get_json_config().and_then(|v| v.get("sentry")) else {
+            return settings;
+        };
+        if let Some(environment) = entry
+            .get("environment")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+        {
+            settings.environment = Some(environment.to_string());
+        }
+        if let Some(rate) = entry.get("sampleRate").and_then(|v| v.as_f64()) {
+            settings.sample_rate = rate.clamp(0.0, 1.0) as f32;
+        }
+        if let Some(rate) = entry.get("tracesSampleRate").and_then(|v| v.as_f64()) {
+            settings.traces_sample_rate = rate.clamp(0.0, 1.0) as f32;
+        }
+        if let Some(attach) = entry.get("attachRequestContext").and_then(|v| v.as_bool()) {
+            settings.attach_request_context = attach;
+        }
+        if let Some(fields) = entry.get("scrubFields").and_then(|v| v.as_array()) {
+            settings
+                .scrub_fields
+                .extend(fields.iter().filter_map(|f| f.as_str().map(str::to_string)));
+        }
+        settings
+    }
+
     /// Returns the configured Etherscan API key if present in the JSON file.
     /// Supports either top-level `etherscanApiKey` in config.json or `ETHERSCAN_API_KEY` env var.
     pub fn etherscan_api_key(&self) -> Option<String> {
@@ -0,0 +1,301 @@
+//! At-rest encryption of stored request data.
+//!
+//! When a storage key is configured, [`crate::storage::Storage`] seals the records that carry
+//! request payloads (requests and their responses, receipts, call results, callbacks,
+//! scheduled and recurring submissions, overpayments) with AES-256-GCM before writing them. Each sealed
+//! value is a version byte, a random 96-bit nonce and the ciphertext with its tag; the record's
+//! RocksDB key is bound in as associated data so a sealed value cannot be moved to another
+//! record. Plaintext JSON written before encryption was enabled stays readable and is sealed
+//! the next time it is written.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use zeroize::Zeroizing;
+
+/// First byte of a sealed value; plaintext records are JSON and start with `{` or `[`
+const SEALED_V1: u8 = 1;
+
+/// A 256-bit storage key, given as 64 hex digits. Its `Debug` output is redacted so it never
+/// reaches the logs with the rest of the configuration.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StorageKey([u8; 32]);
+
+impl StorageKey {
+    /// Read a key from a file, such as one a KMS or secret-manager agent decrypts into place.
+    /// Like the relayer key file, it must not be readable by group or others.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        read_owner_only(path, "storage key file")?
+            .parse()
+            .map_err(|e| anyhow!("storage key file {}: {}", path.display(), e))
+    }
+}
+
+impl FromStr for StorageKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.trim().trim_start_matches("0x");
+        let bytes = hex::decode(digits).map_err(|e| format!("invalid storage key: {}", e))?;
+        let key: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            format!(
+                "invalid storage key: expected 32 bytes (64 hex digits), got {}",
+                bytes.len()
+            )
+        })?;
+        Ok(StorageKey(key))
+    }
+}
+
+impl fmt::Debug for StorageKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StorageKey(<redacted>)")
+    }
+}
+
+/// Where the storage key comes from, shared by the server and the subcommands that open the
+/// database directly
+#[derive(Args, Debug, Clone, Default)]
+pub struct StorageKeyArgs {
+    /// Hex-encoded 32-byte key encrypting stored request data; stored in plaintext when unset
+    #[arg(
+        long = "storage-key",
+        env = "RELAYX_STORAGE_KEY",
+        hide_env_values = true
+    )]
+    pub storage_key: Option<StorageKey>,
+
+    /// File holding the storage key, such as one a KMS or secret-manager agent writes
+    #[arg(
+        long = "storage-key-file",
+        env = "RELAYX_STORAGE_KEY_FILE",
+        conflicts_with = "storage_key"
+    )]
+    pub storage_key_file: Option<PathBuf>,
+}
+
+impl StorageKeyArgs {
+    /// The configured key, if any
+    pub fn resolve(&self) -> Result<Option<StorageKey>> {
+        match (&self.storage_key, &self.storage_key_file) {
+            (Some(key), _) => Ok(Some(key.clone())),
+            (None, Some(path)) => StorageKey::from_file(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// Seals and opens stored records with a [`StorageKey`]
+pub struct RecordCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl RecordCipher {
+    pub fn new(key: &StorageKey) -> Self {
+        let unbound = UnboundKey::new(&AES_256_GCM, &key.0).expect("AES-256 key is 32 bytes");
+        Self {
+            key: LessSafeKey::new(unbound),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Encrypt the value of the record stored under `record_key`
+    pub fn seal(&self, record_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| anyhow!("failed to generate a nonce"))?;
+        let mut sealed = Vec::with_capacity(1 + NONCE_LEN + plaintext.len() + 16);
+        sealed.push(SEALED_V1);
+        sealed.extend_from_slice(&nonce);
+        let mut in_out = plaintext.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(record_key),
+                &mut in_out,
+            )
+            .map_err(|_| anyhow!("failed to encrypt record"))?;
+        sealed.extend_from_slice(&in_out);
+        Ok(sealed)
+    }
+
+    /// Decrypt a value written by [`RecordCipher::seal`] for the same `record_key`
+    pub fn open(&self, record_key: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+        let (nonce, ciphertext) = match sealed.split_first() {
+            Some((&SEALED_V1, rest)) if rest.len() >= NONCE_LEN => rest.split_at(NONCE_LEN),
+            _ => bail!("record is not sealed"),
+        };
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| anyhow!("malformed record nonce"))?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(record_key), &mut in_out)
+            .map_err(|_| {
+                anyhow!(
+                    "failed to decrypt record {}: wrong storage key or corrupted value",
+                    String::from_utf8_lossy(record_key)
+                )
+            })?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+/// Whether a stored value was sealed rather than written as plaintext JSON
+pub fn is_sealed(value: &[u8]) -> bool {
+    value.first() == Some(&SEALED_V1)
+}
+
+/// Read a secret from a file only its owner can access. On Unix a file that group or others
+/// may read or write (anything looser than 0600) is refused, as the secret has then already
+/// leaked to other local users. `what` names the file in errors.
+pub fn read_owner_only(path: &Path, what: &str) -> Result<Zeroizing<String>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(path)
+            .with_context(|| format!("failed to read {} {}", what, path.display()))?
+            .permissions()
+            .mode()
+            & 0o777;
+        if mode & 0o077 != 0 {
+            bail!(
+                "{} {} is accessible by group or others (mode {:o}); restrict it with chmod 600",
+                what,
+                path.display(),
+                mode
+            );
+        }
+    }
+    let contents = Zeroizing::new(
+        fs::read_to_string(path)
+            .with_context(|| format!("failed to read {} {}", what, path.display()))?,
+    );
+    Ok(Zeroizing::new(contents.trim().to_string()))
+}
+
+/// A secret held in memory, shared by every clone of the configuration so that one
+/// [`SecretSlot::wipe`] zeroes it everywhere. Its `Debug` output is redacted.
+#[derive(Clone, Default)]
+pub struct SecretSlot(Arc<RwLock<Option<Zeroizing<String>>>>);
+
+impl SecretSlot {
+    pub fn set(&self, secret: Zeroizing<String>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = Some(secret);
+    }
+
+    pub fn get(&self) -> Option<String> {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|secret| secret.to_string())
+    }
+
+    /// Zero the secret and forget it
+    pub fn wipe(&self) {
+        self.0.write().unwrap_or_else(|e| e.into_inner()).take();
+    }
+}
+
+impl fmt::Debug for SecretSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretSlot(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> StorageKey {
+        hex::encode([byte; 32]).parse().unwrap()
+    }
+
+    #[test]
+    fn test_storage_key_parsing_and_redaction() {
+        let parsed: StorageKey = format!("0x{}", "ab".repeat(32)).parse().unwrap();
+        assert_eq!(parsed, key(0xab));
+        assert_eq!(format!("{:?}", parsed), "StorageKey(<redacted>)");
+        assert!("ab".repeat(16).parse::<StorageKey>().is_err());
+        assert!("zz".repeat(32).parse::<StorageKey>().is_err());
+    }
+
+    #[test]
+    fn test_seal_round_trip_is_bound_to_key_and_record() {
+        let cipher = RecordCipher::new(&key(1));
+        let plaintext = br#"{"to_address":"0x0987654321098765432109876543210987654321"}"#;
+        let sealed = cipher.seal(b"request:1", plaintext).unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.windows(10).any(|w| w == b"0x09876543"));
+        assert_eq!(cipher.open(b"request:1", &sealed).unwrap(), plaintext);
+
+        // Fresh nonce per write
+        assert_ne!(cipher.seal(b"request:1", plaintext).unwrap(), sealed);
+
+        assert!(cipher.open(b"request:2", &sealed).is_err());
+        assert!(RecordCipher::new(&key(2))
+            .open(b"request:1", &sealed)
+            .is_err());
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(cipher.open(b"request:1", &tampered).is_err());
+        assert!(!is_sealed(plaintext));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_owner_only_refuses_shared_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("relayer.key");
+        fs::write(&path, "0xabc\n").unwrap();
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let err = read_owner_only(&path, "relayer key file").unwrap_err();
+        assert!(err.to_string().contains("mode 644"));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        let secret = read_owner_only(&path, "relayer key file").unwrap();
+        assert_eq!(secret.as_str(), "0xabc");
+
+        let slot = SecretSlot::default();
+        slot.set(secret);
+        let shared = slot.clone();
+        assert_eq!(format!("{:?}", shared), "SecretSlot(<redacted>)");
+        assert_eq!(shared.get().as_deref(), Some("0xabc"));
+        slot.wipe();
+        assert_eq!(shared.get(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_storage_key_file_must_be_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("storage.key");
+        fs::write(&path, format!("0x{}\n", "ab".repeat(32))).unwrap();
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        let err = StorageKey::from_file(&path).unwrap_err();
+        assert!(err.to_string().contains("storage key file"));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(StorageKey::from_file(&path).unwrap(), key(0xab));
+    }
+}
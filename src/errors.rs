@@ -0,0 +1,486 @@
+//! Typed relayer errors and their JSON-RPC representation.
+//!
+//! Every failure a handler can report is a [`RelayError`] variant with a fixed
+//! JSON-RPC code and a `data` object whose `reason` field is a stable,
+//! machine-readable slug (plus variant-specific details), so clients can branch
+//! on errors without parsing messages.
+//!
+//! Where the relayer knows more, `data` also names the offending request `field`, carries the
+//! revert `selector` and `args` of a failed simulation, and gives `retryAfterSeconds` for
+//! conditions that clear on their own.
+//!
+//! Codes in the `-42xx` range follow the relay spec's error table; the `-320xx` ones are
+//! server-side conditions that are safe to retry. [`RelayError::Internal`] is reserved for
+//! failures of the relayer itself (storage, serialization, configuration).
+
+use std::fmt;
+
+use jsonrpc_core::{Error, ErrorCode};
+use serde_json::{json, Map, Value};
+
+use crate::types::{FeeBound, IssuedQuote};
+
+/// Caller is not allowed to use the method (admin token missing or wrong)
+pub const UNAUTHORIZED: i64 = -4100;
+/// Fee payment offered is below the fee the relay costs
+pub const INSUFFICIENT_FEE: i64 = -4200;
+/// Authorization or Safe signatures do not recover to the expected signer
+pub const INVALID_SIGNATURE: i64 = -4201;
+/// Payment token is not accepted on the requested chain
+pub const UNSUPPORTED_PAYMENT_TOKEN: i64 = -4202;
+/// Quote the request was priced against is no longer valid
+pub const QUOTE_EXPIRED: i64 = -4204;
+/// Calldata or gas exceeds the relayer's per-transaction limit
+pub const TRANSACTION_TOO_LARGE: i64 = -4207;
+/// No request or transaction with the given id or hash is known
+pub const UNKNOWN_REQUEST: i64 = -4208;
+/// Requested payment type or capability is not offered
+pub const UNSUPPORTED_CAPABILITY: i64 = -4209;
+/// EIP-7702 authorization list could not be decoded or does not match the request
+pub const INVALID_AUTHORIZATION_LIST: i64 = -4210;
+/// Pre-relay simulation reverted
+pub const SIMULATION_FAILED: i64 = -4211;
+/// Same relay was already submitted within the duplicate window
+pub const DUPLICATE_REQUEST: i64 = -4212;
+/// Quoted fee falls outside the payment token's configured bounds
+pub const FEE_OUT_OF_BOUNDS: i64 = -4213;
+/// Calls were required to execute atomically, which the relayer cannot guarantee
+pub const UNSUPPORTED_ATOMICITY: i64 = -4214;
+/// Caller exceeded its request quota
+pub const QUOTA_EXCEEDED: i64 = -4290;
+/// Relayer is at capacity; retry later
+pub const BUSY: i64 = -32005;
+/// Request did not finish within the configured timeout; retry later
+pub const TIMEOUT: i64 = -32006;
+/// A readiness check failed; the relayer cannot accept relays yet
+pub const NOT_READY: i64 = -32007;
+/// Chain's mempool is rejecting transactions at the offered price; retry later
+pub const CHAIN_CONGESTED: i64 = -32008;
+/// Chain's node could not be reached or answered with an error; retry later
+pub const CHAIN_UNAVAILABLE: i64 = -32009;
+/// Node refused the relay transaction
+pub const BROADCAST_FAILED: i64 = -32010;
+
+/// `retryAfterSeconds` for a busy or timed-out relayer
+const RETRY_AFTER_BUSY_SECONDS: u64 = 1;
+/// `retryAfterSeconds` while the relayer or a chain's node is unavailable
+const RETRY_AFTER_UNAVAILABLE_SECONDS: u64 = 5;
+/// `retryAfterSeconds` for a congested chain, about a block on most networks
+const RETRY_AFTER_CONGESTED_SECONDS: u64 = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelayError {
+    /// Malformed or missing request fields; `field` is the request field at fault, in its
+    /// JSON spelling (`to`, `capabilities.payment.token`), when a single one is
+    InvalidParams {
+        message: String,
+        field: Option<String>,
+    },
+    /// Chain is not configured on this relayer
+    UnsupportedChain(String),
+    Unauthorized,
+    /// Fee payment (e.g. a permit's value) is below the relay's fee; amounts in the payment
+    /// token's smallest unit
+    InsufficientFee {
+        required: String,
+        offered: String,
+    },
+    InvalidSignature,
+    UnsupportedPaymentToken(String),
+    /// Quote valid until `expiry` (Unix seconds) has expired; carries a fresh quote for the
+    /// same chain and token to price the relay again with, when one could be issued
+    QuoteExpired {
+        expiry: u64,
+        requote: Option<Box<IssuedQuote>>,
+    },
+    /// Calldata (`bytes`) or gas (`gas`) over the configured limit
+    TransactionTooLarge {
+        unit: &'static str,
+        size: u64,
+        limit: u64,
+    },
+    /// Request id or transaction hash the relayer has no record of
+    UnknownRequest(String),
+    UnsupportedCapability(String),
+    /// Authorization list is malformed or signed for another chain or contract; carries what
+    /// was wrong with it
+    InvalidAuthorizationList(String),
+    /// Simulation reverted; carries the node's error text
+    SimulationFailed(String),
+    /// Same relay was submitted moments ago; carries the id of the earlier request
+    DuplicateRequest(String),
+    /// Wallet cannot cover the native fee (amounts in wei)
+    InsufficientBalance {
+        required: String,
+        available: String,
+    },
+    /// Quoted fee is outside the payment token's `feeBounds` (amounts in the token's smallest
+    /// unit)
+    FeeOutOfBounds {
+        fee: u64,
+        bound: FeeBound,
+        limit: u64,
+    },
+    /// Calls had to execute all-or-nothing, which the relay path cannot guarantee
+    UnsupportedAtomicity,
+    /// Quota is used up; carries the seconds until it resets
+    QuotaExceeded(u64),
+    /// Relayer is at capacity; retry later
+    Busy,
+    /// Handler did not finish within `request_timeout` (seconds); safe to retry
+    Timeout(u64),
+    /// Readiness checks that failed, by name (`storage`, `chains`, `signer`)
+    NotReady(Vec<String>),
+    /// Chain (by id) rejected the relay as underpriced or its mempool is full
+    ChainCongested(String),
+    /// Chain (by id) has no reachable node, or its node failed the call
+    ChainUnavailable(String),
+    /// Node rejected the relay transaction for the chain (by id); details are logged
+    BroadcastFailed(String),
+    /// Unexpected server-side failure; details are logged, not returned
+    Internal,
+}
+
+impl RelayError {
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        RelayError::InvalidParams {
+            message: message.into(),
+            field: None,
+        }
+    }
+
+    /// Invalid params blamed on the request field `field`
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        RelayError::InvalidParams {
+            message: message.into(),
+            field: Some(field.into()),
+        }
+    }
+
+    /// Invalid params for a request that did not deserialize; serde's "missing field `x`" and
+    /// "unknown field `x`" messages name the field
+    pub fn unparsable_params(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let field = message
+            .split_once("field `")
+            .and_then(|(_, rest)| rest.split_once('`'))
+            .map(|(field, _)| field.to_string());
+        RelayError::InvalidParams { message, field }
+    }
+
+    /// Seconds a client should wait before retrying, for failures that clear on their own
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            RelayError::QuotaExceeded(seconds) => Some(*seconds),
+            RelayError::Busy | RelayError::Timeout(_) => Some(RETRY_AFTER_BUSY_SECONDS),
+            RelayError::NotReady(_) | RelayError::ChainUnavailable(_) => {
+                Some(RETRY_AFTER_UNAVAILABLE_SECONDS)
+            }
+            RelayError::ChainCongested(_) => Some(RETRY_AFTER_CONGESTED_SECONDS),
+            _ => None,
+        }
+    }
+
+    /// JSON-RPC error code
+    pub fn code(&self) -> i64 {
+        match self {
+            RelayError::InvalidParams { .. }
+            | RelayError::UnsupportedChain(_)
+            | RelayError::InsufficientBalance { .. } => ErrorCode::InvalidParams.code(),
+            RelayError::Unauthorized => UNAUTHORIZED,
+            RelayError::InsufficientFee { .. } => INSUFFICIENT_FEE,
+            RelayError::InvalidSignature => INVALID_SIGNATURE,
+            RelayError::UnsupportedPaymentToken(_) => UNSUPPORTED_PAYMENT_TOKEN,
+            RelayError::QuoteExpired { .. } => QUOTE_EXPIRED,
+            RelayError::TransactionTooLarge { .. } => TRANSACTION_TOO_LARGE,
+            RelayError::UnknownRequest(_) => UNKNOWN_REQUEST,
+            RelayError::UnsupportedCapability(_) => UNSUPPORTED_CAPABILITY,
+            RelayError::InvalidAuthorizationList(_) => INVALID_AUTHORIZATION_LIST,
+            RelayError::SimulationFailed(_) => SIMULATION_FAILED,
+            RelayError::DuplicateRequest(_) => DUPLICATE_REQUEST,
+            RelayError::FeeOutOfBounds { .. } => FEE_OUT_OF_BOUNDS,
+            RelayError::UnsupportedAtomicity => UNSUPPORTED_ATOMICITY,
+            RelayError::QuotaExceeded(_) => QUOTA_EXCEEDED,
+            RelayError::Busy => BUSY,
+            RelayError::Timeout(_) => TIMEOUT,
+            RelayError::NotReady(_) => NOT_READY,
+            RelayError::ChainCongested(_) => CHAIN_CONGESTED,
+            RelayError::ChainUnavailable(_) => CHAIN_UNAVAILABLE,
+            RelayError::BroadcastFailed(_) => BROADCAST_FAILED,
+            RelayError::Internal => ErrorCode::InternalError.code(),
+        }
+    }
+
+    /// Stable machine-readable identifier, returned as `data.reason`
+    pub fn reason(&self) -> &'static str {
+        match self {
+            RelayError::InvalidParams { .. } => "invalid_params",
+            RelayError::UnsupportedChain(_) => "unsupported_chain",
+            RelayError::Unauthorized => "unauthorized",
+            RelayError::InsufficientFee { .. } => "insufficient_fee",
+            RelayError::InvalidSignature => "invalid_signature",
+            RelayError::UnsupportedPaymentToken(_) => "unsupported_payment_token",
+            RelayError::QuoteExpired { .. } => "quote_expired",
+            RelayError::TransactionTooLarge { .. } => "transaction_too_large",
+            RelayError::UnknownRequest(_) => "unknown_request",
+            RelayError::UnsupportedCapability(_) => "unsupported_capability",
+            RelayError::InvalidAuthorizationList(_) => "invalid_authorization_list",
+            RelayError::SimulationFailed(_) => "simulation_failed",
+            RelayError::DuplicateRequest(_) => "duplicate_request",
+            RelayError::InsufficientBalance { .. } => "insufficient_balance",
+            RelayError::FeeOutOfBounds { .. } => "fee_out_of_bounds",
+            RelayError::UnsupportedAtomicity => "unsupported_atomicity",
+            RelayError::QuotaExceeded(_) => "quota_exceeded",
+            RelayError::Busy => "busy",
+            RelayError::Timeout(_) => "timeout",
+            RelayError::NotReady(_) => "not_ready",
+            RelayError::ChainCongested(_) => "chain_congested",
+            RelayError::ChainUnavailable(_) => "chain_unavailable",
+            RelayError::BroadcastFailed(_) => "broadcast_failed",
+            RelayError::Internal => "internal",
+        }
+    }
+
+    fn data(&self) -> Value {
+        let mut data = json!({ "reason": self.reason() });
+        let details = match self {
+            RelayError::InvalidParams {
+                field: Some(field), ..
+            } => json!({ "field": field }),
+            RelayError::UnsupportedChain(chain_id)
+            | RelayError::ChainCongested(chain_id)
+            | RelayError::ChainUnavailable(chain_id)
+            | RelayError::BroadcastFailed(chain_id) => json!({ "chainId": chain_id }),
+            RelayError::InsufficientFee { required, offered } => {
+                json!({ "required": required, "offered": offered })
+            }
+            RelayError::UnsupportedPaymentToken(token) => json!({ "token": token }),
+            RelayError::QuoteExpired { expiry, requote } => match requote {
+                Some(quote) => json!({ "expiry": expiry, "requote": quote }),
+                None => json!({ "expiry": expiry }),
+            },
+            RelayError::TransactionTooLarge { unit, size, limit } => {
+                json!({ "unit": unit, "size": size, "limit": limit })
+            }
+            RelayError::UnknownRequest(id) => json!({ "id": id }),
+            RelayError::UnsupportedCapability(capability) => json!({ "capability": capability }),
+            RelayError::InvalidAuthorizationList(detail) => json!({ "detail": detail }),
+            RelayError::SimulationFailed(detail) => {
+                let mut details = json!({ "detail": detail });
+                if let (Some(details), Some(revert)) = (details.as_object_mut(), revert(detail)) {
+                    details.extend(revert);
+                }
+                details
+            }
+            RelayError::DuplicateRequest(id) => json!({ "requestId": id }),
+            RelayError::InsufficientBalance {
+                required,
+                available,
+            } => json!({ "required": required, "available": available }),
+            RelayError::FeeOutOfBounds { fee, bound, limit } => {
+                json!({ "fee": fee, "bound": bound, "limit": limit })
+            }
+            RelayError::Timeout(seconds) => json!({ "timeoutSeconds": seconds }),
+            RelayError::NotReady(failing) => json!({ "failing": failing }),
+            _ => Value::Null,
+        };
+        if let (Some(data), Value::Object(details)) = (data.as_object_mut(), details) {
+            data.extend(details);
+        }
+        if let (Some(data), Some(seconds)) = (data.as_object_mut(), self.retry_after()) {
+            data.insert("retryAfterSeconds".to_string(), json!(seconds));
+        }
+        data
+    }
+}
+
+/// Revert `selector` and `args` of the revert data a node quoted in its error message (the
+/// last `0x` hex run of at least four bytes). An `Error(string)` message is decoded; other
+/// arguments are listed as raw 32-byte words.
+fn revert(message: &str) -> Option<Map<String, Value>> {
+    let hex_run = message
+        .match_indices("0x")
+        .filter_map(|(at, _)| {
+            let digits = &message[at + 2..];
+            let len = digits.bytes().take_while(|b| b.is_ascii_hexdigit()).count();
+            (len >= 8 && len % 2 == 0).then(|| &digits[..len])
+        })
+        .last()?;
+    let bytes = hex::decode(hex_run).ok()?;
+    let (selector, payload) = bytes.split_first_chunk::<4>()?;
+    let words: Vec<&[u8]> = payload.chunks(32).collect();
+    let args = match *selector {
+        // Error(string): offset word, length word, then the message
+        [0x08, 0xc3, 0x79, 0xa0] => {
+            let len = words
+                .get(1)
+                .and_then(|word| word.get(24..))
+                .and_then(|tail| <[u8; 8]>::try_from(tail).ok())
+                .map(u64::from_be_bytes)? as usize;
+            let message = payload.get(64..64usize.checked_add(len)?)?;
+            vec![json!(String::from_utf8_lossy(message))]
+        }
+        _ => words
+            .iter()
+            .map(|word| json!(format!("0x{}", hex::encode(word))))
+            .collect(),
+    };
+    let mut revert = Map::new();
+    revert.insert(
+        "selector".to_string(),
+        json!(format!("0x{}", hex::encode(selector))),
+    );
+    revert.insert("args".to_string(), Value::Array(args));
+    Some(revert)
+}
+
+impl fmt::Display for RelayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelayError::InvalidParams { message, .. } => write!(f, "{}", message),
+            RelayError::UnsupportedChain(chain_id) => {
+                write!(f, "Unsupported chain ID: {}", chain_id)
+            }
+            RelayError::Unauthorized => write!(f, "Unauthorized"),
+            RelayError::InsufficientFee { .. } => write!(f, "Insufficient Fee"),
+            RelayError::InvalidSignature => write!(f, "Invalid Signature"),
+            RelayError::UnsupportedPaymentToken(_) => write!(f, "Unsupported Payment Token"),
+            RelayError::QuoteExpired { .. } => write!(f, "Quote Expired"),
+            RelayError::TransactionTooLarge { .. } => write!(f, "Transaction Too Large"),
+            RelayError::UnknownRequest(_) => write!(f, "Unknown Request"),
+            RelayError::UnsupportedCapability(_) => write!(f, "Unsupported Capability"),
+            RelayError::InvalidAuthorizationList(_) => write!(f, "Invalid Authorization List"),
+            RelayError::SimulationFailed(_) => write!(f, "Simulation Failed"),
+            RelayError::DuplicateRequest(_) => write!(f, "Duplicate Request"),
+            RelayError::InsufficientBalance { .. } => write!(f, "Insufficient balance"),
+            RelayError::FeeOutOfBounds { .. } => write!(f, "Fee Out Of Bounds"),
+            RelayError::UnsupportedAtomicity => write!(f, "Unsupported Atomicity"),
+            RelayError::QuotaExceeded(_) => write!(f, "Quota Exceeded"),
+            RelayError::Busy => write!(f, "Relayer Busy"),
+            RelayError::Timeout(_) => write!(f, "Request Timed Out"),
+            RelayError::NotReady(_) => write!(f, "Not Ready"),
+            RelayError::ChainCongested(_) => write!(f, "Chain Congested"),
+            RelayError::ChainUnavailable(_) => write!(f, "Chain Unavailable"),
+            RelayError::BroadcastFailed(_) => write!(f, "Broadcast Failed"),
+            RelayError::Internal => write!(f, "Internal error"),
+        }
+    }
+}
+
+impl std::error::Error for RelayError {}
+
+impl From<RelayError> for Error {
+    fn from(e: RelayError) -> Self {
+        Error {
+            code: ErrorCode::from(e.code()),
+            message: e.to_string(),
+            data: Some(e.data()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relay_error_maps_to_stable_code_and_data() {
+        let err: Error = RelayError::UnsupportedPaymentToken("0xabc".to_string()).into();
+        assert_eq!(err.code, ErrorCode::ServerError(UNSUPPORTED_PAYMENT_TOKEN));
+        assert_eq!(err.message, "Unsupported Payment Token");
+        let data = err.data.unwrap();
+        assert_eq!(data["reason"], "unsupported_payment_token");
+        assert_eq!(data["token"], "0xabc");
+
+        let err: Error = RelayError::UnsupportedChain("999".to_string()).into();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        assert_eq!(err.data.unwrap()["chainId"], "999");
+
+        let err: Error = RelayError::DuplicateRequest("abc".to_string()).into();
+        assert_eq!(err.code, ErrorCode::ServerError(DUPLICATE_REQUEST));
+        assert_eq!(err.data.unwrap()["requestId"], "abc");
+
+        let err: Error = RelayError::FeeOutOfBounds {
+            fee: 90,
+            bound: FeeBound::Max,
+            limit: 50,
+        }
+        .into();
+        assert_eq!(err.code, ErrorCode::ServerError(FEE_OUT_OF_BOUNDS));
+        let data = err.data.unwrap();
+        assert_eq!(data["reason"], "fee_out_of_bounds");
+        assert_eq!(data["bound"], "max");
+        assert_eq!(data["limit"], 50);
+
+        let err: Error = RelayError::Timeout(30).into();
+        assert_eq!(err.code, ErrorCode::ServerError(TIMEOUT));
+        assert_eq!(err.data.unwrap()["timeoutSeconds"], 30);
+
+        let err: Error = RelayError::NotReady(vec!["signer".to_string()]).into();
+        assert_eq!(err.code, ErrorCode::ServerError(NOT_READY));
+        assert_eq!(err.data.unwrap()["failing"], json!(["signer"]));
+
+        let err: Error = RelayError::TransactionTooLarge {
+            unit: "bytes",
+            size: 200_000,
+            limit: 131_072,
+        }
+        .into();
+        assert_eq!(err.code, ErrorCode::ServerError(TRANSACTION_TOO_LARGE));
+        let data = err.data.unwrap();
+        assert_eq!(data["reason"], "transaction_too_large");
+        assert_eq!(data["limit"], 131_072);
+
+        let err: Error = RelayError::ChainUnavailable("8453".to_string()).into();
+        assert_eq!(err.code, ErrorCode::ServerError(CHAIN_UNAVAILABLE));
+        assert_eq!(err.message, "Chain Unavailable");
+        assert_eq!(err.data.unwrap()["chainId"], "8453");
+
+        let err: Error = RelayError::Internal.into();
+        assert_eq!(err.code, ErrorCode::InternalError);
+        assert_eq!(err.data.unwrap(), json!({ "reason": "internal" }));
+    }
+
+    #[test]
+    fn test_error_data_names_field_revert_and_retry_delay() {
+        let err: Error = RelayError::invalid_field("to", "Invalid 'to' address").into();
+        assert_eq!(err.code, ErrorCode::InvalidParams);
+        assert_eq!(err.message, "Invalid 'to' address");
+        assert_eq!(
+            err.data.unwrap(),
+            json!({ "reason": "invalid_params", "field": "to" })
+        );
+        let err: Error = RelayError::invalid_params("Missing params").into();
+        assert_eq!(err.data.unwrap(), json!({ "reason": "invalid_params" }));
+
+        // Error(string) with message "nope"
+        let revert = format!(
+            "execution reverted, data: \"0x08c379a0{:064x}{:064x}{:0<64}\"",
+            32,
+            4,
+            hex::encode("nope")
+        );
+        let data = Error::from(RelayError::SimulationFailed(revert))
+            .data
+            .unwrap();
+        assert_eq!(data["selector"], "0x08c379a0");
+        assert_eq!(data["args"], json!(["nope"]));
+
+        let custom = "execution reverted: 0xdeadbeef00000000000000000000000000000000000000000000000000000000000000ff";
+        let data = Error::from(RelayError::SimulationFailed(custom.to_string()))
+            .data
+            .unwrap();
+        assert_eq!(data["selector"], "0xdeadbeef");
+        assert_eq!(data["args"][0], format!("0x{:064x}", 0xff));
+
+        let data = Error::from(RelayError::SimulationFailed("out of gas".to_string()))
+            .data
+            .unwrap();
+        assert!(data.get("selector").is_none());
+
+        let data = Error::from(RelayError::QuotaExceeded(3600)).data.unwrap();
+        assert_eq!(data["retryAfterSeconds"], 3600);
+        assert_eq!(RelayError::Busy.retry_after(), Some(1));
+        assert_eq!(RelayError::Internal.retry_after(), None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Decoding of receipt logs against operator-registered event ABIs.
+//!
+//! config.json (or a runtime chain registration) may list event ABIs per contract under
+//! `eventAbis`. When a receipt is rendered, each log of such a contract whose first topic is
+//! the selector of one of its events is decoded into the event's name and arguments with
+//! [`decode_log`], so clients do not need their own decoding layer.
+//!
+//! Parameters may be elementary types or one-dimensional dynamic arrays of static elementary
+//! types; logs of events with tuple, fixed-size array or nested array parameters are left
+//! undecoded. Indexed dynamic values are only logged as their keccak256 hash, which is given
+//! as-is.
+
+use alloy::{
+    hex,
+    json_abi::Event,
+    primitives::{Address, B256, I256, U256},
+};
+use serde_json::Value;
+
+use crate::types::DecodedLog;
+
+/// Decode a log with the first of `events` it matches, if any
+pub fn decode_log(events: &[Event], topics: &[B256], data: &[u8]) -> Option<DecodedLog> {
+    let (selector, indexed) = topics.split_first()?;
+    events
+        .iter()
+        .filter(|event| !event.anonymous && event.selector() == *selector)
+        .find_map(|event| decode_event(event, indexed, data))
+}
+
+fn decode_event(event: &Event, topics: &[B256], data: &[u8]) -> Option<DecodedLog> {
+    if event.inputs.iter().filter(|param| param.indexed).count() != topics.len() {
+        return None;
+    }
+    let mut topics = topics.iter();
+    let mut head = 0usize;
+    let mut args = serde_json::Map::new();
+    for (position, param) in event.inputs.iter().enumerate() {
+        let dynamic = is_dynamic(&param.ty)?;
+        let value = if param.indexed {
+            let topic = topics.next()?;
+            if dynamic {
+                Value::String(topic.to_string())
+            } else {
+                decode_word(&param.ty, topic.as_slice())?
+            }
+        } else {
+            let value = decode_data(&param.ty, data, head)?;
+            head = head.checked_add(32)?;
+            value
+        };
+        let name = if param.name.is_empty() {
+            position.to_string()
+        } else {
+            param.name.clone()
+        };
+        args.insert(name, value);
+    }
+    Some(DecodedLog {
+        name: event.name.clone(),
+        signature: event.signature(),
+        args,
+    })
+}
+
+/// Whether values of `ty` are encoded out of line, or `None` for unsupported types
+fn is_dynamic(ty: &str) -> Option<bool> {
+    match ty {
+        "string" | "bytes" => Some(true),
+        _ => match ty.strip_suffix("[]") {
+            Some(element) => is_static_elementary(element).then_some(true),
+            None => is_static_elementary(ty).then_some(false),
+        },
+    }
+}
+
+fn is_static_elementary(ty: &str) -> bool {
+    let bits = |digits: &str| {
+        digits
+            .parse::<usize>()
+            .is_ok_and(|n| n % 8 == 0 && (8..=256).contains(&n))
+    };
+    match ty {
+        "address" | "bool" => true,
+        _ => {
+            if let Some(digits) = ty.strip_prefix("uint").or_else(|| ty.strip_prefix("int")) {
+                bits(digits)
+            } else if let Some(digits) = ty.strip_prefix("bytes") {
+                digits.parse::<usize>().is_ok_and(|n| (1..=32).contains(&n))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+/// Decode a static elementary value from its 32-byte word
+fn decode_word(ty: &str, word: &[u8]) -> Option<Value> {
+    if word.len() != 32 {
+        return None;
+    }
+    let value = match ty {
+        "address" => Value::String(Address::from_slice(&word[12..]).to_checksum(None)),
+        "bool" => Value::Bool(word[31] != 0),
+        _ if ty.starts_with("uint") => Value::String(U256::from_be_slice(word).to_string()),
+        _ if ty.starts_with("int") => {
+            Value::String(I256::from_raw(U256::from_be_slice(word)).to_string())
+        }
+        _ => {
+            let size = ty.strip_prefix("bytes")?.parse::<usize>().ok()?;
+            Value::String(hex::encode_prefixed(word.get(..size)?))
+        }
+    };
+    Some(value)
+}
+
+/// Decode the non-indexed value whose head word is at `head` in the log data
+fn decode_data(ty: &str, data: &[u8], head: usize) -> Option<Value> {
+    let word_at = |offset: usize| data.get(offset..offset.checked_add(32)?);
+    let usize_at = |offset: usize| usize::try_from(U256::from_be_slice(word_at(offset)?)).ok();
+    if !is_dynamic(ty)? {
+        return decode_word(ty, word_at(head)?);
+    }
+
+    let offset = usize_at(head)?;
+    let len = usize_at(offset)?;
+    let start = offset.checked_add(32)?;
+    match ty {
+        "string" => {
+            let bytes = data.get(start..start.checked_add(len)?)?;
+            Some(Value::String(String::from_utf8_lossy(bytes).into_owned()))
+        }
+        "bytes" => {
+            let bytes = data.get(start..start.checked_add(len)?)?;
+            Some(Value::String(hex::encode_prefixed(bytes)))
+        }
+        _ => {
+            let element = ty.strip_suffix("[]")?;
+            (0..len)
+                .map(|i| decode_word(element, word_at(start.checked_add(i.checked_mul(32)?)?)?))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::keccak256;
+
+    fn events(abi: &str) -> Vec<Event> {
+        serde_json::from_str::<alloy::json_abi::JsonAbi>(abi)
+            .unwrap()
+            .events()
+            .cloned()
+            .collect()
+    }
+
+    fn word(value: u64) -> [u8; 32] {
+        U256::from(value).to_be_bytes::<32>()
+    }
+
+    #[test]
+    fn test_decode_transfer() {
+        let abi = events(
+            r#"[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+                {"name":"from","type":"address","indexed":true},
+                {"name":"to","type":"address","indexed":true},
+                {"name":"value","type":"uint256","indexed":false}]}]"#,
+        );
+        let from = Address::repeat_byte(0x11);
+        let to = Address::repeat_byte(0x22);
+        let topics = [
+            keccak256("Transfer(address,address,uint256)"),
+            from.into_word(),
+            to.into_word(),
+        ];
+        let decoded = decode_log(&abi, &topics, &word(1_000_000)).unwrap();
+        assert_eq!(decoded.name, "Transfer");
+        assert_eq!(decoded.signature, "Transfer(address,address,uint256)");
+        assert_eq!(decoded.args["from"], from.to_checksum(None));
+        assert_eq!(decoded.args["to"], to.to_checksum(None));
+        assert_eq!(decoded.args["value"], "1000000");
+
+        // A log with a different topic count is another event sharing the signature
+        assert!(decode_log(&abi, &topics[..2], &word(1)).is_none());
+        assert!(decode_log(&abi, &[B256::ZERO], &word(1)).is_none());
+    }
+
+    #[test]
+    fn test_decode_dynamic_and_signed_values() {
+        let abi = events(
+            r#"[{"type":"event","name":"Noted","anonymous":false,"inputs":[
+                {"name":"tag","type":"string","indexed":true},
+                {"name":"","type":"int64","indexed":false},
+                {"name":"note","type":"string","indexed":false},
+                {"name":"ids","type":"uint32[]","indexed":false},
+                {"name":"flag","type":"bytes4","indexed":false}]}]"#,
+        );
+        let tag = keccak256("tag");
+        let topics = [keccak256("Noted(string,int64,string,uint32[],bytes4)"), tag];
+        let mut data = Vec::new();
+        data.extend_from_slice(&I256::try_from(-5).unwrap().to_be_bytes::<32>());
+        data.extend_from_slice(&word(4 * 32));
+        data.extend_from_slice(&word(6 * 32));
+        let mut flag = [0u8; 32];
+        flag[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        data.extend_from_slice(&flag);
+        data.extend_from_slice(&word(5));
+        let mut note = [0u8; 32];
+        note[..5].copy_from_slice(b"hello");
+        data.extend_from_slice(&note);
+        data.extend_from_slice(&word(2));
+        data.extend_from_slice(&word(7));
+        data.extend_from_slice(&word(9));
+
+        let decoded = decode_log(&abi, &topics, &data).unwrap();
+        assert_eq!(decoded.args["tag"], tag.to_string());
+        assert_eq!(decoded.args["1"], "-5");
+        assert_eq!(decoded.args["note"], "hello");
+        assert_eq!(decoded.args["ids"], serde_json::json!(["7", "9"]));
+        assert_eq!(decoded.args["flag"], "0xdeadbeef");
+
+        // Truncated data leaves the log undecoded
+        assert!(decode_log(&abi, &topics, &data[..5 * 32]).is_none());
+    }
+}
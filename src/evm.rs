@@ -0,0 +1,443 @@
+//! In-process simulation with an embedded EVM.
+//!
+//! [`LocalEvmClient`] wraps another [`ChainClient`] and answers `eth_call`,
+//! `eth_estimateGas` and `eth_simulateV1` by executing the call in [`revm`] instead of on the node. In fork mode
+//! accounts and storage the call touches are read from the wrapped client as needed; without
+//! it the EVM starts empty, so CI can simulate against synthetic state only. Accounts under
+//! `localSimulation.accounts` are seeded on top in both modes. Every other chain call goes to
+//! the wrapped client unchanged.
+
+use std::sync::Arc;
+
+use alloy::{
+    primitives::{keccak256, Address, Bytes, B256, U256},
+    rpc::types::{FeeHistory, TransactionRequest},
+};
+use async_trait::async_trait;
+use revm::{
+    db::{CacheDB, DatabaseRef},
+    primitives::{AccountInfo, Bytecode, ExecutionResult, TxKind, KECCAK_EMPTY},
+    Evm,
+};
+use tokio::runtime::Handle;
+
+use crate::{
+    chain::{CallSimulation, ChainClient, TxLog, TxReceipt, TxTransfer},
+    signer::RelayerSigner,
+    types::LocalEvmSettings,
+};
+
+/// Chain state backing a simulation: read through from the node in fork mode, empty otherwise
+struct ForkDb {
+    fork: Option<(Arc<dyn ChainClient>, String, Handle)>,
+}
+
+impl ForkDb {
+    /// Run a chain read from the blocking simulation thread
+    fn read<T>(
+        &self,
+        read: impl FnOnce(
+            &Arc<dyn ChainClient>,
+            &str,
+        ) -> futures::future::BoxFuture<'static, Result<T, String>>,
+    ) -> Option<Result<T, String>> {
+        let (client, rpc_url, handle) = self.fork.as_ref()?;
+        Some(handle.block_on(read(client, rpc_url)))
+    }
+}
+
+impl DatabaseRef for ForkDb {
+    type Error = String;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let Some(account) = self.read(|client, rpc_url| {
+            let (client, rpc_url) = (client.clone(), rpc_url.to_string());
+            Box::pin(async move {
+                let balance = client.balance(&rpc_url, address).await?;
+                let nonce = client.transaction_count(&rpc_url, address).await?;
+                let code = client.code(&rpc_url, address).await?;
+                Ok((balance, nonce, code))
+            })
+        }) else {
+            return Ok(None);
+        };
+        let (balance, nonce, code) = account?;
+        let code_hash = if code.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            keccak256(&code)
+        };
+        Ok(Some(AccountInfo::new(
+            balance,
+            nonce,
+            code_hash,
+            Bytecode::new_raw(code),
+        )))
+    }
+
+    // Code always arrives with its account in `basic_ref`, so it is never looked up by hash
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Err(format!("Unknown code hash {}", code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.read(|client, rpc_url| {
+            let (client, rpc_url) = (client.clone(), rpc_url.to_string());
+            Box::pin(async move { client.storage_at(&rpc_url, address, index).await })
+        })
+        .unwrap_or(Ok(U256::ZERO))
+    }
+
+    // Block hashes are not fetched; BLOCKHASH sees the same stand-in revm's empty database
+    // uses
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        Ok(keccak256(number.to_string().as_bytes()))
+    }
+}
+
+/// Outcome of one simulated call
+struct Simulation {
+    call: CallSimulation,
+    /// Gas the call needs, refunds included, since they are only paid back after execution
+    gas: u64,
+}
+
+/// [`ChainClient`] that simulates calls in an embedded EVM; see the module docs
+pub struct LocalEvmClient {
+    inner: Arc<dyn ChainClient>,
+    settings: Arc<LocalEvmSettings>,
+}
+
+impl LocalEvmClient {
+    pub fn new(inner: Arc<dyn ChainClient>, settings: LocalEvmSettings) -> Self {
+        Self {
+            inner,
+            settings: Arc::new(settings),
+        }
+    }
+
+    async fn simulate(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Simulation, String> {
+        let (chain_id, block_number) = if self.settings.fork {
+            (
+                self.inner.chain_id(rpc_url).await?,
+                self.inner.block_number(rpc_url).await?,
+            )
+        } else {
+            (1, 1)
+        };
+        let fork = self
+            .settings
+            .fork
+            .then(|| (self.inner.clone(), rpc_url.to_string(), Handle::current()));
+        let settings = self.settings.clone();
+
+        // revm reads state synchronously, so forked reads block this thread on the runtime
+        tokio::task::spawn_blocking(move || {
+            let mut db = CacheDB::new(ForkDb { fork });
+            for (address, account) in &settings.accounts {
+                let code_hash = if account.code.is_empty() {
+                    KECCAK_EMPTY
+                } else {
+                    keccak256(&account.code)
+                };
+                db.insert_account_info(
+                    *address,
+                    AccountInfo::new(
+                        account.balance,
+                        account.nonce,
+                        code_hash,
+                        Bytecode::new_raw(account.code.clone()),
+                    ),
+                );
+                for (slot, value) in &account.storage {
+                    db.insert_account_storage(*address, *slot, *value)?;
+                }
+            }
+
+            let mut evm = Evm::builder()
+                .with_db(db)
+                .modify_cfg_env(|cfg| {
+                    cfg.chain_id = chain_id;
+                    // Like eth_call: no fee, no balance or sender checks
+                    cfg.disable_balance_check = true;
+                    cfg.disable_base_fee = true;
+                    cfg.disable_eip3607 = true;
+                })
+                .modify_block_env(|block| {
+                    block.number = U256::from(block_number);
+                    block.timestamp = U256::from(chrono::Utc::now().timestamp().max(0));
+                    block.gas_limit = U256::from(settings.gas_limit);
+                    block.basefee = U256::ZERO;
+                })
+                .modify_tx_env(|tx| {
+                    tx.caller = Address::ZERO;
+                    tx.transact_to = TxKind::Call(to);
+                    tx.data = input;
+                    tx.value = value;
+                    tx.gas_limit = settings.gas_limit;
+                    tx.gas_price = U256::ZERO;
+                    tx.nonce = None;
+                })
+                .build();
+            let result = evm
+                .transact()
+                .map_err(|e| format!("Local EVM error: {}", e))?
+                .result;
+            Ok(match result {
+                ExecutionResult::Success {
+                    gas_used,
+                    gas_refunded,
+                    output,
+                    logs,
+                    ..
+                } => Simulation {
+                    call: CallSimulation {
+                        success: true,
+                        output: output.into_data(),
+                        gas_used,
+                        logs: logs
+                            .into_iter()
+                            .map(|log| TxLog {
+                                address: log.address,
+                                topics: log.data.topics().to_vec(),
+                                data: log.data.data,
+                            })
+                            .collect(),
+                    },
+                    gas: gas_used + gas_refunded,
+                },
+                ExecutionResult::Revert { gas_used, output } => Simulation {
+                    call: CallSimulation {
+                        success: false,
+                        output,
+                        gas_used,
+                        logs: Vec::new(),
+                    },
+                    gas: gas_used,
+                },
+                ExecutionResult::Halt { reason, .. } => {
+                    return Err(format!("execution halted: {:?}", reason))
+                }
+            })
+        })
+        .await
+        .map_err(|e| format!("Local EVM task failed: {}", e))?
+    }
+}
+
+#[async_trait]
+impl ChainClient for LocalEvmClient {
+    async fn gas_price(&self, rpc_url: &str) -> Result<u128, String> {
+        self.inner.gas_price(rpc_url).await
+    }
+
+    async fn blob_base_fee(&self, rpc_url: &str) -> Result<u128, String> {
+        self.inner.blob_base_fee(rpc_url).await
+    }
+
+    async fn base_fee(&self, rpc_url: &str) -> Result<Option<u128>, String> {
+        self.inner.base_fee(rpc_url).await
+    }
+
+    async fn balance(&self, rpc_url: &str, address: Address) -> Result<U256, String> {
+        self.inner.balance(rpc_url, address).await
+    }
+
+    async fn code(&self, rpc_url: &str, address: Address) -> Result<Bytes, String> {
+        self.inner.code(rpc_url, address).await
+    }
+
+    async fn storage_at(
+        &self,
+        rpc_url: &str,
+        address: Address,
+        slot: U256,
+    ) -> Result<U256, String> {
+        self.inner.storage_at(rpc_url, address, slot).await
+    }
+
+    async fn call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Bytes, String> {
+        let simulation = self
+            .simulate(rpc_url, to, input, value)
+            .await
+            .map_err(|e| format!("eth_call failed: {}", e))?;
+        if !simulation.call.success {
+            return Err(format!(
+                "eth_call failed: execution reverted: {}",
+                simulation.call.output
+            ));
+        }
+        Ok(simulation.call.output)
+    }
+
+    async fn estimate_gas(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<u64, String> {
+        let simulation = self
+            .simulate(rpc_url, to, input, value)
+            .await
+            .map_err(|e| format!("Gas estimation failed: {}", e))?;
+        if !simulation.call.success {
+            return Err(format!(
+                "Gas estimation failed: execution reverted: {}",
+                simulation.call.output
+            ));
+        }
+        Ok(simulation.gas)
+    }
+
+    async fn simulate_call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<CallSimulation, String> {
+        let simulation = self
+            .simulate(rpc_url, to, input, value)
+            .await
+            .map_err(|e| format!("eth_simulateV1 failed: {}", e))?;
+        Ok(simulation.call)
+    }
+
+    async fn transaction_count(&self, rpc_url: &str, address: Address) -> Result<u64, String> {
+        self.inner.transaction_count(rpc_url, address).await
+    }
+
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String> {
+        self.inner.pending_transaction_count(rpc_url, address).await
+    }
+
+    async fn send_transaction(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String> {
+        self.inner.send_transaction(rpc_url, signer, tx).await
+    }
+
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String> {
+        self.inner.send_raw_transaction(rpc_url, raw).await
+    }
+
+    async fn receipt(&self, rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String> {
+        self.inner.receipt(rpc_url, hash).await
+    }
+
+    async fn receipts(
+        &self,
+        rpc_url: &str,
+        hashes: &[B256],
+    ) -> Result<Vec<Option<TxReceipt>>, String> {
+        self.inner.receipts(rpc_url, hashes).await
+    }
+
+    async fn transfer(&self, rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String> {
+        self.inner.transfer(rpc_url, hash).await
+    }
+
+    async fn replay_transaction(
+        &self,
+        rpc_url: &str,
+        hash: B256,
+        block_number: u64,
+    ) -> Result<Bytes, String> {
+        self.inner
+            .replay_transaction(rpc_url, hash, block_number)
+            .await
+    }
+
+    async fn block_number(&self, rpc_url: &str) -> Result<u64, String> {
+        self.inner.block_number(rpc_url).await
+    }
+
+    async fn fee_history(
+        &self,
+        rpc_url: &str,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String> {
+        self.inner
+            .fee_history(rpc_url, block_count, reward_percentiles)
+            .await
+    }
+
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String> {
+        self.inner.chain_id(rpc_url).await
+    }
+
+    async fn next_block(&self, rpc_url: &str) -> u64 {
+        self.inner.next_block(rpc_url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chain::MockChainClient, types::LocalAccount};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_synthetic_state_runs_seeded_code() {
+        // Stores 42 at slot 1, then returns the 32-byte word in slot 0
+        let code: Bytes = "0x602a60015560005460005260206000f3".parse().unwrap();
+        let contract = Address::repeat_byte(0xc0);
+        let settings = LocalEvmSettings {
+            fork: false,
+            accounts: [(
+                contract,
+                LocalAccount {
+                    code,
+                    storage: [(U256::ZERO, U256::from(7))].into_iter().collect(),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let evm = LocalEvmClient::new(Arc::new(MockChainClient::new()), settings);
+
+        let output = evm
+            .call("mock", contract, Bytes::new(), U256::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(U256::from_be_slice(&output), U256::from(7));
+        let gas = evm
+            .estimate_gas("mock", contract, Bytes::new(), U256::ZERO)
+            .await
+            .unwrap();
+        assert!(
+            gas > 21_000 + 20_000,
+            "SSTORE to a fresh slot is costed: {}",
+            gas
+        );
+
+        // No code, so the call succeeds with empty output
+        assert!(evm
+            .call("mock", Address::repeat_byte(0x01), Bytes::new(), U256::ZERO)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
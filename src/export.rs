@@ -0,0 +1,342 @@
+//! Accounting export of mined relays for finance pipelines.
+//!
+//! Every request whose transaction was mined (completed, or failed by an on-chain revert)
+//! within a completion-time range becomes one row giving what the relay cost in gas, what
+//! it was priced at and what was collected for it. Rows are read from the stored requests
+//! and their receipts, so the export works against a live database or a copy of one.
+//!
+//! Only CSV is produced: Parquet would need an Arrow writer this build does not ship, and
+//! is rejected when asked for.
+
+use std::{fmt, io::Write, str::FromStr};
+
+use alloy::primitives::{b256, Address, B256, U256};
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::{
+    storage::Storage,
+    types::{Receipt, RelayerRequest, RequestStatus},
+};
+
+/// Columns of an accounting export, in order
+pub const COLUMNS: [&str; 15] = [
+    "id",
+    "chain_id",
+    "status",
+    "wallet",
+    "relayer",
+    "transaction_hash",
+    "payment_type",
+    "payment_token",
+    "gas_used",
+    "effective_gas_price",
+    "gas_cost_wei",
+    "fee_priced_wei",
+    "fee_collected",
+    "created_at",
+    "mined_at",
+];
+
+/// keccak256("Transfer(address,address,uint256)")
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// File format of an accounting export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" => Err("parquet export is not supported by this build; use csv".to_string()),
+            other => Err(format!("unknown export format '{}' (expected csv)", other)),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// Parse a range bound given as an RFC 3339 time or a `YYYY-MM-DD` date, which stands for
+/// midnight UTC
+pub fn parse_bound(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(Default::default()).and_utc())
+        .map_err(|_| {
+            format!(
+                "'{}' is neither an RFC 3339 time nor a YYYY-MM-DD date",
+                value
+            )
+        })
+}
+
+/// Mined requests that completed at or after `from` and before `to`, oldest first, archived
+/// ones included
+pub fn accounting_requests(
+    storage: &Storage,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<RelayerRequest>> {
+    let mut requests = Vec::new();
+    let mut collect = |req: RelayerRequest| {
+        let mined = match req.status {
+            RequestStatus::Completed => true,
+            RequestStatus::Failed => req.mined_at.is_some(),
+            _ => false,
+        };
+        let at = completed_at(&req);
+        if mined && from.is_none_or(|from| at >= from) && to.is_none_or(|to| at < to) {
+            requests.push(req);
+        }
+        Ok(())
+    };
+    storage.for_each_request(&mut collect)?;
+    storage.for_each_archived_request(&mut collect)?;
+    requests.sort_by_key(completed_at);
+    Ok(requests)
+}
+
+/// Write the accounting rows of requests completed in `[from, to)` to `out`, returning how
+/// many were written
+pub async fn export_accounting(
+    storage: &Storage,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    format: ExportFormat,
+    out: &mut impl Write,
+) -> Result<usize> {
+    let requests = accounting_requests(storage, from, to)?;
+    match format {
+        ExportFormat::Csv => {
+            write_csv_record(out, COLUMNS)?;
+            for req in &requests {
+                let receipt = storage.get_receipt(req.id).await?;
+                write_csv_record(out, accounting_row(req, receipt.as_ref()))?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(requests.len())
+}
+
+fn completed_at(req: &RelayerRequest) -> DateTime<Utc> {
+    req.mined_at.unwrap_or(req.updated_at)
+}
+
+/// One export row. Gas figures come from the stored receipt; requests mined before
+/// receipts were kept leave them blank.
+fn accounting_row(req: &RelayerRequest, receipt: Option<&Receipt>) -> [String; 15] {
+    let hex = |value: Option<&String>| value.and_then(|v| parse_hex(v));
+    let gas_used = receipt.and_then(|r| parse_hex(&r.gas_used));
+    let gas_price = receipt.and_then(|r| hex(r.effective_gas_price.as_ref()));
+    let blob_cost = req
+        .blob_gas_used
+        .map(U256::from)
+        .zip(hex(req.blob_gas_price.as_ref()))
+        .map_or(U256::ZERO, |(used, price)| used.saturating_mul(price));
+    let gas_cost = gas_used
+        .zip(gas_price)
+        .map(|(used, price)| used.saturating_mul(price).saturating_add(blob_cost));
+    let fee = req.fee.as_ref();
+    let collected = fee
+        .filter(|fee| matches!(fee.payment_type.as_str(), "erc20" | "permit"))
+        .zip(receipt)
+        .map(|(fee, receipt)| collected_tokens(receipt, &fee.token, &req.from_address));
+
+    let text = |value: Option<U256>| value.map(|v| v.to_string()).unwrap_or_default();
+    [
+        req.id.to_string(),
+        req.chain_id.to_string(),
+        match req.status {
+            RequestStatus::Completed => "completed",
+            _ => "failed",
+        }
+        .to_string(),
+        req.to_address.clone(),
+        req.from_address.clone(),
+        req.transaction_hash.clone().unwrap_or_default(),
+        fee.map(|fee| fee.payment_type.clone()).unwrap_or_default(),
+        fee.map(|fee| fee.token.clone()).unwrap_or_default(),
+        text(gas_used),
+        text(gas_price),
+        text(gas_cost),
+        fee.map(|fee| fee.priced_wei.clone()).unwrap_or_default(),
+        text(collected),
+        req.created_at.to_rfc3339(),
+        req.mined_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+    ]
+}
+
+/// Sum of the `token` Transfer logs in `receipt` paid to `collector`
+pub(crate) fn collected_tokens(receipt: &Receipt, token: &str, collector: &str) -> U256 {
+    let collector = collector.parse::<Address>().ok();
+    receipt
+        .logs
+        .iter()
+        .filter(|log| log.address.eq_ignore_ascii_case(token))
+        .filter_map(|log| {
+            let topics = log
+                .topics
+                .iter()
+                .map(|topic| topic.parse::<B256>().ok())
+                .collect::<Option<Vec<_>>>()?;
+            match topics.as_slice() {
+                [selector, _, to]
+                    if *selector == TRANSFER_TOPIC
+                        && Some(Address::from_word(*to)) == collector =>
+                {
+                    parse_hex(&log.data)
+                }
+                _ => None,
+            }
+        })
+        .fold(U256::ZERO, |total, value| total.saturating_add(value))
+}
+
+fn parse_hex(value: &str) -> Option<U256> {
+    let digits = value.trim_start_matches("0x");
+    if digits.is_empty() {
+        return None;
+    }
+    U256::from_str_radix(digits, 16).ok()
+}
+
+fn write_csv_record<S: AsRef<str>>(
+    out: &mut impl Write,
+    fields: impl IntoIterator<Item = S>,
+) -> std::io::Result<()> {
+    let line = fields
+        .into_iter()
+        .map(|field| csv_field(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(out, "{}", line)
+}
+
+/// Quote a field that contains a delimiter, quote or line break, per RFC 4180
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Log, RelayFee};
+
+    #[test]
+    fn test_export_format_rejects_parquet() {
+        assert_eq!("CSV".parse::<ExportFormat>(), Ok(ExportFormat::Csv));
+        assert!("parquet"
+            .parse::<ExportFormat>()
+            .unwrap_err()
+            .contains("not supported"));
+        assert!("xlsx".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_parse_bound() {
+        assert_eq!(
+            parse_bound("2026-03-01").unwrap().to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_bound("2026-03-01T12:00:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2026-03-01T10:00:00+00:00"
+        );
+        assert!(parse_bound("March 1st").is_err());
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("0xabc"), "0xabc");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_collected_tokens_sums_transfers_to_collector() {
+        let token = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let collector = Address::repeat_byte(0xcc);
+        let transfer = |token: &str, to: Address, value: u64| Log {
+            address: token.to_string(),
+            topics: vec![
+                TRANSFER_TOPIC.to_string(),
+                Address::repeat_byte(0x11).into_word().to_string(),
+                to.into_word().to_string(),
+            ],
+            data: B256::from(U256::from(value)).to_string(),
+            decoded: None,
+        };
+        let receipt = Receipt {
+            logs: vec![
+                transfer(&token.to_lowercase(), collector, 700),
+                transfer(token, collector, 300),
+                transfer(token, Address::repeat_byte(0x22), 5_000),
+                transfer("0x0000000000000000000000000000000000000001", collector, 9),
+            ],
+            status: "0x1".to_string(),
+            block_hash: String::new(),
+            block_number: "0x1".to_string(),
+            gas_used: "0x5208".to_string(),
+            effective_gas_price: Some("0x3b9aca00".to_string()),
+            transaction_hash: String::new(),
+            chain_id: "1".to_string(),
+            blob_gas_used: None,
+            blob_gas_price: None,
+        };
+        assert_eq!(
+            collected_tokens(&receipt, token, &collector.to_string()),
+            U256::from(1_000)
+        );
+
+        let mut req: RelayerRequest = serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4(),
+            "from_address": collector.to_string(),
+            "to_address": "0x2222222222222222222222222222222222222222",
+            "amount": "0",
+            "gas_limit": 21000,
+            "gas_price": "0x3b9aca00",
+            "data": null,
+            "nonce": 0,
+            "chain_id": 1,
+            "transaction_hash": "0xfeed",
+            "status": "Completed",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:01:00Z",
+            "error_message": null,
+        }))
+        .unwrap();
+        req.fee = Some(RelayFee {
+            payment_type: "erc20".to_string(),
+            token: token.to_string(),
+            priced_wei: "21000000000000".to_string(),
+        });
+        let row = accounting_row(&req, Some(&receipt));
+        assert_eq!(row[8], "21000");
+        assert_eq!(row[9], "1000000000");
+        assert_eq!(row[10], "21000000000000");
+        assert_eq!(row[12], "1000");
+        assert_eq!(row[14], "");
+    }
+}
@@ -1,9 +1,58 @@
+#[cfg(feature = "server")]
+pub mod attestation;
+#[cfg(feature = "server")]
+pub mod callback;
+#[cfg(feature = "server")]
+pub mod chain;
+#[cfg(feature = "server")]
+pub mod cli;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "server")]
 pub mod config;
+#[cfg(feature = "server")]
+pub mod encryption;
+pub mod errors;
+pub mod events;
+#[cfg(feature = "local-evm")]
+pub mod evm;
+#[cfg(feature = "server")]
+pub mod export;
+#[cfg(feature = "client")]
+pub mod loadtest;
+pub mod multicall;
+pub mod openrpc;
+pub mod params;
+#[cfg(feature = "server")]
+pub mod preflight;
+#[cfg(feature = "server")]
+pub mod recording;
+#[cfg(feature = "server")]
+pub mod registry;
+#[cfg(feature = "server")]
 pub mod rpc;
+#[cfg(feature = "server")]
+pub mod safe;
+#[cfg(feature = "server")]
+pub mod signer;
+#[cfg(feature = "server")]
 pub mod storage;
+#[cfg(feature = "server")]
+pub mod systemd;
+#[cfg(feature = "sentry")]
+pub mod telemetry;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
 pub mod types;
+#[cfg(feature = "server")]
+pub mod ws;
 
+#[cfg(feature = "client")]
+pub use client::RelayxClient;
+#[cfg(feature = "server")]
 pub use config::Config;
-pub use rpc::RpcServer;
+#[cfg(feature = "server")]
+pub use rpc::{RpcServer, ServerHandle};
+#[cfg(feature = "server")]
 pub use storage::Storage;
 pub use types::*;
@@ -0,0 +1,284 @@
+//! Load generation behind `relayx loadtest`: fires relayer_sendTransaction,
+//! relayer_getStatus and relayer_getFeeData at fixed rates against a running instance and
+//! reports per-method latency percentiles and error rates.
+//!
+//! Requests are issued open-loop: each method is started on its own schedule whether or not
+//! earlier calls have answered, so a slow relayer shows up as rising latency rather than as a
+//! lower request rate. Calls that would exceed `max_in_flight` are counted as dropped.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::Semaphore, task::JoinSet, time::MissedTickBehavior};
+use uuid::Uuid;
+
+use crate::{
+    client::{ClientError, RelayxClient},
+    types::{FeeDataRequest, SendTransactionRequest},
+};
+
+/// What to send and how fast
+#[derive(Debug, Clone)]
+pub struct LoadTestPlan {
+    pub duration: Duration,
+    /// relayer_sendTransaction calls per second; 0 disables the method
+    pub send_rate: f64,
+    /// relayer_getStatus calls per second, asking for ids the send calls got back
+    pub status_rate: f64,
+    /// relayer_getFeeData calls per second
+    pub fee_data_rate: f64,
+    /// Calls allowed to be outstanding at once across all methods
+    pub max_in_flight: usize,
+    pub send_request: SendTransactionRequest,
+    pub fee_data_request: FeeDataRequest,
+}
+
+/// Outcome of one method's calls
+#[derive(Debug, Clone, Default)]
+pub struct MethodReport {
+    pub method: &'static str,
+    /// Latencies of the calls that answered with a result, in completion order
+    pub latencies: Vec<Duration>,
+    /// Failed calls by kind (`rpc <code>`, `transport`, `decode`)
+    pub errors: BTreeMap<String, u64>,
+    /// Calls not sent because `max_in_flight` calls were outstanding
+    pub dropped: u64,
+}
+
+impl MethodReport {
+    fn new(method: &'static str) -> Self {
+        Self {
+            method,
+            ..Default::default()
+        }
+    }
+
+    /// Calls that were sent, whether they succeeded or not
+    pub fn sent(&self) -> u64 {
+        self.latencies.len() as u64 + self.errors.values().sum::<u64>()
+    }
+
+    /// Share of sent calls that failed, from 0 to 1
+    pub fn error_rate(&self) -> f64 {
+        match self.sent() {
+            0 => 0.0,
+            sent => self.errors.values().sum::<u64>() as f64 / sent as f64,
+        }
+    }
+
+    /// Nearest-rank latency percentile of the successful calls, `p` between 0 and 100
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut sorted = self.latencies.clone();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+
+    fn record(&mut self, latency: Duration, outcome: Result<(), &ClientError>) {
+        match outcome {
+            Ok(()) => self.latencies.push(latency),
+            Err(e) => *self.errors.entry(error_kind(e)).or_default() += 1,
+        }
+    }
+}
+
+fn error_kind(e: &ClientError) -> String {
+    match e {
+        ClientError::Rpc { code, .. } => format!("rpc {}", code),
+        ClientError::Transport(_) => "transport".to_string(),
+        ClientError::Decode(_) => "decode".to_string(),
+        ClientError::Timeout { .. } => "timeout".to_string(),
+    }
+}
+
+/// Results of a load test run
+#[derive(Debug, Clone)]
+pub struct LoadTestReport {
+    /// Time from the first call to the last answer
+    pub elapsed: Duration,
+    pub methods: Vec<MethodReport>,
+}
+
+impl fmt::Display for LoadTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |d: Option<Duration>| {
+            d.map_or("-".to_string(), |d| {
+                format!("{:.1}", d.as_secs_f64() * 1000.0)
+            })
+        };
+        writeln!(
+            f,
+            "{:<26} {:>8} {:>8} {:>8} {:>9} {:>9} {:>9} {:>9}",
+            "method", "sent", "req/s", "errors", "p50 ms", "p90 ms", "p99 ms", "max ms"
+        )?;
+        let secs = self.elapsed.as_secs_f64().max(f64::EPSILON);
+        for m in &self.methods {
+            writeln!(
+                f,
+                "{:<26} {:>8} {:>8.1} {:>7.2}% {:>9} {:>9} {:>9} {:>9}",
+                m.method,
+                m.sent(),
+                m.sent() as f64 / secs,
+                m.error_rate() * 100.0,
+                ms(m.percentile(50.0)),
+                ms(m.percentile(90.0)),
+                ms(m.percentile(99.0)),
+                ms(m.percentile(100.0)),
+            )?;
+        }
+        for m in &self.methods {
+            for (kind, count) in &m.errors {
+                writeln!(f, "{}: {} x {}", m.method, count, kind)?;
+            }
+            if m.dropped > 0 {
+                writeln!(
+                    f,
+                    "{}: {} dropped (too many calls in flight)",
+                    m.method, m.dropped
+                )?;
+            }
+        }
+        write!(f, "elapsed {:.1}s", self.elapsed.as_secs_f64())
+    }
+}
+
+/// Ids returned by relayer_sendTransaction, for relayer_getStatus to ask about
+type SentIds = Arc<Mutex<Vec<String>>>;
+
+/// Run `plan` against the relayer `client` talks to and wait for every call to answer
+pub async fn run_load_test(client: Arc<RelayxClient>, plan: &LoadTestPlan) -> LoadTestReport {
+    let started = Instant::now();
+    let permits = Arc::new(Semaphore::new(plan.max_in_flight.max(1)));
+    let sent_ids: SentIds = Arc::default();
+
+    let methods = [
+        ("relayer_sendTransaction", plan.send_rate),
+        ("relayer_getStatus", plan.status_rate),
+        ("relayer_getFeeData", plan.fee_data_rate),
+    ];
+    let plan = Arc::new(plan.clone());
+    let mut schedules = JoinSet::new();
+    for (method, rate) in methods.into_iter().filter(|(_, rate)| *rate > 0.0) {
+        let client = client.clone();
+        let permits = permits.clone();
+        let sent_ids = sent_ids.clone();
+        let plan = plan.clone();
+        schedules.spawn(async move {
+            let mut report = MethodReport::new(method);
+            let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+            interval.set_missed_tick_behavior(MissedTickBehavior::Burst);
+            let deadline = started + plan.duration;
+            let mut calls = JoinSet::new();
+            loop {
+                interval.tick().await;
+                if Instant::now() >= deadline {
+                    break;
+                }
+                let Ok(permit) = permits.clone().try_acquire_owned() else {
+                    report.dropped += 1;
+                    continue;
+                };
+                let client = client.clone();
+                let sent_ids = sent_ids.clone();
+                let plan = plan.clone();
+                calls.spawn(async move {
+                    let start = Instant::now();
+                    let outcome = call(method, &client, &plan, &sent_ids).await;
+                    drop(permit);
+                    (start.elapsed(), outcome)
+                });
+            }
+            while let Some(joined) = calls.join_next().await {
+                if let Ok((latency, outcome)) = joined {
+                    report.record(latency, outcome.as_ref().map(|_| ()));
+                }
+            }
+            report
+        });
+    }
+
+    let mut reports = Vec::new();
+    while let Some(joined) = schedules.join_next().await {
+        if let Ok(report) = joined {
+            reports.push(report);
+        }
+    }
+    // Present methods in plan order regardless of which finished first
+    reports.sort_by_key(|r| methods.iter().position(|(m, _)| *m == r.method));
+    LoadTestReport {
+        elapsed: started.elapsed(),
+        methods: reports,
+    }
+}
+
+async fn call(
+    method: &str,
+    client: &RelayxClient,
+    plan: &LoadTestPlan,
+    sent_ids: &SentIds,
+) -> Result<(), ClientError> {
+    match method {
+        "relayer_sendTransaction" => {
+            let response = client.send_transaction(&plan.send_request).await?;
+            let mut ids = sent_ids.lock().unwrap();
+            ids.extend(
+                response
+                    .result
+                    .into_iter()
+                    .map(|r| r.id)
+                    .filter(|id| !id.is_empty()),
+            );
+            // Only recent ids are asked about; keep the list from growing with the run
+            if ids.len() > 1024 {
+                ids.drain(..512);
+            }
+        }
+        "relayer_getStatus" => {
+            // Before any send has succeeded, ask about an unknown id (answered with a 404 entry)
+            let id = sent_ids
+                .lock()
+                .unwrap()
+                .last()
+                .cloned()
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            client.get_status(&[id]).await?;
+        }
+        _ => {
+            client.get_fee_data(&plan.fee_data_request).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_percentiles_and_error_rate() {
+        let mut report = MethodReport::new("relayer_getFeeData");
+        for ms in (1..=100).rev() {
+            report.record(Duration::from_millis(ms), Ok(()));
+        }
+        let rpc = ClientError::Rpc {
+            code: -32602,
+            message: "Invalid params".to_string(),
+            data: None,
+        };
+        for _ in 0..25 {
+            report.record(Duration::from_millis(1), Err(&rpc));
+        }
+
+        assert_eq!(report.sent(), 125);
+        assert_eq!(report.error_rate(), 0.2);
+        assert_eq!(report.errors.get("rpc -32602"), Some(&25));
+        assert_eq!(report.percentile(50.0), Some(Duration::from_millis(50)));
+        assert_eq!(report.percentile(99.0), Some(Duration::from_millis(99)));
+        assert_eq!(report.percentile(100.0), Some(Duration::from_millis(100)));
+        assert_eq!(MethodReport::new("empty").percentile(50.0), None);
+    }
+}
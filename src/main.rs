@@ -1,13 +1,36 @@
-use anyhow::Result;
+use std::process::ExitCode;
+
 use clap::Parser;
-use relayx::{config::Config, rpc::RpcServer, storage::Storage};
+use relayx::{
+    cli::{self, Cli, Command},
+    config::Config,
+    rpc::RpcServer,
+    storage::Storage,
+    systemd::{self, ExitError},
+};
+use tokio::signal::unix::{signal, SignalKind};
 use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Parse command line arguments first to get log level
-    let config = Config::parse();
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
 
+    let result = match cli.command {
+        None => serve(cli.config).await,
+        Some(Command::Serve(config)) => serve(*config).await,
+        Some(command) => cli::run(command).await.map_err(ExitError::from),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            systemd::notify(&format!("STATUS=Failed: {}", e));
+            ExitCode::from(e.code)
+        }
+    }
+}
+
+async fn serve(config: Config) -> Result<(), ExitError> {
     // Get the configured log level
     let log_level = config.get_log_level();
 
@@ -44,15 +67,16 @@ async fn main() -> Result<()> {
 
     // Initialize Sentry if DSN is provided (after tracing is set up)
     // Note: With the "panic" feature enabled, panics are automatically captured
+    #[cfg(feature = "sentry")]
     let _sentry_guard = if let Some(dsn) = config.get_sentry_dsn() {
-        tracing::info!("Initializing Sentry error tracking");
-        let guard = sentry::init((
-            dsn.as_str(),
-            sentry::ClientOptions {
-                release: sentry::release_name!(),
-                ..Default::default()
-            },
-        ));
+        let settings = config.sentry_settings();
+        tracing::info!(
+            environment = settings.environment.as_deref().unwrap_or("default"),
+            sample_rate = settings.sample_rate,
+            traces_sample_rate = settings.traces_sample_rate,
+            "Initializing Sentry error tracking"
+        );
+        let guard = sentry::init((dsn.as_str(), relayx::telemetry::client_options(&settings)));
 
         tracing::info!("✓ Sentry initialized successfully (panics will be automatically captured)");
         Some(guard)
@@ -60,6 +84,10 @@ async fn main() -> Result<()> {
         tracing::debug!("Sentry DSN not provided, skipping error tracking initialization");
         None
     };
+    #[cfg(not(feature = "sentry"))]
+    if config.get_sentry_dsn().is_some() {
+        tracing::warn!("Sentry DSN is set but relayx was built without the `sentry` feature");
+    }
 
     tracing::info!("Starting RelayX service");
     tracing::debug!("Configuration: {:?}", config);
@@ -67,9 +95,19 @@ async fn main() -> Result<()> {
 
     // Initialize storage
     tracing::info!("Initializing storage at: {:?}", config.db_path);
-    let storage = Storage::new(&config.db_path)?;
+    let mut storage = Storage::with_tuning(&config.db_path, &config.storage_tuning())
+        .map_err(ExitError::unavailable)?;
+    if let Some(key) = config.encryption.resolve().map_err(ExitError::config)? {
+        tracing::info!("Stored request data is encrypted at rest");
+        storage = storage.with_storage_key(&key);
+    }
     tracing::info!("Storage initialized successfully");
 
+    if let Some(path) = &config.relayer_private_key_file {
+        config.load_relayer_key_file().map_err(ExitError::config)?;
+        tracing::info!("Relayer key read from {}", path.display());
+    }
+
     // Create and start RPC server
     let rpc_host = config.get_http_address();
     let rpc_port = config.get_http_port();
@@ -83,8 +121,28 @@ async fn main() -> Result<()> {
     tracing::info!("✓ Server listening on {}:{}", rpc_host, rpc_port);
     tracing::info!("✓ RelayX service ready to accept requests");
 
-    // Start the RPC server
-    rpc_server.start().await?;
+    // Start the RPC server; it is bound and serving once `start` returns
+    let handle = rpc_server.start().await.map_err(ExitError::unavailable)?;
+    systemd::notify(&format!(
+        "READY=1\nSTATUS=Serving on {}",
+        handle.local_addr()
+    ));
+    let watchdog = systemd::watchdog_interval()
+        .map(|interval| tokio::spawn(systemd::run_watchdog(storage.clone(), interval)));
+
+    // Serve until SIGTERM or SIGINT, then shut down cleanly
+    let mut terminate = signal(SignalKind::terminate()).map_err(anyhow::Error::from)?;
+    let mut interrupt = signal(SignalKind::interrupt()).map_err(anyhow::Error::from)?;
+    tokio::select! {
+        _ = terminate.recv() => tracing::info!("Received SIGTERM"),
+        _ = interrupt.recv() => tracing::info!("Received SIGINT"),
+    }
+    systemd::notify("STOPPING=1");
+    if let Some(watchdog) = watchdog {
+        watchdog.abort();
+    }
+    handle.shutdown().await;
+    tracing::info!("RelayX service stopped");
 
     Ok(())
 }
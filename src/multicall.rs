@@ -0,0 +1,162 @@
+//! Batching of independent calls through Multicall3.
+//!
+//! `relayer_sendCalls` relays its calls as one `aggregate3` transaction to the chain's
+//! Multicall3 contract, with `allowFailure` set on every call so one failing call does not
+//! revert the others. Per-call outcomes are read by running the same calldata through
+//! `eth_call` and decoding the returned `Result[]` with [`decode_aggregate3_results`].
+
+use alloy::primitives::{Address, Bytes, U256};
+
+/// Multicall3 is deployed at this address on most EVM chains
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// `aggregate3((address,bool,bytes)[])`
+pub const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// Outcome of one call of a batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallOutcome {
+    pub success: bool,
+    pub return_data: Bytes,
+}
+
+/// Length-prefixed, zero-padded ABI encoding of a `bytes` value
+fn abi_bytes(data: &[u8]) -> Vec<u8> {
+    let mut out = U256::from(data.len()).to_be_bytes::<32>().to_vec();
+    out.extend_from_slice(data);
+    out.resize(out.len() + (32 - data.len() % 32) % 32, 0);
+    out
+}
+
+/// `aggregate3` calldata running `calls` in order, each allowed to fail on its own
+pub fn aggregate3_calldata(calls: &[(Address, Bytes)]) -> Bytes {
+    // Each `Call3` is dynamic (it holds `bytes`), so the array is a list of offsets,
+    // relative to the first offset, followed by the encoded tuples
+    let tuples: Vec<Vec<u8>> = calls
+        .iter()
+        .map(|(target, data)| {
+            let mut tuple = target.into_word().to_vec();
+            tuple.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+            tuple.extend_from_slice(&U256::from(3 * 32).to_be_bytes::<32>());
+            tuple.extend_from_slice(&abi_bytes(data));
+            tuple
+        })
+        .collect();
+
+    let mut input = AGGREGATE3_SELECTOR.to_vec();
+    input.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+    input.extend_from_slice(&U256::from(calls.len()).to_be_bytes::<32>());
+    let mut offset = calls.len() * 32;
+    for tuple in &tuples {
+        input.extend_from_slice(&U256::from(offset).to_be_bytes::<32>());
+        offset += tuple.len();
+    }
+    for tuple in tuples {
+        input.extend_from_slice(&tuple);
+    }
+    input.into()
+}
+
+/// Decode the `(bool success, bytes returnData)[]` returned by `aggregate3`
+pub fn decode_aggregate3_results(output: &[u8]) -> Option<Vec<CallOutcome>> {
+    let word = |offset: usize| -> Option<usize> {
+        let word = output.get(offset..offset.checked_add(32)?)?;
+        usize::try_from(U256::from_be_slice(word)).ok()
+    };
+    let array = word(0)?;
+    let count = word(array)?;
+    let heads = array.checked_add(32)?;
+    (0..count)
+        .map(|i| {
+            let tuple = heads.checked_add(word(heads.checked_add(i.checked_mul(32)?)?)?)?;
+            let success = word(tuple)? != 0;
+            let data = tuple.checked_add(word(tuple.checked_add(32)?)?)?;
+            let len = word(data)?;
+            let start = data.checked_add(32)?;
+            let return_data = output.get(start..start.checked_add(len)?)?;
+            Some(CallOutcome {
+                success,
+                return_data: Bytes::copy_from_slice(return_data),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::keccak256;
+
+    /// ABI encoding of `(bool,bytes)[]`, as `aggregate3` returns it
+    fn encode_results(results: &[(bool, &[u8])]) -> Vec<u8> {
+        let tuples: Vec<Vec<u8>> = results
+            .iter()
+            .map(|(success, data)| {
+                let mut tuple = U256::from(*success as u8).to_be_bytes::<32>().to_vec();
+                tuple.extend_from_slice(&U256::from(64).to_be_bytes::<32>());
+                tuple.extend_from_slice(&abi_bytes(data));
+                tuple
+            })
+            .collect();
+        let mut out = U256::from(32).to_be_bytes::<32>().to_vec();
+        out.extend_from_slice(&U256::from(results.len()).to_be_bytes::<32>());
+        let mut offset = results.len() * 32;
+        for tuple in &tuples {
+            out.extend_from_slice(&U256::from(offset).to_be_bytes::<32>());
+            offset += tuple.len();
+        }
+        out.extend(tuples.concat());
+        out
+    }
+
+    #[test]
+    fn test_aggregate3_selector_and_layout() {
+        assert_eq!(
+            keccak256("aggregate3((address,bool,bytes)[])")[..4],
+            AGGREGATE3_SELECTOR
+        );
+        let calls = vec![
+            (Address::repeat_byte(1), Bytes::from(vec![0xaa; 4])),
+            (Address::repeat_byte(2), Bytes::from(vec![0xbb; 40])),
+        ];
+        let calldata = aggregate3_calldata(&calls);
+        let word = |i: usize| U256::from_be_slice(&calldata[4 + i * 32..4 + (i + 1) * 32]);
+        assert_eq!(word(0), U256::from(32));
+        assert_eq!(word(1), U256::from(2));
+        // The first tuple follows the two offsets; it takes three head words and two for data
+        assert_eq!(word(2), U256::from(64));
+        assert_eq!(word(3), U256::from(64 + 5 * 32));
+        assert_eq!(
+            word(4),
+            U256::from_be_slice(calls[0].0.into_word().as_slice())
+        );
+        assert_eq!(word(5), U256::from(1));
+        assert_eq!(word(6), U256::from(96));
+        assert_eq!(word(7), U256::from(4));
+        assert_eq!(word(12), U256::from(40));
+        assert_eq!(calldata.len(), 4 + 32 * (4 + 5 + 6));
+    }
+
+    #[test]
+    fn test_decode_aggregate3_results() {
+        let output = encode_results(&[(true, &[0x01; 33]), (false, b"")]);
+        assert_eq!(
+            decode_aggregate3_results(&output),
+            Some(vec![
+                CallOutcome {
+                    success: true,
+                    return_data: Bytes::from(vec![0x01; 33]),
+                },
+                CallOutcome {
+                    success: false,
+                    return_data: Bytes::new(),
+                },
+            ])
+        );
+        assert_eq!(
+            decode_aggregate3_results(&output[..output.len() - 32]),
+            None
+        );
+        assert_eq!(decode_aggregate3_results(&[]), None);
+    }
+}
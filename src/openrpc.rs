@@ -0,0 +1,953 @@
+//! OpenRPC discovery document served by `rpc.discover`.
+//!
+//! Schemas are inferred from fully-populated sample values of the request/response
+//! types in [`crate::types`], so adding a field to a type is a compile error here
+//! until the sample is updated, and the document cannot silently drift.
+
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::types::{
+    BroadcastTransaction, BundleStatusResult, Call, CallResult, CallbackDelivery, CallbackStatus,
+    Capabilities, ChainCapabilities, ChainQueueStats, ConditionOperator, ConditionType,
+    CreateRecurringJobRequest, DecodedLog, DryRunResult, Erc20Payment, EstimateFeeRequest,
+    EstimateFeeResponse, ExchangeRateError, ExchangeRateErrorBody, ExchangeRateQuote,
+    ExchangeRateRequest, ExchangeRateResponse, ExchangeRateResultItem, ExchangeRateSuccess,
+    ExecutionCondition, FeeBreakdown, FeeSample, GasTankDeposit, GetBundleStatusResponse,
+    GetCapabilitiesRequest, GetCapabilitiesResponse, GetFeeHistoryRequest, GetFeeHistoryResponse,
+    GetGasTankBalanceRequest, GetGasTankBalanceResponse, GetGasTankDepositsRequest,
+    GetGasTankDepositsResponse, GetQueueStatsRequest, GetQueueStatsResponse, GetReceiptResponse,
+    GetSponsorshipAllowanceRequest, GetSponsorshipAllowanceResponse, GetStatusResponse,
+    GetSupportedChainsResponse, GetTransactionsByWalletRequest, GetTransactionsByWalletResponse,
+    GetVersionResponse, HealthResponse, LifetimeStats, ListRecurringJobsRequest,
+    ListRecurringJobsResponse, LivenessResponse, Log, MultichainLegStatus, MultichainTransaction,
+    MultichainTransactionResult, NativePayment, OffchainFailure, OnchainFailure,
+    PauseRecurringJobRequest, Payment, PaymentCapability, PaymentType, QuoteInner, QuoteRequest,
+    QuoteRequestCapabilities, QuoteResponse, ReadinessResponse, Receipt, RecurringJob, RelayLimits,
+    RelayPolicies, RelayerCall, ResendTransactionOptions, ResendTransactionResponse, Resubmission,
+    SendCallsRequest, SendCallsResponse, SendRawTransactionRequest, SendTransactionCapabilities,
+    SendTransactionMultichainRequest, SendTransactionMultichainResponse, SendTransactionRequest,
+    SendTransactionResponse, SendTransactionResult, SimulateResponse, Speed, SponsoredPayment,
+    StatusKind, StatusResult, StorageStats, SubmitGasTankDepositRequest,
+    SubmitGasTankDepositResponse, SupportedChain, TokenInfo, VersionFeatures, WalletTransaction,
+};
+
+const OPENRPC_VERSION: &str = "1.2.6";
+const ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+const HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Build the OpenRPC document describing every public method
+pub fn discover_document() -> Value {
+    let methods = vec![
+        method(
+            "relayer_sendTransaction",
+            "Relay a single-chain transaction",
+            by_position(&send_transaction_request()),
+            &SendTransactionResponse {
+                result: vec![SendTransactionResult {
+                    chain_id: "1".to_string(),
+                    id: uuid_sample(),
+                    dry_run: Some(DryRunResult {
+                        would_succeed: true,
+                        gas_limit: 21000,
+                        gas_price: "0x1".to_string(),
+                        l1_data_fee: "0x0".to_string(),
+                        fee: Some("0".to_string()),
+                        token: token_info(),
+                        failure_reason: Some(String::new()),
+                        blob_fee: None,
+                    }),
+                    estimated_confirmation_seconds: Some(24),
+                    payment: None,
+                }],
+            },
+        ),
+        method(
+            "relayer_sendTransactionMultichain",
+            "Relay a bundle of transactions across chains, paid for on one chain",
+            by_position(&SendTransactionMultichainRequest {
+                transactions: vec![MultichainTransaction {
+                    to: ADDRESS.to_string(),
+                    data: "0x".to_string(),
+                    chain_id: "1".to_string(),
+                    authorization_list: "0x".to_string(),
+                    value: None,
+                }],
+                capabilities: send_transaction_request().capabilities,
+                payment_chain_id: "1".to_string(),
+                api_key: None,
+            }),
+            &SendTransactionMultichainResponse {
+                result: vec![MultichainTransactionResult {
+                    chain_id: "1".to_string(),
+                    id: uuid_sample(),
+                    status: MultichainLegStatus::Accepted,
+                    error: None,
+                }],
+                bundle_id: uuid_sample(),
+            },
+        ),
+        method(
+            "relayer_sendRawTransaction",
+            "Broadcast a transaction signed by its sender and track it like a relay",
+            by_position(&SendRawTransactionRequest {
+                chain_id: "1".to_string(),
+                raw_transaction: "0x02f86c".to_string(),
+                private_mempool: true,
+                callback_url: Some("https://example.com/hooks/relayx".to_string()),
+                api_key: None,
+            }),
+            &SendTransactionResponse {
+                result: vec![SendTransactionResult {
+                    chain_id: "1".to_string(),
+                    id: uuid_sample(),
+                    dry_run: None,
+                    estimated_confirmation_seconds: Some(24),
+                    payment: None,
+                }],
+            },
+        ),
+        method(
+            "relayer_sendCalls",
+            "Relay a batch of independent calls on one chain as a single Multicall3 transaction",
+            by_position(&SendCallsRequest {
+                calls: vec![Call {
+                    to: ADDRESS.to_string(),
+                    data: "0x".to_string(),
+                    value: Some("0x0".to_string()),
+                }],
+                chain_id: "1".to_string(),
+                capabilities: send_transaction_request().capabilities,
+                callback_url: Some("https://example.com/hooks/relayx".to_string()),
+                speed: Some(Speed::Standard),
+                api_key: None,
+            }),
+            &SendCallsResponse {
+                result: vec![SendTransactionResult {
+                    chain_id: "1".to_string(),
+                    id: uuid_sample(),
+                    dry_run: None,
+                    estimated_confirmation_seconds: Some(24),
+                    payment: None,
+                }],
+                calls: vec![call_result()],
+            },
+        ),
+        method(
+            "relayer_getStatus",
+            "Status of previously submitted requests",
+            Params::Named(vec![("ids", json!([uuid_sample()]))]),
+            &GetStatusResponse {
+                result: vec![status_result()],
+            },
+        ),
+        method(
+            "relayer_getBundleStatus",
+            "Aggregate status of a multichain bundle",
+            Params::Named(vec![("bundleId", json!(uuid_sample()))]),
+            &GetBundleStatusResponse {
+                result: BundleStatusResult {
+                    version: "2.0.0".to_string(),
+                    bundle_id: uuid_sample(),
+                    status: 200,
+                    status_name: Some(StatusKind::Confirmed),
+                    all_mined: true,
+                    any_failed: false,
+                    legs: vec![status_result()],
+                },
+            },
+        ),
+        method(
+            "relayer_getTransactionsByWallet",
+            "Relay history of a wallet, newest first, with cursor pagination",
+            by_position(&GetTransactionsByWalletRequest {
+                wallet: ADDRESS.to_string(),
+                status: Some(200),
+                limit: Some(50),
+                cursor: Some(String::new()),
+                api_key: None,
+            }),
+            &GetTransactionsByWalletResponse {
+                result: vec![WalletTransaction {
+                    id: uuid_sample(),
+                    chain_id: "1".to_string(),
+                    status: 200,
+                    transaction_hash: Some(HASH.to_string()),
+                    bundle_id: Some(uuid_sample()),
+                    failure_reason: Some(String::new()),
+                    created_at: Utc.timestamp_opt(0, 0).unwrap(),
+                    updated_at: Utc.timestamp_opt(0, 0).unwrap(),
+                }],
+                next_cursor: Some(String::new()),
+            },
+        ),
+        method(
+            "relayer_resendTransaction",
+            "Rebroadcast an in-flight request now with a bumped gas price",
+            Params::Ordered(vec![
+                ("id", true, json!(uuid_sample())),
+                (
+                    "options",
+                    false,
+                    json!(ResendTransactionOptions {
+                        gas_multiplier: Some(1.2),
+                        api_key: None,
+                    }),
+                ),
+            ]),
+            &ResendTransactionResponse {
+                id: uuid_sample(),
+                chain_id: "1".to_string(),
+                transaction_hash: HASH.to_string(),
+                gas_price: "0x1".to_string(),
+            },
+        ),
+        method(
+            "relayer_getReceipt",
+            "Every transaction broadcast for a request and the receipt of the one that mined",
+            Params::Named(vec![("id", json!(uuid_sample()))]),
+            &GetReceiptResponse {
+                id: uuid_sample(),
+                chain_id: "1".to_string(),
+                status: 200,
+                transactions: vec![BroadcastTransaction {
+                    transaction_hash: HASH.to_string(),
+                    mined: true,
+                    replaced: false,
+                }],
+                mined_transaction_hash: Some(HASH.to_string()),
+                receipts: vec![receipt()],
+            },
+        ),
+        method(
+            "relayer_submitGasTankDeposit",
+            "Credit a mined native transfer to the deposit address to the sender's gas tank",
+            by_position(&SubmitGasTankDepositRequest {
+                chain_id: "1".to_string(),
+                transaction_hash: HASH.to_string(),
+            }),
+            &SubmitGasTankDepositResponse {
+                deposit: gas_tank_deposit(),
+                balance: "1000000000000000".to_string(),
+            },
+        ),
+        method(
+            "relayer_getGasTankBalance",
+            "Prepaid gas balance of a wallet on a chain, in wei",
+            by_position(&GetGasTankBalanceRequest {
+                wallet: ADDRESS.to_string(),
+                chain_id: "1".to_string(),
+            }),
+            &GetGasTankBalanceResponse {
+                wallet: ADDRESS.to_string(),
+                chain_id: "1".to_string(),
+                balance: "1000000000000000".to_string(),
+            },
+        ),
+        method(
+            "relayer_getGasTankDeposits",
+            "Deposits credited to a wallet's gas tank, newest first",
+            by_position(&GetGasTankDepositsRequest {
+                wallet: ADDRESS.to_string(),
+                chain_id: Some("1".to_string()),
+                limit: Some(50),
+            }),
+            &GetGasTankDepositsResponse {
+                deposits: vec![gas_tank_deposit()],
+            },
+        ),
+        method(
+            "relayer_getSponsorshipAllowance",
+            "Sponsored gas allowance of the project owning an API key for the current month",
+            by_position(&GetSponsorshipAllowanceRequest {
+                api_key: "project-api-key".to_string(),
+            }),
+            &GetSponsorshipAllowanceResponse {
+                project: "acme".to_string(),
+                tier: "pro".to_string(),
+                period: "2024-01".to_string(),
+                allowance: Some(50_000_000),
+                used: 1_250_000,
+                remaining: Some(48_750_000),
+                resets_at: Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+            },
+        ),
+        method(
+            "relayer_createRecurringJob",
+            "Store a relay template that is relayed again every intervalSeconds",
+            by_position(&CreateRecurringJobRequest {
+                request: recurring_job().request,
+                interval_seconds: 86_400,
+                start_at: Some(Utc.timestamp_opt(0, 0).unwrap()),
+            }),
+            &recurring_job(),
+        ),
+        method(
+            "relayer_pauseRecurringJob",
+            "Pause a recurring job, or resume it with paused set to false",
+            by_position(&PauseRecurringJobRequest {
+                id: uuid_sample(),
+                paused: Some(true),
+                api_key: None,
+            }),
+            &recurring_job(),
+        ),
+        method(
+            "relayer_listRecurringJobs",
+            "Recurring jobs relaying to a wallet, oldest first",
+            by_position(&ListRecurringJobsRequest {
+                wallet: ADDRESS.to_string(),
+                api_key: None,
+            }),
+            &ListRecurringJobsResponse {
+                jobs: vec![recurring_job()],
+            },
+        ),
+        method(
+            "relayer_getExchangeRate",
+            "Gas price expressed in a payment token",
+            by_position(&exchange_rate_request()),
+            &exchange_rate_response(),
+        ),
+        method(
+            "relayer_getFeeData",
+            "Alias of relayer_getExchangeRate",
+            by_position(&exchange_rate_request()),
+            &exchange_rate_response(),
+        ),
+        method(
+            "relayer_getFeeHistory",
+            "Gas prices and token rates sampled by the relayer, oldest first",
+            by_position(&GetFeeHistoryRequest {
+                chain_id: "1".to_string(),
+                token: Some(ADDRESS.to_string()),
+                limit: Some(60),
+            }),
+            &GetFeeHistoryResponse {
+                chain_id: "1".to_string(),
+                token: ADDRESS.to_string(),
+                samples: vec![FeeSample {
+                    timestamp: 0,
+                    gas_price: "0x1".to_string(),
+                    rate: 0.0,
+                }],
+            },
+        ),
+        method(
+            "relayer_getQueueStats",
+            "Per-chain queue depth, broadcast latency, inclusion time and resubmission rate",
+            Params::Positional {
+                required: false,
+                schema: schema_of(&GetQueueStatsRequest {
+                    chain_id: Some("1".to_string()),
+                    window_seconds: Some(3600),
+                    api_key: None,
+                }),
+            },
+            &GetQueueStatsResponse {
+                window_seconds: 3600,
+                chains: vec![ChainQueueStats {
+                    chain_id: "1".to_string(),
+                    queued: 0,
+                    in_flight: 0,
+                    broadcast: 0,
+                    avg_broadcast_latency_ms: Some(0),
+                    avg_inclusion_ms: Some(0),
+                    resubmission_rate: Some(0.0),
+                }],
+            },
+        ),
+        method(
+            "relayer_getQuote",
+            "Fee quote for a transaction",
+            by_position(&QuoteRequest {
+                to: ADDRESS.to_string(),
+                data: "0x".to_string(),
+                capabilities: Some(QuoteRequestCapabilities {
+                    payment: Some(json!({})),
+                }),
+                chain_id: Some("1".to_string()),
+                authorization_list: Some("0x".to_string()),
+                speed: Some(Speed::Fast),
+                blob_count: None,
+                value: None,
+                transactions: Vec::new(),
+                payment_chain_id: None,
+            }),
+            &QuoteResponse {
+                quote: QuoteInner {
+                    fee: "0".to_string(),
+                    decimals: 18,
+                    rate: 0.0,
+                    token: token_info(),
+                },
+                relayer_calls: vec![RelayerCall {
+                    to: ADDRESS.to_string(),
+                    data: "0x".to_string(),
+                }],
+                fee_collector: ADDRESS.to_string(),
+                revert_reason: String::new(),
+                speed: Speed::Fast,
+                fee_bound: None,
+                expiry: 0,
+                signature: None,
+                legs: Vec::new(),
+                breakdown: Some(FeeBreakdown {
+                    gas_limit: Some(21000),
+                    execution_cost: Some("0x1".to_string()),
+                    l1_data_fee: Some("0x0".to_string()),
+                    blob_fee: None,
+                    value: None,
+                    gas_price: "0x1".to_string(),
+                    network_gas_price: "0x1".to_string(),
+                    priority_fee_per_gas: Some("0x1".to_string()),
+                    oracle_rate: 1.0,
+                    markup_bps: 0,
+                }),
+            },
+        ),
+        method(
+            "relayer_estimateFee",
+            "Complete fee breakdown for a transaction paid in a given token",
+            by_position(&estimate_fee_request()),
+            &estimate_fee_response(),
+        ),
+        method(
+            "relayer_simulate",
+            "Pre-flight a relay call: gas, revert reason, emitted logs and fee quote; nothing is stored",
+            by_position(&estimate_fee_request()),
+            &SimulateResponse {
+                success: true,
+                gas_used: 21000,
+                gas_limit: Some(21000),
+                revert_reason: Some("ERC20: transfer amount exceeds balance".to_string()),
+                revert_data: Some("0x08c379a0".to_string()),
+                logs: receipt().logs,
+                quote: Some(estimate_fee_response()),
+            },
+        ),
+        method(
+            "relayer_getCapabilities",
+            "Supported payment options, optionally for a single chain",
+            Params::Positional {
+                required: false,
+                schema: schema_of(&GetCapabilitiesRequest {
+                    chain_id: Some("1".to_string()),
+                }),
+            },
+            &GetCapabilitiesResponse {
+                capabilities: Capabilities {
+                    payment: payments(),
+                },
+                chains: BTreeMap::from([(
+                    "1".to_string(),
+                    ChainCapabilities {
+                        payment: payments(),
+                        wallet_implementations: vec![ADDRESS.to_string()],
+                        atomic: true,
+                    },
+                )]),
+                limits: RelayLimits {
+                    max_calldata_bytes: 131_072,
+                    max_gas: 15_000_000,
+                    quote_expiry_seconds: 600,
+                },
+                policies: RelayPolicies {
+                    sponsorship_requires_api_key: false,
+                    payment_chains: BTreeMap::from([("native".to_string(), vec!["1".to_string()])]),
+                },
+                methods: vec!["relayer_sendTransaction".to_string()],
+            },
+        ),
+        method(
+            "relayer_getSupportedChains",
+            "Chains served by this relayer",
+            Params::None,
+            &GetSupportedChainsResponse {
+                chains: vec![SupportedChain {
+                    chain_id: "1".to_string(),
+                    relayer_address: Some(ADDRESS.to_string()),
+                    payment_tokens: vec![ADDRESS.to_string()],
+                    confirmations: 1,
+                    available: true,
+                    block_number: Some(0),
+                    name: Some("Ethereum Mainnet".to_string()),
+                    explorer_url: Some("https://etherscan.io".to_string()),
+                }],
+            },
+        ),
+        method(
+            "relayer_getVersion",
+            "Build information, spec versions and supported features",
+            Params::None,
+            &GetVersionResponse {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                git_commit: "0123456789ab".to_string(),
+                build_timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+                spec_versions: vec!["2.0.0".to_string()],
+                features: VersionFeatures {
+                    eip7702: true,
+                    erc4337: false,
+                    multichain: true,
+                },
+                methods: vec!["relayer_sendTransaction".to_string()],
+            },
+        ),
+        method(
+            "health_check",
+            "Service health and request counters",
+            Params::None,
+            &HealthResponse {
+                status: "healthy".to_string(),
+                timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+                uptime_seconds: 0,
+                total_requests: 0,
+                pending_requests: 0,
+                completed_requests: 0,
+                failed_requests: 0,
+                storage: StorageStats {
+                    sst_files_bytes: Some(52_428_800),
+                    memtable_bytes: Some(8_388_608),
+                    pending_compaction_bytes: Some(0),
+                    running_compactions: Some(0),
+                    block_cache_usage_bytes: Some(16_777_216),
+                    block_cache_capacity_bytes: Some(33_554_432),
+                    block_cache_hit_rate: Some(0.97),
+                },
+                lifetime: LifetimeStats {
+                    first_started_at: Utc.timestamp_opt(0, 0).unwrap(),
+                    starts: 3,
+                    total_relayed: 48_210,
+                    total_gas_used: 7_231_500_000,
+                    total_gas_spent_wei: "144630000000000000000".to_string(),
+                },
+            },
+        ),
+        method(
+            "health_live",
+            "Liveness probe: the process is up (also GET /health/live)",
+            Params::None,
+            &LivenessResponse {
+                status: "alive".to_string(),
+                uptime_seconds: 0,
+            },
+        ),
+        method(
+            "health_ready",
+            "Readiness probe: storage writable, a chain reachable, signer loaded \
+             (also GET /health/ready)",
+            Params::None,
+            &ReadinessResponse {
+                status: "ready".to_string(),
+                storage_writable: true,
+                chains_configured: 1,
+                chains_reachable: 1,
+                signer_loaded: true,
+            },
+        ),
+    ];
+
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "RelayX",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods,
+    })
+}
+
+/// How a method takes its parameters on the wire
+enum Params {
+    None,
+    /// One request object wrapped in a single-element array
+    Positional {
+        required: bool,
+        schema: Value,
+    },
+    /// A JSON object whose fields are the parameters
+    Named(Vec<(&'static str, Value)>),
+    /// Several values in a fixed order, as `(name, required, sample)`
+    Ordered(Vec<(&'static str, bool, Value)>),
+}
+
+fn method<R: Serialize>(name: &str, summary: &str, params: Params, result: &R) -> Value {
+    let (structure, params) = match params {
+        Params::None => ("by-position", Vec::new()),
+        Params::Positional { required, schema } => (
+            "by-position",
+            vec![json!({ "name": "request", "required": required, "schema": schema })],
+        ),
+        Params::Named(fields) => (
+            "by-name",
+            fields
+                .into_iter()
+                .map(|(name, sample)| {
+                    json!({ "name": name, "required": true, "schema": infer_schema(&sample) })
+                })
+                .collect(),
+        ),
+        Params::Ordered(fields) => (
+            "by-position",
+            fields
+                .into_iter()
+                .map(|(name, required, sample)| {
+                    json!({ "name": name, "required": required, "schema": infer_schema(&sample) })
+                })
+                .collect(),
+        ),
+    };
+    json!({
+        "name": name,
+        "summary": summary,
+        "paramStructure": structure,
+        "params": params,
+        "result": {
+            "name": "result",
+            "schema": schema_of(result),
+        },
+    })
+}
+
+fn by_position<T: Serialize>(sample: &T) -> Params {
+    Params::Positional {
+        required: true,
+        schema: schema_of(sample),
+    }
+}
+
+fn schema_of<T: Serialize>(sample: &T) -> Value {
+    infer_schema(&serde_json::to_value(sample).unwrap_or(Value::Null))
+}
+
+/// JSON Schema for a sample value. Arrays whose elements differ in shape (untagged
+/// enums such as `Payment`) become `oneOf`.
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({}),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => {
+            let mut variants: Vec<Value> = Vec::new();
+            for item in items {
+                let schema = infer_schema(item);
+                if !variants.contains(&schema) {
+                    variants.push(schema);
+                }
+            }
+            let items = match variants.len() {
+                0 => json!({}),
+                1 => variants.remove(0),
+                _ => json!({ "oneOf": variants }),
+            };
+            json!({ "type": "array", "items": items })
+        }
+        // Maps keyed by chain ID (e.g. getCapabilities `chains`)
+        Value::Object(fields)
+            if !fields.is_empty() && fields.keys().all(|k| k.parse::<u64>().is_ok()) =>
+        {
+            let first = fields.values().next().unwrap_or(&Value::Null);
+            json!({ "type": "object", "additionalProperties": infer_schema(first) })
+        }
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(k, v)| (k.clone(), infer_schema(v)))
+                .collect();
+            json!({ "type": "object", "properties": properties })
+        }
+    }
+}
+
+fn uuid_sample() -> String {
+    "00000000-0000-0000-0000-000000000000".to_string()
+}
+
+fn token_info() -> TokenInfo {
+    TokenInfo {
+        decimals: 18,
+        address: ADDRESS.to_string(),
+        symbol: Some("ETH".to_string()),
+        name: Some("Ether".to_string()),
+    }
+}
+
+fn send_transaction_request() -> SendTransactionRequest {
+    SendTransactionRequest {
+        to: ADDRESS.to_string(),
+        data: "0x".to_string(),
+        capabilities: SendTransactionCapabilities {
+            payment: PaymentCapability {
+                payment_type: "native".to_string(),
+                token: ADDRESS.to_string(),
+                data: "0x".to_string(),
+            },
+            dry_run: true,
+            fallback_payments: Vec::new(),
+        },
+        chain_id: "1".to_string(),
+        authorization_list: "0x".to_string(),
+        callback_url: Some("https://example.com/hooks/relayx".to_string()),
+        execute_after: Some(Utc.timestamp_opt(0, 0).unwrap()),
+        condition: Some(execution_condition()),
+        speed: Some(Speed::Fast),
+        blob_sidecar: None,
+        safe: None,
+        value: Some("0x0".to_string()),
+        api_key: None,
+        quote_id: None,
+    }
+}
+
+fn execution_condition() -> ExecutionCondition {
+    ExecutionCondition {
+        condition_type: ConditionType::PriceFeed,
+        address: ADDRESS.to_string(),
+        data: Some("0x".to_string()),
+        operator: Some(ConditionOperator::Gte),
+        value: Some("200000000000".to_string()),
+        expires_at: Some(Utc.timestamp_opt(0, 0).unwrap()),
+    }
+}
+
+fn recurring_job() -> RecurringJob {
+    let mut request = send_transaction_request();
+    request.capabilities.dry_run = false;
+    request.execute_after = None;
+    RecurringJob {
+        id: uuid::Uuid::nil(),
+        request,
+        interval_seconds: 86_400,
+        paused: false,
+        next_run_at: Utc.timestamp_opt(86_400, 0).unwrap(),
+        created_at: Utc.timestamp_opt(0, 0).unwrap(),
+        runs: 1,
+        last_run_at: Some(Utc.timestamp_opt(0, 0).unwrap()),
+        last_request_id: Some(uuid::Uuid::nil()),
+        last_error: Some(String::new()),
+        tenant: None,
+    }
+}
+
+fn gas_tank_deposit() -> GasTankDeposit {
+    GasTankDeposit {
+        chain_id: "1".to_string(),
+        wallet: ADDRESS.to_string(),
+        transaction_hash: HASH.to_string(),
+        amount: "1000000000000000".to_string(),
+        credited_at: Utc.timestamp_opt(0, 0).unwrap(),
+    }
+}
+
+fn estimate_fee_request() -> EstimateFeeRequest {
+    EstimateFeeRequest {
+        to: ADDRESS.to_string(),
+        data: "0x".to_string(),
+        chain_id: "1".to_string(),
+        token: ADDRESS.to_string(),
+        speed: Some(Speed::Fast),
+        value: None,
+    }
+}
+
+fn estimate_fee_response() -> EstimateFeeResponse {
+    EstimateFeeResponse {
+        gas_limit: 21000,
+        gas_price: "0x1".to_string(),
+        max_fee_per_gas: Some("0x1".to_string()),
+        max_priority_fee_per_gas: Some("0x1".to_string()),
+        l1_data_fee: "0x0".to_string(),
+        rate: 0.0,
+        token: token_info(),
+        fee: "0".to_string(),
+        fee_collector: ADDRESS.to_string(),
+        expiry: 0,
+        speed: Speed::Fast,
+        fee_bound: None,
+        quote_id: None,
+        signature: None,
+    }
+}
+
+fn receipt() -> Receipt {
+    Receipt {
+        logs: vec![Log {
+            address: ADDRESS.to_string(),
+            topics: vec![
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".to_string(),
+                HASH.to_string(),
+                HASH.to_string(),
+            ],
+            data: "0x00000000000000000000000000000000000000000000000000000000000f4240".to_string(),
+            decoded: Some(DecodedLog {
+                name: "Transfer".to_string(),
+                signature: "Transfer(address,address,uint256)".to_string(),
+                args: Map::from_iter([
+                    ("from".to_string(), json!(ADDRESS)),
+                    ("to".to_string(), json!(ADDRESS)),
+                    ("value".to_string(), json!("1000000")),
+                ]),
+            }),
+        }],
+        status: "0x1".to_string(),
+        block_hash: HASH.to_string(),
+        block_number: "0x1".to_string(),
+        gas_used: "0x1".to_string(),
+        effective_gas_price: Some("0x4a817c800".to_string()),
+        transaction_hash: HASH.to_string(),
+        chain_id: "1".to_string(),
+        blob_gas_used: None,
+        blob_gas_price: None,
+    }
+}
+
+fn call_result() -> CallResult {
+    CallResult {
+        success: true,
+        return_data: "0x".to_string(),
+    }
+}
+
+fn status_result() -> StatusResult {
+    StatusResult {
+        version: "2.0.0".to_string(),
+        id: uuid_sample(),
+        status: 200,
+        status_name: Some(StatusKind::Confirmed),
+        receipts: vec![receipt()],
+        resubmissions: vec![Resubmission {
+            status: 201,
+            transaction_hash: HASH.to_string(),
+            chain_id: "1".to_string(),
+        }],
+        offchain_failure: vec![OffchainFailure {
+            message: String::new(),
+        }],
+        onchain_failure: vec![OnchainFailure {
+            transaction_hash: HASH.to_string(),
+            chain_id: "1".to_string(),
+            message: String::new(),
+            data: "0x".to_string(),
+        }],
+        bundle_id: Some(uuid_sample()),
+        estimated_confirmation_seconds: Some(24),
+        callback: Some(CallbackDelivery {
+            url: "https://example.com/hooks/relayx".to_string(),
+            status: CallbackStatus::Delivered,
+            attempts: 1,
+            last_attempt_at: Some(Utc.timestamp_opt(0, 0).unwrap()),
+            last_error: None,
+            delivered_at: Some(Utc.timestamp_opt(0, 0).unwrap()),
+        }),
+        execute_after: None,
+        calls: Some(vec![call_result()]),
+        payment: None,
+        resubmission_count: None,
+    }
+}
+
+fn exchange_rate_request() -> ExchangeRateRequest {
+    ExchangeRateRequest {
+        token: ADDRESS.to_string(),
+        chain_id: "1".to_string(),
+    }
+}
+
+fn exchange_rate_response() -> ExchangeRateResponse {
+    ExchangeRateResponse {
+        result: vec![
+            ExchangeRateResultItem::Success(Box::new(ExchangeRateSuccess {
+                quote: ExchangeRateQuote {
+                    rate: 0.0,
+                    token: token_info(),
+                },
+                gas_price: "0x1".to_string(),
+                max_fee_per_gas: Some("0x1".to_string()),
+                max_priority_fee_per_gas: Some("0x1".to_string()),
+                fee_collector: ADDRESS.to_string(),
+                expiry: 0,
+                l1_fee_model: Some("optimism".to_string()),
+                l1_base_fee: Some("0x1".to_string()),
+                quote_id: None,
+                breakdown: Some(FeeBreakdown {
+                    gas_limit: None,
+                    execution_cost: None,
+                    l1_data_fee: None,
+                    blob_fee: None,
+                    value: None,
+                    gas_price: "0x1".to_string(),
+                    network_gas_price: "0x1".to_string(),
+                    priority_fee_per_gas: Some("0x1".to_string()),
+                    oracle_rate: 0.0,
+                    markup_bps: 0,
+                }),
+            })),
+            ExchangeRateResultItem::Error(ExchangeRateError {
+                error: ExchangeRateErrorBody {
+                    id: ADDRESS.to_string(),
+                    message: String::new(),
+                },
+            }),
+        ],
+    }
+}
+
+fn payments() -> Vec<Payment> {
+    vec![
+        Payment::Native(NativePayment {
+            payment_type: PaymentType::Native,
+            token: ADDRESS.to_string(),
+        }),
+        Payment::Erc20(Erc20Payment {
+            payment_type: PaymentType::Erc20,
+            token: ADDRESS.to_string(),
+        }),
+        Payment::Sponsored(SponsoredPayment {
+            payment_type: PaymentType::Sponsored,
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_document_lists_methods_with_schemas() {
+        let doc = discover_document();
+        assert_eq!(doc["openrpc"], OPENRPC_VERSION);
+        let methods = doc["methods"].as_array().unwrap();
+        let send = methods
+            .iter()
+            .find(|m| m["name"] == "relayer_sendTransaction")
+            .unwrap();
+        let params = &send["params"][0]["schema"]["properties"];
+        assert_eq!(params["chainId"]["type"], "string");
+        assert_eq!(
+            params["capabilities"]["properties"]["dryRun"]["type"],
+            "boolean"
+        );
+
+        let status = methods
+            .iter()
+            .find(|m| m["name"] == "relayer_getStatus")
+            .unwrap();
+        assert_eq!(status["paramStructure"], "by-name");
+
+        let caps = methods
+            .iter()
+            .find(|m| m["name"] == "relayer_getCapabilities")
+            .unwrap();
+        let chains = &caps["result"]["schema"]["properties"]["chains"];
+        assert!(chains["additionalProperties"]["properties"]["atomic"].is_object());
+        let payment = &caps["result"]["schema"]["properties"]["capabilities"]["properties"]
+            ["payment"]["items"];
+        // native and erc20 share a shape; sponsored has no token
+        assert_eq!(payment["oneOf"].as_array().unwrap().len(), 2);
+    }
+}
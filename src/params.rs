@@ -0,0 +1,252 @@
+//! Validated parsing of request parameters.
+//!
+//! Every address, chain id, hash, hex quantity and hex byte string a client sends goes through
+//! these parsers, which accept only the canonical JSON-RPC forms:
+//!
+//! - addresses are `0x` plus 40 hex digits, in any letter case (EIP-55 checksums are not
+//!   enforced since existing integrations send arbitrary casing)
+//! - chain ids are non-zero decimal integers without sign, whitespace or leading zeros
+//! - hashes are `0x` plus 64 hex digits
+//! - quantities are `0x` plus 1 to 64 hex digits
+//! - byte strings are `0x` plus an even number of hex digits (`0x` alone is empty)
+//!
+//! The generic `FromStr` parsers are laxer (optional `0x`, `+` signs, surrounding junk in some
+//! cases),
+//! so request handlers must not use them on client input.
+
+use std::fmt;
+
+use alloy::primitives::{Address, Bytes, B256, U256};
+
+/// Why a parameter was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    Empty,
+    /// Hex value without the `0x` prefix
+    MissingPrefix,
+    /// Wrong number of hex digits
+    Length {
+        expected: usize,
+        actual: usize,
+    },
+    OddLength,
+    NotHex,
+    /// Not a plain decimal integer
+    NotDecimal,
+    LeadingZero,
+    Overflow,
+    Zero,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Empty => write!(f, "empty value"),
+            ParamError::MissingPrefix => write!(f, "missing 0x prefix"),
+            ParamError::Length { expected, actual } => {
+                write!(f, "expected {} hex digits, got {}", expected, actual)
+            }
+            ParamError::OddLength => write!(f, "odd number of hex digits"),
+            ParamError::NotHex => write!(f, "not a hex string"),
+            ParamError::NotDecimal => write!(f, "not a decimal integer"),
+            ParamError::LeadingZero => write!(f, "leading zeros are not allowed"),
+            ParamError::Overflow => write!(f, "value too large"),
+            ParamError::Zero => write!(f, "must not be zero"),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Hex digits after the mandatory `0x` prefix
+fn hex_digits(value: &str) -> Result<&str, ParamError> {
+    if value.is_empty() {
+        return Err(ParamError::Empty);
+    }
+    let digits = value.strip_prefix("0x").ok_or(ParamError::MissingPrefix)?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ParamError::NotHex);
+    }
+    Ok(digits)
+}
+
+/// A 20-byte address
+pub fn address(value: &str) -> Result<Address, ParamError> {
+    let digits = hex_digits(value)?;
+    if digits.len() != 40 {
+        return Err(ParamError::Length {
+            expected: 40,
+            actual: digits.len(),
+        });
+    }
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(digits, &mut bytes).map_err(|_| ParamError::NotHex)?;
+    Ok(Address::from(bytes))
+}
+
+/// A non-zero decimal chain id
+pub fn chain_id(value: &str) -> Result<u64, ParamError> {
+    if value.is_empty() {
+        return Err(ParamError::Empty);
+    }
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParamError::NotDecimal);
+    }
+    if value.len() > 1 && value.starts_with('0') {
+        return Err(ParamError::LeadingZero);
+    }
+    match value.parse::<u64>() {
+        Ok(0) => Err(ParamError::Zero),
+        Ok(id) => Ok(id),
+        Err(_) => Err(ParamError::Overflow),
+    }
+}
+
+/// A 32-byte hash
+pub fn hash(value: &str) -> Result<B256, ParamError> {
+    let digits = hex_digits(value)?;
+    if digits.len() != 64 {
+        return Err(ParamError::Length {
+            expected: 64,
+            actual: digits.len(),
+        });
+    }
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(digits, &mut bytes).map_err(|_| ParamError::NotHex)?;
+    Ok(B256::from(bytes))
+}
+
+/// A hex quantity such as a wei amount
+pub fn quantity(value: &str) -> Result<U256, ParamError> {
+    let digits = hex_digits(value)?;
+    if digits.is_empty() {
+        return Err(ParamError::Empty);
+    }
+    if digits.trim_start_matches('0').len() > 64 {
+        return Err(ParamError::Overflow);
+    }
+    U256::from_str_radix(digits, 16).map_err(|_| ParamError::Overflow)
+}
+
+/// Arbitrary hex bytes such as calldata or a signature
+pub fn hex_bytes(value: &str) -> Result<Bytes, ParamError> {
+    let digits = hex_digits(value)?;
+    if digits.len() % 2 != 0 {
+        return Err(ParamError::OddLength);
+    }
+    hex::decode(digits)
+        .map(Bytes::from)
+        .map_err(|_| ParamError::NotHex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_malformed_inputs_are_rejected() {
+        let zz = format!("0x{}", "zz".repeat(20));
+        assert_eq!(address(&zz), Err(ParamError::NotHex));
+        assert_eq!(
+            address("742d35Cc6634C0532925a3b844Bc454e4438f44e"),
+            Err(ParamError::MissingPrefix)
+        );
+        assert_eq!(
+            address("0x742d35cc6634c0532925a3b844bc454e4438f4"),
+            Err(ParamError::Length {
+                expected: 40,
+                actual: 38
+            })
+        );
+        assert_eq!(
+            address(" 0x742d35cc6634c0532925a3b844bc454e4438f44e"),
+            Err(ParamError::MissingPrefix)
+        );
+        assert_eq!(
+            address("0X742d35cc6634c0532925a3b844bc454e4438f44e"),
+            Err(ParamError::MissingPrefix)
+        );
+        assert_eq!(
+            address(&format!("0x{}", "5A".repeat(20))),
+            Ok(Address::from([0x5a; 20]))
+        );
+
+        assert_eq!(chain_id("+1"), Err(ParamError::NotDecimal));
+        assert_eq!(chain_id("0x1"), Err(ParamError::NotDecimal));
+        assert_eq!(chain_id(" 1"), Err(ParamError::NotDecimal));
+        assert_eq!(chain_id("01"), Err(ParamError::LeadingZero));
+        assert_eq!(chain_id("0"), Err(ParamError::Zero));
+        assert_eq!(chain_id(""), Err(ParamError::Empty));
+        assert_eq!(chain_id("18446744073709551616"), Err(ParamError::Overflow));
+        assert_eq!(chain_id("8453"), Ok(8453));
+
+        assert_eq!(hex_bytes("0xabc"), Err(ParamError::OddLength));
+        assert_eq!(hex_bytes("0x0xab"), Err(ParamError::NotHex));
+        assert_eq!(hex_bytes("abcd"), Err(ParamError::MissingPrefix));
+        assert_eq!(hex_bytes("0x"), Ok(Bytes::new()));
+
+        assert_eq!(quantity("0x"), Err(ParamError::Empty));
+        assert_eq!(quantity("0x_1"), Err(ParamError::NotHex));
+        assert_eq!(
+            quantity(&format!("0x1{}", "0".repeat(64))),
+            Err(ParamError::Overflow)
+        );
+        assert_eq!(quantity("0x00ff"), Ok(U256::from(255)));
+
+        assert!(matches!(
+            hash("0x1234"),
+            Err(ParamError::Length { expected: 64, .. })
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parsers_never_panic(value in ".*") {
+            let _ = address(&value);
+            let _ = chain_id(&value);
+            let _ = hash(&value);
+            let _ = quantity(&value);
+            let _ = hex_bytes(&value);
+        }
+
+        #[test]
+        fn prop_hex_like_inputs_never_panic(value in "0x[0-9a-fA-FxX_ +-]{0,80}") {
+            let _ = address(&value);
+            let _ = hash(&value);
+            let _ = quantity(&value);
+            let _ = hex_bytes(&value);
+        }
+
+        #[test]
+        fn prop_addresses_round_trip(bytes in any::<[u8; 20]>()) {
+            let parsed = Address::from(bytes);
+            prop_assert_eq!(address(&parsed.to_checksum(None)), Ok(parsed));
+            prop_assert_eq!(address(&format!("0x{}", hex::encode(bytes))), Ok(parsed));
+        }
+
+        #[test]
+        fn prop_chain_ids_round_trip(id in 1u64..) {
+            prop_assert_eq!(chain_id(&id.to_string()), Ok(id));
+            let padded = format!("0{}", id);
+            prop_assert!(chain_id(&padded).is_err());
+        }
+
+        #[test]
+        fn prop_quantities_round_trip(bytes in any::<[u8; 32]>()) {
+            let value = U256::from_be_bytes(bytes);
+            prop_assert_eq!(quantity(&format!("0x{:x}", value)), Ok(value));
+        }
+
+        #[test]
+        fn prop_bytes_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+            let encoded = format!("0x{}", hex::encode(&bytes));
+            prop_assert_eq!(hex_bytes(&encoded), Ok(Bytes::from(bytes.clone())));
+            if bytes.len() == 32 {
+                prop_assert_eq!(hash(&encoded), Ok(B256::from_slice(&bytes)));
+            } else {
+                prop_assert!(hash(&encoded).is_err());
+            }
+        }
+    }
+}
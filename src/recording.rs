@@ -0,0 +1,543 @@
+//! Record/replay of upstream chain traffic.
+//!
+//! [`RecordingChainClient`] wraps another [`ChainClient`] and appends every call and its
+//! answer to a fixture file, one JSON object per line:
+//!
+//! ```json
+//! {"method":"eth_gasPrice","rpcUrl":"https://rpc.example","params":[],"result":20000000000}
+//! ```
+//!
+//! [`ReplayChainClient`] answers from such a fixture without touching the network, so quote
+//! and simulation logic can be regression-tested against traffic captured from live chains.
+//! Calls are matched on method, endpoint and parameters; repeated calls get the recorded
+//! answers in order, and the last one once those run out, so polling loops replay too.
+
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use alloy::{
+    primitives::{Address, Bytes, B256, U256},
+    rpc::types::{FeeHistory, TransactionRequest},
+};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    chain::{CallSimulation, ChainClient, TxReceipt, TxTransfer},
+    signer::RelayerSigner,
+};
+
+/// One recorded call: what was asked and what the node answered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Exchange {
+    pub method: String,
+    pub rpc_url: String,
+    pub params: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// [`ChainClient`] that records all traffic to a fixture file; see the module docs
+pub struct RecordingChainClient {
+    inner: Arc<dyn ChainClient>,
+    fixture: Mutex<File>,
+}
+
+impl RecordingChainClient {
+    /// Record calls made through `inner`, appending them to the fixture at `path`
+    pub fn new(inner: Arc<dyn ChainClient>, path: &Path) -> std::io::Result<Self> {
+        let fixture = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            inner,
+            fixture: Mutex::new(fixture),
+        })
+    }
+
+    fn record<T: Serialize>(
+        &self,
+        method: &str,
+        rpc_url: &str,
+        params: Value,
+        answer: Result<T, String>,
+    ) -> Result<T, String> {
+        let (result, error) = match &answer {
+            Ok(value) => (
+                Some(serde_json::to_value(value).unwrap_or(Value::Null)),
+                None,
+            ),
+            Err(e) => (None, Some(e.clone())),
+        };
+        let exchange = Exchange {
+            method: method.to_string(),
+            rpc_url: rpc_url.to_string(),
+            params,
+            result,
+            error,
+        };
+        let written = serde_json::to_string(&exchange)
+            .map_err(std::io::Error::from)
+            .and_then(|line| {
+                let mut fixture = self.fixture.lock().unwrap_or_else(|e| e.into_inner());
+                writeln!(fixture, "{}", line)
+            });
+        if let Err(e) = written {
+            tracing::warn!("Failed to record {} call: {}", method, e);
+        }
+        answer
+    }
+}
+
+#[async_trait]
+impl ChainClient for RecordingChainClient {
+    async fn gas_price(&self, rpc_url: &str) -> Result<u128, String> {
+        let answer = self.inner.gas_price(rpc_url).await;
+        self.record("eth_gasPrice", rpc_url, json!([]), answer)
+    }
+
+    async fn blob_base_fee(&self, rpc_url: &str) -> Result<u128, String> {
+        let answer = self.inner.blob_base_fee(rpc_url).await;
+        self.record("eth_blobBaseFee", rpc_url, json!([]), answer)
+    }
+
+    async fn base_fee(&self, rpc_url: &str) -> Result<Option<u128>, String> {
+        let answer = self.inner.base_fee(rpc_url).await;
+        self.record("baseFee", rpc_url, json!([]), answer)
+    }
+
+    async fn balance(&self, rpc_url: &str, address: Address) -> Result<U256, String> {
+        let answer = self.inner.balance(rpc_url, address).await;
+        self.record("eth_getBalance", rpc_url, json!([address]), answer)
+    }
+
+    async fn code(&self, rpc_url: &str, address: Address) -> Result<Bytes, String> {
+        let answer = self.inner.code(rpc_url, address).await;
+        self.record("eth_getCode", rpc_url, json!([address]), answer)
+    }
+
+    async fn storage_at(
+        &self,
+        rpc_url: &str,
+        address: Address,
+        slot: U256,
+    ) -> Result<U256, String> {
+        let answer = self.inner.storage_at(rpc_url, address, slot).await;
+        self.record("eth_getStorageAt", rpc_url, json!([address, slot]), answer)
+    }
+
+    async fn call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Bytes, String> {
+        let params = json!([to, input, value]);
+        let answer = self.inner.call(rpc_url, to, input, value).await;
+        self.record("eth_call", rpc_url, params, answer)
+    }
+
+    async fn estimate_gas(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<u64, String> {
+        let params = json!([to, input, value]);
+        let answer = self.inner.estimate_gas(rpc_url, to, input, value).await;
+        self.record("eth_estimateGas", rpc_url, params, answer)
+    }
+
+    async fn simulate_call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<CallSimulation, String> {
+        let params = json!([to, input, value]);
+        let answer = self.inner.simulate_call(rpc_url, to, input, value).await;
+        self.record("eth_simulateV1", rpc_url, params, answer)
+    }
+
+    async fn transaction_count(&self, rpc_url: &str, address: Address) -> Result<u64, String> {
+        let answer = self.inner.transaction_count(rpc_url, address).await;
+        self.record("eth_getTransactionCount", rpc_url, json!([address]), answer)
+    }
+
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String> {
+        let answer = self.inner.pending_transaction_count(rpc_url, address).await;
+        self.record("pendingTransactionCount", rpc_url, json!([address]), answer)
+    }
+
+    async fn send_transaction(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String> {
+        let params = json!([signer.address(), tx]);
+        let answer = self.inner.send_transaction(rpc_url, signer, tx).await;
+        self.record("eth_sendTransaction", rpc_url, params, answer)
+    }
+
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String> {
+        let params = json!([raw]);
+        let answer = self.inner.send_raw_transaction(rpc_url, raw).await;
+        self.record("eth_sendRawTransaction", rpc_url, params, answer)
+    }
+
+    async fn receipt(&self, rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String> {
+        let answer = self.inner.receipt(rpc_url, hash).await;
+        self.record("eth_getTransactionReceipt", rpc_url, json!([hash]), answer)
+    }
+
+    async fn receipts(
+        &self,
+        rpc_url: &str,
+        hashes: &[B256],
+    ) -> Result<Vec<Option<TxReceipt>>, String> {
+        let answer = self.inner.receipts(rpc_url, hashes).await;
+        self.record("receipts", rpc_url, json!([hashes]), answer)
+    }
+
+    async fn transfer(&self, rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String> {
+        let answer = self.inner.transfer(rpc_url, hash).await;
+        self.record("eth_getTransactionByHash", rpc_url, json!([hash]), answer)
+    }
+
+    async fn replay_transaction(
+        &self,
+        rpc_url: &str,
+        hash: B256,
+        block_number: u64,
+    ) -> Result<Bytes, String> {
+        let answer = self
+            .inner
+            .replay_transaction(rpc_url, hash, block_number)
+            .await;
+        self.record(
+            "replayTransaction",
+            rpc_url,
+            json!([hash, block_number]),
+            answer,
+        )
+    }
+
+    async fn block_number(&self, rpc_url: &str) -> Result<u64, String> {
+        let answer = self.inner.block_number(rpc_url).await;
+        self.record("eth_blockNumber", rpc_url, json!([]), answer)
+    }
+
+    async fn fee_history(
+        &self,
+        rpc_url: &str,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String> {
+        let answer = self
+            .inner
+            .fee_history(rpc_url, block_count, reward_percentiles)
+            .await;
+        self.record(
+            "eth_feeHistory",
+            rpc_url,
+            json!([block_count, reward_percentiles]),
+            answer,
+        )
+    }
+
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String> {
+        let answer = self.inner.chain_id(rpc_url).await;
+        self.record("eth_chainId", rpc_url, json!([]), answer)
+    }
+
+    // Block announcements are pushed, not requested, so they are not recorded
+    async fn next_block(&self, rpc_url: &str) -> u64 {
+        self.inner.next_block(rpc_url).await
+    }
+}
+
+/// Recorded answers to one call, served in order
+#[derive(Debug, Default)]
+struct Answers {
+    exchanges: Vec<Exchange>,
+    next: usize,
+}
+
+/// [`ChainClient`] answering from a recorded fixture; see the module docs
+#[derive(Debug)]
+pub struct ReplayChainClient {
+    answers: Mutex<HashMap<(String, String, String), Answers>>,
+}
+
+impl ReplayChainClient {
+    /// Load the fixture at `path`
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut exchanges = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let exchange = serde_json::from_str(&line).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{} line {}: {}", path.display(), number + 1, e),
+                )
+            })?;
+            exchanges.push(exchange);
+        }
+        Ok(Self::from_exchanges(exchanges))
+    }
+
+    pub fn from_exchanges(exchanges: impl IntoIterator<Item = Exchange>) -> Self {
+        let mut answers: HashMap<_, Answers> = HashMap::new();
+        for exchange in exchanges {
+            let key = (
+                exchange.method.clone(),
+                exchange.rpc_url.clone(),
+                exchange.params.to_string(),
+            );
+            answers.entry(key).or_default().exchanges.push(exchange);
+        }
+        Self {
+            answers: Mutex::new(answers),
+        }
+    }
+
+    fn replay<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        rpc_url: &str,
+        params: Value,
+    ) -> Result<T, String> {
+        let mut answers = self.answers.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (method.to_string(), rpc_url.to_string(), params.to_string());
+        let Some(answers) = answers.get_mut(&key) else {
+            return Err(format!(
+                "No recorded {} on {} for params {}",
+                method, rpc_url, params
+            ));
+        };
+        let exchange = &answers.exchanges[answers.next];
+        answers.next = (answers.next + 1).min(answers.exchanges.len() - 1);
+        match (&exchange.error, &exchange.result) {
+            (Some(error), _) => Err(error.clone()),
+            (None, result) => serde_json::from_value(result.clone().unwrap_or(Value::Null))
+                .map_err(|e| format!("Recorded {} answer does not decode: {}", method, e)),
+        }
+    }
+}
+
+#[async_trait]
+impl ChainClient for ReplayChainClient {
+    async fn gas_price(&self, rpc_url: &str) -> Result<u128, String> {
+        self.replay("eth_gasPrice", rpc_url, json!([]))
+    }
+
+    async fn blob_base_fee(&self, rpc_url: &str) -> Result<u128, String> {
+        self.replay("eth_blobBaseFee", rpc_url, json!([]))
+    }
+
+    async fn base_fee(&self, rpc_url: &str) -> Result<Option<u128>, String> {
+        self.replay("baseFee", rpc_url, json!([]))
+    }
+
+    async fn balance(&self, rpc_url: &str, address: Address) -> Result<U256, String> {
+        self.replay("eth_getBalance", rpc_url, json!([address]))
+    }
+
+    async fn code(&self, rpc_url: &str, address: Address) -> Result<Bytes, String> {
+        self.replay("eth_getCode", rpc_url, json!([address]))
+    }
+
+    async fn storage_at(
+        &self,
+        rpc_url: &str,
+        address: Address,
+        slot: U256,
+    ) -> Result<U256, String> {
+        self.replay("eth_getStorageAt", rpc_url, json!([address, slot]))
+    }
+
+    async fn call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<Bytes, String> {
+        self.replay("eth_call", rpc_url, json!([to, input, value]))
+    }
+
+    async fn estimate_gas(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<u64, String> {
+        self.replay("eth_estimateGas", rpc_url, json!([to, input, value]))
+    }
+
+    async fn simulate_call(
+        &self,
+        rpc_url: &str,
+        to: Address,
+        input: Bytes,
+        value: U256,
+    ) -> Result<CallSimulation, String> {
+        self.replay("eth_simulateV1", rpc_url, json!([to, input, value]))
+    }
+
+    async fn transaction_count(&self, rpc_url: &str, address: Address) -> Result<u64, String> {
+        self.replay("eth_getTransactionCount", rpc_url, json!([address]))
+    }
+
+    async fn pending_transaction_count(
+        &self,
+        rpc_url: &str,
+        address: Address,
+    ) -> Result<u64, String> {
+        self.replay("pendingTransactionCount", rpc_url, json!([address]))
+    }
+
+    async fn send_transaction(
+        &self,
+        rpc_url: &str,
+        signer: &RelayerSigner,
+        tx: TransactionRequest,
+    ) -> Result<B256, String> {
+        self.replay(
+            "eth_sendTransaction",
+            rpc_url,
+            json!([signer.address(), tx]),
+        )
+    }
+
+    async fn send_raw_transaction(&self, rpc_url: &str, raw: Bytes) -> Result<B256, String> {
+        self.replay("eth_sendRawTransaction", rpc_url, json!([raw]))
+    }
+
+    async fn receipt(&self, rpc_url: &str, hash: B256) -> Result<Option<TxReceipt>, String> {
+        self.replay("eth_getTransactionReceipt", rpc_url, json!([hash]))
+    }
+
+    async fn receipts(
+        &self,
+        rpc_url: &str,
+        hashes: &[B256],
+    ) -> Result<Vec<Option<TxReceipt>>, String> {
+        self.replay("receipts", rpc_url, json!([hashes]))
+    }
+
+    async fn transfer(&self, rpc_url: &str, hash: B256) -> Result<Option<TxTransfer>, String> {
+        self.replay("eth_getTransactionByHash", rpc_url, json!([hash]))
+    }
+
+    async fn replay_transaction(
+        &self,
+        rpc_url: &str,
+        hash: B256,
+        block_number: u64,
+    ) -> Result<Bytes, String> {
+        self.replay("replayTransaction", rpc_url, json!([hash, block_number]))
+    }
+
+    async fn block_number(&self, rpc_url: &str) -> Result<u64, String> {
+        self.replay("eth_blockNumber", rpc_url, json!([]))
+    }
+
+    async fn fee_history(
+        &self,
+        rpc_url: &str,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, String> {
+        self.replay(
+            "eth_feeHistory",
+            rpc_url,
+            json!([block_count, reward_percentiles]),
+        )
+    }
+
+    async fn chain_id(&self, rpc_url: &str) -> Result<u64, String> {
+        self.replay("eth_chainId", rpc_url, json!([]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::MockChainClient;
+
+    #[tokio::test]
+    async fn test_replay_answers_what_was_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.jsonl");
+        let wallet = Address::repeat_byte(0x11);
+        let output: Bytes = vec![0x2a].into();
+        let mock = MockChainClient::new()
+            .with_gas_price(7)
+            .with_call_result(wallet, output.clone());
+        let recorder = RecordingChainClient::new(Arc::new(mock), &path).unwrap();
+
+        assert_eq!(recorder.gas_price("https://rpc").await, Ok(7));
+        assert_eq!(
+            recorder
+                .call("https://rpc", wallet, Bytes::new(), U256::ZERO)
+                .await,
+            Ok(output.clone())
+        );
+        assert_eq!(
+            recorder
+                .estimate_gas("https://rpc", wallet, Bytes::new(), U256::ZERO)
+                .await,
+            Ok(150_000)
+        );
+        assert!(recorder.chain_id("https://rpc").await.is_err());
+
+        let replay = ReplayChainClient::load(&path).unwrap();
+        // Repeated calls keep getting the last recorded answer
+        for _ in 0..2 {
+            assert_eq!(replay.gas_price("https://rpc").await, Ok(7));
+        }
+        assert_eq!(
+            replay
+                .call("https://rpc", wallet, Bytes::new(), U256::ZERO)
+                .await,
+            Ok(output)
+        );
+        assert_eq!(
+            replay
+                .estimate_gas("https://rpc", wallet, Bytes::new(), U256::ZERO)
+                .await,
+            Ok(150_000)
+        );
+        assert_eq!(
+            replay.chain_id("https://rpc").await,
+            recorder.chain_id("https://rpc").await
+        );
+        // Anything not recorded fails instead of reaching a node
+        assert!(replay.gas_price("https://other").await.is_err());
+        assert!(replay
+            .call("https://rpc", wallet, vec![0x01].into(), U256::ZERO)
+            .await
+            .is_err());
+    }
+}
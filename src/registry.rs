@@ -0,0 +1,106 @@
+//! Chain metadata from a chain registry in the chainid.network `chains.json` format.
+//!
+//! With `chainRegistry` in config.json, operators list the chain IDs to serve and the relayer
+//! takes each chain's name, native currency, explorer and public RPC endpoint from the
+//! registry, so config.json only needs overrides. The registry is the one embedded from
+//! `resources/chains.json` unless `chainRegistry.path` points at another file, such as a full
+//! download of <https://chainid.network/chains.json>.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+const EMBEDDED_REGISTRY: &str = include_str!("../resources/chains.json");
+
+/// One chain of the registry; fields the relayer does not use are ignored
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RegistryChain {
+    pub name: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    #[serde(rename = "nativeCurrency")]
+    pub native_currency: NativeCurrency,
+    #[serde(default)]
+    pub rpc: Vec<String>,
+    #[serde(default)]
+    pub explorers: Vec<Explorer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct NativeCurrency {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Explorer {
+    pub name: String,
+    pub url: String,
+}
+
+impl RegistryChain {
+    /// First HTTP endpoint usable as-is; endpoints needing an API key substituted into a
+    /// `${...}` placeholder are skipped
+    pub fn public_rpc_url(&self) -> Option<&str> {
+        self.rpc
+            .iter()
+            .map(String::as_str)
+            .find(|url| url.starts_with("http") && !url.contains("${"))
+    }
+
+    /// Base URL of the chain's first block explorer
+    pub fn explorer_url(&self) -> Option<&str> {
+        self.explorers.first().map(|explorer| explorer.url.as_str())
+    }
+}
+
+/// Parse a registry document
+pub fn parse(json: &str) -> Result<Vec<RegistryChain>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// The registry shipped with the relayer
+pub fn embedded() -> Vec<RegistryChain> {
+    parse(EMBEDDED_REGISTRY).expect("embedded chain registry is valid")
+}
+
+/// Read a registry file
+pub fn load(path: &Path) -> Result<Vec<RegistryChain>> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("failed to read chain registry {}", path.display()))?;
+    parse(&json).with_context(|| format!("invalid chain registry {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_registry_has_public_endpoints() {
+        let chains = embedded();
+        let mainnet = chains.iter().find(|chain| chain.chain_id == 1).unwrap();
+        assert_eq!(mainnet.native_currency.symbol, "ETH");
+        // The Infura endpoint comes first but needs a key
+        assert_eq!(
+            mainnet.public_rpc_url(),
+            Some("https://ethereum-rpc.publicnode.com")
+        );
+        assert_eq!(mainnet.explorer_url(), Some("https://etherscan.io"));
+        assert!(chains.iter().all(|chain| chain.public_rpc_url().is_some()));
+    }
+
+    #[test]
+    fn test_parse_ignores_unused_fields() {
+        let chains = parse(
+            r#"[{ "name": "Gnosis", "chain": "GNO", "chainId": 100, "networkId": 100,
+                  "nativeCurrency": { "name": "xDAI", "symbol": "XDAI", "decimals": 18 },
+                  "rpc": ["wss://rpc.gnosischain.com/wss"], "faucets": [] }]"#,
+        )
+        .unwrap();
+        assert_eq!(chains[0].chain_id, 100);
+        assert_eq!(chains[0].public_rpc_url(), None);
+        assert_eq!(chains[0].explorer_url(), None);
+    }
+}
@@ -1,80 +1,187 @@
-use std::net::SocketAddr;
-use std::sync::OnceLock;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    net::SocketAddr,
+    sync::{Arc, Mutex, OnceLock},
+    time::Instant,
+};
 
 use alloy::{
+    consensus::{Transaction, TxEnvelope},
+    eips::{eip2718::Decodable2718, eip4844::BlobTransactionSidecar},
     hex,
     json_abi::JsonAbi,
-    network::EthereumWallet,
-    primitives::{Address, Bytes, U256},
-    providers::{Provider, ProviderBuilder},
+    primitives::{keccak256, Address, Bytes, PrimitiveSignature, B256, I256, U256},
     rpc::types::TransactionRequest,
-    signers::local::PrivateKeySigner,
 };
 use alloy_eip7702::SignedAuthorization;
 use alloy_rlp::decode_exact;
 use anyhow::Result;
-use chrono::Utc;
-use jsonrpc_core::{IoHandler, Params};
-use jsonrpc_http_server::ServerBuilder;
-use tokio::time::{sleep, Duration};
+use chrono::{Datelike, TimeZone, Utc};
+use futures::{
+    future::{join_all, select_all},
+    stream, FutureExt, StreamExt,
+};
+use jsonrpc_core::{
+    futures_util::future::Either,
+    middleware::{Middleware, NoopCallFuture, NoopFuture},
+    Call, Failure, MetaIoHandler, Output, Params,
+};
+use jsonrpc_http_server::{hyper, ServerBuilder};
+#[cfg(feature = "sentry")]
+use sentry::SentryFutureExt;
+use tokio::{
+    sync::Semaphore,
+    time::{sleep, Duration},
+};
 use url::Url;
 use uuid::Uuid;
 
 use crate::{
+    attestation::FeeQuote,
+    callback,
+    chain::{ChainBackend, MockChainClient, TxLog, TxReceipt},
+    cli::{cancel_request, is_cancellable, list_requests, parse_status},
     config::Config,
-    storage::Storage,
+    encryption::SecretSlot,
+    errors::RelayError,
+    export::{collected_tokens, export_accounting, parse_bound, ExportFormat},
+    multicall, params,
+    recording::ReplayChainClient,
+    safe,
+    signer::RelayerSigner,
+    storage::{Storage, StorageBatch, TenantScope, FEE_HISTORY_SLOTS},
     types::{
-        Capabilities, Erc20Payment, ExchangeRateError, ExchangeRateErrorBody, ExchangeRateQuote,
+        AddChainRequest, AdminChainResponse, BroadcastTransaction, Bundle, BundleStatusResult,
+        CallResult, CallbackDelivery, CallbackStatus, CancelRequestRequest, CancelRequestResponse,
+        Capabilities, ChainCapabilities, ChainQueueStats, ConditionOperator, ConditionType,
+        CreateRecurringJobRequest, DexQuoter, DryRunResult, Erc20Payment, EstimateFeeRequest,
+        EstimateFeeResponse, ExchangeRateError, ExchangeRateErrorBody, ExchangeRateQuote,
         ExchangeRateRequest, ExchangeRateResponse, ExchangeRateResultItem, ExchangeRateSuccess,
-        FeeDataRequest, GetCapabilitiesResponse, GetStatusRequest, GetStatusResponse,
-        HealthResponse, Log, MultichainTransactionResult, NativePayment, OffchainFailure,
-        OnchainFailure, Payment, PaymentType, QuoteInner, QuoteRequest, QuoteResponse, Receipt,
-        RelayerCall, RelayerRequest, RequestStatus, Resubmission, SendTransactionMultichainRequest,
-        SendTransactionMultichainResponse, SendTransactionRequest, SendTransactionResponse,
-        SendTransactionResult, SponsoredPayment, StatusResult, TokenInfo,
+        ExecutionCondition, ExportAccountingRequest, ExportAccountingResponse, FeeBound,
+        FeeBreakdown, FeeDataRequest, FeePayment, FeeSample, GasPriceStrategy, GasTankDeposit,
+        GetBundleStatusRequest, GetBundleStatusResponse, GetCapabilitiesRequest,
+        GetCapabilitiesResponse, GetFeeHistoryRequest, GetFeeHistoryResponse,
+        GetGasTankBalanceRequest, GetGasTankBalanceResponse, GetGasTankDepositsRequest,
+        GetGasTankDepositsResponse, GetQueueStatsRequest, GetQueueStatsResponse, GetReceiptRequest,
+        GetReceiptResponse, GetRefundReportRequest, GetRefundReportResponse,
+        GetSponsorshipAllowanceRequest, GetSponsorshipAllowanceResponse, GetStatusRequest,
+        GetStatusResponse, GetSupportedChainsResponse, GetTransactionsByWalletRequest,
+        GetTransactionsByWalletResponse, GetVersionResponse, HealthResponse, IssuedQuote,
+        LifetimeStats, ListRecurringJobsRequest, ListRecurringJobsResponse, ListRequestsRequest,
+        ListRequestsResponse, LivenessResponse, Log, MultichainLegError, MultichainLegStatus,
+        MultichainTransaction, MultichainTransactionResult, NativePayment, NonceHealing,
+        OffchainFailure, OnchainFailure, Overpayment, PauseRecurringJobRequest, Payment,
+        PaymentCapability, PaymentType, QuoteInner, QuoteLeg, QuoteRequest, QuoteResponse,
+        ReadinessResponse, RebalancePolicy, Receipt, RecordingMode, RecurringJob, RefundCall,
+        RelayFee, RelayLimits, RelayPolicies, RelayerCall, RelayerRequest, RemoveChainRequest,
+        RequestStatus, ResendTransactionRequest, ResendTransactionResponse, Resubmission,
+        SafeTransaction, SandboxSettings, ScheduledTransaction, SendCallsRequest,
+        SendCallsResponse, SendRawTransactionRequest, SendTransactionCapabilities,
+        SendTransactionMultichainRequest, SendTransactionMultichainResponse,
+        SendTransactionRequest, SendTransactionResponse, SendTransactionResult, SimulateRequest,
+        SimulateResponse, SimulationFailureMode, Speed, SponsoredPayment, SponsorshipProject,
+        StatusCodes, StatusKind, StatusResult, StorageStats, SubmitGasTankDepositRequest,
+        SubmitGasTankDepositResponse, SupportedChain, Tenant, TokenInfo, TxType, VersionFeatures,
+        WalletTransaction,
     },
 };
 
-fn stub_mode_enabled() -> bool {
-    static FLAG: OnceLock<bool> = OnceLock::new();
-    *FLAG.get_or_init(|| {
-        std::env::var("RELAYX_STUB_MODE")
-            .map(|v| matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"))
-            .unwrap_or(false)
-    })
+/// Check a caller-supplied admin token against the configured one in constant time.
+/// The admin API is disabled entirely when no token is configured.
+fn authorize_admin(cfg: &Config, provided: &str) -> Result<(), jsonrpc_core::Error> {
+    let expected = match cfg.get_admin_token() {
+        Some(token) => token,
+        None => {
+            tracing::warn!("Admin request rejected: admin API is disabled (no admin token)");
+            return Err(RelayError::Unauthorized.into());
+        }
+    };
+    if !secrets_match(&expected, provided) {
+        tracing::warn!("Admin request rejected: invalid admin token");
+        return Err(RelayError::Unauthorized.into());
+    }
+    Ok(())
 }
 
-fn invalid_params_error() -> jsonrpc_core::Error {
-    let mut err = jsonrpc_core::Error::new(jsonrpc_core::ErrorCode::InvalidParams);
-    err.message = "Invalid params".to_string();
-    err
+/// The tenant a call is made for, from the `apiKey` it presents. Without configured tenants
+/// the relayer serves a single tenant and keys are ignored; with them, a call must present the
+/// key of one.
+fn resolve_tenant(
+    cfg: &Config,
+    api_key: Option<&str>,
+) -> Result<Option<String>, jsonrpc_core::Error> {
+    tenant_for_key(cfg.tenants(), api_key)
 }
 
-fn unsupported_payment_token_error() -> jsonrpc_core::Error {
-    let mut err = jsonrpc_core::Error::new(jsonrpc_core::ErrorCode::ServerError(-4202));
-    err.message = "Unsupported Payment Token".to_string();
-    err
+fn tenant_for_key(
+    tenants: Vec<Tenant>,
+    api_key: Option<&str>,
+) -> Result<Option<String>, jsonrpc_core::Error> {
+    if tenants.is_empty() {
+        return Ok(None);
+    }
+    let Some(api_key) = api_key.filter(|key| !key.is_empty()) else {
+        tracing::warn!("Request rejected: no tenant API key");
+        return Err(RelayError::Unauthorized.into());
+    };
+    match tenants
+        .into_iter()
+        .find(|tenant| secrets_match(&tenant.api_key, api_key))
+    {
+        Some(tenant) => Ok(Some(tenant.id)),
+        None => {
+            tracing::warn!("Request rejected: unknown tenant API key");
+            Err(RelayError::Unauthorized.into())
+        }
+    }
 }
 
-fn unsupported_capability_error() -> jsonrpc_core::Error {
-    let mut err = jsonrpc_core::Error::new(jsonrpc_core::ErrorCode::ServerError(-4209));
-    err.message = "Unsupported Capability".to_string();
-    err
+/// Compare two secrets in constant time (for equal lengths)
+fn secrets_match(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
 }
 
-fn invalid_signature_error() -> jsonrpc_core::Error {
-    let mut err = jsonrpc_core::Error::new(jsonrpc_core::ErrorCode::ServerError(-4201));
-    err.message = "Invalid Signature".to_string();
-    err
+/// Serialized payloads longer than this many bytes are cut short in debug logs
+const LOG_PAYLOAD_LIMIT: usize = 2048;
+
+/// Log a request or response payload at debug level, truncated to
+/// [`LOG_PAYLOAD_LIMIT`]. Nothing is serialized unless debug logging is enabled,
+/// so large calldata costs no extra allocation at info level.
+fn log_payload<T: serde::Serialize>(endpoint: &str, label: &str, payload: &T) {
+    if !tracing::enabled!(tracing::Level::DEBUG) {
+        return;
+    }
+    match serde_json::to_string(payload) {
+        Ok(json) => tracing::debug!(
+            bytes = json.len(),
+            "[{}] {}: {}",
+            endpoint,
+            label,
+            truncate_for_log(&json, LOG_PAYLOAD_LIMIT)
+        ),
+        Err(e) => tracing::debug!("[{}] {} (serialization failed: {})", endpoint, label, e),
+    }
 }
 
-fn simulation_failed_error() -> jsonrpc_core::Error {
-    let mut err = jsonrpc_core::Error::new(jsonrpc_core::ErrorCode::ServerError(-4211));
-    err.message = "Simulation Failed".to_string();
-    err
+/// Cut `text` to at most `limit` bytes on a char boundary, noting how much was dropped
+fn truncate_for_log(text: &str, limit: usize) -> std::borrow::Cow<'_, str> {
+    if text.len() <= limit {
+        return text.into();
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…(+{} bytes)", &text[..end], text.len() - end).into()
 }
 
 /// Capture an error in Sentry with context
+#[cfg(feature = "sentry")]
 fn capture_sentry_error(endpoint: &str, error: &jsonrpc_core::Error) {
     sentry::configure_scope(|scope| {
         scope.set_tag("endpoint", endpoint);
@@ -87,6 +194,37 @@ fn capture_sentry_error(endpoint: &str, error: &jsonrpc_core::Error) {
     );
 }
 
+#[cfg(not(feature = "sentry"))]
+fn capture_sentry_error(_endpoint: &str, _error: &jsonrpc_core::Error) {}
+
+/// Record a step of relay `id`'s lifecycle as a Sentry breadcrumb, reported along with the
+/// relay's failure should it fail
+#[cfg(feature = "sentry")]
+fn lifecycle_breadcrumb(id: Uuid, step: &str, message: String) {
+    crate::telemetry::lifecycle_breadcrumb(id, step, message);
+}
+
+#[cfg(not(feature = "sentry"))]
+fn lifecycle_breadcrumb(_id: Uuid, _step: &str, _message: String) {}
+
+/// Capture relay `id`'s failure in Sentry with the breadcrumbs of its lifecycle
+#[cfg(feature = "sentry")]
+fn capture_lifecycle_failure(id: Uuid, message: &str, error: &str) {
+    crate::telemetry::capture_lifecycle_failure(id, message, error);
+}
+
+#[cfg(not(feature = "sentry"))]
+fn capture_lifecycle_failure(_id: Uuid, _message: &str, _error: &str) {}
+
+/// Drop the breadcrumbs of relay `id` once it settled
+#[cfg(feature = "sentry")]
+fn end_lifecycle(id: Uuid) {
+    crate::telemetry::end_lifecycle(id);
+}
+
+#[cfg(not(feature = "sentry"))]
+fn end_lifecycle(_id: Uuid) {}
+
 fn validate_authorization_list(
     authorization_list: &str,
     chain_id: u64,
@@ -100,14 +238,14 @@ fn validate_authorization_list(
     let hex_body = trimmed.strip_prefix("0x").unwrap_or(trimmed);
     if hex_body.is_empty() {
         tracing::warn!("Authorization list provided without payload");
-        return Err(invalid_signature_error());
+        return Err(RelayError::InvalidAuthorizationList("empty payload".to_string()).into());
     }
 
     let bytes = match hex::decode(hex_body) {
         Ok(bytes) => bytes,
         Err(e) => {
             tracing::warn!("Failed to hex-decode authorization list: {}", e);
-            return Err(invalid_signature_error());
+            return Err(RelayError::InvalidAuthorizationList("not hex".to_string()).into());
         }
     };
 
@@ -115,13 +253,13 @@ fn validate_authorization_list(
         Ok(list) => list,
         Err(e) => {
             tracing::warn!("Failed to decode authorization list RLP: {}", e);
-            return Err(invalid_signature_error());
+            return Err(RelayError::InvalidAuthorizationList("invalid RLP".to_string()).into());
         }
     };
 
     if authorizations.is_empty() {
         tracing::warn!("Authorization list decoded to empty set");
-        return Err(invalid_signature_error());
+        return Err(RelayError::InvalidAuthorizationList("no authorizations".to_string()).into());
     }
 
     for auth in authorizations {
@@ -132,7 +270,11 @@ fn validate_authorization_list(
                 chain_id,
                 auth_chain
             );
-            return Err(invalid_signature_error());
+            return Err(RelayError::InvalidAuthorizationList(format!(
+                "authorization for chain {}",
+                auth_chain
+            ))
+            .into());
         }
 
         if auth.address() != &contract_address {
@@ -141,18 +283,238 @@ fn validate_authorization_list(
                 contract_address,
                 auth.address()
             );
-            return Err(invalid_signature_error());
+            return Err(RelayError::InvalidAuthorizationList(format!(
+                "authorization for {}",
+                auth.address()
+            ))
+            .into());
         }
 
         if let Err(e) = auth.recover_authority() {
             tracing::warn!("Failed to recover authority from authorization: {}", e);
-            return Err(invalid_signature_error());
+            return Err(RelayError::InvalidSignature.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a payment capability against `chain_id`, where its fee is charged. Both
+/// relayer_sendTransaction and relayer_sendTransactionMultichain go through here, so the same
+/// problem is reported with the same code on either endpoint.
+fn validate_payment(
+    payment: &PaymentCapability,
+    chain_id: u64,
+    cfg: &Config,
+) -> Result<(), RelayError> {
+    const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+    match payment.payment_type.as_str() {
+        "native" | "gasTank" => {
+            if payment.token != ZERO_ADDRESS {
+                tracing::warn!(
+                    "Invalid {} payment token address: {}",
+                    payment.payment_type,
+                    payment.token
+                );
+                let message = if payment.payment_type == "native" {
+                    "Native payment token must be the zero address"
+                } else {
+                    "Gas tank payment token must be the zero address"
+                };
+                return Err(RelayError::invalid_field(
+                    "capabilities.payment.token",
+                    message,
+                ));
+            }
+        }
+        "erc20" | "permit" => {
+            if params::address(&payment.token).is_err() {
+                tracing::warn!("Invalid ERC20 token address format: {}", payment.token);
+                return Err(RelayError::invalid_field(
+                    "capabilities.payment.token",
+                    "Invalid ERC20 token address",
+                ));
+            }
+            if !cfg.accepts_payment_token(&chain_id.to_string(), &payment.token) {
+                tracing::warn!(
+                    "Unsupported ERC20 payment token supplied: {}",
+                    payment.token
+                );
+                return Err(RelayError::UnsupportedPaymentToken(payment.token.clone()));
+            }
+        }
+        "sponsored" => {}
+        _ => {
+            tracing::warn!("Unsupported payment type: {}", payment.payment_type);
+            return Err(RelayError::UnsupportedCapability(
+                payment.payment_type.clone(),
+            ));
         }
     }
+    Ok(())
+}
+
+/// Classify a node's refusal of a relay transaction: underpriced or pool-full rejections
+/// mean the chain is congested and the relay can be retried, anything else is a plain
+/// broadcast failure
+fn broadcast_error(chain_id: u64, error: &str) -> RelayError {
+    let error = error.to_lowercase();
+    let congested = [
+        "underpriced",
+        "txpool is full",
+        "pool is full",
+        "fee too low",
+    ]
+    .iter()
+    .any(|pattern| error.contains(pattern));
+    if congested {
+        RelayError::ChainCongested(chain_id.to_string())
+    } else {
+        RelayError::BroadcastFailed(chain_id.to_string())
+    }
+}
+
+/// Reject calldata larger than the configured limit
+fn check_calldata_size(data: &str, cfg: &Config) -> Result<(), RelayError> {
+    let bytes = data.trim_start_matches("0x").len().div_ceil(2) as u64;
+    let limit = cfg.max_calldata_bytes();
+    if bytes > limit {
+        tracing::warn!(
+            "Calldata of {} bytes exceeds the {} byte limit",
+            bytes,
+            limit
+        );
+        return Err(RelayError::TransactionTooLarge {
+            unit: "bytes",
+            size: bytes,
+            limit,
+        });
+    }
+    Ok(())
+}
 
+/// Reject gas estimates above the configured per-transaction limit
+fn check_gas_limit(gas: u64, cfg: &Config) -> Result<(), String> {
+    let limit = cfg.max_gas();
+    if gas > limit {
+        return Err(format!(
+            "Transaction needs {} gas; the limit is {}",
+            gas, limit
+        ));
+    }
     Ok(())
 }
 
+/// The sponsorship project whose API key is `api_key`, if any
+fn find_sponsorship_project(api_key: &str, cfg: &Config) -> Option<SponsorshipProject> {
+    cfg.sponsorship_projects()
+        .into_iter()
+        .find(|project| secrets_match(&project.api_key, api_key))
+}
+
+/// When sponsorship API keys or projects are configured, a sponsored relay must present a key
+/// in the payment `data` field. Returns the project to charge, or `None` for an `apiKeys` key
+/// (unmetered) and when sponsorship is open.
+fn check_sponsorship_key(
+    payment: &PaymentCapability,
+    cfg: &Config,
+) -> Result<Option<SponsorshipProject>, RelayError> {
+    if let Some(project) = find_sponsorship_project(&payment.data, cfg) {
+        return Ok(Some(project));
+    }
+    let keys = cfg.sponsorship_api_keys();
+    if (keys.is_empty() && cfg.sponsorship_projects().is_empty())
+        || keys.iter().any(|key| secrets_match(key, &payment.data))
+    {
+        return Ok(None);
+    }
+    tracing::warn!("Sponsored relay rejected: missing or unknown API key");
+    Err(RelayError::Unauthorized)
+}
+
+/// Sponsorship billing period containing `now` (`YYYY-MM`, UTC) and the instant it ends
+fn billing_period(now: chrono::DateTime<Utc>) -> (String, chrono::DateTime<Utc>) {
+    let (year, month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    let resets_at = Utc
+        .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now);
+    (now.format("%Y-%m").to_string(), resets_at)
+}
+
+/// `QuotaExceeded` for a sponsorship allowance, retryable once the billing period resets
+fn sponsorship_quota_exceeded() -> RelayError {
+    let now = Utc::now();
+    let (_, resets_at) = billing_period(now);
+    RelayError::QuotaExceeded((resets_at - now).num_seconds().max(0) as u64)
+}
+
+/// Charge a sponsored relay's gas to its project, failing with `QuotaExceeded` when the
+/// project's monthly allowance cannot cover it
+async fn reserve_sponsored_gas(
+    storage: &Storage,
+    project: &SponsorshipProject,
+    period: &str,
+    gas: u64,
+) -> Result<(), RelayError> {
+    let allowance = project.monthly_gas.unwrap_or(u64::MAX);
+    match storage
+        .reserve_sponsored_gas(&project.id, period, gas, allowance)
+        .await
+    {
+        Ok(Some(used)) => {
+            tracing::info!(
+                "Charged {} sponsored gas to project {} ({} used in {})",
+                gas,
+                project.id,
+                used,
+                period
+            );
+            Ok(())
+        }
+        Ok(None) => {
+            tracing::warn!(
+                "Sponsored relay rejected: project {} has no allowance left for {} gas in {}",
+                project.id,
+                gas,
+                period
+            );
+            Err(sponsorship_quota_exceeded())
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to charge sponsored gas to project {}: {}",
+                project.id,
+                e
+            );
+            Err(RelayError::Internal)
+        }
+    }
+}
+
+/// Give back gas charged for a sponsored relay that was never broadcast
+async fn release_sponsored_gas(
+    storage: &Storage,
+    project: &SponsorshipProject,
+    period: &str,
+    gas: u64,
+) {
+    if let Err(e) = storage
+        .release_sponsored_gas(&project.id, period, gas)
+        .await
+    {
+        tracing::error!(
+            "Failed to release sponsored gas of project {}: {}",
+            project.id,
+            e
+        );
+    }
+}
+
 fn parse_hex_u256(value: &str) -> Option<U256> {
     let trimmed = value.trim_start_matches("0x");
     if trimmed.is_empty() {
@@ -161,6 +523,11 @@ fn parse_hex_u256(value: &str) -> Option<U256> {
     U256::from_str_radix(trimmed, 16).ok()
 }
 
+/// Wei sent with a relayed call, from its optional hex `value`; `None` if it is malformed
+fn call_value(value: Option<&String>) -> Option<U256> {
+    value.map_or(Some(U256::ZERO), |value| params::quantity(value).ok())
+}
+
 pub struct RpcServer {
     host: String,
     port: u16,
@@ -168,53 +535,85 @@ pub struct RpcServer {
     config: Config,
 }
 
-/// Load the wallet ABI from the JSON file
-fn load_wallet_abi() -> Result<JsonAbi, anyhow::Error> {
-    let abi_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("resources")
-        .join("abi.json");
-
-    let abi_content = std::fs::read_to_string(&abi_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read ABI file at {:?}: {}", abi_path, e))?;
+/// Wallet ABI shipped with the binary, used unless config.json sets `walletAbiPath`
+const DEFAULT_WALLET_ABI: &str = include_str!("../resources/abi.json");
 
-    let abi_json: serde_json::Value = serde_json::from_str(&abi_content)
+/// Parse a wallet ABI given either as a compiler artifact (`{ "abi": [...] }`) or as a
+/// bare ABI array
+fn parse_wallet_abi(abi_content: &str) -> Result<JsonAbi, anyhow::Error> {
+    let abi_json: serde_json::Value = serde_json::from_str(abi_content)
         .map_err(|e| anyhow::anyhow!("Failed to parse ABI JSON: {}", e))?;
 
     // Extract the 'abi' field from the JSON
-    let abi_array = abi_json
-        .get("abi")
-        .ok_or_else(|| anyhow::anyhow!("ABI JSON missing 'abi' field"))?;
+    let abi_array = match abi_json {
+        serde_json::Value::Object(mut artifact) => artifact
+            .remove("abi")
+            .ok_or_else(|| anyhow::anyhow!("ABI JSON missing 'abi' field"))?,
+        other => other,
+    };
 
-    let abi: JsonAbi = serde_json::from_value(abi_array.clone())
+    let abi: JsonAbi = serde_json::from_value(abi_array)
         .map_err(|e| anyhow::anyhow!("Failed to deserialize ABI: {}", e))?;
 
     Ok(abi)
 }
 
-/// Get the relayer's private key from environment variable
-fn get_relayer_private_key(cfg: &Config) -> Result<String, String> {
-    cfg.get_relayer_private_key()
-        .ok_or_else(|| "RELAYX_PRIVATE_KEY configuration missing".to_string())
+/// Wallet ABI, parsed once per process from the configured override or the embedded default
+fn load_wallet_abi(cfg: &Config) -> Result<&'static JsonAbi, anyhow::Error> {
+    static WALLET_ABI: OnceLock<Result<JsonAbi, String>> = OnceLock::new();
+    WALLET_ABI
+        .get_or_init(|| {
+            let abi = match cfg.wallet_abi_path() {
+                Some(path) => std::fs::read_to_string(&path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read ABI file at {:?}: {}", path, e))
+                    .and_then(|content| parse_wallet_abi(&content)),
+                None => parse_wallet_abi(DEFAULT_WALLET_ABI),
+            };
+            abi.map_err(|e| e.to_string())
+        })
+        .as_ref()
+        .map_err(|e| anyhow::anyhow!("{}", e))
 }
 
-/// Fetch current gas price for the given chain using the configured RPC provider.
+/// Fetch current gas price for the given chain through its configured gas oracle strategy,
+/// falling back to the node's `eth_gasPrice` when the strategy has no answer, and bounded by
+/// the oracle's floor and ceiling. Prices are cached for [`Config::gas_price_ttl`] so bursts
+/// of sends and quotes share one lookup.
 async fn fetch_gas_price(chain_id: u64, cfg: &Config) -> Result<String, String> {
-    if stub_mode_enabled() {
-        tracing::debug!(
-            "Stub mode enabled: returning default gas price for chain {}",
-            chain_id
-        );
-        return Ok("0x4a817c800".to_string());
-    }
-
     let rpc_url = cfg
         .rpc_url_for_chain(&chain_id.to_string())
         .ok_or_else(|| format!("No RPC URL configured for chain {}", chain_id))?;
+    let oracle = cfg.gas_oracle(&chain_id.to_string());
+
+    let strategy_price = match oracle.strategy {
+        GasPriceStrategy::Node => None,
+        GasPriceStrategy::FeeHistory => {
+            let key = format!("feeHistory:{}:{}", rpc_url, oracle.percentile);
+            cached_strategy_price(&key, cfg.gas_price_ttl(), || {
+                fee_history_gas_price(cfg, &rpc_url, oracle.percentile)
+            })
+            .await
+        }
+        GasPriceStrategy::PolygonGasStation => match &oracle.gas_station_url {
+            Some(url) => {
+                cached_strategy_price(url, cfg.gas_price_ttl(), || gas_station_price(url)).await
+            }
+            None => {
+                tracing::warn!("No gas station URL configured for chain {}", chain_id);
+                None
+            }
+        },
+    };
+    let gas_price = match strategy_price {
+        Some(gas_price) => Ok(gas_price),
+        None => {
+            cfg.chain
+                .cached_gas_price(&rpc_url, cfg.gas_price_ttl())
+                .await
+        }
+    };
 
-    let rpc_endpoint = Url::parse(&rpc_url).map_err(|e| format!("Invalid RPC URL: {}", e))?;
-    let provider = ProviderBuilder::new().on_hyper_http(rpc_endpoint);
-
-    match provider.get_gas_price().await {
+    match gas_price.map(|gas_price| oracle.clamp(gas_price)) {
         Ok(gas_price) => {
             let gas_price_hex = format!("0x{:x}", gas_price);
             tracing::debug!(
@@ -232,93 +631,662 @@ async fn fetch_gas_price(chain_id: u64, cfg: &Config) -> Result<String, String>
     }
 }
 
-/// Simple helper to bump hex gas price by given percent (e.g., 20 => +20%)
-fn bump_gas_price_hex(gas_price_hex: &str, percent: u64) -> String {
-    let s = gas_price_hex.strip_prefix("0x").unwrap_or(gas_price_hex);
-    if let Ok(mut v) = u128::from_str_radix(s, 16) {
-        v = v + (v * percent as u128 / 100u128);
-        return format!("0x{:x}", v);
+/// Transaction types detected by probing each chain's latest block, keyed by chain id
+fn tx_type_cache() -> &'static Mutex<HashMap<u64, TxType>> {
+    static CACHE: OnceLock<Mutex<HashMap<u64, TxType>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Probe whether `chain_id` is past London from `baseFeePerGas` on its latest block and
+/// remember the answer for [`tx_type`]
+async fn probe_tx_type(chain_id: u64, rpc_url: &str, cfg: &Config) -> Result<TxType, String> {
+    let model = match cfg.chain.base_fee(rpc_url).await? {
+        Some(_) => TxType::Eip1559,
+        None => TxType::Legacy,
+    };
+    tx_type_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(chain_id, model);
+    Ok(model)
+}
+
+/// Probe every configured chain without a `txType` override, so the first relay on each
+/// does not pay for the lookup
+async fn detect_tx_types(cfg: &Config) {
+    let probes = cfg
+        .configured_chain_ids()
+        .into_iter()
+        .map(|chain_id| async move {
+            if cfg.tx_type_override(&chain_id.to_string()).is_some() {
+                return;
+            }
+            let Some(rpc_url) = cfg.rpc_url_for_chain(&chain_id.to_string()) else {
+                return;
+            };
+            match probe_tx_type(chain_id, &rpc_url, cfg).await {
+                Ok(model) => tracing::info!("Chain {} uses {:?} transactions", chain_id, model),
+                Err(e) => tracing::warn!(
+                    "Transaction type probe failed for chain {}: {}",
+                    chain_id,
+                    e
+                ),
+            }
+        });
+    join_all(probes).await;
+}
+
+/// Transaction type to build on `chain_id`: the configured override, else the probed one.
+/// A chain not probed yet is probed now; while that fails it is treated as legacy.
+async fn tx_type(chain_id: u64, rpc_url: &str, cfg: &Config) -> TxType {
+    if let Some(model) = cfg.tx_type_override(&chain_id.to_string()) {
+        return model;
+    }
+    let cached = tx_type_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&chain_id)
+        .copied();
+    match cached {
+        Some(model) => model,
+        None => probe_tx_type(chain_id, rpc_url, cfg)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Transaction type probe failed for chain {}: {}",
+                    chain_id,
+                    e
+                );
+                TxType::Legacy
+            }),
     }
-    gas_price_hex.to_string()
 }
 
-/// Send a transaction on-chain by calling executeWithRelayer on the wallet
-async fn send_relay_transaction(
-    wallet_address: &str,
-    calldata: &str,
+/// Price `tx` at `gas_price` in the chain's transaction type. EIP-1559 transactions cap the
+/// fee at `gas_price`, so they never cost more than was charged, and tip whatever the current
+/// base fee leaves. With `full_tip` the tip is the whole cap, which a replacement needs so
+/// both of its fees rise over the transaction it replaces.
+async fn set_gas_fees(
+    tx: &mut TransactionRequest,
     chain_id: u64,
-    gas_limit: u64,
-    gas_price_hex: &str,
+    rpc_url: &str,
+    gas_price: u128,
+    full_tip: bool,
     cfg: &Config,
-) -> Result<String, String> {
-    tracing::info!(
-        "Preparing to send relay transaction to wallet {} on chain {}",
-        wallet_address,
-        chain_id
-    );
+) {
+    match tx_type(chain_id, rpc_url, cfg).await {
+        TxType::Legacy => tx.gas_price = Some(gas_price),
+        TxType::Eip1559 => {
+            let base_fee = if full_tip {
+                None
+            } else {
+                cfg.chain.base_fee(rpc_url).await.ok().flatten()
+            };
+            tx.max_fee_per_gas = Some(gas_price);
+            tx.max_priority_fee_per_gas =
+                Some(base_fee.map_or(gas_price, |base_fee| gas_price.saturating_sub(base_fee)));
+        }
+    }
+}
 
-    if stub_mode_enabled() {
-        tracing::info!(
-            "Stub mode enabled: returning synthetic transaction hash for wallet {}",
-            wallet_address
-        );
-        let fake_hash = format!("0x{}", hex::encode(Uuid::new_v4().as_bytes()));
-        return Ok(fake_hash);
+/// Relays sent on the fallback gas limit after a failed simulation, per chain, with the start
+/// of the hour they are counted in
+fn fallback_relay_counts() -> &'static Mutex<HashMap<u64, (Instant, u32)>> {
+    static COUNTS: OnceLock<Mutex<HashMap<u64, (Instant, u32)>>> = OnceLock::new();
+    COUNTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Gas limit to relay with after simulation failed, or `None` when the relay must be rejected.
+/// With `reserve`, the relay counts against the `fallbackWithCap` hourly cap.
+fn fallback_gas_after_failed_simulation(chain_id: u64, reserve: bool, cfg: &Config) -> Option<u64> {
+    fallback_allowed(cfg.simulation_failure_mode(), chain_id, reserve)
+        .then(|| cfg.fallback_gas_limit(chain_id))
+}
+
+fn fallback_allowed(mode: SimulationFailureMode, chain_id: u64, reserve: bool) -> bool {
+    match mode {
+        SimulationFailureMode::Reject => return false,
+        SimulationFailureMode::Fallback => {}
+        SimulationFailureMode::FallbackWithCap(cap) => {
+            let mut counts = fallback_relay_counts()
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let (since, count) = counts.entry(chain_id).or_insert((now, 0));
+            if now.duration_since(*since) >= Duration::from_secs(3600) {
+                (*since, *count) = (now, 0);
+            }
+            if *count >= cap {
+                tracing::warn!(
+                    "Fallback cap of {} relays per hour reached on chain {}",
+                    cap,
+                    chain_id
+                );
+                return false;
+            }
+            if reserve {
+                *count += 1;
+            }
+        }
+    }
+    true
+}
+
+/// Gas prices from non-node strategies, keyed by source, with the instant they go stale
+fn strategy_price_cache() -> &'static Mutex<HashMap<String, (Instant, u128)>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, (Instant, u128)>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Answer from the strategy price cache under `key`, or from `fetch` when stale or missing
+async fn cached_strategy_price<F, Fut>(key: &str, ttl: Duration, fetch: F) -> Option<u128>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Option<u128>>,
+{
+    let now = Instant::now();
+    if let Some((_, gas_price)) = strategy_price_cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(key)
+        .filter(|(stale_at, _)| !ttl.is_zero() && now < *stale_at)
+    {
+        return Some(*gas_price);
     }
+    let gas_price = fetch().await?;
+    if !ttl.is_zero() {
+        strategy_price_cache()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), (now + ttl, gas_price));
+    }
+    Some(gas_price)
+}
 
-    // Get private key for signing
-    let private_key = get_relayer_private_key(cfg)?;
+/// Next block's base fee plus the median, over the last 10 blocks, of the priority fee paid
+/// at `percentile`. `None` when the chain has no usable fee history.
+async fn fee_history_gas_price(cfg: &Config, rpc_url: &str, percentile: f64) -> Option<u128> {
+    let history = match cfg.chain.fee_history(rpc_url, 10, &[percentile]).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::debug!("Fee history unavailable from {}: {}", rpc_url, e);
+            return None;
+        }
+    };
+    let base_fee = history.base_fee_per_gas.last().copied()?;
+    let mut tips: Vec<u128> = history
+        .reward
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|block| block.first().copied())
+        .collect();
+    if tips.is_empty() {
+        return None;
+    }
+    tips.sort_unstable();
+    Some(base_fee.saturating_add(tips[tips.len() / 2]))
+}
 
-    // Parse private key and create signer
-    let signer = private_key
-        .parse::<PrivateKeySigner>()
-        .map_err(|e| format!("Failed to parse private key: {}", e))?;
+/// Bound on a gas station request
+const GAS_STATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fetch the `standard` max fee from a Polygon gas station endpoint, in wei
+async fn gas_station_price(url: &str) -> Option<u128> {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    let client = CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(GAS_STATION_TIMEOUT)
+            .build()
+            .unwrap_or_default()
+    });
+    let response = match client.get(url).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!("Gas station {} unreachable: {}", url, e);
+            return None;
+        }
+    };
+    match response.json::<serde_json::Value>().await {
+        Ok(body) => parse_gas_station_price(&body).or_else(|| {
+            tracing::warn!("Gas station {} returned no standard max fee", url);
+            None
+        }),
+        Err(e) => {
+            tracing::warn!("Gas station {} returned invalid JSON: {}", url, e);
+            None
+        }
+    }
+}
 
-    let relayer_address = signer.address();
-    tracing::debug!("Relayer address: 0x{:x}", relayer_address);
+/// Read `standard.maxFee` (gwei) from a Polygon gas station response, in wei
+fn parse_gas_station_price(body: &serde_json::Value) -> Option<u128> {
+    body.get("standard")?
+        .get("maxFee")?
+        .as_f64()
+        .filter(|gwei| *gwei > 0.0)
+        .map(|gwei| (gwei * 1e9).ceil() as u128)
+}
 
-    // Create wallet from signer
-    let wallet = EthereumWallet::from(signer);
+/// OP-stack GasPriceOracle predeploy
+const OP_GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000F";
+/// Arbitrum NodeInterface virtual contract (only reachable through eth_call)
+const ARB_NODE_INTERFACE: &str = "0x00000000000000000000000000000000000000C8";
+/// Arbitrum ArbGasInfo precompile
+const ARB_GAS_INFO: &str = "0x000000000000000000000000000000000000006C";
 
-    // Get RPC URL for the chain
+/// Execute a raw eth_call against the chain's configured RPC
+async fn eth_call_raw(
+    chain_id: u64,
+    to: &str,
+    calldata: Vec<u8>,
+    cfg: &Config,
+) -> Result<Vec<u8>, String> {
     let rpc_url = cfg
         .rpc_url_for_chain(&chain_id.to_string())
         .ok_or_else(|| format!("No RPC URL configured for chain {}", chain_id))?;
+    let to: Address = to
+        .parse()
+        .map_err(|e| format!("Invalid call target: {}", e))?;
+    cfg.chain
+        .call(&rpc_url, to, Bytes::from(calldata), U256::ZERO)
+        .await
+        .map(|bytes| bytes.to_vec())
+}
 
-    // Create provider with wallet for signing
-    let rpc_endpoint = Url::parse(&rpc_url).map_err(|e| format!("Invalid RPC URL: {}", e))?;
-    let provider = ProviderBuilder::new()
-        .with_recommended_fillers()
-        .wallet(wallet)
-        .on_hyper_http(rpc_endpoint);
+/// ABI-encode a dynamic `bytes` value (length word followed by right-padded data)
+fn abi_encode_bytes_tail(data: &[u8]) -> Vec<u8> {
+    let mut out = U256::from(data.len()).to_be_bytes::<32>().to_vec();
+    out.extend_from_slice(data);
+    let padding = (32 - data.len() % 32) % 32;
+    out.resize(out.len() + padding, 0u8);
+    out
+}
 
-    // Parse wallet address
-    let to_address: Address = wallet_address
-        .parse()
-        .map_err(|e| format!("Invalid wallet address: {}", e))?;
+/// Read the 32-byte word at `index` of an ABI-encoded return value
+fn abi_word(out: &[u8], index: usize) -> Option<U256> {
+    let start = index * 32;
+    out.get(start..start + 32).map(U256::from_be_slice)
+}
 
-    // Parse calldata (should already be hex encoded)
-    let calldata_bytes = if let Some(stripped) = calldata.strip_prefix("0x") {
-        hex::decode(stripped).map_err(|e| format!("Invalid calldata hex: {}", e))?
-    } else {
-        hex::decode(calldata).map_err(|e| format!("Invalid calldata hex: {}", e))?
+/// Estimate the L1 data fee (in wei) a relay of `calldata` to `to` incurs on an L2.
+/// Returns zero for chains without a fee model. On OP-stack chains the oracle expects the
+/// signed transaction, so the calldata is padded with a conservative signature/envelope
+/// overhead. On Arbitrum the NodeInterface L1 component is added on top of the L2 gas,
+/// which errs on the side of overcharging.
+async fn fetch_l1_data_fee(chain_id: u64, to: &str, calldata: &str, cfg: &Config) -> U256 {
+    let model = match cfg.fee_model(&chain_id.to_string()) {
+        Some(model) => model,
+        None => return U256::ZERO,
+    };
+    let data = match hex::decode(calldata.trim_start_matches("0x")) {
+        Ok(data) => data,
+        Err(_) => return U256::ZERO,
     };
 
-    // Parse gas price
-    let gas_price_value = if let Some(stripped) = gas_price_hex.strip_prefix("0x") {
-        u128::from_str_radix(stripped, 16).map_err(|e| format!("Invalid gas price hex: {}", e))?
-    } else {
-        u128::from_str_radix(gas_price_hex, 16)
-            .map_err(|e| format!("Invalid gas price hex: {}", e))?
+    let result = match model.as_str() {
+        "optimism" => {
+            let mut tx_bytes = data;
+            tx_bytes.resize(tx_bytes.len() + 68, 0xff);
+            // getL1Fee(bytes)
+            let mut input = vec![0x49, 0x94, 0x8e, 0x0e];
+            input.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+            input.extend(abi_encode_bytes_tail(&tx_bytes));
+            eth_call_raw(chain_id, OP_GAS_PRICE_ORACLE, input, cfg)
+                .await
+                .and_then(|out| abi_word(&out, 0).ok_or_else(|| "short response".to_string()))
+        }
+        "arbitrum" => {
+            let target: Address = to.parse().unwrap_or_default();
+            // gasEstimateL1Component(address,bool,bytes)
+            let mut input = vec![0x77, 0xd4, 0x88, 0xa2];
+            input.extend_from_slice(&[0u8; 12]);
+            input.extend_from_slice(target.as_slice());
+            input.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+            input.extend_from_slice(&U256::from(96).to_be_bytes::<32>());
+            input.extend(abi_encode_bytes_tail(&data));
+            eth_call_raw(chain_id, ARB_NODE_INTERFACE, input, cfg)
+                .await
+                .and_then(|out| {
+                    let l1_gas = abi_word(&out, 0).ok_or_else(|| "short response".to_string())?;
+                    let base_fee = abi_word(&out, 1).ok_or_else(|| "short response".to_string())?;
+                    Ok(l1_gas.saturating_mul(base_fee))
+                })
+        }
+        other => Err(format!("unknown fee model '{}'", other)),
     };
 
-    // Get nonce for the relayer address
-    let nonce = provider
-        .get_transaction_count(relayer_address)
-        .await
-        .map_err(|e| format!("Failed to get nonce: {}", e))?;
+    match result {
+        Ok(fee) => {
+            tracing::debug!(
+                "L1 data fee for chain {} ({}): {} wei",
+                chain_id,
+                model,
+                fee
+            );
+            fee
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch L1 data fee for chain {}: {}", chain_id, e);
+            U256::ZERO
+        }
+    }
+}
 
-    tracing::debug!(
+/// Fetch the L1 base fee reported by the chain's L2 gas oracle, hex-encoded
+async fn fetch_l1_base_fee(chain_id: u64, cfg: &Config) -> Option<String> {
+    let (oracle, selector) = match cfg.fee_model(&chain_id.to_string())?.as_str() {
+        // l1BaseFee()
+        "optimism" => (OP_GAS_PRICE_ORACLE, vec![0x51, 0x9b, 0x4b, 0xd3]),
+        // getL1BaseFeeEstimate()
+        "arbitrum" => (ARB_GAS_INFO, vec![0xf5, 0xd6, 0xde, 0xd7]),
+        _ => return None,
+    };
+    match eth_call_raw(chain_id, oracle, selector, cfg).await {
+        Ok(out) => abi_word(&out, 0).map(|fee| format!("0x{:x}", fee)),
+        Err(e) => {
+            tracing::warn!("Failed to fetch L1 base fee for chain {}: {}", chain_id, e);
+            None
+        }
+    }
+}
+
+/// Suggested EIP-1559 `(maxFeePerGas, maxPriorityFeePerGas)` in wei: the median tip over
+/// recent blocks, and a cap of twice the next block's base fee plus that tip. `None` when
+/// the chain has no usable fee history.
+async fn fetch_eip1559_fees(chain_id: u64, cfg: &Config) -> Option<(u128, u128)> {
+    let rpc_url = cfg.rpc_url_for_chain(&chain_id.to_string())?;
+    let history = match cfg.chain.fee_history(&rpc_url, 10, &[50.0]).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::debug!("Fee history unavailable for chain {}: {}", chain_id, e);
+            return None;
+        }
+    };
+
+    let base_fee = *history.base_fee_per_gas.last()?;
+    let mut tips: Vec<u128> = history
+        .reward?
+        .iter()
+        .filter_map(|block| block.first().copied())
+        .collect();
+    if tips.is_empty() {
+        return None;
+    }
+    tips.sort_unstable();
+    let tip = tips[tips.len() / 2];
+    Some((base_fee.saturating_mul(2).saturating_add(tip), tip))
+}
+
+/// Gas price for a relay in `speed`'s lane: the next base fee plus the lane's priority fee
+/// percentile over recent blocks, or the node's suggestion for lanes without a percentile
+/// and when fee history is unavailable
+async fn lane_gas_price(chain_id: u64, speed: Speed, cfg: &Config) -> Result<String, String> {
+    let network = fetch_gas_price(chain_id, cfg).await?;
+    let Some(percentile) = cfg.speed_lane(&chain_id.to_string(), speed).percentile else {
+        return Ok(network);
+    };
+    let Some(rpc_url) = cfg.rpc_url_for_chain(&chain_id.to_string()) else {
+        return Ok(network);
+    };
+    let Some(price) = fee_history_gas_price(cfg, &rpc_url, percentile).await else {
+        return Ok(network);
+    };
+    let price = cfg.gas_oracle(&chain_id.to_string()).clamp(price);
+    tracing::debug!(
+        "Gas price for chain {} in the {:?} lane: {} wei",
+        chain_id,
+        speed,
+        price
+    );
+    Ok(format!("0x{:x}", price))
+}
+
+/// Fetch the latest block number for the given chain, bounded by a short timeout so a
+/// dead RPC cannot stall the caller.
+async fn fetch_block_number(chain_id: u64, cfg: &Config) -> Result<u64, String> {
+    let rpc_url = cfg
+        .rpc_url_for_chain(&chain_id.to_string())
+        .ok_or_else(|| format!("No RPC URL configured for chain {}", chain_id))?;
+
+    match tokio::time::timeout(Duration::from_secs(5), cfg.chain.block_number(&rpc_url)).await {
+        Ok(result) => result,
+        Err(_) => Err("Timed out fetching block number".to_string()),
+    }
+}
+
+/// Reward percentiles sampled from `eth_feeHistory` and the blocks a transaction tipping at
+/// each of them typically waits for inclusion
+const INCLUSION_PERCENTILES: [(f64, u64); 5] =
+    [(90.0, 1), (75.0, 1), (50.0, 2), (25.0, 3), (10.0, 6)];
+/// Blocks assumed when the tip is below the 10th percentile
+const SLOW_INCLUSION_BLOCKS: u64 = 12;
+/// Blocks assumed when the gas price does not even cover the current base fee
+const STALLED_INCLUSION_BLOCKS: u64 = 20;
+/// Relays the relayer expects to land per block; deeper queues push the estimate out
+const RELAYS_PER_BLOCK: u64 = 8;
+
+/// Blocks a relay paying `gas_price_hex` should wait, judged by where its tip falls among
+/// the priority fees paid over the last few blocks. Falls back to the median when fee
+/// history is unavailable.
+async fn blocks_until_inclusion(chain_id: u64, gas_price_hex: &str, cfg: &Config) -> u64 {
+    let median = INCLUSION_PERCENTILES[2].1;
+    let (Some(rpc_url), Some(gas_price)) = (
+        cfg.rpc_url_for_chain(&chain_id.to_string()),
+        parse_hex_u256(gas_price_hex).and_then(|v| u128::try_from(v).ok()),
+    ) else {
+        return median;
+    };
+
+    let percentiles: Vec<f64> = INCLUSION_PERCENTILES.iter().map(|(p, _)| *p).collect();
+    let history = match cfg.chain.fee_history(&rpc_url, 10, &percentiles).await {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::debug!("Fee history unavailable for chain {}: {}", chain_id, e);
+            return median;
+        }
+    };
+    let rewards = match history.reward.filter(|r| !r.is_empty()) {
+        Some(rewards) => rewards,
+        None => return median,
+    };
+
+    let base_fee = history.base_fee_per_gas.last().copied().unwrap_or(0);
+    let tip = match gas_price.checked_sub(base_fee) {
+        Some(tip) => tip,
+        None => return STALLED_INCLUSION_BLOCKS,
+    };
+
+    for (index, (_, blocks)) in INCLUSION_PERCENTILES.iter().enumerate() {
+        let mut tips: Vec<u128> = rewards
+            .iter()
+            .filter_map(|block| block.get(index).copied())
+            .collect();
+        if tips.is_empty() {
+            continue;
+        }
+        tips.sort_unstable();
+        if tip >= tips[tips.len() / 2] {
+            return *blocks;
+        }
+    }
+    SLOW_INCLUSION_BLOCKS
+}
+
+/// Rough seconds until a relay paying `gas_price_hex` is mined with `queue_depth` relays
+/// ahead of it on the same chain
+async fn estimate_confirmation_seconds(
+    chain_id: u64,
+    gas_price_hex: &str,
+    queue_depth: u64,
+    cfg: &Config,
+) -> u64 {
+    let blocks =
+        blocks_until_inclusion(chain_id, gas_price_hex, cfg).await + queue_depth / RELAYS_PER_BLOCK;
+    let block_time = cfg.block_time_seconds(&chain_id.to_string());
+    (blocks as f64 * block_time).ceil() as u64
+}
+
+/// Confirmation estimate for a stored pending request, queued behind older in-flight
+/// requests on its chain
+async fn estimate_pending_confirmation(storage: &Storage, id: &str, cfg: &Config) -> Option<u64> {
+    let req = storage
+        .get_request(TenantScope::All, Uuid::parse_str(id).ok()?)
+        .await
+        .ok()??;
+    let queue_depth = storage
+        .count_in_flight(req.chain_id, Some(req.created_at))
+        .await
+        .unwrap_or(0);
+    Some(estimate_confirmation_seconds(req.chain_id, &req.gas_price, queue_depth, cfg).await)
+}
+
+/// Derive the relayer's signing address from the configured private key or remote signer
+fn relayer_address(cfg: &Config) -> Option<String> {
+    match cfg.relayer_signer()? {
+        Ok(signer) => Some(format!("0x{:x}", signer.address())),
+        Err(e) => {
+            tracing::warn!("Failed to load relayer key: {}", e);
+            None
+        }
+    }
+}
+
+/// Simple helper to bump hex gas price by given percent (e.g., 20 => +20%)
+fn bump_gas_price_hex(gas_price_hex: &str, percent: u64) -> String {
+    let s = gas_price_hex.strip_prefix("0x").unwrap_or(gas_price_hex);
+    if let Ok(mut v) = u128::from_str_radix(s, 16) {
+        v = v + (v * percent as u128 / 100u128);
+        return format!("0x{:x}", v);
+    }
+    gas_price_hex.to_string()
+}
+
+/// Blob gas consumed by each blob (2^17)
+const BLOB_GAS_PER_BLOB: u128 = 131_072;
+/// Most blobs a relayed transaction may carry
+const MAX_BLOBS_PER_TRANSACTION: usize = 6;
+
+/// Check that a blob sidecar has between one and [`MAX_BLOBS_PER_TRANSACTION`] blobs, each
+/// with a commitment and proof. Blob transactions cannot also carry an EIP-7702
+/// authorization list.
+fn validate_blob_sidecar(
+    sidecar: &BlobTransactionSidecar,
+    authorization_list: &str,
+) -> Result<(), RelayError> {
+    let blobs = sidecar.blobs.len();
+    if blobs == 0 || blobs > MAX_BLOBS_PER_TRANSACTION {
+        tracing::warn!("Blob sidecar with {} blobs rejected", blobs);
+        return Err(RelayError::invalid_params(format!(
+            "blobSidecar must carry 1 to {} blobs",
+            MAX_BLOBS_PER_TRANSACTION
+        )));
+    }
+    if sidecar.commitments.len() != blobs || sidecar.proofs.len() != blobs {
+        tracing::warn!(
+            "Blob sidecar mismatch: {} blobs, {} commitments, {} proofs",
+            blobs,
+            sidecar.commitments.len(),
+            sidecar.proofs.len()
+        );
+        return Err(RelayError::invalid_params(
+            "blobSidecar needs one commitment and one proof per blob",
+        ));
+    }
+    if !authorization_list.trim().is_empty() {
+        tracing::warn!("Blob sidecar rejected alongside an authorization list");
+        return Err(RelayError::invalid_params(
+            "blobSidecar cannot be combined with authorizationList",
+        ));
+    }
+    Ok(())
+}
+
+/// Blob gas price cap for a blob relay: twice the current blob base fee, leaving room for
+/// it to rise before inclusion. Fails on chains without EIP-4844.
+async fn fetch_max_fee_per_blob_gas(chain_id: u64, cfg: &Config) -> Result<u128, String> {
+    let rpc_url = cfg
+        .rpc_url_for_chain(&chain_id.to_string())
+        .ok_or_else(|| format!("No RPC URL configured for chain {}", chain_id))?;
+    let base_fee = cfg.chain.blob_base_fee(&rpc_url).await?;
+    Ok(base_fee.saturating_mul(2).max(1))
+}
+
+/// Blob gas fee in wei for `blob_count` blobs at `max_fee_per_blob_gas`
+fn blob_fee(blob_count: usize, max_fee_per_blob_gas: u128) -> U256 {
+    U256::from(BLOB_GAS_PER_BLOB)
+        .saturating_mul(U256::from(blob_count))
+        .saturating_mul(U256::from(max_fee_per_blob_gas))
+}
+
+/// How [`send_relay_transaction`] builds a transaction beyond its target, calldata and gas
+#[derive(Debug, Default, Clone, Copy)]
+struct SendOptions<'a> {
+    /// Nonce of the stuck transaction this one replaces; a new nonce is reserved otherwise
+    replaces: Option<u64>,
+    /// Blobs and blob gas price cap, sent as a type-3 transaction
+    blobs: Option<(&'a BlobTransactionSidecar, u128)>,
+    /// Native currency sent along with the call, in wei
+    value: U256,
+}
+
+/// Send a transaction on-chain by calling executeWithRelayer on the wallet.
+/// Returns the transaction hash and the nonce it was sent with.
+async fn send_relay_transaction(
+    wallet_address: &str,
+    calldata: &str,
+    chain_id: u64,
+    gas_limit: u64,
+    gas_price_hex: &str,
+    options: SendOptions<'_>,
+    cfg: &Config,
+) -> Result<(String, u64), String> {
+    tracing::info!(
+        "Preparing to send relay transaction to wallet {} on chain {}",
+        wallet_address,
+        chain_id
+    );
+
+    // Load the signer: the private key, or the remote signer holding the key
+    let signer = cfg
+        .relayer_signer_for_chain(&chain_id.to_string())
+        .ok_or_else(|| "RELAYX_PRIVATE_KEY configuration missing".to_string())??;
+
+    let relayer_address = signer.address();
+    tracing::debug!("Relayer address: 0x{:x}", relayer_address);
+
+    // Get RPC URL for the chain
+    let rpc_url = cfg
+        .rpc_url_for_chain(&chain_id.to_string())
+        .ok_or_else(|| format!("No RPC URL configured for chain {}", chain_id))?;
+
+    // Parse wallet address
+    let to_address: Address = wallet_address
+        .parse()
+        .map_err(|e| format!("Invalid wallet address: {}", e))?;
+
+    // Parse calldata (should already be hex encoded)
+    let calldata_bytes = if let Some(stripped) = calldata.strip_prefix("0x") {
+        hex::decode(stripped).map_err(|e| format!("Invalid calldata hex: {}", e))?
+    } else {
+        hex::decode(calldata).map_err(|e| format!("Invalid calldata hex: {}", e))?
+    };
+
+    // Parse gas price
+    let gas_price_value = if let Some(stripped) = gas_price_hex.strip_prefix("0x") {
+        u128::from_str_radix(stripped, 16).map_err(|e| format!("Invalid gas price hex: {}", e))?
+    } else {
+        u128::from_str_radix(gas_price_hex, 16)
+            .map_err(|e| format!("Invalid gas price hex: {}", e))?
+    };
+
+    // New relays reserve the relayer's next nonce from the local tracker. A replacement
+    // reuses the nonce recorded for the stuck transaction it replaces.
+    let nonce = match options.replaces {
+        Some(nonce) => nonce,
+        None => cfg.chain.reserve_nonce(&rpc_url, relayer_address).await?,
+    };
+
+    tracing::debug!(
         "Building transaction - Nonce: {}, Gas limit: {}, Gas price: {} wei",
         nonce,
         gas_limit,
@@ -329,18 +1297,38 @@ async fn send_relay_transaction(
     let mut tx = TransactionRequest::default()
         .to(to_address)
         .input(calldata_bytes.into())
+        .value(options.value)
         .gas_limit(gas_limit);
 
     tx.nonce = Some(nonce);
-    tx.gas_price = Some(gas_price_value);
     tx.chain_id = Some(chain_id);
+    match options.blobs {
+        // Blob transactions are dynamic-fee; a tip equal to the cap pays the legacy price
+        Some((sidecar, max_fee_per_blob_gas)) => {
+            tx.max_fee_per_gas = Some(gas_price_value);
+            tx.max_priority_fee_per_gas = Some(gas_price_value);
+            tx.max_fee_per_blob_gas = Some(max_fee_per_blob_gas);
+            tx.blob_versioned_hashes = Some(sidecar.versioned_hashes().collect());
+            tx.sidecar = Some(sidecar.clone());
+        }
+        None => {
+            set_gas_fees(
+                &mut tx,
+                chain_id,
+                &rpc_url,
+                gas_price_value,
+                options.replaces.is_some(),
+                cfg,
+            )
+            .await
+        }
+    }
 
     tracing::info!("Sending transaction to chain {}...", chain_id);
 
     // Send transaction
-    match provider.send_transaction(tx).await {
-        Ok(pending_tx) => {
-            let tx_hash = *pending_tx.tx_hash();
+    match cfg.chain.send_transaction(&rpc_url, &signer, tx).await {
+        Ok(tx_hash) => {
             let tx_hash_hex = format!("0x{:x}", tx_hash);
 
             tracing::info!(
@@ -349,24 +1337,66 @@ async fn send_relay_transaction(
                 chain_id
             );
 
-            Ok(tx_hash_hex)
+            Ok((tx_hash_hex, nonce))
         }
-        Err(e) => {
-            let error_msg = format!("Failed to send transaction: {}", e);
+        Err(error_msg) => {
             tracing::error!("{}", error_msg);
+            // The reserved nonce may be unused or already taken; re-read it on the next send
+            cfg.chain.resync_nonce(&rpc_url, relayer_address).await;
             // Capture critical transaction sending errors in Sentry
+            #[cfg(feature = "sentry")]
             sentry::capture_message(&error_msg, sentry::Level::Error);
             Err(error_msg)
         }
     }
 }
 
-/// Simulate a transaction and estimate gas consumption
+/// Check that `calldata` calls a function the relayer forwards: the wallet ABI's
+/// `executeWithRelayer`, or `execTransaction` for Safe relays and `aggregate3` for call
+/// batches
+fn check_relay_selector(calldata: &[u8], cfg: &Config) -> Result<(), String> {
+    // Load the ABI and verify the function being called
+    let abi = load_wallet_abi(cfg).map_err(|e| format!("Failed to load wallet ABI: {}", e))?;
+
+    // Check if the calldata is calling executeWithRelayer
+    // The first 4 bytes are the function selector
+    if calldata.len() < 4 {
+        return Err("Calldata too short".to_string());
+    }
+
+    let function_selector = &calldata[..4];
+
+    // Find the executeWithRelayer function
+    let execute_with_relayer_fn = abi
+        .functions()
+        .find(|f| f.name == "executeWithRelayer")
+        .ok_or_else(|| "executeWithRelayer function not found in ABI".to_string())?;
+
+    // Get the expected selector
+    let expected_selector = execute_with_relayer_fn.selector();
+
+    // Verify the selector matches; Safe relays call execTransaction on the Safe, and call
+    // batches aggregate3 on Multicall3, instead
+    if function_selector != expected_selector.as_slice()
+        && function_selector != safe::EXEC_TRANSACTION_SELECTOR
+        && function_selector != multicall::AGGREGATE3_SELECTOR
+    {
+        return Err(format!(
+            "Transaction is not calling executeWithRelayer (expected selector: 0x{}, got: 0x{})",
+            hex::encode(expected_selector),
+            hex::encode(function_selector)
+        ));
+    }
+    Ok(())
+}
+
+/// Simulate a transaction sending `value` wei and estimate gas consumption
 /// Returns the estimated gas on success
 /// If simulation is disabled, returns a default gas limit
 async fn simulate_transaction(
     wallet_address: &str,
     calldata: &str,
+    value: U256,
     chain_id: u64,
     cfg: &Config,
 ) -> Result<u64, String> {
@@ -376,17 +1406,9 @@ async fn simulate_transaction(
             wallet_address,
             chain_id
         );
-        return Ok(150_000);
+        return Ok(cfg.fallback_gas_limit(chain_id));
     }
 
-    if stub_mode_enabled() {
-        tracing::debug!(
-            "Stub mode enabled: skipping on-chain simulation for wallet {} on chain {}",
-            wallet_address,
-            chain_id
-        );
-        return Ok(150_000);
-    }
     // Get RPC URL for the chain
     let rpc_url = cfg
         .rpc_url_for_chain(&chain_id.to_string())
@@ -402,53 +1424,25 @@ async fn simulate_transaction(
         .parse()
         .map_err(|e| format!("Invalid calldata format: {}", e))?;
 
-    // Load the ABI and verify the function being called
-    let abi = load_wallet_abi().map_err(|e| format!("Failed to load wallet ABI: {}", e))?;
-
-    // Check if the calldata is calling executeWithRelayer
-    // The first 4 bytes are the function selector
-    if calldata_bytes.len() < 4 {
-        return Err("Calldata too short".to_string());
-    }
-
-    let function_selector = &calldata_bytes[..4];
-
-    // Find the executeWithRelayer function
-    let execute_with_relayer_fn = abi
-        .functions()
-        .find(|f| f.name == "executeWithRelayer")
-        .ok_or_else(|| "executeWithRelayer function not found in ABI".to_string())?;
-
-    // Get the expected selector
-    let expected_selector = execute_with_relayer_fn.selector();
-
-    // Verify the selector matches
-    if function_selector != expected_selector.as_slice() {
-        return Err(format!(
-            "Transaction is not calling executeWithRelayer (expected selector: 0x{}, got: 0x{})",
-            hex::encode(expected_selector),
-            hex::encode(function_selector)
-        ));
-    }
-
-    // Create provider for the chain
-    let rpc_endpoint = Url::parse(&rpc_url).map_err(|e| format!("Invalid RPC URL: {}", e))?;
-    let provider = ProviderBuilder::new().on_hyper_http(rpc_endpoint);
-
-    // Create a transaction request for simulation
-    let tx = TransactionRequest::default()
-        .to(wallet_addr)
-        .input(calldata_bytes.into());
+    check_relay_selector(&calldata_bytes, cfg)?;
 
     // First, simulate the transaction using eth_call to ensure it won't revert
-    if let Err(e) = provider.call(&tx).await {
+    if let Err(e) = cfg
+        .chain
+        .call(&rpc_url, wallet_addr, calldata_bytes.clone(), value)
+        .await
+    {
         let error_msg = format!("Transaction simulation failed: {}", e);
         tracing::warn!("{}", error_msg);
         return Err(error_msg);
     }
 
     // Now estimate the gas required for the transaction
-    match provider.estimate_gas(&tx).await {
+    match cfg
+        .chain
+        .estimate_gas(&rpc_url, wallet_addr, calldata_bytes, value)
+        .await
+    {
         Ok(gas_estimate) => {
             tracing::info!(
                 "Transaction simulation succeeded for wallet {} on chain {}, estimated gas: {}",
@@ -458,82 +1452,806 @@ async fn simulate_transaction(
             );
             Ok(gas_estimate)
         }
-        Err(e) => {
-            let error_msg = format!("Gas estimation failed: {}", e);
+        Err(error_msg) => {
             tracing::warn!("{}", error_msg);
             Err(error_msg)
         }
     }
 }
 
-/// Endpoint business logic functions
-async fn process_send_transaction(
-    storage: Storage,
-    input: &SendTransactionRequest,
+/// Gas price of `speed`'s lane in wei, or `network_per_gas` when it cannot be priced
+async fn lane_wei_per_gas(
+    chain_id: u64,
+    speed: Speed,
+    network_per_gas: U256,
     cfg: &Config,
-) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
-    tracing::info!("=== relayer_sendTransaction request received ===");
-    tracing::debug!(
-        "Request details - To: {}, ChainId: {}, Payment: {}",
-        input.to,
-        input.chain_id,
-        input.capabilities.payment.payment_type
-    );
+) -> U256 {
+    lane_gas_price(chain_id, speed, cfg)
+        .await
+        .ok()
+        .and_then(|price| parse_hex_u256(&price))
+        .unwrap_or(network_per_gas)
+}
 
-    // Validate the transaction request
-    if input.to.is_empty() {
-        tracing::warn!("Validation failed: Missing 'to' field");
-        return Err(invalid_params_error());
+/// How a relay costing `cost` is priced: its wei cost by component, its gas price against the
+/// network's `network_per_gas`, and the oracle rate behind a token `rate` quoted per gas unit
+/// at `network_per_gas` (1 when the fee is paid in the native token). Without a `cost` only
+/// the prices are described.
+async fn fee_breakdown(
+    chain_id: u64,
+    cost: Option<RelayCost>,
+    network_per_gas: U256,
+    rate: Option<&ExchangeRateQuote>,
+    cfg: &Config,
+) -> FeeBreakdown {
+    let native_decimals = cfg.native_token_info(&chain_id.to_string()).decimals;
+    let oracle_rate = match rate {
+        Some(quote) if !network_per_gas.is_zero() => {
+            quote.rate * 10f64.powi(native_decimals as i32) / f64::from(network_per_gas)
+        }
+        _ => 1.0,
+    };
+    let wei_per_gas = cost.map_or(network_per_gas, |cost| cost.wei_per_gas);
+    let markup_bps = match network_per_gas.is_zero() {
+        true => 0,
+        false => {
+            let bps = |diff: U256| -> i64 {
+                (diff.saturating_mul(U256::from(10_000)) / network_per_gas).saturating_to()
+            };
+            if wei_per_gas >= network_per_gas {
+                bps(wei_per_gas - network_per_gas)
+            } else {
+                -bps(network_per_gas - wei_per_gas)
+            }
+        }
+    };
+    let hex = |wei: U256| format!("0x{:x}", wei);
+    FeeBreakdown {
+        gas_limit: cost.map(|cost| cost.gas_limit),
+        execution_cost: cost
+            .map(|cost| hex(cost.wei_per_gas.saturating_mul(U256::from(cost.gas_limit)))),
+        l1_data_fee: cost.map(|cost| hex(cost.l1_data_fee)),
+        blob_fee: cost
+            .map(|cost| cost.blob_gas_fee)
+            .filter(|fee| !fee.is_zero())
+            .map(hex),
+        value: cost
+            .map(|cost| cost.value)
+            .filter(|value| !value.is_zero())
+            .map(hex),
+        gas_price: hex(wei_per_gas),
+        network_gas_price: hex(network_per_gas),
+        priority_fee_per_gas: fetch_eip1559_fees(chain_id, cfg)
+            .await
+            .map(|(_, tip)| format!("0x{:x}", tip)),
+        oracle_rate,
+        markup_bps,
     }
+}
 
-    if input.data.is_empty() {
-        tracing::warn!("Validation failed: Missing 'data' field");
-        return Err(invalid_params_error());
+/// Re-express a token `rate` quoted per gas unit at `network_per_gas` for gas bought at
+/// `wei_per_gas`
+fn rate_at_gas_price(rate: f64, wei_per_gas: U256, network_per_gas: U256) -> f64 {
+    if network_per_gas.is_zero() {
+        return rate;
     }
+    rate * f64::from(wei_per_gas) / f64::from(network_per_gas)
+}
 
-    if input.chain_id.is_empty() {
-        tracing::warn!("Validation failed: Missing 'chainId' field");
-        return Err(invalid_params_error());
-    }
+/// Fixed-point scale a token rate is carried at once it enters integer fee math
+const RATE_SCALE: u128 = 1_000_000_000_000_000_000;
 
-    if input.capabilities.payment.payment_type.trim().is_empty() {
-        tracing::warn!("Validation failed: Missing payment type");
-        return Err(invalid_params_error());
+/// Convert a wei fee into the smallest unit of the quoted token, rounding up. `quote.rate` is
+/// whole tokens per gas unit at `wei_per_gas`; it is fixed to [`RATE_SCALE`] and the rest of
+/// the conversion stays in integers, so fees in 18-decimal tokens keep every digit.
+fn fee_in_token_units(fee_wei: U256, wei_per_gas: U256, quote: &ExchangeRateQuote) -> Option<U256> {
+    if wei_per_gas.is_zero() {
+        return None;
     }
+    let rate = U256::from((quote.rate * RATE_SCALE as f64).round() as u128);
+    let token_unit = U256::from(10).pow(U256::from(quote.token.decimals));
+    let numerator = fee_wei.saturating_mul(rate).saturating_mul(token_unit);
+    let denominator = wei_per_gas.saturating_mul(U256::from(RATE_SCALE));
+    let (fee, remainder) = numerator.div_rem(denominator);
+    Some(fee + U256::from(!remainder.is_zero() as u8))
+}
 
-    if input.capabilities.payment.token.trim().is_empty() {
-        tracing::warn!("Validation failed: Missing payment token");
-        return Err(invalid_params_error());
+/// Hold a quoted fee within the payment token's `feeBounds`, returning the fee and the bound
+/// it was clamped to. Bounds set to reject fail with `-4213` instead.
+fn apply_fee_bounds(
+    fee: U256,
+    chain_id: &str,
+    token: &str,
+    cfg: &Config,
+) -> Result<(U256, Option<FeeBound>), RelayError> {
+    let Some(bounds) = cfg.fee_bounds(chain_id, token) else {
+        return Ok((fee, None));
+    };
+    // Bounds are u64; a larger fee is past any `max` all the same
+    match bounds.exceeded(fee.saturating_to()) {
+        None => Ok((fee, None)),
+        Some((bound, limit)) if bounds.reject => {
+            tracing::warn!(
+                "Fee {} in token {} on chain {} is outside its {:?} bound {}",
+                fee,
+                token,
+                chain_id,
+                bound,
+                limit
+            );
+            Err(RelayError::FeeOutOfBounds {
+                fee: fee.saturating_to(),
+                bound,
+                limit,
+            })
+        }
+        Some((bound, limit)) => {
+            tracing::info!(
+                "Clamping fee {} in token {} on chain {} to its {:?} bound {}",
+                fee,
+                token,
+                chain_id,
+                bound,
+                limit
+            );
+            Ok((U256::from(limit), Some(bound)))
+        }
     }
+}
+
+/// Endpoint business logic functions
+/// Price a validated dry-run submission in its payment token
+async fn build_dry_run_result(
+    input: &SendTransactionRequest,
+    chain_id: u64,
+    gas_limit: u64,
+    gas_price: &str,
+    l1_data_fee: U256,
+    failure_reason: Option<String>,
+    cfg: &Config,
+) -> DryRunResult {
+    let payment = &input.capabilities.payment;
+    let wei_per_gas = parse_hex_u256(gas_price).unwrap_or(U256::ZERO);
+    let blob_gas_fee = match &input.blob_sidecar {
+        Some(sidecar) => fetch_max_fee_per_blob_gas(chain_id, cfg)
+            .await
+            .ok()
+            .map(|max_fee| blob_fee(sidecar.blobs.len(), max_fee)),
+        None => None,
+    };
+    let value = call_value(input.value.as_ref()).unwrap_or_default();
+    let fee_wei = wei_per_gas
+        .saturating_mul(U256::from(gas_limit))
+        .saturating_add(l1_data_fee)
+        .saturating_add(blob_gas_fee.unwrap_or_default())
+        .saturating_add(value);
+
+    let (fee, token) = match payment.payment_type.as_str() {
+        "erc20" | "permit" => {
+            let rate_request = ExchangeRateRequest {
+                token: payment.token.clone(),
+                chain_id: chain_id.to_string(),
+            };
+            let response = build_exchange_rate_response(cfg, &rate_request).await;
+            match response.result.into_iter().next() {
+                Some(ExchangeRateResultItem::Success(success)) if !wei_per_gas.is_zero() => (
+                    fee_in_token_units(fee_wei, wei_per_gas, &success.quote),
+                    success.quote.token,
+                ),
+                _ => (
+                    None,
+                    TokenInfo {
+                        decimals: 18,
+                        address: payment.token.clone(),
+                        symbol: None,
+                        name: None,
+                    },
+                ),
+            }
+        }
+        "sponsored" => (
+            Some(U256::ZERO),
+            cfg.native_token_info(&chain_id.to_string()),
+        ),
+        _ => (Some(fee_wei), cfg.native_token_info(&chain_id.to_string())),
+    };
+
+    DryRunResult {
+        would_succeed: failure_reason.is_none(),
+        gas_limit,
+        gas_price: gas_price.to_string(),
+        l1_data_fee: format!("0x{:x}", l1_data_fee),
+        blob_fee: blob_gas_fee.map(|fee| format!("0x{:x}", fee)),
+        fee: fee.map(|fee| fee.to_string()),
+        token,
+        failure_reason,
+    }
+}
+
+/// What a relay costs in wei before it is converted to a payment token
+#[derive(Debug, Clone, Copy)]
+struct RelayCost {
+    gas_limit: u64,
+    wei_per_gas: U256,
+    l1_data_fee: U256,
+    blob_gas_fee: U256,
+    /// Native value the relayer fronts and is paid back with the fee
+    value: U256,
+}
+
+impl RelayCost {
+    fn fee_wei(&self) -> U256 {
+        self.wei_per_gas
+            .saturating_mul(U256::from(self.gas_limit))
+            .saturating_add(self.l1_data_fee)
+            .saturating_add(self.blob_gas_fee)
+            .saturating_add(self.value)
+    }
+}
+
+/// ERC-20 `balanceOf(address)` selector
+const ERC20_BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Token balance of `owner`, or `None` when it cannot be read
+async fn erc20_balance(chain_id: u64, token: &str, owner: Address, cfg: &Config) -> Option<U256> {
+    let mut calldata = ERC20_BALANCE_OF_SELECTOR.to_vec();
+    calldata.extend_from_slice(owner.into_word().as_slice());
+    let output = eth_call_raw(chain_id, token, calldata, cfg).await.ok()?;
+    abi_word(&output, 0)
+}
+
+/// Whether `wallet` can pay for a relay costing `cost` with `payment`. Mirrors the checks the
+/// relay itself makes, so an option that passes here is only rejected later if balances move.
+async fn can_afford(
+    storage: &Storage,
+    payment: &PaymentCapability,
+    chain_id: u64,
+    wallet: Address,
+    cost: RelayCost,
+    cfg: &Config,
+) -> bool {
+    const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+    match payment.payment_type.as_str() {
+        "native" => {
+            let Some(rpc_url) = cfg.rpc_url_for_chain(&chain_id.to_string()) else {
+                return false;
+            };
+            payment.token == ZERO_ADDRESS
+                && cfg
+                    .chain
+                    .balance(&rpc_url, wallet)
+                    .await
+                    .is_ok_and(|balance| balance >= cost.fee_wei())
+        }
+        "gasTank" => {
+            payment.token == ZERO_ADDRESS
+                && decode_gas_tank_authorization(&payment.data)
+                    .is_ok_and(|authorization| authorization.max_fee >= cost.fee_wei())
+                && storage
+                    .get_gas_tank_balance(&chain_id.to_string(), &format!("{:#x}", wallet))
+                    .await
+                    .is_ok_and(|balance| U256::from(balance) >= cost.fee_wei())
+        }
+        "erc20" | "permit" => {
+            if !cfg.accepts_payment_token(&chain_id.to_string(), &payment.token) {
+                return false;
+            }
+            let Some(fee) = token_fee(&payment.token, chain_id, cost, cfg).await else {
+                return false;
+            };
+            let fee = fee_with_slippage(fee, cfg.rate_slippage_bps(chain_id));
+            // A permit is paid from its owner's balance, up to the permitted value
+            let payer = if payment.payment_type == "permit" {
+                match decode_fee_permit(&payment.data) {
+                    Ok(permit) if check_permit_deadline(&permit).is_ok() && permit.value >= fee => {
+                        permit.owner
+                    }
+                    _ => return false,
+                }
+            } else {
+                wallet
+            };
+            erc20_balance(chain_id, &payment.token, payer, cfg)
+                .await
+                .is_some_and(|balance| balance >= fee)
+        }
+        "sponsored" => {
+            if !cost.value.is_zero() {
+                return false;
+            }
+            match check_sponsorship_key(payment, cfg) {
+                Ok(Some(project)) => match project.monthly_gas {
+                    Some(allowance) => storage
+                        .get_sponsored_gas_used(&project.id, &billing_period(Utc::now()).0)
+                        .await
+                        .is_ok_and(|used| used.saturating_add(cost.gas_limit) <= allowance),
+                    None => true,
+                },
+                Ok(None) => true,
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// What a relay costing `cost` is charged in `token` at the current rate, priced like
+/// relayer_getQuote prices the same relay. `None` without a rate for the token.
+async fn token_fee(token: &str, chain_id: u64, cost: RelayCost, cfg: &Config) -> Option<U256> {
+    let rate_request = ExchangeRateRequest {
+        token: token.to_string(),
+        chain_id: chain_id.to_string(),
+    };
+    match build_exchange_rate_response(cfg, &rate_request)
+        .await
+        .result
+        .into_iter()
+        .next()
+    {
+        Some(ExchangeRateResultItem::Success(rate)) => {
+            let network_per_gas = parse_hex_u256(&rate.gas_price).unwrap_or(U256::ZERO);
+            fee_in_token_units(cost.fee_wei(), network_per_gas, &rate.quote)
+        }
+        _ => None,
+    }
+}
+
+/// Lowest payment accepted against a `fee` re-priced at verification time: the fee less
+/// `slippage_bps` of it (the chain's `rateSlippageBps`)
+fn fee_with_slippage(fee: U256, slippage_bps: u64) -> U256 {
+    let bps = U256::from(slippage_bps.min(10_000));
+    fee - fee.saturating_mul(bps) / U256::from(10_000)
+}
+
+/// The first payment option of `input` the wallet can afford, or the preferred one when it
+/// can afford none so the relay fails with that option's error
+async fn choose_payment(
+    storage: &Storage,
+    input: &SendTransactionRequest,
+    chain_id: u64,
+    wallet: Address,
+    cost: RelayCost,
+    cfg: &Config,
+) -> PaymentCapability {
+    for payment in input.capabilities.payment_options() {
+        if can_afford(storage, payment, chain_id, wallet, cost, cfg).await {
+            return payment.clone();
+        }
+        tracing::debug!(
+            "Wallet {} cannot afford {} payment in {}; trying the next option",
+            input.to,
+            payment.payment_type,
+            payment.token
+        );
+    }
+    input.capabilities.payment.clone()
+}
+
+async fn process_send_transaction(
+    storage: Storage,
+    input: &SendTransactionRequest,
+    cfg: &Config,
+) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
+    relay_transaction(storage, input, cfg, None).await
+}
+
+/// Callbacks are signed, so they are only accepted once a callback secret is configured
+fn validate_callback_url(url: &str, cfg: &Config) -> Result<(), jsonrpc_core::Error> {
+    if cfg.callback_secret.is_none() {
+        tracing::warn!("Callback rejected: no callback secret configured");
+        return Err(RelayError::UnsupportedCapability("callbackUrl".to_string()).into());
+    }
+    callback::validate_url(url, cfg.callback_host_policy()).map_err(|e| {
+        tracing::warn!("Validation failed: {}", e);
+        RelayError::invalid_params(e)
+    })?;
+    Ok(())
+}
+
+/// Checks on a `relayer_sendTransaction` request that need no chain access: required
+/// fields, calldata size, callback, chain support, value and the authorization list. Returns
+/// the chain id, target wallet and value.
+fn validate_relay_request(
+    input: &SendTransactionRequest,
+    cfg: &Config,
+) -> Result<(u64, Address, U256), jsonrpc_core::Error> {
+    // Validate the transaction request
+    if input.to.is_empty() {
+        tracing::warn!("Validation failed: Missing 'to' field");
+        return Err(RelayError::invalid_field("to", "Missing required field: 'to'").into());
+    }
+
+    if input.data.is_empty() && input.safe.is_none() {
+        tracing::warn!("Validation failed: Missing 'data' field");
+        return Err(RelayError::invalid_field("data", "Missing required field: 'data'").into());
+    }
+    if input.safe.is_some() && !input.data.trim_start_matches("0x").is_empty() {
+        tracing::warn!("Validation failed: 'data' given alongside 'safe'");
+        return Err(RelayError::invalid_params(
+            "'data' must be empty when 'safe' is set; the relayer builds execTransaction",
+        )
+        .into());
+    }
+    check_calldata_size(&input.data, cfg)?;
+    if let Some(url) = &input.callback_url {
+        validate_callback_url(url, cfg)?;
+    }
+
+    if input.chain_id.is_empty() {
+        tracing::warn!("Validation failed: Missing 'chainId' field");
+        return Err(
+            RelayError::invalid_field("chainId", "Missing required field: 'chainId'").into(),
+        );
+    }
+
+    for payment in input.capabilities.payment_options() {
+        if payment.payment_type.trim().is_empty() {
+            tracing::warn!("Validation failed: Missing payment type");
+            return Err(RelayError::invalid_field(
+                "capabilities.payment.type",
+                "Missing required field: payment type",
+            )
+            .into());
+        }
+
+        if payment.token.trim().is_empty() {
+            tracing::warn!("Validation failed: Missing payment token");
+            return Err(RelayError::invalid_field(
+                "capabilities.payment.token",
+                "Missing required field: payment token",
+            )
+            .into());
+        }
+    }
+
+    // Validate chain ID is a valid number
+    let chain_id: u64 = params::chain_id(&input.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", input.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+    })?;
 
-    // Validate chain ID is a valid number
-    let chain_id: u64 = input.chain_id.parse().map_err(|_| {
-        tracing::warn!("Invalid chainId format: {}", input.chain_id);
-        invalid_params_error()
-    })?;
-
     tracing::debug!("Validating chain support for chainId: {}", chain_id);
 
     // Check if chain is supported by the relayer
     if !cfg.is_chain_supported(chain_id) {
         tracing::warn!("Unsupported chain ID requested: {}", chain_id);
-        return Err(jsonrpc_core::Error::invalid_params(format!(
-            "Unsupported chain ID: {}",
-            chain_id
-        )));
+        return Err(RelayError::UnsupportedChain(chain_id.to_string()).into());
     }
 
     tracing::debug!("Chain {} is supported", chain_id);
 
-    let wallet_address: Address = input.to.parse().map_err(|e| {
+    let wallet_address: Address = params::address(&input.to).map_err(|e| {
         tracing::warn!("Invalid wallet address {}: {}", input.to, e);
-        invalid_params_error()
+        RelayError::invalid_field("to", "Invalid wallet address")
+    })?;
+
+    // The relayer fronts the value and is paid it back with the fee, which a sponsored
+    // relay does not charge
+    let value = call_value(input.value.as_ref()).ok_or_else(|| {
+        tracing::warn!("Invalid value: {:?}", input.value);
+        RelayError::invalid_field("value", "Invalid 'value': expected a hex quantity")
     })?;
+    if !value.is_zero() && input.capabilities.payment.payment_type == "sponsored" {
+        tracing::warn!("Sponsored relay to {} carries value {}", input.to, value);
+        return Err(RelayError::UnsupportedCapability("value".to_string()).into());
+    }
 
     validate_authorization_list(&input.authorization_list, chain_id, wallet_address)?;
+    if let Some(sidecar) = &input.blob_sidecar {
+        validate_blob_sidecar(sidecar, &input.authorization_list)?;
+    }
+    if let Some(condition) = &input.condition {
+        validate_condition(condition)?;
+    }
 
-    // Fetch current gas price from the chain
-    let gas_price = match fetch_gas_price(chain_id, cfg).await {
+    Ok((chain_id, wallet_address, value))
+}
+
+/// Check a Safe request against the Safe's current owners, threshold and nonce, and build the
+/// `execTransaction` calldata relaying it to `safe_address`
+async fn prepare_safe_transaction(
+    safe: &SafeTransaction,
+    safe_address: Address,
+    chain_id: u64,
+    cfg: &Config,
+) -> Result<String, jsonrpc_core::Error> {
+    let address = |field: &str, value: Option<&String>| match value {
+        None => Ok(Address::ZERO),
+        Some(value) => params::address(value).map_err(|_| {
+            tracing::warn!("Invalid safe.{}: {}", field, value);
+            RelayError::invalid_field(
+                format!("safe.{}", field),
+                format!("Invalid safe.{}: expected an address", field),
+            )
+        }),
+    };
+    let quantity = |field: &str, value: Option<&String>| match value {
+        None => Ok(U256::ZERO),
+        Some(value) => params::quantity(value).ok().ok_or_else(|| {
+            tracing::warn!("Invalid safe.{}: {}", field, value);
+            RelayError::invalid_field(
+                format!("safe.{}", field),
+                format!("Invalid safe.{}: expected a hex quantity", field),
+            )
+        }),
+    };
+    let to = address("to", Some(&safe.to))?;
+    let data: Bytes = if safe.data.is_empty() {
+        Bytes::new()
+    } else {
+        params::hex_bytes(&safe.data).map_err(|_| {
+            tracing::warn!("Invalid safe.data: {}", safe.data);
+            RelayError::invalid_field("safe.data", "Invalid safe.data: expected hex calldata")
+        })?
+    };
+    if safe.operation > 1 {
+        tracing::warn!("Invalid safe.operation: {}", safe.operation);
+        return Err(RelayError::invalid_params(
+            "Invalid safe.operation: expected 0 (call) or 1 (delegatecall)",
+        )
+        .into());
+    }
+    let signatures: Bytes = params::hex_bytes(&safe.signatures).map_err(|_| {
+        tracing::warn!("Safe signatures are not valid hex");
+        RelayError::InvalidSignature
+    })?;
+
+    let rpc_url = cfg
+        .rpc_url_for_chain(&chain_id.to_string())
+        .ok_or_else(|| {
+            tracing::error!("No RPC URL configured for chain {}", chain_id);
+            RelayError::ChainUnavailable(chain_id.to_string())
+        })?;
+    let read = |selector: [u8; 4]| {
+        cfg.chain.call(
+            &rpc_url,
+            safe_address,
+            Bytes::from(selector.to_vec()),
+            U256::ZERO,
+        )
+    };
+    let (threshold, owners, nonce) = tokio::join!(
+        read(safe::GET_THRESHOLD_SELECTOR),
+        read(safe::GET_OWNERS_SELECTOR),
+        read(safe::NONCE_SELECTOR)
+    );
+    let state = match (threshold, owners, nonce) {
+        (Ok(threshold), Ok(owners), Ok(nonce)) => abi_word(&threshold, 0)
+            .and_then(|threshold| usize::try_from(threshold).ok())
+            .zip(safe::decode_owners(&owners))
+            .zip(abi_word(&nonce, 0)),
+        _ => None,
+    };
+    let Some(((threshold, owners), current_nonce)) = state else {
+        tracing::warn!("{} is not a Safe on chain {}", safe_address, chain_id);
+        return Err(RelayError::invalid_params(format!(
+            "0x{:x} is not a Safe on chain {}",
+            safe_address, chain_id
+        ))
+        .into());
+    };
+    let nonce = match &safe.nonce {
+        Some(_) => quantity("nonce", safe.nonce.as_ref())?,
+        None => current_nonce,
+    };
+    if nonce != current_nonce {
+        tracing::warn!(
+            "Safe {} nonce mismatch: signed {}, current {}",
+            safe_address,
+            nonce,
+            current_nonce
+        );
+        return Err(RelayError::invalid_params(format!(
+            "safe.nonce {} does not match the Safe's current nonce {}",
+            nonce, current_nonce
+        ))
+        .into());
+    }
+
+    let tx = safe::SafeTx {
+        to,
+        value: quantity("value", safe.value.as_ref())?,
+        data,
+        operation: safe.operation,
+        safe_tx_gas: quantity("safeTxGas", safe.safe_tx_gas.as_ref())?,
+        base_gas: quantity("baseGas", safe.base_gas.as_ref())?,
+        gas_price: quantity("gasPrice", safe.gas_price.as_ref())?,
+        gas_token: address("gasToken", safe.gas_token.as_ref())?,
+        refund_receiver: address("refundReceiver", safe.refund_receiver.as_ref())?,
+        nonce,
+    };
+    let hash = tx.hash(chain_id, safe_address);
+    if let Err(e) = safe::check_signatures(hash, &signatures, threshold, &owners) {
+        tracing::warn!("Safe {} signatures rejected: {}", safe_address, e);
+        return Err(RelayError::InvalidSignature.into());
+    }
+    tracing::debug!(
+        "Safe {} transaction {} approved by {} of {} owners",
+        safe_address,
+        hash,
+        threshold,
+        owners.len()
+    );
+    Ok(format!("0x{}", hex::encode(tx.exec_calldata(&signatures))))
+}
+
+/// Identifies a relay for duplicate detection: the same call, value and payment to the same
+/// address on the same chain, for the same tenant key. A Safe relay's calldata is built from
+/// its SafeTx, so the SafeTx fields and signatures stand in for `data`.
+fn submission_fingerprint(input: &SendTransactionRequest) -> String {
+    let value = input
+        .value
+        .as_deref()
+        .and_then(parse_hex_u256)
+        .unwrap_or_default();
+    let safe = input
+        .safe
+        .as_ref()
+        .and_then(|safe| serde_json::to_string(safe).ok())
+        .unwrap_or_default();
+    let payment = serde_json::to_string(&input.capabilities.payment).unwrap_or_default();
+    let tuple = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+        input.api_key.as_deref().unwrap_or_default(),
+        input.chain_id,
+        input.to.to_lowercase(),
+        input.data.to_lowercase(),
+        value,
+        safe.to_lowercase(),
+        payment
+    );
+    hex::encode(keccak256(tuple.as_bytes()))
+}
+
+/// Validate, simulate, charge and broadcast a relay. `scheduled` is the stored record of a
+/// scheduled request being executed, whose id and creation time are kept; without it a
+/// request with a future `executeAfter` is queued for the scheduler instead.
+///
+/// With `duplicates` configured, a new relay repeating one submitted within the window is
+/// rejected, or answered with the earlier request's id, instead of being relayed twice.
+async fn relay_transaction(
+    storage: Storage,
+    input: &SendTransactionRequest,
+    cfg: &Config,
+    scheduled: Option<&RelayerRequest>,
+) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
+    let request_id = scheduled.map_or_else(Uuid::new_v4, |req| req.id);
+    let policy = cfg
+        .duplicate_policy()
+        .filter(|_| scheduled.is_none() && !input.capabilities.dry_run);
+    let Some(policy) = policy else {
+        return relay_transaction_as(storage, input, cfg, scheduled, request_id).await;
+    };
+
+    let fingerprint = submission_fingerprint(input);
+    let now = Utc::now();
+    let since = now - chrono::Duration::seconds(policy.window_seconds as i64);
+    let claimed = storage
+        .claim_submission(&fingerprint, request_id, now, since)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check for duplicate submission: {}", e);
+            RelayError::Internal
+        })?;
+    if let Some(existing) = claimed {
+        tracing::info!(
+            "Duplicate relay to {} on chain {} within {}s of request {}",
+            input.to,
+            input.chain_id,
+            policy.window_seconds,
+            existing
+        );
+        if !policy.return_existing {
+            return Err(RelayError::DuplicateRequest(existing.to_string()).into());
+        }
+        return Ok(SendTransactionResponse {
+            result: vec![SendTransactionResult {
+                chain_id: input.chain_id.clone(),
+                id: existing.to_string(),
+                dry_run: None,
+                estimated_confirmation_seconds: None,
+                payment: None,
+            }],
+        });
+    }
+
+    let outcome = relay_transaction_as(storage.clone(), input, cfg, None, request_id).await;
+    // A rejected relay did not happen, so sending it again is not a duplicate
+    if outcome.is_err() {
+        if let Err(e) = storage.release_submission(&fingerprint, request_id).await {
+            tracing::warn!("Failed to release submission of {}: {}", request_id, e);
+        }
+    }
+    outcome
+}
+
+/// [`relay_transaction`] recording the request under `request_id`
+async fn relay_transaction_as(
+    storage: Storage,
+    input: &SendTransactionRequest,
+    cfg: &Config,
+    scheduled: Option<&RelayerRequest>,
+    request_id: Uuid,
+) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
+    let outcome = run_relay(storage, input, cfg, scheduled, request_id).await;
+    // Rejected relays and dry runs never reach the chain, so their lifecycle ends here
+    if outcome.is_err() || input.capabilities.dry_run {
+        end_lifecycle(request_id);
+    }
+    outcome
+}
+
+async fn run_relay(
+    storage: Storage,
+    input: &SendTransactionRequest,
+    cfg: &Config,
+    scheduled: Option<&RelayerRequest>,
+    request_id: Uuid,
+) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        to = %input.to,
+        chain_id = %input.chain_id,
+        "=== relayer_sendTransaction request received ==="
+    );
+    tracing::debug!(
+        "Request details - To: {}, ChainId: {}, Payment: {}",
+        input.to,
+        input.chain_id,
+        input.capabilities.payment.payment_type
+    );
+
+    // A scheduled request keeps the tenant it was queued under
+    let tenant = match scheduled {
+        Some(req) => req.tenant.clone(),
+        None => resolve_tenant(cfg, input.api_key.as_deref())?,
+    };
+    let (chain_id, wallet_address, value) = validate_relay_request(input, cfg)?;
+    // A scheduled request was held to its quote when it was queued
+    if scheduled.is_none() {
+        check_quote(&storage, input, chain_id, cfg).await?;
+    }
+    lifecycle_breadcrumb(
+        request_id,
+        "validated",
+        format!("validated relay to {} on chain {}", input.to, chain_id),
+    );
+
+    // A Safe request is relayed as the execTransaction call built from its SafeTx
+    let safe_request;
+    let input = match &input.safe {
+        Some(safe) => {
+            safe_request = SendTransactionRequest {
+                data: prepare_safe_transaction(safe, wallet_address, chain_id, cfg).await?,
+                safe: None,
+                ..input.clone()
+            };
+            check_calldata_size(&safe_request.data, cfg)?;
+            &safe_request
+        }
+        None => input,
+    };
+
+    // Gas is simulated and priced when a scheduled request runs, not when it is queued
+    let execute_after = input.execute_after.filter(|at| *at > Utc::now());
+    if (execute_after.is_some() || input.condition.is_some())
+        && scheduled.is_none()
+        && !input.capabilities.dry_run
+    {
+        return schedule_transaction(
+            &storage,
+            input,
+            request_id,
+            chain_id,
+            execute_after,
+            tenant,
+            cfg,
+        )
+        .await;
+    }
+
+    // Fetch current gas price from the chain, priced for the requested lane
+    let speed = input.speed.unwrap_or_default();
+    let gas_price = match lane_gas_price(chain_id, speed, cfg).await {
         Ok(price) => price,
         Err(e) => {
             tracing::warn!("Failed to fetch gas price, using default: {}", e);
@@ -541,14 +2259,14 @@ async fn process_send_transaction(
         }
     };
 
-    tracing::debug!(
-        "Validating payment capability: {}",
-        input.capabilities.payment.payment_type
-    );
-
-    let payment_type = input.capabilities.payment.payment_type.as_str();
+    // Dry runs report simulation and balance problems instead of rejecting the request
+    let dry_run = input.capabilities.dry_run;
+    let mut dry_run_failure: Option<String> = None;
+    if dry_run {
+        tracing::info!("Dry run requested: transaction will not be stored or broadcast");
+    }
 
-    let sim_gas = match simulate_transaction(&input.to, &input.data, chain_id, cfg).await {
+    let sim_gas = match simulate_transaction(&input.to, &input.data, value, chain_id, cfg).await {
         Ok(gas) => {
             tracing::debug!(
                 "Pre-relay simulation succeeded for wallet {} on chain {} with gas {}",
@@ -566,7 +2284,26 @@ async fn process_send_transaction(
                     input.to,
                     chain_id
                 );
-                150_000 // Default gas limit
+                cfg.fallback_gas_limit(chain_id)
+            } else if let Some(gas) = fallback_gas_after_failed_simulation(chain_id, !dry_run, cfg)
+            {
+                tracing::warn!(
+                    "Pre-relay simulation failed for wallet {} on chain {}, relaying with fallback gas limit {}: {}",
+                    input.to,
+                    chain_id,
+                    gas,
+                    e
+                );
+                gas
+            } else if dry_run {
+                tracing::info!(
+                    "Dry run: simulation failed for wallet {} on chain {}: {}",
+                    input.to,
+                    chain_id,
+                    e
+                );
+                dry_run_failure = Some(format!("simulation failed: {}", e));
+                cfg.fallback_gas_limit(chain_id)
             } else {
                 tracing::warn!(
                     "Pre-relay simulation failed for wallet {} on chain {}: {}",
@@ -574,25 +2311,96 @@ async fn process_send_transaction(
                     chain_id,
                     e
                 );
-                return Err(simulation_failed_error());
+                return Err(RelayError::SimulationFailed(e).into());
             }
         }
     };
 
     let gas_limit = sim_gas;
+    lifecycle_breadcrumb(
+        request_id,
+        "simulated",
+        format!("simulated gas {} at gas price {}", gas_limit, gas_price),
+    );
+    // Fee to take from the wallet's gas tank, and the authorization it is taken under
+    let mut gas_tank_debit: Option<(u128, B256)> = None;
+    let mut sponsorship: Option<(SponsorshipProject, String)> = None;
+    if let Err(e) = check_gas_limit(gas_limit, cfg) {
+        tracing::warn!(
+            "Relay to {} on chain {} rejected: {}",
+            input.to,
+            chain_id,
+            e
+        );
+        if !dry_run {
+            return Err(RelayError::TransactionTooLarge {
+                unit: "gas",
+                size: gas_limit,
+                limit: cfg.max_gas(),
+            }
+            .into());
+        }
+        dry_run_failure.get_or_insert(e);
+    }
+
+    // Blob relays also buy blob gas, charged alongside the gas and L1 data fee
+    let blobs = match &input.blob_sidecar {
+        Some(sidecar) => match fetch_max_fee_per_blob_gas(chain_id, cfg).await {
+            Ok(max_fee_per_blob_gas) => Some((sidecar, max_fee_per_blob_gas)),
+            Err(e) => {
+                tracing::warn!("Blob relay rejected on chain {}: {}", chain_id, e);
+                return Err(RelayError::UnsupportedCapability("blobSidecar".to_string()).into());
+            }
+        },
+        None => None,
+    };
+    let blob_gas_fee = blobs.map_or(U256::ZERO, |(sidecar, max_fee_per_blob_gas)| {
+        blob_fee(sidecar.blobs.len(), max_fee_per_blob_gas)
+    });
+
+    // With several payment options, the first one the wallet can afford is charged
+    let chosen_request;
+    let (input, chosen_payment) = if input.capabilities.fallback_payments.is_empty() {
+        (input, None)
+    } else {
+        let cost = RelayCost {
+            gas_limit: sim_gas,
+            wei_per_gas: parse_hex_u256(&gas_price).unwrap_or(U256::ZERO),
+            l1_data_fee: fetch_l1_data_fee(chain_id, &input.to, &input.data, cfg).await,
+            blob_gas_fee,
+            value,
+        };
+        let payment = choose_payment(&storage, input, chain_id, wallet_address, cost, cfg).await;
+        tracing::info!(
+            "Relay to {} on chain {} pays with {} in {}",
+            input.to,
+            chain_id,
+            payment.payment_type,
+            payment.token
+        );
+        chosen_request = SendTransactionRequest {
+            capabilities: SendTransactionCapabilities {
+                payment: payment.clone(),
+                fallback_payments: Vec::new(),
+                dry_run,
+            },
+            ..input.clone()
+        };
+        (&chosen_request, Some(payment))
+    };
+
+    tracing::debug!(
+        "Validating payment capability: {}",
+        input.capabilities.payment.payment_type
+    );
+
+    let payment_type = input.capabilities.payment.payment_type.as_str();
+    validate_payment(&input.capabilities.payment, chain_id, cfg)?;
 
-    match payment_type {
+    let l1_data_fee = match payment_type {
         "native" => {
             tracing::debug!("Processing native payment transaction");
 
-            if input.capabilities.payment.token != "0x0000000000000000000000000000000000000000" {
-                tracing::warn!(
-                    "Invalid native payment token address: {}",
-                    input.capabilities.payment.token
-                );
-                return Err(invalid_params_error());
-            }
-
             tracing::info!(
                 "Transaction simulation successful - Wallet: {}, Chain: {}, Estimated Gas: {}",
                 input.to,
@@ -608,11 +2416,17 @@ async fn process_send_transaction(
                         gas_price,
                         chain_id
                     );
-                    return Err(jsonrpc_core::Error::internal_error());
+                    return Err(RelayError::Internal.into());
                 }
             };
 
-            let required_balance = match gas_price_u256.checked_mul(U256::from(sim_gas)) {
+            let l1_data_fee = fetch_l1_data_fee(chain_id, &input.to, &input.data, cfg).await;
+            let required_balance = match gas_price_u256
+                .checked_mul(U256::from(sim_gas))
+                .and_then(|fee| fee.checked_add(l1_data_fee))
+                .and_then(|fee| fee.checked_add(blob_gas_fee))
+                .and_then(|fee| fee.checked_add(value))
+            {
                 Some(value) => value,
                 None => {
                     tracing::error!(
@@ -621,7 +2435,7 @@ async fn process_send_transaction(
                         sim_gas,
                         chain_id
                     );
-                    return Err(jsonrpc_core::Error::internal_error());
+                    return Err(RelayError::Internal.into());
                 }
             };
 
@@ -632,25 +2446,12 @@ async fn process_send_transaction(
                         "No RPC URL configured for chain {} while checking native balance",
                         chain_id
                     );
-                    return Err(jsonrpc_core::Error::internal_error());
+                    return Err(RelayError::ChainUnavailable(chain_id.to_string()).into());
                 }
             };
 
-            let provider = match Url::parse(&rpc_url) {
-                Ok(endpoint) => ProviderBuilder::new().on_hyper_http(endpoint),
-                Err(e) => {
-                    tracing::error!(
-                        "Invalid RPC URL '{}' for chain {}: {}",
-                        rpc_url,
-                        chain_id,
-                        e
-                    );
-                    return Err(jsonrpc_core::Error::internal_error());
-                }
-            };
-
-            let balance = match provider.get_balance(wallet_address).await {
-                Ok(bal) => bal,
+            let balance = match cfg.chain.balance(&rpc_url, wallet_address).await {
+                Ok(bal) => bal,
                 Err(e) => {
                     tracing::error!(
                         "Failed to fetch native balance for {} on chain {}: {}",
@@ -658,7 +2459,7 @@ async fn process_send_transaction(
                         chain_id,
                         e
                     );
-                    return Err(jsonrpc_core::Error::internal_error());
+                    return Err(RelayError::ChainUnavailable(chain_id.to_string()).into());
                 }
             };
 
@@ -670,61 +2471,304 @@ async fn process_send_transaction(
                     required_balance,
                     balance
                 );
-                return Err(invalid_params_error());
+                if !dry_run {
+                    return Err(RelayError::InsufficientBalance {
+                        required: required_balance.to_string(),
+                        available: balance.to_string(),
+                    }
+                    .into());
+                }
+                dry_run_failure.get_or_insert_with(|| {
+                    format!(
+                        "insufficient native balance (required: {}, available: {})",
+                        required_balance, balance
+                    )
+                });
             }
+
+            l1_data_fee
         }
-        "erc20" => {
+        "erc20" | "permit" => {
             tracing::debug!(
-                "Processing ERC20 payment transaction with token: {}",
+                "Processing {} payment transaction with token: {}",
+                input.capabilities.payment.payment_type,
                 input.capabilities.payment.token
             );
 
-            if !input.capabilities.payment.token.starts_with("0x")
-                || input.capabilities.payment.token.len() != 42
+            let permit = match payment_type {
+                "permit" => {
+                    let permit = decode_fee_permit(&input.capabilities.payment.data)?;
+                    check_permit_deadline(&permit)?;
+                    tracing::debug!(
+                        "Fee permit from {} for {} (deadline {})",
+                        permit.owner,
+                        permit.value,
+                        permit.deadline
+                    );
+                    Some(permit)
+                }
+                _ => None,
+            };
+
+            let l1_data_fee = fetch_l1_data_fee(chain_id, &input.to, &input.data, cfg).await;
+            tracing::info!(
+                "ERC20 transaction gas estimate: {}, L1 data fee: {} wei",
+                sim_gas,
+                l1_data_fee
+            );
+
+            // The fee re-priced now, L1 data fee included; a token that cannot be priced is
+            // left to the fee transfer built into the quote
+            let cost = RelayCost {
+                gas_limit: sim_gas,
+                wei_per_gas: parse_hex_u256(&gas_price).unwrap_or(U256::ZERO),
+                l1_data_fee,
+                blob_gas_fee,
+                value,
+            };
+            let token = &input.capabilities.payment.token;
+            let fee = token_fee(token, chain_id, cost, cfg).await;
+
+            // The permit was signed for a quote; it must still cover the fee re-priced now,
+            // within the chain's slippage tolerance
+            if let Some(permit) = &permit {
+                if let Some(fee) = fee {
+                    if permit.value < fee_with_slippage(fee, cfg.rate_slippage_bps(chain_id)) {
+                        tracing::warn!(
+                            "Permit value {} is below the re-priced fee {} for {} on chain {}",
+                            permit.value,
+                            fee,
+                            input.to,
+                            chain_id
+                        );
+                        if !dry_run {
+                            return Err(RelayError::InsufficientFee {
+                                required: fee.to_string(),
+                                offered: permit.value.to_string(),
+                            }
+                            .into());
+                        }
+                        dry_run_failure.get_or_insert_with(|| {
+                            format!(
+                                "permit value below the fee (required: {}, offered: {})",
+                                fee, permit.value
+                            )
+                        });
+                    }
+                }
+            }
+
+            // The payer, the permit owner or else the wallet, must hold enough of the token
+            // to pay for execution and the L1 data fee
+            let payer = match &permit {
+                Some(permit) => Some(permit.owner),
+                None => input.to.parse::<Address>().ok(),
+            };
+            if let (Some(fee), Some(payer)) = (fee, payer) {
+                let required = fee_with_slippage(fee, cfg.rate_slippage_bps(chain_id));
+                if let Some(balance) = erc20_balance(chain_id, token, payer, cfg).await {
+                    if balance < required {
+                        tracing::warn!(
+                            "Insufficient {} balance for {} on chain {} (required: {}, available: {}, L1 data fee: {} wei)",
+                            token,
+                            payer,
+                            chain_id,
+                            required,
+                            balance,
+                            l1_data_fee
+                        );
+                        if !dry_run {
+                            return Err(RelayError::InsufficientBalance {
+                                required: required.to_string(),
+                                available: balance.to_string(),
+                            }
+                            .into());
+                        }
+                        dry_run_failure.get_or_insert_with(|| {
+                            format!(
+                                "insufficient token balance (required: {}, available: {})",
+                                required, balance
+                            )
+                        });
+                    }
+                }
+            }
+            l1_data_fee
+        }
+        "gasTank" => {
+            tracing::debug!("Processing gas tank transaction");
+
+            // Anyone can name the wallet in `to`, so the debit needs the wallet's signature
+            let authorization = decode_gas_tank_authorization(&input.capabilities.payment.data)?;
+            let authorization_hash =
+                verify_gas_tank_authorization(&authorization, chain_id, &input.to, &input.data)?;
+            if storage
+                .is_gas_tank_authorization_spent(
+                    &chain_id.to_string(),
+                    &authorization_hash.to_string(),
+                )
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to read gas tank authorization: {}", e);
+                    RelayError::Internal
+                })?
             {
-                tracing::warn!(
-                    "Invalid ERC20 token address format: {}",
-                    input.capabilities.payment.token
+                return Err(
+                    RelayError::invalid_params("Gas tank authorization already used").into(),
                 );
-                return Err(invalid_params_error());
             }
 
-            let supported_tokens = cfg.get_supported_tokens();
-            let token_lower = input.capabilities.payment.token.to_ascii_lowercase();
-            if !supported_tokens
-                .iter()
-                .any(|token| token.to_ascii_lowercase() == token_lower)
-            {
+            let l1_data_fee = fetch_l1_data_fee(chain_id, &input.to, &input.data, cfg).await;
+            let fee = parse_hex_u256(&gas_price)
+                .and_then(|price| price.checked_mul(U256::from(sim_gas)))
+                .and_then(|fee| fee.checked_add(l1_data_fee))
+                .and_then(|fee| fee.checked_add(blob_gas_fee))
+                .and_then(|fee| fee.checked_add(value))
+                .and_then(|fee| u128::try_from(fee).ok())
+                .ok_or_else(|| {
+                    tracing::error!(
+                        "Gas tank fee overflow: price={}, gas={} on chain {}",
+                        gas_price,
+                        sim_gas,
+                        chain_id
+                    );
+                    RelayError::Internal
+                })?;
+            let balance = storage
+                .get_gas_tank_balance(&chain_id.to_string(), &input.to)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to read gas tank of {}: {}", input.to, e);
+                    RelayError::Internal
+                })?;
+            if U256::from(fee) > authorization.max_fee {
                 tracing::warn!(
-                    "Unsupported ERC20 payment token supplied: {}",
-                    input.capabilities.payment.token
+                    "Gas tank fee {} exceeds the {} authorized by {}",
+                    fee,
+                    authorization.max_fee,
+                    input.to
                 );
-                return Err(unsupported_payment_token_error());
+                if !dry_run {
+                    return Err(RelayError::InsufficientFee {
+                        required: fee.to_string(),
+                        offered: authorization.max_fee.to_string(),
+                    }
+                    .into());
+                }
+                dry_run_failure.get_or_insert_with(|| {
+                    format!(
+                        "gas tank fee above the authorized maximum (required: {}, authorized: {})",
+                        fee, authorization.max_fee
+                    )
+                });
             }
-
-            tracing::info!("ERC20 transaction gas estimate: {}", sim_gas);
+            if balance < fee {
+                tracing::warn!(
+                    "Insufficient gas tank balance for wallet {} on chain {} (required: {}, available: {})",
+                    input.to,
+                    chain_id,
+                    fee,
+                    balance
+                );
+                if !dry_run {
+                    return Err(RelayError::InsufficientBalance {
+                        required: fee.to_string(),
+                        available: balance.to_string(),
+                    }
+                    .into());
+                }
+                dry_run_failure.get_or_insert_with(|| {
+                    format!(
+                        "insufficient gas tank balance (required: {}, available: {})",
+                        fee, balance
+                    )
+                });
+            }
+            gas_tank_debit = Some((fee, authorization_hash));
+            l1_data_fee
         }
         "sponsored" => {
             tracing::debug!("Processing sponsored transaction");
+            if let Some(project) = check_sponsorship_key(&input.capabilities.payment, cfg)? {
+                let (period, _) = billing_period(Utc::now());
+                if let Some(allowance) = project.monthly_gas {
+                    let used = storage
+                        .get_sponsored_gas_used(&project.id, &period)
+                        .await
+                        .map_err(|e| {
+                            tracing::error!(
+                                "Failed to read sponsored gas of project {}: {}",
+                                project.id,
+                                e
+                            );
+                            RelayError::Internal
+                        })?;
+                    if used.saturating_add(sim_gas) > allowance {
+                        tracing::warn!(
+                            "Project {} cannot cover {} sponsored gas ({} of {} used in {})",
+                            project.id,
+                            sim_gas,
+                            used,
+                            allowance,
+                            period
+                        );
+                        if !dry_run {
+                            return Err(sponsorship_quota_exceeded().into());
+                        }
+                        dry_run_failure.get_or_insert_with(|| {
+                            format!(
+                                "sponsorship allowance exceeded ({} of {} gas used in {})",
+                                used, allowance, period
+                            )
+                        });
+                    }
+                }
+                sponsorship = Some((project, period));
+            }
             tracing::info!("Sponsored transaction gas estimate: {}", sim_gas);
+            U256::ZERO
         }
+        // Every other payment type was rejected by validate_payment
         _ => {
-            tracing::warn!(
-                "Unsupported payment type: {}",
-                input.capabilities.payment.payment_type
-            );
-            return Err(unsupported_capability_error());
+            return Err(RelayError::UnsupportedCapability(
+                input.capabilities.payment.payment_type.clone(),
+            )
+            .into());
         }
+    };
+
+    if dry_run {
+        let result = build_dry_run_result(
+            input,
+            chain_id,
+            gas_limit,
+            &gas_price,
+            l1_data_fee,
+            dry_run_failure,
+            cfg,
+        )
+        .await;
+        tracing::info!(
+            "✓ Dry run complete - To: {}, Chain: {}, Gas: {}, Would succeed: {}",
+            input.to,
+            chain_id,
+            gas_limit,
+            result.would_succeed
+        );
+        return Ok(SendTransactionResponse {
+            result: vec![SendTransactionResult {
+                chain_id: input.chain_id.clone(),
+                id: String::new(),
+                dry_run: Some(result),
+                estimated_confirmation_seconds: None,
+                payment: chosen_payment,
+            }],
+        });
     }
 
-    // Get fee collector address from config
-    let fee_collector = std::env::var("RELAYX_FEE_COLLECTOR")
-        .ok()
-        .or_else(|| cfg.fee_collector())
-        .unwrap_or_else(|| "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string());
+    let fee_collector = fee_collector_for_chain(cfg, &chain_id.to_string());
 
-    // Generate a unique transaction ID
-    let transaction_id = Uuid::new_v4().to_string();
+    let transaction_id = request_id.to_string();
 
     tracing::info!("Generated transaction ID: {}", transaction_id);
     tracing::debug!(
@@ -739,27 +2783,47 @@ async fn process_send_transaction(
         id: Uuid::parse_str(&transaction_id).unwrap(),
         from_address: fee_collector.clone(), // Use fee collector as sender address
         to_address: input.to.clone(),
-        amount: "0".to_string(), // Will be calculated based on transaction
-        gas_limit,               // Gas limit from simulation
+        amount: value.to_string(),
+        gas_limit,                    // Gas limit from simulation
         gas_price: gas_price.clone(), // Dynamic gas price from RPC
         data: Some(input.data.clone()),
-        nonce: 0, // Will be fetched from chain
+        nonce: 0, // Set when the transaction is sent
         chain_id,
         transaction_hash: None, // Will be set when transaction is sent
         status: RequestStatus::Pending,
-        created_at: Utc::now(),
+        created_at: scheduled.map_or_else(Utc::now, |req| req.created_at),
         updated_at: Utc::now(),
         error_message: None,
+        bundle_id: None,
+        execute_after: scheduled.and_then(|req| req.execute_after),
+        speed,
+        broadcast_at: None,
+        mined_at: None,
+        blob_sidecar: input.blob_sidecar.clone(),
+        max_fee_per_blob_gas: blobs.map(|(_, max_fee)| format!("0x{:x}", max_fee)),
+        blob_gas_used: None,
+        blob_gas_price: None,
+        raw_transaction: None,
+        private_mempool: false,
+        payment: chosen_payment.clone(),
+        fee: Some(RelayFee {
+            payment_type: input.capabilities.payment.payment_type.clone(),
+            token: input.capabilities.payment.token.clone(),
+            priced_wei: RelayCost {
+                gas_limit,
+                wei_per_gas: parse_hex_u256(&gas_price).unwrap_or_default(),
+                l1_data_fee,
+                blob_gas_fee,
+                value: U256::ZERO,
+            }
+            .fee_wei()
+            .to_string(),
+        }),
+        resubmission_count: 0,
+        tenant,
     };
 
-    // Store the request in storage
-    tracing::debug!("Storing transaction request in database");
-    if let Err(e) = storage.create_request(relayer_request.clone()).await {
-        tracing::error!("Failed to store transaction request: {}", e);
-        return Err(jsonrpc_core::Error::internal_error());
-    }
-
-    tracing::debug!("Transaction request stored successfully");
+    let created_at = relayer_request.created_at;
 
     // Log the transaction request
     tracing::info!(
@@ -771,47 +2835,109 @@ async fn process_send_transaction(
         gas_limit
     );
 
+    // Gas tank relays are paid up front; the debit is returned if the broadcast fails
+    if let Some((fee, authorization)) = gas_tank_debit {
+        match storage
+            .debit_gas_tank(
+                &chain_id.to_string(),
+                &input.to,
+                fee,
+                &authorization.to_string(),
+                relayer_request.id,
+            )
+            .await
+        {
+            Ok(Some(remaining)) => tracing::info!(
+                "Debited {} wei from the gas tank of {} ({} wei left)",
+                fee,
+                input.to,
+                remaining
+            ),
+            Ok(None) => {
+                let available = storage
+                    .get_gas_tank_balance(&chain_id.to_string(), &input.to)
+                    .await
+                    .unwrap_or(0);
+                return Err(RelayError::InsufficientBalance {
+                    required: fee.to_string(),
+                    available: available.to_string(),
+                }
+                .into());
+            }
+            Err(e) => {
+                tracing::error!("Failed to debit gas tank of {}: {}", input.to, e);
+                return Err(RelayError::Internal.into());
+            }
+        }
+    }
+
+    if let Some((project, period)) = &sponsorship {
+        reserve_sponsored_gas(&storage, project, period, gas_limit).await?;
+    }
+
     // Send the transaction on-chain
     tracing::info!("Sending relay transaction on-chain...");
-    match send_relay_transaction(&input.to, &input.data, chain_id, gas_limit, &gas_price, cfg).await
-    {
-        Ok(tx_hash) => {
+    let sent = send_relay_transaction(
+        &input.to,
+        &input.data,
+        chain_id,
+        gas_limit,
+        &gas_price,
+        SendOptions {
+            replaces: None,
+            blobs,
+            value,
+        },
+        cfg,
+    )
+    .await;
+
+    // Record the request together with the outcome of its broadcast in one write
+    let mut recorded_request = relayer_request;
+    recorded_request.updated_at = Utc::now();
+    match &sent {
+        Ok((tx_hash, nonce)) => {
+            recorded_request.status = RequestStatus::Processing;
+            recorded_request.transaction_hash = Some(tx_hash.clone());
+            recorded_request.nonce = *nonce;
+            recorded_request.broadcast_at = Some(recorded_request.updated_at);
+        }
+        Err(e) => {
+            recorded_request.status = RequestStatus::Failed;
+            recorded_request.error_message = Some(e.clone());
+        }
+    }
+    tracing::debug!("Storing transaction request in database");
+    let mut batch = StorageBatch::new();
+    // A scheduled request registered its callback when it was queued
+    let callback = input
+        .callback_url
+        .as_deref()
+        .filter(|_| scheduled.is_none())
+        .map(pending_callback);
+    let staged = batch
+        .put_request(&recorded_request)
+        .and_then(|()| match &callback {
+            Some(callback) => batch.put_callback(recorded_request.id, callback),
+            None => Ok(()),
+        });
+    let stored = match staged {
+        Ok(()) => storage.commit(batch).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = stored {
+        tracing::error!("Failed to store transaction request: {}", e);
+        return Err(RelayError::Internal.into());
+    }
+
+    match sent {
+        Ok((tx_hash, _)) => {
             tracing::info!(
                 "✓ Relay transaction sent successfully - TX Hash: {}, ID: {}",
                 tx_hash,
                 transaction_id
             );
-
-            // Update storage with transaction hash and set status to Processing
-            let mut updated_request = relayer_request;
-            updated_request.status = RequestStatus::Processing;
-
-            // store tx hash
-            if let Err(e) = storage
-                .update_request_tx_hash(updated_request.id, tx_hash.clone())
-                .await
-            {
-                tracing::warn!("Failed to store tx hash: {}", e);
-            }
-
-            if let Err(e) = storage
-                .update_request_status(updated_request.id, RequestStatus::Processing, None)
-                .await
-            {
-                tracing::warn!("Failed to update request status to Processing: {}", e);
-            }
-
-            if stub_mode_enabled() {
-                if let Err(e) = storage
-                    .update_request_status(updated_request.id, RequestStatus::Completed, None)
-                    .await
-                {
-                    tracing::warn!(
-                        "Stub mode: failed to update request status to Completed: {}",
-                        e
-                    );
-                }
-            }
+            lifecycle_breadcrumb(request_id, "broadcast", format!("broadcast {}", tx_hash));
 
             tracing::info!(
                 "✓ Transaction relay complete - TX Hash: {}, ID: {}, Chain: {}",
@@ -827,1258 +2953,6841 @@ async fn process_send_transaction(
                 e
             );
 
+            if gas_tank_debit.is_some() {
+                if let Err(e) = storage.refund_gas_tank(recorded_request.id).await {
+                    tracing::error!("Failed to refund gas tank of {}: {}", input.to, e);
+                }
+            }
+            if let Some((project, period)) = &sponsorship {
+                release_sponsored_gas(&storage, project, period, gas_limit).await;
+            }
+            spawn_callback(&storage, cfg, recorded_request.id);
+
             // Capture critical transaction relay failure in Sentry
-            sentry::configure_scope(|scope| {
-                scope.set_tag("transaction_id", &transaction_id);
-                scope.set_extra("error", e.clone().into());
-            });
-            sentry::capture_message(
+            capture_lifecycle_failure(
+                request_id,
                 &format!("Failed to send relay transaction: {}", e),
-                sentry::Level::Error,
+                &e,
             );
 
-            // Update storage with error status
-            if let Err(update_err) = storage
-                .update_request_status(relayer_request.id, RequestStatus::Failed, Some(e.clone()))
-                .await
-            {
-                tracing::error!("Failed to update request status to Failed: {}", update_err);
-            }
-
-            return Err(jsonrpc_core::Error::internal_error());
+            return Err(broadcast_error(chain_id, &e).into());
         }
     }
 
+    let queue_depth = storage
+        .count_in_flight(chain_id, Some(created_at))
+        .await
+        .unwrap_or(0);
+    let estimated_confirmation_seconds =
+        estimate_confirmation_seconds(chain_id, &gas_price, queue_depth, cfg).await;
+
     // Return the response with the generated transaction ID
     Ok(SendTransactionResponse {
         result: vec![SendTransactionResult {
             chain_id: input.chain_id.clone(),
             id: transaction_id,
+            dry_run: None,
+            estimated_confirmation_seconds: Some(estimated_confirmation_seconds),
+            payment: chosen_payment,
         }],
     })
 }
 
-/// Process multichain transaction request
-async fn process_send_transaction_multichain(
-    storage: Storage,
-    input: &SendTransactionMultichainRequest,
+/// Queue a relay for the scheduler to broadcast at `execute_after` (right away when `None`),
+/// once its condition holds if it has one. Only the static checks have run; simulation,
+/// pricing and payment happen when it comes due.
+async fn schedule_transaction(
+    storage: &Storage,
+    input: &SendTransactionRequest,
+    request_id: Uuid,
+    chain_id: u64,
+    execute_after: Option<chrono::DateTime<Utc>>,
+    tenant: Option<String>,
     cfg: &Config,
-) -> Result<SendTransactionMultichainResponse, jsonrpc_core::Error> {
-    tracing::info!("=== relayer_sendTransactionMultichain request received ===");
-    tracing::debug!(
-        "Request details - Transactions: {}, PaymentChainId: {}, Payment: {}",
-        input.transactions.len(),
-        input.payment_chain_id,
-        input.capabilities.payment.payment_type
-    );
-
-    // Validate that we have at least one transaction
-    if input.transactions.is_empty() {
-        tracing::warn!("Validation failed: No transactions provided");
-        return Err(jsonrpc_core::Error::invalid_params(
-            "At least one transaction is required",
-        ));
-    }
-
-    // Validate payment chain ID
-    if input.payment_chain_id.is_empty() {
-        tracing::warn!("Validation failed: Missing 'paymentChainId' field");
-        return Err(jsonrpc_core::Error::invalid_params(
-            "Missing required field: 'paymentChainId'",
-        ));
-    }
-
-    let payment_chain_id: u64 = input.payment_chain_id.parse().map_err(|_| {
-        tracing::warn!("Invalid paymentChainId format: {}", input.payment_chain_id);
-        jsonrpc_core::Error::invalid_params("Invalid paymentChainId: must be a valid number")
-    })?;
+) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
+    let fee_collector = fee_collector_for_chain(cfg, &chain_id.to_string());
+    let now = Utc::now();
+    let request = RelayerRequest {
+        id: request_id,
+        from_address: fee_collector,
+        to_address: input.to.clone(),
+        amount: "0".to_string(),
+        gas_limit: 0, // Simulated at execution time
+        gas_price: "0x0".to_string(),
+        data: Some(input.data.clone()),
+        nonce: 0,
+        chain_id,
+        transaction_hash: None,
+        status: RequestStatus::Scheduled,
+        created_at: now,
+        updated_at: now,
+        error_message: None,
+        bundle_id: None,
+        execute_after,
+        speed: input.speed.unwrap_or_default(),
+        broadcast_at: None,
+        mined_at: None,
+        blob_sidecar: None,
+        max_fee_per_blob_gas: None,
+        blob_gas_used: None,
+        blob_gas_price: None,
+        raw_transaction: None,
+        private_mempool: false,
+        payment: None,
+        fee: None,
+        resubmission_count: 0,
+        tenant,
+    };
+    let scheduled = ScheduledTransaction {
+        request_id: request.id,
+        execute_after: execute_after.unwrap_or(now),
+        request: input.clone(),
+    };
 
-    // Validate payment chain is supported
-    if !cfg.is_chain_supported(payment_chain_id) {
-        tracing::warn!("Unsupported payment chain ID: {}", payment_chain_id);
-        return Err(jsonrpc_core::Error::invalid_params(format!(
-            "Unsupported payment chain ID: {}",
-            payment_chain_id
-        )));
+    let mut batch = StorageBatch::new();
+    let staged = batch
+        .put_request(&request)
+        .and_then(|()| batch.put_schedule(&scheduled))
+        .and_then(|()| match input.callback_url.as_deref() {
+            Some(url) => batch.put_callback(request.id, &pending_callback(url)),
+            None => Ok(()),
+        });
+    let stored = match staged {
+        Ok(()) => storage.commit(batch).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = stored {
+        tracing::error!("Failed to store scheduled request: {}", e);
+        return Err(RelayError::Internal.into());
     }
 
-    tracing::debug!(
-        "Validating payment capability: {}",
-        input.capabilities.payment.payment_type
+    tracing::info!(
+        "✓ Transaction scheduled - ID: {}, To: {}, Chain: {}, Execute after: {}, Conditional: {}",
+        request.id,
+        input.to,
+        chain_id,
+        scheduled.execute_after,
+        input.condition.is_some()
     );
+    Ok(SendTransactionResponse {
+        result: vec![SendTransactionResult {
+            chain_id: input.chain_id.clone(),
+            id: request.id.to_string(),
+            dry_run: None,
+            estimated_confirmation_seconds: None,
+            payment: None,
+        }],
+    })
+}
 
-    // Validate payment capability
-    match input.capabilities.payment.payment_type.as_str() {
-        "native" => {
-            if input.capabilities.payment.token != "0x0000000000000000000000000000000000000000" {
-                tracing::warn!(
-                    "Invalid native payment token address: {}",
-                    input.capabilities.payment.token
-                );
-                return Err(invalid_params_error());
-            }
+/// How often the scheduler looks for scheduled requests that have come due
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(1);
+/// Scheduled requests executed per scheduler pass at most
+const SCHEDULER_BATCH: usize = 100;
+
+/// Relay every scheduled request that has come due, returning how many were taken. Each is
+/// removed from the queue before it runs, so one is never broadcast twice; a request that
+/// cannot be relayed any more is marked Failed with the reason.
+async fn run_due_schedules(storage: &Storage, cfg: &Config) -> usize {
+    let due = match storage.get_due_schedules(Utc::now(), SCHEDULER_BATCH).await {
+        Ok(due) => due,
+        Err(e) => {
+            tracing::error!("Failed to read the scheduler queue: {}", e);
+            return 0;
         }
-        "erc20" => {
-            if !input.capabilities.payment.token.starts_with("0x")
-                || input.capabilities.payment.token.len() != 42
-            {
-                tracing::warn!(
-                    "Invalid ERC20 token address format: {}",
-                    input.capabilities.payment.token
+    };
+
+    for scheduled in &due {
+        let mut batch = StorageBatch::new();
+        batch.remove_schedule(scheduled);
+        if let Err(e) = storage.commit(batch).await {
+            tracing::error!(
+                "Failed to dequeue scheduled request {}: {}",
+                scheduled.request_id,
+                e
+            );
+            continue;
+        }
+        // Requests cancelled while queued are no longer Scheduled
+        let req = match storage
+            .get_request(TenantScope::All, scheduled.request_id)
+            .await
+        {
+            Ok(Some(req)) if req.status == RequestStatus::Scheduled => req,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load scheduled request {}: {}",
+                    scheduled.request_id,
+                    e
                 );
-                return Err(invalid_params_error());
+                continue;
+            }
+        };
+
+        if let Some(condition) = &scheduled.request.condition {
+            if condition.expires_at.is_some_and(|at| at <= Utc::now()) {
+                tracing::info!("Condition of scheduled request {} expired", req.id);
+                fail_scheduled(storage, cfg, req.id, "condition not met before expiresAt").await;
+                continue;
+            }
+            match evaluate_condition(req.chain_id, condition, cfg).await {
+                Ok(true) => tracing::info!("Condition of scheduled request {} met", req.id),
+                outcome => {
+                    if let Err(e) = outcome {
+                        tracing::warn!("Failed to check condition of {}: {}", req.id, e);
+                    }
+                    let recheck = ScheduledTransaction {
+                        execute_after: Utc::now() + CONDITION_CHECK_INTERVAL,
+                        ..scheduled.clone()
+                    };
+                    let mut batch = StorageBatch::new();
+                    let requeued = match batch.put_schedule(&recheck) {
+                        Ok(()) => storage.commit(batch).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = requeued {
+                        tracing::error!("Failed to requeue scheduled request {}: {}", req.id, e);
+                    }
+                    continue;
+                }
             }
         }
-        "sponsored" => {
-            tracing::debug!("Processing sponsored multichain transaction");
-        }
-        _ => {
-            tracing::warn!(
-                "Unsupported payment type: {}",
-                input.capabilities.payment.payment_type
-            );
-            return Err(unsupported_capability_error());
+
+        tracing::info!("Executing scheduled request {}", req.id);
+        let Err(e) = relay_transaction(storage.clone(), &scheduled.request, cfg, Some(&req)).await
+        else {
+            continue;
+        };
+        tracing::warn!("Scheduled request {} failed: {}", req.id, e.message);
+        fail_scheduled(storage, cfg, req.id, &e.message).await;
+    }
+    due.len()
+}
+
+/// Mark a scheduled request that will not run Failed and report it to its callback. Relays
+/// that failed to broadcast are already recorded (and reported) and are left alone.
+async fn fail_scheduled(storage: &Storage, cfg: &Config, id: Uuid, message: &str) {
+    if let Ok(Some(current)) = storage.get_request(TenantScope::All, id).await {
+        if current.status == RequestStatus::Scheduled {
+            let _ = storage
+                .update_request_status(id, RequestStatus::Failed, Some(message.to_string()))
+                .await;
+            spawn_callback(storage, cfg, id);
         }
     }
+}
 
-    // Get fee collector address from config (shared across all transactions)
-    let fee_collector = std::env::var("RELAYX_FEE_COLLECTOR")
-        .ok()
-        .or_else(|| cfg.fee_collector())
-        .unwrap_or_else(|| "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string());
+/// How long a scheduled request waits before its unmet condition is checked again
+const CONDITION_CHECK_INTERVAL: chrono::Duration = chrono::Duration::seconds(5);
 
-    let mut results = Vec::new();
+/// Chainlink `latestRoundData()` selector
+const LATEST_ROUND_DATA_SELECTOR: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
 
-    // Process each transaction
-    for (idx, tx) in input.transactions.iter().enumerate() {
-        tracing::debug!(
-            "Processing transaction {} of {}: ChainId: {}, To: {}",
-            idx + 1,
-            input.transactions.len(),
-            tx.chain_id,
-            tx.to
-        );
-
-        // Validate transaction fields
-        if tx.to.is_empty() {
-            tracing::warn!("Transaction {} missing 'to' field", idx);
-            return Err(jsonrpc_core::Error::invalid_params(format!(
-                "Transaction {}: Missing required field: 'to'",
-                idx
-            )));
+/// Check that a condition names a valid contract and carries the fields its type needs
+fn validate_condition(condition: &ExecutionCondition) -> Result<(), RelayError> {
+    params::address(&condition.address).map_err(|e| {
+        tracing::warn!("Invalid condition address {}: {}", condition.address, e);
+        RelayError::invalid_params("Invalid condition address")
+    })?;
+    match condition.condition_type {
+        ConditionType::PriceFeed => {
+            if condition.operator.is_none() {
+                return Err(RelayError::invalid_params(
+                    "priceFeed condition requires an operator",
+                ));
+            }
+            let value = condition.value.as_deref().unwrap_or_default();
+            I256::from_dec_str(value).map_err(|_| {
+                RelayError::invalid_params("priceFeed condition value must be a decimal integer")
+            })?;
         }
-
-        if tx.data.is_empty() {
-            tracing::warn!("Transaction {} missing 'data' field", idx);
-            return Err(jsonrpc_core::Error::invalid_params(format!(
-                "Transaction {}: Missing required field: 'data'",
-                idx
-            )));
+        ConditionType::Call => {
+            let data = condition.data.as_deref().unwrap_or_default();
+            hex::decode(data.trim_start_matches("0x")).map_err(|_| {
+                RelayError::invalid_params("call condition data must be hex calldata")
+            })?;
         }
+    }
+    if condition.expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(RelayError::invalid_params(
+            "condition expiresAt is in the past",
+        ));
+    }
+    Ok(())
+}
 
-        if tx.chain_id.is_empty() {
-            tracing::warn!("Transaction {} missing 'chainId' field", idx);
-            return Err(jsonrpc_core::Error::invalid_params(format!(
-                "Transaction {}: Missing required field: 'chainId'",
-                idx
-            )));
+/// Whether a (validated) condition holds on `chain_id` right now
+async fn evaluate_condition(
+    chain_id: u64,
+    condition: &ExecutionCondition,
+    cfg: &Config,
+) -> Result<bool, String> {
+    match condition.condition_type {
+        ConditionType::PriceFeed => {
+            let out = eth_call_raw(
+                chain_id,
+                &condition.address,
+                LATEST_ROUND_DATA_SELECTOR.to_vec(),
+                cfg,
+            )
+            .await?;
+            // latestRoundData() returns (roundId, answer, startedAt, updatedAt, answeredInRound)
+            let answer = abi_word(&out, 1)
+                .map(I256::from_raw)
+                .ok_or_else(|| "feed returned no answer".to_string())?;
+            let threshold = I256::from_dec_str(condition.value.as_deref().unwrap_or_default())
+                .map_err(|e| e.to_string())?;
+            Ok(match condition.operator {
+                Some(ConditionOperator::Gt) => answer > threshold,
+                Some(ConditionOperator::Gte) => answer >= threshold,
+                Some(ConditionOperator::Lt) => answer < threshold,
+                Some(ConditionOperator::Lte) => answer <= threshold,
+                Some(ConditionOperator::Eq) => answer == threshold,
+                None => false,
+            })
         }
-
-        // Validate chain ID format and support
-        let chain_id: u64 = tx.chain_id.parse().map_err(|_| {
-            tracing::warn!("Transaction {} invalid chainId: {}", idx, tx.chain_id);
-            jsonrpc_core::Error::invalid_params(format!(
-                "Transaction {}: Invalid chainId format",
-                idx
-            ))
-        })?;
-
-        if !cfg.is_chain_supported(chain_id) {
-            tracing::warn!("Transaction {} unsupported chain: {}", idx, chain_id);
-            return Err(jsonrpc_core::Error::invalid_params(format!(
-                "Transaction {}: Unsupported chain ID: {}",
-                idx, chain_id
-            )));
+        ConditionType::Call => {
+            let data = condition.data.as_deref().unwrap_or_default();
+            let calldata = hex::decode(data.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+            let out = eth_call_raw(chain_id, &condition.address, calldata, cfg).await?;
+            Ok(abi_word(&out, 0).is_some_and(|word| !word.is_zero()))
         }
+    }
+}
 
-        // Fetch current gas price from the chain for this transaction
-        let gas_price = match fetch_gas_price(chain_id, cfg).await {
-            Ok(price) => price,
-            Err(e) => {
-                tracing::warn!(
-                    "Transaction {}: Failed to fetch gas price, using default: {}",
-                    idx,
-                    e
-                );
-                "0x4a817c800".to_string() // 20 gwei fallback
-            }
-        };
-
-        // Estimate gas limit for this transaction
-        let gas_limit = match simulate_transaction(&tx.to, &tx.data, chain_id, cfg).await {
-            Ok(gas) => {
-                tracing::debug!("Transaction {}: Estimated gas: {}", idx, gas);
-                gas
-            }
-            Err(e) => {
-                // If simulation is disabled, use default gas limit instead of failing
-                if cfg.is_simulation_disabled() {
-                    tracing::debug!(
-                        "Transaction {}: Simulation disabled, using default gas limit",
-                        idx
-                    );
-                    150_000 // Default gas limit
-                } else {
-                    tracing::warn!(
-                        "Transaction {}: Simulation failed, using default gas limit: {}",
-                        idx,
-                        e
-                    );
-                    150_000 // Use default if simulation fails
-                }
-            }
-        };
+/// Shortest interval a recurring job may repeat at
+const RECURRING_MIN_INTERVAL_SECS: u64 = 60;
+
+/// First tick of a job's schedule after `now`; ticks missed while the relayer was down or
+/// the job was paused are skipped rather than relayed in a burst
+fn next_run_after(
+    previous: chrono::DateTime<Utc>,
+    interval_seconds: u64,
+    now: chrono::DateTime<Utc>,
+) -> chrono::DateTime<Utc> {
+    let interval = i64::try_from(interval_seconds).unwrap_or(i64::MAX);
+    let elapsed = (now - previous).num_seconds().max(0);
+    let ticks = elapsed / interval + 1;
+    previous + chrono::Duration::seconds(interval.saturating_mul(ticks))
+}
 
-        // Generate unique transaction ID
-        let transaction_id = Uuid::new_v4().to_string();
+async fn process_create_recurring_job(
+    storage: Storage,
+    input: &CreateRecurringJobRequest,
+    cfg: &Config,
+) -> Result<RecurringJob, jsonrpc_core::Error> {
+    tracing::info!(
+        to = %input.request.to,
+        chain_id = %input.request.chain_id,
+        interval_seconds = input.interval_seconds,
+        "=== relayer_createRecurringJob request received ==="
+    );
 
-        tracing::info!(
-            "Transaction {}: Generated ID {} for chain {} (gas: {}, gasPrice: {})",
-            idx,
-            transaction_id,
-            chain_id,
-            gas_limit,
-            gas_price
+    if input.interval_seconds < RECURRING_MIN_INTERVAL_SECS {
+        return Err(RelayError::invalid_params(format!(
+            "intervalSeconds must be at least {}",
+            RECURRING_MIN_INTERVAL_SECS
+        ))
+        .into());
+    }
+    if input.request.capabilities.dry_run {
+        return Err(
+            RelayError::invalid_params("dryRun is not supported for recurring jobs").into(),
         );
+    }
+    if input.request.execute_after.is_some() {
+        return Err(RelayError::invalid_params(
+            "executeAfter is not supported for recurring jobs; use startAt",
+        )
+        .into());
+    }
+    let tenant = resolve_tenant(cfg, input.request.api_key.as_deref())?;
+    validate_relay_request(&input.request, cfg)?;
+
+    let now = Utc::now();
+    let job = RecurringJob {
+        id: Uuid::new_v4(),
+        request: input.request.clone(),
+        interval_seconds: input.interval_seconds,
+        paused: false,
+        next_run_at: input.start_at.unwrap_or(now).max(now),
+        created_at: now,
+        runs: 0,
+        last_run_at: None,
+        last_request_id: None,
+        last_error: None,
+        tenant,
+    };
+    storage.store_recurring_job(&job).await.map_err(|e| {
+        tracing::error!("Failed to store recurring job: {}", e);
+        RelayError::Internal
+    })?;
+    Ok(job)
+}
 
-        // Create relayer request record
-        let relayer_request = RelayerRequest {
-            id: Uuid::parse_str(&transaction_id).unwrap(),
-            from_address: fee_collector.clone(), // Use fee collector as sender address
-            to_address: tx.to.clone(),
-            amount: "0".to_string(),
-            gas_limit,                    // Dynamic gas limit from simulation
-            gas_price: gas_price.clone(), // Dynamic gas price from RPC
-            data: Some(tx.data.clone()),
-            nonce: 0,
-            chain_id,
-            transaction_hash: None, // Will be set when transaction is sent
-            status: RequestStatus::Pending,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-            error_message: None,
-        };
-
-        // Store the request
-        if let Err(e) = storage.create_request(relayer_request.clone()).await {
-            tracing::error!("Failed to store transaction {} request: {}", idx, e);
-            return Err(jsonrpc_core::Error::internal_error());
-        }
-
-        tracing::debug!("Transaction {} stored successfully", idx);
-
-        // Send the transaction on-chain
-        match send_relay_transaction(&tx.to, &tx.data, chain_id, gas_limit, &gas_price, cfg).await {
-            Ok(tx_hash) => {
-                tracing::info!(
-                    "✓ Multichain relay sent - idx: {}, TX Hash: {}, ID: {}, Chain: {}",
-                    idx,
-                    tx_hash,
-                    transaction_id,
-                    chain_id
-                );
-                // store tx hash and set Processing
-                if let Err(e) = storage
-                    .update_request_tx_hash(Uuid::parse_str(&transaction_id).unwrap(), tx_hash)
-                    .await
-                {
-                    tracing::warn!("Transaction {}: failed to store tx hash: {}", idx, e);
-                }
-                if let Err(e) = storage
-                    .update_request_status(
-                        Uuid::parse_str(&transaction_id).unwrap(),
-                        RequestStatus::Processing,
-                        None,
-                    )
-                    .await
-                {
-                    tracing::warn!("Transaction {}: failed to set Processing: {}", idx, e);
-                }
-                if stub_mode_enabled() {
-                    if let Err(e) = storage
-                        .update_request_status(
-                            Uuid::parse_str(&transaction_id).unwrap(),
-                            RequestStatus::Completed,
-                            None,
-                        )
-                        .await
-                    {
-                        tracing::warn!(
-                            "Stub mode: transaction {} failed to set Completed: {}",
-                            idx,
-                            e
-                        );
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::error!("Transaction {}: failed to send: {}", idx, e);
-                if let Err(update_err) = storage
-                    .update_request_status(
-                        Uuid::parse_str(&transaction_id).unwrap(),
-                        RequestStatus::Failed,
-                        Some(e.clone()),
-                    )
-                    .await
-                {
-                    tracing::warn!("Transaction {}: failed to set Failed: {}", idx, update_err);
-                }
-            }
-        }
+async fn process_pause_recurring_job(
+    storage: Storage,
+    input: &PauseRecurringJobRequest,
+    cfg: &Config,
+) -> Result<RecurringJob, jsonrpc_core::Error> {
+    tracing::info!(id = %input.id, "=== relayer_pauseRecurringJob request received ===");
 
-        // Add to results
-        results.push(MultichainTransactionResult {
-            chain_id: tx.chain_id.clone(),
-            id: transaction_id,
-        });
+    let tenant = resolve_tenant(cfg, input.api_key.as_deref())?;
+    let id = Uuid::parse_str(&input.id).map_err(|e| {
+        tracing::warn!("Invalid recurring job id {}: {}", input.id, e);
+        RelayError::invalid_params("Invalid job id")
+    })?;
+    let paused = input.paused.unwrap_or(true);
+    let now = Utc::now();
+    let owned = storage
+        .get_recurring_job(TenantScope::Tenant(tenant.as_deref()), id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read recurring job {}: {}", id, e);
+            RelayError::Internal
+        })?;
+    if owned.is_none() {
+        return Err(RelayError::invalid_params("Unknown job id").into());
     }
+    storage
+        .update_recurring_job(id, |job| {
+            // A resumed job picks up at its next tick rather than replaying missed ones
+            if job.paused && !paused && job.next_run_at < now {
+                job.next_run_at = next_run_after(job.next_run_at, job.interval_seconds, now);
+            }
+            job.paused = paused;
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update recurring job {}: {}", id, e);
+            RelayError::Internal
+        })?
+        .ok_or_else(|| RelayError::invalid_params("Unknown job id").into())
+}
 
+async fn process_list_recurring_jobs(
+    storage: Storage,
+    input: &ListRecurringJobsRequest,
+    cfg: &Config,
+) -> Result<ListRecurringJobsResponse, jsonrpc_core::Error> {
     tracing::info!(
-        "✓ Multichain transaction accepted - {} transaction(s) across {} chain(s), Payment chain: {}",
-        results.len(),
-        input.transactions.iter().map(|t| &t.chain_id).collect::<std::collections::HashSet<_>>().len(),
-        input.payment_chain_id
+        wallet = %input.wallet,
+        "=== relayer_listRecurringJobs request received ==="
     );
 
-    Ok(SendTransactionMultichainResponse { result: results })
+    let tenant = resolve_tenant(cfg, input.api_key.as_deref())?;
+    params::address(&input.wallet).map_err(|e| {
+        tracing::warn!("Invalid wallet address {}: {}", input.wallet, e);
+        RelayError::invalid_field("wallet", "Invalid wallet address")
+    })?;
+    let jobs = storage
+        .get_recurring_jobs(TenantScope::Tenant(tenant.as_deref()))
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read recurring jobs: {}", e);
+            RelayError::Internal
+        })?;
+    Ok(ListRecurringJobsResponse {
+        jobs: jobs
+            .into_iter()
+            .filter(|job| job.request.to.eq_ignore_ascii_case(&input.wallet))
+            .collect(),
+    })
 }
 
-async fn process_get_status(
-    storage: Storage,
-    request: &GetStatusRequest,
-    _cfg: &Config,
-) -> Result<GetStatusResponse, jsonrpc_core::Error> {
-    tracing::info!("=== relayer_getStatus request received ===");
-    tracing::debug!("Querying status for {} transaction(s)", request.ids.len());
-
-    let mut results: Vec<StatusResult> = Vec::new();
-
-    for id in &request.ids {
-        let mut status_result = StatusResult {
-            version: "2.0.0".to_string(),
-            id: id.clone(),
-            status: 404,
-            receipts: Vec::new(),
-            resubmissions: Vec::new(),
-            offchain_failure: Vec::new(),
-            onchain_failure: Vec::new(),
-        };
-
-        match Uuid::parse_str(id) {
-            Ok(uuid) => match storage.get_request(uuid).await {
-                Ok(Some(req)) => {
-                    // Map internal status to HTTP-style code
-                    status_result.status = match req.status {
-                        RequestStatus::Pending | RequestStatus::Processing => 201,
-                        RequestStatus::Completed => 200,
-                        RequestStatus::Failed => 500,
-                    };
+/// Relay every active recurring job whose next tick has come, returning how many ran. The
+/// schedule is advanced before relaying, so a run is never repeated even if it fails; each
+/// run goes through the same policy, balance and payment checks as a direct call.
+async fn run_due_recurring_jobs(storage: &Storage, cfg: &Config) -> usize {
+    let jobs = match storage.get_recurring_jobs(TenantScope::All).await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::error!("Failed to read recurring jobs: {}", e);
+            return 0;
+        }
+    };
 
-                    // If there was an off-chain error, include it
-                    if let Some(msg) = req.error_message.clone() {
-                        status_result
-                            .offchain_failure
-                            .push(OffchainFailure { message: msg });
-                    }
+    let now = Utc::now();
+    let mut ran = 0;
+    for job in jobs
+        .iter()
+        .filter(|job| !job.paused && job.next_run_at <= now)
+    {
+        // Re-checked under the lock: the job may have been paused since it was listed
+        let mut due = false;
+        let advanced = storage
+            .update_recurring_job(job.id, |job| {
+                due = !job.paused && job.next_run_at <= now;
+                if due {
+                    job.next_run_at = next_run_after(job.next_run_at, job.interval_seconds, now);
+                }
+            })
+            .await;
+        if let Err(e) = advanced {
+            tracing::error!("Failed to advance recurring job {}: {}", job.id, e);
+            continue;
+        }
+        if !due {
+            continue;
+        }
 
-                    // Include any resubmissions recorded
-                    if let Ok(mut resubs) = storage.get_resubmissions(uuid).await {
-                        // sort stable (optional)
-                        status_result.resubmissions.append(&mut resubs);
+        tracing::info!("Running recurring job {}", job.id);
+        // Repeating the same relay is the point of a job, so runs skip duplicate detection
+        let outcome =
+            relay_transaction_as(storage.clone(), &job.request, cfg, None, Uuid::new_v4()).await;
+        if let Err(e) = &outcome {
+            tracing::warn!("Recurring job {} run failed: {}", job.id, e.message);
+        }
+        let recorded = storage
+            .update_recurring_job(job.id, |job| {
+                job.runs += 1;
+                job.last_run_at = Some(now);
+                match &outcome {
+                    Ok(response) => {
+                        job.last_request_id = response
+                            .result
+                            .first()
+                            .and_then(|result| Uuid::parse_str(&result.id).ok());
+                        job.last_error = None;
                     }
+                    Err(e) => job.last_error = Some(e.message.clone()),
                 }
-                Ok(None) => {
-                    // keep 404
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to read request {}: {}", id, e);
-                    status_result.status = 500;
-                    status_result.offchain_failure.push(OffchainFailure {
-                        message: "internal storage error".to_string(),
-                    });
-                }
-            },
-            Err(_) => {
-                status_result.status = 400;
-                status_result.offchain_failure.push(OffchainFailure {
-                    message: "invalid id format".to_string(),
-                });
-            }
+            })
+            .await;
+        if let Err(e) = recorded {
+            tracing::error!("Failed to record run of recurring job {}: {}", job.id, e);
         }
-
-        results.push(status_result);
+        ran += 1;
     }
-
-    tracing::info!(
-        "✓ Status query completed for {} transaction(s)",
-        results.len()
-    );
-    Ok(GetStatusResponse { result: results })
+    ran
 }
 
-async fn process_health_check(
+/// Broadcast a transaction its sender signed and track it like a relay. A transaction reusing
+/// the sender and nonce of an in-flight raw transaction is recorded as that request's
+/// replacement rather than as a new request.
+async fn process_send_raw_transaction(
     storage: Storage,
-    _cfg: &Config,
-) -> Result<HealthResponse, jsonrpc_core::Error> {
-    tracing::debug!("=== health_check request received ===");
+    input: &SendRawTransactionRequest,
+    cfg: &Config,
+) -> Result<SendTransactionResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        chain_id = %input.chain_id,
+        private_mempool = input.private_mempool,
+        "=== relayer_sendRawTransaction request received ==="
+    );
 
-    let total_requests = storage.get_total_request_count().await.map_err(|e| {
-        tracing::error!("Failed to get total request count: {}", e);
-        jsonrpc_core::Error::internal_error()
+    let tenant = resolve_tenant(cfg, input.api_key.as_deref())?;
+    if let Some(url) = &input.callback_url {
+        validate_callback_url(url, cfg)?;
+    }
+    let chain_id: u64 = params::chain_id(&input.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", input.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
     })?;
+    if !cfg.is_chain_supported(chain_id) {
+        tracing::warn!("Unsupported chain ID requested: {}", chain_id);
+        return Err(RelayError::UnsupportedChain(chain_id.to_string()).into());
+    }
 
-    let pending_requests = storage
-        .get_request_count_by_status(RequestStatus::Pending)
-        .await
+    let raw: Bytes = params::hex_bytes(&input.raw_transaction).map_err(|_| {
+        tracing::warn!("rawTransaction is not valid hex");
+        RelayError::invalid_field("rawTransaction", "Invalid rawTransaction: expected hex")
+    })?;
+    let envelope = TxEnvelope::decode_2718(&mut raw.as_ref()).map_err(|e| {
+        tracing::warn!("Failed to decode rawTransaction: {}", e);
+        RelayError::invalid_field("rawTransaction", format!("Invalid rawTransaction: {}", e))
+    })?;
+    // Transactions without EIP-155 replay protection could be replayed on any chain
+    if envelope.chain_id() != Some(chain_id) {
+        tracing::warn!(
+            "rawTransaction chain id {:?} does not match chain {}",
+            envelope.chain_id(),
+            chain_id
+        );
+        return Err(RelayError::invalid_params(format!(
+            "rawTransaction must be signed for chain {}",
+            chain_id
+        ))
+        .into());
+    }
+    check_calldata_size(&format!("0x{}", hex::encode(envelope.input())), cfg)?;
+    let sender = envelope
+        .signature()
+        .recover_address_from_prehash(&envelope.signature_hash())
         .map_err(|e| {
-            tracing::error!("Failed to get pending request count: {}", e);
-            jsonrpc_core::Error::internal_error()
+            tracing::warn!("Failed to recover rawTransaction sender: {}", e);
+            RelayError::InvalidSignature
         })?;
+    let sender_hex = format!("0x{:x}", sender);
+    let nonce = envelope.nonce();
 
-    let completed_requests = storage
-        .get_request_count_by_status(RequestStatus::Completed)
+    let rpc_url = cfg.rpc_url_for_chain(&input.chain_id).ok_or_else(|| {
+        tracing::error!("No RPC URL configured for chain {}", chain_id);
+        RelayError::ChainUnavailable(chain_id.to_string())
+    })?;
+    let broadcast_url = if input.private_mempool {
+        cfg.private_rpc_url(&input.chain_id).ok_or_else(|| {
+            tracing::warn!(
+                "No private mempool endpoint configured for chain {}",
+                chain_id
+            );
+            RelayError::UnsupportedCapability("privateMempool".to_string())
+        })?
+    } else {
+        rpc_url.clone()
+    };
+
+    match cfg.chain.transaction_count(&rpc_url, sender).await {
+        Ok(next_nonce) if nonce < next_nonce => {
+            tracing::warn!(
+                "rawTransaction nonce {} of {} is already used (next {})",
+                nonce,
+                sender_hex,
+                next_nonce
+            );
+            return Err(RelayError::invalid_params(format!(
+                "Nonce {} is already used; the sender's next nonce is {}",
+                nonce, next_nonce
+            ))
+            .into());
+        }
+        Ok(_) => {}
+        // The node applies the same check when the transaction is broadcast
+        Err(e) => tracing::warn!("Failed to read nonce of {}: {}", sender_hex, e),
+    }
+
+    let replaced = match storage
+        .get_request_id_by_sender_nonce(chain_id, &sender_hex, nonce)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get completed request count: {}", e);
-            jsonrpc_core::Error::internal_error()
-        })?;
+    {
+        Ok(Some(id)) => storage
+            .get_request(TenantScope::Tenant(tenant.as_deref()), id)
+            .await
+            .ok()
+            .flatten()
+            .filter(|req| {
+                matches!(
+                    req.status,
+                    RequestStatus::Pending | RequestStatus::Processing
+                )
+            }),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::error!("Failed to look up nonce {} of {}: {}", nonce, sender_hex, e);
+            return Err(RelayError::Internal.into());
+        }
+    };
 
-    let failed_requests = storage
-        .get_request_count_by_status(RequestStatus::Failed)
+    let tx_hash = cfg
+        .chain
+        .send_raw_transaction(&broadcast_url, raw)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to get failed request count: {}", e);
-            jsonrpc_core::Error::internal_error()
+            tracing::warn!("rawTransaction rejected on chain {}: {}", chain_id, e);
+            RelayError::invalid_params(format!("rawTransaction was rejected: {}", e))
         })?;
+    let tx_hash = format!("0x{:x}", tx_hash);
+    let now = Utc::now();
 
-    tracing::debug!(
-        "Health metrics - Total: {}, Pending: {}, Completed: {}, Failed: {}, Uptime: {}s",
-        total_requests,
-        pending_requests,
-        completed_requests,
-        failed_requests,
-        storage.get_uptime_seconds()
-    );
-
-    Ok(build_health_response(
-        storage.get_uptime_seconds(),
-        total_requests,
-        pending_requests,
-        completed_requests,
-        failed_requests,
-    ))
-}
-
-async fn process_get_capabilities(
-    _storage: Storage,
-    cfg: &Config,
-) -> Result<GetCapabilitiesResponse, jsonrpc_core::Error> {
-    tracing::info!("=== relayer_getCapabilities request received ===");
+    let mut batch = StorageBatch::new();
+    let request = match replaced {
+        Some(mut request) => {
+            tracing::info!(
+                "rawTransaction {} replaces {} of request {}",
+                tx_hash,
+                request.transaction_hash.as_deref().unwrap_or_default(),
+                request.id
+            );
+            request.transaction_hash = Some(tx_hash.clone());
+            request.gas_price = format!("0x{:x}", envelope.max_fee_per_gas());
+            request.gas_limit = envelope.gas_limit();
+            request.raw_transaction = Some(input.raw_transaction.clone());
+            request.private_mempool = input.private_mempool;
+            request.status = RequestStatus::Processing;
+            request.error_message = None;
+            request.updated_at = now;
+            request.resubmission_count = request.resubmission_count.saturating_add(1);
+            let resubmission = Resubmission {
+                status: 201,
+                transaction_hash: tx_hash.clone(),
+                chain_id: input.chain_id.clone(),
+            };
+            if let Err(e) = batch.add_resubmission(request.id, &resubmission) {
+                tracing::error!("Failed to stage resubmission of {}: {}", request.id, e);
+                return Err(RelayError::Internal.into());
+            }
+            request
+        }
+        None => RelayerRequest {
+            id: Uuid::new_v4(),
+            from_address: sender_hex,
+            to_address: envelope
+                .to()
+                .map(|to| format!("0x{:x}", to))
+                .unwrap_or_default(),
+            amount: envelope.value().to_string(),
+            gas_limit: envelope.gas_limit(),
+            gas_price: format!("0x{:x}", envelope.max_fee_per_gas()),
+            data: None,
+            nonce,
+            chain_id,
+            transaction_hash: Some(tx_hash.clone()),
+            status: RequestStatus::Processing,
+            created_at: now,
+            updated_at: now,
+            error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::default(),
+            broadcast_at: Some(now),
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: envelope
+                .max_fee_per_blob_gas()
+                .map(|max_fee| format!("0x{:x}", max_fee)),
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: Some(input.raw_transaction.clone()),
+            private_mempool: input.private_mempool,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: tenant.clone(),
+        },
+    };
+    let callback = input.callback_url.as_deref().map(pending_callback);
+    let staged = batch.put_request(&request).and_then(|()| match &callback {
+        Some(callback) => batch.put_callback(request.id, callback),
+        None => Ok(()),
+    });
+    let stored = match staged {
+        Ok(()) => storage.commit(batch).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = stored {
+        tracing::error!("Failed to store raw transaction request: {}", e);
+        return Err(RelayError::Internal.into());
+    }
+    if request.resubmission_count > 0 {
+        compact_resubmissions(&storage, cfg, request.id).await;
+    }
+    tracing::info!(
+        "✓ Raw transaction broadcast - TX Hash: {}, ID: {}, Chain: {}",
+        tx_hash,
+        request.id,
+        chain_id
+    );
 
-    // Build capabilities based on configuration
-    // Extract all supported tokens from the chainlink configuration
-    let supported_tokens = cfg.get_supported_tokens();
+    let queue_depth = storage
+        .count_in_flight(chain_id, Some(request.created_at))
+        .await
+        .unwrap_or(0);
+    let estimated_confirmation_seconds =
+        estimate_confirmation_seconds(chain_id, &request.gas_price, queue_depth, cfg).await;
+    Ok(SendTransactionResponse {
+        result: vec![SendTransactionResult {
+            chain_id: input.chain_id.clone(),
+            id: request.id.to_string(),
+            dry_run: None,
+            estimated_confirmation_seconds: Some(estimated_confirmation_seconds),
+            payment: None,
+        }],
+    })
+}
 
-    tracing::debug!(
-        "Found {} supported token(s) from configuration",
-        supported_tokens.len()
+/// Relay a batch of independent calls as one Multicall3 `aggregate3` transaction, recording
+/// each call's outcome with the request
+async fn process_send_calls(
+    storage: Storage,
+    input: &SendCallsRequest,
+    cfg: &Config,
+) -> Result<SendCallsResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        calls = input.calls.len(),
+        chain_id = %input.chain_id,
+        "=== relayer_sendCalls request received ==="
     );
 
-    let mut payments = Vec::new();
-
-    // Add ERC20 payment options for each supported token
-    for token in &supported_tokens {
-        tracing::debug!("Adding ERC20 payment capability for token: {}", token);
-        payments.push(Payment::Erc20(Erc20Payment {
-            payment_type: PaymentType::Erc20,
-            token: token.clone(),
-        }));
+    resolve_tenant(cfg, input.api_key.as_deref())?;
+    if input.calls.is_empty() {
+        tracing::warn!("Validation failed: No calls provided");
+        return Err(RelayError::invalid_params("At least one call is required").into());
+    }
+    if !input.capabilities.fallback_payments.is_empty() {
+        tracing::warn!("Validation failed: payment options for a call batch");
+        return Err(RelayError::invalid_params(
+            "Multiple payment options are only supported by relayer_sendTransaction",
+        )
+        .into());
     }
 
-    // If no tokens found in config, fall back to default token
-    if payments.is_empty() {
-        let default_token = cfg
-            .default_token()
-            .unwrap_or_else(|| "0x036CbD53842c5426634e7929541eC2318f3dCF7e".to_string()); // USDC on Ethereum
-
-        tracing::debug!(
-            "No tokens configured, using default token: {}",
-            default_token
+    // Multicall3 executes the batch, so there is no wallet a fee could be collected from
+    if input.capabilities.payment.payment_type != "sponsored" {
+        tracing::warn!(
+            "Unsupported payment type for call batch: {}",
+            input.capabilities.payment.payment_type
         );
-        payments.push(Payment::Erc20(Erc20Payment {
-            payment_type: PaymentType::Erc20,
-            token: default_token,
-        }));
+        return Err(RelayError::UnsupportedCapability(
+            input.capabilities.payment.payment_type.clone(),
+        )
+        .into());
     }
 
-    // Always include native payment option
-    tracing::debug!("Adding native payment capability");
-    payments.push(Payment::Native(NativePayment {
-        payment_type: PaymentType::Native,
-        token: "0x0000000000000000000000000000000000000000".to_string(),
-    }));
+    let mut calls = Vec::with_capacity(input.calls.len());
+    for (idx, call) in input.calls.iter().enumerate() {
+        let to: Address = params::address(&call.to).map_err(|_| {
+            tracing::warn!("Call {} has an invalid 'to': {}", idx, call.to);
+            RelayError::invalid_field(
+                format!("calls[{}].to", idx),
+                format!("Call {}: Invalid 'to' address", idx),
+            )
+        })?;
+        let data: Bytes = if call.data.is_empty() {
+            Bytes::new()
+        } else {
+            params::hex_bytes(&call.data).map_err(|_| {
+                tracing::warn!("Call {} has invalid 'data'", idx);
+                RelayError::invalid_field(
+                    format!("calls[{}].data", idx),
+                    format!("Call {}: Invalid 'data': expected hex", idx),
+                )
+            })?
+        };
+        // aggregate3 cannot forward value, and a sponsored batch has no payer to repay it
+        if let Some(value) = &call.value {
+            match params::quantity(value).ok() {
+                Some(value) if value.is_zero() => {}
+                Some(_) => {
+                    tracing::warn!("Call {} carries value", idx);
+                    return Err(RelayError::UnsupportedCapability("value".to_string()).into());
+                }
+                None => {
+                    tracing::warn!("Call {} has an invalid 'value': {}", idx, value);
+                    return Err(RelayError::invalid_params(format!(
+                        "Call {}: Invalid 'value': expected a hex quantity",
+                        idx
+                    ))
+                    .into());
+                }
+            }
+        }
+        calls.push((to, data));
+    }
 
-    // Always include sponsored payment option
-    tracing::debug!("Adding sponsored payment capability");
-    payments.push(Payment::Sponsored(SponsoredPayment {
-        payment_type: PaymentType::Sponsored,
-    }));
+    let calldata = multicall::aggregate3_calldata(&calls);
+    let request = SendTransactionRequest {
+        to: cfg.multicall3_address(&input.chain_id),
+        data: format!("0x{}", hex::encode(&calldata)),
+        capabilities: input.capabilities.clone(),
+        chain_id: input.chain_id.clone(),
+        authorization_list: String::new(),
+        callback_url: input.callback_url.clone(),
+        execute_after: None,
+        condition: None,
+        speed: input.speed,
+        blob_sidecar: None,
+        safe: None,
+        value: None,
+        api_key: input.api_key.clone(),
+        quote_id: None,
+    };
+    let (chain_id, multicall_address, _) = validate_relay_request(&request, cfg)?;
 
-    let capabilities = Capabilities { payment: payments };
+    // Outcomes come from running the batch against the latest state; a failing call does not
+    // revert the others, so the relay itself only fails if Multicall3 does
+    let results = if cfg.is_simulation_disabled() {
+        Vec::new()
+    } else {
+        let rpc_url = cfg.rpc_url_for_chain(&input.chain_id).ok_or_else(|| {
+            tracing::error!("No RPC URL configured for chain {}", chain_id);
+            RelayError::ChainUnavailable(chain_id.to_string())
+        })?;
+        let output = cfg
+            .chain
+            .call(&rpc_url, multicall_address, calldata, U256::ZERO)
+            .await
+            .map_err(|e| {
+                tracing::warn!("Call batch simulation failed on chain {}: {}", chain_id, e);
+                RelayError::SimulationFailed(e)
+            })?;
+        multicall::decode_aggregate3_results(&output)
+            .filter(|outcomes| outcomes.len() == calls.len())
+            .ok_or_else(|| {
+                tracing::warn!(
+                    "0x{:x} returned no aggregate3 results on chain {}",
+                    multicall_address,
+                    chain_id
+                );
+                RelayError::SimulationFailed(format!(
+                    "0x{:x} is not a Multicall3 deployment on chain {}",
+                    multicall_address, chain_id
+                ))
+            })?
+            .into_iter()
+            .map(|outcome| CallResult {
+                success: outcome.success,
+                return_data: format!("0x{}", hex::encode(outcome.return_data)),
+            })
+            .collect()
+    };
 
-    tracing::info!(
-        "✓ Returning {} payment capability option(s)",
-        capabilities.payment.len()
-    );
+    let response = relay_transaction(storage.clone(), &request, cfg, None).await?;
+    for result in &response.result {
+        // Dry runs store nothing and return no id
+        let Ok(id) = Uuid::parse_str(&result.id) else {
+            continue;
+        };
+        if let Err(e) = storage.store_call_results(id, &results).await {
+            tracing::error!("Failed to store call results of {}: {}", id, e);
+        }
+    }
 
-    Ok(GetCapabilitiesResponse { capabilities })
+    Ok(SendCallsResponse {
+        result: response.result,
+        calls: results,
+    })
 }
 
-// (unused) Kept for potential reuse; prefer cached path used in start()
-// async fn process_get_exchange_rate(cfg: &Config, input: &ExchangeRateRequest) ->
-// Result<ExchangeRateResponse, jsonrpc_core::Error> { 	let now = Utc::now().timestamp() as u64;
-// 	let expiry = now + 600;
-// 	Ok(build_exchange_rate_response_with_provider(cfg, &get_or_create_provider(cfg,
-// &input.chain_id).await, input, expiry).await) }
-
-// async fn process_get_quote(_cfg: &Config) -> Result<QuoteResponse, jsonrpc_core::Error> {
-//     Ok(build_quote_response())
-// }
-
-/// Build a response for the health_check endpoint
-fn build_health_response(
-    uptime_seconds: u64,
-    total_requests: u64,
-    pending_requests: u64,
-    completed_requests: u64,
-    failed_requests: u64,
-) -> HealthResponse {
-    HealthResponse {
-        status: "healthy".to_string(),
-        timestamp: Utc::now(),
-        uptime_seconds,
-        total_requests,
-        pending_requests,
-        completed_requests,
-        failed_requests,
+/// Validate and simulate leg `idx` of a multichain submission, returning its chain id, value,
+/// gas price and gas limit
+async fn prepare_multichain_leg(
+    idx: usize,
+    tx: &MultichainTransaction,
+    sponsored: bool,
+    cfg: &Config,
+) -> Result<(u64, U256, String, u64), RelayError> {
+    if tx.to.is_empty() {
+        tracing::warn!("Transaction {} missing 'to' field", idx);
+        return Err(RelayError::invalid_field(
+            format!("transactions[{}].to", idx),
+            format!("Transaction {}: Missing required field: 'to'", idx),
+        ));
+    }
+    if let Err(e) = params::address(&tx.to) {
+        tracing::warn!(
+            "Transaction {} invalid wallet address {}: {}",
+            idx,
+            tx.to,
+            e
+        );
+        return Err(RelayError::invalid_field(
+            format!("transactions[{}].to", idx),
+            format!("Transaction {}: Invalid wallet address", idx),
+        ));
     }
-}
 
-/// Build a dynamic response for the relayer_getExchangeRate endpoint
-async fn build_exchange_rate_response(
-    cfg: &Config,
-    req: &ExchangeRateRequest,
-) -> ExchangeRateResponse {
-    tracing::debug!(
-        "Building exchange rate response for token: {} on chain: {}",
-        req.token,
-        req.chain_id
-    );
+    if tx.data.is_empty() {
+        tracing::warn!("Transaction {} missing 'data' field", idx);
+        return Err(RelayError::invalid_field(
+            format!("transactions[{}].data", idx),
+            format!("Transaction {}: Missing required field: 'data'", idx),
+        ));
+    }
+    check_calldata_size(&tx.data, cfg)?;
+    let value = call_value(tx.value.as_ref()).ok_or_else(|| {
+        tracing::warn!("Transaction {} invalid value: {:?}", idx, tx.value);
+        RelayError::invalid_field(
+            format!("transactions[{}].value", idx),
+            format!(
+                "Transaction {}: Invalid 'value': expected a hex quantity",
+                idx
+            ),
+        )
+    })?;
+    if !value.is_zero() && sponsored {
+        tracing::warn!("Transaction {} carries value in a sponsored bundle", idx);
+        return Err(RelayError::UnsupportedCapability("value".to_string()));
+    }
 
-    let now = Utc::now().timestamp() as u64;
-    let expiry = now + 600;
+    if tx.chain_id.is_empty() {
+        tracing::warn!("Transaction {} missing 'chainId' field", idx);
+        return Err(RelayError::invalid_field(
+            format!("transactions[{}].chainId", idx),
+            format!("Transaction {}: Missing required field: 'chainId'", idx),
+        ));
+    }
 
-    if stub_mode_enabled() {
-        tracing::debug!(
-            "Stub mode enabled: returning synthetic exchange rate for token {} on chain {}",
-            req.token,
-            req.chain_id
+    // Validate chain ID format and support
+    let chain_id: u64 = params::chain_id(&tx.chain_id).map_err(|e| {
+        tracing::warn!(
+            "Transaction {} invalid chainId: {} ({})",
+            idx,
+            tx.chain_id,
+            e
         );
-        let item = ExchangeRateResultItem::Success(ExchangeRateSuccess {
-            quote: ExchangeRateQuote {
-                rate: 1.0e-8,
-                token: TokenInfo {
-                    decimals: 18,
-                    address: req.token.clone(),
-                    symbol: Some("TOKEN".to_string()),
-                    name: Some("Stub Token".to_string()),
-                },
-            },
-            gas_price: "0x4a817c800".to_string(),
-            max_fee_per_gas: None,
-            max_priority_fee_per_gas: None,
-            fee_collector: std::env::var("RELAYX_FEE_COLLECTOR")
-                .ok()
-                .or_else(|| cfg.fee_collector())
-                .unwrap_or_else(|| "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string()),
-            expiry,
-        });
-        return ExchangeRateResponse { result: vec![item] };
+        RelayError::invalid_field(
+            format!("transactions[{}].chainId", idx),
+            format!(
+                "Transaction {}: Invalid chainId: must be a valid number",
+                idx
+            ),
+        )
+    })?;
+
+    if !cfg.is_chain_supported(chain_id) {
+        tracing::warn!("Transaction {} unsupported chain: {}", idx, chain_id);
+        return Err(RelayError::UnsupportedChain(chain_id.to_string()));
     }
 
-    let chain_id: u64 = match req.chain_id.parse() {
-        Ok(v) => v,
-        Err(_) => {
-            return ExchangeRateResponse {
-                result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
-                    error: ExchangeRateErrorBody {
-                        id: req.chain_id.clone(),
-                        message: "invalid chainId".to_string(),
-                    },
-                })],
-            };
+    // Fetch current gas price from the chain for this transaction
+    let gas_price = match fetch_gas_price(chain_id, cfg).await {
+        Ok(price) => price,
+        Err(e) => {
+            tracing::warn!(
+                "Transaction {}: Failed to fetch gas price, using default: {}",
+                idx,
+                e
+            );
+            "0x4a817c800".to_string() // 20 gwei fallback
         }
     };
 
-    // Zero address denotes native token
-    let zero_addr = "0x0000000000000000000000000000000000000000".to_string();
-
-    if req.token.to_lowercase() == zero_addr {
-        // Native token: rate per gas = gasPrice (wei) / 1e18 ETH per gas
-        let gas_price = fetch_gas_price(chain_id, cfg)
-            .await
-            .unwrap_or_else(|_| "0x4a817c800".to_string());
-        let wei =
-            u128::from_str_radix(gas_price.trim_start_matches("0x"), 16).unwrap_or(20_000_000_000);
-        let rate_eth_per_gas = (wei as f64) / 1e18_f64;
-        let item = ExchangeRateResultItem::Success(ExchangeRateSuccess {
-            quote: ExchangeRateQuote {
-                rate: rate_eth_per_gas,
-                token: TokenInfo {
-                    decimals: 18,
-                    address: zero_addr.clone(),
-                    symbol: Some("ETH".to_string()),
-                    name: Some("Ethereum".to_string()),
-                },
-            },
-            gas_price,
-            max_fee_per_gas: None,
-            max_priority_fee_per_gas: None,
-            fee_collector: std::env::var("RELAYX_FEE_COLLECTOR")
-                .ok()
-                .or_else(|| cfg.fee_collector())
-                .unwrap_or_else(|| "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string()),
-            expiry,
+    // Estimate gas limit for this transaction
+    let gas_limit = match simulate_transaction(&tx.to, &tx.data, value, chain_id, cfg).await {
+        Ok(gas) => {
+            tracing::debug!("Transaction {}: Estimated gas: {}", idx, gas);
+            gas
+        }
+        Err(e) => {
+            // If simulation is disabled, use default gas limit instead of failing
+            if cfg.is_simulation_disabled() {
+                tracing::debug!(
+                    "Transaction {}: Simulation disabled, using default gas limit",
+                    idx
+                );
+                cfg.fallback_gas_limit(chain_id)
+            } else if let Some(gas) = fallback_gas_after_failed_simulation(chain_id, true, cfg) {
+                tracing::warn!(
+                    "Transaction {}: Simulation failed, using fallback gas limit {}: {}",
+                    idx,
+                    gas,
+                    e
+                );
+                gas
+            } else {
+                tracing::warn!("Transaction {}: Simulation failed: {}", idx, e);
+                return Err(RelayError::SimulationFailed(format!(
+                    "Transaction {}: {}",
+                    idx, e
+                )));
+            }
+        }
+    };
+    if let Err(e) = check_gas_limit(gas_limit, cfg) {
+        tracing::warn!("Transaction {} rejected: {}", idx, e);
+        return Err(RelayError::TransactionTooLarge {
+            unit: "gas",
+            size: gas_limit,
+            limit: cfg.max_gas(),
         });
-        return ExchangeRateResponse { result: vec![item] };
     }
 
-    // ERC20 token: compute rate using Chainlink token/USD and native/USD feeds if configured
-    let chain_str = chain_id.to_string();
-
-    // Look up feeds from config
-    let token_feed = cfg.chainlink_token_usd(&chain_str, &req.token);
-    let native_feed = cfg.chainlink_native_usd(&chain_str);
+    Ok((chain_id, value, gas_price, gas_limit))
+}
 
-    if token_feed.is_none() || native_feed.is_none() {
-        return ExchangeRateResponse {
-            result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
-                error: ExchangeRateErrorBody {
-                    id: req.token.clone(),
-                    message: "oracle feed not configured for chain/token".to_string(),
-                },
-            })],
-        };
+/// Result for a multichain leg that failed with `error`
+fn failed_leg(chain_id: &str, id: String, error: RelayError) -> MultichainTransactionResult {
+    let error = jsonrpc_core::Error::from(error);
+    MultichainTransactionResult {
+        chain_id: chain_id.to_string(),
+        id,
+        status: MultichainLegStatus::Failed,
+        error: Some(MultichainLegError {
+            code: error.code.code(),
+            message: error.message,
+            data: error.data,
+        }),
     }
+}
 
-    let token_feed_addr = token_feed.unwrap();
-    let native_feed_addr = native_feed.unwrap();
+/// Process multichain transaction request. Each leg is relayed on its own: one that fails
+/// validation, simulation or broadcast is reported as `failed` in its result while the others
+/// go ahead.
+async fn process_send_transaction_multichain(
+    storage: Storage,
+    input: &SendTransactionMultichainRequest,
+    cfg: &Config,
+) -> Result<SendTransactionMultichainResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        transactions = input.transactions.len(),
+        payment_chain_id = %input.payment_chain_id,
+        "=== relayer_sendTransactionMultichain request received ==="
+    );
+    let tenant = resolve_tenant(cfg, input.api_key.as_deref())?;
+    tracing::debug!(
+        "Request details - Transactions: {}, PaymentChainId: {}, Payment: {}",
+        input.transactions.len(),
+        input.payment_chain_id,
+        input.capabilities.payment.payment_type
+    );
 
-    // Helper to call a contract view function
-    async fn eth_call_bytes(rpc_url: &str, to_address: &str, calldata: &[u8]) -> Option<Vec<u8>> {
-        let provider = ProviderBuilder::new().on_hyper_http(Url::parse(rpc_url).ok()?);
-        let to: Address = to_address.parse().ok()?;
-        let tx = TransactionRequest::default()
-            .to(to)
-            .input(Bytes::from(calldata.to_vec()).into());
-        provider.call(&tx).await.ok().map(|bytes| bytes.to_vec())
+    // Validate that we have at least one transaction
+    if input.transactions.is_empty() {
+        tracing::warn!("Validation failed: No transactions provided");
+        return Err(RelayError::invalid_params("At least one transaction is required").into());
     }
 
-    // Read aggregator decimals (function selector 0x313ce567)
-    async fn read_decimals(rpc_url: &str, contract: &str) -> Option<u8> {
-        let sel_decimals: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
-        let out = eth_call_bytes(rpc_url, contract, &sel_decimals).await?;
-        // last 32 bytes right-padded; take last byte for u8
-        out.last().cloned()
+    // Bundles are never dry-run; refuse rather than broadcast something the caller meant to test
+    if input.capabilities.dry_run {
+        tracing::warn!("Validation failed: dryRun is not supported for multichain submissions");
+        return Err(RelayError::invalid_params(
+            "dryRun is only supported by relayer_sendTransaction",
+        )
+        .into());
     }
 
-    // Read aggregator latestAnswer() (selector 0x50d25bcd) -> int256
-    async fn read_latest_answer(rpc_url: &str, aggregator: &str) -> Option<i128> {
-        let sel_latest_answer: [u8; 4] = [0x50, 0xd2, 0x5b, 0xcd];
-        let out = eth_call_bytes(rpc_url, aggregator, &sel_latest_answer).await?;
-        if out.len() < 32 {
-            return None;
-        }
-        let mut buf = [0u8; 16];
-        // take the lower 16 bytes (int128) from the 32-byte big-endian word
-        buf.copy_from_slice(&out[16..32]);
-        Some(i128::from_be_bytes(buf))
+    // One payment covers every leg, so there is no single wallet to pick an option for
+    if !input.capabilities.fallback_payments.is_empty() {
+        tracing::warn!("Validation failed: payment options for a multichain submission");
+        return Err(RelayError::invalid_params(
+            "Multiple payment options are only supported by relayer_sendTransaction",
+        )
+        .into());
     }
 
-    // Get RPC URL
-    let rpc_url = match cfg.rpc_url_for_chain(&chain_str) {
-        Some(u) => u,
-        None => {
-            return ExchangeRateResponse {
-                result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
-                    error: ExchangeRateErrorBody {
-                        id: chain_str,
-                        message: "rpc not configured for chain".to_string(),
-                    },
-                })],
-            };
-        }
-    };
+    // Validate payment chain ID
+    if input.payment_chain_id.is_empty() {
+        tracing::warn!("Validation failed: Missing 'paymentChainId' field");
+        return Err(RelayError::invalid_field(
+            "paymentChainId",
+            "Missing required field: 'paymentChainId'",
+        )
+        .into());
+    }
 
-    // Fetch prices and decimals
-    let native_dec = read_decimals(&rpc_url, &native_feed_addr)
-        .await
-        .unwrap_or(8);
-    let token_dec = read_decimals(&rpc_url, &token_feed_addr).await.unwrap_or(8);
-    let native_px = read_latest_answer(&rpc_url, &native_feed_addr).await;
-    let token_px = read_latest_answer(&rpc_url, &token_feed_addr).await;
+    let payment_chain_id: u64 = params::chain_id(&input.payment_chain_id).map_err(|e| {
+        tracing::warn!(
+            "Invalid paymentChainId format: {} ({})",
+            input.payment_chain_id,
+            e
+        );
+        RelayError::invalid_field(
+            "paymentChainId",
+            "Invalid paymentChainId: must be a valid number",
+        )
+    })?;
+
+    // Validate payment chain is supported
+    if !cfg.is_chain_supported(payment_chain_id) {
+        tracing::warn!("Unsupported payment chain ID: {}", payment_chain_id);
+        return Err(RelayError::UnsupportedChain(payment_chain_id.to_string()).into());
+    }
 
-    let (native_px, token_px) = match (native_px, token_px) {
-        (Some(n), Some(t)) if n > 0 && t > 0 => (n as f64, t as f64),
+    tracing::debug!(
+        "Validating payment capability: {}",
+        input.capabilities.payment.payment_type
+    );
+
+    // Validate payment capability; sponsored legs are charged to the caller's project
+    validate_payment(&input.capabilities.payment, payment_chain_id, cfg)?;
+    let mut sponsorship: Option<(SponsorshipProject, String)> = None;
+    match input.capabilities.payment.payment_type.as_str() {
+        "native" | "erc20" => {}
+        "sponsored" => {
+            tracing::debug!("Processing sponsored multichain transaction");
+            sponsorship = check_sponsorship_key(&input.capabilities.payment, cfg)?
+                .map(|project| (project, billing_period(Utc::now()).0));
+        }
+        // Permits and gas tanks pay for a single relay
         _ => {
-            return ExchangeRateResponse {
-                result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
-                    error: ExchangeRateErrorBody {
-                        id: req.token.clone(),
-                        message: "failed to read oracle price".to_string(),
-                    },
-                })],
-            };
+            tracing::warn!(
+                "Payment type {} is not supported for multichain submissions",
+                input.capabilities.payment.payment_type
+            );
+            return Err(RelayError::UnsupportedCapability(
+                input.capabilities.payment.payment_type.clone(),
+            )
+            .into());
         }
-    };
+    }
 
-    // Convert to floating prices in USD
-    let native_usd = native_px / 10f64.powi(native_dec as i32);
-    let token_usd = token_px / 10f64.powi(token_dec as i32);
+    // Group all legs of this submission under a single bundle ID
+    let bundle_id = Uuid::new_v4();
+    let mut bundle_request_ids = Vec::new();
 
-    // Fetch gas price
-    let gas_price_hex = fetch_gas_price(chain_id, cfg)
+    let mut results = Vec::new();
+
+    // Process each transaction
+    for (idx, tx) in input.transactions.iter().enumerate() {
+        tracing::debug!(
+            "Processing transaction {} of {}: ChainId: {}, To: {}",
+            idx + 1,
+            input.transactions.len(),
+            tx.chain_id,
+            tx.to
+        );
+
+        let (chain_id, value, gas_price, gas_limit) = match prepare_multichain_leg(
+            idx,
+            tx,
+            input.capabilities.payment.payment_type == "sponsored",
+            cfg,
+        )
         .await
-        .unwrap_or_else(|_| "0x4a817c800".to_string());
-    let wei =
-        u128::from_str_radix(gas_price_hex.trim_start_matches("0x"), 16).unwrap_or(20_000_000_000);
+        {
+            Ok(leg) => leg,
+            Err(e) => {
+                tracing::warn!("Transaction {} rejected: {}", idx, e);
+                results.push(failed_leg(&tx.chain_id, String::new(), e));
+                continue;
+            }
+        };
+        if let Some((project, period)) = &sponsorship {
+            match reserve_sponsored_gas(&storage, project, period, gas_limit).await {
+                Ok(()) => {}
+                Err(RelayError::Internal) => return Err(RelayError::Internal.into()),
+                Err(e) => {
+                    results.push(failed_leg(&tx.chain_id, String::new(), e));
+                    continue;
+                }
+            }
+        }
 
-    // native per gas in ether
-    let native_per_gas = (wei as f64) / 1e18_f64;
-    // token per gas = native_per_gas * (native_usd / token_usd)
-    let token_per_gas = native_per_gas * (native_usd / token_usd);
+        // Generate unique transaction ID
+        let transaction_id = Uuid::new_v4().to_string();
 
-    // Determine token decimals via ERC20 decimals() if possible
-    async fn read_erc20_decimals(rpc_url: &str, token: &str) -> Option<u8> {
-        read_decimals(rpc_url, token).await
-    }
-    let token_decimals = read_erc20_decimals(&rpc_url, &req.token)
+        tracing::info!(
+            "Transaction {}: Generated ID {} for chain {} (gas: {}, gasPrice: {})",
+            idx,
+            transaction_id,
+            chain_id,
+            gas_limit,
+            gas_price
+        );
+
+        let fee_collector = fee_collector_for_chain(cfg, &chain_id.to_string());
+
+        // Create relayer request record
+        let relayer_request = RelayerRequest {
+            id: Uuid::parse_str(&transaction_id).unwrap(),
+            from_address: fee_collector.clone(), // Use fee collector as sender address
+            to_address: tx.to.clone(),
+            amount: value.to_string(),
+            gas_limit,                    // Dynamic gas limit from simulation
+            gas_price: gas_price.clone(), // Dynamic gas price from RPC
+            data: Some(tx.data.clone()),
+            nonce: 0,
+            chain_id,
+            transaction_hash: None, // Will be set when transaction is sent
+            status: RequestStatus::Pending,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            error_message: None,
+            bundle_id: Some(bundle_id),
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: Some(RelayFee {
+                payment_type: input.capabilities.payment.payment_type.clone(),
+                token: input.capabilities.payment.token.clone(),
+                priced_wei: parse_hex_u256(&gas_price)
+                    .unwrap_or_default()
+                    .saturating_mul(U256::from(gas_limit))
+                    .to_string(),
+            }),
+            resubmission_count: 0,
+            tenant: tenant.clone(),
+        };
+
+        // Send the transaction on-chain, then record the request with its outcome
+        let mut relayer_request = relayer_request;
+        let mut broadcast_failure = None;
+        match send_relay_transaction(
+            &tx.to,
+            &tx.data,
+            chain_id,
+            gas_limit,
+            &gas_price,
+            SendOptions {
+                value,
+                ..SendOptions::default()
+            },
+            cfg,
+        )
         .await
-        .unwrap_or(18);
+        {
+            Ok((tx_hash, nonce)) => {
+                tracing::info!(
+                    "✓ Multichain relay sent - idx: {}, TX Hash: {}, ID: {}, Chain: {}",
+                    idx,
+                    tx_hash,
+                    transaction_id,
+                    chain_id
+                );
+                relayer_request.status = RequestStatus::Processing;
+                relayer_request.transaction_hash = Some(tx_hash);
+                relayer_request.nonce = nonce;
+                relayer_request.broadcast_at = Some(Utc::now());
+            }
+            Err(e) => {
+                tracing::error!("Transaction {}: failed to send: {}", idx, e);
+                if let Some((project, period)) = &sponsorship {
+                    release_sponsored_gas(&storage, project, period, gas_limit).await;
+                }
+                broadcast_failure = Some(broadcast_error(chain_id, &e));
+                relayer_request.status = RequestStatus::Failed;
+                relayer_request.error_message = Some(e);
+            }
+        }
+        relayer_request.updated_at = Utc::now();
 
-    let item = ExchangeRateResultItem::Success(ExchangeRateSuccess {
-        quote: ExchangeRateQuote {
-            rate: token_per_gas,
-            token: TokenInfo {
-                decimals: token_decimals,
-                address: req.token.clone(),
-                symbol: None,
-                name: None,
+        let mut batch = StorageBatch::new();
+        let stored = match batch.put_request(&relayer_request) {
+            Ok(()) => storage.commit(batch).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            tracing::error!("Failed to store transaction {} request: {}", idx, e);
+            return Err(RelayError::Internal.into());
+        }
+
+        tracing::debug!("Transaction {} stored successfully", idx);
+
+        // Add to results
+        bundle_request_ids.push(relayer_request.id);
+        results.push(match broadcast_failure {
+            Some(e) => failed_leg(&tx.chain_id, transaction_id, e),
+            None => MultichainTransactionResult {
+                chain_id: tx.chain_id.clone(),
+                id: transaction_id,
+                status: MultichainLegStatus::Accepted,
+                error: None,
             },
-        },
-        gas_price: gas_price_hex,
-        max_fee_per_gas: None,
-        max_priority_fee_per_gas: None,
-        fee_collector: std::env::var("RELAYX_FEE_COLLECTOR")
-            .ok()
-            .or_else(|| cfg.fee_collector())
-            .unwrap_or_else(|| "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string()),
-        expiry,
-    });
+        });
+    }
 
-    ExchangeRateResponse { result: vec![item] }
-}
+    let bundle = Bundle {
+        id: bundle_id,
+        payment_chain_id,
+        request_ids: bundle_request_ids,
+        created_at: Utc::now(),
+    };
+    if let Err(e) = storage.store_bundle(&bundle).await {
+        tracing::error!("Failed to store bundle {}: {}", bundle_id, e);
+        return Err(RelayError::Internal.into());
+    }
 
-/// Build a response for the relayer_getStatus endpoint
-#[allow(dead_code)]
-fn build_get_status_response(_req: &GetStatusRequest) -> GetStatusResponse {
-    GetStatusResponse {
-		result: vec![StatusResult {
-			version: "2.0.0".to_string(),
-			id: "0x00000000000000000000000000000000000000000000000000000000000000000e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331".to_string(),
-			status: 200,
-			receipts: vec![Receipt {
-				logs: vec![Log {
-					address: "0xa922b54716264130634d6ff183747a8ead91a40b".to_string(),
-					topics: vec!["0x5a2a90727cc9d000dd060b1132a5c977c9702bb3a52afe360c9c22f0e9451a68".to_string()],
-					data: "0xabcd".to_string(),
-				}],
-				status: "0x1".to_string(),
-				block_hash: "0xf19bbafd9fd0124ec110b848e8de4ab4f62bf60c189524e54213285e7f540d4a".to_string(),
-				block_number: "0xabcd".to_string(),
-				gas_used: "0xdef".to_string(),
-				transaction_hash: "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaff".to_string(),
-				chain_id: "1".to_string(),
-			}],
-			resubmissions: vec![Resubmission {
-				status: 200,
-				transaction_hash: "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaf3".to_string(),
-				chain_id: "1".to_string(),
-			}],
-			offchain_failure: vec![OffchainFailure {
-				message: "insufficient fee provided".to_string(),
-			}],
-			onchain_failure: vec![OnchainFailure {
-				transaction_hash: "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaf2".to_string(),
-				chain_id: "1".to_string(),
-				message: "execution reverted: transfer failed".to_string(),
-				data: "0x08c379a000000000000000000000000000000000000000000000000000000000".to_string(),
-			}],
-		}],
-	}
+    tracing::info!(
+        "✓ Multichain transaction accepted - {} transaction(s) across {} chain(s), Payment chain: {}, Bundle: {}",
+        results.len(),
+        input.transactions.iter().map(|t| &t.chain_id).collect::<std::collections::HashSet<_>>().len(),
+        input.payment_chain_id,
+        bundle_id
+    );
+
+    Ok(SendTransactionMultichainResponse {
+        result: results,
+        bundle_id: bundle_id.to_string(),
+    })
 }
 
-// Build a response for the relayer_sendTransaction endpoint (removed unused builder)
-/// Build a response for the relayer_getQuote endpoint
-#[allow(dead_code)]
-fn build_quote_response() -> QuoteResponse {
-    QuoteResponse {
-		quote: QuoteInner {
-			fee: 132,
-			rate: 3702.23,
-			token: TokenInfo {
-				decimals: 6,
-				address: "0x036CbD53842c5426634e7929541eC2318f3dCF7e".to_string(),
-				symbol: Some("USDC".to_string()),
-				name: Some("USDC".to_string()),
-			},
-		},
-		relayer_calls: vec![RelayerCall { to: "0x...".to_string(), data: "0x...".to_string() }],
-		fee_collector: "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
-		revert_reason: "0x87f20438000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000840000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000008408c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000002645524332303a207472616e7366657220616d6f756e7420657863656564732062616c616e6365000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000".to_string(),
-	}
+/// Whether `id` looks like a 32-byte transaction hash rather than a request id
+fn is_tx_hash(id: &str) -> bool {
+    params::hash(id).is_ok()
 }
 
-impl RpcServer {
-    pub fn new(host: String, port: u16, storage: Storage, config: Config) -> Result<Self> {
-        Ok(Self {
-            host,
-            port,
-            storage,
-            config,
-        })
+/// Where a stored request stands; a failed request that was mined reverted
+fn status_kind(req: &RelayerRequest) -> StatusKind {
+    match req.status {
+        RequestStatus::Scheduled => StatusKind::Scheduled,
+        RequestStatus::Pending | RequestStatus::Processing => StatusKind::Pending,
+        RequestStatus::Completed => StatusKind::Confirmed,
+        RequestStatus::Failed if req.mined_at.is_some() => StatusKind::Reverted,
+        RequestStatus::Failed => StatusKind::Failed,
     }
+}
 
-    pub async fn start(&self) -> Result<()> {
-        tracing::info!("Initializing JSON-RPC handler");
-        let mut io = IoHandler::new();
+/// Build the status entry for a single request id, or for the request that broadcast a
+/// transaction hash (the entry then carries the request id). Requests `visible` rejects
+/// are reported as unknown. Status codes are taken from `codes`.
+pub(crate) async fn build_status_result(
+    storage: &Storage,
+    id: &str,
+    codes: &StatusCodes,
+    scope: TenantScope<'_>,
+) -> StatusResult {
+    let mut kind = StatusKind::NotFound;
+    let mut status_result = StatusResult {
+        version: RELAY_SPEC_VERSION.to_string(),
+        id: id.to_string(),
+        status: 0,
+        status_name: None,
+        receipts: Vec::new(),
+        resubmissions: Vec::new(),
+        offchain_failure: Vec::new(),
+        onchain_failure: Vec::new(),
+        bundle_id: None,
+        estimated_confirmation_seconds: None,
+        callback: None,
+        execute_after: None,
+        calls: None,
+        payment: None,
+        resubmission_count: None,
+    };
 
-        // Endpoint 1: relayer_sendTransaction
-        tracing::debug!("Registering endpoint: relayer_sendTransaction");
-        let storage1 = self.storage.clone();
-        let cfg1 = self.config.clone();
-        io.add_method("relayer_sendTransaction", move |params: Params| {
-            let storage = storage1.clone();
-            let cfg = cfg1.clone();
+    let parsed = match Uuid::parse_str(id) {
+        Ok(uuid) => Ok(Some(uuid)),
+        Err(_) if is_tx_hash(id) => match storage.get_request_id_by_tx_hash(id).await {
+            Ok(found) => {
+                if let Some(uuid) = found {
+                    status_result.id = uuid.to_string();
+                }
+                Ok(found)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to look up tx hash {}: {}", id, e);
+                kind = StatusKind::Error;
+                status_result.offchain_failure.push(OffchainFailure {
+                    message: "internal storage error".to_string(),
+                });
+                return status_result.with_status(kind, codes);
+            }
+        },
+        Err(e) => Err(e),
+    };
 
-            async move {
-                tracing::info!("[relayer_sendTransaction] Request received");
-                if let Ok(params_json) = serde_json::to_string(&params) {
-                    tracing::debug!("[relayer_sendTransaction] Request params: {}", params_json);
+    match parsed {
+        Ok(None) => {
+            // unknown tx hash: not found
+        }
+        Ok(Some(uuid)) => match storage.get_request(scope, uuid).await {
+            Ok(Some(req)) => {
+                kind = status_kind(&req);
+                status_result.bundle_id = req.bundle_id.map(|id| id.to_string());
+                status_result.payment = req.payment.clone();
+                status_result.resubmission_count =
+                    (req.resubmission_count > 0).then_some(req.resubmission_count);
+                if req.status == RequestStatus::Scheduled {
+                    status_result.execute_after = req.execute_after;
                 }
 
-                let inputs: Vec<SendTransactionRequest> =
-                    params.parse::<Vec<SendTransactionRequest>>().map_err(|e| {
-                        tracing::warn!("[relayer_sendTransaction] Failed to parse params: {}", e);
-                        let err = invalid_params_error();
-                        tracing::error!(
-                            "[relayer_sendTransaction] Error response: code={:?}, message={}",
-                            err.code,
-                            err.message
-                        );
-                        capture_sentry_error("relayer_sendTransaction", &err);
+                // If there was an off-chain error, include it
+                if let Some(msg) = req.error_message.clone() {
+                    status_result
+                        .offchain_failure
+                        .push(OffchainFailure { message: msg });
+                }
+
+                // Include any resubmissions recorded
+                if let Ok(mut resubs) = storage.get_resubmissions(uuid).await {
+                    // sort stable (optional)
+                    status_result.resubmissions.append(&mut resubs);
+                }
+                status_result.callback = storage.get_callback(uuid).await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read callback of {}: {}", uuid, e);
+                    None
+                });
+                status_result.calls = storage.get_call_results(uuid).await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read call results of {}: {}", uuid, e);
+                    None
+                });
+                match storage.get_receipt(uuid).await {
+                    Ok(receipt) => status_result.receipts.extend(receipt),
+                    Err(e) => tracing::warn!("Failed to read receipt of {}: {}", uuid, e),
+                }
+                match storage.get_onchain_failure(uuid).await {
+                    Ok(failure) => status_result.onchain_failure.extend(failure),
+                    Err(e) => tracing::warn!("Failed to read on-chain failure of {}: {}", uuid, e),
+                }
+            }
+            Ok(None) => {
+                // not found, or another tenant's request: without revealing the id
+                status_result.id = id.to_string();
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read request {}: {}", id, e);
+                kind = StatusKind::Error;
+                status_result.offchain_failure.push(OffchainFailure {
+                    message: "internal storage error".to_string(),
+                });
+            }
+        },
+        Err(_) => {
+            kind = StatusKind::InvalidId;
+            status_result.offchain_failure.push(OffchainFailure {
+                message: "invalid id format".to_string(),
+            });
+        }
+    }
+
+    status_result.with_status(kind, codes)
+}
+
+async fn process_get_status(
+    storage: Storage,
+    request: &GetStatusRequest,
+    cfg: &Config,
+) -> Result<GetStatusResponse, jsonrpc_core::Error> {
+    tracing::info!("=== relayer_getStatus request received ===");
+    tracing::debug!("Querying status for {} transaction(s)", request.ids.len());
+
+    let tenant = resolve_tenant(cfg, request.api_key.as_deref())?;
+    let codes = cfg.status_codes();
+    let mut results: Vec<StatusResult> = Vec::new();
+
+    let scope = TenantScope::Tenant(tenant.as_deref());
+    for id in &request.ids {
+        let mut result = build_status_result(&storage, id, &codes, scope).await;
+        if result.status_name == Some(StatusKind::Pending) {
+            result.estimated_confirmation_seconds =
+                estimate_pending_confirmation(&storage, &result.id, cfg).await;
+        }
+        results.push(result);
+    }
+
+    tracing::info!(
+        "✓ Status query completed for {} transaction(s)",
+        results.len()
+    );
+    Ok(GetStatusResponse { result: results })
+}
+
+/// Page size for `relayer_getTransactionsByWallet` when the caller does not pick one
+const WALLET_HISTORY_DEFAULT_LIMIT: usize = 50;
+/// Largest page `relayer_getTransactionsByWallet` returns
+const WALLET_HISTORY_MAX_LIMIT: usize = 100;
+
+async fn process_get_transactions_by_wallet(
+    storage: Storage,
+    request: &GetTransactionsByWalletRequest,
+    cfg: &Config,
+) -> Result<GetTransactionsByWalletResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        wallet = %request.wallet,
+        "=== relayer_getTransactionsByWallet request received ==="
+    );
+
+    let tenant = resolve_tenant(cfg, request.api_key.as_deref())?;
+    params::address(&request.wallet).map_err(|e| {
+        tracing::warn!("Invalid wallet address {}: {}", request.wallet, e);
+        RelayError::invalid_field("wallet", "Invalid wallet address")
+    })?;
+    let limit = match request.limit {
+        Some(0) => return Err(RelayError::invalid_params("limit must be at least 1").into()),
+        Some(limit) => limit.min(WALLET_HISTORY_MAX_LIMIT),
+        None => WALLET_HISTORY_DEFAULT_LIMIT,
+    };
+
+    let codes = cfg.status_codes();
+    let (requests, next_cursor) = storage
+        .get_requests_by_wallet(
+            tenant.as_deref(),
+            &request.wallet,
+            request.cursor.as_deref(),
+            limit,
+            |req| {
+                request
+                    .status
+                    .is_none_or(|code| codes.code(status_kind(req)) == code)
+            },
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to read history for wallet {}: {}",
+                request.wallet,
+                e
+            );
+            RelayError::Internal
+        })?;
+
+    let result: Vec<WalletTransaction> = requests
+        .into_iter()
+        .map(|req| WalletTransaction {
+            id: req.id.to_string(),
+            chain_id: req.chain_id.to_string(),
+            status: codes.code(status_kind(&req)),
+            transaction_hash: req.transaction_hash,
+            bundle_id: req.bundle_id.map(|id| id.to_string()),
+            failure_reason: req.error_message,
+            created_at: req.created_at,
+            updated_at: req.updated_at,
+        })
+        .collect();
+
+    tracing::info!(
+        "✓ Wallet history query returned {} request(s) for {}",
+        result.len(),
+        request.wallet
+    );
+    Ok(GetTransactionsByWalletResponse {
+        result,
+        next_cursor,
+    })
+}
+
+/// Gas multiplier `relayer_resendTransaction` applies when the caller does not pick one,
+/// matching the monitor's 20% bump
+const RESEND_DEFAULT_GAS_MULTIPLIER: f64 = 1.2;
+/// Nodes reject replacements priced less than 10% above the pending transaction
+const RESEND_MIN_GAS_MULTIPLIER: f64 = 1.1;
+/// Guards against a typo draining the relayer's balance on a single replacement
+const RESEND_MAX_GAS_MULTIPLIER: f64 = 10.0;
+
+async fn process_resend_transaction(
+    storage: Storage,
+    request: &ResendTransactionRequest,
+    cfg: &Config,
+) -> Result<ResendTransactionResponse, jsonrpc_core::Error> {
+    let ResendTransactionRequest(id, options) = request;
+    tracing::info!(id = %id, "=== relayer_resendTransaction request received ===");
+
+    let tenant = resolve_tenant(
+        cfg,
+        options
+            .as_ref()
+            .and_then(|options| options.api_key.as_deref()),
+    )?;
+    let multiplier = options
+        .as_ref()
+        .and_then(|options| options.gas_multiplier)
+        .unwrap_or(RESEND_DEFAULT_GAS_MULTIPLIER);
+    if !(RESEND_MIN_GAS_MULTIPLIER..=RESEND_MAX_GAS_MULTIPLIER).contains(&multiplier) {
+        return Err(RelayError::invalid_params(format!(
+            "gasMultiplier must be between {} and {}",
+            RESEND_MIN_GAS_MULTIPLIER, RESEND_MAX_GAS_MULTIPLIER
+        ))
+        .into());
+    }
+
+    let request_id = Uuid::parse_str(id).map_err(|e| {
+        tracing::warn!("Invalid request id {}: {}", id, e);
+        RelayError::invalid_params("Invalid request id")
+    })?;
+    let req = storage
+        .get_request(TenantScope::Tenant(tenant.as_deref()), request_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load request {}: {}", request_id, e);
+            RelayError::Internal
+        })?
+        .ok_or_else(|| RelayError::UnknownRequest(id.to_string()))?;
+
+    if req.raw_transaction.is_some() {
+        return Err(RelayError::invalid_params(
+            "Raw transactions are signed by their sender; send a replacement with relayer_sendRawTransaction",
+        )
+        .into());
+    }
+    let tx_hash = match (&req.status, &req.transaction_hash, &req.data) {
+        (RequestStatus::Pending | RequestStatus::Processing, Some(tx_hash), Some(_)) => {
+            tx_hash.clone()
+        }
+        _ => return Err(RelayError::invalid_params("Request is not in flight").into()),
+    };
+    let max_attempts = cfg.resubmission_policy().max_attempts;
+    if req.resubmission_count >= max_attempts {
+        return Err(RelayError::invalid_params(format!(
+            "Request has reached the limit of {} resubmissions",
+            max_attempts
+        ))
+        .into());
+    }
+    if cfg.rpc_url_for_chain(&req.chain_id.to_string()).is_none() {
+        return Err(RelayError::UnsupportedChain(req.chain_id.to_string()).into());
+    }
+
+    // A transaction mined since the last monitor pass is finalized instead of replaced
+    let pending = [(req.clone(), tx_hash.clone())];
+    let receipt = lookup_receipts(cfg, req.chain_id, &pending)
+        .await
+        .pop()
+        .unwrap_or(Ok(None));
+    if apply_receipt(&storage, cfg, &req, &tx_hash, receipt)
+        .await
+        .is_some()
+    {
+        return Err(RelayError::invalid_params("Transaction is already mined").into());
+    }
+
+    // The replacement must outbid the pending transaction even if the network price fell
+    let parse_wei = |hex: &str| u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok();
+    let network_price = match fetch_gas_price(req.chain_id, cfg).await {
+        Ok(price_hex) => parse_wei(&price_hex),
+        Err(e) => {
+            tracing::warn!("Gas price lookup failed for chain {}: {}", req.chain_id, e);
+            None
+        }
+    };
+    let base_price = network_price
+        .into_iter()
+        .chain(parse_wei(&req.gas_price))
+        .max()
+        .ok_or(RelayError::Internal)?;
+    let percent = ((multiplier - 1.0) * 100.0).round() as u64;
+    let gas_price = bump_gas_price_hex(&format!("0x{:x}", base_price), percent);
+
+    let transaction_hash = resubmit_request(&storage, cfg, &req, &gas_price)
+        .await
+        .map_err(|e| {
+            tracing::error!("Manual resubmission of {} failed: {}", request_id, e);
+            RelayError::BroadcastFailed(req.chain_id.to_string())
+        })?;
+
+    tracing::info!(
+        "✓ Request {} resubmitted as {} at gas price {}",
+        request_id,
+        transaction_hash,
+        gas_price
+    );
+    Ok(ResendTransactionResponse {
+        id: request_id.to_string(),
+        chain_id: req.chain_id.to_string(),
+        transaction_hash,
+        gas_price,
+    })
+}
+
+/// Render chain logs for clients, decoding those of contracts with registered event ABIs
+fn format_logs(logs: &[TxLog], chain_id: u64, cfg: &Config) -> Vec<Log> {
+    let mut event_abis: HashMap<Address, Vec<alloy::json_abi::Event>> = HashMap::new();
+    logs.iter()
+        .map(|log| {
+            let events = event_abis
+                .entry(log.address)
+                .or_insert_with(|| cfg.event_abis(&chain_id.to_string(), &log.address.to_string()));
+            Log {
+                address: log.address.to_string(),
+                topics: log.topics.iter().map(|topic| topic.to_string()).collect(),
+                data: log.data.to_string(),
+                decoded: crate::events::decode_log(events, &log.topics, &log.data),
+            }
+        })
+        .collect()
+}
+
+/// Render a chain receipt in the `Receipt` shape returned to clients, decoding the logs of
+/// contracts with registered event ABIs
+fn format_receipt(receipt: &TxReceipt, tx_hash: &str, chain_id: u64, cfg: &Config) -> Receipt {
+    Receipt {
+        logs: format_logs(&receipt.logs, chain_id, cfg),
+        status: if receipt.success { "0x1" } else { "0x0" }.to_string(),
+        block_hash: receipt
+            .block_hash
+            .map(|hash| hash.to_string())
+            .unwrap_or_default(),
+        block_number: receipt
+            .block_number
+            .map(|number| format!("0x{:x}", number))
+            .unwrap_or_default(),
+        gas_used: format!("0x{:x}", receipt.gas_used),
+        effective_gas_price: Some(format!("0x{:x}", receipt.effective_gas_price)),
+        transaction_hash: tx_hash.to_string(),
+        chain_id: chain_id.to_string(),
+        blob_gas_used: receipt.blob_gas_used.map(|used| format!("0x{:x}", used)),
+        blob_gas_price: receipt.blob_gas_price.map(|price| format!("0x{:x}", price)),
+    }
+}
+
+async fn process_get_receipt(
+    storage: Storage,
+    request: &GetReceiptRequest,
+    cfg: &Config,
+) -> Result<GetReceiptResponse, jsonrpc_core::Error> {
+    tracing::info!(id = %request.id, "=== relayer_getReceipt request received ===");
+
+    let tenant = resolve_tenant(cfg, request.api_key.as_deref())?;
+    build_receipt(
+        storage,
+        request,
+        cfg,
+        TenantScope::Tenant(tenant.as_deref()),
+    )
+    .await
+}
+
+/// Gather the receipts of a request, treating requests outside `scope` as unknown
+async fn build_receipt(
+    storage: Storage,
+    request: &GetReceiptRequest,
+    cfg: &Config,
+    scope: TenantScope<'_>,
+) -> Result<GetReceiptResponse, jsonrpc_core::Error> {
+    let storage_error = |e: anyhow::Error| {
+        tracing::error!("Failed to load receipts for {}: {}", request.id, e);
+        jsonrpc_core::Error::from(RelayError::Internal)
+    };
+    let request_id = match Uuid::parse_str(&request.id) {
+        Ok(uuid) => uuid,
+        Err(_) if is_tx_hash(&request.id) => storage
+            .get_request_id_by_tx_hash(&request.id)
+            .await
+            .map_err(storage_error)?
+            .ok_or_else(|| RelayError::UnknownRequest(request.id.clone()))?,
+        Err(_) => return Err(RelayError::invalid_params("Invalid request id").into()),
+    };
+    let req = storage
+        .get_request(scope, request_id)
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| RelayError::UnknownRequest(request.id.clone()))?;
+
+    // Requests recorded before hashes were listed per request only know their
+    // resubmissions and current hash
+    let mut hashes = storage
+        .get_transaction_hashes(request_id)
+        .await
+        .map_err(storage_error)?;
+    let resubmissions = storage
+        .get_resubmissions(request_id)
+        .await
+        .map_err(storage_error)?;
+    let known = resubmissions
+        .into_iter()
+        .map(|resub| resub.transaction_hash)
+        .chain(req.transaction_hash.clone());
+    for tx_hash in known {
+        let tx_hash = tx_hash.to_lowercase();
+        if !hashes.contains(&tx_hash) {
+            hashes.push(tx_hash);
+        }
+    }
+
+    let receipts = if hashes.is_empty() {
+        Vec::new()
+    } else {
+        if cfg.rpc_url_for_chain(&req.chain_id.to_string()).is_none() {
+            return Err(RelayError::UnsupportedChain(req.chain_id.to_string()).into());
+        }
+        let lookups: Vec<(RelayerRequest, String)> = hashes
+            .iter()
+            .map(|tx_hash| (req.clone(), tx_hash.clone()))
+            .collect();
+        lookup_receipts(cfg, req.chain_id, &lookups)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                tracing::error!("Receipt lookup for {} failed: {}", request_id, e);
+                RelayError::ChainUnavailable(req.chain_id.to_string())
+            })?
+    };
+
+    let last = hashes.len().saturating_sub(1);
+    let transactions = hashes
+        .iter()
+        .zip(&receipts)
+        .enumerate()
+        .map(|(i, (tx_hash, receipt))| BroadcastTransaction {
+            transaction_hash: tx_hash.clone(),
+            mined: receipt.is_some(),
+            replaced: i < last,
+        })
+        .collect();
+    let mined: Vec<Receipt> = hashes
+        .iter()
+        .zip(&receipts)
+        .filter_map(|(tx_hash, receipt)| {
+            receipt
+                .as_ref()
+                .map(|receipt| format_receipt(receipt, tx_hash, req.chain_id, cfg))
+        })
+        .collect();
+
+    Ok(GetReceiptResponse {
+        id: request_id.to_string(),
+        chain_id: req.chain_id.to_string(),
+        status: cfg.status_codes().code(status_kind(&req)),
+        transactions,
+        mined_transaction_hash: mined
+            .first()
+            .map(|receipt| receipt.transaction_hash.clone()),
+        receipts: mined,
+    })
+}
+
+/// Delivery record for a callback that has not been attempted yet
+fn pending_callback(url: &str) -> CallbackDelivery {
+    CallbackDelivery {
+        url: url.to_string(),
+        status: CallbackStatus::Pending,
+        attempts: 0,
+        last_attempt_at: None,
+        last_error: None,
+        delivered_at: None,
+    }
+}
+
+/// Post a finished request's final status and receipts (the `relayer_getReceipt` result)
+/// to its callback URL in the background. Requests without a pending callback are skipped.
+fn spawn_callback(storage: &Storage, cfg: &Config, request_id: Uuid) {
+    let Some(secret) = cfg.callback_secret.clone() else {
+        return;
+    };
+    let storage = storage.clone();
+    let cfg = cfg.clone();
+    tokio::spawn(async move {
+        match storage.get_callback(request_id).await {
+            Ok(Some(pending)) if pending.status == CallbackStatus::Pending => {}
+            _ => return,
+        }
+        let request = GetReceiptRequest {
+            id: request_id.to_string(),
+            api_key: None,
+        };
+        let payload = match build_receipt(storage.clone(), &request, &cfg, TenantScope::All).await {
+            Ok(receipt) => serde_json::to_value(receipt),
+            Err(e) => {
+                // Still report the final status when receipts cannot be fetched
+                tracing::warn!(
+                    "Callback for {} sent without receipts: {}",
+                    request_id,
+                    e.message
+                );
+                let codes = cfg.status_codes();
+                serde_json::to_value(
+                    build_status_result(&storage, &request.id, &codes, TenantScope::All).await,
+                )
+            }
+        };
+        match payload {
+            Ok(payload) => {
+                let policy = cfg.callback_host_policy();
+                callback::deliver(&storage, request_id, &secret, policy, &payload).await
+            }
+            Err(e) => tracing::error!("Failed to build callback for {}: {}", request_id, e),
+        }
+    });
+}
+
+/// Resume the deliveries a restart cut short: pending callbacks of requests that already
+/// completed or failed. Returns how many were resumed.
+async fn resume_callbacks(storage: &Storage, cfg: &Config) -> usize {
+    let pending = match storage.get_pending_callbacks().await {
+        Ok(pending) => pending,
+        Err(e) => {
+            tracing::error!("Failed to scan pending callbacks: {}", e);
+            return 0;
+        }
+    };
+    let mut resumed = 0;
+    for request_id in pending {
+        match storage.get_request(TenantScope::All, request_id).await {
+            Ok(Some(req))
+                if matches!(req.status, RequestStatus::Completed | RequestStatus::Failed) =>
+            {
+                spawn_callback(storage, cfg, request_id);
+                resumed += 1;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to load request {}: {}", request_id, e),
+        }
+    }
+    resumed
+}
+
+/// Fee collector used when none is configured
+const DEFAULT_FEE_COLLECTOR: &str = "0x55f3a93f544e01ce4378d25e927d7c493b863bd6";
+
+/// Address fees on a chain are paid to: the chain's `feeCollectors` entry, else
+/// `RELAYX_FEE_COLLECTOR`, else the global `feeCollector`
+fn fee_collector_for_chain(cfg: &Config, chain_id: &str) -> String {
+    cfg.chain_fee_collector(chain_id)
+        .or_else(|| std::env::var("RELAYX_FEE_COLLECTOR").ok())
+        .or_else(|| cfg.fee_collector())
+        .unwrap_or_else(|| DEFAULT_FEE_COLLECTOR.to_string())
+}
+
+/// Address gas tank deposits on a chain must be sent to: the configured deposit address,
+/// else the chain's fee collector
+fn gas_tank_deposit_address(cfg: &Config, chain_id: &str) -> String {
+    cfg.gas_tank_deposit_address()
+        .unwrap_or_else(|| fee_collector_for_chain(cfg, chain_id))
+}
+
+/// Verify a mined native transfer to the deposit address and credit it to the sender's gas
+/// tank on that chain
+async fn process_submit_gas_tank_deposit(
+    storage: Storage,
+    request: &SubmitGasTankDepositRequest,
+    cfg: &Config,
+) -> Result<SubmitGasTankDepositResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        chain_id = %request.chain_id,
+        tx_hash = %request.transaction_hash,
+        "=== relayer_submitGasTankDeposit request received ==="
+    );
+
+    let chain_id: u64 = params::chain_id(&request.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", request.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+    })?;
+    let rpc_url = cfg
+        .rpc_url_for_chain(&chain_id.to_string())
+        .filter(|_| cfg.is_chain_supported(chain_id))
+        .ok_or_else(|| RelayError::UnsupportedChain(chain_id.to_string()))?;
+    let hash: B256 = request.transaction_hash.parse().map_err(|e| {
+        tracing::warn!("Invalid deposit hash {}: {}", request.transaction_hash, e);
+        RelayError::invalid_field("transactionHash", "Invalid transaction hash")
+    })?;
+    let deposit_address: Address = gas_tank_deposit_address(cfg, &chain_id.to_string())
+        .parse()
+        .map_err(|e| {
+            tracing::error!("Configured gas tank deposit address is invalid: {}", e);
+            RelayError::Internal
+        })?;
+
+    let chain_error = |e: String| {
+        tracing::error!(
+            "Deposit lookup for {} on chain {} failed: {}",
+            hash,
+            chain_id,
+            e
+        );
+        RelayError::ChainUnavailable(chain_id.to_string())
+    };
+    let transfer = cfg
+        .chain
+        .transfer(&rpc_url, hash)
+        .await
+        .map_err(chain_error)?
+        .ok_or_else(|| RelayError::UnknownRequest(request.transaction_hash.clone()))?;
+    if transfer.to != Some(deposit_address) {
+        return Err(RelayError::invalid_params(format!(
+            "Deposits must be sent to {}",
+            deposit_address
+        ))
+        .into());
+    }
+    let amount = u128::try_from(transfer.value)
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(|| RelayError::invalid_params("Deposit carries no usable value"))?;
+
+    let receipt = cfg
+        .chain
+        .receipt(&rpc_url, hash)
+        .await
+        .map_err(chain_error)?
+        .ok_or_else(|| RelayError::invalid_params("Deposit is not mined yet"))?;
+    if !receipt.success {
+        return Err(RelayError::invalid_params("Deposit transaction reverted").into());
+    }
+    // Credit only once the deposit is as final as the chain's confirmation depth requires
+    let head = fetch_block_number(chain_id, cfg)
+        .await
+        .map_err(chain_error)?;
+    let confirmations = receipt
+        .block_number
+        .map(|mined_at| head.saturating_sub(mined_at) + 1)
+        .unwrap_or(0);
+    if confirmations < cfg.confirmation_depth(&chain_id.to_string()) {
+        return Err(RelayError::invalid_params(format!(
+            "Deposit has {} of {} required confirmations",
+            confirmations,
+            cfg.confirmation_depth(&chain_id.to_string())
+        ))
+        .into());
+    }
+
+    let deposit = GasTankDeposit {
+        chain_id: chain_id.to_string(),
+        wallet: transfer.from.to_string(),
+        transaction_hash: hash.to_string(),
+        amount: amount.to_string(),
+        credited_at: Utc::now(),
+    };
+    let balance = storage
+        .credit_gas_tank_deposit(&deposit)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to credit deposit {}: {}", hash, e);
+            RelayError::Internal
+        })?
+        .ok_or_else(|| RelayError::invalid_params("Deposit already credited"))?;
+
+    tracing::info!(
+        "✓ Credited {} wei to the gas tank of {} on chain {} (balance: {} wei)",
+        amount,
+        deposit.wallet,
+        chain_id,
+        balance
+    );
+    Ok(SubmitGasTankDepositResponse {
+        deposit,
+        balance: balance.to_string(),
+    })
+}
+
+async fn process_get_gas_tank_balance(
+    storage: Storage,
+    request: &GetGasTankBalanceRequest,
+    cfg: &Config,
+) -> Result<GetGasTankBalanceResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        wallet = %request.wallet,
+        chain_id = %request.chain_id,
+        "=== relayer_getGasTankBalance request received ==="
+    );
+
+    params::address(&request.wallet).map_err(|e| {
+        tracing::warn!("Invalid wallet address {}: {}", request.wallet, e);
+        RelayError::invalid_field("wallet", "Invalid wallet address")
+    })?;
+    let chain_id: u64 = params::chain_id(&request.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", request.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+    })?;
+    if !cfg.is_chain_supported(chain_id) {
+        return Err(RelayError::UnsupportedChain(chain_id.to_string()).into());
+    }
+
+    let balance = storage
+        .get_gas_tank_balance(&chain_id.to_string(), &request.wallet)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read gas tank of {}: {}", request.wallet, e);
+            RelayError::Internal
+        })?;
+    Ok(GetGasTankBalanceResponse {
+        wallet: request.wallet.clone(),
+        chain_id: chain_id.to_string(),
+        balance: balance.to_string(),
+    })
+}
+
+async fn process_get_gas_tank_deposits(
+    storage: Storage,
+    request: &GetGasTankDepositsRequest,
+) -> Result<GetGasTankDepositsResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        wallet = %request.wallet,
+        "=== relayer_getGasTankDeposits request received ==="
+    );
+
+    params::address(&request.wallet).map_err(|e| {
+        tracing::warn!("Invalid wallet address {}: {}", request.wallet, e);
+        RelayError::invalid_field("wallet", "Invalid wallet address")
+    })?;
+    let limit = match request.limit {
+        Some(0) => return Err(RelayError::invalid_params("limit must be at least 1").into()),
+        Some(limit) => limit.min(WALLET_HISTORY_MAX_LIMIT),
+        None => WALLET_HISTORY_DEFAULT_LIMIT,
+    };
+
+    let deposits = storage
+        .get_gas_tank_deposits(&request.wallet, limit, |deposit| {
+            request
+                .chain_id
+                .as_ref()
+                .is_none_or(|chain_id| *chain_id == deposit.chain_id)
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read deposits of {}: {}", request.wallet, e);
+            RelayError::Internal
+        })?;
+    Ok(GetGasTankDepositsResponse { deposits })
+}
+
+async fn process_get_sponsorship_allowance(
+    storage: Storage,
+    request: &GetSponsorshipAllowanceRequest,
+    cfg: &Config,
+) -> Result<GetSponsorshipAllowanceResponse, jsonrpc_core::Error> {
+    tracing::info!("=== relayer_getSponsorshipAllowance request received ===");
+
+    let project = find_sponsorship_project(&request.api_key, cfg).ok_or_else(|| {
+        tracing::warn!("Sponsorship allowance rejected: unknown API key");
+        RelayError::Unauthorized
+    })?;
+    let (period, resets_at) = billing_period(Utc::now());
+    let used = storage
+        .get_sponsored_gas_used(&project.id, &period)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to read sponsored gas of project {}: {}",
+                project.id,
+                e
+            );
+            RelayError::Internal
+        })?;
+    Ok(GetSponsorshipAllowanceResponse {
+        remaining: project
+            .monthly_gas
+            .map(|allowance| allowance.saturating_sub(used)),
+        allowance: project.monthly_gas,
+        project: project.id,
+        tier: project.tier,
+        period,
+        used,
+        resets_at,
+    })
+}
+
+async fn process_get_bundle_status(
+    storage: Storage,
+    request: &GetBundleStatusRequest,
+    cfg: &Config,
+) -> Result<GetBundleStatusResponse, jsonrpc_core::Error> {
+    tracing::info!("=== relayer_getBundleStatus request received ===");
+
+    let tenant = resolve_tenant(cfg, request.api_key.as_deref())?;
+    let codes = cfg.status_codes();
+    let mut result = BundleStatusResult {
+        version: RELAY_SPEC_VERSION.to_string(),
+        bundle_id: request.bundle_id.clone(),
+        status: codes.code(StatusKind::NotFound),
+        status_name: Some(StatusKind::NotFound),
+        all_mined: false,
+        any_failed: false,
+        legs: Vec::new(),
+    };
+
+    let bundle_id = match Uuid::parse_str(&request.bundle_id) {
+        Ok(id) => id,
+        Err(_) => {
+            tracing::warn!("Invalid bundleId format: {}", request.bundle_id);
+            result.status = codes.code(StatusKind::InvalidId);
+            result.status_name = Some(StatusKind::InvalidId);
+            return Ok(GetBundleStatusResponse { result });
+        }
+    };
+
+    let bundle = match storage.get_bundle(bundle_id).await {
+        Ok(Some(bundle)) => bundle,
+        Ok(None) => {
+            tracing::debug!("Bundle {} not found", bundle_id);
+            return Ok(GetBundleStatusResponse { result });
+        }
+        Err(e) => {
+            tracing::error!("Failed to read bundle {}: {}", bundle_id, e);
+            return Err(RelayError::Internal.into());
+        }
+    };
+
+    for request_id in &bundle.request_ids {
+        result.legs.push(
+            build_status_result(
+                &storage,
+                &request_id.to_string(),
+                &codes,
+                TenantScope::Tenant(tenant.as_deref()),
+            )
+            .await,
+        );
+    }
+    // Every leg is relayed for the same tenant, so a bundle showing none of them is
+    // someone else's
+    let leg_is = |leg: &StatusResult, kind| leg.status_name == Some(kind);
+    if result
+        .legs
+        .iter()
+        .all(|leg| leg_is(leg, StatusKind::NotFound))
+    {
+        result.legs.clear();
+        return Ok(GetBundleStatusResponse { result });
+    }
+
+    result.all_mined = !result.legs.is_empty()
+        && result
+            .legs
+            .iter()
+            .all(|leg| leg_is(leg, StatusKind::Confirmed));
+    result.any_failed = result
+        .legs
+        .iter()
+        .any(|leg| !leg_is(leg, StatusKind::Confirmed) && !leg_is(leg, StatusKind::Pending));
+    let kind = if result.any_failed {
+        StatusKind::Failed
+    } else if result.all_mined {
+        StatusKind::Confirmed
+    } else {
+        StatusKind::Pending
+    };
+    result.status = codes.code(kind);
+    result.status_name = Some(kind);
+
+    tracing::info!(
+        "✓ Bundle status query completed for {} ({} leg(s), status {})",
+        bundle_id,
+        result.legs.len(),
+        result.status
+    );
+    Ok(GetBundleStatusResponse { result })
+}
+
+async fn process_health_check(
+    storage: Storage,
+    _cfg: &Config,
+) -> Result<HealthResponse, jsonrpc_core::Error> {
+    tracing::debug!("=== health_check request received ===");
+
+    let total_requests = storage.get_total_request_count().await.map_err(|e| {
+        tracing::error!("Failed to get total request count: {}", e);
+        RelayError::Internal
+    })?;
+
+    let pending_requests = storage
+        .get_request_count_by_status(RequestStatus::Pending)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get pending request count: {}", e);
+            RelayError::Internal
+        })?;
+
+    let completed_requests = storage
+        .get_request_count_by_status(RequestStatus::Completed)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get completed request count: {}", e);
+            RelayError::Internal
+        })?;
+
+    let failed_requests = storage
+        .get_request_count_by_status(RequestStatus::Failed)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get failed request count: {}", e);
+            RelayError::Internal
+        })?;
+
+    let lifetime = storage.get_lifetime_stats().await.map_err(|e| {
+        tracing::error!("Failed to read lifetime stats: {}", e);
+        RelayError::Internal
+    })?;
+
+    tracing::debug!(
+        "Health metrics - Total: {}, Pending: {}, Completed: {}, Failed: {}, Uptime: {}s",
+        total_requests,
+        pending_requests,
+        completed_requests,
+        failed_requests,
+        storage.get_uptime_seconds()
+    );
+
+    Ok(build_health_response(
+        storage.get_uptime_seconds(),
+        total_requests,
+        pending_requests,
+        completed_requests,
+        failed_requests,
+        storage.stats(),
+        lifetime,
+    ))
+}
+
+/// Relay API spec version reported in status results
+const RELAY_SPEC_VERSION: &str = "2.0.0";
+
+/// Build information embedded by `build.rs` plus the protocol features this relayer serves
+/// The OpenRPC document without the methods listed in `disabledMethods`
+fn discover_document(cfg: &Config) -> serde_json::Value {
+    let mut doc = crate::openrpc::discover_document();
+    let disabled = cfg.disabled_methods();
+    if let Some(methods) = doc["methods"].as_array_mut() {
+        methods.retain(|method| {
+            method["name"]
+                .as_str()
+                .is_none_or(|name| !disabled.contains(name))
+        });
+    }
+    doc
+}
+
+/// Names of the documented methods this relayer serves
+fn enabled_methods(cfg: &Config) -> Vec<String> {
+    discover_document(cfg)["methods"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|method| method["name"].as_str().map(str::to_string))
+        .collect()
+}
+
+fn build_version_response(cfg: &Config) -> GetVersionResponse {
+    let built_at = env!("RELAYX_BUILD_TIMESTAMP").parse().unwrap_or(0);
+    GetVersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: env!("RELAYX_GIT_COMMIT").to_string(),
+        build_timestamp: Utc.timestamp_opt(built_at, 0).single().unwrap_or_default(),
+        spec_versions: vec![RELAY_SPEC_VERSION.to_string()],
+        features: VersionFeatures {
+            eip7702: true,
+            erc4337: false,
+            multichain: cfg.is_method_enabled("relayer_sendTransactionMultichain"),
+        },
+        methods: enabled_methods(cfg),
+    }
+}
+
+fn build_liveness_response(storage: &Storage) -> LivenessResponse {
+    LivenessResponse {
+        status: "alive".to_string(),
+        uptime_seconds: storage.get_uptime_seconds(),
+    }
+}
+
+/// Readiness: storage accepts writes, at least one configured chain answers and a relayer
+/// key is loaded. Fails with [`RelayError::NotReady`] naming the failing checks.
+async fn process_health_ready(
+    storage: Storage,
+    cfg: &Config,
+) -> Result<ReadinessResponse, jsonrpc_core::Error> {
+    tracing::debug!("=== health_ready request received ===");
+
+    let storage_writable = match storage.check_writable().await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::error!("Readiness: storage is not writable: {}", e);
+            false
+        }
+    };
+
+    let chain_ids = cfg.configured_chain_ids();
+    let probes = chain_ids.iter().map(|&chain_id| async move {
+        fetch_block_number(chain_id, cfg)
+            .await
+            .map_err(|e| tracing::warn!("Readiness: chain {} unreachable: {}", chain_id, e))
+            .is_ok()
+    });
+    let chains_reachable = join_all(probes)
+        .await
+        .into_iter()
+        .filter(|reachable| *reachable)
+        .count();
+
+    let signer_loaded = relayer_address(cfg).is_some()
+        || chain_ids.iter().any(|chain_id| {
+            cfg.relayer_signer_for_chain(&chain_id.to_string())
+                .is_some_and(|signer| signer.is_ok())
+        });
+
+    let failing: Vec<String> = [
+        ("storage", storage_writable),
+        ("chains", chains_reachable > 0),
+        ("signer", signer_loaded),
+    ]
+    .into_iter()
+    .filter(|(_, ok)| !ok)
+    .map(|(check, _)| check.to_string())
+    .collect();
+    if !failing.is_empty() {
+        tracing::warn!("Readiness check failed: {}", failing.join(", "));
+        return Err(RelayError::NotReady(failing).into());
+    }
+
+    Ok(ReadinessResponse {
+        status: "ready".to_string(),
+        storage_writable,
+        chains_configured: chain_ids.len(),
+        chains_reachable,
+        signer_loaded,
+    })
+}
+
+/// Payment options offered on a single chain: configured ERC20 tokens, native, and sponsored
+fn chain_payment_options(cfg: &Config, chain_id: &str) -> Vec<Payment> {
+    let mut payments: Vec<Payment> = cfg
+        .get_supported_tokens_for_chain(chain_id)
+        .into_iter()
+        .map(|token| {
+            Payment::Erc20(Erc20Payment {
+                payment_type: PaymentType::Erc20,
+                token,
+            })
+        })
+        .collect();
+    payments.push(Payment::Native(NativePayment {
+        payment_type: PaymentType::Native,
+        token: "0x0000000000000000000000000000000000000000".to_string(),
+    }));
+    payments.push(Payment::Sponsored(SponsoredPayment {
+        payment_type: PaymentType::Sponsored,
+    }));
+    payments
+}
+
+async fn process_get_capabilities(
+    _storage: Storage,
+    request: &GetCapabilitiesRequest,
+    cfg: &Config,
+) -> Result<GetCapabilitiesResponse, jsonrpc_core::Error> {
+    tracing::info!("=== relayer_getCapabilities request received ===");
+
+    // Resolve the chains to describe, honouring the optional chainId filter
+    let chain_ids: Vec<String> = match &request.chain_id {
+        Some(chain_id) => {
+            let parsed: u64 = params::chain_id(chain_id).map_err(|e| {
+                tracing::warn!("Invalid chainId format: {} ({})", chain_id, e);
+                RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+            })?;
+            if !cfg.is_chain_supported(parsed) {
+                tracing::warn!("Unsupported chain ID requested: {}", parsed);
+                return Err(RelayError::UnsupportedChain(parsed.to_string()).into());
+            }
+            vec![parsed.to_string()]
+        }
+        None => cfg
+            .configured_chain_ids()
+            .into_iter()
+            .map(|id| id.to_string())
+            .collect(),
+    };
+
+    let mut chains = std::collections::BTreeMap::new();
+    let mut payment_chains: std::collections::BTreeMap<String, Vec<String>> =
+        std::collections::BTreeMap::new();
+    for chain_id in &chain_ids {
+        let payment = chain_payment_options(cfg, chain_id);
+        for option in &payment {
+            let payment_type = match option {
+                Payment::Erc20(_) => "erc20",
+                Payment::Native(_) => "native",
+                Payment::Sponsored(_) => "sponsored",
+            };
+            let listed = payment_chains.entry(payment_type.to_string()).or_default();
+            if !listed.contains(chain_id) {
+                listed.push(chain_id.clone());
+            }
+        }
+        // A DEX quoter accepts any ERC-20 fee token, and any chain with ERC-20 fee tokens
+        // also accepts ERC-2612 permit payment
+        if payment
+            .iter()
+            .any(|option| matches!(option, Payment::Erc20(_)))
+            || cfg.dex_quoter(chain_id).is_some()
+        {
+            for payment_type in ["erc20", "permit"] {
+                let listed = payment_chains.entry(payment_type.to_string()).or_default();
+                if !listed.contains(chain_id) {
+                    listed.push(chain_id.clone());
+                }
+            }
+        }
+        payment_chains
+            .entry("gasTank".to_string())
+            .or_default()
+            .push(chain_id.clone());
+        tracing::debug!(
+            "Chain {}: {} payment capability option(s)",
+            chain_id,
+            payment.len()
+        );
+        chains.insert(
+            chain_id.clone(),
+            ChainCapabilities {
+                payment,
+                wallet_implementations: cfg.wallet_implementations(chain_id),
+                atomic: true,
+            },
+        );
+    }
+
+    // Build the flat payment list: tokens for the selected chain, or every configured token
+    let supported_tokens = match &request.chain_id {
+        Some(_) => chain_ids
+            .first()
+            .map(|id| cfg.get_supported_tokens_for_chain(id))
+            .unwrap_or_default(),
+        None => cfg.get_supported_tokens(),
+    };
+
+    tracing::debug!(
+        "Found {} supported token(s) from configuration",
+        supported_tokens.len()
+    );
+
+    let mut payments = Vec::new();
+
+    // Add ERC20 payment options for each supported token
+    for token in &supported_tokens {
+        tracing::debug!("Adding ERC20 payment capability for token: {}", token);
+        payments.push(Payment::Erc20(Erc20Payment {
+            payment_type: PaymentType::Erc20,
+            token: token.clone(),
+        }));
+    }
+
+    // If no tokens found in config, fall back to default token
+    if payments.is_empty() && request.chain_id.is_none() {
+        let default_token = cfg
+            .default_token()
+            .unwrap_or_else(|| "0x036CbD53842c5426634e7929541eC2318f3dCF7e".to_string()); // USDC on Ethereum
+
+        tracing::debug!(
+            "No tokens configured, using default token: {}",
+            default_token
+        );
+        payments.push(Payment::Erc20(Erc20Payment {
+            payment_type: PaymentType::Erc20,
+            token: default_token,
+        }));
+    }
+
+    // Always include native payment option
+    tracing::debug!("Adding native payment capability");
+    payments.push(Payment::Native(NativePayment {
+        payment_type: PaymentType::Native,
+        token: "0x0000000000000000000000000000000000000000".to_string(),
+    }));
+
+    // Always include sponsored payment option
+    tracing::debug!("Adding sponsored payment capability");
+    payments.push(Payment::Sponsored(SponsoredPayment {
+        payment_type: PaymentType::Sponsored,
+    }));
+
+    let capabilities = Capabilities { payment: payments };
+
+    tracing::info!(
+        "✓ Returning {} payment capability option(s) across {} chain(s)",
+        capabilities.payment.len(),
+        chains.len()
+    );
+
+    Ok(GetCapabilitiesResponse {
+        capabilities,
+        chains,
+        limits: RelayLimits {
+            max_calldata_bytes: cfg.max_calldata_bytes(),
+            max_gas: cfg.max_gas(),
+            quote_expiry_seconds: QUOTE_EXPIRY_SECONDS,
+        },
+        policies: RelayPolicies {
+            sponsorship_requires_api_key: !cfg.sponsorship_api_keys().is_empty()
+                || !cfg.sponsorship_projects().is_empty(),
+            payment_chains,
+        },
+        methods: enabled_methods(cfg),
+    })
+}
+
+async fn process_get_supported_chains(
+    _storage: Storage,
+    cfg: &Config,
+) -> Result<GetSupportedChainsResponse, jsonrpc_core::Error> {
+    tracing::info!("=== relayer_getSupportedChains request received ===");
+
+    let relayer_address = relayer_address(cfg);
+    let mut chains = Vec::new();
+
+    for chain_id in cfg.configured_chain_ids() {
+        let chain_str = chain_id.to_string();
+
+        let mut payment_tokens = vec!["0x0000000000000000000000000000000000000000".to_string()];
+        payment_tokens.extend(cfg.get_supported_tokens_for_chain(&chain_str));
+
+        let (available, block_number) = match fetch_block_number(chain_id, cfg).await {
+            Ok(block_number) => (true, Some(block_number)),
+            Err(e) => {
+                tracing::warn!("Chain {} unavailable: {}", chain_id, e);
+                (false, None)
+            }
+        };
+
+        tracing::debug!(
+            "Chain {} - available: {}, tokens: {}",
+            chain_id,
+            available,
+            payment_tokens.len()
+        );
+
+        let registry = cfg.registry_chain(&chain_str);
+        chains.push(SupportedChain {
+            chain_id: chain_str.clone(),
+            relayer_address: relayer_address.clone(),
+            payment_tokens,
+            confirmations: cfg.confirmation_depth(&chain_str),
+            available,
+            block_number,
+            explorer_url: registry
+                .as_ref()
+                .and_then(|chain| chain.explorer_url().map(str::to_string)),
+            name: registry.map(|chain| chain.name),
+        });
+    }
+
+    tracing::info!("✓ Returning {} supported chain(s)", chains.len());
+
+    Ok(GetSupportedChainsResponse { chains })
+}
+
+async fn process_add_chain(
+    storage: Storage,
+    input: &AddChainRequest,
+    cfg: &Config,
+) -> Result<AdminChainResponse, jsonrpc_core::Error> {
+    tracing::info!("=== admin_addChain request received ===");
+    authorize_admin(cfg, &input.admin_token)?;
+
+    let chain = &input.chain;
+    let chain_id: u64 = params::chain_id(&chain.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", chain.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+    })?;
+
+    if cfg.is_file_configured_chain(&chain.chain_id) {
+        tracing::warn!("Chain {} is already defined in the config file", chain_id);
+        return Err(RelayError::invalid_params(format!(
+            "Chain {} is defined in the config file",
+            chain_id
+        ))
+        .into());
+    }
+
+    Url::parse(&chain.rpc_url).map_err(|e| {
+        tracing::warn!("Invalid RPC URL {}: {}", chain.rpc_url, e);
+        RelayError::invalid_params(format!("Invalid rpcUrl: {}", e))
+    })?;
+
+    let feeds = chain
+        .tokens
+        .iter()
+        .flat_map(|(token, feed)| [token, feed])
+        .chain(chain.native_usd_feed.iter());
+    for address in feeds {
+        if params::address(address).is_err() {
+            tracing::warn!("Invalid address in chain registration: {}", address);
+            return Err(RelayError::invalid_params(format!("Invalid address: {}", address)).into());
+        }
+    }
+
+    // Validate connectivity before accepting the chain
+    let remote_chain_id = match tokio::time::timeout(
+        Duration::from_secs(5),
+        cfg.chain.chain_id(&chain.rpc_url),
+    )
+    .await
+    {
+        Ok(Ok(id)) => id,
+        Ok(Err(e)) => {
+            tracing::warn!("Connectivity check failed for chain {}: {}", chain_id, e);
+            return Err(RelayError::invalid_params(format!(
+                "RPC connectivity check failed: {}",
+                e
+            ))
+            .into());
+        }
+        Err(_) => {
+            tracing::warn!("Connectivity check timed out for chain {}", chain_id);
+            return Err(RelayError::invalid_params("RPC connectivity check timed out").into());
+        }
+    };
+    if remote_chain_id != chain_id {
+        tracing::warn!(
+            "RPC chain mismatch: expected {}, endpoint reports {}",
+            chain_id,
+            remote_chain_id
+        );
+        return Err(RelayError::invalid_params(format!(
+            "RPC endpoint reports chain {} (expected {})",
+            remote_chain_id, chain_id
+        ))
+        .into());
+    }
+
+    if let Err(e) = storage.store_chain_registration(chain).await {
+        tracing::error!("Failed to persist chain {}: {}", chain_id, e);
+        return Err(RelayError::Internal.into());
+    }
+    cfg.register_runtime_chain(chain.clone());
+    if chain.tx_type.is_none() {
+        // The chain is new, or now served by an endpoint other than the one probed before
+        if let Err(e) = probe_tx_type(chain_id, &chain.rpc_url, cfg).await {
+            tracing::warn!(
+                "Transaction type probe failed for chain {}: {}",
+                chain_id,
+                e
+            );
+        }
+    }
+
+    tracing::info!(
+        "✓ Chain {} registered at runtime ({} payment token(s))",
+        chain_id,
+        chain.tokens.len()
+    );
+
+    Ok(AdminChainResponse {
+        chain_id: chain.chain_id.clone(),
+        status: "added".to_string(),
+    })
+}
+
+async fn process_remove_chain(
+    storage: Storage,
+    input: &RemoveChainRequest,
+    cfg: &Config,
+) -> Result<AdminChainResponse, jsonrpc_core::Error> {
+    tracing::info!("=== admin_removeChain request received ===");
+    authorize_admin(cfg, &input.admin_token)?;
+
+    if cfg.runtime_chain(&input.chain_id).is_none() {
+        tracing::warn!("Chain {} is not registered at runtime", input.chain_id);
+        return Err(RelayError::invalid_params(format!(
+            "Chain {} is not registered at runtime",
+            input.chain_id
+        ))
+        .into());
+    }
+
+    if let Err(e) = storage.delete_chain_registration(&input.chain_id).await {
+        tracing::error!("Failed to delete chain {}: {}", input.chain_id, e);
+        return Err(RelayError::Internal.into());
+    }
+    cfg.unregister_runtime_chain(&input.chain_id);
+
+    tracing::info!("✓ Chain {} removed", input.chain_id);
+
+    Ok(AdminChainResponse {
+        chain_id: input.chain_id.clone(),
+        status: "removed".to_string(),
+    })
+}
+
+async fn process_export_accounting(
+    storage: Storage,
+    input: &ExportAccountingRequest,
+    cfg: &Config,
+) -> Result<ExportAccountingResponse, jsonrpc_core::Error> {
+    tracing::info!("=== admin_exportAccounting request received ===");
+    authorize_admin(cfg, &input.admin_token)?;
+
+    let format = match &input.format {
+        Some(format) => format
+            .parse::<ExportFormat>()
+            .map_err(RelayError::invalid_params)?,
+        None => ExportFormat::default(),
+    };
+    let parse_time = |name: &str, value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|value| {
+                parse_bound(value)
+                    .map_err(|e| RelayError::invalid_params(format!("Invalid {}: {}", name, e)))
+            })
+            .transpose()
+    };
+    let from = parse_time("from", &input.from)?;
+    let to = parse_time("to", &input.to)?;
+
+    let mut data = Vec::new();
+    let rows = export_accounting(&storage, from, to, format, &mut data)
+        .await
+        .map_err(|e| {
+            tracing::error!("Accounting export failed: {}", e);
+            RelayError::Internal
+        })?;
+    tracing::info!("✓ Exported {} request(s) as {}", rows, format);
+
+    Ok(ExportAccountingResponse {
+        format: format.to_string(),
+        rows,
+        data: String::from_utf8_lossy(&data).into_owned(),
+    })
+}
+
+async fn process_get_refund_report(
+    storage: Storage,
+    input: &GetRefundReportRequest,
+    cfg: &Config,
+) -> Result<GetRefundReportResponse, jsonrpc_core::Error> {
+    tracing::info!("=== admin_getRefundReport request received ===");
+    authorize_admin(cfg, &input.admin_token)?;
+
+    let parse_time = |name: &str, value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|value| {
+                parse_bound(value)
+                    .map_err(|e| RelayError::invalid_params(format!("Invalid {}: {}", name, e)))
+            })
+            .transpose()
+    };
+    let from = parse_time("from", &input.from)?;
+    let to = parse_time("to", &input.to)?;
+    let chain_id = input
+        .chain_id
+        .as_deref()
+        .map(|chain_id| {
+            params::chain_id(chain_id).map_err(|_| {
+                RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+            })
+        })
+        .transpose()?;
+
+    let refunds: Vec<Overpayment> = storage
+        .get_overpayments(from, to)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read overpayments: {}", e);
+            RelayError::Internal
+        })?
+        .into_iter()
+        .filter(|refund| chain_id.is_none_or(|chain_id| refund.chain_id == chain_id))
+        .collect();
+    let calls = if input.include_calls {
+        refunds.iter().filter_map(refund_call).collect()
+    } else {
+        Vec::new()
+    };
+    tracing::info!("✓ Reported {} overpaid relay(s)", refunds.len());
+
+    Ok(GetRefundReportResponse { refunds, calls })
+}
+
+/// Requests `admin_listRequests` returns when the caller does not set a limit
+const LIST_REQUESTS_DEFAULT_LIMIT: usize = 50;
+
+async fn process_list_requests(
+    storage: Storage,
+    input: &ListRequestsRequest,
+    cfg: &Config,
+) -> Result<ListRequestsResponse, jsonrpc_core::Error> {
+    tracing::info!("=== admin_listRequests request received ===");
+    authorize_admin(cfg, &input.admin_token)?;
+
+    let status = input
+        .status
+        .as_deref()
+        .map(|status| parse_status(status).map_err(|e| RelayError::invalid_field("status", e)))
+        .transpose()?;
+    let requests = list_requests(
+        &storage,
+        status,
+        input.to.as_deref(),
+        input.tenant.as_deref(),
+        input.limit.unwrap_or(LIST_REQUESTS_DEFAULT_LIMIT),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to list requests: {}", e);
+        RelayError::Internal
+    })?;
+    tracing::info!("✓ Listed {} request(s)", requests.len());
+
+    Ok(ListRequestsResponse { requests })
+}
+
+/// Cancel a request the scheduler or monitor still tracks. A transaction the relayer signed
+/// and broadcast for it is first withdrawn by taking its nonce with a self-transfer; raw and
+/// blob transactions cannot be and may still be mined. The request is then marked failed,
+/// its gas tank debit returned when its call can no longer run, and its callback delivered.
+async fn process_cancel_request(
+    storage: Storage,
+    input: &CancelRequestRequest,
+    cfg: &Config,
+) -> Result<CancelRequestResponse, jsonrpc_core::Error> {
+    tracing::info!(id = %input.id, "=== admin_cancelRequest request received ===");
+    authorize_admin(cfg, &input.admin_token)?;
+
+    let request_id = Uuid::parse_str(&input.id).map_err(|e| {
+        tracing::warn!("Invalid request id {}: {}", input.id, e);
+        RelayError::invalid_field("id", "Invalid request id")
+    })?;
+    let req = storage
+        .get_request(TenantScope::All, request_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load request {}: {}", request_id, e);
+            RelayError::Internal
+        })?
+        .ok_or_else(|| RelayError::UnknownRequest(input.id.clone()))?;
+    if !is_cancellable(&req.status) {
+        return Err(RelayError::invalid_params(format!(
+            "Request is {:?} and cannot be cancelled",
+            req.status
+        ))
+        .into());
+    }
+
+    let replacement_hash = match &req.transaction_hash {
+        Some(tx_hash) if req.raw_transaction.is_none() && req.blob_sidecar.is_none() => {
+            // A transaction mined since the last monitor pass is finalized instead
+            let pending = [(req.clone(), tx_hash.clone())];
+            let receipt = lookup_receipts(cfg, req.chain_id, &pending)
+                .await
+                .pop()
+                .unwrap_or(Ok(None));
+            if apply_receipt(&storage, cfg, &req, tx_hash, receipt)
+                .await
+                .is_some()
+            {
+                return Err(RelayError::invalid_params("Transaction is already mined").into());
+            }
+            let replacement = withdraw_transaction(cfg, &req).await.map_err(|e| {
+                tracing::error!("Failed to withdraw transaction of {}: {}", request_id, e);
+                RelayError::BroadcastFailed(req.chain_id.to_string())
+            })?;
+            lifecycle_breadcrumb(
+                request_id,
+                "cancelled",
+                format!("replaced {} with self-transfer {}", tx_hash, replacement),
+            );
+            Some(replacement)
+        }
+        _ => None,
+    };
+
+    let (_, refunded) = cancel_request(&storage, request_id, replacement_hash.is_some())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to cancel request {}: {}", request_id, e);
+            RelayError::Internal
+        })?;
+    spawn_callback(&storage, cfg, request_id);
+    end_lifecycle(request_id);
+    tracing::info!(
+        "✓ Request {} cancelled{}",
+        request_id,
+        refunded
+            .map(|wei| format!(", {} wei refunded to its gas tank", wei))
+            .unwrap_or_default()
+    );
+
+    Ok(CancelRequestResponse {
+        id: request_id.to_string(),
+        replacement_hash,
+        refunded_wei: refunded.map(|wei| wei.to_string()),
+    })
+}
+
+/// Take the nonce of a request's pending transaction with a zero-value self-transfer priced
+/// above it and the network by the `fast` lane's bump, so its call can no longer be mined.
+/// Returns the self-transfer's hash.
+async fn withdraw_transaction(cfg: &Config, req: &RelayerRequest) -> Result<String, String> {
+    let chain_id = req.chain_id.to_string();
+    let signer = cfg
+        .relayer_signer_for_chain(&chain_id)
+        .ok_or_else(|| "RELAYX_PRIVATE_KEY configuration missing".to_string())??;
+    let rpc_url = cfg
+        .rpc_url_for_chain(&chain_id)
+        .ok_or_else(|| format!("No RPC URL configured for chain {}", chain_id))?;
+
+    let network_price = cfg
+        .chain
+        .gas_price(&rpc_url)
+        .await
+        .map_err(|e| format!("Gas price lookup failed: {}", e))?;
+    let pending_price =
+        u128::from_str_radix(req.gas_price.trim_start_matches("0x"), 16).unwrap_or_default();
+    // Nodes reject replacements priced less than 10% above the pending transaction
+    let bump = cfg.speed_lane(&chain_id, Speed::Fast).bump_percent.max(10);
+    let base_price = network_price.max(pending_price);
+    let gas_price = base_price + base_price * bump as u128 / 100;
+
+    let relayer = signer.address();
+    let mut tx = TransactionRequest::default()
+        .to(relayer)
+        .value(U256::ZERO)
+        .gas_limit(21_000);
+    tx.nonce = Some(req.nonce);
+    tx.chain_id = Some(req.chain_id);
+    set_gas_fees(&mut tx, req.chain_id, &rpc_url, gas_price, false, cfg).await;
+
+    let tx_hash = cfg.chain.send_transaction(&rpc_url, &signer, tx).await?;
+    Ok(format!("0x{:x}", tx_hash))
+}
+
+/// Call paying `refund` back to its wallet; gas tank fees are credited back rather than sent
+fn refund_call(refund: &Overpayment) -> Option<RefundCall> {
+    let wallet = refund.wallet.parse::<Address>().ok()?;
+    let amount = refund.refund_amount.parse::<U256>().ok()?;
+    if amount.is_zero() {
+        return None;
+    }
+    let (to, data, value) = match refund.payment_type.as_str() {
+        "erc20" | "permit" => (
+            refund.token.clone(),
+            erc20_transfer_calldata(wallet, amount),
+            U256::ZERO,
+        ),
+        "native" => (refund.wallet.clone(), "0x".to_string(), amount),
+        _ => return None,
+    };
+    Some(RefundCall {
+        request_id: refund.request_id,
+        chain_id: refund.chain_id,
+        to,
+        data,
+        value: format!("0x{:x}", value),
+    })
+}
+
+// (unused) Kept for potential reuse; prefer cached path used in start()
+// async fn process_get_exchange_rate(cfg: &Config, input: &ExchangeRateRequest) ->
+// Result<ExchangeRateResponse, jsonrpc_core::Error> { 	let now = Utc::now().timestamp() as u64;
+// 	let expiry = now + 600;
+// 	Ok(build_exchange_rate_response_with_provider(cfg, &get_or_create_provider(cfg,
+// &input.chain_id).await, input, expiry).await) }
+
+/// Build a response for the health_check endpoint
+fn build_health_response(
+    uptime_seconds: u64,
+    total_requests: u64,
+    pending_requests: u64,
+    completed_requests: u64,
+    failed_requests: u64,
+    storage: StorageStats,
+    lifetime: LifetimeStats,
+) -> HealthResponse {
+    HealthResponse {
+        status: "healthy".to_string(),
+        timestamp: Utc::now(),
+        uptime_seconds,
+        total_requests,
+        pending_requests,
+        completed_requests,
+        failed_requests,
+        storage,
+        lifetime,
+    }
+}
+
+/// Seconds an exchange rate, and so any quote priced from it, stays valid
+const QUOTE_EXPIRY_SECONDS: u64 = 600;
+
+/// Record `quote` under a new id and return the id, or `None` when it could not be stored
+/// (the quote is then handed out without one)
+async fn issue_quote(storage: &Storage, quote: IssuedQuote) -> Option<String> {
+    let quote = IssuedQuote {
+        id: Uuid::new_v4().to_string(),
+        ..quote
+    };
+    match storage.store_quote(&quote).await {
+        Ok(()) => Some(quote.id),
+        Err(e) => {
+            tracing::warn!("Failed to record quote for chain {}: {}", quote.chain_id, e);
+            None
+        }
+    }
+}
+
+/// Record the rates of a relayer_getExchangeRate/relayer_getFeeData answer for `chain_id` as
+/// quotes and set their `quoteId`s
+async fn issue_rate_quotes(storage: &Storage, chain_id: &str, response: &mut ExchangeRateResponse) {
+    for item in &mut response.result {
+        if let ExchangeRateResultItem::Success(success) = item {
+            success.quote_id = issue_quote(storage, rate_quote(chain_id, success)).await;
+        }
+    }
+}
+
+/// The quote an exchange rate on `chain_id` amounts to, yet to be issued an id
+fn rate_quote(chain_id: &str, rate: &ExchangeRateSuccess) -> IssuedQuote {
+    IssuedQuote {
+        id: String::new(),
+        chain_id: chain_id.to_string(),
+        token: rate.quote.token.address.clone(),
+        rate: rate.quote.rate,
+        gas_price: rate.gas_price.clone(),
+        fee: None,
+        expiry: rate.expiry,
+    }
+}
+
+/// The relayer's EIP-712 attestation of a `fee` quoted to `wallet` in `token`, signed with the
+/// chain's relayer key, or by the remote signer holding it. `None` without a key, when the
+/// addresses do not parse, or when signing fails.
+async fn sign_fee_quote(
+    cfg: &Config,
+    chain_id: u64,
+    wallet: &str,
+    token: &str,
+    fee: U256,
+    expiry: u64,
+) -> Option<String> {
+    let signer = cfg.relayer_signer_for_chain(&chain_id.to_string())?.ok()?;
+    let quote = FeeQuote {
+        wallet: params::address(wallet).ok()?,
+        // Native fees are quoted without a token address
+        token: match token {
+            "" => Address::ZERO,
+            token => params::address(token).ok()?,
+        },
+        fee,
+        expiry,
+        chain_id,
+    };
+    quote
+        .sign(&signer)
+        .await
+        .inspect_err(|e| tracing::warn!("{}", e))
+        .ok()
+}
+
+/// Hold a relay to the quote it names in `quoteId`: the quote must have been issued for the
+/// relay's chain and one of its payment tokens, and must not have expired. An expired quote
+/// is answered with a fresh one for the same chain and token to price the relay again with.
+async fn check_quote(
+    storage: &Storage,
+    input: &SendTransactionRequest,
+    chain_id: u64,
+    cfg: &Config,
+) -> Result<(), RelayError> {
+    let Some(quote_id) = &input.quote_id else {
+        return Ok(());
+    };
+    let quote = storage
+        .get_quote(quote_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read quote {}: {}", quote_id, e);
+            RelayError::Internal
+        })?
+        .ok_or_else(|| RelayError::invalid_field("quoteId", "Unknown or pruned quote"))?;
+    let for_payment = input
+        .capabilities
+        .payment_options()
+        .any(|payment| payment.token.eq_ignore_ascii_case(&quote.token));
+    if quote.chain_id != chain_id.to_string() || !for_payment {
+        return Err(RelayError::invalid_field(
+            "quoteId",
+            "Quote was issued for another chain or payment token",
+        ));
+    }
+    if quote.expiry >= Utc::now().timestamp() as u64 {
+        return Ok(());
+    }
+
+    tracing::warn!(
+        "Relay to {} on chain {} priced from quote {} that expired at {}",
+        input.to,
+        chain_id,
+        quote.id,
+        quote.expiry
+    );
+    let rate_request = ExchangeRateRequest {
+        token: quote.token.clone(),
+        chain_id: quote.chain_id.clone(),
+    };
+    let mut requote = None;
+    let fresh = build_exchange_rate_response(cfg, &rate_request).await;
+    if let Some(ExchangeRateResultItem::Success(rate)) = fresh.result.first() {
+        let fresh = rate_quote(&quote.chain_id, rate);
+        if let Some(id) = issue_quote(storage, fresh.clone()).await {
+            requote = Some(Box::new(IssuedQuote { id, ..fresh }));
+        }
+    }
+    Err(RelayError::QuoteExpired {
+        expiry: quote.expiry,
+        requote,
+    })
+}
+
+/// Build a dynamic response for the relayer_getExchangeRate endpoint
+async fn build_exchange_rate_response(
+    cfg: &Config,
+    req: &ExchangeRateRequest,
+) -> ExchangeRateResponse {
+    tracing::debug!(
+        "Building exchange rate response for token: {} on chain: {}",
+        req.token,
+        req.chain_id
+    );
+
+    let now = Utc::now().timestamp() as u64;
+    let expiry = now + QUOTE_EXPIRY_SECONDS;
+
+    let chain_id: u64 = match req.chain_id.parse() {
+        Ok(v) => v,
+        Err(_) => {
+            return ExchangeRateResponse {
+                result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
+                    error: ExchangeRateErrorBody {
+                        id: req.chain_id.clone(),
+                        message: "invalid chainId".to_string(),
+                    },
+                })],
+            };
+        }
+    };
+
+    // Zero address denotes native token
+    let zero_addr = "0x0000000000000000000000000000000000000000".to_string();
+
+    if req.token.to_lowercase() == zero_addr {
+        // Native token: rate per gas = gasPrice (smallest unit) / 10^decimals of the gas token
+        let gas_price = fetch_gas_price(chain_id, cfg)
+            .await
+            .unwrap_or_else(|_| "0x4a817c800".to_string());
+        let wei =
+            u128::from_str_radix(gas_price.trim_start_matches("0x"), 16).unwrap_or(20_000_000_000);
+        let native_token = cfg.native_token_info(&chain_id.to_string());
+        let rate_native_per_gas = (wei as f64) / 10f64.powi(native_token.decimals as i32);
+        let item = ExchangeRateResultItem::Success(Box::new(ExchangeRateSuccess {
+            quote: ExchangeRateQuote {
+                rate: rate_native_per_gas,
+                token: native_token,
+            },
+            gas_price,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            fee_collector: fee_collector_for_chain(cfg, &chain_id.to_string()),
+            expiry,
+            l1_fee_model: cfg.fee_model(&chain_id.to_string()),
+            l1_base_fee: fetch_l1_base_fee(chain_id, cfg).await,
+            quote_id: None,
+            breakdown: None,
+        }));
+        return ExchangeRateResponse { result: vec![item] };
+    }
+
+    // ERC20 token: compute rate using Chainlink token/USD and native/USD feeds if configured,
+    // else through the chain's DEX quoter
+    let chain_str = chain_id.to_string();
+
+    // Look up feeds from config
+    let token_feed = cfg.chainlink_token_usd(&chain_str, &req.token);
+    let native_feed = cfg.chainlink_native_usd(&chain_str);
+    let oracle_feeds = token_feed.zip(native_feed);
+    let dex_quoter = cfg.dex_quoter(&chain_str);
+    let not_configured = || ExchangeRateResponse {
+        result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
+            error: ExchangeRateErrorBody {
+                id: req.token.clone(),
+                message: "oracle feed not configured for chain/token".to_string(),
+            },
+        })],
+    };
+    if oracle_feeds.is_none() && dex_quoter.is_none() {
+        return not_configured();
+    }
+
+    // Helper to call a contract view function
+    async fn eth_call_bytes(
+        cfg: &Config,
+        rpc_url: &str,
+        to_address: &str,
+        calldata: &[u8],
+    ) -> Option<Vec<u8>> {
+        let to: Address = to_address.parse().ok()?;
+        cfg.chain
+            .call(rpc_url, to, Bytes::from(calldata.to_vec()), U256::ZERO)
+            .await
+            .ok()
+            .map(|bytes| bytes.to_vec())
+    }
+
+    // Read aggregator decimals (function selector 0x313ce567)
+    async fn read_decimals(cfg: &Config, rpc_url: &str, contract: &str) -> Option<u8> {
+        let sel_decimals: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+        let out = eth_call_bytes(cfg, rpc_url, contract, &sel_decimals).await?;
+        // last 32 bytes right-padded; take last byte for u8
+        out.last().cloned()
+    }
+
+    // Read aggregator latestAnswer() (selector 0x50d25bcd) -> int256
+    async fn read_latest_answer(cfg: &Config, rpc_url: &str, aggregator: &str) -> Option<i128> {
+        let sel_latest_answer: [u8; 4] = [0x50, 0xd2, 0x5b, 0xcd];
+        let out = eth_call_bytes(cfg, rpc_url, aggregator, &sel_latest_answer).await?;
+        if out.len() < 32 {
+            return None;
+        }
+        let mut buf = [0u8; 16];
+        // take the lower 16 bytes (int128) from the 32-byte big-endian word
+        buf.copy_from_slice(&out[16..32]);
+        Some(i128::from_be_bytes(buf))
+    }
+
+    // Get RPC URL
+    let rpc_url = match cfg.rpc_url_for_chain(&chain_str) {
+        Some(u) => u,
+        None => {
+            return ExchangeRateResponse {
+                result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
+                    error: ExchangeRateErrorBody {
+                        id: chain_str,
+                        message: "rpc not configured for chain".to_string(),
+                    },
+                })],
+            };
+        }
+    };
+
+    // Determine token decimals via ERC20 decimals() if possible
+    async fn read_erc20_decimals(cfg: &Config, rpc_url: &str, token: &str) -> Option<u8> {
+        read_decimals(cfg, rpc_url, token).await
+    }
+    let token_decimals = read_erc20_decimals(cfg, &rpc_url, &req.token)
+        .await
+        .unwrap_or(18);
+    let native_decimals = cfg.native_token_info(&chain_str).decimals;
+
+    // Whole tokens per whole native token
+    let token_per_native = match (oracle_feeds, dex_quoter) {
+        (Some((token_feed_addr, native_feed_addr)), _) => {
+            // Fetch prices and decimals
+            let native_dec = read_decimals(cfg, &rpc_url, &native_feed_addr)
+                .await
+                .unwrap_or(8);
+            let token_dec = read_decimals(cfg, &rpc_url, &token_feed_addr)
+                .await
+                .unwrap_or(8);
+            let native_px = read_latest_answer(cfg, &rpc_url, &native_feed_addr).await;
+            let token_px = read_latest_answer(cfg, &rpc_url, &token_feed_addr).await;
+
+            let (native_px, token_px) = match (native_px, token_px) {
+                (Some(n), Some(t)) if n > 0 && t > 0 => (n as f64, t as f64),
+                _ => {
+                    return ExchangeRateResponse {
+                        result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
+                            error: ExchangeRateErrorBody {
+                                id: req.token.clone(),
+                                message: "failed to read oracle price".to_string(),
+                            },
+                        })],
+                    };
+                }
+            };
+
+            // Convert to floating prices in USD
+            let native_usd = native_px / 10f64.powi(native_dec as i32);
+            let token_usd = token_px / 10f64.powi(token_dec as i32);
+            native_usd / token_usd
+        }
+        (None, Some(dex)) => match dex_token_per_native(cfg, &rpc_url, &dex, &req.token).await {
+            Ok(out_per_in) => {
+                out_per_in * 10f64.powi(native_decimals as i32 - token_decimals as i32)
+            }
+            Err(message) => {
+                tracing::warn!(
+                    "DEX pricing of token {} on chain {} failed: {}",
+                    req.token,
+                    chain_id,
+                    message
+                );
+                return ExchangeRateResponse {
+                    result: vec![ExchangeRateResultItem::Error(ExchangeRateError {
+                        error: ExchangeRateErrorBody {
+                            id: req.token.clone(),
+                            message,
+                        },
+                    })],
+                };
+            }
+        },
+        (None, None) => return not_configured(),
+    };
+
+    // Fetch gas price
+    let gas_price_hex = fetch_gas_price(chain_id, cfg)
+        .await
+        .unwrap_or_else(|_| "0x4a817c800".to_string());
+    let wei =
+        u128::from_str_radix(gas_price_hex.trim_start_matches("0x"), 16).unwrap_or(20_000_000_000);
+
+    // native per gas in whole gas-token units
+    let native_per_gas = (wei as f64) / 10f64.powi(native_decimals as i32);
+    // token per gas = native_per_gas * (native_usd / token_usd)
+    let token_per_gas = native_per_gas * token_per_native;
+
+    let item = ExchangeRateResultItem::Success(Box::new(ExchangeRateSuccess {
+        quote: ExchangeRateQuote {
+            rate: token_per_gas,
+            token: TokenInfo {
+                decimals: token_decimals,
+                address: req.token.clone(),
+                symbol: None,
+                name: None,
+            },
+        },
+        gas_price: gas_price_hex,
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        fee_collector: fee_collector_for_chain(cfg, &chain_str),
+        expiry,
+        l1_fee_model: cfg.fee_model(&chain_str),
+        l1_base_fee: fetch_l1_base_fee(chain_id, cfg).await,
+        quote_id: None,
+        breakdown: None,
+    }));
+
+    ExchangeRateResponse { result: vec![item] }
+}
+
+/// Uniswap v3 QuoterV2 `quoteExactInputSingle((address,address,uint256,uint24,uint160))`
+/// selector
+const QUOTE_EXACT_INPUT_SINGLE_SELECTOR: [u8; 4] = [0xc6, 0xa5, 0x02, 0x6a];
+
+/// Calldata quoting a swap of `amount_in` wrapped native into `token` through `dex`
+fn dex_quote_calldata(dex: &DexQuoter, token: Address, amount_in: U256) -> Option<Bytes> {
+    let token_in: Address = dex.wrapped_native.parse().ok()?;
+    let mut input = QUOTE_EXACT_INPUT_SINGLE_SELECTOR.to_vec();
+    input.extend_from_slice(token_in.into_word().as_slice());
+    input.extend_from_slice(token.into_word().as_slice());
+    input.extend_from_slice(&amount_in.to_be_bytes::<32>());
+    input.extend_from_slice(&U256::from(dex.fee_tier).to_be_bytes::<32>());
+    // sqrtPriceLimitX96 = 0: no limit
+    input.extend_from_slice(&[0u8; 32]);
+    Some(Bytes::from(input))
+}
+
+/// Token smallest units received per wei swapped, read from the DEX quoter with the
+/// configured probe. A probe 100 times smaller is quoted as well; when the probe's price
+/// impact against it exceeds `maxPriceImpactBps`, the pool is too thin to take fees in.
+async fn dex_token_per_native(
+    cfg: &Config,
+    rpc_url: &str,
+    dex: &DexQuoter,
+    token: &str,
+) -> Result<f64, String> {
+    let quoter: Address = dex
+        .quoter
+        .parse()
+        .map_err(|_| "invalid DEX quoter address".to_string())?;
+    let token: Address = token
+        .parse()
+        .map_err(|_| "invalid token address".to_string())?;
+    let probe = U256::from(dex.probe_amount.max(100));
+    let small_probe = probe / U256::from(100);
+
+    let mut out_per_in = Vec::with_capacity(2);
+    for amount_in in [probe, small_probe] {
+        let input = dex_quote_calldata(dex, token, amount_in)
+            .ok_or_else(|| "invalid wrapped native address".to_string())?;
+        let output = cfg
+            .chain
+            .call(rpc_url, quoter, input, U256::ZERO)
+            .await
+            .map_err(|e| format!("DEX quote failed: {}", e))?;
+        let amount_out = output
+            .get(..32)
+            .map(U256::from_be_slice)
+            .filter(|amount| !amount.is_zero())
+            .ok_or_else(|| "no DEX liquidity for token".to_string())?;
+        out_per_in.push(f64::from(amount_out) / f64::from(amount_in));
+    }
+
+    let (probe_rate, small_rate) = (out_per_in[0], out_per_in[1]);
+    let impact_bps = ((small_rate - probe_rate) / small_rate * 10_000.0).max(0.0);
+    if impact_bps > f64::from(dex.max_price_impact_bps) {
+        return Err(format!(
+            "DEX price impact {:.0} bps exceeds {} bps",
+            impact_bps, dex.max_price_impact_bps
+        ));
+    }
+    Ok(probe_rate)
+}
+
+async fn process_estimate_fee(
+    storage: &Storage,
+    input: &EstimateFeeRequest,
+    cfg: &Config,
+) -> Result<EstimateFeeResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        to = %input.to,
+        chain_id = %input.chain_id,
+        token = %input.token,
+        "=== relayer_estimateFee request received ==="
+    );
+
+    let (chain_id, _, value, native) = parse_estimate_request(input, cfg)?;
+
+    let gas_limit = match simulate_transaction(&input.to, &input.data, value, chain_id, cfg).await {
+        Ok(gas) => gas,
+        Err(_) if cfg.is_simulation_disabled() => cfg.fallback_gas_limit(chain_id),
+        Err(e) => match fallback_gas_after_failed_simulation(chain_id, false, cfg) {
+            Some(gas) => gas,
+            None => {
+                tracing::warn!(
+                    "Fee estimate simulation failed for wallet {} on chain {}: {}",
+                    input.to,
+                    chain_id,
+                    e
+                );
+                return Err(RelayError::SimulationFailed(e).into());
+            }
+        },
+    };
+
+    let (mut estimate, fee) =
+        price_estimate(input, chain_id, native, value, gas_limit, cfg).await?;
+    estimate.signature =
+        sign_fee_quote(cfg, chain_id, &input.to, &input.token, fee, estimate.expiry).await;
+    let quote = IssuedQuote {
+        id: String::new(),
+        chain_id: input.chain_id.clone(),
+        token: input.token.clone(),
+        rate: estimate.rate,
+        gas_price: estimate.gas_price.clone(),
+        fee: Some(estimate.fee.clone()),
+        expiry: estimate.expiry,
+    };
+    estimate.quote_id = issue_quote(storage, quote).await;
+    Ok(estimate)
+}
+
+/// Validate a `relayer_estimateFee` or `relayer_simulate` request, returning its chain id,
+/// target, value and whether the fee is paid in the native token
+fn parse_estimate_request(
+    input: &EstimateFeeRequest,
+    cfg: &Config,
+) -> Result<(u64, Address, U256, bool), jsonrpc_core::Error> {
+    if input.to.is_empty() || input.data.is_empty() || input.chain_id.is_empty() {
+        tracing::warn!("Validation failed: 'to', 'data' and 'chainId' are required");
+        return Err(RelayError::invalid_params(
+            "Missing required field: 'to', 'data' or 'chainId'",
+        )
+        .into());
+    }
+
+    let chain_id: u64 = params::chain_id(&input.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", input.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+    })?;
+    if !cfg.is_chain_supported(chain_id) {
+        tracing::warn!("Unsupported chain ID requested: {}", chain_id);
+        return Err(RelayError::UnsupportedChain(chain_id.to_string()).into());
+    }
+    let to = params::address(&input.to).map_err(|e| {
+        tracing::warn!("Invalid wallet address {}: {}", input.to, e);
+        RelayError::invalid_field("to", "Invalid wallet address")
+    })?;
+    let value = call_value(input.value.as_ref()).ok_or_else(|| {
+        tracing::warn!("Invalid value: {:?}", input.value);
+        RelayError::invalid_field("value", "Invalid 'value': expected a hex quantity")
+    })?;
+
+    let native = input
+        .token
+        .eq_ignore_ascii_case("0x0000000000000000000000000000000000000000");
+    if !native && !cfg.accepts_payment_token(&input.chain_id, &input.token) {
+        tracing::warn!("Unsupported payment token supplied: {}", input.token);
+        return Err(RelayError::UnsupportedPaymentToken(input.token.clone()).into());
+    }
+
+    Ok((chain_id, to, value, native))
+}
+
+/// Simulate a relay call and price it, returning everything a wallet needs for a pre-flight
+/// check: the same selector check, simulation and gas estimate the relay path runs, the
+/// decoded revert reason when it reverts, the logs it would emit and the fee quote.
+/// Nothing is stored.
+async fn process_simulate(
+    input: &SimulateRequest,
+    cfg: &Config,
+) -> Result<SimulateResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        to = %input.to,
+        chain_id = %input.chain_id,
+        token = %input.token,
+        "=== relayer_simulate request received ==="
+    );
+
+    let (chain_id, to, value, native) = parse_estimate_request(input, cfg)?;
+    let calldata: Bytes = params::hex_bytes(&input.data).map_err(|e| {
+        tracing::warn!("Invalid calldata {}: {}", input.data, e);
+        RelayError::invalid_field("data", "Invalid 'data': expected hex calldata")
+    })?;
+    check_relay_selector(&calldata, cfg).map_err(RelayError::invalid_params)?;
+    let rpc_url = cfg.rpc_url_for_chain(&input.chain_id).ok_or_else(|| {
+        RelayError::SimulationFailed(format!("No RPC URL configured for chain {}", chain_id))
+    })?;
+
+    let simulation = cfg
+        .chain
+        .simulate_call(&rpc_url, to, calldata.clone(), value)
+        .await
+        .map_err(|e| {
+            tracing::warn!("Simulation of {} on chain {} failed: {}", to, chain_id, e);
+            RelayError::SimulationFailed(e)
+        })?;
+    let logs = format_logs(&simulation.logs, chain_id, cfg);
+    if !simulation.success {
+        let revert_reason = decode_revert_reason(&simulation.output, cfg);
+        tracing::info!(
+            "Simulated call to {} on chain {} reverts: {}",
+            to,
+            chain_id,
+            revert_reason.as_deref().unwrap_or("no reason")
+        );
+        return Ok(SimulateResponse {
+            success: false,
+            gas_used: simulation.gas_used,
+            gas_limit: None,
+            revert_reason,
+            revert_data: Some(simulation.output.to_string()),
+            logs,
+            quote: None,
+        });
+    }
+
+    let gas_limit = cfg
+        .chain
+        .estimate_gas(&rpc_url, to, calldata, value)
+        .await
+        .map_err(RelayError::SimulationFailed)?;
+    let (quote, _) = price_estimate(input, chain_id, native, value, gas_limit, cfg).await?;
+    tracing::info!(
+        "✓ Simulated call to {} on chain {} - Gas used: {}, Gas limit: {}, Fee: {}",
+        to,
+        chain_id,
+        simulation.gas_used,
+        gas_limit,
+        quote.fee
+    );
+    Ok(SimulateResponse {
+        success: true,
+        gas_used: simulation.gas_used,
+        gas_limit: Some(gas_limit),
+        revert_reason: None,
+        revert_data: None,
+        logs,
+        quote: Some(quote),
+    })
+}
+
+/// Human-readable reason in revert data: the message of `Error(string)`, the meaning of a
+/// `Panic(uint256)` code, or the signature of a custom error declared in the wallet ABI
+fn decode_revert_reason(data: &[u8], cfg: &Config) -> Option<String> {
+    let (selector, args) = data.split_first_chunk::<4>()?;
+    match *selector {
+        // Error(string)
+        [0x08, 0xc3, 0x79, 0xa0] => {
+            let word = |at: usize| -> Option<usize> {
+                usize::try_from(U256::from_be_slice(args.get(at..at + 32)?)).ok()
+            };
+            let offset = word(0)?;
+            let len = word(offset)?;
+            let start = offset.checked_add(32)?;
+            let message = args.get(start..start.checked_add(len)?)?;
+            Some(String::from_utf8_lossy(message).into_owned())
+        }
+        // Panic(uint256)
+        [0x4e, 0x48, 0x7b, 0x71] => {
+            let code = U256::from_be_slice(args.get(..32)?);
+            let meaning = match u64::try_from(code).unwrap_or(u64::MAX) {
+                0x01 => "assertion failed",
+                0x11 => "arithmetic overflow or underflow",
+                0x12 => "division or modulo by zero",
+                0x21 => "invalid enum value",
+                0x31 => "pop on empty array",
+                0x32 => "array index out of bounds",
+                0x41 => "out of memory",
+                0x51 => "call to uninitialized function",
+                _ => "panic",
+            };
+            Some(format!("{} (Panic 0x{:x})", meaning, code))
+        }
+        _ => load_wallet_abi(cfg)
+            .ok()?
+            .errors()
+            .find(|error| error.selector() == *selector)
+            .map(|error| error.signature()),
+    }
+}
+
+/// Price a relay of `gas_limit` gas sending `value` wei in `input.token`, returning the
+/// estimate with the fee it quotes
+async fn price_estimate(
+    input: &EstimateFeeRequest,
+    chain_id: u64,
+    native: bool,
+    value: U256,
+    gas_limit: u64,
+    cfg: &Config,
+) -> Result<(EstimateFeeResponse, U256), jsonrpc_core::Error> {
+    let rate_request = ExchangeRateRequest {
+        token: input.token.clone(),
+        chain_id: chain_id.to_string(),
+    };
+    let rate = match build_exchange_rate_response(cfg, &rate_request)
+        .await
+        .result
+        .into_iter()
+        .next()
+    {
+        Some(ExchangeRateResultItem::Success(success)) => success,
+        Some(ExchangeRateResultItem::Error(e)) => {
+            tracing::warn!(
+                "No rate for token {} on chain {}: {}",
+                input.token,
+                chain_id,
+                e.error.message
+            );
+            return Err(RelayError::UnsupportedPaymentToken(input.token.clone()).into());
+        }
+        None => return Err(RelayError::Internal.into()),
+    };
+
+    let l1_data_fee = fetch_l1_data_fee(chain_id, &input.to, &input.data, cfg).await;
+    let speed = input.speed.unwrap_or_default();
+    let network_per_gas = parse_hex_u256(&rate.gas_price).unwrap_or(U256::ZERO);
+    let wei_per_gas = lane_wei_per_gas(chain_id, speed, network_per_gas, cfg).await;
+    let fee_wei = wei_per_gas
+        .saturating_mul(U256::from(gas_limit))
+        .saturating_add(l1_data_fee)
+        .saturating_add(value);
+    let fee = if native {
+        Some(fee_wei)
+    } else {
+        fee_in_token_units(fee_wei, network_per_gas, &rate.quote)
+    }
+    .ok_or_else(|| {
+        tracing::error!("Zero gas price reported for chain {}", chain_id);
+        RelayError::Internal
+    })?;
+    let (fee, fee_bound) = apply_fee_bounds(fee, &input.chain_id, &input.token, cfg)?;
+
+    let eip1559 = fetch_eip1559_fees(chain_id, cfg).await;
+    tracing::info!(
+        "✓ Fee estimate - To: {}, Chain: {}, Gas: {}, Fee: {}",
+        input.to,
+        chain_id,
+        gas_limit,
+        fee
+    );
+
+    let estimate = EstimateFeeResponse {
+        gas_limit,
+        gas_price: format!("0x{:x}", wei_per_gas),
+        max_fee_per_gas: eip1559.map(|(max_fee, _)| format!("0x{:x}", max_fee)),
+        max_priority_fee_per_gas: eip1559.map(|(_, tip)| format!("0x{:x}", tip)),
+        l1_data_fee: format!("0x{:x}", l1_data_fee),
+        rate: rate_at_gas_price(rate.quote.rate, wei_per_gas, network_per_gas),
+        token: rate.quote.token,
+        fee: fee.to_string(),
+        fee_collector: rate.fee_collector,
+        expiry: rate.expiry,
+        speed,
+        fee_bound,
+        quote_id: None,
+        signature: None,
+    };
+    Ok((estimate, fee))
+}
+
+/// ERC-20 `transfer(address,uint256)` selector
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// Calldata for an ERC-20 `transfer(to, amount)`
+fn erc20_transfer_calldata(to: Address, amount: U256) -> String {
+    let mut input = ERC20_TRANSFER_SELECTOR.to_vec();
+    input.extend_from_slice(to.into_word().as_slice());
+    input.extend_from_slice(&amount.to_be_bytes::<32>());
+    format!("0x{}", hex::encode(input))
+}
+
+/// ERC-20 `approve(address,uint256)` selector
+const ERC20_APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// Calldata for an ERC-20 `approve(spender, amount)`
+fn erc20_approve_calldata(spender: Address, amount: U256) -> String {
+    let mut input = ERC20_APPROVE_SELECTOR.to_vec();
+    input.extend_from_slice(spender.into_word().as_slice());
+    input.extend_from_slice(&amount.to_be_bytes::<32>());
+    format!("0x{}", hex::encode(input))
+}
+
+/// ERC-2612 `permit(address,address,uint256,uint256,uint8,bytes32,bytes32)` selector
+const ERC2612_PERMIT_SELECTOR: [u8; 4] = [0xd5, 0x05, 0xac, 0xcf];
+
+/// ERC-20 `transferFrom(address,address,uint256)` selector
+const ERC20_TRANSFER_FROM_SELECTOR: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// A signed ERC-2612 permit carried in `payment.data` for `permit` fee payment
+#[derive(Debug, Clone, PartialEq)]
+struct FeePermit {
+    owner: Address,
+    value: U256,
+    deadline: U256,
+    v: u8,
+    r: B256,
+    s: B256,
+}
+
+/// Decode `payment.data` as the ABI encoding of
+/// `(address owner, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)`
+fn decode_fee_permit(data: &str) -> Result<FeePermit, RelayError> {
+    let bytes = data
+        .strip_prefix("0x")
+        .and_then(|hex_data| hex::decode(hex_data).ok())
+        .ok_or_else(|| RelayError::invalid_params("Permit data must be 0x-prefixed hex"))?;
+    if bytes.len() != 6 * 32 {
+        return Err(RelayError::invalid_params(
+            "Permit data must encode (owner, value, deadline, v, r, s)",
+        ));
+    }
+    let word = |index: usize| &bytes[index * 32..(index + 1) * 32];
+    if word(0)[..12].iter().any(|byte| *byte != 0) {
+        return Err(RelayError::invalid_params("Invalid permit owner"));
+    }
+    let v = U256::from_be_slice(word(3));
+    if v != U256::from(27) && v != U256::from(28) {
+        return Err(RelayError::invalid_params("Invalid permit signature"));
+    }
+    Ok(FeePermit {
+        owner: Address::from_slice(&word(0)[12..]),
+        value: U256::from_be_slice(word(1)),
+        deadline: U256::from_be_slice(word(2)),
+        v: v.to::<u8>(),
+        r: B256::from_slice(word(4)),
+        s: B256::from_slice(word(5)),
+    })
+}
+
+/// Reject a permit whose deadline has already passed
+fn check_permit_deadline(permit: &FeePermit) -> Result<(), RelayError> {
+    if permit.deadline <= U256::from(Utc::now().timestamp().max(0) as u64) {
+        tracing::warn!(
+            "Expired fee permit from {} (deadline {})",
+            permit.owner,
+            permit.deadline
+        );
+        return Err(RelayError::invalid_params("Permit deadline has passed"));
+    }
+    Ok(())
+}
+
+/// A wallet's signed consent to pay a relay from its gas tank, carried in `payment.data`
+/// for `gasTank` fee payment
+#[derive(Debug, Clone, PartialEq)]
+struct GasTankAuthorization {
+    max_fee: U256,
+    deadline: U256,
+    signature: PrimitiveSignature,
+}
+
+/// Decode `payment.data` as the ABI encoding of
+/// `(uint256 maxFee, uint256 deadline, uint8 v, bytes32 r, bytes32 s)`
+fn decode_gas_tank_authorization(data: &str) -> Result<GasTankAuthorization, RelayError> {
+    let bytes = data
+        .strip_prefix("0x")
+        .and_then(|hex_data| hex::decode(hex_data).ok())
+        .ok_or_else(|| {
+            RelayError::invalid_params("Gas tank authorization must be 0x-prefixed hex")
+        })?;
+    if bytes.len() != 5 * 32 {
+        return Err(RelayError::invalid_params(
+            "Gas tank authorization must encode (maxFee, deadline, v, r, s)",
+        ));
+    }
+    let word = |index: usize| &bytes[index * 32..(index + 1) * 32];
+    let v = U256::from_be_slice(word(2));
+    if v != U256::from(27) && v != U256::from(28) {
+        return Err(RelayError::invalid_params(
+            "Invalid gas tank authorization signature",
+        ));
+    }
+    let mut signature = word(3).to_vec();
+    signature.extend_from_slice(word(4));
+    Ok(GasTankAuthorization {
+        max_fee: U256::from_be_slice(word(0)),
+        deadline: U256::from_be_slice(word(1)),
+        signature: PrimitiveSignature::from_bytes_and_parity(&signature, v == U256::from(28)),
+    })
+}
+
+/// Hash a wallet signs, as an `eth_sign` message, to let the relay of `calldata` to it on
+/// `chain_id` be paid from its gas tank:
+/// `keccak256("relayx gas tank debit" ++ chainId ++ wallet ++ keccak256(calldata) ++ maxFee ++ deadline)`
+/// with every value a 32-byte word
+fn gas_tank_debit_hash(
+    chain_id: u64,
+    wallet: Address,
+    calldata: &[u8],
+    max_fee: U256,
+    deadline: U256,
+) -> B256 {
+    let mut message = b"relayx gas tank debit".to_vec();
+    message.extend_from_slice(&U256::from(chain_id).to_be_bytes::<32>());
+    message.extend_from_slice(wallet.into_word().as_slice());
+    message.extend_from_slice(keccak256(calldata).as_slice());
+    message.extend_from_slice(&max_fee.to_be_bytes::<32>());
+    message.extend_from_slice(&deadline.to_be_bytes::<32>());
+    keccak256(message)
+}
+
+/// Check that `wallet` signed `authorization` for relaying `calldata` on `chain_id` and that
+/// it has not expired. Returns the signed hash, which identifies the authorization once spent.
+fn verify_gas_tank_authorization(
+    authorization: &GasTankAuthorization,
+    chain_id: u64,
+    wallet: &str,
+    calldata: &str,
+) -> Result<B256, RelayError> {
+    if authorization.deadline <= U256::from(Utc::now().timestamp().max(0) as u64) {
+        return Err(RelayError::invalid_params(
+            "Gas tank authorization deadline has passed",
+        ));
+    }
+    let wallet: Address = wallet
+        .parse()
+        .map_err(|_| RelayError::invalid_field("to", "Invalid wallet address"))?;
+    let calldata = calldata
+        .strip_prefix("0x")
+        .and_then(|hex_data| hex::decode(hex_data).ok())
+        .ok_or_else(|| RelayError::invalid_field("data", "Invalid calldata hex"))?;
+    let hash = gas_tank_debit_hash(
+        chain_id,
+        wallet,
+        &calldata,
+        authorization.max_fee,
+        authorization.deadline,
+    );
+    match authorization.signature.recover_address_from_msg(hash) {
+        Ok(signer) if signer == wallet => Ok(hash),
+        _ => {
+            tracing::warn!("Gas tank authorization for {} not signed by it", wallet);
+            Err(RelayError::InvalidSignature)
+        }
+    }
+}
+
+/// Calldata for ERC-2612 `permit(owner, spender, value, deadline, v, r, s)`
+fn erc2612_permit_calldata(permit: &FeePermit, spender: Address) -> String {
+    let mut input = ERC2612_PERMIT_SELECTOR.to_vec();
+    input.extend_from_slice(permit.owner.into_word().as_slice());
+    input.extend_from_slice(spender.into_word().as_slice());
+    input.extend_from_slice(&permit.value.to_be_bytes::<32>());
+    input.extend_from_slice(&permit.deadline.to_be_bytes::<32>());
+    input.extend_from_slice(&U256::from(permit.v).to_be_bytes::<32>());
+    input.extend_from_slice(permit.r.as_slice());
+    input.extend_from_slice(permit.s.as_slice());
+    format!("0x{}", hex::encode(input))
+}
+
+/// Calldata for an ERC-20 `transferFrom(from, to, amount)`
+fn erc20_transfer_from_calldata(from: Address, to: Address, amount: U256) -> String {
+    let mut input = ERC20_TRANSFER_FROM_SELECTOR.to_vec();
+    input.extend_from_slice(from.into_word().as_slice());
+    input.extend_from_slice(to.into_word().as_slice());
+    input.extend_from_slice(&amount.to_be_bytes::<32>());
+    format!("0x{}", hex::encode(input))
+}
+
+/// Quote a transaction in the payment token requested through `capabilities.payment`
+/// (native when omitted). ERC-20 quotes are converted through the oracle rate and carry
+/// the fee `transfer` to the fee collector (or its `approve`, per the chain's `feePayment`)
+/// as an extra relayer call. Permit quotes instead
+/// carry the signed `permit` followed by a `transferFrom` of the fee out of the permit
+/// owner's balance, so the payer needs no prior allowance.
+async fn process_get_quote(
+    input: &QuoteRequest,
+    cfg: &Config,
+) -> Result<QuoteResponse, jsonrpc_core::Error> {
+    let payment = input
+        .capabilities
+        .as_ref()
+        .and_then(|capabilities| capabilities.payment.as_ref());
+    let payment_type = payment
+        .and_then(|payment| payment.get("type"))
+        .and_then(|value| value.as_str())
+        .unwrap_or("native");
+    let token = payment
+        .and_then(|payment| payment.get("token"))
+        .and_then(|value| value.as_str());
+    if !input.transactions.is_empty() {
+        return process_get_multichain_quote(input, payment_type, token, cfg).await;
+    }
+    let speed = input.speed.unwrap_or_default();
+    tracing::info!(
+        to = %input.to,
+        payment_type,
+        ?speed,
+        "=== relayer_getQuote request received ==="
+    );
+
+    // Minimal realistic quote: estimate gas and use current gas price
+    let chain_id: u64 = input
+        .chain_id
+        .as_ref()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(1);
+
+    let value = call_value(input.value.as_ref()).ok_or_else(|| {
+        tracing::warn!("Invalid value: {:?}", input.value);
+        RelayError::invalid_field("value", "Invalid 'value': expected a hex quantity")
+    })?;
+    if !value.is_zero() && payment_type == "sponsored" {
+        tracing::warn!("Sponsored quote for {} carries value {}", input.to, value);
+        return Err(RelayError::UnsupportedCapability("value".to_string()).into());
+    }
+
+    let gas_limit = simulate_transaction(&input.to, &input.data, value, chain_id, cfg)
+        .await
+        .unwrap_or_else(|_| cfg.fallback_gas_limit(chain_id));
+    let l1_data_fee = fetch_l1_data_fee(chain_id, &input.to, &input.data, cfg).await;
+    let blob_gas_fee = match input.blob_count.filter(|count| *count > 0) {
+        Some(count) if count as usize > MAX_BLOBS_PER_TRANSACTION => {
+            tracing::warn!("Quote requested for {} blobs", count);
+            return Err(RelayError::invalid_params(format!(
+                "blobCount must be at most {}",
+                MAX_BLOBS_PER_TRANSACTION
+            ))
+            .into());
+        }
+        Some(count) => {
+            let max_fee_per_blob_gas =
+                fetch_max_fee_per_blob_gas(chain_id, cfg)
+                    .await
+                    .map_err(|e| {
+                        tracing::warn!("Blob quote rejected on chain {}: {}", chain_id, e);
+                        RelayError::UnsupportedCapability("blobCount".to_string())
+                    })?;
+            blob_fee(count as usize, max_fee_per_blob_gas)
+        }
+        None => U256::ZERO,
+    };
+    let fee_collector = fee_collector_for_chain(cfg, &chain_id.to_string());
+    let mut relayer_calls = vec![RelayerCall {
+        to: input.to.clone(),
+        data: input.data.clone(),
+    }];
+
+    let fee_bound;
+    let breakdown;
+    let quoted_fee;
+    let quote = match payment_type {
+        "native" | "gasTank" | "sponsored" => {
+            let gas_price_hex = lane_gas_price(chain_id, speed, cfg)
+                .await
+                .unwrap_or_else(|_| "0x4a817c800".to_string());
+            let wei_per_gas = u128::from_str_radix(gas_price_hex.trim_start_matches("0x"), 16)
+                .unwrap_or(20_000_000_000);
+            let network_per_gas = fetch_gas_price(chain_id, cfg)
+                .await
+                .ok()
+                .and_then(|price| parse_hex_u256(&price))
+                .unwrap_or(U256::from(wei_per_gas));
+            let cost = RelayCost {
+                gas_limit,
+                wei_per_gas: U256::from(wei_per_gas),
+                l1_data_fee,
+                blob_gas_fee,
+                value,
+            };
+            breakdown = fee_breakdown(chain_id, Some(cost), network_per_gas, None, cfg).await;
+            let fee_wei = U256::from(wei_per_gas)
+                .saturating_mul(U256::from(gas_limit))
+                .saturating_add(l1_data_fee)
+                .saturating_add(blob_gas_fee)
+                .saturating_add(value);
+            let (fee, bound) = match payment_type {
+                "sponsored" => (U256::ZERO, None),
+                _ => apply_fee_bounds(
+                    fee_wei,
+                    &chain_id.to_string(),
+                    "0x0000000000000000000000000000000000000000",
+                    cfg,
+                )?,
+            };
+            fee_bound = bound;
+            quoted_fee = fee;
+            let native_token = cfg.native_token_info(&chain_id.to_string());
+            QuoteInner {
+                fee: fee.to_string(),
+                decimals: native_token.decimals,
+                rate: (wei_per_gas as f64) / 10f64.powi(native_token.decimals as i32),
+                token: native_token,
+            }
+        }
+        "erc20" | "permit" => {
+            let permit = match payment_type {
+                "permit" => {
+                    let data = payment
+                        .and_then(|payment| payment.get("data"))
+                        .and_then(|value| value.as_str())
+                        .ok_or_else(|| {
+                            tracing::warn!("Permit quote requested without permit data");
+                            RelayError::invalid_field(
+                                "payment.data",
+                                "Missing required field: 'payment.data'",
+                            )
+                        })?;
+                    let permit = decode_fee_permit(data)?;
+                    check_permit_deadline(&permit)?;
+                    Some(permit)
+                }
+                _ => None,
+            };
+            let token = token.ok_or_else(|| {
+                tracing::warn!("ERC20 quote requested without a payment token");
+                RelayError::invalid_field(
+                    "payment.token",
+                    "Missing required field: 'payment.token'",
+                )
+            })?;
+            if params::address(token).is_err() {
+                tracing::warn!("Invalid ERC20 token address format: {}", token);
+                return Err(RelayError::invalid_field(
+                    "payment.token",
+                    "Invalid ERC20 token address",
+                )
+                .into());
+            }
+            if !cfg.accepts_payment_token(&chain_id.to_string(), token) {
+                tracing::warn!("Unsupported ERC20 payment token supplied: {}", token);
+                return Err(RelayError::UnsupportedPaymentToken(token.to_string()).into());
+            }
+
+            let rate_request = ExchangeRateRequest {
+                token: token.to_string(),
+                chain_id: chain_id.to_string(),
+            };
+            let rate = match build_exchange_rate_response(cfg, &rate_request)
+                .await
+                .result
+                .into_iter()
+                .next()
+            {
+                Some(ExchangeRateResultItem::Success(success)) => success,
+                Some(ExchangeRateResultItem::Error(e)) => {
+                    tracing::warn!(
+                        "No rate for token {} on chain {}: {}",
+                        token,
+                        chain_id,
+                        e.error.message
+                    );
+                    return Err(RelayError::UnsupportedPaymentToken(token.to_string()).into());
+                }
+                None => return Err(RelayError::Internal.into()),
+            };
+
+            let network_per_gas = parse_hex_u256(&rate.gas_price).unwrap_or(U256::ZERO);
+            let wei_per_gas = lane_wei_per_gas(chain_id, speed, network_per_gas, cfg).await;
+            let cost = RelayCost {
+                gas_limit,
+                wei_per_gas,
+                l1_data_fee,
+                blob_gas_fee,
+                value,
+            };
+            let fee_wei = cost.fee_wei();
+            breakdown = fee_breakdown(
+                chain_id,
+                Some(cost),
+                network_per_gas,
+                Some(&rate.quote),
+                cfg,
+            )
+            .await;
+            let fee =
+                fee_in_token_units(fee_wei, network_per_gas, &rate.quote).ok_or_else(|| {
+                    tracing::error!("Zero gas price reported for chain {}", chain_id);
+                    RelayError::Internal
+                })?;
+            let (fee, bound) = apply_fee_bounds(fee, &chain_id.to_string(), token, cfg)?;
+            fee_bound = bound;
+            quoted_fee = fee;
+            let collector = fee_collector.parse::<Address>().map_err(|e| {
+                tracing::error!("Invalid fee collector address {}: {}", fee_collector, e);
+                RelayError::Internal
+            })?;
+            match permit {
+                Some(permit) => {
+                    if permit.value < fee {
+                        tracing::warn!(
+                            "Permit value {} is below the quoted fee {}",
+                            permit.value,
+                            fee
+                        );
+                        return Err(RelayError::InsufficientFee {
+                            required: fee.to_string(),
+                            offered: permit.value.to_string(),
+                        }
+                        .into());
+                    }
+                    let wallet = params::address(&input.to).map_err(|_| {
+                        RelayError::invalid_params("Invalid wallet address in 'to'")
+                    })?;
+                    relayer_calls.push(RelayerCall {
+                        to: token.to_string(),
+                        data: erc2612_permit_calldata(&permit, wallet),
+                    });
+                    relayer_calls.push(RelayerCall {
+                        to: token.to_string(),
+                        data: erc20_transfer_from_calldata(permit.owner, collector, fee),
+                    });
+                }
+                None => {
+                    let data = match cfg.fee_payment(&chain_id.to_string()) {
+                        FeePayment::Transfer => erc20_transfer_calldata(collector, fee),
+                        FeePayment::Approve => erc20_approve_calldata(collector, fee),
+                    };
+                    relayer_calls.push(RelayerCall {
+                        to: token.to_string(),
+                        data,
+                    });
+                }
+            }
+            QuoteInner {
+                fee: fee.to_string(),
+                decimals: rate.quote.token.decimals,
+                rate: rate_at_gas_price(rate.quote.rate, wei_per_gas, network_per_gas),
+                token: rate.quote.token,
+            }
+        }
+        other => {
+            tracing::warn!("Unsupported payment type: {}", other);
+            return Err(RelayError::UnsupportedCapability(other.to_string()).into());
+        }
+    };
+
+    tracing::info!(
+        "✓ Quote - To: {}, Chain: {}, Gas: {}, Fee: {} (token {})",
+        input.to,
+        chain_id,
+        gas_limit,
+        quote.fee,
+        quote.token.address
+    );
+    let expiry = Utc::now().timestamp() as u64 + QUOTE_EXPIRY_SECONDS;
+    let signature = sign_fee_quote(
+        cfg,
+        chain_id,
+        &input.to,
+        &quote.token.address,
+        quoted_fee,
+        expiry,
+    )
+    .await;
+    Ok(QuoteResponse {
+        quote,
+        relayer_calls,
+        fee_collector,
+        revert_reason: "".to_string(),
+        speed,
+        fee_bound,
+        expiry,
+        signature,
+        legs: Vec::new(),
+        breakdown: Some(breakdown),
+    })
+}
+
+/// Quote the legs of a `relayer_sendTransactionMultichain` submission as one fee. Every leg is
+/// validated and simulated as the submission would be and priced at the same network gas
+/// price, and the legs' wei costs are summed and converted into the payment token on
+/// `paymentChainId`. Legs must share the payment chain's native currency to be summed.
+async fn process_get_multichain_quote(
+    input: &QuoteRequest,
+    payment_type: &str,
+    token: Option<&str>,
+    cfg: &Config,
+) -> Result<QuoteResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        transactions = input.transactions.len(),
+        payment_chain_id = ?input.payment_chain_id,
+        payment_type,
+        "=== relayer_getQuote multichain request received ==="
+    );
+    if !cfg.is_method_enabled("relayer_sendTransactionMultichain") {
+        tracing::warn!("Multichain quote requested with multichain submissions disabled");
+        return Err(RelayError::UnsupportedCapability("transactions".to_string()).into());
+    }
+
+    let payment_chain = input.payment_chain_id.as_deref().unwrap_or_default();
+    if payment_chain.is_empty() {
+        tracing::warn!("Validation failed: Missing 'paymentChainId' field");
+        return Err(RelayError::invalid_field(
+            "paymentChainId",
+            "Missing required field: 'paymentChainId'",
+        )
+        .into());
+    }
+    let payment_chain_id: u64 = params::chain_id(payment_chain).map_err(|e| {
+        tracing::warn!("Invalid paymentChainId format: {} ({})", payment_chain, e);
+        RelayError::invalid_field(
+            "paymentChainId",
+            "Invalid paymentChainId: must be a valid number",
+        )
+    })?;
+    if !cfg.is_chain_supported(payment_chain_id) {
+        tracing::warn!("Unsupported payment chain ID: {}", payment_chain_id);
+        return Err(RelayError::UnsupportedChain(payment_chain_id.to_string()).into());
+    }
+    let payment_chain = payment_chain_id.to_string();
+
+    // The payment types relayer_sendTransactionMultichain accepts
+    let token = match payment_type {
+        "native" | "sponsored" => "0x0000000000000000000000000000000000000000",
+        "erc20" => {
+            let token = token.ok_or_else(|| {
+                tracing::warn!("ERC20 quote requested without a payment token");
+                RelayError::invalid_field(
+                    "payment.token",
+                    "Missing required field: 'payment.token'",
+                )
+            })?;
+            let payment = PaymentCapability {
+                payment_type: payment_type.to_string(),
+                token: token.to_string(),
+                data: String::new(),
+            };
+            validate_payment(&payment, payment_chain_id, cfg)?;
+            token
+        }
+        other => {
+            tracing::warn!(
+                "Payment type {} is not supported for multichain quotes",
+                other
+            );
+            return Err(RelayError::UnsupportedCapability(other.to_string()).into());
+        }
+    };
+    let sponsored = payment_type == "sponsored";
+    let payment_native = cfg.native_token_info(&payment_chain);
+
+    let mut legs = Vec::new();
+    let mut fee_wei = U256::ZERO;
+    for (idx, tx) in input.transactions.iter().enumerate() {
+        let (chain_id, _, gas_price, gas_limit) =
+            prepare_multichain_leg(idx, tx, sponsored, cfg).await?;
+        if !sponsored
+            && cfg.native_token_info(&chain_id.to_string()).symbol != payment_native.symbol
+        {
+            tracing::warn!(
+                "Transaction {} on chain {} is not paid in the native currency of chain {}",
+                idx,
+                chain_id,
+                payment_chain_id
+            );
+            return Err(RelayError::invalid_field(
+                format!("transactions[{}].chainId", idx),
+                format!(
+                    "Transaction {}: native currency differs from the payment chain's",
+                    idx
+                ),
+            )
+            .into());
+        }
+        // Charged as the submission records it: gas limit at the network gas price
+        let leg_wei = parse_hex_u256(&gas_price)
+            .unwrap_or_default()
+            .saturating_mul(U256::from(gas_limit));
+        fee_wei = fee_wei.saturating_add(leg_wei);
+        legs.push(QuoteLeg {
+            chain_id: chain_id.to_string(),
+            gas_limit,
+            gas_price,
+            fee_wei: format!("0x{:x}", leg_wei),
+        });
+    }
+
+    let rate_request = ExchangeRateRequest {
+        token: token.to_string(),
+        chain_id: payment_chain.clone(),
+    };
+    let rate = match build_exchange_rate_response(cfg, &rate_request)
+        .await
+        .result
+        .into_iter()
+        .next()
+    {
+        Some(ExchangeRateResultItem::Success(success)) => success,
+        Some(ExchangeRateResultItem::Error(e)) => {
+            tracing::warn!(
+                "No rate for token {} on chain {}: {}",
+                token,
+                payment_chain,
+                e.error.message
+            );
+            return Err(RelayError::UnsupportedPaymentToken(token.to_string()).into());
+        }
+        None => return Err(RelayError::Internal.into()),
+    };
+    let (fee, fee_bound) = match payment_type {
+        "sponsored" => (U256::ZERO, None),
+        "native" => apply_fee_bounds(fee_wei, &payment_chain, token, cfg)?,
+        _ => {
+            let network_per_gas = parse_hex_u256(&rate.gas_price).unwrap_or(U256::ZERO);
+            let fee =
+                fee_in_token_units(fee_wei, network_per_gas, &rate.quote).ok_or_else(|| {
+                    tracing::error!("Zero gas price reported for chain {}", payment_chain);
+                    RelayError::Internal
+                })?;
+            apply_fee_bounds(fee, &payment_chain, token, cfg)?
+        }
+    };
+
+    let fee_collector = fee_collector_for_chain(cfg, &payment_chain);
+    let mut relayer_calls = Vec::new();
+    if payment_type == "erc20" {
+        let collector = fee_collector.parse::<Address>().map_err(|e| {
+            tracing::error!("Invalid fee collector address {}: {}", fee_collector, e);
+            RelayError::Internal
+        })?;
+        let data = match cfg.fee_payment(&payment_chain) {
+            FeePayment::Transfer => erc20_transfer_calldata(collector, fee),
+            FeePayment::Approve => erc20_approve_calldata(collector, fee),
+        };
+        relayer_calls.push(RelayerCall {
+            to: token.to_string(),
+            data,
+        });
+    }
+
+    tracing::info!(
+        "✓ Multichain quote - Legs: {}, Payment chain: {}, Fee: {} (token {})",
+        legs.len(),
+        payment_chain,
+        fee,
+        token
+    );
+    // Attested to the wallet that pays, when one of the legs runs on the payment chain
+    let expiry = Utc::now().timestamp() as u64 + QUOTE_EXPIRY_SECONDS;
+    let payer = input
+        .transactions
+        .iter()
+        .find(|tx| params::chain_id(&tx.chain_id).ok() == Some(payment_chain_id));
+    let signature = match payer {
+        Some(payer) => sign_fee_quote(cfg, payment_chain_id, &payer.to, token, fee, expiry).await,
+        None => None,
+    };
+    Ok(QuoteResponse {
+        quote: QuoteInner {
+            fee: fee.to_string(),
+            decimals: rate.quote.token.decimals,
+            rate: rate.quote.rate,
+            token: rate.quote.token,
+        },
+        relayer_calls,
+        fee_collector,
+        revert_reason: String::new(),
+        // Legs are priced at the network gas price whatever lane was asked for
+        speed: Speed::Standard,
+        fee_bound,
+        expiry,
+        signature,
+        legs,
+        breakdown: None,
+    })
+}
+
+/// How often the background sampler records gas prices and token rates
+const FEE_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Record the current gas price and the rate of every payment token (native included) on
+/// each configured chain. Chains whose gas price cannot be read are skipped rather than
+/// sampled at the fallback price.
+async fn sample_fees(storage: &Storage, cfg: &Config) {
+    let chains = cfg
+        .configured_chain_ids()
+        .into_iter()
+        .map(|chain_id| async move {
+            if let Err(e) = fetch_gas_price(chain_id, cfg).await {
+                tracing::debug!("Skipping fee sample for chain {}: {}", chain_id, e);
+                return;
+            }
+            let timestamp = Utc::now().timestamp() as u64;
+            let mut tokens = vec!["0x0000000000000000000000000000000000000000".to_string()];
+            tokens.extend(cfg.get_supported_tokens_for_chain(&chain_id.to_string()));
+
+            for token in tokens {
+                let request = ExchangeRateRequest {
+                    token: token.clone(),
+                    chain_id: chain_id.to_string(),
+                };
+                let sample = match build_exchange_rate_response(cfg, &request)
+                    .await
+                    .result
+                    .into_iter()
+                    .next()
+                {
+                    Some(ExchangeRateResultItem::Success(success)) => FeeSample {
+                        timestamp,
+                        gas_price: success.gas_price,
+                        rate: success.quote.rate,
+                    },
+                    _ => continue,
+                };
+                if let Err(e) = storage.record_fee_sample(chain_id, &token, &sample).await {
+                    tracing::warn!(
+                        "Failed to record fee sample for chain {} token {}: {}",
+                        chain_id,
+                        token,
+                        e
+                    );
+                }
+            }
+        });
+    join_all(chains).await;
+}
+
+async fn process_get_fee_history(
+    storage: Storage,
+    request: &GetFeeHistoryRequest,
+    cfg: &Config,
+) -> Result<GetFeeHistoryResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        chain_id = %request.chain_id,
+        "=== relayer_getFeeHistory request received ==="
+    );
+
+    let chain_id: u64 = params::chain_id(&request.chain_id).map_err(|e| {
+        tracing::warn!("Invalid chainId format: {} ({})", request.chain_id, e);
+        RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+    })?;
+    if !cfg.is_chain_supported(chain_id) {
+        tracing::warn!("Unsupported chain ID requested: {}", chain_id);
+        return Err(RelayError::UnsupportedChain(chain_id.to_string()).into());
+    }
+    let limit = match request.limit {
+        Some(0) => return Err(RelayError::invalid_params("limit must be at least 1").into()),
+        Some(limit) => limit.min(FEE_HISTORY_SLOTS as usize),
+        None => FEE_HISTORY_SLOTS as usize,
+    };
+    let token = request
+        .token
+        .clone()
+        .unwrap_or_else(|| "0x0000000000000000000000000000000000000000".to_string());
+
+    let samples = storage
+        .get_fee_samples(chain_id, &token, limit)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to read fee history for chain {} token {}: {}",
+                chain_id,
+                token,
+                e
+            );
+            RelayError::Internal
+        })?;
+
+    Ok(GetFeeHistoryResponse {
+        chain_id: request.chain_id.clone(),
+        token,
+        samples,
+    })
+}
+
+/// Window `relayer_getQueueStats` averages over when the caller does not pick one
+const QUEUE_STATS_DEFAULT_WINDOW_SECS: u64 = 3600;
+
+/// Longest window `relayer_getQueueStats` accepts: one week
+const QUEUE_STATS_MAX_WINDOW_SECS: u64 = 7 * 24 * 3600;
+
+/// Running sums behind one chain's [`ChainQueueStats`] averages
+#[derive(Default)]
+struct QueueTotals {
+    broadcast_latency_ms: i64,
+    inclusion_ms: i64,
+    included: u64,
+    resubmissions: u64,
+}
+
+async fn process_get_queue_stats(
+    storage: Storage,
+    request: &GetQueueStatsRequest,
+    cfg: &Config,
+) -> Result<GetQueueStatsResponse, jsonrpc_core::Error> {
+    tracing::info!(
+        chain_id = ?request.chain_id,
+        window_seconds = ?request.window_seconds,
+        "=== relayer_getQueueStats request received ==="
+    );
+
+    let chain_filter = match &request.chain_id {
+        Some(chain_id) => {
+            let chain_id: u64 = params::chain_id(chain_id).map_err(|e| {
+                tracing::warn!("Invalid chainId format: {} ({})", chain_id, e);
+                RelayError::invalid_field("chainId", "Invalid chainId: must be a valid number")
+            })?;
+            if !cfg.is_chain_supported(chain_id) {
+                tracing::warn!("Unsupported chain ID requested: {}", chain_id);
+                return Err(RelayError::UnsupportedChain(chain_id.to_string()).into());
+            }
+            Some(chain_id)
+        }
+        None => None,
+    };
+    let window_seconds = match request.window_seconds {
+        Some(0) => {
+            return Err(RelayError::invalid_params("windowSeconds must be at least 1").into())
+        }
+        Some(window) => window.min(QUEUE_STATS_MAX_WINDOW_SECS),
+        None => QUEUE_STATS_DEFAULT_WINDOW_SECS,
+    };
+    let since = Utc::now() - chrono::Duration::seconds(window_seconds as i64);
+
+    let tenant = resolve_tenant(cfg, request.api_key.as_deref())?;
+    let requests = storage
+        .get_requests(TenantScope::Tenant(tenant.as_deref()), None)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read requests for queue stats: {}", e);
+            RelayError::Internal
+        })?;
+
+    let new_stats = |chain_id: u64| ChainQueueStats {
+        chain_id: chain_id.to_string(),
+        ..Default::default()
+    };
+    let mut stats: BTreeMap<u64, (ChainQueueStats, QueueTotals)> = cfg
+        .configured_chain_ids()
+        .into_iter()
+        .filter(|chain_id| chain_filter.is_none_or(|only| only == *chain_id))
+        .map(|chain_id| (chain_id, (new_stats(chain_id), QueueTotals::default())))
+        .collect();
+
+    for req in requests {
+        if chain_filter.is_some_and(|only| only != req.chain_id) {
+            continue;
+        }
+        let (chain, totals) = stats
+            .entry(req.chain_id)
+            .or_insert_with(|| (new_stats(req.chain_id), QueueTotals::default()));
+        match req.status {
+            RequestStatus::Scheduled => chain.queued += 1,
+            RequestStatus::Pending | RequestStatus::Processing => {
+                if req.transaction_hash.is_some() {
+                    chain.in_flight += 1;
+                } else {
+                    chain.queued += 1;
+                }
+            }
+            RequestStatus::Completed | RequestStatus::Failed => {}
+        }
+
+        let Some(broadcast_at) = req.broadcast_at.filter(|at| *at >= since) else {
+            continue;
+        };
+        chain.broadcast += 1;
+        // Scheduled requests wait for their time on purpose; only the delay after it counts
+        let accepted = req
+            .execute_after
+            .map_or(req.created_at, |at| at.max(req.created_at));
+        totals.broadcast_latency_ms += (broadcast_at - accepted).num_milliseconds().max(0);
+        if let Some(mined_at) = req.mined_at {
+            totals.inclusion_ms += (mined_at - broadcast_at).num_milliseconds().max(0);
+            totals.included += 1;
+        }
+        // Requests recorded before resubmissions were counted only have their records
+        totals.resubmissions += if req.resubmission_count > 0 {
+            u64::from(req.resubmission_count)
+        } else {
+            storage
+                .get_resubmissions(req.id)
+                .await
+                .map(|resubmissions| resubmissions.len() as u64)
+                .unwrap_or(0)
+        };
+    }
+
+    let chains = stats
+        .into_values()
+        .map(|(mut chain, totals)| {
+            chain.avg_broadcast_latency_ms =
+                (totals.broadcast_latency_ms as u64).checked_div(chain.broadcast);
+            chain.avg_inclusion_ms = (totals.inclusion_ms as u64).checked_div(totals.included);
+            chain.resubmission_rate =
+                (chain.broadcast > 0).then(|| totals.resubmissions as f64 / chain.broadcast as f64);
+            chain
+        })
+        .collect();
+
+    Ok(GetQueueStatsResponse {
+        window_seconds,
+        chains,
+    })
+}
+
+/// Build a response for the relayer_getStatus endpoint
+#[allow(dead_code)]
+fn build_get_status_response(_req: &GetStatusRequest) -> GetStatusResponse {
+    GetStatusResponse {
+		result: vec![StatusResult {
+			version: "2.0.0".to_string(),
+			id: "0x00000000000000000000000000000000000000000000000000000000000000000e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331".to_string(),
+			status: 200,
+			status_name: Some(StatusKind::Confirmed),
+			receipts: vec![Receipt {
+				logs: vec![Log {
+					address: "0xa922b54716264130634d6ff183747a8ead91a40b".to_string(),
+					topics: vec!["0x5a2a90727cc9d000dd060b1132a5c977c9702bb3a52afe360c9c22f0e9451a68".to_string()],
+					data: "0xabcd".to_string(),
+					decoded: None,
+				}],
+				status: "0x1".to_string(),
+				block_hash: "0xf19bbafd9fd0124ec110b848e8de4ab4f62bf60c189524e54213285e7f540d4a".to_string(),
+				block_number: "0xabcd".to_string(),
+				gas_used: "0xdef".to_string(),
+				effective_gas_price: None,
+				transaction_hash: "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaff".to_string(),
+				chain_id: "1".to_string(),
+				blob_gas_used: None,
+				blob_gas_price: None,
+			}],
+			resubmissions: vec![Resubmission {
+				status: 200,
+				transaction_hash: "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaf3".to_string(),
+				chain_id: "1".to_string(),
+			}],
+			offchain_failure: vec![OffchainFailure {
+				message: "insufficient fee provided".to_string(),
+			}],
+			onchain_failure: vec![OnchainFailure {
+				transaction_hash: "0x9b7bb827c2e5e3c1a0a44dc53e573aa0b3af3bd1f9f5ed03071b100bb039eaf2".to_string(),
+				chain_id: "1".to_string(),
+				message: "execution reverted: transfer failed".to_string(),
+				data: "0x08c379a000000000000000000000000000000000000000000000000000000000".to_string(),
+			}],
+			bundle_id: None,
+			estimated_confirmation_seconds: None,
+			callback: None,
+			execute_after: None,
+			calls: None,
+			payment: None,
+			resubmission_count: None,
+		}],
+	}
+}
+
+/// How long a call may wait for a free slot before it is rejected as busy
+const CONCURRENCY_QUEUE_WAIT: Duration = Duration::from_millis(250);
+
+/// Methods that bypass the concurrency limits so the relayer stays observable under load
+const UNLIMITED_METHODS: [&str; 4] = [
+    "health_check",
+    "health_live",
+    "health_ready",
+    "rpc.discover",
+];
+
+/// Plain HTTP GET path answering liveness probes
+const LIVENESS_PATH: &str = "/health/live";
+/// Plain HTTP GET path answering readiness probes: 200 when ready, 503 otherwise
+const READINESS_PATH: &str = "/health/ready";
+
+/// Caps in-flight calls at `max_concurrent_requests` overall and at the `methodConcurrency`
+/// limits per method. A call over the cap waits up to [`CONCURRENCY_QUEUE_WAIT`] for a slot
+/// and is otherwise rejected with [`RelayError::Busy`].
+#[derive(Debug, Clone)]
+struct ConcurrencyLimit {
+    global: Option<Arc<Semaphore>>,
+    per_method: HashMap<String, Arc<Semaphore>>,
+}
+
+impl ConcurrencyLimit {
+    fn new(cfg: &Config) -> Self {
+        let semaphore = |permits: usize| (permits > 0).then(|| Arc::new(Semaphore::new(permits)));
+        Self {
+            global: semaphore(cfg.max_concurrent_requests),
+            per_method: cfg
+                .method_concurrency_limits()
+                .into_iter()
+                .filter_map(|(method, permits)| Some((method, semaphore(permits)?)))
+                .collect(),
+        }
+    }
+}
+
+impl Middleware<()> for ConcurrencyLimit {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: (), next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, ()) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        let Call::MethodCall(method_call) = &call else {
+            return Either::Right(next(call, meta));
+        };
+        if UNLIMITED_METHODS.contains(&method_call.method.as_str()) {
+            return Either::Right(next(call, meta));
+        }
+        let semaphores: Vec<Arc<Semaphore>> = self
+            .global
+            .iter()
+            .chain(self.per_method.get(&method_call.method))
+            .cloned()
+            .collect();
+        if semaphores.is_empty() {
+            return Either::Right(next(call, meta));
+        }
+
+        let jsonrpc = method_call.jsonrpc;
+        let id = method_call.id.clone();
+        let method = method_call.method.clone();
+        let response = next(call, meta);
+        Either::Left(Box::pin(async move {
+            let acquire = async {
+                let mut permits = Vec::with_capacity(semaphores.len());
+                for semaphore in semaphores {
+                    permits.push(semaphore.acquire_owned().await.ok()?);
+                }
+                Some(permits)
+            };
+            match tokio::time::timeout(CONCURRENCY_QUEUE_WAIT, acquire).await {
+                Ok(Some(_permits)) => response.await,
+                _ => {
+                    let error: jsonrpc_core::Error = RelayError::Busy.into();
+                    tracing::warn!(
+                        "[{}] Error response: code={:?}, message={}",
+                        method,
+                        error.code,
+                        error.message
+                    );
+                    Some(Output::Failure(Failure { jsonrpc, error, id }))
+                }
+            }
+        }))
+    }
+}
+
+/// Answers calls to methods listed in `disabledMethods` with "method not found", as if they
+/// were never registered
+#[derive(Debug, Clone)]
+struct DisabledMethods(Arc<BTreeSet<String>>);
+
+impl Middleware<()> for DisabledMethods {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: (), next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, ()) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        let Call::MethodCall(method_call) = &call else {
+            return Either::Right(next(call, meta));
+        };
+        if !self.0.contains(&method_call.method) {
+            return Either::Right(next(call, meta));
+        }
+
+        tracing::warn!("[{}] Called while disabled", method_call.method);
+        let output = Output::Failure(Failure {
+            jsonrpc: method_call.jsonrpc,
+            error: jsonrpc_core::Error::method_not_found(),
+            id: method_call.id.clone(),
+        });
+        Either::Left(Box::pin(async move { Some(output) }))
+    }
+}
+
+/// Sandbox mode's scripted method behavior: every call waits out the method's configured
+/// latency, and methods with a configured failure answer it instead of running
+#[derive(Debug, Clone)]
+struct SandboxMode(Option<Arc<SandboxSettings>>);
+
+impl Middleware<()> for SandboxMode {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: (), next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, ()) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        let (Some(settings), Call::MethodCall(method_call)) = (&self.0, &call) else {
+            return Either::Right(next(call, meta));
+        };
+
+        let latency = settings.latency(&method_call.method);
+        let Some(failure) = settings.failures.get(&method_call.method) else {
+            let response = next(call, meta);
+            return Either::Left(Box::pin(async move {
+                sleep(latency).await;
+                response.await
+            }));
+        };
+        tracing::info!(
+            "[{}] Sandbox failure: code={}, message={}",
+            method_call.method,
+            failure.code,
+            failure.message
+        );
+        let output = Output::Failure(Failure {
+            jsonrpc: method_call.jsonrpc,
+            error: jsonrpc_core::Error {
+                code: jsonrpc_core::ErrorCode::from(failure.code),
+                message: failure.message.clone(),
+                data: Some(serde_json::json!({ "reason": "sandbox" })),
+            },
+            id: method_call.id.clone(),
+        });
+        Either::Left(Box::pin(async move {
+            sleep(latency).await;
+            Some(output)
+        }))
+    }
+}
+
+/// Runs each call on its own Sentry hub whose scope carries the call's method and params, so
+/// error events reported while serving it include the request (`sentry.attachRequestContext`).
+/// Passes calls straight through in builds without the `sentry` feature.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(feature = "sentry"), allow(dead_code))]
+struct SentryRequestContext(bool);
+
+impl Middleware<()> for SentryRequestContext {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: (), next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, ()) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        match &call {
+            #[cfg(feature = "sentry")]
+            Call::MethodCall(method_call) if self.0 => {
+                let hub = Arc::new(sentry::Hub::new_from_top(sentry::Hub::current()));
+                let request = BTreeMap::from([
+                    ("method".to_string(), method_call.method.clone().into()),
+                    (
+                        "params".to_string(),
+                        serde_json::to_value(&method_call.params).unwrap_or_default(),
+                    ),
+                ]);
+                hub.configure_scope(|scope| {
+                    scope.set_context("rpc_request", sentry::protocol::Context::Other(request));
+                });
+                Either::Left(Box::pin(next(call, meta).bind_hub(hub)))
+            }
+            _ => Either::Right(next(call, meta)),
+        }
+    }
+}
+
+/// Fails any call still running after `request_timeout` with [`RelayError::Timeout`], so a
+/// hung upstream RPC cannot hold a handler indefinitely
+#[derive(Debug, Clone, Copy)]
+struct RequestTimeout(Duration);
+
+impl Middleware<()> for RequestTimeout {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: (), next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, ()) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        let Call::MethodCall(method_call) = &call else {
+            return Either::Right(next(call, meta));
+        };
+        if self.0.is_zero() {
+            return Either::Right(next(call, meta));
+        }
+
+        let limit = self.0;
+        let jsonrpc = method_call.jsonrpc;
+        let id = method_call.id.clone();
+        let method = method_call.method.clone();
+        let response = next(call, meta);
+        Either::Left(Box::pin(async move {
+            match tokio::time::timeout(limit, response).await {
+                Ok(output) => output,
+                Err(_) => {
+                    let error: jsonrpc_core::Error = RelayError::Timeout(limit.as_secs()).into();
+                    tracing::error!(
+                        "[{}] Error response: code={:?}, message={}",
+                        method,
+                        error.code,
+                        error.message
+                    );
+                    capture_sentry_error(&method, &error);
+                    Some(Output::Failure(Failure { jsonrpc, error, id }))
+                }
+            }
+        }))
+    }
+}
+
+impl RpcServer {
+    /// Chain calls made on behalf of this server are bounded by `config.request_timeout`.
+    /// In sandbox mode chain calls are answered by [`MockChainClient::sandbox`] instead.
+    /// With `localSimulation` (and the `local-evm` feature) calls are simulated in-process.
+    pub fn new(host: String, port: u16, storage: Storage, mut config: Config) -> Result<Self> {
+        if std::env::var_os("RELAYX_STUB_MODE").is_some() {
+            tracing::warn!("RELAYX_STUB_MODE is no longer read; configure `sandbox` instead");
+        }
+        if let Some(settings) = config.sandbox_settings() {
+            tracing::warn!("Sandbox mode: chain calls are simulated, nothing is broadcast");
+            config.chain = ChainBackend::new(MockChainClient::sandbox(&settings));
+        }
+        if let Some(recording) = config.rpc_recording() {
+            config.chain = match recording.mode {
+                RecordingMode::Record => {
+                    tracing::warn!(
+                        "Recording upstream chain traffic to {}",
+                        recording.path.display()
+                    );
+                    config.chain.recorded(&recording.path)?
+                }
+                RecordingMode::Replay => {
+                    tracing::warn!(
+                        "Replaying chain traffic from {}; no node is contacted",
+                        recording.path.display()
+                    );
+                    ChainBackend::new(ReplayChainClient::load(&recording.path)?)
+                }
+            };
+        }
+        if let Some(settings) = config.local_evm_settings() {
+            #[cfg(feature = "local-evm")]
+            {
+                tracing::info!(
+                    "Simulating calls in the local EVM ({})",
+                    if settings.fork {
+                        "forked"
+                    } else {
+                        "synthetic state"
+                    }
+                );
+                config.chain = config.chain.with_local_evm(settings);
+            }
+            #[cfg(not(feature = "local-evm"))]
+            {
+                let _ = settings;
+                tracing::warn!(
+                    "localSimulation is set but relayx was built without the `local-evm` feature; simulating over RPC"
+                );
+            }
+        }
+        config.chain = config
+            .chain
+            .with_timeout(Duration::from_secs(config.request_timeout));
+        Ok(Self {
+            host,
+            port,
+            storage,
+            config,
+        })
+    }
+
+    /// Register all endpoints, bind the HTTP server and spawn the background monitor,
+    /// scheduler, fee sampler and rebalancer.
+    /// Returns once the server is listening; use the handle to wait or shut down.
+    pub async fn start(&self) -> Result<ServerHandle> {
+        // Restore chains registered at runtime through the admin API
+        for chain in self.storage.get_chain_registrations().await? {
+            tracing::info!("Restoring runtime chain registration: {}", chain.chain_id);
+            self.config.register_runtime_chain(chain);
+        }
+        detect_tx_types(&self.config).await;
+        let lifetime = self.storage.record_service_start().await?;
+        tracing::info!(
+            "Relayer start #{} since {} ({} relays mined)",
+            lifetime.starts,
+            lifetime.first_started_at,
+            lifetime.total_relayed
+        );
+
+        tracing::info!("Initializing JSON-RPC handler");
+        let disabled_methods = self.config.disabled_methods();
+        if !disabled_methods.is_empty() {
+            tracing::info!("Disabled methods: {:?}", disabled_methods);
+        }
+        let mut io = MetaIoHandler::with_middleware((
+            DisabledMethods(Arc::new(disabled_methods)),
+            ConcurrencyLimit::new(&self.config),
+            SentryRequestContext(self.config.sentry_settings().attach_request_context),
+            (
+                RequestTimeout(Duration::from_secs(self.config.request_timeout)),
+                SandboxMode(self.config.sandbox_settings().map(Arc::new)),
+            ),
+        ));
+
+        // Endpoint 1: relayer_sendTransaction
+        tracing::debug!("Registering endpoint: relayer_sendTransaction");
+        let storage1 = self.storage.clone();
+        let cfg1 = self.config.clone();
+        io.add_method("relayer_sendTransaction", move |params: Params| {
+            let storage = storage1.clone();
+            let cfg = cfg1.clone();
+
+            async move {
+                tracing::info!("[relayer_sendTransaction] Request received");
+                log_payload("relayer_sendTransaction", "Request params", &params);
+
+                let inputs: Vec<SendTransactionRequest> =
+                    params.parse::<Vec<SendTransactionRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_sendTransaction] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_sendTransaction] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_sendTransaction", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_sendTransaction] Missing params: expected one object");
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
+                    tracing::error!(
+                        "[relayer_sendTransaction] Error response: code={:?}, message={}",
+                        err.code,
+                        err.message
+                    );
+                    err
+                })?;
+
+                match process_send_transaction(storage, input, &cfg).await {
+                    Ok(response) => {
+                        let id = response.result.first().map(|r| r.id.as_str());
+                        tracing::info!(
+                            to = %input.to,
+                            chain_id = %input.chain_id,
+                            id = id.unwrap_or_default(),
+                            "[relayer_sendTransaction] Success response"
+                        );
+                        log_payload("relayer_sendTransaction", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_sendTransaction] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_sendTransaction] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        // Capture error in Sentry
+                        capture_sentry_error("relayer_sendTransaction", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 1b: relayer_sendTransactionMultichain
+        tracing::debug!("Registering endpoint: relayer_sendTransactionMultichain");
+        let storage1b = self.storage.clone();
+        let cfg1b = self.config.clone();
+        io.add_method(
+            "relayer_sendTransactionMultichain",
+            move |params: Params| {
+                let storage = storage1b.clone();
+                let cfg = cfg1b.clone();
+
+                async move {
+                    tracing::info!("[relayer_sendTransactionMultichain] Request received");
+                    log_payload("relayer_sendTransactionMultichain", "Request params", &params);
+
+                    let inputs: Vec<SendTransactionMultichainRequest> = params
+                        .parse::<Vec<SendTransactionMultichainRequest>>()
+                        .map_err(|e| {
+                            tracing::warn!("[relayer_sendTransactionMultichain] Failed to parse params: {}", e);
+                            let err: jsonrpc_core::Error = RelayError::unparsable_params(e.to_string()).into();
+                            tracing::error!("[relayer_sendTransactionMultichain] Error response: code={:?}, message={}", err.code, err.message);
+                            capture_sentry_error("relayer_sendTransactionMultichain", &err);
+                            err
+                        })?;
+                    let input = inputs.first().ok_or_else(|| {
+                        tracing::warn!("[relayer_sendTransactionMultichain] Missing params: expected one object");
+                        let err: jsonrpc_core::Error = RelayError::invalid_params("missing params: expected one object").into();
+                        tracing::error!("[relayer_sendTransactionMultichain] Error response: code={:?}, message={}", err.code, err.message);
+                        err
+                    })?;
+
+                    match process_send_transaction_multichain(storage, input, &cfg).await {
+                        Ok(response) => {
+                            tracing::info!(
+                                bundle_id = %response.bundle_id,
+                                transactions = response.result.len(),
+                                "[relayer_sendTransactionMultichain] Success response"
+                            );
+                            log_payload("relayer_sendTransactionMultichain", "Success response", &response);
+                            serde_json::to_value(response).map_err(|e| {
+                                tracing::error!("[relayer_sendTransactionMultichain] Failed to serialize response: {}", e);
+                                RelayError::Internal.into()
+                            })
+                        }
+                        Err(e) => {
+                            tracing::error!("[relayer_sendTransactionMultichain] Error response: code={:?}, message={}", e.code, e.message);
+                            capture_sentry_error("relayer_sendTransactionMultichain", &e);
+                            Err(e)
+                        }
+                    }
+                }
+            },
+        );
+
+        // Endpoint 1c: relayer_sendCalls
+        tracing::debug!("Registering endpoint: relayer_sendCalls");
+        let storage1c = self.storage.clone();
+        let cfg1c = self.config.clone();
+        io.add_method("relayer_sendCalls", move |params: Params| {
+            let storage = storage1c.clone();
+            let cfg = cfg1c.clone();
+
+            async move {
+                tracing::info!("[relayer_sendCalls] Request received");
+                log_payload("relayer_sendCalls", "Request params", &params);
+
+                let inputs: Vec<SendCallsRequest> =
+                    params.parse::<Vec<SendCallsRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_sendCalls] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_sendCalls] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_sendCalls", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_sendCalls] Missing params: expected one object");
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
+                    tracing::error!(
+                        "[relayer_sendCalls] Error response: code={:?}, message={}",
+                        err.code,
+                        err.message
+                    );
+                    err
+                })?;
+
+                match process_send_calls(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            calls = response.calls.len(),
+                            "[relayer_sendCalls] Success response"
+                        );
+                        log_payload("relayer_sendCalls", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_sendCalls] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_sendCalls] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_sendCalls", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 1d: relayer_sendRawTransaction
+        tracing::debug!("Registering endpoint: relayer_sendRawTransaction");
+        let storage1d = self.storage.clone();
+        let cfg1d = self.config.clone();
+        io.add_method("relayer_sendRawTransaction", move |params: Params| {
+            let storage = storage1d.clone();
+            let cfg = cfg1d.clone();
+
+            async move {
+                tracing::info!("[relayer_sendRawTransaction] Request received");
+                log_payload("relayer_sendRawTransaction", "Request params", &params);
+
+                let inputs: Vec<SendRawTransactionRequest> = params
+                    .parse::<Vec<SendRawTransactionRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "[relayer_sendRawTransaction] Failed to parse params: {}",
+                            e
+                        );
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_sendRawTransaction] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_sendRawTransaction", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_sendRawTransaction] Missing params: expected one object"
+                    );
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
+                    tracing::error!(
+                        "[relayer_sendRawTransaction] Error response: code={:?}, message={}",
+                        err.code,
+                        err.message
+                    );
+                    err
+                })?;
+
+                match process_send_raw_transaction(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            transactions = response.result.len(),
+                            "[relayer_sendRawTransaction] Success response"
+                        );
+                        log_payload("relayer_sendRawTransaction", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_sendRawTransaction] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_sendRawTransaction] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_sendRawTransaction", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2: relayer_getStatus
+        tracing::debug!("Registering endpoint: relayer_getStatus");
+        let storage2 = self.storage.clone();
+        let cfg2 = self.config.clone();
+        io.add_method("relayer_getStatus", move |params: Params| {
+            let storage = storage2.clone();
+            let cfg = cfg2.clone();
+
+            async move {
+                tracing::info!("[relayer_getStatus] Request received");
+                log_payload("relayer_getStatus", "Request params", &params);
+
+                let request: GetStatusRequest =
+                    params.parse::<GetStatusRequest>().map_err(|e| {
+                        tracing::warn!("[relayer_getStatus] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_getStatus] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_getStatus", &err);
+                        err
+                    })?;
+
+                match process_get_status(storage, &request, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            ids = request.ids.len(),
+                            "[relayer_getStatus] Success response"
+                        );
+                        log_payload("relayer_getStatus", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getStatus] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getStatus] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getStatus", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2b: relayer_getBundleStatus
+        tracing::debug!("Registering endpoint: relayer_getBundleStatus");
+        let storage2b = self.storage.clone();
+        let cfg2b = self.config.clone();
+        io.add_method("relayer_getBundleStatus", move |params: Params| {
+            let storage = storage2b.clone();
+            let cfg = cfg2b.clone();
+
+            async move {
+                tracing::info!("[relayer_getBundleStatus] Request received");
+                log_payload("relayer_getBundleStatus", "Request params", &params);
+
+                let inputs: Vec<GetBundleStatusRequest> =
+                    params.parse::<Vec<GetBundleStatusRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_getBundleStatus] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_getBundleStatus] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_getBundleStatus", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_getBundleStatus] Missing params: expected one object");
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
+                    tracing::error!(
+                        "[relayer_getBundleStatus] Error response: code={:?}, message={}",
+                        err.code,
+                        err.message
+                    );
+                    err
+                })?;
+
+                match process_get_bundle_status(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            bundle_id = %input.bundle_id,
+                            "[relayer_getBundleStatus] Success response"
+                        );
+                        log_payload("relayer_getBundleStatus", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getBundleStatus] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getBundleStatus] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getBundleStatus", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2c: relayer_getTransactionsByWallet
+        tracing::debug!("Registering endpoint: relayer_getTransactionsByWallet");
+        let storage2c = self.storage.clone();
+        let cfg2c = self.config.clone();
+        io.add_method("relayer_getTransactionsByWallet", move |params: Params| {
+            let storage = storage2c.clone();
+            let cfg = cfg2c.clone();
+
+            async move {
+                tracing::info!("[relayer_getTransactionsByWallet] Request received");
+                log_payload("relayer_getTransactionsByWallet", "Request params", &params);
+
+                let inputs: Vec<GetTransactionsByWalletRequest> = params
+                    .parse::<Vec<GetTransactionsByWalletRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "[relayer_getTransactionsByWallet] Failed to parse params: {}",
+                            e
+                        );
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_getTransactionsByWallet", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_getTransactionsByWallet] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_get_transactions_by_wallet(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            wallet = %input.wallet,
+                            results = response.result.len(),
+                            "[relayer_getTransactionsByWallet] Success response"
+                        );
+                        log_payload(
+                            "relayer_getTransactionsByWallet",
+                            "Success response",
+                            &response,
+                        );
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getTransactionsByWallet] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getTransactionsByWallet] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getTransactionsByWallet", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2d: relayer_resendTransaction
+        tracing::debug!("Registering endpoint: relayer_resendTransaction");
+        let storage2d = self.storage.clone();
+        let cfg2d = self.config.clone();
+        io.add_method("relayer_resendTransaction", move |params: Params| {
+            let storage = storage2d.clone();
+            let cfg = cfg2d.clone();
+
+            async move {
+                tracing::info!("[relayer_resendTransaction] Request received");
+                log_payload("relayer_resendTransaction", "Request params", &params);
+
+                let input: ResendTransactionRequest =
+                    params.parse::<ResendTransactionRequest>().map_err(|e| {
+                        tracing::warn!("[relayer_resendTransaction] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_resendTransaction", &err);
+                        err
+                    })?;
+
+                match process_resend_transaction(storage, &input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            id = %response.id,
+                            chain_id = %response.chain_id,
+                            "[relayer_resendTransaction] Success response"
+                        );
+                        log_payload("relayer_resendTransaction", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_resendTransaction] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_resendTransaction] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_resendTransaction", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2e: relayer_getReceipt
+        tracing::debug!("Registering endpoint: relayer_getReceipt");
+        let storage2e = self.storage.clone();
+        let cfg2e = self.config.clone();
+        io.add_method("relayer_getReceipt", move |params: Params| {
+            let storage = storage2e.clone();
+            let cfg = cfg2e.clone();
+
+            async move {
+                tracing::info!("[relayer_getReceipt] Request received");
+                log_payload("relayer_getReceipt", "Request params", &params);
+
+                let input: GetReceiptRequest =
+                    params.parse::<GetReceiptRequest>().map_err(|e| {
+                        tracing::warn!("[relayer_getReceipt] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_getReceipt", &err);
+                        err
+                    })?;
+
+                match process_get_receipt(storage, &input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            id = %response.id,
+                            transactions = response.transactions.len(),
+                            mined = response.mined_transaction_hash.is_some(),
+                            "[relayer_getReceipt] Success response"
+                        );
+                        log_payload("relayer_getReceipt", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getReceipt] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getReceipt] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getReceipt", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2f: relayer_submitGasTankDeposit
+        tracing::debug!("Registering endpoint: relayer_submitGasTankDeposit");
+        let storage2f = self.storage.clone();
+        let cfg2f = self.config.clone();
+        io.add_method("relayer_submitGasTankDeposit", move |params: Params| {
+            let storage = storage2f.clone();
+            let cfg = cfg2f.clone();
+
+            async move {
+                tracing::info!("[relayer_submitGasTankDeposit] Request received");
+                log_payload("relayer_submitGasTankDeposit", "Request params", &params);
+
+                let inputs: Vec<SubmitGasTankDepositRequest> = params
+                    .parse::<Vec<SubmitGasTankDepositRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "[relayer_submitGasTankDeposit] Failed to parse params: {}",
+                            e
+                        );
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_submitGasTankDeposit", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_submitGasTankDeposit] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_submit_gas_tank_deposit(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            wallet = %response.deposit.wallet,
+                            chain_id = %response.deposit.chain_id,
+                            amount = %response.deposit.amount,
+                            "[relayer_submitGasTankDeposit] Success response"
+                        );
+                        log_payload(
+                            "relayer_submitGasTankDeposit",
+                            "Success response",
+                            &response,
+                        );
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_submitGasTankDeposit] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_submitGasTankDeposit] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_submitGasTankDeposit", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2g: relayer_getGasTankBalance
+        tracing::debug!("Registering endpoint: relayer_getGasTankBalance");
+        let storage2g = self.storage.clone();
+        let cfg2g = self.config.clone();
+        io.add_method("relayer_getGasTankBalance", move |params: Params| {
+            let storage = storage2g.clone();
+            let cfg = cfg2g.clone();
+
+            async move {
+                tracing::info!("[relayer_getGasTankBalance] Request received");
+                log_payload("relayer_getGasTankBalance", "Request params", &params);
+
+                let inputs: Vec<GetGasTankBalanceRequest> = params
+                    .parse::<Vec<GetGasTankBalanceRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!("[relayer_getGasTankBalance] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_getGasTankBalance", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_getGasTankBalance] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_get_gas_tank_balance(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            wallet = %response.wallet,
+                            chain_id = %response.chain_id,
+                            "[relayer_getGasTankBalance] Success response"
+                        );
+                        log_payload("relayer_getGasTankBalance", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getGasTankBalance] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getGasTankBalance] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getGasTankBalance", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2h: relayer_getGasTankDeposits
+        tracing::debug!("Registering endpoint: relayer_getGasTankDeposits");
+        let storage2h = self.storage.clone();
+        io.add_method("relayer_getGasTankDeposits", move |params: Params| {
+            let storage = storage2h.clone();
+
+            async move {
+                tracing::info!("[relayer_getGasTankDeposits] Request received");
+                log_payload("relayer_getGasTankDeposits", "Request params", &params);
+
+                let inputs: Vec<GetGasTankDepositsRequest> = params
+                    .parse::<Vec<GetGasTankDepositsRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "[relayer_getGasTankDeposits] Failed to parse params: {}",
+                            e
+                        );
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_getGasTankDeposits", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_getGasTankDeposits] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_get_gas_tank_deposits(storage, input).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            wallet = %input.wallet,
+                            deposits = response.deposits.len(),
+                            "[relayer_getGasTankDeposits] Success response"
+                        );
+                        log_payload("relayer_getGasTankDeposits", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getGasTankDeposits] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getGasTankDeposits] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getGasTankDeposits", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2i: relayer_getSponsorshipAllowance
+        tracing::debug!("Registering endpoint: relayer_getSponsorshipAllowance");
+        let storage2i = self.storage.clone();
+        let cfg2i = self.config.clone();
+        io.add_method("relayer_getSponsorshipAllowance", move |params: Params| {
+            let storage = storage2i.clone();
+            let cfg = cfg2i.clone();
+
+            async move {
+                tracing::info!("[relayer_getSponsorshipAllowance] Request received");
+
+                let inputs: Vec<GetSponsorshipAllowanceRequest> = params
+                    .parse::<Vec<GetSponsorshipAllowanceRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "[relayer_getSponsorshipAllowance] Failed to parse params: {}",
+                            e
+                        );
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_getSponsorshipAllowance", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_getSponsorshipAllowance] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_get_sponsorship_allowance(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            project = %response.project,
+                            period = %response.period,
+                            used = response.used,
+                            "[relayer_getSponsorshipAllowance] Success response"
+                        );
+                        log_payload(
+                            "relayer_getSponsorshipAllowance",
+                            "Success response",
+                            &response,
+                        );
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getSponsorshipAllowance] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getSponsorshipAllowance] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getSponsorshipAllowance", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2j: relayer_createRecurringJob
+        tracing::debug!("Registering endpoint: relayer_createRecurringJob");
+        let storage2j = self.storage.clone();
+        let cfg2j = self.config.clone();
+        io.add_method("relayer_createRecurringJob", move |params: Params| {
+            let storage = storage2j.clone();
+            let cfg = cfg2j.clone();
+
+            async move {
+                tracing::info!("[relayer_createRecurringJob] Request received");
+                log_payload("relayer_createRecurringJob", "Request params", &params);
+
+                let inputs: Vec<CreateRecurringJobRequest> = params
+                    .parse::<Vec<CreateRecurringJobRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!(
+                            "[relayer_createRecurringJob] Failed to parse params: {}",
+                            e
+                        );
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_createRecurringJob", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_createRecurringJob] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_create_recurring_job(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            id = %response.id,
+                            paused = response.paused,
+                            next_run_at = %response.next_run_at,
+                            "[relayer_createRecurringJob] Success response"
+                        );
+                        log_payload("relayer_createRecurringJob", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_createRecurringJob] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_createRecurringJob] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_createRecurringJob", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2k: relayer_pauseRecurringJob
+        tracing::debug!("Registering endpoint: relayer_pauseRecurringJob");
+        let storage2k = self.storage.clone();
+        let cfg2k = self.config.clone();
+        io.add_method("relayer_pauseRecurringJob", move |params: Params| {
+            let storage = storage2k.clone();
+            let cfg = cfg2k.clone();
+
+            async move {
+                tracing::info!("[relayer_pauseRecurringJob] Request received");
+                log_payload("relayer_pauseRecurringJob", "Request params", &params);
+
+                let inputs: Vec<PauseRecurringJobRequest> = params
+                    .parse::<Vec<PauseRecurringJobRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!("[relayer_pauseRecurringJob] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_pauseRecurringJob", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_pauseRecurringJob] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_pause_recurring_job(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            id = %response.id,
+                            paused = response.paused,
+                            next_run_at = %response.next_run_at,
+                            "[relayer_pauseRecurringJob] Success response"
+                        );
+                        log_payload("relayer_pauseRecurringJob", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_pauseRecurringJob] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_pauseRecurringJob] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_pauseRecurringJob", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 2l: relayer_listRecurringJobs
+        tracing::debug!("Registering endpoint: relayer_listRecurringJobs");
+        let storage2l = self.storage.clone();
+        let cfg2l = self.config.clone();
+        io.add_method("relayer_listRecurringJobs", move |params: Params| {
+            let storage = storage2l.clone();
+            let cfg = cfg2l.clone();
+
+            async move {
+                tracing::info!("[relayer_listRecurringJobs] Request received");
+                log_payload("relayer_listRecurringJobs", "Request params", &params);
+
+                let inputs: Vec<ListRecurringJobsRequest> = params
+                    .parse::<Vec<ListRecurringJobsRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!("[relayer_listRecurringJobs] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_listRecurringJobs", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!(
+                        "[relayer_listRecurringJobs] Missing params: expected one object"
+                    );
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_list_recurring_jobs(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            jobs = response.jobs.len(),
+                            "[relayer_listRecurringJobs] Success response"
+                        );
+                        log_payload("relayer_listRecurringJobs", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_listRecurringJobs] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_listRecurringJobs] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_listRecurringJobs", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 3: Health check
+        tracing::debug!("Registering endpoint: health_check");
+        let storage3 = self.storage.clone();
+        let cfg3 = self.config.clone();
+        io.add_method("health_check", move |_params: Params| {
+            let storage = storage3.clone();
+            let cfg = cfg3.clone();
+
+            async move {
+                tracing::info!("[health_check] Request received");
+
+                match process_health_check(storage, &cfg).await {
+                    Ok(health) => {
+                        tracing::info!("[health_check] Success response");
+                        log_payload("health_check", "Success response", &health);
+                        serde_json::to_value(health).map_err(|e| {
+                            tracing::error!("[health_check] Failed to serialize response: {}", e);
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[health_check] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("health_check", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 3a: health_live / health_ready (also served over plain HTTP GET)
+        tracing::debug!("Registering endpoints: health_live, health_ready");
+        let storage_live = self.storage.clone();
+        io.add_method("health_live", move |_params: Params| {
+            let storage = storage_live.clone();
+            async move {
+                tracing::debug!("[health_live] Request received");
+                serde_json::to_value(build_liveness_response(&storage)).map_err(|e| {
+                    tracing::error!("[health_live] Failed to serialize response: {}", e);
+                    RelayError::Internal.into()
+                })
+            }
+        });
+
+        let storage_ready = self.storage.clone();
+        let cfg_ready = self.config.clone();
+        io.add_method("health_ready", move |_params: Params| {
+            let storage = storage_ready.clone();
+            let cfg = cfg_ready.clone();
+
+            async move {
+                tracing::debug!("[health_ready] Request received");
+
+                match process_health_ready(storage, &cfg).await {
+                    Ok(readiness) => {
+                        log_payload("health_ready", "Success response", &readiness);
+                        serde_json::to_value(readiness).map_err(|e| {
+                            tracing::error!("[health_ready] Failed to serialize response: {}", e);
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "[health_ready] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint 3b: rpc.discover (OpenRPC service discovery)
+        tracing::debug!("Registering endpoint: rpc.discover");
+        let cfg_discover = self.config.clone();
+        io.add_method("rpc.discover", move |_params: Params| {
+            let cfg = cfg_discover.clone();
+            async move {
+                tracing::info!("[rpc.discover] Request received");
+                Ok(discover_document(&cfg))
+            }
+        });
+
+        // New Endpoint: relayer_getExchangeRate
+        tracing::debug!("Registering endpoint: relayer_getExchangeRate");
+        let storage4 = self.storage.clone();
+        let cfg4 = self.config.clone();
+        io.add_method("relayer_getExchangeRate", move |params: Params| {
+            let storage = storage4.clone();
+            let cfg = cfg4.clone();
+            async move {
+                tracing::info!("[relayer_getExchangeRate] Request received");
+                log_payload("relayer_getExchangeRate", "Request params", &params);
+
+                let inputs: Vec<ExchangeRateRequest> =
+                    params.parse::<Vec<ExchangeRateRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_getExchangeRate] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_getExchangeRate] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_getExchangeRate", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_getExchangeRate] Missing params: expected one object");
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
+                    tracing::error!(
+                        "[relayer_getExchangeRate] Error response: code={:?}, message={}",
+                        err.code,
+                        err.message
+                    );
+                    capture_sentry_error("relayer_getExchangeRate", &err);
+                    err
+                })?;
+
+                let mut payload = build_exchange_rate_response(&cfg, input).await;
+                issue_rate_quotes(&storage, &input.chain_id, &mut payload).await;
+                tracing::info!("[relayer_getExchangeRate] Success response");
+                log_payload("relayer_getExchangeRate", "Success response", &payload);
+                serde_json::to_value(payload).map_err(|e| {
+                    tracing::error!(
+                        "[relayer_getExchangeRate] Failed to serialize response: {}",
+                        e
+                    );
+                    RelayError::Internal.into()
+                })
+            }
+        });
+
+        // New Endpoint: relayer_getQuote
+        tracing::debug!("Registering endpoint: relayer_getQuote");
+        let cfg6 = self.config.clone();
+        io.add_method("relayer_getQuote", move |params: Params| {
+            let cfg = cfg6.clone();
+            async move {
+                tracing::info!("[relayer_getQuote] Request received");
+                log_payload("relayer_getQuote", "Request params", &params);
+
+                let inputs: Vec<QuoteRequest> =
+                    params.parse::<Vec<QuoteRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_getQuote] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[relayer_getQuote] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        capture_sentry_error("relayer_getQuote", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_getQuote] Missing params: expected one object");
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
+                    tracing::error!(
+                        "[relayer_getQuote] Error response: code={:?}, message={}",
+                        err.code,
+                        err.message
+                    );
+                    capture_sentry_error("relayer_getQuote", &err);
+                    err
+                })?;
+
+                match process_get_quote(input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            to = %input.to,
+                            fee = %response.quote.fee,
+                            token = %response.quote.token.address,
+                            "[relayer_getQuote] Success response"
+                        );
+                        log_payload("relayer_getQuote", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getQuote] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getQuote] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getQuote", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint: relayer_estimateFee
+        tracing::debug!("Registering endpoint: relayer_estimateFee");
+        let storage_estimate = self.storage.clone();
+        let cfg_estimate = self.config.clone();
+        io.add_method("relayer_estimateFee", move |params: Params| {
+            let storage = storage_estimate.clone();
+            let cfg = cfg_estimate.clone();
+            async move {
+                tracing::info!("[relayer_estimateFee] Request received");
+                log_payload("relayer_estimateFee", "Request params", &params);
+
+                let inputs: Vec<EstimateFeeRequest> =
+                    params.parse::<Vec<EstimateFeeRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_estimateFee] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_estimateFee", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_estimateFee] Missing params: expected one object");
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_estimate_fee(&storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            to = %input.to,
+                            chain_id = %input.chain_id,
+                            fee = response.fee,
+                            "[relayer_estimateFee] Success response"
+                        );
+                        log_payload("relayer_estimateFee", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_estimateFee] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_estimateFee] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_estimateFee", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint: relayer_simulate
+        tracing::debug!("Registering endpoint: relayer_simulate");
+        let cfg_simulate = self.config.clone();
+        io.add_method("relayer_simulate", move |params: Params| {
+            let cfg = cfg_simulate.clone();
+            async move {
+                tracing::info!("[relayer_simulate] Request received");
+                log_payload("relayer_simulate", "Request params", &params);
+
+                let inputs: Vec<SimulateRequest> =
+                    params.parse::<Vec<SimulateRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_simulate] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_simulate", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_simulate] Missing params: expected one object");
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_simulate(input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            to = %input.to,
+                            chain_id = %input.chain_id,
+                            success = response.success,
+                            "[relayer_simulate] Success response"
+                        );
+                        log_payload("relayer_simulate", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_simulate] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_simulate] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_simulate", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // New Endpoint: relayer_getCapabilities
+        tracing::debug!("Registering endpoint: relayer_getCapabilities");
+        let storage5 = self.storage.clone();
+        let cfg5 = self.config.clone();
+        io.add_method("relayer_getCapabilities", move |params: Params| {
+            let storage = storage5.clone();
+            let cfg = cfg5.clone();
+
+            async move {
+                tracing::info!("[relayer_getCapabilities] Request received");
+
+                // Params are optional: [] or [{ "chainId": "1" }]
+                let request = match params {
+                    Params::None => GetCapabilitiesRequest::default(),
+                    params => params
+                        .parse::<Vec<GetCapabilitiesRequest>>()
+                        .map_err(|e| {
+                            tracing::warn!(
+                                "[relayer_getCapabilities] Failed to parse params: {}",
+                                e
+                            );
+                            let err: jsonrpc_core::Error =
+                                RelayError::unparsable_params(e.to_string()).into();
+                            capture_sentry_error("relayer_getCapabilities", &err);
+                            err
+                        })?
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default(),
+                };
+
+                match process_get_capabilities(storage, &request, &cfg).await {
+                    Ok(capabilities) => {
+                        tracing::info!("[relayer_getCapabilities] Success response");
+                        log_payload("relayer_getCapabilities", "Success response", &capabilities);
+                        serde_json::to_value(capabilities).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getCapabilities] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getCapabilities] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getCapabilities", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint: relayer_getSupportedChains
+        tracing::debug!("Registering endpoint: relayer_getSupportedChains");
+        let storage_chains = self.storage.clone();
+        let cfg_chains = self.config.clone();
+        io.add_method("relayer_getSupportedChains", move |_params: Params| {
+            let storage = storage_chains.clone();
+            let cfg = cfg_chains.clone();
+
+            async move {
+                tracing::info!("[relayer_getSupportedChains] Request received");
+
+                match process_get_supported_chains(storage, &cfg).await {
+                    Ok(chains) => {
+                        tracing::info!("[relayer_getSupportedChains] Success response");
+                        log_payload("relayer_getSupportedChains", "Success response", &chains);
+                        serde_json::to_value(chains).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getSupportedChains] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getSupportedChains] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getSupportedChains", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint: relayer_getVersion
+        tracing::debug!("Registering endpoint: relayer_getVersion");
+        let cfg_version = self.config.clone();
+        io.add_method("relayer_getVersion", move |_params: Params| {
+            let cfg = cfg_version.clone();
+            async move {
+                tracing::info!("[relayer_getVersion] Request received");
+                serde_json::to_value(build_version_response(&cfg)).map_err(|e| {
+                    tracing::error!("[relayer_getVersion] Failed to serialize response: {}", e);
+                    RelayError::Internal.into()
+                })
+            }
+        });
+
+        // Admin endpoint: admin_addChain
+        tracing::debug!("Registering endpoint: admin_addChain");
+        let storage_add_chain = self.storage.clone();
+        let cfg_add_chain = self.config.clone();
+        io.add_method("admin_addChain", move |params: Params| {
+            let storage = storage_add_chain.clone();
+            let cfg = cfg_add_chain.clone();
+
+            async move {
+                tracing::info!("[admin_addChain] Request received");
+
+                let inputs: Vec<AddChainRequest> =
+                    params.parse::<Vec<AddChainRequest>>().map_err(|e| {
+                        tracing::warn!("[admin_addChain] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[admin_addChain] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[admin_addChain] Missing params: expected one object");
+                    RelayError::invalid_params("missing params: expected one object")
+                })?;
+
+                match process_add_chain(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!("[admin_addChain] Success response");
+                        log_payload("admin_addChain", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!("[admin_addChain] Failed to serialize response: {}", e);
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[admin_addChain] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("admin_addChain", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Admin endpoint: admin_removeChain
+        tracing::debug!("Registering endpoint: admin_removeChain");
+        let storage_remove_chain = self.storage.clone();
+        let cfg_remove_chain = self.config.clone();
+        io.add_method("admin_removeChain", move |params: Params| {
+            let storage = storage_remove_chain.clone();
+            let cfg = cfg_remove_chain.clone();
+
+            async move {
+                tracing::info!("[admin_removeChain] Request received");
+
+                let inputs: Vec<RemoveChainRequest> =
+                    params.parse::<Vec<RemoveChainRequest>>().map_err(|e| {
+                        tracing::warn!("[admin_removeChain] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[admin_removeChain] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
                         err
                     })?;
                 let input = inputs.first().ok_or_else(|| {
-                    tracing::warn!("[relayer_sendTransaction] Missing params: expected one object");
-                    let err = invalid_params_error();
-                    tracing::error!(
-                        "[relayer_sendTransaction] Error response: code={:?}, message={}",
-                        err.code,
-                        err.message
-                    );
-                    err
+                    tracing::warn!("[admin_removeChain] Missing params: expected one object");
+                    RelayError::invalid_params("missing params: expected one object")
                 })?;
 
-                match process_send_transaction(storage, input, &cfg).await {
+                match process_remove_chain(storage, input, &cfg).await {
                     Ok(response) => {
-                        if let Ok(response_json) = serde_json::to_string(&response) {
-                            tracing::info!(
-                                "[relayer_sendTransaction] Success response: {}",
-                                response_json
-                            );
-                        } else {
-                            tracing::info!(
-                                "[relayer_sendTransaction] Success response (serialization failed)"
-                            );
-                        }
+                        tracing::info!("[admin_removeChain] Success response");
+                        log_payload("admin_removeChain", "Success response", &response);
                         serde_json::to_value(response).map_err(|e| {
                             tracing::error!(
-                                "[relayer_sendTransaction] Failed to serialize response: {}",
+                                "[admin_removeChain] Failed to serialize response: {}",
                                 e
                             );
-                            jsonrpc_core::Error::internal_error()
+                            RelayError::Internal.into()
                         })
                     }
                     Err(e) => {
                         tracing::error!(
-                            "[relayer_sendTransaction] Error response: code={:?}, message={}",
+                            "[admin_removeChain] Error response: code={:?}, message={}",
                             e.code,
                             e.message
                         );
-                        // Capture error in Sentry
-                        capture_sentry_error("relayer_sendTransaction", &e);
+                        capture_sentry_error("admin_removeChain", &e);
                         Err(e)
                     }
                 }
             }
         });
 
-        // Endpoint 1b: relayer_sendTransactionMultichain
-        tracing::debug!("Registering endpoint: relayer_sendTransactionMultichain");
-        let storage1b = self.storage.clone();
-        let cfg1b = self.config.clone();
-        io.add_method(
-            "relayer_sendTransactionMultichain",
-            move |params: Params| {
-                let storage = storage1b.clone();
-                let cfg = cfg1b.clone();
-
-                async move {
-                    tracing::info!("[relayer_sendTransactionMultichain] Request received");
-                    if let Ok(params_json) = serde_json::to_string(&params) {
-                        tracing::debug!("[relayer_sendTransactionMultichain] Request params: {}", params_json);
-                    }
-
-                    let inputs: Vec<SendTransactionMultichainRequest> = params
-                        .parse::<Vec<SendTransactionMultichainRequest>>()
-                        .map_err(|e| {
-                            tracing::warn!("[relayer_sendTransactionMultichain] Failed to parse params: {}", e);
-                            let err = jsonrpc_core::Error::invalid_params(e.to_string());
-                            tracing::error!("[relayer_sendTransactionMultichain] Error response: code={:?}, message={}", err.code, err.message);
-                            capture_sentry_error("relayer_sendTransactionMultichain", &err);
-                            err
-                        })?;
-                    let input = inputs.first().ok_or_else(|| {
-                        tracing::warn!("[relayer_sendTransactionMultichain] Missing params: expected one object");
-                        let err = jsonrpc_core::Error::invalid_params("missing params: expected one object");
-                        tracing::error!("[relayer_sendTransactionMultichain] Error response: code={:?}, message={}", err.code, err.message);
-                        err
-                    })?;
-
-                    match process_send_transaction_multichain(storage, input, &cfg).await {
-                        Ok(response) => {
-                            if let Ok(response_json) = serde_json::to_string(&response) {
-                                tracing::info!("[relayer_sendTransactionMultichain] Success response: {}", response_json);
-                            } else {
-                                tracing::info!("[relayer_sendTransactionMultichain] Success response (serialization failed)");
-                            }
-                            serde_json::to_value(response).map_err(|e| {
-                                tracing::error!("[relayer_sendTransactionMultichain] Failed to serialize response: {}", e);
-                                jsonrpc_core::Error::internal_error()
-                            })
-                        }
-                        Err(e) => {
-                            tracing::error!("[relayer_sendTransactionMultichain] Error response: code={:?}, message={}", e.code, e.message);
-                            capture_sentry_error("relayer_sendTransactionMultichain", &e);
-                            Err(e)
-                        }
-                    }
-                }
-            },
-        );
-
-        // Endpoint 2: relayer_getStatus
-        tracing::debug!("Registering endpoint: relayer_getStatus");
-        let storage2 = self.storage.clone();
-        let cfg2 = self.config.clone();
-        io.add_method("relayer_getStatus", move |params: Params| {
-            let storage = storage2.clone();
-            let cfg = cfg2.clone();
+        // Admin endpoint: admin_exportAccounting
+        tracing::debug!("Registering endpoint: admin_exportAccounting");
+        let storage_export = self.storage.clone();
+        let cfg_export = self.config.clone();
+        io.add_method("admin_exportAccounting", move |params: Params| {
+            let storage = storage_export.clone();
+            let cfg = cfg_export.clone();
 
             async move {
-                tracing::info!("[relayer_getStatus] Request received");
-                if let Ok(params_json) = serde_json::to_string(&params) {
-                    tracing::debug!("[relayer_getStatus] Request params: {}", params_json);
-                }
-
-                let request: GetStatusRequest =
-                    params.parse::<GetStatusRequest>().map_err(|e| {
-                        tracing::warn!("[relayer_getStatus] Failed to parse params: {}", e);
-                        let err = jsonrpc_core::Error::invalid_params(e.to_string());
+                tracing::info!("[admin_exportAccounting] Request received");
+
+                let inputs: Vec<ExportAccountingRequest> = params
+                    .parse::<Vec<ExportAccountingRequest>>()
+                    .map_err(|e| {
+                        tracing::warn!("[admin_exportAccounting] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
                         tracing::error!(
-                            "[relayer_getStatus] Error response: code={:?}, message={}",
+                            "[admin_exportAccounting] Error response: code={:?}, message={}",
                             err.code,
                             err.message
                         );
-                        capture_sentry_error("relayer_getStatus", &err);
                         err
                     })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[admin_exportAccounting] Missing params: expected one object");
+                    RelayError::invalid_params("missing params: expected one object")
+                })?;
 
-                match process_get_status(storage, &request, &cfg).await {
+                match process_export_accounting(storage, input, &cfg).await {
                     Ok(response) => {
-                        if let Ok(response_json) = serde_json::to_string(&response) {
-                            tracing::info!(
-                                "[relayer_getStatus] Success response: {}",
-                                response_json
-                            );
-                        } else {
-                            tracing::info!(
-                                "[relayer_getStatus] Success response (serialization failed)"
-                            );
-                        }
+                        tracing::info!("[admin_exportAccounting] Success response");
                         serde_json::to_value(response).map_err(|e| {
                             tracing::error!(
-                                "[relayer_getStatus] Failed to serialize response: {}",
+                                "[admin_exportAccounting] Failed to serialize response: {}",
                                 e
                             );
-                            jsonrpc_core::Error::internal_error()
+                            RelayError::Internal.into()
                         })
                     }
                     Err(e) => {
                         tracing::error!(
-                            "[relayer_getStatus] Error response: code={:?}, message={}",
+                            "[admin_exportAccounting] Error response: code={:?}, message={}",
                             e.code,
                             e.message
                         );
-                        capture_sentry_error("relayer_getStatus", &e);
+                        capture_sentry_error("admin_exportAccounting", &e);
                         Err(e)
                     }
                 }
             }
         });
 
-        // Endpoint 3: Health check
-        tracing::debug!("Registering endpoint: health_check");
-        let storage3 = self.storage.clone();
-        let cfg3 = self.config.clone();
-        io.add_method("health_check", move |_params: Params| {
-            let storage = storage3.clone();
-            let cfg = cfg3.clone();
+        // Admin endpoint: admin_getRefundReport
+        tracing::debug!("Registering endpoint: admin_getRefundReport");
+        let storage_refunds = self.storage.clone();
+        let cfg_refunds = self.config.clone();
+        io.add_method("admin_getRefundReport", move |params: Params| {
+            let storage = storage_refunds.clone();
+            let cfg = cfg_refunds.clone();
 
             async move {
-                tracing::info!("[health_check] Request received");
+                tracing::info!("[admin_getRefundReport] Request received");
 
-                match process_health_check(storage, &cfg).await {
-                    Ok(health) => {
-                        if let Ok(health_json) = serde_json::to_string(&health) {
-                            tracing::info!("[health_check] Success response: {}", health_json);
-                        } else {
-                            tracing::info!(
-                                "[health_check] Success response (serialization failed)"
+                let inputs: Vec<GetRefundReportRequest> =
+                    params.parse::<Vec<GetRefundReportRequest>>().map_err(|e| {
+                        tracing::warn!("[admin_getRefundReport] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        tracing::error!(
+                            "[admin_getRefundReport] Error response: code={:?}, message={}",
+                            err.code,
+                            err.message
+                        );
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[admin_getRefundReport] Missing params: expected one object");
+                    RelayError::invalid_params("missing params: expected one object")
+                })?;
+
+                match process_get_refund_report(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!("[admin_getRefundReport] Success response");
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[admin_getRefundReport] Failed to serialize response: {}",
+                                e
                             );
-                        }
-                        serde_json::to_value(health).map_err(|e| {
-                            tracing::error!("[health_check] Failed to serialize response: {}", e);
-                            jsonrpc_core::Error::internal_error()
+                            RelayError::Internal.into()
                         })
                     }
                     Err(e) => {
                         tracing::error!(
-                            "[health_check] Error response: code={:?}, message={}",
+                            "[admin_getRefundReport] Error response: code={:?}, message={}",
                             e.code,
                             e.message
                         );
-                        capture_sentry_error("health_check", &e);
+                        capture_sentry_error("admin_getRefundReport", &e);
                         Err(e)
                     }
                 }
             }
         });
 
-        // New Endpoint: relayer_getExchangeRate
-        tracing::debug!("Registering endpoint: relayer_getExchangeRate");
-        let cfg4 = self.config.clone();
-        io.add_method("relayer_getExchangeRate", move |params: Params| {
-            let cfg = cfg4.clone();
+        // Admin endpoint: admin_listRequests
+        tracing::debug!("Registering endpoint: admin_listRequests");
+        let storage_list = self.storage.clone();
+        let cfg_list = self.config.clone();
+        io.add_method("admin_listRequests", move |params: Params| {
+            let storage = storage_list.clone();
+            let cfg = cfg_list.clone();
+
             async move {
-                tracing::info!("[relayer_getExchangeRate] Request received");
-                if let Ok(params_json) = serde_json::to_string(&params) {
-                    tracing::debug!("[relayer_getExchangeRate] Request params: {}", params_json);
-                }
+                tracing::info!("[admin_listRequests] Request received");
 
-                let inputs: Vec<ExchangeRateRequest> =
-                    params.parse::<Vec<ExchangeRateRequest>>().map_err(|e| {
-                        tracing::warn!("[relayer_getExchangeRate] Failed to parse params: {}", e);
-                        let err = jsonrpc_core::Error::invalid_params(e.to_string());
+                let inputs: Vec<ListRequestsRequest> =
+                    params.parse::<Vec<ListRequestsRequest>>().map_err(|e| {
+                        tracing::warn!("[admin_listRequests] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
                         tracing::error!(
-                            "[relayer_getExchangeRate] Error response: code={:?}, message={}",
+                            "[admin_listRequests] Error response: code={:?}, message={}",
                             err.code,
                             err.message
                         );
-                        capture_sentry_error("relayer_getExchangeRate", &err);
                         err
                     })?;
                 let input = inputs.first().ok_or_else(|| {
-                    tracing::warn!("[relayer_getExchangeRate] Missing params: expected one object");
-                    let err =
-                        jsonrpc_core::Error::invalid_params("missing params: expected one object");
-                    tracing::error!(
-                        "[relayer_getExchangeRate] Error response: code={:?}, message={}",
-                        err.code,
-                        err.message
-                    );
-                    capture_sentry_error("relayer_getExchangeRate", &err);
-                    err
+                    tracing::warn!("[admin_listRequests] Missing params: expected one object");
+                    RelayError::invalid_params("missing params: expected one object")
                 })?;
 
-                let payload = build_exchange_rate_response(&cfg, input).await;
-                if let Ok(payload_json) = serde_json::to_string(&payload) {
-                    tracing::info!(
-                        "[relayer_getExchangeRate] Success response: {}",
-                        payload_json
-                    );
-                } else {
-                    tracing::info!(
-                        "[relayer_getExchangeRate] Success response (serialization failed)"
-                    );
+                match process_list_requests(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!("[admin_listRequests] Success response");
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[admin_listRequests] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[admin_listRequests] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("admin_listRequests", &e);
+                        Err(e)
+                    }
                 }
-                serde_json::to_value(payload).map_err(|e| {
-                    tracing::error!(
-                        "[relayer_getExchangeRate] Failed to serialize response: {}",
-                        e
-                    );
-                    jsonrpc_core::Error::internal_error()
-                })
             }
         });
 
-        // New Endpoint: relayer_getQuote
-        tracing::debug!("Registering endpoint: relayer_getQuote");
-        let cfg6 = self.config.clone();
-        io.add_method("relayer_getQuote", move |params: Params| {
-            let cfg = cfg6.clone();
+        // Admin endpoint: admin_cancelRequest
+        tracing::debug!("Registering endpoint: admin_cancelRequest");
+        let storage_cancel = self.storage.clone();
+        let cfg_cancel = self.config.clone();
+        io.add_method("admin_cancelRequest", move |params: Params| {
+            let storage = storage_cancel.clone();
+            let cfg = cfg_cancel.clone();
+
             async move {
-                tracing::info!("[relayer_getQuote] Request received");
-                if let Ok(params_json) = serde_json::to_string(&params) {
-                    tracing::debug!("[relayer_getQuote] Request params: {}", params_json);
-                }
+                tracing::info!("[admin_cancelRequest] Request received");
 
-                let inputs: Vec<QuoteRequest> =
-                    params.parse::<Vec<QuoteRequest>>().map_err(|e| {
-                        tracing::warn!("[relayer_getQuote] Failed to parse params: {}", e);
-                        let err = jsonrpc_core::Error::invalid_params(e.to_string());
+                let inputs: Vec<CancelRequestRequest> =
+                    params.parse::<Vec<CancelRequestRequest>>().map_err(|e| {
+                        tracing::warn!("[admin_cancelRequest] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
                         tracing::error!(
-                            "[relayer_getQuote] Error response: code={:?}, message={}",
+                            "[admin_cancelRequest] Error response: code={:?}, message={}",
                             err.code,
                             err.message
                         );
-                        capture_sentry_error("relayer_getQuote", &err);
                         err
                     })?;
                 let input = inputs.first().ok_or_else(|| {
-                    tracing::warn!("[relayer_getQuote] Missing params: expected one object");
-                    let err =
-                        jsonrpc_core::Error::invalid_params("missing params: expected one object");
-                    tracing::error!(
-                        "[relayer_getQuote] Error response: code={:?}, message={}",
-                        err.code,
-                        err.message
-                    );
-                    capture_sentry_error("relayer_getQuote", &err);
-                    err
+                    tracing::warn!("[admin_cancelRequest] Missing params: expected one object");
+                    RelayError::invalid_params("missing params: expected one object")
                 })?;
 
-                // Minimal realistic quote: estimate gas and use current gas price
-                let chain_id: u64 = input
-                    .chain_id
-                    .as_ref()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(1);
-
-                let gas_limit = simulate_transaction(&input.to, &input.data, chain_id, &cfg)
-                    .await
-                    .unwrap_or(21000);
-
-                let gas_price_hex = fetch_gas_price(chain_id, &cfg)
-                    .await
-                    .unwrap_or_else(|_| "0x4a817c800".to_string());
-                let wei_per_gas = u128::from_str_radix(gas_price_hex.trim_start_matches("0x"), 16)
-                    .unwrap_or(20_000_000_000);
-                let fee_wei = (wei_per_gas as u128).saturating_mul(gas_limit as u128);
-                let fee = u64::try_from(fee_wei.min(u128::from(u64::MAX))).unwrap_or(u64::MAX);
-
-                let payload = QuoteResponse {
-                    quote: QuoteInner {
-                        fee,
-                        rate: (wei_per_gas as f64) / 1e18_f64,
-                        token: TokenInfo {
-                            decimals: 18,
-                            address: "0x0000000000000000000000000000000000000000".to_string(),
-                            symbol: Some("ETH".to_string()),
-                            name: Some("Ethereum".to_string()),
-                        },
-                    },
-                    relayer_calls: vec![RelayerCall {
-                        to: input.to.clone(),
-                        data: input.data.clone(),
-                    }],
-                    fee_collector: std::env::var("RELAYX_FEE_COLLECTOR").ok().unwrap_or_else(
-                        || "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
-                    ),
-                    revert_reason: "".to_string(),
-                };
-
-                if let Ok(payload_json) = serde_json::to_string(&payload) {
-                    tracing::info!("[relayer_getQuote] Success response: {}", payload_json);
-                } else {
-                    tracing::info!("[relayer_getQuote] Success response (serialization failed)");
-                }
-                serde_json::to_value(payload).map_err(|e| {
-                    tracing::error!("[relayer_getQuote] Failed to serialize response: {}", e);
-                    jsonrpc_core::Error::internal_error()
-                })
-            }
-        });
-
-        // New Endpoint: relayer_getCapabilities
-        tracing::debug!("Registering endpoint: relayer_getCapabilities");
-        let storage5 = self.storage.clone();
-        let cfg5 = self.config.clone();
-        io.add_method("relayer_getCapabilities", move |_params: Params| {
-            let storage = storage5.clone();
-            let cfg = cfg5.clone();
-
-            async move {
-                tracing::info!("[relayer_getCapabilities] Request received");
-
-                match process_get_capabilities(storage, &cfg).await {
-                    Ok(capabilities) => {
-                        if let Ok(capabilities_json) = serde_json::to_string(&capabilities) {
-                            tracing::info!(
-                                "[relayer_getCapabilities] Success response: {}",
-                                capabilities_json
-                            );
-                        } else {
-                            tracing::info!(
-                                "[relayer_getCapabilities] Success response (serialization failed)"
-                            );
-                        }
-                        serde_json::to_value(capabilities).map_err(|e| {
+                match process_cancel_request(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!("[admin_cancelRequest] Success response");
+                        serde_json::to_value(response).map_err(|e| {
                             tracing::error!(
-                                "[relayer_getCapabilities] Failed to serialize response: {}",
+                                "[admin_cancelRequest] Failed to serialize response: {}",
                                 e
                             );
-                            jsonrpc_core::Error::internal_error()
+                            RelayError::Internal.into()
                         })
                     }
                     Err(e) => {
                         tracing::error!(
-                            "[relayer_getCapabilities] Error response: code={:?}, message={}",
+                            "[admin_cancelRequest] Error response: code={:?}, message={}",
                             e.code,
                             e.message
                         );
-                        capture_sentry_error("relayer_getCapabilities", &e);
+                        capture_sentry_error("admin_cancelRequest", &e);
                         Err(e)
                     }
                 }
@@ -2087,19 +9796,20 @@ impl RpcServer {
 
         // Endpoint: relayer_getFeeData (spec-compliant replacement for relayer_getExchangeRate)
         tracing::debug!("Registering endpoint: relayer_getFeeData");
+        let storage_fee = self.storage.clone();
         let cfg_fee = self.config.clone();
         io.add_method("relayer_getFeeData", move |params: Params| {
+            let storage = storage_fee.clone();
             let cfg = cfg_fee.clone();
             async move {
                 tracing::info!("[relayer_getFeeData] Request received");
-                if let Ok(params_json) = serde_json::to_string(&params) {
-                    tracing::debug!("[relayer_getFeeData] Request params: {}", params_json);
-                }
+                log_payload("relayer_getFeeData", "Request params", &params);
 
                 let inputs: Vec<FeeDataRequest> =
                     params.parse::<Vec<FeeDataRequest>>().map_err(|e| {
                         tracing::warn!("[relayer_getFeeData] Failed to parse params: {}", e);
-                        let err = jsonrpc_core::Error::invalid_params(e.to_string());
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
                         tracing::error!(
                             "[relayer_getFeeData] Error response: code={:?}, message={}",
                             err.code,
@@ -2110,8 +9820,8 @@ impl RpcServer {
                     })?;
                 let input = inputs.first().ok_or_else(|| {
                     tracing::warn!("[relayer_getFeeData] Missing params: expected one object");
-                    let err =
-                        jsonrpc_core::Error::invalid_params("missing params: expected one object");
+                    let err: jsonrpc_core::Error =
+                        RelayError::invalid_params("missing params: expected one object").into();
                     tracing::error!(
                         "[relayer_getFeeData] Error response: code={:?}, message={}",
                         err.code,
@@ -2121,16 +9831,145 @@ impl RpcServer {
                     err
                 })?;
 
-                let payload = build_exchange_rate_response(&cfg, input).await;
-                if let Ok(payload_json) = serde_json::to_string(&payload) {
-                    tracing::info!("[relayer_getFeeData] Success response: {}", payload_json);
-                } else {
-                    tracing::info!("[relayer_getFeeData] Success response (serialization failed)");
+                let mut payload = build_exchange_rate_response(&cfg, input).await;
+                issue_rate_quotes(&storage, &input.chain_id, &mut payload).await;
+                if let Ok(chain_id) = input.chain_id.parse::<u64>() {
+                    for item in payload.result.iter_mut() {
+                        if let ExchangeRateResultItem::Success(success) = item {
+                            let network_per_gas =
+                                parse_hex_u256(&success.gas_price).unwrap_or(U256::ZERO);
+                            success.breakdown = Some(
+                                fee_breakdown(
+                                    chain_id,
+                                    None,
+                                    network_per_gas,
+                                    Some(&success.quote),
+                                    &cfg,
+                                )
+                                .await,
+                            );
+                        }
+                    }
+                }
+                tracing::info!("[relayer_getFeeData] Success response");
+                log_payload("relayer_getFeeData", "Success response", &payload);
+                serde_json::to_value(payload).map_err(|e| {
+                    tracing::error!("[relayer_getFeeData] Failed to serialize response: {}", e);
+                    RelayError::Internal.into()
+                })
+            }
+        });
+
+        // Endpoint: relayer_getFeeHistory
+        tracing::debug!("Registering endpoint: relayer_getFeeHistory");
+        let storage_history = self.storage.clone();
+        let cfg_history = self.config.clone();
+        io.add_method("relayer_getFeeHistory", move |params: Params| {
+            let storage = storage_history.clone();
+            let cfg = cfg_history.clone();
+            async move {
+                tracing::info!("[relayer_getFeeHistory] Request received");
+                log_payload("relayer_getFeeHistory", "Request params", &params);
+
+                let inputs: Vec<GetFeeHistoryRequest> =
+                    params.parse::<Vec<GetFeeHistoryRequest>>().map_err(|e| {
+                        tracing::warn!("[relayer_getFeeHistory] Failed to parse params: {}", e);
+                        let err: jsonrpc_core::Error =
+                            RelayError::unparsable_params(e.to_string()).into();
+                        capture_sentry_error("relayer_getFeeHistory", &err);
+                        err
+                    })?;
+                let input = inputs.first().ok_or_else(|| {
+                    tracing::warn!("[relayer_getFeeHistory] Missing params: expected one object");
+                    jsonrpc_core::Error::from(RelayError::invalid_params(
+                        "missing params: expected one object",
+                    ))
+                })?;
+
+                match process_get_fee_history(storage, input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            chain_id = %response.chain_id,
+                            token = %response.token,
+                            samples = response.samples.len(),
+                            "[relayer_getFeeHistory] Success response"
+                        );
+                        log_payload("relayer_getFeeHistory", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getFeeHistory] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getFeeHistory] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getFeeHistory", &e);
+                        Err(e)
+                    }
+                }
+            }
+        });
+
+        // Endpoint: relayer_getQueueStats
+        tracing::debug!("Registering endpoint: relayer_getQueueStats");
+        let storage_queue = self.storage.clone();
+        let cfg_queue = self.config.clone();
+        io.add_method("relayer_getQueueStats", move |params: Params| {
+            let storage = storage_queue.clone();
+            let cfg = cfg_queue.clone();
+            async move {
+                tracing::info!("[relayer_getQueueStats] Request received");
+                log_payload("relayer_getQueueStats", "Request params", &params);
+
+                // Params are optional: no params reports every chain over the default window
+                let input = match params {
+                    Params::None => GetQueueStatsRequest::default(),
+                    params => params
+                        .parse::<Vec<GetQueueStatsRequest>>()
+                        .map_err(|e| {
+                            tracing::warn!("[relayer_getQueueStats] Failed to parse params: {}", e);
+                            let err: jsonrpc_core::Error =
+                                RelayError::unparsable_params(e.to_string()).into();
+                            capture_sentry_error("relayer_getQueueStats", &err);
+                            err
+                        })?
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default(),
+                };
+
+                match process_get_queue_stats(storage, &input, &cfg).await {
+                    Ok(response) => {
+                        tracing::info!(
+                            chains = response.chains.len(),
+                            window_seconds = response.window_seconds,
+                            "[relayer_getQueueStats] Success response"
+                        );
+                        log_payload("relayer_getQueueStats", "Success response", &response);
+                        serde_json::to_value(response).map_err(|e| {
+                            tracing::error!(
+                                "[relayer_getQueueStats] Failed to serialize response: {}",
+                                e
+                            );
+                            RelayError::Internal.into()
+                        })
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "[relayer_getQueueStats] Error response: code={:?}, message={}",
+                            e.code,
+                            e.message
+                        );
+                        capture_sentry_error("relayer_getQueueStats", &e);
+                        Err(e)
+                    }
                 }
-                serde_json::to_value(payload).map_err(|e| {
-                    tracing::error!("[relayer_getFeeData] Failed to serialize response: {}", e);
-                    jsonrpc_core::Error::internal_error()
-                })
             }
         });
 
@@ -2145,175 +9984,1099 @@ impl RpcServer {
         })?;
 
         tracing::debug!("Binding server to address: {}", socket_addr);
+        // Liveness is answered before JSON-RPC dispatch; readiness goes through health_ready
+        let storage_probe = self.storage.clone();
         let server = ServerBuilder::new(io)
             .threads(4)
+            .health_api((READINESS_PATH, "health_ready"))
+            .request_middleware(move |request: hyper::Request<hyper::Body>| {
+                if request.method() != hyper::Method::GET || request.uri().path() != LIVENESS_PATH {
+                    return request.into();
+                }
+                let body = serde_json::to_string(&build_liveness_response(&storage_probe))
+                    .unwrap_or_default();
+                jsonrpc_http_server::Response::ok(body).into()
+            })
             .start_http(&socket_addr)
             .map_err(|e| {
                 tracing::error!("Failed to start HTTP server on {}: {}", socket_addr, e);
                 e
             })?;
 
-        tracing::info!("✓ JSON-RPC server listening on {}", socket_addr);
+        let local_addr = *server.address();
+        tracing::info!("✓ JSON-RPC server listening on {}", local_addr);
         tracing::info!("Available endpoints:");
         tracing::info!("  - relayer_sendTransaction");
         tracing::info!("  - relayer_sendTransactionMultichain");
+        tracing::info!("  - relayer_sendCalls");
+        tracing::info!("  - relayer_sendRawTransaction");
         tracing::info!("  - relayer_getStatus");
+        tracing::info!("  - relayer_getBundleStatus");
+        tracing::info!("  - relayer_getTransactionsByWallet");
+        tracing::info!("  - relayer_resendTransaction");
+        tracing::info!("  - relayer_getReceipt");
+        tracing::info!("  - relayer_submitGasTankDeposit");
+        tracing::info!("  - relayer_getGasTankBalance");
+        tracing::info!("  - relayer_getGasTankDeposits");
+        tracing::info!("  - relayer_getSponsorshipAllowance");
+        tracing::info!("  - relayer_createRecurringJob");
+        tracing::info!("  - relayer_pauseRecurringJob");
+        tracing::info!("  - relayer_listRecurringJobs");
         tracing::info!("  - relayer_getCapabilities");
+        tracing::info!("  - relayer_getSupportedChains");
+        tracing::info!("  - relayer_getVersion");
         tracing::info!("  - relayer_getFeeData");
+        tracing::info!("  - relayer_getFeeHistory");
+        tracing::info!("  - relayer_getQueueStats");
         tracing::info!("  - relayer_getExchangeRate");
         tracing::info!("  - relayer_getQuote");
+        tracing::info!("  - relayer_estimateFee");
         tracing::info!("  - health_check");
+        tracing::info!("  - health_live (also GET {})", LIVENESS_PATH);
+        tracing::info!("  - health_ready (also GET {})", READINESS_PATH);
+        tracing::info!("  - rpc.discover");
+        if self.config.get_admin_token().is_some() {
+            tracing::info!("  - admin_addChain");
+            tracing::info!("  - admin_removeChain");
+            tracing::info!("  - admin_exportAccounting");
+            tracing::info!("  - admin_getRefundReport");
+            tracing::info!("  - admin_listRequests");
+            tracing::info!("  - admin_cancelRequest");
+        }
 
         // Spawn background monitor for pending/processing transactions
-        {
+        let monitor = {
             let storage_bg = self.storage.clone();
             let cfg_bg = self.config.clone();
             tokio::spawn(async move {
+                let resumed = resume_callbacks(&storage_bg, &cfg_bg).await;
+                if resumed > 0 {
+                    tracing::info!(resumed, "Resumed pending callback deliveries");
+                }
+                let mut gap_suspects = NonceGapSuspects::new();
                 loop {
-                    // Poll every 10 seconds
-                    sleep(Duration::from_secs(10)).await;
-                    if let Ok(requests) = storage_bg.get_requests(Some(1000)).await {
-                        for req in requests {
-                            if matches!(
-                                req.status,
-                                RequestStatus::Pending | RequestStatus::Processing
-                            ) {
-                                if let Some(tx_hash) = req.transaction_hash.clone() {
-                                    // Try fetch receipt
-                                    if let Some(receipt_status) = fetch_and_update_receipt(
-                                        &storage_bg,
-                                        &cfg_bg,
-                                        &req,
-                                        &tx_hash,
-                                    )
-                                    .await
-                                    {
-                                        tracing::debug!(
-                                            "Receipt processed for {} => {:?}",
-                                            req.id,
-                                            receipt_status
-                                        );
-                                    } else {
-                                        // If still pending, attempt gas-bump resubmission
-                                        if let Ok(price_hex) =
-                                            fetch_gas_price(req.chain_id, &cfg_bg).await
-                                        {
-                                            let bumped = bump_gas_price_hex(&price_hex, 20);
-                                            if let Some(data) = req.data.clone() {
-                                                match send_relay_transaction(
-                                                    &req.to_address,
-                                                    &data,
-                                                    req.chain_id,
-                                                    req.gas_limit,
-                                                    &bumped,
-                                                    &cfg_bg,
-                                                )
-                                                .await
-                                                {
-                                                    Ok(new_tx_hash) => {
-                                                        let _ = storage_bg
-                                                            .update_request_tx_hash(
-                                                                req.id,
-                                                                new_tx_hash.clone(),
-                                                            )
-                                                            .await;
-                                                        let _ = storage_bg
-                                                            .add_resubmission(
-                                                                req.id,
-                                                                &Resubmission {
-                                                                    status: 201,
-                                                                    transaction_hash: new_tx_hash,
-                                                                    chain_id: req
-                                                                        .chain_id
-                                                                        .to_string(),
-                                                                },
-                                                            )
-                                                            .await;
-                                                        let _ = storage_bg
-                                                            .update_request_status(
-                                                                req.id,
-                                                                RequestStatus::Processing,
-                                                                None,
-                                                            )
-                                                            .await;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = storage_bg
-                                                            .update_request_status(
-                                                                req.id,
-                                                                RequestStatus::Failed,
-                                                                Some(e),
-                                                            )
-                                                            .await;
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                    wait_for_monitor_pass(&cfg_bg).await;
+                    if let Ok(requests) =
+                        storage_bg.get_requests(TenantScope::All, Some(1000)).await
+                    {
+                        let summary = poll_in_flight(&storage_bg, &cfg_bg, requests).await;
+                        if summary != MonitorSummary::default() {
+                            tracing::debug!(
+                                finalized = summary.finalized,
+                                resubmitted = summary.resubmitted,
+                                failed = summary.failed,
+                                pending = summary.pending,
+                                "Monitor pass complete"
+                            );
+                        }
+                    }
+                    let healed = heal_nonce_gaps(&storage_bg, &cfg_bg, &mut gap_suspects).await;
+                    if healed > 0 {
+                        tracing::debug!(healed, "Nonce gaps filled");
+                    }
+                }
+            })
+        };
+
+        // Spawn background scheduler broadcasting scheduled requests and recurring jobs once
+        // they come due
+        let scheduler = {
+            let storage_bg = self.storage.clone();
+            let cfg_bg = self.config.clone();
+            tokio::spawn(async move {
+                let mut ticks = tokio::time::interval(SCHEDULER_INTERVAL);
+                loop {
+                    ticks.tick().await;
+                    let executed = run_due_schedules(&storage_bg, &cfg_bg).await
+                        + run_due_recurring_jobs(&storage_bg, &cfg_bg).await;
+                    if executed > 0 {
+                        tracing::debug!(executed, "Scheduler pass complete");
+                    }
+                }
+            })
+        };
+
+        // Spawn background sampler feeding relayer_getFeeHistory; the first sample is
+        // taken right away. Expired quotes are dropped on the same schedule.
+        let sampler = {
+            let storage_bg = self.storage.clone();
+            let cfg_bg = self.config.clone();
+            tokio::spawn(async move {
+                let mut ticks = tokio::time::interval(FEE_SAMPLE_INTERVAL);
+                loop {
+                    ticks.tick().await;
+                    sample_fees(&storage_bg, &cfg_bg).await;
+                    if let Err(e) = storage_bg.prune_quotes(Utc::now().timestamp() as u64).await {
+                        tracing::warn!("Failed to prune expired quotes: {}", e);
+                    }
+                }
+            })
+        };
+
+        // Spawn background rebalancer moving native funds between each chain's keys; chains
+        // without a `rebalancer` entry are left alone
+        let rebalancer = {
+            let cfg_bg = self.config.clone();
+            tokio::spawn(async move {
+                let mut ticks = tokio::time::interval(cfg_bg.rebalance_interval());
+                loop {
+                    ticks.tick().await;
+                    let transfers = rebalance_keys(&cfg_bg).await;
+                    if transfers > 0 {
+                        tracing::debug!(transfers, "Rebalancer pass complete");
+                    }
+                }
+            })
+        };
+
+        // Spawn background archiver moving finished requests out of the hot set; without an
+        // `archive` entry every request stays where it is
+        let archiver = {
+            let storage_bg = self.storage.clone();
+            let policy = self.config.archive_policy();
+            tokio::spawn(async move {
+                let Some(policy) = policy else {
+                    return;
+                };
+                let mut ticks = tokio::time::interval(Duration::from_secs(policy.interval_seconds));
+                loop {
+                    ticks.tick().await;
+                    let cutoff = Utc::now() - chrono::Duration::days(policy.after_days.into());
+                    match storage_bg.archive_requests(cutoff).await {
+                        Ok(archived) if archived > 0 => {
+                            tracing::debug!(archived, "Archive pass complete")
                         }
+                        Ok(_) => {}
+                        Err(e) => tracing::warn!("Archive pass failed: {}", e),
                     }
                 }
+            })
+        };
+
+        tracing::info!("Server is ready and waiting for requests");
+        Ok(ServerHandle {
+            local_addr,
+            relayer_key: self.config.relayer_key_from_file.clone(),
+            server,
+            monitor,
+            scheduler,
+            sampler,
+            rebalancer,
+            archiver,
+        })
+    }
+}
+
+/// A running relayer returned by [`RpcServer::start`]
+pub struct ServerHandle {
+    local_addr: SocketAddr,
+    /// Zeroed once the server stops
+    relayer_key: SecretSlot,
+    server: jsonrpc_http_server::Server,
+    monitor: tokio::task::JoinHandle<()>,
+    scheduler: tokio::task::JoinHandle<()>,
+    sampler: tokio::task::JoinHandle<()>,
+    rebalancer: tokio::task::JoinHandle<()>,
+    archiver: tokio::task::JoinHandle<()>,
+}
+
+impl ServerHandle {
+    /// Address the HTTP server is bound to; reports the assigned port when started on port 0
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop accepting requests, stop the background monitor, scheduler, fee sampler,
+    /// rebalancer and archiver and wait for the HTTP worker threads to exit
+    pub async fn shutdown(self) {
+        tracing::info!("Shutting down JSON-RPC server on {}", self.local_addr);
+        self.monitor.abort();
+        self.scheduler.abort();
+        self.sampler.abort();
+        self.rebalancer.abort();
+        self.archiver.abort();
+        self.relayer_key.wipe();
+        let server = self.server;
+        // Closing tears down the server's own runtime, which must not happen on an async worker
+        let _ = tokio::task::spawn_blocking(move || {
+            server.close_handle().close();
+            server.wait();
+        })
+        .await;
+    }
+
+    /// Serve until the process exits
+    pub async fn wait(self) {
+        let server = self.server;
+        let _ = tokio::task::spawn_blocking(move || server.wait()).await;
+        self.monitor.abort();
+        self.scheduler.abort();
+        self.sampler.abort();
+        self.rebalancer.abort();
+        self.archiver.abort();
+        self.relayer_key.wipe();
+    }
+}
+
+/// Longest wait between monitor passes
+const MONITOR_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Shortest wait between monitor passes, so chains with fast blocks do not keep it busy
+const MONITOR_MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Wait until the next monitor pass is due: [`MONITOR_INTERVAL`] from now, or as soon as a
+/// chain watched over WebSocket announces a block, but no sooner than
+/// [`MONITOR_MIN_INTERVAL`]
+async fn wait_for_monitor_pass(cfg: &Config) {
+    let started = tokio::time::Instant::now();
+    let blocks: Vec<_> = cfg
+        .configured_chain_ids()
+        .into_iter()
+        .filter_map(|chain_id| cfg.ws_url_for_chain(&chain_id.to_string()))
+        .map(|ws_url| {
+            async move {
+                cfg.chain.next_block(&ws_url).await;
+            }
+            .boxed()
+        })
+        .collect();
+    if blocks.is_empty() {
+        sleep(MONITOR_INTERVAL).await;
+        return;
+    }
+    tokio::select! {
+        _ = sleep(MONITOR_INTERVAL) => {}
+        _ = select_all(blocks) => {}
+    }
+    tokio::time::sleep_until(started + MONITOR_MIN_INTERVAL).await;
+}
+
+/// In-flight requests the monitor checks at once on a single chain. Chains are polled
+/// independently, so a slow endpoint only delays its own requests.
+const MONITOR_CONCURRENCY_PER_CHAIN: usize = 8;
+
+/// What one monitor pass did with an in-flight request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MonitorOutcome {
+    /// Receipt found; the request is now Completed or Failed
+    Finalized,
+    /// Still unmined; rebroadcast with a bumped gas price
+    Resubmitted,
+    /// Still unmined and the rebroadcast failed; the request is now Failed
+    ResubmitFailed,
+    /// Still unmined and left untouched
+    Pending,
+}
+
+/// Outcome counts across one monitor pass
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct MonitorSummary {
+    finalized: usize,
+    resubmitted: usize,
+    failed: usize,
+    pending: usize,
+}
+
+/// Check every pending or processing request that has a transaction hash, fanning out
+/// per chain with at most [`MONITOR_CONCURRENCY_PER_CHAIN`] receipt checks in flight each.
+async fn poll_in_flight(
+    storage: &Storage,
+    cfg: &Config,
+    requests: Vec<RelayerRequest>,
+) -> MonitorSummary {
+    let mut by_chain: BTreeMap<u64, Vec<(RelayerRequest, String)>> = BTreeMap::new();
+    for req in requests {
+        if !matches!(
+            req.status,
+            RequestStatus::Pending | RequestStatus::Processing
+        ) {
+            continue;
+        }
+        if let Some(tx_hash) = req.transaction_hash.clone() {
+            by_chain
+                .entry(req.chain_id)
+                .or_default()
+                .push((req, tx_hash));
+        }
+    }
+
+    let chains = by_chain.into_iter().map(|(chain_id, requests)| async move {
+        let receipts = lookup_receipts(cfg, chain_id, &requests).await;
+        stream::iter(requests.into_iter().zip(receipts))
+            .map(|((req, tx_hash), receipt)| async move {
+                check_in_flight(storage, cfg, &req, &tx_hash, receipt).await
+            })
+            .buffer_unordered(MONITOR_CONCURRENCY_PER_CHAIN)
+            .collect::<Vec<_>>()
+            .await
+    });
+
+    let mut summary = MonitorSummary::default();
+    for outcome in join_all(chains).await.into_iter().flatten() {
+        match outcome {
+            MonitorOutcome::Finalized => summary.finalized += 1,
+            MonitorOutcome::Resubmitted => summary.resubmitted += 1,
+            MonitorOutcome::ResubmitFailed => summary.failed += 1,
+            MonitorOutcome::Pending => summary.pending += 1,
+        }
+    }
+    summary
+}
+
+/// Receipts requested per upstream JSON-RPC batch by the monitor
+const RECEIPT_BATCH_SIZE: usize = 100;
+
+/// Look up the receipts of one chain's in-flight requests, batching the lookups into as few
+/// upstream calls as the endpoint allows. Results line up with `requests`.
+async fn lookup_receipts(
+    cfg: &Config,
+    chain_id: u64,
+    requests: &[(RelayerRequest, String)],
+) -> Vec<Result<Option<TxReceipt>, String>> {
+    let Some(rpc_url) = cfg.monitor_url_for_chain(&chain_id.to_string()) else {
+        return requests.iter().map(|_| Ok(None)).collect();
+    };
+
+    // Hashes that fail to parse cannot be mined; they are reported as still pending
+    let hashes: Vec<Option<B256>> = requests
+        .iter()
+        .map(|(_, tx_hash)| tx_hash.parse::<B256>().ok())
+        .collect();
+    let valid: Vec<B256> = hashes.iter().flatten().copied().collect();
+
+    let mut found = Vec::with_capacity(valid.len());
+    for chunk in valid.chunks(RECEIPT_BATCH_SIZE) {
+        match cfg.chain.receipts(&rpc_url, chunk).await {
+            Ok(receipts) => found.extend(receipts.into_iter().map(Ok)),
+            Err(e) => found.extend(chunk.iter().map(|_| Err(e.clone()))),
+        }
+    }
+
+    let mut found = found.into_iter();
+    hashes
+        .iter()
+        .map(|hash| match hash {
+            Some(_) => found.next().unwrap_or(Ok(None)),
+            None => Ok(None),
+        })
+        .collect()
+}
+
+/// Act on the receipt lookup for an in-flight request and, while it is still unmined,
+/// rebroadcast it at its lane's price bumped by the lane's `bumpPercent`
+async fn check_in_flight(
+    storage: &Storage,
+    cfg: &Config,
+    req: &RelayerRequest,
+    tx_hash: &str,
+    receipt: Result<Option<TxReceipt>, String>,
+) -> MonitorOutcome {
+    if let Some(receipt_status) = apply_receipt(storage, cfg, req, tx_hash, receipt).await {
+        tracing::debug!("Receipt processed for {} => {:?}", req.id, receipt_status);
+        spawn_callback(storage, cfg, req.id);
+        return MonitorOutcome::Finalized;
+    }
+    if let Some(raw) = &req.raw_transaction {
+        return rebroadcast_raw_transaction(storage, cfg, req, raw).await;
+    }
+
+    // A transaction still unmined after this many bumps is not going to be
+    let max_attempts = cfg.resubmission_policy().max_attempts;
+    if req.resubmission_count >= max_attempts {
+        tracing::warn!(
+            "Request {} not mined after {} resubmissions; giving up",
+            req.id,
+            req.resubmission_count
+        );
+        let error = format!("not mined after {} resubmissions", req.resubmission_count);
+        capture_lifecycle_failure(req.id, &format!("Relay transaction {}", error), &error);
+        let _ = storage
+            .update_request_status(req.id, RequestStatus::Failed, Some(error))
+            .await;
+        spawn_callback(storage, cfg, req.id);
+        return MonitorOutcome::ResubmitFailed;
+    }
+
+    let lane_price = lane_gas_price(req.chain_id, req.speed, cfg).await;
+    let (Ok(price_hex), Some(_)) = (lane_price, &req.data) else {
+        return MonitorOutcome::Pending;
+    };
+    let lane = cfg.speed_lane(&req.chain_id.to_string(), req.speed);
+    let bumped = bump_gas_price_hex(&price_hex, lane.bump_percent);
+    match resubmit_request(storage, cfg, req, &bumped).await {
+        Ok(_) => MonitorOutcome::Resubmitted,
+        Err(e) => {
+            capture_lifecycle_failure(
+                req.id,
+                &format!("Failed to resubmit relay transaction: {}", e),
+                &e,
+            );
+            let _ = storage
+                .update_request_status(req.id, RequestStatus::Failed, Some(e))
+                .await;
+            spawn_callback(storage, cfg, req.id);
+            MonitorOutcome::ResubmitFailed
+        }
+    }
+}
+
+/// Rebroadcast an unmined raw transaction unchanged, as the relayer cannot re-sign it. Once
+/// the sender's nonce has moved past it, a transaction the relayer never saw took the nonce:
+/// the request is finalized by whichever of its own hashes mined, or failed as replaced.
+async fn rebroadcast_raw_transaction(
+    storage: &Storage,
+    cfg: &Config,
+    req: &RelayerRequest,
+    raw: &str,
+) -> MonitorOutcome {
+    let chain_id = req.chain_id.to_string();
+    let (Some(rpc_url), Ok(sender)) = (
+        cfg.rpc_url_for_chain(&chain_id),
+        req.from_address.parse::<Address>(),
+    ) else {
+        return MonitorOutcome::Pending;
+    };
+    match cfg.chain.transaction_count(&rpc_url, sender).await {
+        Ok(next_nonce) if next_nonce > req.nonce => {
+            // An earlier hash of the request may be the one that mined
+            let hashes = storage
+                .get_transaction_hashes(req.id)
+                .await
+                .unwrap_or_default();
+            let mut finalized = false;
+            for tx_hash in hashes.iter().rev() {
+                let receipt = match tx_hash.parse::<B256>() {
+                    Ok(hash) => cfg.chain.receipt(&rpc_url, hash).await,
+                    Err(_) => Ok(None),
+                };
+                if apply_receipt(storage, cfg, req, tx_hash, receipt)
+                    .await
+                    .is_some()
+                {
+                    finalized = true;
+                    break;
+                }
+            }
+            if !finalized {
+                tracing::warn!(
+                    "Nonce {} of {} was used by another transaction; request {} replaced",
+                    req.nonce,
+                    req.from_address,
+                    req.id
+                );
+                let _ = storage
+                    .update_request_status(
+                        req.id,
+                        RequestStatus::Failed,
+                        Some(format!(
+                            "nonce {} was used by another transaction",
+                            req.nonce
+                        )),
+                    )
+                    .await;
+            }
+            spawn_callback(storage, cfg, req.id);
+            return MonitorOutcome::Finalized;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::debug!("Failed to read nonce of {}: {}", req.from_address, e);
+            return MonitorOutcome::Pending;
+        }
+    }
+
+    let broadcast_url = if req.private_mempool {
+        cfg.private_rpc_url(&chain_id)
+    } else {
+        Some(rpc_url)
+    };
+    if let (Some(url), Ok(raw)) = (broadcast_url, raw.parse::<Bytes>()) {
+        // Nodes still holding the transaction answer "already known"
+        if let Err(e) = cfg.chain.send_raw_transaction(&url, raw).await {
+            tracing::debug!("Rebroadcast of request {} not accepted: {}", req.id, e);
+        }
+    }
+    MonitorOutcome::Pending
+}
+
+/// Rebroadcast an in-flight request at `gas_price_hex`, replacing its pending transaction,
+/// and record the new hash together with a resubmission entry in one write batch.
+/// Returns the replacement's hash; the stored request is left untouched if the send fails.
+async fn resubmit_request(
+    storage: &Storage,
+    cfg: &Config,
+    req: &RelayerRequest,
+    gas_price_hex: &str,
+) -> Result<String, String> {
+    let data = req
+        .data
+        .as_deref()
+        .ok_or_else(|| "request has no calldata to resubmit".to_string())?;
+
+    // Nodes only replace a blob transaction when both its gas and blob gas price caps at
+    // least double
+    let mut gas_price_hex = gas_price_hex.to_string();
+    let blobs = match &req.blob_sidecar {
+        Some(sidecar) => {
+            let doubled = bump_gas_price_hex(&req.gas_price, 100);
+            if parse_hex_u256(&doubled) > parse_hex_u256(&gas_price_hex) {
+                gas_price_hex = doubled;
+            }
+            let previous = req
+                .max_fee_per_blob_gas
+                .as_deref()
+                .and_then(parse_hex_u256)
+                .and_then(|fee| u128::try_from(fee).ok())
+                .unwrap_or_default();
+            let current = fetch_max_fee_per_blob_gas(req.chain_id, cfg).await?;
+            Some((sidecar, current.max(previous.saturating_mul(2))))
+        }
+        None => None,
+    };
+    let (new_tx_hash, _) = send_relay_transaction(
+        &req.to_address,
+        data,
+        req.chain_id,
+        req.gas_limit,
+        &gas_price_hex,
+        SendOptions {
+            replaces: Some(req.nonce),
+            blobs,
+            value: req.amount.parse().unwrap_or_default(),
+        },
+        cfg,
+    )
+    .await?;
+    lifecycle_breadcrumb(
+        req.id,
+        "bumped",
+        format!(
+            "rebroadcast {} as {} at gas price {} (resubmission {})",
+            req.transaction_hash.as_deref().unwrap_or_default(),
+            new_tx_hash,
+            gas_price_hex,
+            req.resubmission_count.saturating_add(1)
+        ),
+    );
+
+    let mut updated = req.clone();
+    updated.transaction_hash = Some(new_tx_hash.clone());
+    updated.gas_price = gas_price_hex;
+    updated.max_fee_per_blob_gas = blobs.map(|(_, max_fee)| format!("0x{:x}", max_fee));
+    updated.status = RequestStatus::Processing;
+    updated.error_message = None;
+    updated.updated_at = Utc::now();
+    updated.resubmission_count = req.resubmission_count.saturating_add(1);
+    let resubmission = Resubmission {
+        status: 201,
+        transaction_hash: new_tx_hash.clone(),
+        chain_id: req.chain_id.to_string(),
+    };
+
+    let mut batch = StorageBatch::new();
+    let recorded = batch
+        .put_request(&updated)
+        .and_then(|()| batch.add_resubmission(req.id, &resubmission));
+    let stored = match recorded {
+        Ok(()) => storage.commit(batch).await,
+        Err(e) => Err(e),
+    };
+    match stored {
+        Ok(()) => compact_resubmissions(storage, cfg, req.id).await,
+        Err(e) => tracing::error!("Failed to record resubmission for {}: {}", req.id, e),
+    }
+    Ok(new_tx_hash)
+}
+
+/// Trim a request's resubmission records to its first and the configured number of latest
+async fn compact_resubmissions(storage: &Storage, cfg: &Config, request_id: Uuid) {
+    let keep_last = cfg.resubmission_policy().keep_last;
+    if let Err(e) = storage.compact_resubmissions(request_id, keep_last).await {
+        tracing::warn!("Failed to compact resubmissions of {}: {}", request_id, e);
+    }
+}
+
+/// Consecutive monitor passes a nonce gap must be seen on before it is filled, so a nonce
+/// reserved by a relay that is still being broadcast is not mistaken for a dropped one
+const NONCE_GAP_PASSES: u32 = 2;
+
+/// Gaps seen on earlier monitor passes per RPC URL and relayer: the stalled nonce and the
+/// number of consecutive passes it has been stalled for
+type NonceGapSuspects = HashMap<(String, Address), (u64, u32)>;
+
+/// Check the relayer key of every configured chain for a nonce gap and fill those that have
+/// persisted for [`NONCE_GAP_PASSES`] passes. Returns how many gaps were filled.
+async fn heal_nonce_gaps(
+    storage: &Storage,
+    cfg: &Config,
+    suspects: &mut NonceGapSuspects,
+) -> usize {
+    let mut healed = 0;
+    for chain_id in cfg.configured_chain_ids() {
+        let Some(rpc_url) = cfg.rpc_url_for_chain(&chain_id.to_string()) else {
+            continue;
+        };
+        let Some(Ok(signer)) = cfg.relayer_signer_for_chain(&chain_id.to_string()) else {
+            continue;
+        };
+        if heal_nonce_gap(storage, cfg, chain_id, &rpc_url, &signer, suspects)
+            .await
+            .is_some()
+        {
+            healed += 1;
+        }
+    }
+    healed
+}
+
+/// Detect a nonce gap for `signer` on one chain and fill it with a zero-value self-transfer.
+///
+/// A gap is a nonce the relayer has handed out past the node's transaction count while the
+/// node has nothing executable at that count: the transaction using it was dropped or never
+/// arrived, and every later one is queued behind it. Once the same stalled nonce has been
+/// seen on [`NONCE_GAP_PASSES`] passes it is filled at the node's gas price plus the `fast`
+/// lane's bump, and the healing is logged and recorded.
+async fn heal_nonce_gap(
+    storage: &Storage,
+    cfg: &Config,
+    chain_id: u64,
+    rpc_url: &str,
+    signer: &RelayerSigner,
+    suspects: &mut NonceGapSuspects,
+) -> Option<NonceHealing> {
+    let relayer = signer.address();
+    let key = (rpc_url.to_string(), relayer);
+    // Only nonces reserved since startup can have been lost by this process
+    let Some(next) = cfg.chain.tracked_nonce(rpc_url, relayer).await else {
+        suspects.remove(&key);
+        return None;
+    };
+    let (mined, pending) = match (
+        cfg.chain.transaction_count(rpc_url, relayer).await,
+        cfg.chain.pending_transaction_count(rpc_url, relayer).await,
+    ) {
+        (Ok(mined), Ok(pending)) => (mined, pending),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::debug!("Nonce gap check skipped on chain {}: {}", chain_id, e);
+            return None;
+        }
+    };
+    if next <= mined || pending > mined {
+        suspects.remove(&key);
+        return None;
+    }
+
+    let passes = match suspects.get(&key) {
+        Some(&(nonce, passes)) if nonce == mined => passes + 1,
+        _ => 1,
+    };
+    if passes < NONCE_GAP_PASSES {
+        suspects.insert(key, (mined, passes));
+        return None;
+    }
+    suspects.remove(&key);
+
+    tracing::warn!(
+        "Nonce gap on chain {}: relayer 0x{:x} has reserved nonces up to {} but nothing is pending at {}; filling it",
+        chain_id,
+        relayer,
+        next - 1,
+        mined
+    );
+    let bump = cfg
+        .speed_lane(&chain_id.to_string(), Speed::Fast)
+        .bump_percent;
+    let gas_price = match cfg.chain.gas_price(rpc_url).await {
+        Ok(price) => price + price * bump as u128 / 100,
+        Err(e) => {
+            tracing::warn!("Nonce gap on chain {} left unfilled: {}", chain_id, e);
+            return None;
+        }
+    };
+    let mut tx = TransactionRequest::default()
+        .to(relayer)
+        .value(U256::ZERO)
+        .gas_limit(21_000);
+    tx.nonce = Some(mined);
+    tx.chain_id = Some(chain_id);
+    set_gas_fees(&mut tx, chain_id, rpc_url, gas_price, false, cfg).await;
+
+    let tx_hash = match cfg.chain.send_transaction(rpc_url, signer, tx).await {
+        Ok(hash) => format!("0x{:x}", hash),
+        Err(e) => {
+            let msg = format!(
+                "Failed to fill nonce gap {} on chain {}: {}",
+                mined, chain_id, e
+            );
+            tracing::error!("{}", msg);
+            sentry::capture_message(&msg, sentry::Level::Error);
+            return None;
+        }
+    };
+    let healing = NonceHealing {
+        chain_id,
+        relayer: format!("0x{:x}", relayer),
+        nonce: mined,
+        transaction_hash: tx_hash,
+        gas_price: format!("0x{:x}", gas_price),
+        healed_at: Utc::now(),
+    };
+    tracing::warn!(
+        chain_id,
+        relayer = %healing.relayer,
+        nonce = healing.nonce,
+        tx_hash = %healing.transaction_hash,
+        gas_price = %healing.gas_price,
+        "Nonce gap filled with a self-transfer"
+    );
+    sentry::capture_message(
+        &format!(
+            "Filled nonce gap {} for relayer {} on chain {}",
+            healing.nonce, healing.relayer, chain_id
+        ),
+        sentry::Level::Warning,
+    );
+    if let Err(e) = storage.store_nonce_healing(&healing).await {
+        tracing::error!(
+            "Failed to record nonce healing on chain {}: {}",
+            chain_id,
+            e
+        );
+    }
+    Some(healing)
+}
+
+/// Gas of a plain native transfer
+const TRANSFER_GAS: u64 = 21_000;
+
+/// Top up the keys of every chain with a `rebalancer` entry from its overfunded keys.
+/// Returns the number of transfers broadcast.
+async fn rebalance_keys(cfg: &Config) -> usize {
+    let chains = cfg
+        .configured_chain_ids()
+        .into_iter()
+        .filter_map(|chain_id| Some((chain_id, cfg.rebalance_policy(&chain_id.to_string())?)))
+        .map(|(chain_id, policy)| async move { rebalance_chain(cfg, chain_id, &policy).await });
+    join_all(chains).await.into_iter().sum()
+}
+
+/// The chain's relayer key followed by the keys in `policy.key_refs`, without duplicates.
+/// References to unset or invalid keys are skipped with a warning.
+fn rebalance_signers(cfg: &Config, chain_id: u64, policy: &RebalancePolicy) -> Vec<RelayerSigner> {
+    let relayer_key = cfg
+        .relayer_signer_for_chain(&chain_id.to_string())
+        .map(|signer| ("relayer key".to_string(), signer));
+    let referenced =
+        policy
+            .key_refs
+            .iter()
+            .filter_map(|key_ref| match cfg.signer_for_key_ref(key_ref) {
+                Some(signer) => Some((key_ref.clone(), signer)),
+                None => {
+                    tracing::warn!(
+                        "Rebalancer key reference {} for chain {} is not set",
+                        key_ref,
+                        chain_id
+                    );
+                    None
+                }
             });
+
+    let mut signers: Vec<RelayerSigner> = Vec::new();
+    for (name, signer) in relayer_key.into_iter().chain(referenced) {
+        match signer {
+            Ok(signer) if signers.iter().all(|s| s.address() != signer.address()) => {
+                signers.push(signer)
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(
+                "Invalid rebalancer key {} for chain {}: {}",
+                name,
+                chain_id,
+                e
+            ),
+        }
+    }
+    signers
+}
+
+/// Bring every key of one chain that is below `policy.min_balance` up to
+/// `policy.target_balance`, funded by keys holding more than the target. Returns the number
+/// of transfers broadcast.
+async fn rebalance_chain(cfg: &Config, chain_id: u64, policy: &RebalancePolicy) -> usize {
+    let Some(rpc_url) = cfg.rpc_url_for_chain(&chain_id.to_string()) else {
+        return 0;
+    };
+    let signers = rebalance_signers(cfg, chain_id, policy);
+    if signers.len() < 2 {
+        return 0;
+    }
+
+    let mut balances = Vec::with_capacity(signers.len());
+    for signer in &signers {
+        match cfg.chain.balance(&rpc_url, signer.address()).await {
+            Ok(balance) => balances.push(u128::try_from(balance).unwrap_or(u128::MAX)),
+            Err(e) => {
+                tracing::warn!("Rebalancer skipped chain {}: {}", chain_id, e);
+                return 0;
+            }
+        }
+    }
+    let gas_price = match cfg.chain.gas_price(&rpc_url).await {
+        Ok(price) => price,
+        Err(e) => {
+            tracing::warn!("Rebalancer skipped chain {}: {}", chain_id, e);
+            return 0;
         }
+    };
+    let fee = gas_price.saturating_mul(TRANSFER_GAS as u128);
 
-        // Keep the server running
-        tracing::info!("Server is ready and waiting for requests");
-        server.wait();
+    let mut sent = 0;
+    for (from, to, amount) in plan_rebalance(&balances, policy, fee) {
+        let (donor, recipient) = (&signers[from], signers[to].address());
+        let nonce = match cfg.chain.reserve_nonce(&rpc_url, donor.address()).await {
+            Ok(nonce) => nonce,
+            Err(e) => {
+                tracing::warn!("Rebalancer skipped a transfer on chain {}: {}", chain_id, e);
+                continue;
+            }
+        };
+        let mut tx = TransactionRequest::default()
+            .to(recipient)
+            .value(U256::from(amount))
+            .gas_limit(TRANSFER_GAS);
+        tx.nonce = Some(nonce);
+        tx.chain_id = Some(chain_id);
+        set_gas_fees(&mut tx, chain_id, &rpc_url, gas_price, false, cfg).await;
+
+        match cfg.chain.send_transaction(&rpc_url, donor, tx).await {
+            Ok(hash) => {
+                sent += 1;
+                tracing::info!(
+                    chain_id,
+                    from = %format!("0x{:x}", donor.address()),
+                    to = %format!("0x{:x}", recipient),
+                    amount_wei = %amount,
+                    tx_hash = %format!("0x{:x}", hash),
+                    "Rebalanced relayer key"
+                );
+            }
+            Err(e) => {
+                cfg.chain.resync_nonce(&rpc_url, donor.address()).await;
+                tracing::error!(
+                    "Rebalance transfer of {} wei to 0x{:x} on chain {} failed: {}",
+                    amount,
+                    recipient,
+                    chain_id,
+                    e
+                );
+            }
+        }
+    }
+    sent
+}
 
-        Ok(())
+/// Transfers `(from, to, amount)` between the keys with `balances` that bring every key
+/// below `policy.min_balance` up to `policy.target_balance`, lowest balance first. Donors
+/// only give what they hold above the target after paying `fee` for each transfer, so a
+/// key is never drained to fund another.
+fn plan_rebalance(
+    balances: &[u128],
+    policy: &RebalancePolicy,
+    fee: u128,
+) -> Vec<(usize, usize, u128)> {
+    let mut balances = balances.to_vec();
+    let mut needy: Vec<usize> = (0..balances.len())
+        .filter(|&i| balances[i] < policy.min_balance)
+        .collect();
+    needy.sort_by_key(|&i| balances[i]);
+
+    let mut transfers = Vec::new();
+    for to in needy {
+        let mut need = policy.target_balance - balances[to];
+        while need > 0 {
+            let donor = (0..balances.len())
+                .filter(|&i| i != to)
+                .map(|i| {
+                    let surplus = balances[i]
+                        .saturating_sub(policy.target_balance)
+                        .saturating_sub(fee);
+                    (i, surplus)
+                })
+                .filter(|(_, surplus)| *surplus > 0)
+                .max_by_key(|(_, surplus)| *surplus);
+            let Some((from, surplus)) = donor else {
+                break;
+            };
+            let amount = need.min(surplus);
+            balances[from] -= amount + fee;
+            balances[to] += amount;
+            need -= amount;
+            transfers.push((from, to, amount));
+        }
     }
+    transfers
 }
 
-/// Fetch transaction receipt and update storage status accordingly
-async fn fetch_and_update_receipt(
-    storage: &Storage,
+/// Why `tx_hash`, broadcast for `req` and mined in `block_number`, reverted: its revert data,
+/// recovered by replaying it at that block, decoded against the wallet ABI. A replay that fails
+/// or no longer reverts leaves the data empty and a generic message.
+async fn onchain_failure(
     cfg: &Config,
     req: &RelayerRequest,
     tx_hash: &str,
-) -> Option<RequestStatus> {
-    if stub_mode_enabled() {
-        let _ = storage
-            .update_request_status(req.id, RequestStatus::Completed, None)
-            .await;
-        return Some(RequestStatus::Completed);
-    }
-    let rpc_url = match cfg.rpc_url_for_chain(&req.chain_id.to_string()) {
-        Some(u) => u,
-        None => return None,
+    block_number: Option<u64>,
+) -> OnchainFailure {
+    let rpc_url = cfg.rpc_url_for_chain(&req.chain_id.to_string());
+    let data = match (rpc_url, params::hash(tx_hash), block_number) {
+        (Some(rpc_url), Ok(hash), Some(block_number)) => cfg
+            .chain
+            .replay_transaction(&rpc_url, hash, block_number)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to replay reverted {}: {}", tx_hash, e);
+                Bytes::new()
+            }),
+        _ => Bytes::new(),
     };
+    OnchainFailure {
+        transaction_hash: tx_hash.to_string(),
+        chain_id: req.chain_id.to_string(),
+        message: decode_revert_reason(&data, cfg)
+            .unwrap_or_else(|| "execution reverted".to_string()),
+        data: data.to_string(),
+    }
+}
 
-    let provider = ProviderBuilder::new().on_hyper_http(Url::parse(&rpc_url).ok()?);
+/// What a relay charged `priced` wei was overcharged when its gas cost `spent` wei: the
+/// difference, once it is at least `threshold_percent` of the price
+fn overpayment(priced: U256, spent: U256, threshold_percent: u64) -> Option<U256> {
+    let overpaid = priced
+        .checked_sub(spent)
+        .filter(|overpaid| !overpaid.is_zero())?;
+    (overpaid.saturating_mul(U256::from(100))
+        >= priced.saturating_mul(U256::from(threshold_percent)))
+    .then_some(overpaid)
+}
 
-    // alloy's get_transaction_receipt expects a TxHash; parse hex string
-    let hash = match tx_hash.strip_prefix("0x") {
-        Some(s) => s,
-        None => tx_hash,
+/// Record the overpayment of a relay whose gas came in well below the price it was charged.
+/// Sponsored relays charged nothing; chains with an L1 fee model are skipped because the
+/// receipt leaves out the L1 data fee the price covered.
+async fn record_overpayment(
+    storage: &Storage,
+    cfg: &Config,
+    req: &RelayerRequest,
+    gas_spent: u128,
+    receipt: &Receipt,
+) {
+    let Some(fee) = req
+        .fee
+        .as_ref()
+        .filter(|fee| fee.payment_type != "sponsored")
+    else {
+        return;
     };
-
-    let hash_bytes = hex::decode(hash).ok()?;
-    if hash_bytes.len() != 32 {
-        return None;
+    if req.raw_transaction.is_some() || cfg.fee_model(&req.chain_id.to_string()).is_some() {
+        return;
+    }
+    let Ok(priced) = fee.priced_wei.parse::<U256>() else {
+        return;
+    };
+    let spent = U256::from(gas_spent);
+    let Some(overpaid) = overpayment(priced, spent, cfg.refund_threshold_percent()) else {
+        return;
+    };
+    let refund = match fee.payment_type.as_str() {
+        "erc20" | "permit" => {
+            collected_tokens(receipt, &fee.token, &req.from_address).saturating_mul(overpaid)
+                / priced
+        }
+        _ => overpaid,
+    };
+    let overpayment = Overpayment {
+        request_id: req.id,
+        chain_id: req.chain_id,
+        wallet: req.to_address.clone(),
+        transaction_hash: receipt.transaction_hash.clone(),
+        payment_type: fee.payment_type.clone(),
+        token: fee.token.clone(),
+        priced_wei: priced.to_string(),
+        spent_wei: spent.to_string(),
+        overpaid_wei: overpaid.to_string(),
+        refund_amount: refund.to_string(),
+        recorded_at: Utc::now(),
+    };
+    tracing::info!(
+        "Relay {} overpaid by {} wei ({} wei priced, {} wei spent)",
+        req.id,
+        overpaid,
+        priced,
+        spent
+    );
+    if let Err(e) = storage.store_overpayment(&overpayment).await {
+        tracing::warn!("Failed to record overpayment of {}: {}", req.id, e);
     }
-    let mut arr = [0u8; 32];
-    arr.copy_from_slice(&hash_bytes);
-    let txh = alloy::primitives::B256::from(arr);
+}
 
-    match provider.get_transaction_receipt(txh).await {
+/// Record a receipt lookup for an in-flight request, moving it to Completed or Failed once
+/// the transaction is mined. Returns the new status, or `None` while it is still pending.
+async fn apply_receipt(
+    storage: &Storage,
+    cfg: &Config,
+    req: &RelayerRequest,
+    tx_hash: &str,
+    receipt: Result<Option<TxReceipt>, String>,
+) -> Option<RequestStatus> {
+    match receipt {
         Ok(Some(rcpt)) => {
             // status: true = success, false = fail
-            let status_val = rcpt.status();
+            let status_val = rcpt.success;
             let status_label = if status_val { "success" } else { "failed" };
+            let blob_gas = rcpt
+                .blob_gas_used
+                .and_then(|used| u64::try_from(used).ok())
+                .zip(rcpt.blob_gas_price);
             tracing::info!(
                 "Transaction receipt received - ID: {}, tx hash: {}, status: {}",
                 req.id,
                 tx_hash,
                 status_label
             );
+            lifecycle_breadcrumb(
+                req.id,
+                "receipt",
+                format!(
+                    "{} mined in block {} with status {}",
+                    tx_hash,
+                    rcpt.block_number.unwrap_or_default(),
+                    status_label
+                ),
+            );
+            let formatted = format_receipt(&rcpt, tx_hash, req.chain_id, cfg);
+            if let Err(e) = storage.store_receipt(req.id, &formatted).await {
+                tracing::warn!("Failed to store receipt of {}: {}", req.id, e);
+            }
+            // The sender of a raw transaction paid for its gas, not the relayer
+            let (gas_used, gas_spent) = match &req.raw_transaction {
+                Some(_) => (0, 0),
+                None => (
+                    u64::try_from(rcpt.gas_used).unwrap_or(u64::MAX),
+                    rcpt.gas_used
+                        .saturating_mul(rcpt.effective_gas_price)
+                        .saturating_add(
+                            blob_gas
+                                .map_or(0, |(used, price)| u128::from(used).saturating_mul(price)),
+                        ),
+                ),
+            };
+            if let Err(e) = storage.record_relay_mined(gas_used, gas_spent).await {
+                tracing::warn!("Failed to count mined relay {}: {}", req.id, e);
+            }
+            record_overpayment(storage, cfg, req, gas_spent, &formatted).await;
             if status_val {
+                end_lifecycle(req.id);
                 let _ = storage
-                    .update_request_status(req.id, RequestStatus::Completed, None)
+                    .record_mined(req.id, RequestStatus::Completed, None, blob_gas)
                     .await;
                 Some(RequestStatus::Completed)
             } else {
@@ -2322,11 +11085,21 @@ async fn fetch_and_update_receipt(
                     req.id,
                     tx_hash
                 );
+                let failure = onchain_failure(cfg, req, tx_hash, rcpt.block_number).await;
+                capture_lifecycle_failure(
+                    req.id,
+                    &format!("Relay transaction reverted on-chain: {}", failure.message),
+                    &failure.data,
+                );
+                if let Err(e) = storage.store_onchain_failure(req.id, &failure).await {
+                    tracing::warn!("Failed to store on-chain failure of {}: {}", req.id, e);
+                }
                 let _ = storage
-                    .update_request_status(
+                    .record_mined(
                         req.id,
                         RequestStatus::Failed,
                         Some("onchain revert".to_string()),
+                        blob_gas,
                     )
                     .await;
                 Some(RequestStatus::Failed)
@@ -2350,80 +11123,710 @@ async fn fetch_and_update_receipt(
             None
         }
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::{ChainBackend, ChainClient, MockChainClient};
+    use crate::types::{
+        FeeBounds, GasOracle, MultichainTransaction, PaymentCapability, SendTransactionCapabilities,
+    };
+    use alloy::signers::local::PrivateKeySigner;
+    use tempfile::tempdir;
+
+    fn test_config() -> Config {
+        Config {
+            rpc_host: "127.0.0.1".to_string(),
+            rpc_port: 8545,
+            db_path: std::path::PathBuf::from("./relayx_db_test"),
+            relayers: "".to_string(),
+            max_concurrent_requests: 100,
+            request_timeout: 30,
+            config_path: None,
+            http_address: "127.0.0.1".to_string(),
+            http_port: 4937,
+            http_cors: "*".to_string(),
+            log_level: "debug".to_string(),
+            relayer_private_key: None,
+            relayer_private_key_file: None,
+            disable_simulation: false,
+            sentry_dsn: None,
+            admin_token: None,
+            callback_secret: None,
+            callback_allow_private_hosts: false,
+            gas_price_ttl_ms: 3000,
+            chain: ChainBackend::new(MockChainClient::default()),
+            encryption: Default::default(),
+            relayer_key_from_file: Default::default(),
+        }
+    }
+
+    async fn test_storage() -> Storage {
+        let dir = tempdir().unwrap();
+        Storage::new(dir.path()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_capabilities_contains_native_and_sponsored() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let resp =
+            super::process_get_capabilities(storage, &GetCapabilitiesRequest::default(), &cfg)
+                .await
+                .unwrap();
+        let mut has_native = false;
+        let mut has_sponsored = false;
+        for p in resp.capabilities.payment {
+            match p {
+                Payment::Native(_) => has_native = true,
+                Payment::Sponsored(_) => has_sponsored = true,
+                _ => {}
+            }
+        }
+        assert!(has_native && has_sponsored);
+    }
+
+    #[tokio::test]
+    async fn test_get_capabilities_unsupported_chain_filter() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let req = GetCapabilitiesRequest {
+            chain_id: Some("999999".to_string()),
+        };
+        let err = super::process_get_capabilities(storage, &req, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    }
+
+    #[tokio::test]
+    async fn test_get_supported_chains_without_config_is_empty() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let resp = super::process_get_supported_chains(storage, &cfg)
+            .await
+            .unwrap();
+        assert!(resp.chains.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_admin_chain_requires_token() {
+        let storage = test_storage().await;
+        let mut cfg = test_config();
+        let req = AddChainRequest {
+            admin_token: "secret".to_string(),
+            chain: crate::types::ChainRegistration {
+                chain_id: "777001".to_string(),
+                rpc_url: "http://127.0.0.1:1".to_string(),
+                tokens: Default::default(),
+                native_usd_feed: None,
+                relayer_key_ref: None,
+                dex_quoter: None,
+                tx_type: None,
+                private_rpc_url: None,
+                event_abis: Default::default(),
+            },
+        };
+
+        // Admin API disabled without a configured token
+        let err = super::process_add_chain(storage.clone(), &req, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::ServerError(-4100));
+
+        // Wrong token rejected
+        cfg.admin_token = Some("other".to_string());
+        let err = super::process_add_chain(storage.clone(), &req, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::ServerError(-4100));
+
+        // Removing an unknown chain is rejected once authorized
+        cfg.admin_token = Some("secret".to_string());
+        let remove = RemoveChainRequest {
+            admin_token: "secret".to_string(),
+            chain_id: "777002".to_string(),
+        };
+        let err = super::process_remove_chain(storage, &remove, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    }
+
+    #[tokio::test]
+    async fn test_export_accounting_filters_by_completion_time() {
+        let storage = test_storage().await;
+        let mut cfg = test_config();
+        let request = |status: RequestStatus, mined_at: &str| RelayerRequest {
+            id: Uuid::new_v4(),
+            from_address: "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
+            to_address: "0x0987654321098765432109876543210987654321".to_string(),
+            amount: "0".to_string(),
+            gas_limit: 21000,
+            gas_price: "0x4a817c800".to_string(),
+            data: Some("0x".to_string()),
+            nonce: 0,
+            chain_id: 1,
+            transaction_hash: Some(format!("0x{}", "ab".repeat(32))),
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: Some(mined_at.parse().unwrap()),
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: Some(RelayFee {
+                payment_type: "native".to_string(),
+                token: "0x0000000000000000000000000000000000000000".to_string(),
+                priced_wei: "420000000000000".to_string(),
+            }),
+            resubmission_count: 0,
+            tenant: None,
+        };
+        let in_range = request(RequestStatus::Completed, "2026-03-01T10:00:00Z");
+        let reverted = request(RequestStatus::Failed, "2026-03-01T23:59:59Z");
+        let next_day = request(RequestStatus::Completed, "2026-03-02T00:00:00Z");
+        for req in [&in_range, &reverted, &next_day] {
+            storage.create_request(req.clone()).await.unwrap();
+        }
+        let mut export = ExportAccountingRequest {
+            admin_token: "secret".to_string(),
+            from: Some("2026-03-01".to_string()),
+            to: Some("2026-03-02".to_string()),
+            format: None,
+        };
+
+        let err = super::process_export_accounting(storage.clone(), &export, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::ServerError(-4100));
+
+        cfg.admin_token = Some("secret".to_string());
+        let resp = super::process_export_accounting(storage.clone(), &export, &cfg)
+            .await
+            .unwrap();
+        assert_eq!(resp.format, "csv");
+        assert_eq!(resp.rows, 2);
+        let lines: Vec<&str> = resp.data.lines().collect();
+        assert!(lines[0].starts_with("id,chain_id,status,"));
+        assert!(lines[1].starts_with(&format!("{},1,completed,", in_range.id)));
+        assert!(lines[2].starts_with(&format!("{},1,failed,", reverted.id)));
+        assert!(lines[1].contains(",native,0x0000000000000000000000000000000000000000,"));
+        assert!(!resp.data.contains(&next_day.id.to_string()));
+
+        export.format = Some("parquet".to_string());
+        let err = super::process_export_accounting(storage.clone(), &export, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+
+        export.format = None;
+        export.from = Some("yesterday".to_string());
+        let err = super::process_export_accounting(storage, &export, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    }
+
+    #[test]
+    fn test_abi_encode_bytes_tail_pads_to_word() {
+        let encoded = super::abi_encode_bytes_tail(&[0xab; 33]);
+        assert_eq!(encoded.len(), 32 + 64);
+        assert_eq!(super::abi_word(&encoded, 0), Some(U256::from(33)));
+        assert_eq!(encoded[32 + 33..], [0u8; 31]);
+    }
+
+    #[tokio::test]
+    async fn test_l1_data_fee_zero_without_fee_model() {
+        let cfg = test_config();
+        assert_eq!(cfg.fee_model("1"), None);
+        assert_eq!(cfg.fee_model("10").as_deref(), Some("optimism"));
+        assert_eq!(cfg.fee_model("42161").as_deref(), Some("arbitrum"));
+        let fee = super::fetch_l1_data_fee(
+            1,
+            "0x0000000000000000000000000000000000000000",
+            "0x12",
+            &cfg,
+        )
+        .await;
+        assert_eq!(fee, U256::ZERO);
+    }
+
+    #[test]
+    fn test_native_token_info_defaults() {
+        let cfg = test_config();
+        let eth = cfg.native_token_info("1");
+        assert_eq!(eth.symbol.as_deref(), Some("ETH"));
+        assert_eq!(eth.decimals, 18);
+        let pol = cfg.native_token_info("137");
+        assert_eq!(pol.symbol.as_deref(), Some("POL"));
+        assert_eq!(pol.address, "0x0000000000000000000000000000000000000000");
+    }
+
+    #[tokio::test]
+    async fn test_health_check_initial_counts() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let health = super::process_health_check(storage, &cfg).await.unwrap();
+        assert_eq!(health.total_requests, 0);
+        assert_eq!(health.pending_requests, 0);
+        assert_eq!(health.completed_requests, 0);
+        assert_eq!(health.failed_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_status_invalid_id_format() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let req = GetStatusRequest {
+            ids: vec!["not-a-uuid".to_string()],
+            api_key: None,
+        };
+        let resp = super::process_get_status(storage, &req, &cfg)
+            .await
+            .unwrap();
+        assert_eq!(resp.result.len(), 1);
+        assert_eq!(resp.result[0].status, 400);
+    }
+
+    #[tokio::test]
+    async fn test_get_status_by_transaction_hash() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let original = format!("0x{}", "ab".repeat(32));
+        let resubmitted = format!("0x{}", "cd".repeat(32));
+
+        let req = RelayerRequest {
+            id: Uuid::new_v4(),
+            from_address: "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
+            to_address: "0x0987654321098765432109876543210987654321".to_string(),
+            amount: "0".to_string(),
+            gas_limit: 21000,
+            gas_price: "0x4a817c800".to_string(),
+            data: Some("0x".to_string()),
+            nonce: 0,
+            chain_id: 1,
+            transaction_hash: None,
+            status: RequestStatus::Completed,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
+        };
+        storage.create_request(req.clone()).await.unwrap();
+        storage
+            .update_request_tx_hash(req.id, original.clone())
+            .await
+            .unwrap();
+        let resub = Resubmission {
+            status: 201,
+            transaction_hash: resubmitted.clone(),
+            chain_id: "1".to_string(),
+        };
+        storage.add_resubmission(req.id, &resub).await.unwrap();
+
+        let ids = vec![
+            original,
+            resubmitted.to_uppercase().replacen("0X", "0x", 1),
+            format!("0x{}", "ef".repeat(32)),
+        ];
+        let resp =
+            super::process_get_status(storage, &GetStatusRequest { ids, api_key: None }, &cfg)
+                .await
+                .unwrap();
+        assert_eq!(resp.result[0].status, 200);
+        assert_eq!(resp.result[0].id, req.id.to_string());
+        assert_eq!(resp.result[1].status, 200);
+        assert_eq!(resp.result[1].id, req.id.to_string());
+        assert_eq!(resp.result[2].status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_status_codes_follow_configured_scheme() {
+        let storage = test_storage().await;
+        let failed = RelayerRequest {
+            id: Uuid::new_v4(),
+            from_address: "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
+            to_address: "0x0987654321098765432109876543210987654321".to_string(),
+            amount: "0".to_string(),
+            gas_limit: 21000,
+            gas_price: "0x4a817c800".to_string(),
+            data: Some("0x".to_string()),
+            nonce: 0,
+            chain_id: 1,
+            transaction_hash: None,
+            status: RequestStatus::Failed,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            error_message: Some("broadcast failed".to_string()),
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
+        };
+        let reverted = RelayerRequest {
+            id: Uuid::new_v4(),
+            mined_at: Some(Utc::now()),
+            ..failed.clone()
+        };
+        let pending = RelayerRequest {
+            id: Uuid::new_v4(),
+            status: RequestStatus::Pending,
+            ..failed.clone()
+        };
+        for req in [&failed, &reverted, &pending] {
+            storage.create_request(req.clone()).await.unwrap();
+        }
+
+        let status = |id: Uuid, codes: StatusCodes| {
+            let storage = storage.clone();
+            async move {
+                let result =
+                    super::build_status_result(&storage, &id.to_string(), &codes, TenantScope::All)
+                        .await;
+                (result.status, result.status_name.unwrap())
+            }
+        };
+        let http = StatusCodes::default();
+        assert_eq!(status(failed.id, http).await, (500, StatusKind::Failed));
+        assert_eq!(status(reverted.id, http).await, (500, StatusKind::Reverted));
+        assert_eq!(status(pending.id, http).await, (201, StatusKind::Pending));
+        assert_eq!(
+            status(Uuid::new_v4(), http).await,
+            (404, StatusKind::NotFound)
+        );
+
+        let mut eip5792 = StatusCodes::scheme("eip5792").unwrap();
+        assert_eq!(status(failed.id, eip5792).await, (400, StatusKind::Failed));
+        assert_eq!(
+            status(reverted.id, eip5792).await,
+            (500, StatusKind::Reverted)
+        );
+        assert_eq!(
+            status(pending.id, eip5792).await,
+            (100, StatusKind::Pending)
+        );
+        eip5792.set(StatusKind::Pending, 102);
+        assert_eq!(
+            status(pending.id, eip5792).await,
+            (102, StatusKind::Pending)
+        );
+        assert!(StatusCodes::scheme("grpc").is_none());
+    }
+
+    #[test]
+    fn test_simulation_fallback_follows_failure_mode() {
+        use SimulationFailureMode::*;
+        assert!(!super::fallback_allowed(Reject, 990001, true));
+        assert!(super::fallback_allowed(Fallback, 990001, true));
+
+        let capped = FallbackWithCap(2);
+        // Checking without reserving does not use up the cap
+        assert!(super::fallback_allowed(capped, 990002, false));
+        assert!(super::fallback_allowed(capped, 990002, true));
+        assert!(super::fallback_allowed(capped, 990002, true));
+        assert!(!super::fallback_allowed(capped, 990002, false));
+        assert!(!super::fallback_allowed(capped, 990002, true));
+        // The cap is per chain
+        assert!(super::fallback_allowed(capped, 990003, true));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::{PaymentCapability, SendTransactionCapabilities};
-    use tempfile::tempdir;
+    #[test]
+    fn test_tenant_for_key_requires_configured_key() {
+        assert_eq!(
+            super::tenant_for_key(Vec::new(), Some("any")).unwrap(),
+            None
+        );
 
-    fn test_config() -> Config {
-        Config {
-            rpc_host: "127.0.0.1".to_string(),
-            rpc_port: 8545,
-            db_path: std::path::PathBuf::from("./relayx_db_test"),
-            relayers: "".to_string(),
-            max_concurrent_requests: 100,
-            request_timeout: 30,
-            config_path: None,
-            http_address: "127.0.0.1".to_string(),
-            http_port: 4937,
-            http_cors: "*".to_string(),
-            log_level: "debug".to_string(),
-            relayer_private_key: None,
-            disable_simulation: false,
-            sentry_dsn: None,
+        let tenants = || {
+            vec![
+                Tenant {
+                    id: "acme".to_string(),
+                    api_key: "acme-key".to_string(),
+                },
+                Tenant {
+                    id: "globex".to_string(),
+                    api_key: "globex-key".to_string(),
+                },
+            ]
+        };
+        assert_eq!(
+            super::tenant_for_key(tenants(), Some("globex-key")).unwrap(),
+            Some("globex".to_string())
+        );
+        for key in [None, Some(""), Some("acme-kez")] {
+            let err = super::tenant_for_key(tenants(), key).unwrap_err();
+            assert_eq!(err.code, jsonrpc_core::ErrorCode::ServerError(-4100));
         }
     }
 
-    async fn test_storage() -> Storage {
-        let dir = tempdir().unwrap();
-        Storage::new(dir.path()).unwrap()
+    #[tokio::test]
+    async fn test_status_hides_requests_of_other_tenants() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let tx_hash = format!("0x{}", "ab".repeat(32));
+        let mut req: RelayerRequest = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "from_address": "0x55f3a93f544e01ce4378d25e927d7c493b863bd6",
+            "to_address": "0x0987654321098765432109876543210987654321",
+            "amount": "0",
+            "gas_limit": 21000,
+            "gas_price": "0x4a817c800",
+            "nonce": 0,
+            "chain_id": 1,
+            "status": "Completed",
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+        }))
+        .unwrap();
+        req.tenant = Some("acme".to_string());
+        storage.create_request(req.clone()).await.unwrap();
+        storage
+            .update_request_tx_hash(req.id, tx_hash.clone())
+            .await
+            .unwrap();
+
+        let acme = TenantScope::Tenant(Some("acme"));
+        let codes = StatusCodes::default();
+        let found = super::build_status_result(&storage, &tx_hash, &codes, acme).await;
+        assert_eq!(found.status, 200);
+        assert_eq!(found.id, req.id.to_string());
+
+        let globex = TenantScope::Tenant(Some("globex"));
+        let hidden = super::build_status_result(&storage, &tx_hash, &codes, globex).await;
+        assert_eq!(hidden.status, 404);
+        assert_eq!(hidden.id, tx_hash);
+
+        // Without tenants configured callers are untenanted and see none of acme's requests
+        let ids = vec![req.id.to_string()];
+        let resp =
+            super::process_get_status(storage, &GetStatusRequest { ids, api_key: None }, &cfg)
+                .await
+                .unwrap();
+        assert_eq!(resp.result[0].status, 404);
     }
 
     #[tokio::test]
-    async fn test_get_capabilities_contains_native_and_sponsored() {
+    async fn test_get_bundle_status_unknown_and_invalid() {
         let storage = test_storage().await;
         let cfg = test_config();
-        let resp = super::process_get_capabilities(storage, &cfg)
+
+        let req = GetBundleStatusRequest {
+            bundle_id: Uuid::new_v4().to_string(),
+            api_key: None,
+        };
+        let resp = super::process_get_bundle_status(storage.clone(), &req, &cfg)
             .await
             .unwrap();
-        let mut has_native = false;
-        let mut has_sponsored = false;
-        for p in resp.capabilities.payment {
-            match p {
-                Payment::Native(_) => has_native = true,
-                Payment::Sponsored(_) => has_sponsored = true,
-                _ => {}
-            }
-        }
-        assert!(has_native && has_sponsored);
+        assert_eq!(resp.result.status, 404);
+        assert!(resp.result.legs.is_empty());
+
+        let req = GetBundleStatusRequest {
+            bundle_id: "not-a-uuid".to_string(),
+            api_key: None,
+        };
+        let resp = super::process_get_bundle_status(storage, &req, &cfg)
+            .await
+            .unwrap();
+        assert_eq!(resp.result.status, 400);
     }
 
     #[tokio::test]
-    async fn test_health_check_initial_counts() {
+    async fn test_get_bundle_status_aggregates_legs() {
         let storage = test_storage().await;
         let cfg = test_config();
-        let health = super::process_health_check(storage, &cfg).await.unwrap();
-        assert_eq!(health.total_requests, 0);
-        assert_eq!(health.pending_requests, 0);
-        assert_eq!(health.completed_requests, 0);
-        assert_eq!(health.failed_requests, 0);
+
+        let mut request_ids = Vec::new();
+        for status in [RequestStatus::Completed, RequestStatus::Processing] {
+            let req = RelayerRequest {
+                id: Uuid::new_v4(),
+                from_address: "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
+                to_address: "0x0987654321098765432109876543210987654321".to_string(),
+                amount: "0".to_string(),
+                gas_limit: 21000,
+                gas_price: "0x4a817c800".to_string(),
+                data: Some("0x".to_string()),
+                nonce: 0,
+                chain_id: 1,
+                transaction_hash: None,
+                status,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                error_message: None,
+                bundle_id: None,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
+            };
+            request_ids.push(req.id);
+            storage.create_request(req).await.unwrap();
+        }
+
+        let bundle = Bundle {
+            id: Uuid::new_v4(),
+            payment_chain_id: 1,
+            request_ids: request_ids.clone(),
+            created_at: Utc::now(),
+        };
+        storage.store_bundle(&bundle).await.unwrap();
+
+        let req = GetBundleStatusRequest {
+            bundle_id: bundle.id.to_string(),
+            api_key: None,
+        };
+        let resp = super::process_get_bundle_status(storage.clone(), &req, &cfg)
+            .await
+            .unwrap();
+        assert_eq!(resp.result.legs.len(), 2);
+        assert_eq!(resp.result.status, 201);
+        assert!(!resp.result.all_mined);
+        assert!(!resp.result.any_failed);
+
+        storage
+            .update_request_status(
+                request_ids[1],
+                RequestStatus::Failed,
+                Some("onchain revert".to_string()),
+            )
+            .await
+            .unwrap();
+        let resp = super::process_get_bundle_status(storage, &req, &cfg)
+            .await
+            .unwrap();
+        assert_eq!(resp.result.status, 500);
+        assert!(resp.result.any_failed);
+    }
+
+    #[test]
+    fn test_fee_payment_calls_pay_the_collector() {
+        let collector: Address = "0x55f3a93f544e01ce4378d25e927d7c493b863bd6"
+            .parse()
+            .unwrap();
+        let args = format!(
+            "{:0>64}{:064x}",
+            "55f3a93f544e01ce4378d25e927d7c493b863bd6", 21_000
+        );
+        assert_eq!(
+            super::erc20_transfer_calldata(collector, U256::from(21_000)),
+            format!("0xa9059cbb{}", args)
+        );
+        assert_eq!(
+            super::erc20_approve_calldata(collector, U256::from(21_000)),
+            format!("0x095ea7b3{}", args)
+        );
+        assert_eq!(test_config().fee_payment("1"), FeePayment::Transfer);
     }
 
     #[tokio::test]
-    async fn test_get_status_invalid_id_format() {
+    async fn test_relays_are_held_to_their_quote() {
         let storage = test_storage().await;
         let cfg = test_config();
-        let req = GetStatusRequest {
-            ids: vec!["not-a-uuid".to_string()],
+        let native = "0x0000000000000000000000000000000000000000";
+        let request: SendTransactionRequest = serde_json::from_value(serde_json::json!({
+            "to": "0x0000000000000000000000000000000000000001",
+            "data": "0x12",
+            "capabilities": { "payment": { "type": "native", "token": native, "data": "" } },
+            "chainId": "1",
+            "authorizationList": "",
+            "quoteId": "q1"
+        }))
+        .unwrap();
+        let quote = |expiry| IssuedQuote {
+            id: "q1".to_string(),
+            chain_id: "1".to_string(),
+            token: native.to_string(),
+            rate: 0.00002,
+            gas_price: "0x4a817c800".to_string(),
+            fee: None,
+            expiry,
         };
-        let resp = super::process_get_status(storage, &req, &cfg)
+        let now = Utc::now().timestamp() as u64;
+
+        let unknown = super::check_quote(&storage, &request, 1, &cfg).await;
+        assert!(matches!(
+            unknown,
+            Err(RelayError::InvalidParams { field: Some(field), .. }) if field == "quoteId"
+        ));
+
+        storage.store_quote(&quote(now + 60)).await.unwrap();
+        assert!(super::check_quote(&storage, &request, 1, &cfg)
             .await
-            .unwrap();
-        assert_eq!(resp.result.len(), 1);
-        assert_eq!(resp.result[0].status, 400);
+            .is_ok());
+        assert!(super::check_quote(&storage, &request, 10, &cfg)
+            .await
+            .is_err());
+
+        // An expired quote is rejected with a fresh one for the same chain and token
+        storage.store_quote(&quote(now - 1)).await.unwrap();
+        let Err(RelayError::QuoteExpired { expiry, requote }) =
+            super::check_quote(&storage, &request, 1, &cfg).await
+        else {
+            panic!("expired quote accepted");
+        };
+        assert_eq!(expiry, now - 1);
+        let requote = requote.expect("no fresh quote issued");
+        assert_eq!(
+            (requote.chain_id.as_str(), requote.token.as_str()),
+            ("1", native)
+        );
+        assert!(requote.expiry > now);
+        assert_eq!(
+            storage.get_quote(&requote.id).await.unwrap(),
+            Some(*requote)
+        );
     }
 
     #[tokio::test]
@@ -2441,15 +11844,27 @@ mod tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: "".to_string(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: "".to_string(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
         let err = super::process_send_transaction(storage.clone(), &req1, &cfg)
             .await
             .err()
             .unwrap();
         assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+        assert_eq!(err.data.unwrap()["field"], "to");
 
         // Missing 'data'
         let req2 = SendTransactionRequest {
@@ -2473,6 +11888,86 @@ mod tests {
             .err()
             .unwrap();
         assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+
+        // Callbacks cannot be signed without a callback secret
+        let req4 = SendTransactionRequest {
+            to: "0x0000000000000000000000000000000000000001".to_string(),
+            data: "0x12".to_string(),
+            callback_url: Some("https://example.com/hook".to_string()),
+            execute_after: None,
+            condition: None,
+            ..req1.clone()
+        };
+        let err = super::process_send_transaction(storage.clone(), &req4, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::ServerError(-4209));
+    }
+
+    #[test]
+    fn test_submission_fingerprint_covers_safe_transaction_value_and_payment() {
+        let safe_tx = |nonce: &str| SafeTransaction {
+            to: "0x00000000000000000000000000000000000000aa".to_string(),
+            value: None,
+            data: "0xabababab".to_string(),
+            operation: 0,
+            safe_tx_gas: None,
+            base_gas: None,
+            gas_price: None,
+            gas_token: None,
+            refund_receiver: None,
+            nonce: Some(nonce.to_string()),
+            signatures: format!("0x{}", "11".repeat(65)),
+        };
+        let first = SendTransactionRequest {
+            to: "0x00000000000000000000000000000000000000fe".to_string(),
+            data: String::new(),
+            capabilities: SendTransactionCapabilities {
+                payment: PaymentCapability {
+                    payment_type: "sponsored".to_string(),
+                    token: "0x0000000000000000000000000000000000000000".to_string(),
+                    data: "".to_string(),
+                },
+                dry_run: false,
+                fallback_payments: Vec::new(),
+            },
+            chain_id: "1".to_string(),
+            authorization_list: "".to_string(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: Some(safe_tx("0x0")),
+            value: None,
+            api_key: None,
+            quote_id: None,
+        };
+        let fingerprint = submission_fingerprint(&first);
+
+        // Two Safe transactions to the same Safe are different relays
+        let second = SendTransactionRequest {
+            safe: Some(safe_tx("0x1")),
+            ..first.clone()
+        };
+        assert_ne!(fingerprint, submission_fingerprint(&second));
+
+        let with_value = SendTransactionRequest {
+            value: Some("0x1".to_string()),
+            ..first.clone()
+        };
+        assert_ne!(fingerprint, submission_fingerprint(&with_value));
+        // Zero value is the default
+        let zero_value = SendTransactionRequest {
+            value: Some("0x0".to_string()),
+            ..first.clone()
+        };
+        assert_eq!(fingerprint, submission_fingerprint(&zero_value));
+
+        let mut native = first.clone();
+        native.capabilities.payment.payment_type = "native".to_string();
+        assert_ne!(fingerprint, submission_fingerprint(&native));
     }
 
     #[tokio::test]
@@ -2488,9 +11983,20 @@ mod tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: "".to_string(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "999999".to_string(),
             authorization_list: "".to_string(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
         let err = super::process_send_transaction(storage, &req, &cfg)
             .await
@@ -2511,8 +12017,11 @@ mod tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: "".to_string(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             payment_chain_id: "1".to_string(),
+            api_key: None,
         };
         let err = super::process_send_transaction_multichain(storage, &req, &cfg)
             .await
@@ -2521,6 +12030,75 @@ mod tests {
         assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
     }
 
+    #[tokio::test]
+    async fn test_multichain_rejects_dry_run() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+        let req = SendTransactionMultichainRequest {
+            transactions: vec![MultichainTransaction {
+                to: "0x0000000000000000000000000000000000000001".to_string(),
+                data: "0x12".to_string(),
+                chain_id: "1".to_string(),
+                authorization_list: "".to_string(),
+                value: None,
+            }],
+            capabilities: SendTransactionCapabilities {
+                payment: PaymentCapability {
+                    payment_type: "sponsored".to_string(),
+                    token: "0x0000000000000000000000000000000000000000".to_string(),
+                    data: "".to_string(),
+                },
+                dry_run: true,
+                fallback_payments: Vec::new(),
+            },
+            payment_chain_id: "1".to_string(),
+            api_key: None,
+        };
+        let err = super::process_send_transaction_multichain(storage.clone(), &req, &cfg)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+        assert!(err.message.contains("dryRun"));
+        assert!(storage
+            .get_requests(TenantScope::All, None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_payment_validation_codes() {
+        let cfg = test_config();
+        let payment = |payment_type: &str, token: &str| PaymentCapability {
+            payment_type: payment_type.to_string(),
+            token: token.to_string(),
+            data: "".to_string(),
+        };
+        let code =
+            |payment: PaymentCapability| validate_payment(&payment, 1, &cfg).map_err(|e| e.code());
+        let usdc = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        let zero = "0x0000000000000000000000000000000000000000";
+
+        assert_eq!(code(payment("native", zero)), Ok(()));
+        assert_eq!(code(payment("sponsored", "")), Ok(()));
+        assert_eq!(code(payment("native", usdc)), Err(-32602));
+        assert_eq!(code(payment("gasTank", usdc)), Err(-32602));
+        assert_eq!(code(payment("erc20", "0x1234")), Err(-32602));
+        assert_eq!(
+            code(payment("erc20", usdc)),
+            Err(crate::errors::UNSUPPORTED_PAYMENT_TOKEN)
+        );
+        assert_eq!(
+            code(payment("permit", usdc)),
+            Err(crate::errors::UNSUPPORTED_PAYMENT_TOKEN)
+        );
+        assert_eq!(
+            code(payment("barter", zero)),
+            Err(crate::errors::UNSUPPORTED_CAPABILITY)
+        );
+    }
+
     #[tokio::test]
     async fn test_exchange_rate_invalid_chain_and_erc20_unavailable() {
         let cfg = test_config();
@@ -2546,4 +12124,449 @@ mod tests {
             ExchangeRateResultItem::Error(_)
         ));
     }
+
+    #[tokio::test]
+    async fn test_monitor_pass_only_checks_in_flight_requests() {
+        let storage = test_storage().await;
+        let cfg = test_config();
+
+        let request =
+            |chain_id: u64, status: RequestStatus, tx_hash: Option<&str>| RelayerRequest {
+                id: Uuid::new_v4(),
+                from_address: "0x55f3a93f544e01ce4378d25e927d7c493b863bd6".to_string(),
+                to_address: "0x0987654321098765432109876543210987654321".to_string(),
+                amount: "0".to_string(),
+                gas_limit: 21000,
+                gas_price: "0x4a817c800".to_string(),
+                data: Some("0x".to_string()),
+                nonce: 0,
+                chain_id,
+                transaction_hash: tx_hash.map(str::to_string),
+                status,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                error_message: None,
+                bundle_id: None,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
+            };
+        let hash = format!("0x{}", "ab".repeat(32));
+        let requests = vec![
+            request(1, RequestStatus::Pending, Some(&hash)),
+            request(10, RequestStatus::Processing, Some(&hash)),
+            request(10, RequestStatus::Processing, None),
+            request(10, RequestStatus::Completed, Some(&hash)),
+        ];
+
+        // Neither chain has an RPC URL, so in-flight requests stay pending untouched
+        let summary = super::poll_in_flight(&storage, &cfg, requests).await;
+        assert_eq!(
+            summary,
+            super::MonitorSummary {
+                pending: 2,
+                ..Default::default()
+            }
+        );
+
+        // An unmined request out of resubmissions is failed rather than bumped again
+        let mut hopeless = request(1, RequestStatus::Processing, Some(&hash));
+        hopeless.resubmission_count = cfg.resubmission_policy().max_attempts;
+        storage.create_request(hopeless.clone()).await.unwrap();
+        let outcome = super::check_in_flight(&storage, &cfg, &hopeless, &hash, Ok(None)).await;
+        assert_eq!(outcome, super::MonitorOutcome::ResubmitFailed);
+        let failed = storage
+            .get_request(TenantScope::All, hopeless.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(failed.status, RequestStatus::Failed);
+        assert_eq!(
+            failed.error_message.as_deref(),
+            Some("not mined after 50 resubmissions")
+        );
+    }
+
+    #[test]
+    fn test_log_payload_truncation_respects_char_boundaries() {
+        assert_eq!(truncate_for_log("0xabcdef", 16), "0xabcdef");
+        assert_eq!(truncate_for_log("0xabcdef", 4), "0xab…(+4 bytes)");
+        // 'é' is two bytes; the cut must back off rather than split it
+        assert_eq!(truncate_for_log("aé", 2), "a…(+2 bytes)");
+    }
+
+    #[test]
+    fn test_billing_period_resets_at_the_next_month() {
+        let (period, resets_at) =
+            billing_period(Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 59).unwrap());
+        assert_eq!(period, "2024-02");
+        assert_eq!(
+            resets_at,
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+
+        let (period, resets_at) =
+            billing_period(Utc.with_ymd_and_hms(2024, 12, 15, 8, 0, 0).unwrap());
+        assert_eq!(period, "2024-12");
+        assert_eq!(
+            resets_at,
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_run_skips_missed_ticks() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let hour = 3600;
+        // On time: the following tick
+        assert_eq!(
+            next_run_after(start, hour, start),
+            Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap()
+        );
+        // Three and a half hours late: the ticks at 1:00 to 3:00 are dropped
+        assert_eq!(
+            next_run_after(start, hour, start + chrono::Duration::minutes(210)),
+            Utc.with_ymd_and_hms(2024, 1, 1, 4, 0, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_nonce_gap_is_filled_once_it_persists() {
+        let storage = test_storage().await;
+        let mock = Arc::new(MockChainClient::new().with_gas_price(10_000_000_000));
+        let mut cfg = test_config();
+        cfg.chain = ChainBackend::from_arc(mock.clone());
+        let signer = RelayerSigner::from(PrivateKeySigner::random());
+        let relayer = signer.address();
+        let mut suspects = NonceGapSuspects::new();
+
+        for _ in 0..3 {
+            let nonce = cfg.chain.reserve_nonce("mock", relayer).await.unwrap();
+            let mut tx = TransactionRequest::default().to(relayer);
+            tx.nonce = Some(nonce);
+            cfg.chain
+                .send_transaction("mock", &signer, tx)
+                .await
+                .unwrap();
+        }
+        assert!(
+            heal_nonce_gap(&storage, &cfg, 1, "mock", &signer, &mut suspects)
+                .await
+                .is_none()
+        );
+        assert!(suspects.is_empty());
+
+        // Nonce 1 is evicted: 2 is queued behind it and the count stalls at 1
+        mock.drop_nonce(1);
+        assert!(
+            heal_nonce_gap(&storage, &cfg, 1, "mock", &signer, &mut suspects)
+                .await
+                .is_none()
+        );
+        let healing = heal_nonce_gap(&storage, &cfg, 1, "mock", &signer, &mut suspects)
+            .await
+            .expect("gap filled on the second pass");
+        assert_eq!(healing.nonce, 1);
+        assert_eq!(healing.relayer, format!("0x{:x}", relayer));
+        // Node price plus the fast lane's 40% bump
+        assert_eq!(healing.gas_price, format!("0x{:x}", 14_000_000_000u128));
+
+        let filler = mock.sent_transactions().pop().unwrap();
+        assert_eq!(filler.nonce, Some(1));
+        assert_eq!(filler.to, Some(relayer.into()));
+        assert_eq!(filler.value, Some(U256::ZERO));
+        assert_eq!(storage.get_nonce_healings(10).await.unwrap(), vec![healing]);
+
+        // The sequence is contiguous again
+        assert!(
+            heal_nonce_gap(&storage, &cfg, 1, "mock", &signer, &mut suspects)
+                .await
+                .is_none()
+        );
+        assert!(
+            heal_nonce_gap(&storage, &cfg, 1, "mock", &signer, &mut suspects)
+                .await
+                .is_none()
+        );
+        assert_eq!(mock.sent_transactions().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_gas_oracle_strategies_and_bounds() {
+        let gwei = 1_000_000_000u128;
+        let station = serde_json::json!({
+            "safeLow": { "maxPriorityFee": 30.0, "maxFee": 31.5 },
+            "standard": { "maxPriorityFee": 32.0, "maxFee": 45.25 },
+            "fast": { "maxPriorityFee": 40.0, "maxFee": 60.0 },
+            "estimatedBaseFee": 1.2
+        });
+        assert_eq!(parse_gas_station_price(&station), Some(45_250_000_000));
+        assert_eq!(
+            parse_gas_station_price(&serde_json::json!({ "standard": {} })),
+            None
+        );
+
+        let tips = vec![vec![gwei], vec![3 * gwei], vec![2 * gwei]];
+        let mock = Arc::new(MockChainClient::new().with_fee_history(
+            alloy::rpc::types::FeeHistory {
+                base_fee_per_gas: vec![10 * gwei, 12 * gwei],
+                reward: Some(tips),
+                ..Default::default()
+            },
+        ));
+        let mut cfg = test_config();
+        cfg.chain = ChainBackend::from_arc(mock);
+        assert_eq!(
+            fee_history_gas_price(&cfg, "mock", 60.0).await,
+            Some(14 * gwei)
+        );
+        cfg.chain = ChainBackend::from_arc(Arc::new(MockChainClient::new()));
+        assert_eq!(fee_history_gas_price(&cfg, "mock", 60.0).await, None);
+
+        let oracle = GasOracle {
+            strategy: GasPriceStrategy::FeeHistory,
+            percentile: 60.0,
+            gas_station_url: None,
+            floor_wei: Some(30 * gwei),
+            ceiling_wei: Some(100 * gwei),
+        };
+        assert_eq!(oracle.clamp(14 * gwei), 30 * gwei);
+        assert_eq!(oracle.clamp(45 * gwei), 45 * gwei);
+        assert_eq!(oracle.clamp(500 * gwei), 100 * gwei);
+        assert_eq!(cfg.gas_oracle("1").strategy, GasPriceStrategy::Node);
+        assert_eq!(
+            cfg.gas_oracle("137").gas_station_url.as_deref(),
+            Some("https://gasstation.polygon.technology/v2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulated_reverts_are_decoded() {
+        let cfg = test_config();
+        let mock = MockChainClient::new().with_revert("ERC20: transfer amount exceeds balance");
+        let simulation = mock
+            .simulate_call("mock", Address::ZERO, Bytes::new(), U256::ZERO)
+            .await
+            .unwrap();
+        assert!(!simulation.success);
+        assert_eq!(
+            decode_revert_reason(&simulation.output, &cfg).as_deref(),
+            Some("ERC20: transfer amount exceeds balance")
+        );
+
+        let mut panic = vec![0x4e, 0x48, 0x7b, 0x71];
+        panic.extend_from_slice(&U256::from(0x11).to_be_bytes::<32>());
+        assert_eq!(
+            decode_revert_reason(&panic, &cfg).as_deref(),
+            Some("arithmetic overflow or underflow (Panic 0x11)")
+        );
+        // Truncated data and unknown selectors have no reason
+        assert_eq!(decode_revert_reason(&panic[..20], &cfg), None);
+        assert_eq!(decode_revert_reason(&[0xde, 0xad], &cfg), None);
+    }
+
+    #[test]
+    fn test_token_fees_keep_full_precision() {
+        let quote = |rate: f64, decimals: u8| ExchangeRateQuote {
+            rate,
+            token: TokenInfo {
+                decimals,
+                address: "0xToken".to_string(),
+                symbol: None,
+                name: None,
+            },
+        };
+        let gwei = U256::from(1_000_000_000u64);
+
+        // 21000 gas at 1 gwei, 0.5 tokens per gas: 10500 tokens, beyond u64 in 18 decimals
+        let fee = fee_in_token_units(U256::from(21_000) * gwei, gwei, &quote(0.5, 18)).unwrap();
+        assert_eq!(fee.to_string(), "10500000000000000000000");
+
+        // Fractions of a unit round up, and a zero gas price cannot be priced
+        let fee = fee_in_token_units(U256::from(3) * gwei, gwei, &quote(1e-6, 6)).unwrap();
+        assert_eq!(fee, U256::from(3));
+        let fee = fee_in_token_units(U256::from(1), gwei, &quote(1e-6, 6)).unwrap();
+        assert_eq!(fee, U256::from(1));
+        assert_eq!(fee_in_token_units(gwei, U256::ZERO, &quote(1.0, 6)), None);
+    }
+
+    #[test]
+    fn test_payment_slippage_lowers_the_accepted_fee() {
+        let fee = U256::from(1_000_000);
+        assert_eq!(fee_with_slippage(fee, 0), fee);
+        assert_eq!(fee_with_slippage(fee, 200), U256::from(980_000));
+        assert_eq!(fee_with_slippage(U256::from(99), 200), U256::from(98));
+        assert_eq!(fee_with_slippage(fee, 20_000), U256::ZERO);
+    }
+
+    #[test]
+    fn test_fee_bounds_report_the_exceeded_limit() {
+        let bounds = FeeBounds {
+            min: Some(10_000),
+            max: Some(50_000_000),
+            reject: false,
+        };
+        assert_eq!(bounds.exceeded(9_999), Some((FeeBound::Min, 10_000)));
+        assert_eq!(bounds.exceeded(10_000), None);
+        assert_eq!(bounds.exceeded(50_000_000), None);
+        assert_eq!(
+            bounds.exceeded(50_000_001),
+            Some((FeeBound::Max, 50_000_000))
+        );
+
+        let floor_only = FeeBounds {
+            max: None,
+            ..bounds
+        };
+        assert_eq!(floor_only.exceeded(u64::MAX), None);
+
+        let cfg = test_config();
+        assert_eq!(
+            apply_fee_bounds(U256::from(7), "1", "0xToken", &cfg),
+            Ok((U256::from(7), None))
+        );
+    }
+
+    #[test]
+    fn test_broadcast_errors_separate_congestion_from_rejection() {
+        assert_eq!(
+            broadcast_error(8453, "replacement transaction underpriced"),
+            RelayError::ChainCongested("8453".to_string())
+        );
+        assert_eq!(
+            broadcast_error(1, "RPC error: TxPool is full"),
+            RelayError::ChainCongested("1".to_string())
+        );
+        assert_eq!(
+            broadcast_error(1, "nonce too low"),
+            RelayError::BroadcastFailed("1".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_methods_answer_method_not_found() {
+        let disabled = BTreeSet::from(["relayer_getQuote".to_string()]);
+        let mut io = MetaIoHandler::with_middleware(DisabledMethods(Arc::new(disabled)));
+        io.add_method("relayer_getQuote", |_params: Params| async {
+            Ok(serde_json::json!("quote"))
+        });
+        io.add_method("relayer_getVersion", |_params: Params| async {
+            Ok(serde_json::json!("version"))
+        });
+
+        let call = |method: &str| {
+            format!(
+                r#"{{"jsonrpc":"2.0","method":"{}","params":[],"id":1}}"#,
+                method
+            )
+        };
+        let response: serde_json::Value = serde_json::from_str(
+            &io.handle_request(&call("relayer_getQuote"), ())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], -32601);
+        let response: serde_json::Value = serde_json::from_str(
+            &io.handle_request(&call("relayer_getVersion"), ())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response["result"], "version");
+
+        let version = build_version_response(&test_config());
+        assert!(version.features.multichain);
+        assert!(version.methods.iter().any(|m| m == "relayer_getQuote"));
+    }
+
+    #[test]
+    fn test_rebalance_tops_up_from_surplus_without_draining_donors() {
+        let policy = RebalancePolicy {
+            key_refs: Vec::new(),
+            min_balance: 20,
+            target_balance: 100,
+        };
+        let fee = 1;
+        // Key 1 needs 95 and key 3 needs 90; key 0 has 60 to spare and key 2 has 40
+        let transfers = plan_rebalance(&[161, 5, 141, 10], &policy, fee);
+        assert_eq!(transfers, vec![(0, 1, 60), (2, 1, 35), (2, 3, 4)]);
+
+        // Nothing below the minimum, or nobody above the target: no transfers
+        assert!(plan_rebalance(&[50, 20, 1000], &policy, fee).is_empty());
+        assert!(plan_rebalance(&[100, 0], &policy, fee).is_empty());
+    }
+
+    #[test]
+    fn test_wallet_abi_accepts_artifact_and_bare_array() {
+        let abi = super::parse_wallet_abi(super::DEFAULT_WALLET_ABI).unwrap();
+        assert!(abi.functions().any(|f| f.name == "executeWithRelayer"));
+
+        let bare = r#"[{"type":"function","name":"ping","inputs":[],"outputs":[],"stateMutability":"view"}]"#;
+        let abi = super::parse_wallet_abi(bare).unwrap();
+        assert_eq!(abi.functions().count(), 1);
+
+        assert!(super::parse_wallet_abi(r#"{"bytecode":"0x"}"#).is_err());
+    }
+
+    #[test]
+    fn test_overpayment_reported_past_threshold_with_refund_calls() {
+        let wei = U256::from;
+        // 25% under the price clears a 20% threshold; 10% under, or over the price, does not
+        assert_eq!(super::overpayment(wei(1000), wei(750), 20), Some(wei(250)));
+        assert_eq!(super::overpayment(wei(1000), wei(800), 20), Some(wei(200)));
+        assert_eq!(super::overpayment(wei(1000), wei(900), 20), None);
+        assert_eq!(super::overpayment(wei(1000), wei(1200), 0), None);
+        assert_eq!(super::overpayment(wei(1000), wei(1000), 0), None);
+
+        let wallet = "0x742d35cc6634c0532925a3b844bc454e4438f44e";
+        let token = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        let refund = |payment_type: &str, token: &str| Overpayment {
+            request_id: Uuid::new_v4(),
+            chain_id: 1,
+            wallet: wallet.to_string(),
+            transaction_hash: format!("0x{}", "ab".repeat(32)),
+            payment_type: payment_type.to_string(),
+            token: token.to_string(),
+            priced_wei: "1000".to_string(),
+            spent_wei: "750".to_string(),
+            overpaid_wei: "250".to_string(),
+            refund_amount: "500".to_string(),
+            recorded_at: Utc::now(),
+        };
+
+        let call = super::refund_call(&refund("erc20", token)).unwrap();
+        assert_eq!(call.to, token);
+        assert_eq!(
+            call.data,
+            super::erc20_transfer_calldata(wallet.parse().unwrap(), wei(500))
+        );
+        assert_eq!(call.value, "0x0");
+
+        let call = super::refund_call(&refund(
+            "native",
+            "0x0000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        assert_eq!(call.to, wallet);
+        assert_eq!(call.data, "0x");
+        assert_eq!(call.value, "0x1f4");
+
+        assert!(super::refund_call(&refund(
+            "gasTank",
+            "0x0000000000000000000000000000000000000000"
+        ))
+        .is_none());
+    }
 }
@@ -0,0 +1,289 @@
+//! Relaying of Safe (Gnosis Safe) `execTransaction` calls.
+//!
+//! A `relayer_sendTransaction` call may carry a `safe` object instead of calldata: the SafeTx
+//! fields and the owners' signatures. The relayer reads the Safe's owners, threshold and nonce,
+//! checks the signatures over the EIP-712 SafeTx hash with [`check_signatures`], and relays
+//! [`SafeTx::exec_calldata`] to the Safe.
+//!
+//! The checks follow `checkSignatures` in Safe 1.3 and later: the first `threshold` 65-byte
+//! entries must come from distinct owners in ascending address order. ECDSA and `eth_sign`
+//! entries are recovered here. Contract (EIP-1271) and approved-hash entries name their owner
+//! in `r` and are only verified by the Safe itself, during simulation.
+
+use alloy::primitives::{b256, keccak256, Address, Bytes, PrimitiveSignature, B256, U256};
+
+/// `execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)`
+pub const EXEC_TRANSACTION_SELECTOR: [u8; 4] = [0x6a, 0x76, 0x12, 0x02];
+/// `getThreshold()`
+pub const GET_THRESHOLD_SELECTOR: [u8; 4] = [0xe7, 0x52, 0x35, 0xb8];
+/// `getOwners()`
+pub const GET_OWNERS_SELECTOR: [u8; 4] = [0xa0, 0xe6, 0x7e, 0x2b];
+/// `nonce()`
+pub const NONCE_SELECTOR: [u8; 4] = [0xaf, 0xfe, 0xd0, 0xe0];
+
+/// `keccak256("EIP712Domain(uint256 chainId,address verifyingContract)")`
+const DOMAIN_SEPARATOR_TYPEHASH: B256 =
+    b256!("47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218");
+/// `keccak256("SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,
+/// uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)")`
+const SAFE_TX_TYPEHASH: B256 =
+    b256!("bb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8");
+
+/// Length of one entry in the static part of a signature blob: `r`, `s` and `v`
+const SIGNATURE_LENGTH: usize = 65;
+
+/// The transaction a Safe's owners sign
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeTx {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    /// 0 for a call, 1 for a delegatecall
+    pub operation: u8,
+    pub safe_tx_gas: U256,
+    pub base_gas: U256,
+    pub gas_price: U256,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    pub nonce: U256,
+}
+
+impl SafeTx {
+    /// EIP-712 hash the owners of `safe` sign on `chain_id`
+    pub fn hash(&self, chain_id: u64, safe: Address) -> B256 {
+        let mut domain = DOMAIN_SEPARATOR_TYPEHASH.to_vec();
+        domain.extend_from_slice(&U256::from(chain_id).to_be_bytes::<32>());
+        domain.extend_from_slice(safe.into_word().as_slice());
+
+        let mut message = SAFE_TX_TYPEHASH.to_vec();
+        message.extend_from_slice(self.to.into_word().as_slice());
+        message.extend_from_slice(&self.value.to_be_bytes::<32>());
+        message.extend_from_slice(keccak256(&self.data).as_slice());
+        message.extend_from_slice(&U256::from(self.operation).to_be_bytes::<32>());
+        message.extend_from_slice(&self.safe_tx_gas.to_be_bytes::<32>());
+        message.extend_from_slice(&self.base_gas.to_be_bytes::<32>());
+        message.extend_from_slice(&self.gas_price.to_be_bytes::<32>());
+        message.extend_from_slice(self.gas_token.into_word().as_slice());
+        message.extend_from_slice(self.refund_receiver.into_word().as_slice());
+        message.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+
+        let mut digest = vec![0x19, 0x01];
+        digest.extend_from_slice(keccak256(domain).as_slice());
+        digest.extend_from_slice(keccak256(message).as_slice());
+        keccak256(digest)
+    }
+
+    /// `execTransaction` calldata executing this transaction with `signatures`
+    pub fn exec_calldata(&self, signatures: &[u8]) -> Bytes {
+        let data = abi_bytes(&self.data);
+        // Ten head words, then the `data` and `signatures` tails
+        let data_offset = 10 * 32;
+        let signatures_offset = data_offset + data.len();
+
+        let mut input = EXEC_TRANSACTION_SELECTOR.to_vec();
+        input.extend_from_slice(self.to.into_word().as_slice());
+        input.extend_from_slice(&self.value.to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(data_offset).to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(self.operation).to_be_bytes::<32>());
+        input.extend_from_slice(&self.safe_tx_gas.to_be_bytes::<32>());
+        input.extend_from_slice(&self.base_gas.to_be_bytes::<32>());
+        input.extend_from_slice(&self.gas_price.to_be_bytes::<32>());
+        input.extend_from_slice(self.gas_token.into_word().as_slice());
+        input.extend_from_slice(self.refund_receiver.into_word().as_slice());
+        input.extend_from_slice(&U256::from(signatures_offset).to_be_bytes::<32>());
+        input.extend_from_slice(&data);
+        input.extend_from_slice(&abi_bytes(signatures));
+        input.into()
+    }
+}
+
+/// Length-prefixed, zero-padded ABI encoding of a `bytes` value
+fn abi_bytes(data: &[u8]) -> Vec<u8> {
+    let mut out = U256::from(data.len()).to_be_bytes::<32>().to_vec();
+    out.extend_from_slice(data);
+    out.resize(out.len() + (32 - data.len() % 32) % 32, 0);
+    out
+}
+
+/// Owner behind each of the first `threshold` entries of `signatures`, in order
+pub fn signers(hash: B256, signatures: &[u8], threshold: usize) -> Result<Vec<Address>, String> {
+    if signatures.len() < threshold * SIGNATURE_LENGTH {
+        return Err(format!(
+            "{} signatures required, got {}",
+            threshold,
+            signatures.len() / SIGNATURE_LENGTH
+        ));
+    }
+    signatures
+        .chunks(SIGNATURE_LENGTH)
+        .take(threshold)
+        .map(|entry| {
+            let v = entry[64];
+            let recovered = match v {
+                // Contract signature or pre-approved hash: `r` holds the owner
+                0 | 1 => return Ok(Address::from_word(B256::from_slice(&entry[..32]))),
+                27 | 28 => PrimitiveSignature::from_bytes_and_parity(&entry[..64], v == 28)
+                    .recover_address_from_prehash(&hash),
+                // `eth_sign` over the hash, with v shifted by 4
+                31 | 32 => PrimitiveSignature::from_bytes_and_parity(&entry[..64], v == 32)
+                    .recover_address_from_msg(hash),
+                _ => return Err(format!("Unsupported signature type v={}", v)),
+            };
+            recovered.map_err(|e| format!("Invalid signature: {}", e))
+        })
+        .collect()
+}
+
+/// Check that the first `threshold` entries of `signatures` approve `hash` and come from
+/// distinct `owners` in ascending address order, as the Safe requires
+pub fn check_signatures(
+    hash: B256,
+    signatures: &[u8],
+    threshold: usize,
+    owners: &[Address],
+) -> Result<(), String> {
+    if threshold == 0 {
+        return Err("Safe is not set up (threshold is zero)".to_string());
+    }
+    let mut last = Address::ZERO;
+    for signer in signers(hash, signatures, threshold)? {
+        if !owners.contains(&signer) {
+            return Err(format!("0x{:x} is not an owner of the Safe", signer));
+        }
+        if signer <= last {
+            return Err("Signatures must be ordered by ascending owner address".to_string());
+        }
+        last = signer;
+    }
+    Ok(())
+}
+
+/// Owners from the ABI-encoded return value of `getOwners()`
+pub fn decode_owners(output: &[u8]) -> Option<Vec<Address>> {
+    let word = |offset: usize| output.get(offset..offset.checked_add(32)?);
+    let start = usize::try_from(U256::from_be_slice(word(0)?)).ok()?;
+    let count = usize::try_from(U256::from_be_slice(word(start)?)).ok()?;
+    (0..count)
+        .map(|i| {
+            let offset = start.checked_add(32)?.checked_add(i.checked_mul(32)?)?;
+            word(offset).map(|w| Address::from_word(B256::from_slice(w)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+    fn safe_tx() -> SafeTx {
+        SafeTx {
+            to: Address::repeat_byte(0x11),
+            value: U256::from(1_000u64),
+            data: Bytes::from(vec![0xab; 36]),
+            operation: 0,
+            safe_tx_gas: U256::ZERO,
+            base_gas: U256::ZERO,
+            gas_price: U256::ZERO,
+            gas_token: Address::ZERO,
+            refund_receiver: Address::ZERO,
+            nonce: U256::from(7u64),
+        }
+    }
+
+    /// Concatenated signatures of `hash` by `signers`, in the order given
+    fn sign(hash: B256, signers: &[&PrivateKeySigner]) -> Vec<u8> {
+        signers
+            .iter()
+            .flat_map(|signer| signer.sign_hash_sync(&hash).unwrap().as_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_constants_match_their_definitions() {
+        assert_eq!(
+            keccak256("EIP712Domain(uint256 chainId,address verifyingContract)"),
+            DOMAIN_SEPARATOR_TYPEHASH
+        );
+        assert_eq!(
+            keccak256(
+                "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,\
+                 uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,\
+                 uint256 nonce)"
+            ),
+            SAFE_TX_TYPEHASH
+        );
+        let selector = |signature: &str| <[u8; 4]>::try_from(&keccak256(signature)[..4]).unwrap();
+        assert_eq!(
+            selector("execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)"),
+            EXEC_TRANSACTION_SELECTOR
+        );
+        assert_eq!(selector("getThreshold()"), GET_THRESHOLD_SELECTOR);
+        assert_eq!(selector("getOwners()"), GET_OWNERS_SELECTOR);
+        assert_eq!(selector("nonce()"), NONCE_SELECTOR);
+    }
+
+    #[test]
+    fn test_check_signatures_enforces_owners_order_and_threshold() {
+        let safe = Address::repeat_byte(0x5a);
+        let hash = safe_tx().hash(1, safe);
+        let mut keys = [
+            PrivateKeySigner::random(),
+            PrivateKeySigner::random(),
+            PrivateKeySigner::random(),
+        ];
+        keys.sort_by_key(|key| key.address());
+        let owners: Vec<Address> = keys[..2].iter().map(|key| key.address()).collect();
+
+        let sorted = sign(hash, &[&keys[0], &keys[1]]);
+        assert!(check_signatures(hash, &sorted, 2, &owners).is_ok());
+        assert_eq!(signers(hash, &sorted, 2).unwrap(), owners);
+
+        let reversed = sign(hash, &[&keys[1], &keys[0]]);
+        assert!(check_signatures(hash, &reversed, 2, &owners).is_err());
+        let duplicated = sign(hash, &[&keys[0], &keys[0]]);
+        assert!(check_signatures(hash, &duplicated, 2, &owners).is_err());
+        let stranger = sign(hash, &[&keys[0], &keys[2]]);
+        assert!(check_signatures(hash, &stranger, 2, &owners).is_err());
+        assert!(check_signatures(hash, &sorted[..65], 2, &owners).is_err());
+        // Signed for another chain
+        let elsewhere = sign(safe_tx().hash(10, safe), &[&keys[0], &keys[1]]);
+        assert!(check_signatures(hash, &elsewhere, 2, &owners).is_err());
+
+        // `eth_sign` entries carry v + 4; approved hashes name the owner in `r`
+        let mut eth_sign = keys[0]
+            .sign_message_sync(hash.as_slice())
+            .unwrap()
+            .as_bytes();
+        eth_sign[64] += 4;
+        let mut approved = [0u8; 65];
+        approved[..32].copy_from_slice(keys[1].address().into_word().as_slice());
+        approved[64] = 1;
+        let mixed = [eth_sign.as_slice(), approved.as_slice()].concat();
+        assert!(check_signatures(hash, &mixed, 2, &owners).is_ok());
+    }
+
+    #[test]
+    fn test_exec_calldata_layout_and_owner_decoding() {
+        let tx = safe_tx();
+        let calldata = tx.exec_calldata(&[0xcd; 65]);
+        let word = |i: usize| U256::from_be_slice(&calldata[4 + i * 32..4 + (i + 1) * 32]);
+        assert_eq!(calldata[..4], EXEC_TRANSACTION_SELECTOR);
+        assert_eq!(word(1), tx.value);
+        // 36 bytes of data take two words after their length
+        assert_eq!(word(2), U256::from(320));
+        assert_eq!(word(9), U256::from(320 + 96));
+        assert_eq!(word(10), U256::from(36));
+        assert_eq!(word(13), U256::from(65));
+        assert_eq!(calldata.len(), 4 + 32 * (10 + 3 + 4));
+
+        let owners = vec![Address::repeat_byte(1), Address::repeat_byte(2)];
+        let mut output = U256::from(32).to_be_bytes::<32>().to_vec();
+        output.extend_from_slice(&U256::from(2).to_be_bytes::<32>());
+        for owner in &owners {
+            output.extend_from_slice(owner.into_word().as_slice());
+        }
+        assert_eq!(decode_owners(&output), Some(owners));
+        assert_eq!(decode_owners(&output[..96]), None);
+    }
+}
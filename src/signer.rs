@@ -0,0 +1,372 @@
+//! Relayer keys: a private key held in memory, or a key held by a remote
+//! [Web3Signer](https://docs.web3signer.consensys.io) that signs over HTTP(S).
+//!
+//! A key is remote when config.json lists it under `remoteSigners` by the name it is
+//! otherwise referenced with: `default` for the relayer key, or the environment variable a
+//! chain's `relayerKeyRef` or a rebalance policy's `keyRefs` names. The relayer then never
+//! holds the key material. Each transaction's signing payload is posted to the signer's
+//! `/api/v1/eth1/sign/{publicKey}` endpoint, authenticated with a client certificate, and
+//! the returned signature is checked to recover to the configured key before it is used.
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use alloy::{
+    consensus::SignableTransaction,
+    network::TxSigner,
+    primitives::{keccak256, Address, PrimitiveSignature},
+    signers::{local::PrivateKeySigner, Error as SignerError, SignerSync},
+};
+use async_trait::async_trait;
+use reqwest::{Certificate, Identity};
+
+use crate::types::RemoteSignerSettings;
+
+/// A relayer key, local or remote, that signs the relayer's transactions
+#[derive(Clone)]
+pub enum RelayerSigner {
+    Local(PrivateKeySigner),
+    Remote(Arc<Web3Signer>),
+}
+
+impl RelayerSigner {
+    /// Signer for the key `settings` describes. Signers are built once per settings and
+    /// shared, so every use keeps the same connection pool.
+    pub fn remote(settings: &RemoteSignerSettings) -> Result<Self, String> {
+        static REMOTE_SIGNERS: OnceLock<Mutex<HashMap<RemoteSignerSettings, Arc<Web3Signer>>>> =
+            OnceLock::new();
+        let mut signers = REMOTE_SIGNERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(signer) = signers.get(settings) {
+            return Ok(Self::Remote(signer.clone()));
+        }
+        let signer = Arc::new(Web3Signer::new(settings)?);
+        signers.insert(settings.clone(), signer.clone());
+        Ok(Self::Remote(signer))
+    }
+
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Local(signer) => signer.address(),
+            Self::Remote(signer) => signer.address,
+        }
+    }
+
+    /// Signature of `keccak256(payload)`, such as an EIP-712 digest whose preimage is
+    /// `payload`. A remote signer is sent the payload itself, since it hashes what it signs.
+    pub async fn sign_keccak(&self, payload: &[u8]) -> Result<PrimitiveSignature, String> {
+        match self {
+            Self::Local(signer) => signer
+                .sign_hash_sync(&keccak256(payload))
+                .map_err(|e| e.to_string()),
+            Self::Remote(signer) => signer.sign_checked(payload).await,
+        }
+    }
+
+    /// The private key, for signing done in-process; `None` for a remote key
+    pub fn local(&self) -> Option<&PrivateKeySigner> {
+        match self {
+            Self::Local(signer) => Some(signer),
+            Self::Remote(_) => None,
+        }
+    }
+}
+
+impl From<PrivateKeySigner> for RelayerSigner {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self::Local(signer)
+    }
+}
+
+impl fmt::Debug for RelayerSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local(signer) => write!(f, "Local({:?})", signer.address()),
+            Self::Remote(signer) => write!(f, "Remote({:?} at {})", signer.address, signer.url),
+        }
+    }
+}
+
+#[async_trait]
+impl TxSigner<PrimitiveSignature> for RelayerSigner {
+    fn address(&self) -> Address {
+        RelayerSigner::address(self)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<PrimitiveSignature>,
+    ) -> alloy::signers::Result<PrimitiveSignature> {
+        match self {
+            Self::Local(signer) => TxSigner::sign_transaction(signer, tx).await,
+            Self::Remote(signer) => signer.sign_transaction(tx).await,
+        }
+    }
+}
+
+/// Client of one key held by a Web3Signer
+pub struct Web3Signer {
+    /// Base URL of the signer
+    url: String,
+    /// Signing endpoint of the key
+    sign_url: String,
+    address: Address,
+    client: reqwest::Client,
+}
+
+impl Web3Signer {
+    pub fn new(settings: &RemoteSignerSettings) -> Result<Self, String> {
+        let public_key = hex::decode(settings.public_key.trim_start_matches("0x"))
+            .map_err(|e| format!("Remote signer public key is not hex: {}", e))?;
+        // Accepted with or without the 0x04 uncompressed-point prefix
+        let public_key = match public_key.as_slice() {
+            [0x04, key @ ..] if key.len() == 64 => key,
+            key if key.len() == 64 => key,
+            _ => {
+                return Err("Remote signer public key must be an uncompressed secp256k1 key".into())
+            }
+        };
+
+        let mut client = reqwest::Client::builder()
+            .use_rustls_tls()
+            .timeout(Duration::from_millis(settings.timeout_ms));
+        if let Some(path) = &settings.ca_cert {
+            let cert = Certificate::from_pem(&read_pem(path)?)
+                .map_err(|e| format!("Invalid CA certificate {}: {}", path.display(), e))?;
+            client = client.add_root_certificate(cert);
+        }
+        match (&settings.client_cert, &settings.client_key) {
+            (Some(cert), Some(key)) => {
+                let mut pem = read_pem(cert)?;
+                pem.extend(read_pem(key)?);
+                let identity = Identity::from_pem(&pem)
+                    .map_err(|e| format!("Invalid client certificate {}: {}", cert.display(), e))?;
+                client = client.identity(identity);
+            }
+            (None, None) => {}
+            _ => return Err("Remote signer clientCert and clientKey must be set together".into()),
+        }
+        let client = client
+            .build()
+            .map_err(|e| format!("Failed to build remote signer client: {}", e))?;
+
+        let url = settings.url.trim_end_matches('/').to_string();
+        Ok(Self {
+            sign_url: format!("{}/api/v1/eth1/sign/0x{}", url, hex::encode(public_key)),
+            url,
+            address: Address::from_raw_public_key(public_key),
+            client,
+        })
+    }
+
+    /// Base URL of the signer
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Have the signer sign `payload`, which it hashes with keccak256 first
+    async fn sign(&self, payload: &[u8]) -> Result<PrimitiveSignature, String> {
+        let response = self
+            .client
+            .post(&self.sign_url)
+            .json(&serde_json::json!({ "data": format!("0x{}", hex::encode(payload)) }))
+            .send()
+            .await
+            .map_err(|e| format!("Remote signer at {} unreachable: {}", self.url, e))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read remote signer response: {}", e))?;
+        if !status.is_success() {
+            return Err(format!(
+                "Remote signer at {} answered {}: {}",
+                self.url,
+                status,
+                body.trim()
+            ));
+        }
+        let bytes = hex::decode(body.trim().trim_start_matches("0x"))
+            .map_err(|e| format!("Remote signer returned a non-hex signature: {}", e))?;
+        PrimitiveSignature::from_raw(&bytes)
+            .map_err(|e| format!("Remote signer returned an invalid signature: {}", e))
+    }
+
+    /// [`Web3Signer::sign`], refusing a signature that does not recover to the key. A signer
+    /// answering for another key would otherwise only show up as transactions from an
+    /// unexpected, unfunded sender.
+    async fn sign_checked(&self, payload: &[u8]) -> Result<PrimitiveSignature, String> {
+        let signature = self.sign(payload).await?;
+        let signer = signature
+            .recover_address_from_prehash(&keccak256(payload))
+            .map_err(|e| e.to_string())?;
+        if signer != self.address {
+            return Err(format!(
+                "Remote signer at {} signed as {:?} instead of {:?}",
+                self.url, signer, self.address
+            ));
+        }
+        Ok(signature)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<PrimitiveSignature>,
+    ) -> alloy::signers::Result<PrimitiveSignature> {
+        self.sign_checked(&tx.encoded_for_signing())
+            .await
+            .map_err(SignerError::other)
+    }
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::FeeQuote;
+    use alloy::{
+        consensus::TxLegacy,
+        primitives::{TxKind, U256},
+        signers::k256::elliptic_curve::sec1::ToEncodedPoint,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Accept one signing request and answer it with `key`'s signature of its data, as
+    /// Web3Signer does. Returns the requested path.
+    async fn serve_signer(listener: &TcpListener, key: &PrivateKeySigner) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(socket.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        let length: usize = head
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("content-length: ")?
+                    .parse()
+                    .ok()
+            })
+            .unwrap();
+        let mut body = vec![0; length];
+        socket.read_exact(&mut body).await.unwrap();
+
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let data = hex::decode(request["data"].as_str().unwrap().trim_start_matches("0x")).unwrap();
+        let signature = key.sign_hash_sync(&keccak256(data)).unwrap();
+        let signature = format!("0x{}", hex::encode(signature.as_bytes()));
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            signature.len(),
+            signature
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        head.split_whitespace().nth(1).unwrap().to_string()
+    }
+
+    fn settings(url: String, key: &PrivateKeySigner) -> RemoteSignerSettings {
+        let public_key = key.credential().verifying_key().to_encoded_point(false);
+        RemoteSignerSettings {
+            url,
+            public_key: format!("0x{}", hex::encode(public_key.as_bytes())),
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            timeout_ms: 5000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signature_recovers_to_the_configured_key() {
+        let key = PrivateKeySigner::random();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let signer = RelayerSigner::remote(&settings(url, &key)).unwrap();
+        assert_eq!(signer.address(), key.address());
+        assert!(signer.local().is_none());
+
+        let mut tx = TxLegacy {
+            chain_id: Some(8453),
+            nonce: 7,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::repeat_byte(0x11)),
+            value: U256::from(1),
+            input: Default::default(),
+        };
+        let (signature, path) = tokio::join!(
+            TxSigner::sign_transaction(&signer, &mut tx),
+            serve_signer(&listener, &key)
+        );
+        let public_key = settings(String::new(), &key).public_key;
+        assert_eq!(path, format!("/api/v1/eth1/sign/0x{}", &public_key[4..]));
+        let signature = signature.unwrap();
+        assert_eq!(
+            signature
+                .recover_address_from_prehash(&tx.signature_hash())
+                .unwrap(),
+            key.address()
+        );
+
+        // A signer holding some other key is refused
+        let impostor = PrivateKeySigner::random();
+        let (signature, _) = tokio::join!(
+            TxSigner::sign_transaction(&signer, &mut tx),
+            serve_signer(&listener, &impostor)
+        );
+        assert!(signature.unwrap_err().to_string().contains("instead of"));
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_attests_fee_quotes() {
+        let key = PrivateKeySigner::random();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let signer = RelayerSigner::remote(&settings(url, &key)).unwrap();
+        let quote = FeeQuote {
+            wallet: Address::repeat_byte(0x11),
+            token: Address::ZERO,
+            fee: U256::from(21_000),
+            expiry: 1_755_917_874,
+            chain_id: 8453,
+        };
+
+        let (signature, _) = tokio::join!(quote.sign(&signer), serve_signer(&listener, &key));
+        let bytes = hex::decode(signature.unwrap().trim_start_matches("0x")).unwrap();
+        let recovered = PrimitiveSignature::from_raw(&bytes)
+            .unwrap()
+            .recover_address_from_prehash(&quote.hash())
+            .unwrap();
+        assert_eq!(recovered, key.address());
+    }
+
+    #[test]
+    fn test_remote_signer_settings_are_validated() {
+        let key = PrivateKeySigner::random();
+        let mut bad_key = settings("http://localhost:9000".into(), &key);
+        bad_key.public_key = "0x1234".to_string();
+        assert!(Web3Signer::new(&bad_key).is_err());
+
+        let mut half_identity = settings("http://localhost:9000".into(), &key);
+        half_identity.client_cert = Some("client.pem".into());
+        assert!(Web3Signer::new(&half_identity)
+            .err()
+            .unwrap()
+            .contains("set together"));
+    }
+}
@@ -1,51 +1,503 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
-use anyhow::Result;
-use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use rocksdb::{
+    BlockBasedOptions, BoundColumnFamily, Cache, ColumnFamilyDescriptor, DBCompressionType,
+    DBWithThreadMode, MultiThreaded, Options, WriteBatch,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json;
 use uuid::Uuid;
 
-use crate::types::{RelayerRequest, RelayerResponse, RequestStatus, Resubmission};
+use crate::{
+    encryption::{is_sealed, RecordCipher, StorageKey},
+    types::{
+        Bundle, CallResult, CallbackDelivery, CallbackStatus, ChainRegistration, FeeSample,
+        GasTankDeposit, IssuedQuote, LifetimeStats, NonceHealing, OnchainFailure, Overpayment,
+        Receipt, RecurringJob, RelayerRequest, RelayerResponse, RequestStatus, Resubmission,
+        ScheduledTransaction, StorageCompression, StorageStats, StorageTuning,
+    },
+};
+
+/// Fee samples kept per chain and token; older ones are overwritten in ring order
+pub const FEE_HISTORY_SLOTS: u64 = 1440;
+
+/// Column family, zstd-compressed, that finished requests are moved to by
+/// [`Storage::archive_requests`]
+const ARCHIVE_CF: &str = "archive";
+
+/// Prefixes of the per-request records moved to the archive, each followed by the request id.
+/// Resubmission records go with them; index entries stay so hash and wallet lookups still
+/// find archived requests.
+const ARCHIVED_RECORD_PREFIXES: [&str; 7] = [
+    "request:",
+    "response:",
+    "receipt:",
+    "onchain_failure:",
+    "call_results:",
+    "callback:",
+    "gas_tank_debit:",
+];
+
+/// Whose records a read returns. Reads made for a caller take the scope of the tenant its
+/// API key resolved to, so another tenant's records read as missing; the relayer's own tasks
+/// and operator tooling read every tenant's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantScope<'a> {
+    All,
+    /// One tenant's records; `None` is the records made when no tenants are configured
+    Tenant(Option<&'a str>),
+}
+
+impl TenantScope<'_> {
+    /// Whether a record owned by `tenant` is in scope
+    pub fn admits(&self, tenant: Option<&str>) -> bool {
+        match self {
+            TenantScope::All => true,
+            TenantScope::Tenant(scope) => *scope == tenant,
+        }
+    }
+}
 
 pub struct Storage {
     db: Arc<DBWithThreadMode<MultiThreaded>>,
+    /// Options the database was opened with; they share its statistics collector
+    options: Arc<Options>,
+    /// Seals request records when a storage key is configured
+    cipher: Option<Arc<RecordCipher>>,
     start_time: std::time::Instant,
 }
 
+/// Writes staged in memory and applied atomically, in one RocksDB write, by
+/// [`Storage::commit`]. Lets a request be recorded together with its index entries and
+/// initial transitions instead of one put per step.
+#[derive(Default)]
+pub struct StorageBatch {
+    writes: Vec<StagedWrite>,
+}
+
+/// A staged write, kept in order so a later write to the same key wins
+enum StagedWrite {
+    Put(Vec<u8>, Vec<u8>),
+    /// Serialized request record, sealed at commit when the storage has a key
+    Record(Vec<u8>, Vec<u8>),
+    Delete(Vec<u8>),
+}
+
+impl StorageBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.writes
+            .push(StagedWrite::Put(key.to_vec(), value.to_vec()));
+    }
+
+    fn put_record<T: Serialize + ?Sized>(&mut self, key: &[u8], value: &T) -> Result<()> {
+        self.writes.push(StagedWrite::Record(
+            key.to_vec(),
+            serde_json::to_vec(value)?,
+        ));
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.writes.push(StagedWrite::Delete(key.to_vec()));
+    }
+
+    /// Stage a request record along with its wallet, bundle and transaction hash index
+    /// entries, and the sender nonce index entry of a raw transaction
+    pub fn put_request(&mut self, request: &RelayerRequest) -> Result<()> {
+        self.put_record(format!("request:{}", request.id).as_bytes(), request)?;
+        // created_at never changes, so rewriting a request lands on the same index key
+        self.put(
+            wallet_index_key(
+                request.tenant.as_deref(),
+                &request.to_address,
+                &wallet_index_position(request),
+            )
+            .as_bytes(),
+            request.id.to_string().as_bytes(),
+        );
+        if let Some(bundle_id) = request.bundle_id {
+            self.put(
+                format!("bundle_index:{}:{}", bundle_id, request.id).as_bytes(),
+                request.id.to_string().as_bytes(),
+            );
+        }
+        if let Some(tx_hash) = &request.transaction_hash {
+            self.index_tx_hash(tx_hash, request.id);
+        }
+        if request.raw_transaction.is_some() {
+            self.put(
+                sender_nonce_key(request.chain_id, &request.from_address, request.nonce).as_bytes(),
+                request.id.to_string().as_bytes(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Stage a resubmission record and its transaction hash index entry
+    pub fn add_resubmission(&mut self, request_id: Uuid, resub: &Resubmission) -> Result<()> {
+        let key = format!(
+            "resubmission:{}:{}:{}",
+            request_id, resub.chain_id, resub.transaction_hash
+        );
+        let value = serde_json::to_string(resub)?;
+        self.put(key.as_bytes(), value.as_bytes());
+        self.index_tx_hash(&resub.transaction_hash, request_id);
+        Ok(())
+    }
+
+    /// Queue a scheduled transaction, ordered by execution time
+    pub fn put_schedule(&mut self, scheduled: &ScheduledTransaction) -> Result<()> {
+        self.put_record(schedule_key(scheduled).as_bytes(), scheduled)
+    }
+
+    /// Take a scheduled transaction off the queue
+    pub fn remove_schedule(&mut self, scheduled: &ScheduledTransaction) {
+        self.delete(schedule_key(scheduled).as_bytes());
+    }
+
+    /// Stage the callback registered for a request
+    pub fn put_callback(&mut self, request_id: Uuid, callback: &CallbackDelivery) -> Result<()> {
+        self.put_record(callback_key(request_id).as_bytes(), callback)
+    }
+
+    /// Point a broadcast transaction hash at the request that sent it, and list it under
+    /// the request with the time it was last current
+    fn index_tx_hash(&mut self, tx_hash: &str, request_id: Uuid) {
+        let tx_hash = tx_hash.to_lowercase();
+        self.put(
+            format!("tx_index:{}", tx_hash).as_bytes(),
+            request_id.to_string().as_bytes(),
+        );
+        self.put(
+            format!("request_tx:{}:{}", request_id, tx_hash).as_bytes(),
+            Utc::now().timestamp_micros().to_string().as_bytes(),
+        );
+    }
+}
+
+/// Scheduler queue key; execution time first so a forward scan yields the most overdue first
+fn schedule_key(scheduled: &ScheduledTransaction) -> String {
+    let millis = u64::try_from(scheduled.execute_after.timestamp_millis()).unwrap_or(0);
+    format!("schedule:{:020}:{}", millis, scheduled.request_id)
+}
+
+fn callback_key(request_id: Uuid) -> String {
+    format!("callback:{}", request_id)
+}
+
+/// Index key of the raw transaction request holding `nonce` of `sender` on a chain
+fn sender_nonce_key(chain_id: u64, sender: &str, nonce: u64) -> String {
+    format!(
+        "sender_nonce:{}:{}:{}",
+        chain_id,
+        sender.to_lowercase(),
+        nonce
+    )
+}
+
+fn call_results_key(request_id: Uuid) -> String {
+    format!("call_results:{}", request_id)
+}
+
+fn receipt_key(request_id: Uuid) -> String {
+    format!("receipt:{}", request_id)
+}
+
+fn onchain_failure_key(request_id: Uuid) -> String {
+    format!("onchain_failure:{}", request_id)
+}
+
+/// Index key listing `wallet`'s requests; `position` orders them newest first. Each tenant
+/// has its own partition, so one tenant's listing never reaches another's requests.
+fn wallet_index_key(tenant: Option<&str>, wallet: &str, position: &str) -> String {
+    match tenant {
+        Some(tenant) => format!(
+            "tenant:{}:wallet_index:{}:{}",
+            tenant,
+            wallet.to_lowercase(),
+            position
+        ),
+        None => format!("wallet_index:{}:{}", wallet.to_lowercase(), position),
+    }
+}
+
+/// Position of a request within its wallet's index: inverted creation time, so a forward
+/// scan yields the newest request first, then the id to keep keys unique
+fn wallet_index_position(request: &RelayerRequest) -> String {
+    let millis = u64::try_from(request.created_at.timestamp_millis()).unwrap_or(0);
+    format!("{:020}:{}", u64::MAX - millis, request.id)
+}
+
+/// Gas tank balances are read, adjusted and written back; this keeps concurrent credits and
+/// debits from losing updates
+static GAS_TANK_LOCK: Mutex<()> = Mutex::new(());
+
+fn gas_tank_balance_key(chain_id: &str, wallet: &str) -> String {
+    format!("gas_tank_balance:{}:{}", chain_id, wallet.to_lowercase())
+}
+
+/// Marks a deposit transaction as credited so it cannot be claimed twice
+fn gas_tank_credited_key(chain_id: &str, tx_hash: &str) -> String {
+    format!("gas_tank_credited:{}:{}", chain_id, tx_hash.to_lowercase())
+}
+
+/// Marks a gas tank authorization as spent so the same signature cannot pay for two relays
+fn gas_tank_authorization_key(chain_id: &str, authorization: &str) -> String {
+    format!(
+        "gas_tank_authorization:{}:{}",
+        chain_id,
+        authorization.to_lowercase()
+    )
+}
+
+/// Debit a request took from a gas tank, kept so cancelling the request can return it
+fn gas_tank_debit_key(request_id: Uuid) -> String {
+    format!("gas_tank_debit:{}", request_id)
+}
+
+#[derive(Serialize, Deserialize)]
+struct GasTankDebit {
+    chain_id: String,
+    wallet: String,
+    amount: String,
+    authorization: String,
+}
+
+/// Sponsored gas counters are read, incremented and written back under this lock
+static SPONSORED_GAS_LOCK: Mutex<()> = Mutex::new(());
+
+fn sponsored_gas_key(project: &str, period: &str) -> String {
+    format!("sponsored_gas:{}:{}", project, period)
+}
+
+/// Recurring jobs are changed both by the scheduler and by callers pausing them; updates are
+/// read-modify-write under this lock so neither overwrites the other
+static RECURRING_JOB_LOCK: Mutex<()> = Mutex::new(());
+
+fn recurring_job_key(id: Uuid) -> String {
+    format!("recurring_job:{}", id)
+}
+
+/// Service lifetime figures are read, adjusted and written back under this lock
+static LIFETIME_STATS_LOCK: Mutex<()> = Mutex::new(());
+
+const LIFETIME_STATS_KEY: &[u8] = b"lifetime_stats";
+
+/// Submissions are claimed under this lock so two copies of a relay arriving together cannot
+/// both pass the duplicate check
+static SUBMISSION_LOCK: Mutex<()> = Mutex::new(());
+
+/// Latest request submitted with a fingerprint, and when
+fn submission_key(fingerprint: &str) -> String {
+    format!("submission:{}", fingerprint)
+}
+
+fn quote_key(id: &str) -> String {
+    format!("quote:{}", id)
+}
+
+/// Deposit history key; inverted credit time orders a wallet's deposits newest first
+fn gas_tank_deposit_key(deposit: &GasTankDeposit) -> String {
+    let millis = u64::try_from(deposit.credited_at.timestamp_millis()).unwrap_or(0);
+    format!(
+        "gas_tank_deposit:{}:{:020}:{}:{}",
+        deposit.wallet.to_lowercase(),
+        u64::MAX - millis,
+        deposit.chain_id,
+        deposit.transaction_hash.to_lowercase()
+    )
+}
+
+/// Overpayment log key; the recording time orders entries oldest first for range reports
+fn overpayment_key(millis: u64, id: Uuid) -> String {
+    format!("overpayment:{:020}:{}", millis, id)
+}
+
+/// Nonce healing log key; inverted healing time orders entries newest first
+fn nonce_healing_key(healing: &NonceHealing) -> String {
+    let millis = u64::try_from(healing.healed_at.timestamp_millis()).unwrap_or(0);
+    format!(
+        "nonce_healing:{:020}:{}:{}",
+        u64::MAX - millis,
+        healing.chain_id,
+        healing.nonce
+    )
+}
+
+fn fee_history_head_key(chain_id: u64, token: &str) -> String {
+    format!("fee_history_head:{}:{}", chain_id, token.to_lowercase())
+}
+
+fn fee_sample_key(chain_id: u64, token: &str, slot: u64) -> String {
+    format!(
+        "fee_sample:{}:{}:{:05}",
+        chain_id,
+        token.to_lowercase(),
+        slot
+    )
+}
+
+/// Sum of a ticker's counts in a RocksDB statistics dump, where each ticker is a line such as
+/// `rocksdb.block.cache.hit COUNT : 42`
+fn ticker_count(statistics: &str, ticker: &str) -> Option<u64> {
+    statistics.lines().find_map(|line| {
+        let rest = line.strip_prefix(ticker)?.strip_prefix(" COUNT : ")?;
+        rest.trim().parse().ok()
+    })
+}
+
+/// Share of block cache lookups that hit, from a RocksDB statistics dump
+fn block_cache_hit_rate(statistics: &str) -> Option<f64> {
+    let hits = ticker_count(statistics, "rocksdb.block.cache.hit")?;
+    let misses = ticker_count(statistics, "rocksdb.block.cache.miss")?;
+    let lookups = hits + misses;
+    (lookups > 0).then(|| hits as f64 / lookups as f64)
+}
+
 impl Storage {
+    /// Open the database with default tuning
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        tracing::debug!("Opening RocksDB database at: {:?}", path.as_ref());
+        Self::with_tuning(path, &StorageTuning::default())
+    }
+
+    /// Open the database with the block cache, memtable, compression and statistics settings
+    /// of `tuning`
+    pub fn with_tuning<P: AsRef<Path>>(path: P, tuning: &StorageTuning) -> Result<Self> {
+        tracing::debug!(
+            "Opening RocksDB database at: {:?} ({:?})",
+            path.as_ref(),
+            tuning
+        );
 
         let mut opts = Options::default();
         opts.create_if_missing(true);
-        opts.set_max_open_files(10000);
+        opts.create_missing_column_families(true);
+        opts.set_max_open_files(tuning.max_open_files);
         opts.set_use_fsync(false);
         opts.set_bytes_per_sync(1024 * 1024);
+        opts.set_write_buffer_size(tuning.write_buffer_bytes);
+        opts.set_compression_type(match tuning.compression {
+            StorageCompression::None => DBCompressionType::None,
+            StorageCompression::Snappy => DBCompressionType::Snappy,
+            StorageCompression::Lz4 => DBCompressionType::Lz4,
+            StorageCompression::Zstd => DBCompressionType::Zstd,
+        });
+        let mut table = BlockBasedOptions::default();
+        table.set_block_cache(&Cache::new_lru_cache(tuning.block_cache_bytes));
+        opts.set_block_based_table_factory(&table);
+        if tuning.statistics {
+            opts.enable_statistics();
+        }
 
-        let db = DBWithThreadMode::<MultiThreaded>::open(&opts, path.as_ref()).map_err(|e| {
-            tracing::error!("Failed to open RocksDB database: {}", e);
-            e
-        })?;
+        // Archived records are written once and rarely read, so they trade CPU for space
+        let mut archive_opts = Options::default();
+        archive_opts.set_compression_type(DBCompressionType::Zstd);
+        let archive = ColumnFamilyDescriptor::new(ARCHIVE_CF, archive_opts);
+
+        let db =
+            DBWithThreadMode::<MultiThreaded>::open_cf_descriptors(&opts, path.as_ref(), [archive])
+                .map_err(|e| {
+                    tracing::error!("Failed to open RocksDB database: {}", e);
+                    e
+                })?;
 
         tracing::debug!("RocksDB database opened successfully");
 
         Ok(Self {
             db: Arc::new(db),
+            options: Arc::new(opts),
+            cipher: None,
             start_time: std::time::Instant::now(),
         })
     }
 
+    /// Seal request records written from now on with `key`, and open sealed ones with it
+    pub fn with_storage_key(mut self, key: &StorageKey) -> Self {
+        self.cipher = Some(Arc::new(RecordCipher::new(key)));
+        self
+    }
+
+    /// Serialize a request record stored under `key`, sealed when a storage key is set
+    fn encode_record<T: Serialize + ?Sized>(&self, key: &[u8], value: &T) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(value)?;
+        match &self.cipher {
+            Some(cipher) => cipher.seal(key, &json),
+            None => Ok(json),
+        }
+    }
+
+    /// Deserialize a request record stored under `key`, whether sealed or plaintext
+    fn decode_record<T: DeserializeOwned>(&self, key: &[u8], value: &[u8]) -> Result<T> {
+        if !is_sealed(value) {
+            return Ok(serde_json::from_slice(value)?);
+        }
+        let cipher = self.cipher.as_ref().ok_or_else(|| {
+            anyhow!(
+                "record {} is encrypted; set RELAYX_STORAGE_KEY or RELAYX_STORAGE_KEY_FILE",
+                String::from_utf8_lossy(key)
+            )
+        })?;
+        Ok(serde_json::from_slice(&cipher.open(key, value)?)?)
+    }
+
+    fn archive(&self) -> Result<Arc<BoundColumnFamily<'_>>> {
+        self.db
+            .cf_handle(ARCHIVE_CF)
+            .ok_or_else(|| anyhow!("column family {} is missing", ARCHIVE_CF))
+    }
+
+    /// Value stored under `key`, read from the archive when its request has been archived
+    fn get_stored(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        match self.db.get(key)? {
+            Some(value) => Ok(Some(value)),
+            None => Ok(self.db.get_cf(&self.archive()?, key)?),
+        }
+    }
+
+    /// Current SST, memtable, compaction and block cache figures
+    pub fn stats(&self) -> StorageStats {
+        let property = |name: &str| match self.db.property_int_value(name) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::debug!("RocksDB property {} unavailable: {}", name, e);
+                None
+            }
+        };
+        StorageStats {
+            sst_files_bytes: property("rocksdb.total-sst-files-size"),
+            memtable_bytes: property("rocksdb.cur-size-all-mem-tables"),
+            pending_compaction_bytes: property("rocksdb.estimate-pending-compaction-bytes"),
+            running_compactions: property("rocksdb.num-running-compactions"),
+            block_cache_usage_bytes: property("rocksdb.block-cache-usage"),
+            block_cache_capacity_bytes: property("rocksdb.block-cache-capacity"),
+            block_cache_hit_rate: self
+                .options
+                .get_statistics()
+                .as_deref()
+                .and_then(block_cache_hit_rate),
+        }
+    }
+
     /// Store a new relayer request
     pub async fn store_request(&self, request: &RelayerRequest) -> Result<()> {
         let key = format!("request:{}", request.id);
         tracing::trace!("Storing request with key: {}", key);
 
-        let value = serde_json::to_string(request).map_err(|e| {
+        let value = self.encode_record(key.as_bytes(), request).map_err(|e| {
             tracing::error!("Failed to serialize request: {}", e);
             e
         })?;
 
-        self.db.put(key.as_bytes(), value.as_bytes()).map_err(|e| {
+        self.db.put(key.as_bytes(), value).map_err(|e| {
             tracing::error!("Failed to store request {}: {}", request.id, e);
             e
         })?;
@@ -62,20 +514,55 @@ impl Storage {
             request.status,
             request.chain_id
         );
-        self.store_request(&request).await
+        let mut batch = StorageBatch::new();
+        batch.put_request(&request)?;
+        self.commit(batch).await
+    }
+
+    /// Apply every write staged in `batch` atomically
+    pub async fn commit(&self, batch: StorageBatch) -> Result<()> {
+        tracing::trace!("Committing storage batch of {} writes", batch.writes.len());
+        let mut write = WriteBatch::default();
+        for staged in batch.writes {
+            match staged {
+                StagedWrite::Put(key, value) => write.put(key, value),
+                StagedWrite::Record(key, json) => match &self.cipher {
+                    Some(cipher) => write.put(&key, cipher.seal(&key, &json)?),
+                    None => write.put(key, json),
+                },
+                StagedWrite::Delete(key) => write.delete(key),
+            }
+        }
+        self.db.write(write).map_err(|e| {
+            tracing::error!("Failed to commit storage batch: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+
+    /// Retrieve a relayer request by ID; a request outside `scope` reads as missing
+    pub async fn get_request(
+        &self,
+        scope: TenantScope<'_>,
+        id: Uuid,
+    ) -> Result<Option<RelayerRequest>> {
+        Ok(self
+            .load_request(id)
+            .await?
+            .filter(|request| scope.admits(request.tenant.as_deref())))
     }
 
-    /// Retrieve a relayer request by ID
-    pub async fn get_request(&self, id: Uuid) -> Result<Option<RelayerRequest>> {
+    async fn load_request(&self, id: Uuid) -> Result<Option<RelayerRequest>> {
         let key = format!("request:{}", id);
         tracing::trace!("Retrieving request with key: {}", key);
 
-        match self.db.get(key.as_bytes())? {
+        match self.get_stored(key.as_bytes())? {
             Some(value) => {
-                let request: RelayerRequest = serde_json::from_slice(&value).map_err(|e| {
-                    tracing::error!("Failed to deserialize request {}: {}", id, e);
-                    e
-                })?;
+                let request: RelayerRequest =
+                    self.decode_record(key.as_bytes(), &value).map_err(|e| {
+                        tracing::error!("Failed to deserialize request {}: {}", id, e);
+                        e
+                    })?;
                 tracing::trace!(
                     "Request {} retrieved successfully - Status: {:?}",
                     id,
@@ -93,9 +580,9 @@ impl Storage {
     /// Store a relayer response
     pub async fn store_response(&self, response: &RelayerResponse) -> Result<()> {
         let key = format!("response:{}", response.request_id);
-        let value = serde_json::to_string(response)?;
+        let value = self.encode_record(key.as_bytes(), response)?;
 
-        self.db.put(key.as_bytes(), value.as_bytes())?;
+        self.db.put(key.as_bytes(), value)?;
         Ok(())
     }
 
@@ -103,9 +590,9 @@ impl Storage {
     pub async fn get_response(&self, request_id: Uuid) -> Result<Option<RelayerResponse>> {
         let key = format!("response:{}", request_id);
 
-        match self.db.get(key.as_bytes())? {
+        match self.get_stored(key.as_bytes())? {
             Some(value) => {
-                let response: RelayerResponse = serde_json::from_slice(&value)?;
+                let response: RelayerResponse = self.decode_record(key.as_bytes(), &value)?;
                 Ok(Some(response))
             }
             None => Ok(None),
@@ -121,7 +608,7 @@ impl Storage {
     ) -> Result<()> {
         tracing::debug!("Updating request {} status to: {:?}", id, status);
 
-        if let Some(mut request) = self.get_request(id).await? {
+        if let Some(mut request) = self.load_request(id).await? {
             let old_status = request.status.clone();
             request.status = status;
             request.updated_at = chrono::Utc::now();
@@ -144,14 +631,46 @@ impl Storage {
         Ok(())
     }
 
+    /// Move a request whose transaction was found mined to its final status, recording
+    /// when it was found and, for blob transactions, the blob gas used and its price
+    pub async fn record_mined(
+        &self,
+        id: Uuid,
+        status: RequestStatus,
+        error_message: Option<String>,
+        blob_gas: Option<(u64, u128)>,
+    ) -> Result<()> {
+        if let Some(mut request) = self.load_request(id).await? {
+            let now = chrono::Utc::now();
+            tracing::info!("Request {} mined: {:?} -> {:?}", id, request.status, status);
+            request.status = status;
+            request.updated_at = now;
+            request.mined_at = Some(now);
+            request.error_message = error_message;
+            if let Some((used, price)) = blob_gas {
+                request.blob_gas_used = Some(used);
+                request.blob_gas_price = Some(format!("0x{:x}", price));
+            }
+            self.store_request(&request).await?;
+        } else {
+            tracing::warn!(
+                "Attempted to record receipt for non-existent request: {}",
+                id
+            );
+        }
+        Ok(())
+    }
+
     /// Update request transaction hash
     pub async fn update_request_tx_hash(&self, id: Uuid, tx_hash: String) -> Result<()> {
         tracing::debug!("Updating request {} tx hash to: {}", id, tx_hash);
 
-        if let Some(mut request) = self.get_request(id).await? {
+        if let Some(mut request) = self.load_request(id).await? {
             request.transaction_hash = Some(tx_hash);
             request.updated_at = chrono::Utc::now();
-            self.store_request(&request).await?;
+            let mut batch = StorageBatch::new();
+            batch.put_request(&request)?;
+            self.commit(batch).await?;
             tracing::info!("Request {} transaction hash stored", id);
         } else {
             tracing::warn!("Attempted to set tx hash for non-existent request: {}", id);
@@ -162,7 +681,7 @@ impl Storage {
     /// Update request nonce
     pub async fn update_request_nonce(&self, id: Uuid, nonce: u64) -> Result<()> {
         tracing::debug!("Updating request {} nonce to: {}", id, nonce);
-        if let Some(mut request) = self.get_request(id).await? {
+        if let Some(mut request) = self.load_request(id).await? {
             request.nonce = nonce;
             request.updated_at = chrono::Utc::now();
             self.store_request(&request).await?;
@@ -175,19 +694,40 @@ impl Storage {
 
     /// Record a resubmission attempt for a request
     pub async fn add_resubmission(&self, request_id: Uuid, resub: &Resubmission) -> Result<()> {
-        let key = format!(
-            "resubmission:{}:{}:{}",
-            request_id, resub.chain_id, resub.transaction_hash
-        );
-        let value = serde_json::to_string(resub)?;
-        self.db.put(key.as_bytes(), value.as_bytes())?;
-        Ok(())
+        let mut batch = StorageBatch::new();
+        batch.add_resubmission(request_id, resub)?;
+        self.commit(batch).await
     }
 
-    /// Retrieve resubmissions for a request
-    pub async fn get_resubmissions(&self, request_id: Uuid) -> Result<Vec<Resubmission>> {
+    /// Find the request that broadcast `tx_hash`, including resubmitted hashes
+    pub async fn get_request_id_by_tx_hash(&self, tx_hash: &str) -> Result<Option<Uuid>> {
+        let key = format!("tx_index:{}", tx_hash.to_lowercase());
+        match self.db.get(key.as_bytes())? {
+            Some(value) => Ok(Uuid::parse_str(&String::from_utf8_lossy(&value)).ok()),
+            None => Ok(None),
+        }
+    }
+
+    /// Find the raw transaction request that broadcast `nonce` of `sender` on a chain
+    pub async fn get_request_id_by_sender_nonce(
+        &self,
+        chain_id: u64,
+        sender: &str,
+        nonce: u64,
+    ) -> Result<Option<Uuid>> {
+        let key = sender_nonce_key(chain_id, sender, nonce);
+        match self.db.get(key.as_bytes())? {
+            Some(value) => Ok(Uuid::parse_str(&String::from_utf8_lossy(&value)).ok()),
+            None => Ok(None),
+        }
+    }
+
+    /// Every transaction hash broadcast for a request, oldest first. A replaced hash stops
+    /// being rewritten once its successor is recorded, so ordering by the last write time
+    /// gives broadcast order.
+    pub async fn get_transaction_hashes(&self, request_id: Uuid) -> Result<Vec<String>> {
         let mut items = Vec::new();
-        let prefix = format!("resubmission:{}:", request_id);
+        let prefix = format!("request_tx:{}:", request_id);
         let iter = self.db.iterator(rocksdb::IteratorMode::From(
             prefix.as_bytes(),
             rocksdb::Direction::Forward,
@@ -195,87 +735,1061 @@ impl Storage {
         for result in iter {
             let (key, value) = result?;
             let key_str = String::from_utf8_lossy(&key);
-            if !key_str.starts_with(&prefix) {
+            let Some(tx_hash) = key_str.strip_prefix(&prefix) else {
                 break;
-            }
-            if let Ok(resub) = serde_json::from_slice::<Resubmission>(&value) {
-                items.push(resub);
+            };
+            let written_at = String::from_utf8_lossy(&value).parse::<i64>().unwrap_or(0);
+            items.push((written_at, tx_hash.to_string()));
+        }
+        items.sort();
+        Ok(items.into_iter().map(|(_, tx_hash)| tx_hash).collect())
+    }
+
+    /// Retrieve resubmissions for a request, oldest first
+    pub async fn get_resubmissions(&self, request_id: Uuid) -> Result<Vec<Resubmission>> {
+        Ok(self
+            .resubmission_records(request_id)
+            .await?
+            .into_iter()
+            .map(|(_, resub)| resub)
+            .collect())
+    }
+
+    /// Resubmission records of a request with their keys, in broadcast order. Records are
+    /// keyed by hash, so the order comes from the request's transaction hash list.
+    async fn resubmission_records(&self, request_id: Uuid) -> Result<Vec<(Vec<u8>, Resubmission)>> {
+        let broadcast_order = self.get_transaction_hashes(request_id).await?;
+        let mut items = Vec::new();
+        let prefix = format!("resubmission:{}:", request_id);
+        let start = rocksdb::IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward);
+        let archive = self.archive()?;
+        for iter in [
+            self.db.iterator(start),
+            self.db.iterator_cf(&archive, start),
+        ] {
+            for result in iter {
+                let (key, value) = result?;
+                if !key.starts_with(prefix.as_bytes()) {
+                    break;
+                }
+                if let Ok(resub) = serde_json::from_slice::<Resubmission>(&value) {
+                    items.push((key.to_vec(), resub));
+                }
             }
         }
+        items.sort_by_cached_key(|(_, resub)| {
+            broadcast_order
+                .iter()
+                .position(|tx_hash| tx_hash.eq_ignore_ascii_case(&resub.transaction_hash))
+        });
         Ok(items)
     }
 
-    /// Get all requests with optional filtering
-    pub async fn get_requests(&self, limit: Option<usize>) -> Result<Vec<RelayerRequest>> {
-        tracing::debug!("Retrieving requests with limit: {:?}", limit);
+    /// Drop the resubmission records of a request other than its first and its `keep_last`
+    /// latest, together with the dropped hashes' entries in its transaction hash list.
+    /// Looking a request up by a dropped hash keeps working. Returns how many were dropped.
+    pub async fn compact_resubmissions(&self, request_id: Uuid, keep_last: usize) -> Result<usize> {
+        let records = self.resubmission_records(request_id).await?;
+        let Some(end) = records.len().checked_sub(keep_last).filter(|end| *end > 1) else {
+            return Ok(0);
+        };
+        let mut batch = StorageBatch::new();
+        for (key, resub) in &records[1..end] {
+            batch.delete(key);
+            batch.delete(
+                format!(
+                    "request_tx:{}:{}",
+                    request_id,
+                    resub.transaction_hash.to_lowercase()
+                )
+                .as_bytes(),
+            );
+        }
+        self.commit(batch).await?;
+        tracing::debug!(
+            "Compacted {} resubmission record(s) of request {}",
+            end - 1,
+            request_id
+        );
+        Ok(end - 1)
+    }
 
-        let mut requests = Vec::new();
+    /// Up to `limit` scheduled transactions due at or before `now`, earliest first
+    pub async fn get_due_schedules(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ScheduledTransaction>> {
         let iter = self.db.iterator(rocksdb::IteratorMode::From(
-            b"request:",
+            b"schedule:",
             rocksdb::Direction::Forward,
         ));
-
+        let mut due = Vec::new();
         for result in iter {
             let (key, value) = result?;
-            let key_str = String::from_utf8_lossy(&key);
-
-            if key_str.starts_with("request:") {
-                if let Ok(request) = serde_json::from_slice::<RelayerRequest>(&value) {
-                    requests.push(request);
-
-                    if let Some(limit) = limit {
-                        if requests.len() >= limit {
-                            break;
-                        }
-                    }
-                }
-            } else {
+            if !key.starts_with(b"schedule:") || due.len() >= limit {
+                break;
+            }
+            let scheduled: ScheduledTransaction = self.decode_record(&key, &value)?;
+            if scheduled.execute_after > now {
                 break;
             }
+            due.push(scheduled);
         }
-
-        tracing::debug!("Retrieved {} requests", requests.len());
-        Ok(requests)
+        Ok(due)
     }
 
-    /// Get request count by status
-    pub async fn get_request_count_by_status(&self, status: RequestStatus) -> Result<u64> {
-        tracing::trace!("Counting requests with status: {:?}", status);
+    /// Callback registered for a request and its delivery state
+    pub async fn get_callback(&self, request_id: Uuid) -> Result<Option<CallbackDelivery>> {
+        let key = callback_key(request_id);
+        match self.get_stored(key.as_bytes())? {
+            Some(value) => Ok(Some(self.decode_record(key.as_bytes(), &value)?)),
+            None => Ok(None),
+        }
+    }
 
-        let mut count = 0;
+    /// Requests whose callback is still pending delivery
+    pub async fn get_pending_callbacks(&self) -> Result<Vec<Uuid>> {
         let iter = self.db.iterator(rocksdb::IteratorMode::From(
-            b"request:",
+            b"callback:",
             rocksdb::Direction::Forward,
         ));
-
+        let mut pending = Vec::new();
         for result in iter {
             let (key, value) = result?;
-            let key_str = String::from_utf8_lossy(&key);
-
-            if !key_str.starts_with("request:") {
+            if !key.starts_with(b"callback:") {
                 break;
             }
-
-            if let Ok(request) = serde_json::from_slice::<RelayerRequest>(&value) {
-                if request.status == status {
-                    count += 1;
-                }
+            let callback: CallbackDelivery = self.decode_record(&key, &value)?;
+            if callback.status != CallbackStatus::Pending {
+                continue;
+            }
+            if let Ok(id) = Uuid::parse_str(&String::from_utf8_lossy(&key["callback:".len()..])) {
+                pending.push(id);
             }
         }
+        Ok(pending)
+    }
 
-        tracing::trace!("Found {} requests with status {:?}", count, status);
-        Ok(count)
+    /// Record the outcome of a callback delivery attempt
+    pub async fn update_callback(
+        &self,
+        request_id: Uuid,
+        callback: &CallbackDelivery,
+    ) -> Result<()> {
+        let key = callback_key(request_id);
+        let value = self.encode_record(key.as_bytes(), callback)?;
+        self.db.put(key.as_bytes(), value)?;
+        Ok(())
     }
 
-    /// Get total request count
-    pub async fn get_total_request_count(&self) -> Result<u64> {
-        tracing::trace!("Counting total requests");
+    /// Record the per-call outcomes of a `relayer_sendCalls` batch
+    pub async fn store_call_results(&self, request_id: Uuid, calls: &[CallResult]) -> Result<()> {
+        let key = call_results_key(request_id);
+        let value = self.encode_record(key.as_bytes(), calls)?;
+        self.db.put(key.as_bytes(), value)?;
+        Ok(())
+    }
 
-        let mut count = 0;
-        let iter = self.db.iterator(rocksdb::IteratorMode::From(
-            b"request:",
-            rocksdb::Direction::Forward,
-        ));
+    /// Per-call outcomes of a `relayer_sendCalls` batch, if the request was one
+    pub async fn get_call_results(&self, request_id: Uuid) -> Result<Option<Vec<CallResult>>> {
+        let key = call_results_key(request_id);
+        match self.get_stored(key.as_bytes())? {
+            Some(value) => Ok(Some(self.decode_record(key.as_bytes(), &value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Record the receipt of the transaction a request was finalized by
+    pub async fn store_receipt(&self, request_id: Uuid, receipt: &Receipt) -> Result<()> {
+        let key = receipt_key(request_id);
+        let value = self.encode_record(key.as_bytes(), receipt)?;
+        self.db.put(key.as_bytes(), value)?;
+        Ok(())
+    }
+
+    /// Receipt of the transaction a request was finalized by, once it has been mined
+    pub async fn get_receipt(&self, request_id: Uuid) -> Result<Option<Receipt>> {
+        let key = receipt_key(request_id);
+        match self.get_stored(key.as_bytes())? {
+            Some(value) => Ok(Some(self.decode_record(key.as_bytes(), &value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Record why the transaction a request was finalized by reverted
+    pub async fn store_onchain_failure(
+        &self,
+        request_id: Uuid,
+        failure: &OnchainFailure,
+    ) -> Result<()> {
+        let key = onchain_failure_key(request_id);
+        let value = self.encode_record(key.as_bytes(), failure)?;
+        self.db.put(key.as_bytes(), value)?;
+        Ok(())
+    }
+
+    /// Why the transaction a request was finalized by reverted, if it did
+    pub async fn get_onchain_failure(&self, request_id: Uuid) -> Result<Option<OnchainFailure>> {
+        let key = onchain_failure_key(request_id);
+        match self.get_stored(key.as_bytes())? {
+            Some(value) => Ok(Some(self.decode_record(key.as_bytes(), &value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Store a multichain bundle record
+    pub async fn store_bundle(&self, bundle: &Bundle) -> Result<()> {
+        let key = format!("bundle:{}", bundle.id);
+        tracing::trace!("Storing bundle with key: {}", key);
+
+        let value = serde_json::to_string(bundle)?;
+        self.db.put(key.as_bytes(), value.as_bytes()).map_err(|e| {
+            tracing::error!("Failed to store bundle {}: {}", bundle.id, e);
+            e
+        })?;
+        Ok(())
+    }
+
+    /// Retrieve a multichain bundle record by ID
+    pub async fn get_bundle(&self, id: Uuid) -> Result<Option<Bundle>> {
+        let key = format!("bundle:{}", id);
+
+        match self.db.get(key.as_bytes())? {
+            Some(value) => {
+                let bundle: Bundle = serde_json::from_slice(&value)?;
+                Ok(Some(bundle))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieve the requests in `scope` belonging to a multichain bundle via the bundle index
+    pub async fn get_requests_by_bundle(
+        &self,
+        scope: TenantScope<'_>,
+        bundle_id: Uuid,
+    ) -> Result<Vec<RelayerRequest>> {
+        let mut requests = Vec::new();
+        let prefix = format!("bundle_index:{}:", bundle_id);
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        for result in iter {
+            let (key, value) = result?;
+            let key_str = String::from_utf8_lossy(&key);
+            if !key_str.starts_with(&prefix) {
+                break;
+            }
+            let request_id = match Uuid::parse_str(&String::from_utf8_lossy(&value)) {
+                Ok(id) => id,
+                Err(_) => continue,
+            };
+            if let Some(request) = self.get_request(scope, request_id).await? {
+                requests.push(request);
+            }
+        }
+        Ok(requests)
+    }
+
+    /// Page through the requests `tenant` sent to `wallet`, newest first, via the wallet
+    /// index.
+    ///
+    /// Returns up to `limit` requests accepted by `filter`, starting after `cursor` (a
+    /// value previously returned by this method), plus the cursor for the next page when
+    /// the page is full.
+    pub async fn get_requests_by_wallet(
+        &self,
+        tenant: Option<&str>,
+        wallet: &str,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: impl Fn(&RelayerRequest) -> bool,
+    ) -> Result<(Vec<RelayerRequest>, Option<String>)> {
+        let prefix = wallet_index_key(tenant, wallet, "");
+        let start = wallet_index_key(tenant, wallet, cursor.unwrap_or(""));
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            start.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+
+        let mut requests = Vec::new();
+        for result in iter {
+            let (key, value) = result?;
+            let key_str = String::from_utf8_lossy(&key);
+            if !key_str.starts_with(&prefix) {
+                break;
+            }
+            // The cursor names the last request of the previous page
+            if cursor.is_some() && *key_str == start {
+                continue;
+            }
+            let request_id = match Uuid::parse_str(&String::from_utf8_lossy(&value)) {
+                Ok(id) => id,
+                Err(_) => continue,
+            };
+            // Read synchronously: the iterator must not be held across an await
+            let request_key = format!("request:{}", request_id);
+            let request = match self.get_stored(request_key.as_bytes())? {
+                Some(value) => {
+                    self.decode_record::<RelayerRequest>(request_key.as_bytes(), &value)?
+                }
+                None => continue,
+            };
+            if !filter(&request) {
+                continue;
+            }
+            requests.push(request);
+            if requests.len() >= limit {
+                return Ok((requests, Some(key_str[prefix.len()..].to_string())));
+            }
+        }
+        Ok((requests, None))
+    }
+
+    /// Append a fee sample to the chain and token's ring buffer, overwriting the oldest
+    /// sample once [`FEE_HISTORY_SLOTS`] are filled
+    pub async fn record_fee_sample(
+        &self,
+        chain_id: u64,
+        token: &str,
+        sample: &FeeSample,
+    ) -> Result<()> {
+        let head_key = fee_history_head_key(chain_id, token);
+        let head = self.read_fee_history_head(&head_key)?;
+
+        let mut batch = StorageBatch::new();
+        batch.put(
+            fee_sample_key(chain_id, token, head % FEE_HISTORY_SLOTS).as_bytes(),
+            serde_json::to_string(sample)?.as_bytes(),
+        );
+        batch.put(head_key.as_bytes(), (head + 1).to_string().as_bytes());
+        self.commit(batch).await
+    }
+
+    /// Up to `limit` of the most recent fee samples for a chain and token, oldest first
+    pub async fn get_fee_samples(
+        &self,
+        chain_id: u64,
+        token: &str,
+        limit: usize,
+    ) -> Result<Vec<FeeSample>> {
+        let head = self.read_fee_history_head(&fee_history_head_key(chain_id, token))?;
+        let count = head.min(FEE_HISTORY_SLOTS).min(limit as u64);
+
+        let mut samples = Vec::with_capacity(count as usize);
+        for sequence in head - count..head {
+            let key = fee_sample_key(chain_id, token, sequence % FEE_HISTORY_SLOTS);
+            if let Some(value) = self.db.get(key.as_bytes())? {
+                samples.push(serde_json::from_slice(&value)?);
+            }
+        }
+        Ok(samples)
+    }
+
+    /// Number of samples ever recorded under `head_key`
+    fn read_fee_history_head(&self, head_key: &str) -> Result<u64> {
+        Ok(self
+            .db
+            .get(head_key.as_bytes())?
+            .and_then(|value| String::from_utf8_lossy(&value).parse().ok())
+            .unwrap_or(0))
+    }
+
+    /// Prepaid gas balance of `wallet` on a chain, in wei
+    pub async fn get_gas_tank_balance(&self, chain_id: &str, wallet: &str) -> Result<u128> {
+        self.read_gas_tank_balance(&gas_tank_balance_key(chain_id, wallet))
+    }
+
+    fn read_gas_tank_balance(&self, key: &str) -> Result<u128> {
+        match self.db.get(key.as_bytes())? {
+            Some(value) => Ok(String::from_utf8_lossy(&value).parse()?),
+            None => Ok(0),
+        }
+    }
+
+    /// Credit a deposit to its wallet and record it in the deposit history, in one write.
+    /// Returns the new balance, or `None` if the deposit transaction was already credited.
+    pub async fn credit_gas_tank_deposit(&self, deposit: &GasTankDeposit) -> Result<Option<u128>> {
+        let amount: u128 = deposit.amount.parse()?;
+        let _guard = GAS_TANK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let credited_key = gas_tank_credited_key(&deposit.chain_id, &deposit.transaction_hash);
+        if self.db.get(credited_key.as_bytes())?.is_some() {
+            return Ok(None);
+        }
+        let balance_key = gas_tank_balance_key(&deposit.chain_id, &deposit.wallet);
+        let balance = self
+            .read_gas_tank_balance(&balance_key)?
+            .checked_add(amount)
+            .ok_or_else(|| anyhow::anyhow!("gas tank balance overflow"))?;
+
+        let mut batch = WriteBatch::default();
+        batch.put(balance_key.as_bytes(), balance.to_string().as_bytes());
+        batch.put(credited_key.as_bytes(), deposit.wallet.as_bytes());
+        batch.put(
+            gas_tank_deposit_key(deposit).as_bytes(),
+            serde_json::to_string(deposit)?.as_bytes(),
+        );
+        self.db.write(batch)?;
+        Ok(Some(balance))
+    }
+
+    /// Whether a gas tank authorization already paid for a relay
+    pub async fn is_gas_tank_authorization_spent(
+        &self,
+        chain_id: &str,
+        authorization: &str,
+    ) -> Result<bool> {
+        let key = gas_tank_authorization_key(chain_id, authorization);
+        Ok(self.db.get(key.as_bytes())?.is_some())
+    }
+
+    /// Take `amount` wei from a wallet's balance under `authorization` to pay for
+    /// `request_id`, marking it spent and recording the debit in the same write. Returns the
+    /// remaining balance, or `None` (leaving the balance untouched) when it cannot cover the
+    /// amount. Fails if the authorization was spent.
+    pub async fn debit_gas_tank(
+        &self,
+        chain_id: &str,
+        wallet: &str,
+        amount: u128,
+        authorization: &str,
+        request_id: Uuid,
+    ) -> Result<Option<u128>> {
+        let _guard = GAS_TANK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let authorization_key = gas_tank_authorization_key(chain_id, authorization);
+        if self.db.get(authorization_key.as_bytes())?.is_some() {
+            return Err(anyhow!(
+                "gas tank authorization {} already spent",
+                authorization
+            ));
+        }
+        let key = gas_tank_balance_key(chain_id, wallet);
+        let Some(remaining) = self.read_gas_tank_balance(&key)?.checked_sub(amount) else {
+            return Ok(None);
+        };
+        let mut batch = WriteBatch::default();
+        batch.put(key.as_bytes(), remaining.to_string().as_bytes());
+        batch.put(
+            authorization_key.as_bytes(),
+            wallet.to_lowercase().as_bytes(),
+        );
+        let debit = GasTankDebit {
+            chain_id: chain_id.to_string(),
+            wallet: wallet.to_lowercase(),
+            amount: amount.to_string(),
+            authorization: authorization.to_string(),
+        };
+        batch.put(
+            gas_tank_debit_key(request_id).as_bytes(),
+            serde_json::to_vec(&debit)?,
+        );
+        self.db.write(batch)?;
+        Ok(Some(remaining))
+    }
+
+    /// Return the debit `request_id` took when it was not spent, such as for a relay that
+    /// failed to broadcast or was cancelled, and release its authorization so it can be
+    /// submitted again. Returns the refunded amount, or `None` when the request holds no
+    /// debit, so a debit is returned at most once.
+    pub async fn refund_gas_tank(&self, request_id: Uuid) -> Result<Option<u128>> {
+        let _guard = GAS_TANK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let debit_key = gas_tank_debit_key(request_id);
+        let Some(value) = self.db.get(debit_key.as_bytes())? else {
+            return Ok(None);
+        };
+        let debit: GasTankDebit = serde_json::from_slice(&value)?;
+        let amount: u128 = debit.amount.parse()?;
+        let key = gas_tank_balance_key(&debit.chain_id, &debit.wallet);
+        let balance = self.read_gas_tank_balance(&key)?.saturating_add(amount);
+        let mut batch = WriteBatch::default();
+        batch.put(key.as_bytes(), balance.to_string().as_bytes());
+        batch.delete(gas_tank_authorization_key(&debit.chain_id, &debit.authorization).as_bytes());
+        batch.delete(debit_key.as_bytes());
+        self.db.write(batch)?;
+        Ok(Some(amount))
+    }
+
+    /// Up to `limit` deposits credited to `wallet` matching `filter`, newest first
+    pub async fn get_gas_tank_deposits(
+        &self,
+        wallet: &str,
+        limit: usize,
+        filter: impl Fn(&GasTankDeposit) -> bool,
+    ) -> Result<Vec<GasTankDeposit>> {
+        let prefix = format!("gas_tank_deposit:{}:", wallet.to_lowercase());
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        let mut deposits = Vec::new();
+        for result in iter {
+            let (key, value) = result?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let deposit: GasTankDeposit = serde_json::from_slice(&value)?;
+            if !filter(&deposit) {
+                continue;
+            }
+            deposits.push(deposit);
+            if deposits.len() >= limit {
+                break;
+            }
+        }
+        Ok(deposits)
+    }
+
+    /// Sponsored gas charged to `project` in a billing period
+    pub async fn get_sponsored_gas_used(&self, project: &str, period: &str) -> Result<u64> {
+        self.read_sponsored_gas(&sponsored_gas_key(project, period))
+    }
+
+    fn read_sponsored_gas(&self, key: &str) -> Result<u64> {
+        match self.db.get(key.as_bytes())? {
+            Some(value) => Ok(String::from_utf8_lossy(&value).parse()?),
+            None => Ok(0),
+        }
+    }
+
+    /// Charge `gas` to a project's usage for the period. Returns the new usage, or `None`
+    /// (leaving the usage untouched) when it would exceed `allowance`.
+    pub async fn reserve_sponsored_gas(
+        &self,
+        project: &str,
+        period: &str,
+        gas: u64,
+        allowance: u64,
+    ) -> Result<Option<u64>> {
+        let _guard = SPONSORED_GAS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let key = sponsored_gas_key(project, period);
+        let used = self.read_sponsored_gas(&key)?.saturating_add(gas);
+        if used > allowance {
+            return Ok(None);
+        }
+        self.db.put(key.as_bytes(), used.to_string().as_bytes())?;
+        Ok(Some(used))
+    }
+
+    /// Return a charge that was not spent, such as for a relay that failed to broadcast
+    pub async fn release_sponsored_gas(
+        &self,
+        project: &str,
+        period: &str,
+        gas: u64,
+    ) -> Result<u64> {
+        let _guard = SPONSORED_GAS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let key = sponsored_gas_key(project, period);
+        let used = self.read_sponsored_gas(&key)?.saturating_sub(gas);
+        self.db.put(key.as_bytes(), used.to_string().as_bytes())?;
+        Ok(used)
+    }
+
+    /// Record a nonce gap filled by the monitor
+    pub async fn store_nonce_healing(&self, healing: &NonceHealing) -> Result<()> {
+        let value = serde_json::to_string(healing)?;
+        self.db
+            .put(nonce_healing_key(healing).as_bytes(), value.as_bytes())?;
+        Ok(())
+    }
+
+    /// Up to `limit` nonce healings, newest first
+    pub async fn get_nonce_healings(&self, limit: usize) -> Result<Vec<NonceHealing>> {
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"nonce_healing:",
+            rocksdb::Direction::Forward,
+        ));
+        let mut healings = Vec::new();
+        for result in iter {
+            let (key, value) = result?;
+            if !key.starts_with(b"nonce_healing:") || healings.len() >= limit {
+                break;
+            }
+            healings.push(serde_json::from_slice::<NonceHealing>(&value)?);
+        }
+        Ok(healings)
+    }
+
+    /// Record what a mined relay was overcharged
+    pub async fn store_overpayment(&self, overpayment: &Overpayment) -> Result<()> {
+        let millis = u64::try_from(overpayment.recorded_at.timestamp_millis()).unwrap_or(0);
+        let key = overpayment_key(millis, overpayment.request_id);
+        let value = self.encode_record(key.as_bytes(), overpayment)?;
+        self.db.put(key.as_bytes(), value)?;
+        Ok(())
+    }
+
+    /// Overpayments recorded at or after `from` and before `to`, oldest first
+    pub async fn get_overpayments(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Overpayment>> {
+        let millis = |at: DateTime<Utc>| u64::try_from(at.timestamp_millis()).unwrap_or(0);
+        let start = format!("overpayment:{:020}:", from.map_or(0, millis));
+        let end = to.map(|to| format!("overpayment:{:020}:", millis(to)));
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            start.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        let mut overpayments = Vec::new();
+        for result in iter {
+            let (key, value) = result?;
+            if !key.starts_with(b"overpayment:")
+                || end.as_ref().is_some_and(|end| *key >= *end.as_bytes())
+            {
+                break;
+            }
+            overpayments.push(self.decode_record(&key, &value)?);
+        }
+        Ok(overpayments)
+    }
+
+    /// Store a new recurring job
+    pub async fn store_recurring_job(&self, job: &RecurringJob) -> Result<()> {
+        let key = recurring_job_key(job.id);
+        let value = self.encode_record(key.as_bytes(), job)?;
+        self.db.put(key.as_bytes(), value)?;
+        Ok(())
+    }
+
+    /// A recurring job by id; a job outside `scope` reads as missing
+    pub async fn get_recurring_job(
+        &self,
+        scope: TenantScope<'_>,
+        id: Uuid,
+    ) -> Result<Option<RecurringJob>> {
+        let key = recurring_job_key(id);
+        match self.db.get(key.as_bytes())? {
+            Some(value) => Ok(
+                Some(self.decode_record::<RecurringJob>(key.as_bytes(), &value)?)
+                    .filter(|job| scope.admits(job.tenant.as_deref())),
+            ),
+            None => Ok(None),
+        }
+    }
+
+    /// Every recurring job in `scope`, oldest first
+    pub async fn get_recurring_jobs(&self, scope: TenantScope<'_>) -> Result<Vec<RecurringJob>> {
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"recurring_job:",
+            rocksdb::Direction::Forward,
+        ));
+        let mut jobs = Vec::new();
+        for result in iter {
+            let (key, value) = result?;
+            if !key.starts_with(b"recurring_job:") {
+                break;
+            }
+            let job: RecurringJob = self.decode_record(&key, &value)?;
+            if scope.admits(job.tenant.as_deref()) {
+                jobs.push(job);
+            }
+        }
+        jobs.sort_by_key(|job| job.created_at);
+        Ok(jobs)
+    }
+
+    /// Apply `update` to a stored recurring job and return the result, or `None` when there
+    /// is no such job
+    pub async fn update_recurring_job(
+        &self,
+        id: Uuid,
+        update: impl FnOnce(&mut RecurringJob),
+    ) -> Result<Option<RecurringJob>> {
+        let _guard = RECURRING_JOB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let key = recurring_job_key(id);
+        let Some(value) = self.db.get(key.as_bytes())? else {
+            return Ok(None);
+        };
+        let mut job: RecurringJob = self.decode_record(key.as_bytes(), &value)?;
+        update(&mut job);
+        self.db
+            .put(key.as_bytes(), self.encode_record(key.as_bytes(), &job)?)?;
+        Ok(Some(job))
+    }
+
+    /// Service lifetime figures; counters are zero before the relayer first starts on this
+    /// database
+    pub async fn get_lifetime_stats(&self) -> Result<LifetimeStats> {
+        match self.db.get(LIFETIME_STATS_KEY)? {
+            Some(value) => Ok(serde_json::from_slice(&value)?),
+            None => Ok(LifetimeStats::default()),
+        }
+    }
+
+    /// Count a start of the relayer, setting the first start time on a fresh database
+    pub async fn record_service_start(&self) -> Result<LifetimeStats> {
+        self.update_lifetime_stats(|stats| stats.starts += 1)
+    }
+
+    /// Count a mined relay and the gas it cost the relayer
+    pub async fn record_relay_mined(&self, gas_used: u64, gas_spent_wei: u128) -> Result<()> {
+        self.update_lifetime_stats(|stats| {
+            stats.total_relayed += 1;
+            stats.total_gas_used = stats.total_gas_used.saturating_add(gas_used);
+            let spent: u128 = stats.total_gas_spent_wei.parse().unwrap_or(0);
+            stats.total_gas_spent_wei = spent.saturating_add(gas_spent_wei).to_string();
+        })?;
+        Ok(())
+    }
+
+    fn update_lifetime_stats(
+        &self,
+        update: impl FnOnce(&mut LifetimeStats),
+    ) -> Result<LifetimeStats> {
+        let _guard = LIFETIME_STATS_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut stats = match self.db.get(LIFETIME_STATS_KEY)? {
+            Some(value) => serde_json::from_slice(&value)?,
+            None => LifetimeStats::default(),
+        };
+        update(&mut stats);
+        self.db
+            .put(LIFETIME_STATS_KEY, serde_json::to_vec(&stats)?)?;
+        Ok(stats)
+    }
+
+    /// Claim `fingerprint` for `request_id`. Returns the request that claimed it at or after
+    /// `since` instead, leaving that claim in place.
+    pub async fn claim_submission(
+        &self,
+        fingerprint: &str,
+        request_id: Uuid,
+        now: DateTime<Utc>,
+        since: DateTime<Utc>,
+    ) -> Result<Option<Uuid>> {
+        let _guard = SUBMISSION_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let key = submission_key(fingerprint);
+        if let Some(value) = self.db.get(key.as_bytes())? {
+            let (claimed_by, claimed_at): (Uuid, DateTime<Utc>) = serde_json::from_slice(&value)?;
+            if claimed_at >= since {
+                return Ok(Some(claimed_by));
+            }
+        }
+        self.db
+            .put(key.as_bytes(), serde_json::to_vec(&(request_id, now))?)?;
+        Ok(None)
+    }
+
+    /// Drop `request_id`'s claim on `fingerprint`, such as when its relay was rejected, so the
+    /// same relay can be sent again. A newer claim is left alone.
+    pub async fn release_submission(&self, fingerprint: &str, request_id: Uuid) -> Result<()> {
+        let _guard = SUBMISSION_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let key = submission_key(fingerprint);
+        if let Some(value) = self.db.get(key.as_bytes())? {
+            let (claimed_by, _): (Uuid, DateTime<Utc>) = serde_json::from_slice(&value)?;
+            if claimed_by == request_id {
+                self.db.delete(key.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a quote handed out to a client
+    pub async fn store_quote(&self, quote: &IssuedQuote) -> Result<()> {
+        let key = quote_key(&quote.id);
+        self.db.put(key.as_bytes(), serde_json::to_vec(quote)?)?;
+        Ok(())
+    }
+
+    /// Quote issued under `id`, until it is pruned
+    pub async fn get_quote(&self, id: &str) -> Result<Option<IssuedQuote>> {
+        match self.db.get(quote_key(id).as_bytes())? {
+            Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete the quotes that expired before `now` (Unix seconds) and return how many
+    pub async fn prune_quotes(&self, now: u64) -> Result<usize> {
+        let prefix = "quote:";
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        let mut batch = WriteBatch::default();
+        let mut pruned = 0;
+        for result in iter {
+            let (key, value) = result?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let expired = serde_json::from_slice::<IssuedQuote>(&value)
+                .map_or(true, |quote| quote.expiry < now);
+            if expired {
+                batch.delete(&key);
+                pruned += 1;
+            }
+        }
+        self.db.write(batch)?;
+        Ok(pruned)
+    }
+
+    /// Persist a chain registered through the admin API
+    pub async fn store_chain_registration(&self, chain: &ChainRegistration) -> Result<()> {
+        let key = format!("chain:{}", chain.chain_id);
+        let value = serde_json::to_string(chain)?;
+        self.db.put(key.as_bytes(), value.as_bytes()).map_err(|e| {
+            tracing::error!("Failed to store chain {}: {}", chain.chain_id, e);
+            e
+        })?;
+        Ok(())
+    }
+
+    /// Remove a persisted chain registration
+    pub async fn delete_chain_registration(&self, chain_id: &str) -> Result<()> {
+        let key = format!("chain:{}", chain_id);
+        self.db.delete(key.as_bytes())?;
+        Ok(())
+    }
+
+    /// Retrieve all persisted chain registrations
+    pub async fn get_chain_registrations(&self) -> Result<Vec<ChainRegistration>> {
+        let mut items = Vec::new();
+        let prefix = "chain:";
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        for result in iter {
+            let (key, value) = result?;
+            let key_str = String::from_utf8_lossy(&key);
+            if !key_str.starts_with(prefix) {
+                break;
+            }
+            if let Ok(chain) = serde_json::from_slice::<ChainRegistration>(&value) {
+                items.push(chain);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Get up to `limit` requests in `scope`
+    pub async fn get_requests(
+        &self,
+        scope: TenantScope<'_>,
+        limit: Option<usize>,
+    ) -> Result<Vec<RelayerRequest>> {
+        tracing::debug!("Retrieving requests with limit: {:?}", limit);
+
+        let mut requests = Vec::new();
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"request:",
+            rocksdb::Direction::Forward,
+        ));
+
+        for result in iter {
+            let (key, value) = result?;
+            let key_str = String::from_utf8_lossy(&key);
+
+            if key_str.starts_with("request:") {
+                let decoded = self.decode_record::<RelayerRequest>(&key, &value);
+                if let Some(request) = decoded
+                    .ok()
+                    .filter(|request| scope.admits(request.tenant.as_deref()))
+                {
+                    requests.push(request);
+
+                    if let Some(limit) = limit {
+                        if requests.len() >= limit {
+                            break;
+                        }
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+
+        tracing::debug!("Retrieved {} requests", requests.len());
+        Ok(requests)
+    }
+
+    /// Visit every stored request in key order, decoding one record at a time so a full scan
+    /// does not hold them all in memory. Archived requests are left out.
+    pub fn for_each_request(&self, visit: impl FnMut(RelayerRequest) -> Result<()>) -> Result<()> {
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"request:",
+            rocksdb::Direction::Forward,
+        ));
+        self.visit_requests(iter, visit)
+    }
+
+    /// Visit every archived request, like [`Storage::for_each_request`]
+    pub fn for_each_archived_request(
+        &self,
+        visit: impl FnMut(RelayerRequest) -> Result<()>,
+    ) -> Result<()> {
+        let archive = self.archive()?;
+        let iter = self.db.iterator_cf(
+            &archive,
+            rocksdb::IteratorMode::From(b"request:", rocksdb::Direction::Forward),
+        );
+        self.visit_requests(iter, visit)
+    }
+
+    fn visit_requests(
+        &self,
+        iter: impl Iterator<Item = Result<(Box<[u8]>, Box<[u8]>), rocksdb::Error>>,
+        mut visit: impl FnMut(RelayerRequest) -> Result<()>,
+    ) -> Result<()> {
+        let prefix = b"request:";
+        for result in iter {
+            let (key, value) = result?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            if let Ok(request) = self.decode_record::<RelayerRequest>(&key, &value) {
+                visit(request)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the records of requests that completed or failed before `cutoff` (their mined
+    /// time, or last update when never mined) to the archive column family, out of the scans
+    /// the monitor and stats run. Records are moved as stored, sealed ones included. Requests
+    /// whose callback is still being delivered wait for a later pass. Returns how many
+    /// requests were archived.
+    pub async fn archive_requests(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut due = Vec::new();
+        self.for_each_request(|req| {
+            let finished_at = req.mined_at.unwrap_or(req.updated_at);
+            if matches!(req.status, RequestStatus::Completed | RequestStatus::Failed)
+                && finished_at < cutoff
+            {
+                due.push(req.id);
+            }
+            Ok(())
+        })?;
+
+        let archive = self.archive()?;
+        let mut archived = 0;
+        for request_id in due {
+            let callback = self.get_callback(request_id).await?;
+            if callback.is_some_and(|callback| callback.status == CallbackStatus::Pending) {
+                continue;
+            }
+            let mut keys: Vec<Vec<u8>> = ARCHIVED_RECORD_PREFIXES
+                .iter()
+                .map(|prefix| format!("{}{}", prefix, request_id).into_bytes())
+                .collect();
+            let prefix = format!("resubmission:{}:", request_id);
+            let iter = self.db.iterator(rocksdb::IteratorMode::From(
+                prefix.as_bytes(),
+                rocksdb::Direction::Forward,
+            ));
+            for result in iter {
+                let (key, _) = result?;
+                if !key.starts_with(prefix.as_bytes()) {
+                    break;
+                }
+                keys.push(key.to_vec());
+            }
+
+            let mut write = WriteBatch::default();
+            for key in keys {
+                if let Some(value) = self.db.get(&key)? {
+                    write.put_cf(&archive, &key, value);
+                    write.delete(&key);
+                }
+            }
+            self.db.write(write).map_err(|e| {
+                tracing::error!("Failed to archive request {}: {}", request_id, e);
+                e
+            })?;
+            archived += 1;
+        }
+
+        if archived > 0 {
+            tracing::debug!(
+                "Archived {} request(s) finished before {}",
+                archived,
+                cutoff
+            );
+        }
+        Ok(archived)
+    }
+
+    /// Get request count by status
+    pub async fn get_request_count_by_status(&self, status: RequestStatus) -> Result<u64> {
+        tracing::trace!("Counting requests with status: {:?}", status);
+
+        let mut count = 0;
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"request:",
+            rocksdb::Direction::Forward,
+        ));
+
+        for result in iter {
+            let (key, value) = result?;
+            let key_str = String::from_utf8_lossy(&key);
+
+            if !key_str.starts_with("request:") {
+                break;
+            }
+
+            if let Ok(request) = self.decode_record::<RelayerRequest>(&key, &value) {
+                if request.status == status {
+                    count += 1;
+                }
+            }
+        }
+
+        tracing::trace!("Found {} requests with status {:?}", count, status);
+        Ok(count)
+    }
+
+    /// Count requests on `chain_id` that are still pending or processing, optionally only
+    /// those created before `created_before`
+    pub async fn count_in_flight(
+        &self,
+        chain_id: u64,
+        created_before: Option<DateTime<Utc>>,
+    ) -> Result<u64> {
+        let mut count = 0;
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"request:",
+            rocksdb::Direction::Forward,
+        ));
+
+        for result in iter {
+            let (key, value) = result?;
+            let key_str = String::from_utf8_lossy(&key);
+
+            if !key_str.starts_with("request:") {
+                break;
+            }
+
+            if let Ok(request) = self.decode_record::<RelayerRequest>(&key, &value) {
+                if request.chain_id == chain_id
+                    && matches!(
+                        request.status,
+                        RequestStatus::Pending | RequestStatus::Processing
+                    )
+                    && created_before.is_none_or(|before| request.created_at < before)
+                {
+                    count += 1;
+                }
+            }
+        }
+
+        tracing::trace!("Found {} in-flight requests on chain {}", count, chain_id);
+        Ok(count)
+    }
+
+    /// Get total request count
+    pub async fn get_total_request_count(&self) -> Result<u64> {
+        tracing::trace!("Counting total requests");
+
+        let mut count = 0;
+        let iter = self.db.iterator(rocksdb::IteratorMode::From(
+            b"request:",
+            rocksdb::Direction::Forward,
+        ));
 
         for result in iter {
             let (key, _) = result?;
@@ -292,6 +1806,13 @@ impl Storage {
         Ok(count)
     }
 
+    /// Write a probe key to prove the database still accepts writes
+    pub async fn check_writable(&self) -> Result<()> {
+        let now = chrono::Utc::now().timestamp().to_string();
+        self.db.put(b"readiness_probe", now.as_bytes())?;
+        Ok(())
+    }
+
     /// Get uptime in seconds
     pub fn get_uptime_seconds(&self) -> u64 {
         self.start_time.elapsed().as_secs()
@@ -302,7 +1823,455 @@ impl Clone for Storage {
     fn clone(&self) -> Self {
         Self {
             db: Arc::clone(&self.db),
+            options: Arc::clone(&self.options),
+            cipher: self.cipher.clone(),
             start_time: self.start_time,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_cache_hit_rate_from_statistics_dump() {
+        let statistics = "rocksdb.block.cache.miss COUNT : 25\n\
+                          rocksdb.block.cache.hit COUNT : 75\n\
+                          rocksdb.block.cache.add COUNT : 25\n\
+                          rocksdb.db.get.micros P50 : 1.000000 P95 : 2.000000";
+        assert_eq!(
+            ticker_count(statistics, "rocksdb.block.cache.add"),
+            Some(25)
+        );
+        assert_eq!(block_cache_hit_rate(statistics), Some(0.75));
+        // No lookups yet, or statistics disabled
+        assert_eq!(
+            block_cache_hit_rate(
+                "rocksdb.block.cache.miss COUNT : 0\nrocksdb.block.cache.hit COUNT : 0"
+            ),
+            None
+        );
+        assert_eq!(block_cache_hit_rate(""), None);
+    }
+
+    fn request(to_address: &str) -> RelayerRequest {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "from_address": "0x55f3a93f544e01ce4378d25e927d7c493b863bd6",
+            "to_address": to_address,
+            "amount": "0",
+            "gas_limit": 21000,
+            "gas_price": "0x4a817c800",
+            "data": "0xdeadbeef",
+            "nonce": 0,
+            "chain_id": 1,
+            "transaction_hash": null,
+            "status": "Pending",
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+            "error_message": null,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_compact_resubmissions_keeps_first_and_latest() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+        let req = request("0x0987654321098765432109876543210987654321");
+        storage.create_request(req.clone()).await.unwrap();
+
+        // Hashes sort differently from broadcast order
+        let hashes: Vec<String> = (0..6u8)
+            .rev()
+            .map(|i| format!("0x{}", format!("{:02x}", i).repeat(32)))
+            .collect();
+        for tx_hash in &hashes {
+            let resub = Resubmission {
+                status: 201,
+                transaction_hash: tx_hash.clone(),
+                chain_id: "1".to_string(),
+            };
+            storage.add_resubmission(req.id, &resub).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        }
+
+        assert_eq!(storage.compact_resubmissions(req.id, 2).await.unwrap(), 3);
+        let kept: Vec<String> = storage
+            .get_resubmissions(req.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|resub| resub.transaction_hash)
+            .collect();
+        assert_eq!(kept, [&*hashes[0], &*hashes[4], &*hashes[5]]);
+        assert_eq!(storage.get_transaction_hashes(req.id).await.unwrap(), kept);
+        assert_eq!(
+            storage.get_request_id_by_tx_hash(&hashes[2]).await.unwrap(),
+            Some(req.id)
+        );
+        assert_eq!(storage.compact_resubmissions(req.id, 2).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_archive_moves_finished_requests_out_of_hot_scans() {
+        let dir = tempfile::tempdir().unwrap();
+        let key: StorageKey = "11".repeat(32).parse().unwrap();
+        let storage = Storage::new(dir.path()).unwrap().with_storage_key(&key);
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        let long_ago = cutoff - chrono::Duration::days(1);
+
+        let mut old = request("0x0987654321098765432109876543210987654321");
+        old.status = RequestStatus::Completed;
+        old.transaction_hash = Some(format!("0x{}", "ab".repeat(32)));
+        old.updated_at = long_ago;
+        let mut recent = old.clone();
+        recent.id = Uuid::new_v4();
+        recent.transaction_hash = None;
+        recent.updated_at = Utc::now();
+        let mut stuck = old.clone();
+        stuck.id = Uuid::new_v4();
+        stuck.status = RequestStatus::Processing;
+        stuck.transaction_hash = None;
+        let mut notifying = old.clone();
+        notifying.id = Uuid::new_v4();
+        notifying.transaction_hash = None;
+        for req in [&old, &recent, &stuck, &notifying] {
+            storage.create_request(req.clone()).await.unwrap();
+        }
+        let resub = Resubmission {
+            status: 200,
+            transaction_hash: format!("0x{}", "cd".repeat(32)),
+            chain_id: "1".to_string(),
+        };
+        storage.add_resubmission(old.id, &resub).await.unwrap();
+        let receipt = Receipt {
+            logs: vec![],
+            status: "0x1".to_string(),
+            block_hash: String::new(),
+            block_number: "0x1".to_string(),
+            gas_used: "0x5208".to_string(),
+            effective_gas_price: None,
+            transaction_hash: resub.transaction_hash.clone(),
+            chain_id: "1".to_string(),
+            blob_gas_used: None,
+            blob_gas_price: None,
+        };
+        storage.store_receipt(old.id, &receipt).await.unwrap();
+        let callback = CallbackDelivery {
+            url: "https://example.com/hook".to_string(),
+            status: CallbackStatus::Pending,
+            attempts: 1,
+            last_attempt_at: None,
+            last_error: None,
+            delivered_at: None,
+        };
+        storage
+            .update_callback(notifying.id, &callback)
+            .await
+            .unwrap();
+
+        assert_eq!(storage.archive_requests(cutoff).await.unwrap(), 1);
+        assert_eq!(storage.archive_requests(cutoff).await.unwrap(), 0);
+
+        // Gone from the scans the monitor and stats run
+        assert_eq!(storage.get_total_request_count().await.unwrap(), 3);
+        let hot = storage.get_requests(TenantScope::All, None).await.unwrap();
+        assert!(hot.iter().all(|req| req.id != old.id));
+        let mut archived = Vec::new();
+        storage
+            .for_each_archived_request(|req| {
+                archived.push(req.id);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(archived, [old.id]);
+        assert!(storage
+            .db
+            .get(format!("request:{}", old.id))
+            .unwrap()
+            .is_none());
+
+        // Still readable by id and through the kept indexes, sealed records included
+        let stored = storage
+            .get_request(TenantScope::All, old.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, RequestStatus::Completed);
+        assert_eq!(stored.transaction_hash, old.transaction_hash);
+        let stored = storage.get_receipt(old.id).await.unwrap().unwrap();
+        assert_eq!(stored.transaction_hash, receipt.transaction_hash);
+        let resubmissions = storage.get_resubmissions(old.id).await.unwrap();
+        assert_eq!(resubmissions.len(), 1);
+        assert_eq!(resubmissions[0].transaction_hash, resub.transaction_hash);
+        assert_eq!(
+            storage
+                .get_request_id_by_tx_hash(&resub.transaction_hash)
+                .await
+                .unwrap(),
+            Some(old.id)
+        );
+        let (by_wallet, _) = storage
+            .get_requests_by_wallet(None, &old.to_address, None, 10, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(by_wallet.len(), 4);
+
+        // Once its callback is settled the remaining finished request follows
+        let delivered = CallbackDelivery {
+            status: CallbackStatus::Delivered,
+            ..callback
+        };
+        storage
+            .update_callback(notifying.id, &delivered)
+            .await
+            .unwrap();
+        assert_eq!(storage.archive_requests(cutoff).await.unwrap(), 1);
+        assert_eq!(
+            storage.get_callback(notifying.id).await.unwrap(),
+            Some(delivered)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_records_sealed_with_storage_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = Storage::new(dir.path()).unwrap();
+        let key: StorageKey = "11".repeat(32).parse().unwrap();
+        let sealed = plain.clone().with_storage_key(&key);
+
+        // Written before encryption was enabled
+        let legacy = request("0x1111111111111111111111111111111111111111");
+        plain.store_request(&legacy).await.unwrap();
+
+        let wallet = "0x0987654321098765432109876543210987654321";
+        let fresh = request(wallet);
+        sealed.create_request(fresh.clone()).await.unwrap();
+        let raw = plain
+            .db
+            .get(format!("request:{}", fresh.id).as_bytes())
+            .unwrap()
+            .unwrap();
+        assert!(is_sealed(&raw));
+        assert!(!raw.windows(wallet.len()).any(|w| w == wallet.as_bytes()));
+
+        let read = sealed
+            .get_request(TenantScope::All, fresh.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.to_address, wallet);
+        assert_eq!(read.data.as_deref(), Some("0xdeadbeef"));
+        let legacy_read = sealed
+            .get_request(TenantScope::All, legacy.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(legacy_read.to_address, legacy.to_address);
+        assert_eq!(
+            sealed
+                .get_requests(TenantScope::All, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        let (by_wallet, _) = sealed
+            .get_requests_by_wallet(None, wallet, None, 10, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(by_wallet.len(), 1);
+
+        // Sealed records cannot be read without the key
+        let err = plain
+            .get_request(TenantScope::All, fresh.id)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("encrypted"));
+    }
+
+    #[tokio::test]
+    async fn test_lifetime_stats_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = {
+            let storage = Storage::new(dir.path()).unwrap();
+            let first = storage.record_service_start().await.unwrap();
+            storage.record_relay_mined(21_000, 420_000).await.unwrap();
+            storage.record_relay_mined(50_000, 1_000_000).await.unwrap();
+            first
+        };
+
+        let storage = Storage::new(dir.path()).unwrap();
+        let stats = storage.record_service_start().await.unwrap();
+        assert_eq!(stats.first_started_at, first.first_started_at);
+        assert_eq!(stats.starts, 2);
+        assert_eq!(stats.total_relayed, 2);
+        assert_eq!(stats.total_gas_used, 71_000);
+        assert_eq!(stats.total_gas_spent_wei, "1420000");
+        assert_eq!(storage.get_lifetime_stats().await.unwrap(), stats);
+    }
+
+    #[tokio::test]
+    async fn test_submission_claimed_until_window_passes_or_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(10);
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let claim = |id, at| storage.claim_submission("fp", id, at, at - window);
+        assert_eq!(claim(first, now).await.unwrap(), None);
+        assert_eq!(
+            claim(second, now + chrono::Duration::seconds(5))
+                .await
+                .unwrap(),
+            Some(first)
+        );
+        // Only the claimant releases a claim
+        storage.release_submission("fp", second).await.unwrap();
+        assert_eq!(claim(second, now).await.unwrap(), Some(first));
+
+        storage.release_submission("fp", first).await.unwrap();
+        assert_eq!(claim(second, now).await.unwrap(), None);
+        let later = now + chrono::Duration::seconds(11);
+        assert_eq!(claim(first, later).await.unwrap(), None);
+        assert_eq!(claim(second, later).await.unwrap(), Some(first));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_listing_partitioned_by_tenant() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+        let wallet = "0x0987654321098765432109876543210987654321";
+
+        let mut acme = request(wallet);
+        acme.tenant = Some("acme".to_string());
+        storage.create_request(acme.clone()).await.unwrap();
+        let mut globex = request(wallet);
+        globex.tenant = Some("globex".to_string());
+        storage.create_request(globex.clone()).await.unwrap();
+
+        let (listed, _) = storage
+            .get_requests_by_wallet(Some("acme"), wallet, None, 10, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, acme.id);
+        let (listed, _) = storage
+            .get_requests_by_wallet(Some("globex"), wallet, None, 10, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, globex.id);
+        let (untenanted, _) = storage
+            .get_requests_by_wallet(None, wallet, None, 10, |_| true)
+            .await
+            .unwrap();
+        assert!(untenanted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_request_reads_scoped_to_tenant() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+        let mut acme = request("0x0987654321098765432109876543210987654321");
+        acme.tenant = Some("acme".to_string());
+        storage.create_request(acme.clone()).await.unwrap();
+        let untenanted = request("0x0987654321098765432109876543210987654321");
+        storage.create_request(untenanted.clone()).await.unwrap();
+
+        for (scope, visible) in [
+            (TenantScope::Tenant(Some("acme")), true),
+            (TenantScope::All, true),
+            // Another tenant, or a caller without one, reads it as missing
+            (TenantScope::Tenant(Some("globex")), false),
+            (TenantScope::Tenant(None), false),
+        ] {
+            let read = storage.get_request(scope, acme.id).await.unwrap();
+            assert_eq!(read.is_some(), visible, "{:?}", scope);
+        }
+
+        let ids = |requests: Vec<RelayerRequest>| requests.iter().map(|r| r.id).collect::<Vec<_>>();
+        let scoped = storage
+            .get_requests(TenantScope::Tenant(None), None)
+            .await
+            .unwrap();
+        assert_eq!(ids(scoped), [untenanted.id]);
+        let all = storage.get_requests(TenantScope::All, None).await.unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_prune_quotes_drops_only_expired_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new(dir.path()).unwrap();
+        let quote = |id: &str, expiry| IssuedQuote {
+            id: id.to_string(),
+            chain_id: "1".to_string(),
+            token: "0x0000000000000000000000000000000000000000".to_string(),
+            rate: 0.00002,
+            gas_price: "0x4a817c800".to_string(),
+            fee: None,
+            expiry,
+        };
+        storage.store_quote(&quote("stale", 100)).await.unwrap();
+        storage.store_quote(&quote("fresh", 200)).await.unwrap();
+
+        assert_eq!(storage.prune_quotes(150).await.unwrap(), 1);
+        assert!(storage.get_quote("stale").await.unwrap().is_none());
+        assert_eq!(
+            storage.get_quote("fresh").await.unwrap(),
+            Some(quote("fresh", 200))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overpayments_listed_in_recording_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let key: StorageKey = "11".repeat(32).parse().unwrap();
+        let storage = Storage::new(dir.path()).unwrap().with_storage_key(&key);
+        let overpayment = |recorded_at: &str| Overpayment {
+            request_id: Uuid::new_v4(),
+            chain_id: 1,
+            wallet: "0x742d35cc6634c0532925a3b844bc454e4438f44e".to_string(),
+            transaction_hash: format!("0x{}", "ab".repeat(32)),
+            payment_type: "native".to_string(),
+            token: "0x0000000000000000000000000000000000000000".to_string(),
+            priced_wei: "1000".to_string(),
+            spent_wei: "600".to_string(),
+            overpaid_wei: "400".to_string(),
+            refund_amount: "400".to_string(),
+            recorded_at: recorded_at.parse().unwrap(),
+        };
+        let early = overpayment("2026-03-01T00:00:00Z");
+        let late = overpayment("2026-03-02T12:00:00Z");
+        storage.store_overpayment(&late).await.unwrap();
+        storage.store_overpayment(&early).await.unwrap();
+        let (_, raw) = storage
+            .db
+            .prefix_iterator(b"overpayment:")
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(is_sealed(&raw));
+        assert!(!raw
+            .windows(early.wallet.len())
+            .any(|w| w == early.wallet.as_bytes()));
+
+        assert_eq!(
+            storage.get_overpayments(None, None).await.unwrap(),
+            vec![early.clone(), late.clone()]
+        );
+        let to = "2026-03-02T00:00:00Z".parse().ok();
+        assert_eq!(
+            storage.get_overpayments(None, to).await.unwrap(),
+            vec![early]
+        );
+        assert_eq!(
+            storage.get_overpayments(to, None).await.unwrap(),
+            vec![late]
+        );
+    }
+}
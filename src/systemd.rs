@@ -0,0 +1,181 @@
+//! systemd service manager integration.
+//!
+//! Under a `Type=notify` unit the server reports `READY=1` once the HTTP listener is bound,
+//! `STOPPING=1` when it begins shutting down, and, when `WatchdogSec=` is set, pings the
+//! watchdog at half the configured interval for as long as storage accepts writes. Every
+//! notification is a no-op when `NOTIFY_SOCKET` is unset, so the same binary runs unchanged
+//! outside systemd.
+//!
+//! Exit statuses follow sysexits(3), letting a unit skip restarts on configuration errors with
+//! `RestartPreventExitStatus=78`.
+
+use std::{fmt, os::unix::net::UnixDatagram, time::Duration};
+
+use crate::storage::Storage;
+
+/// Startup failed because of invalid configuration (`EX_CONFIG`)
+pub const EXIT_CONFIG: u8 = 78;
+/// A required resource such as storage or the listen address was unavailable
+/// (`EX_UNAVAILABLE`)
+pub const EXIT_UNAVAILABLE: u8 = 69;
+/// Any other failure
+pub const EXIT_FAILURE: u8 = 1;
+
+/// Failure that ends the process with a specific exit status
+#[derive(Debug)]
+pub struct ExitError {
+    pub code: u8,
+    pub source: anyhow::Error,
+}
+
+impl ExitError {
+    pub fn config(source: impl Into<anyhow::Error>) -> Self {
+        Self {
+            code: EXIT_CONFIG,
+            source: source.into(),
+        }
+    }
+
+    pub fn unavailable(source: impl Into<anyhow::Error>) -> Self {
+        Self {
+            code: EXIT_UNAVAILABLE,
+            source: source.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ExitError {
+    fn from(source: anyhow::Error) -> Self {
+        Self {
+            code: EXIT_FAILURE,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for ExitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.source)
+    }
+}
+
+/// Send `state` (newline-separated `KEY=value` assignments) to the service manager. Returns
+/// whether a notification was delivered; false outside systemd or when sending fails.
+pub fn notify(state: &str) -> bool {
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return false;
+    };
+    let sent = UnixDatagram::unbound().and_then(|socket| {
+        let path = path.to_string_lossy();
+        match path.strip_prefix('@') {
+            Some(name) => send_abstract(&socket, name, state),
+            None => socket.send_to(state.as_bytes(), path.as_ref()),
+        }
+    });
+    match sent {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::warn!("Failed to notify the service manager: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_abstract(socket: &UnixDatagram, name: &str, state: &str) -> std::io::Result<usize> {
+    use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+    let addr = SocketAddr::from_abstract_name(name.as_bytes())?;
+    socket.send_to_addr(state.as_bytes(), &addr)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_abstract(_socket: &UnixDatagram, _name: &str, _state: &str) -> std::io::Result<usize> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "abstract notify sockets are Linux-only",
+    ))
+}
+
+/// How often to ping the watchdog: half of `WATCHDOG_USEC`, or `None` when the watchdog is
+/// off or armed for another process (`WATCHDOG_PID`)
+pub fn watchdog_interval() -> Option<Duration> {
+    watchdog_interval_from(
+        std::env::var("WATCHDOG_USEC").ok().as_deref(),
+        std::env::var("WATCHDOG_PID").ok().as_deref(),
+        std::process::id(),
+    )
+}
+
+fn watchdog_interval_from(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if pid.is_some_and(|pid| pid.parse::<u32>().ok() != Some(own_pid)) {
+        return None;
+    }
+    let usec = usec?.parse::<u64>().ok().filter(|usec| *usec > 0)?;
+    Some(Duration::from_micros(usec / 2))
+}
+
+/// Ping the watchdog every `interval` while storage accepts writes. A failed check skips the
+/// ping, so a wedged process is restarted once `WatchdogSec` passes without one.
+pub async fn run_watchdog(storage: Storage, interval: Duration) {
+    tracing::info!("Pinging the systemd watchdog every {:?}", interval);
+    let mut ticker = tokio::time::interval(interval);
+    let mut healthy = true;
+    loop {
+        ticker.tick().await;
+        match storage.check_writable().await {
+            Ok(()) => {
+                if !healthy {
+                    tracing::info!("Storage writable again; resuming watchdog pings");
+                    healthy = true;
+                }
+                notify("WATCHDOG=1");
+            }
+            Err(e) => {
+                if healthy {
+                    tracing::error!("Storage not writable, withholding watchdog ping: {}", e);
+                    healthy = false;
+                }
+                notify(&format!("STATUS=Storage not writable: {}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_interval_is_half_the_timeout_for_this_process() {
+        assert_eq!(
+            watchdog_interval_from(Some("30000000"), None, 42),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(
+            watchdog_interval_from(Some("30000000"), Some("42"), 42),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(
+            watchdog_interval_from(Some("30000000"), Some("7"), 42),
+            None
+        );
+        assert_eq!(watchdog_interval_from(Some("0"), None, 42), None);
+        assert_eq!(watchdog_interval_from(None, None, 42), None);
+    }
+
+    #[test]
+    fn test_notify_delivers_state_to_the_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let listener = UnixDatagram::bind(&path).unwrap();
+
+        std::env::set_var("NOTIFY_SOCKET", &path);
+        assert!(notify("READY=1"));
+        std::env::remove_var("NOTIFY_SOCKET");
+        assert!(!notify("READY=1"));
+
+        let mut buf = [0u8; 64];
+        let len = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1");
+    }
+}
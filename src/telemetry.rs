@@ -0,0 +1,227 @@
+//! Sentry client setup.
+//!
+//! Builds the client options from the `sentry` section of config.json and installs a
+//! `before_send` scrubber that masks sensitive fields (API keys, signatures, callback URLs and
+//! any configured `scrubFields`) wherever they appear in an event's tags, extra data or
+//! contexts.
+//!
+//! Also keeps a hub per in-flight relay that collects breadcrumbs for each step of its
+//! lifecycle (validation, simulation, broadcast, bumps, receipt), so an event captured when
+//! the relay fails carries the whole history rather than just the final error.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Instant,
+};
+
+use sentry::protocol::{Breadcrumb, Context, Event};
+use sentry::{Hub, Level};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::types::SentrySettings;
+
+/// Replacement for scrubbed values
+const FILTERED: &str = "[Filtered]";
+
+/// Breadcrumb category of relay lifecycle steps
+const LIFECYCLE_CATEGORY: &str = "relay.lifecycle";
+
+/// Relays whose lifecycle is tracked at once; past it the oldest trail is dropped
+const MAX_TRACKED_LIFECYCLES: usize = 10_000;
+
+/// Client options honoring the configured environment, sample rates and scrub fields
+pub fn client_options(settings: &SentrySettings) -> sentry::ClientOptions {
+    let fields = Arc::new(settings.scrub_fields.clone());
+    sentry::ClientOptions {
+        release: sentry::release_name!(),
+        environment: settings.environment.clone().map(Into::into),
+        sample_rate: settings.sample_rate,
+        traces_sample_rate: settings.traces_sample_rate,
+        before_send: Some(Arc::new(move |event| Some(scrub_event(event, &fields)))),
+        ..Default::default()
+    }
+}
+
+/// Mask every value in the event whose key is one of `fields` (case-insensitive)
+fn scrub_event(mut event: Event<'static>, fields: &[String]) -> Event<'static> {
+    for (key, value) in event.tags.iter_mut() {
+        if is_scrubbed(key, fields) {
+            *value = FILTERED.to_string();
+        }
+    }
+    for (key, value) in event.extra.iter_mut() {
+        scrub_entry(key, value, fields);
+    }
+    for context in event.contexts.values_mut() {
+        if let Context::Other(map) = context {
+            for (key, value) in map.iter_mut() {
+                scrub_entry(key, value, fields);
+            }
+        }
+    }
+    for breadcrumb in event.breadcrumbs.values.iter_mut() {
+        for (key, value) in breadcrumb.data.iter_mut() {
+            scrub_entry(key, value, fields);
+        }
+    }
+    event
+}
+
+fn scrub_entry(key: &str, value: &mut Value, fields: &[String]) {
+    if is_scrubbed(key, fields) {
+        *value = Value::String(FILTERED.to_string());
+    } else {
+        scrub_value(value, fields);
+    }
+}
+
+fn scrub_value(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                scrub_entry(key, value, fields);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                scrub_value(item, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Hub of each relay that has not settled yet, with when it was first seen
+type Lifecycles = Mutex<HashMap<Uuid, (Instant, Arc<Hub>)>>;
+
+/// Hubs of the relays that have not settled yet, with when each was first seen
+fn lifecycles() -> &'static Lifecycles {
+    static LIFECYCLES: OnceLock<Lifecycles> = OnceLock::new();
+    LIFECYCLES.get_or_init(Default::default)
+}
+
+/// The hub collecting `request_id`'s breadcrumbs, forked from the current one (and so
+/// inheriting the per-call request context) the first time the relay is seen
+fn lifecycle_hub(request_id: Uuid) -> Arc<Hub> {
+    let mut hubs = lifecycles().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, hub)) = hubs.get(&request_id) {
+        return hub.clone();
+    }
+    if hubs.len() >= MAX_TRACKED_LIFECYCLES {
+        let oldest = hubs
+            .iter()
+            .min_by_key(|(_, (seen, _))| *seen)
+            .map(|(id, _)| *id);
+        if let Some(oldest) = oldest {
+            hubs.remove(&oldest);
+        }
+    }
+    let hub = Arc::new(Hub::new_from_top(Hub::current()));
+    hub.configure_scope(|scope| scope.set_tag("transaction_id", request_id));
+    hubs.insert(request_id, (Instant::now(), hub.clone()));
+    hub
+}
+
+/// Record a step of `request_id`'s lifecycle, e.g. `("broadcast", "sent 0xabc…")`
+pub fn lifecycle_breadcrumb(request_id: Uuid, step: &str, message: String) {
+    lifecycle_hub(request_id).add_breadcrumb(Breadcrumb {
+        category: Some(LIFECYCLE_CATEGORY.to_string()),
+        message: Some(message),
+        level: Level::Info,
+        data: [("step".to_string(), Value::from(step))]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    });
+}
+
+/// Capture `message` as an error carrying `request_id`'s lifecycle and stop tracking it
+pub fn capture_lifecycle_failure(request_id: Uuid, message: &str, error: &str) {
+    let hub = lifecycle_hub(request_id);
+    lifecycles()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&request_id);
+    hub.configure_scope(|scope| scope.set_extra("error", error.into()));
+    hub.capture_message(message, Level::Error);
+}
+
+/// Stop tracking `request_id` once it settled without anything to report
+pub fn end_lifecycle(request_id: Uuid) {
+    lifecycles()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&request_id);
+}
+
+fn is_scrubbed(key: &str, fields: &[String]) -> bool {
+    fields.iter().any(|field| field.eq_ignore_ascii_case(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_scrub_event_masks_configured_fields_at_any_depth() {
+        let fields = SentrySettings::default().scrub_fields;
+        let mut event = Event::new();
+        event
+            .tags
+            .insert("endpoint".to_string(), "relayer_getQuote".to_string());
+        event
+            .tags
+            .insert("apikey".to_string(), "secret".to_string());
+        event
+            .extra
+            .insert("signature".to_string(), json!("0xdeadbeef"));
+        event.contexts.insert(
+            "rpc_request".to_string(),
+            Context::Other(
+                [(
+                    "params".to_string(),
+                    json!([{ "to": "0xabc", "apiKey": "key", "capabilities": {
+                        "payment": { "authorizationList": "0x01" }
+                    } }]),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        );
+
+        let event = scrub_event(event, &fields);
+        assert_eq!(event.tags["endpoint"], "relayer_getQuote");
+        assert_eq!(event.tags["apikey"], FILTERED);
+        assert_eq!(event.extra["signature"], FILTERED);
+        let Context::Other(request) = &event.contexts["rpc_request"] else {
+            panic!("request context replaced");
+        };
+        let params = &request["params"][0];
+        assert_eq!(params["to"], "0xabc");
+        assert_eq!(params["apiKey"], FILTERED);
+        assert_eq!(
+            params["capabilities"]["payment"]["authorizationList"],
+            FILTERED
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_breadcrumbs_are_kept_per_request() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        lifecycle_breadcrumb(first, "validated", "validated relay".to_string());
+        lifecycle_breadcrumb(second, "validated", "validated relay".to_string());
+        lifecycle_breadcrumb(first, "broadcast", "sent 0xabc".to_string());
+
+        let hub = lifecycle_hub(first);
+        assert!(Arc::ptr_eq(&hub, &lifecycle_hub(first)));
+        assert!(!Arc::ptr_eq(&hub, &lifecycle_hub(second)));
+
+        end_lifecycle(first);
+        end_lifecycle(second);
+        let hubs = lifecycles().lock().unwrap_or_else(|e| e.into_inner());
+        assert!(!hubs.contains_key(&first) && !hubs.contains_key(&second));
+    }
+}
@@ -0,0 +1,247 @@
+//! Fixtures for integration tests of services that embed relayx (`test_utils` feature).
+//!
+//! Covers what tests/rpc_tests.rs otherwise builds by hand: storage in a throwaway
+//! directory, a [`Config`] that talks to the in-memory [`MockChainClient`] instead of a node,
+//! and canned requests that pass the relayer's parameter validation.
+//!
+//! ```no_run
+//! # async fn example() {
+//! use relayx::test_utils::{native_payment_request, temp_storage, ConfigBuilder, MockChainClient};
+//! use relayx::RpcServer;
+//!
+//! let (_dir, storage) = temp_storage();
+//! let config = ConfigBuilder::new()
+//!     .mock_chain(MockChainClient::new().with_gas_price(2_000_000_000))
+//!     .build();
+//! let handle = RpcServer::new("127.0.0.1".to_string(), 0, storage, config)
+//!     .unwrap()
+//!     .start()
+//!     .await
+//!     .unwrap();
+//! let request = native_payment_request();
+//! # }
+//! ```
+
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+pub use crate::chain::{ChainBackend, MockChainClient};
+use crate::{
+    config::Config,
+    storage::Storage,
+    types::{
+        ChainRegistration, ExchangeRateRequest, GetStatusRequest, PaymentCapability,
+        SendTransactionCapabilities, SendTransactionRequest,
+    },
+};
+
+/// Wallet the canned requests are sent to
+pub const TEST_WALLET: &str = "0x742d35cc6634c0532925a3b844bc454e4438f44e";
+
+/// Token address that stands for the chain's native currency
+pub const NATIVE_TOKEN: &str = "0x0000000000000000000000000000000000000000";
+
+/// Chain the canned requests target
+pub const TEST_CHAIN_ID: &str = "1";
+
+/// Storage in a fresh temporary directory. The directory, and the database with it, is
+/// removed when the returned `TempDir` is dropped, so keep it alive as long as the storage.
+pub fn temp_storage() -> (TempDir, Storage) {
+    let dir = TempDir::new().expect("failed to create temporary directory");
+    let storage = Storage::new(dir.path().join("db")).expect("failed to open test storage");
+    (dir, storage)
+}
+
+/// Builds a [`Config`] for tests: ephemeral ports, no relayer key, no config file and a
+/// default [`MockChainClient`] for every chain interaction
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    config: Config,
+    chains: Vec<ChainRegistration>,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: Config {
+                rpc_host: "127.0.0.1".to_string(),
+                rpc_port: 0,
+                db_path: PathBuf::from("./relayx_db_test"),
+                relayers: String::new(),
+                max_concurrent_requests: 100,
+                request_timeout: 30,
+                config_path: None,
+                http_address: "127.0.0.1".to_string(),
+                http_port: 0,
+                http_cors: "*".to_string(),
+                log_level: "info".to_string(),
+                relayer_private_key: None,
+                relayer_private_key_file: None,
+                disable_simulation: false,
+                sentry_dsn: None,
+                admin_token: None,
+                callback_secret: None,
+                callback_allow_private_hosts: false,
+                gas_price_ttl_ms: 3000,
+                chain: ChainBackend::new(MockChainClient::default()),
+                encryption: Default::default(),
+                relayer_key_from_file: Default::default(),
+            },
+            chains: Vec::new(),
+        }
+    }
+
+    /// Answer chain calls with `client`
+    pub fn mock_chain(self, client: MockChainClient) -> Self {
+        self.chain(ChainBackend::new(client))
+    }
+
+    /// Use `chain` for node access, e.g. one built from a replay recording
+    pub fn chain(mut self, chain: ChainBackend) -> Self {
+        self.config.chain = chain;
+        self
+    }
+
+    /// Read JSON settings (chains, tokens, tenants, ...) from `path`. The JSON config is loaded
+    /// once per process, so every test in a binary sees the file of the first one to read it.
+    pub fn config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.config_path = Some(path.into());
+        self
+    }
+
+    /// Register a chain at runtime, as `admin_addChain` does, once the config is built
+    pub fn with_chain(mut self, chain: ChainRegistration) -> Self {
+        self.chains.push(chain);
+        self
+    }
+
+    pub fn relayer_private_key(mut self, key: impl Into<String>) -> Self {
+        self.config.relayer_private_key = Some(key.into());
+        self
+    }
+
+    pub fn admin_token(mut self, token: impl Into<String>) -> Self {
+        self.config.admin_token = Some(token.into());
+        self
+    }
+
+    pub fn callback_secret(mut self, secret: impl Into<String>) -> Self {
+        self.config.callback_secret = Some(secret.into());
+        self
+    }
+
+    pub fn disable_simulation(mut self) -> Self {
+        self.config.disable_simulation = true;
+        self
+    }
+
+    pub fn request_timeout(mut self, secs: u64) -> Self {
+        self.config.request_timeout = secs;
+        self
+    }
+
+    pub fn build(self) -> Config {
+        for chain in self.chains {
+            self.config.register_runtime_chain(chain);
+        }
+        self.config
+    }
+}
+
+/// A `relayer_sendTransaction` request to [`TEST_WALLET`] on [`TEST_CHAIN_ID`], paid with
+/// `payment_type` in `token`
+pub fn send_transaction_request(payment_type: &str, token: &str) -> SendTransactionRequest {
+    SendTransactionRequest {
+        to: TEST_WALLET.to_string(),
+        data: "0x1234".to_string(),
+        value: None,
+        capabilities: SendTransactionCapabilities {
+            payment: PaymentCapability {
+                payment_type: payment_type.to_string(),
+                token: token.to_string(),
+                data: String::new(),
+            },
+            fallback_payments: Vec::new(),
+            dry_run: false,
+        },
+        chain_id: TEST_CHAIN_ID.to_string(),
+        authorization_list: String::new(),
+        callback_url: None,
+        execute_after: None,
+        condition: None,
+        speed: None,
+        blob_sidecar: None,
+        safe: None,
+        api_key: None,
+        quote_id: None,
+    }
+}
+
+/// A request paying its fee in the native currency
+pub fn native_payment_request() -> SendTransactionRequest {
+    send_transaction_request("native", NATIVE_TOKEN)
+}
+
+/// A request paying its fee in the ERC20 `token`
+pub fn erc20_payment_request(token: &str) -> SendTransactionRequest {
+    send_transaction_request("erc20", token)
+}
+
+/// A request whose fee the relayer sponsors
+pub fn sponsored_request() -> SendTransactionRequest {
+    send_transaction_request("sponsored", "")
+}
+
+/// A `relayer_getStatus` request for `ids`
+pub fn status_request(ids: &[&str]) -> GetStatusRequest {
+    GetStatusRequest {
+        ids: ids.iter().map(|id| id.to_string()).collect(),
+        api_key: None,
+    }
+}
+
+/// A `relayer_getExchangeRate`/`relayer_getFeeData` request for `token` on [`TEST_CHAIN_ID`]
+pub fn exchange_rate_request(token: &str) -> ExchangeRateRequest {
+    ExchangeRateRequest {
+        token: token.to_string(),
+        chain_id: TEST_CHAIN_ID.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{params, RpcServer};
+
+    #[tokio::test]
+    async fn test_fixtures_start_a_server() {
+        let (dir, storage) = temp_storage();
+        assert!(dir.path().join("db").exists());
+
+        let config = ConfigBuilder::new()
+            .mock_chain(MockChainClient::new().with_gas_price(1))
+            .admin_token("secret")
+            .build();
+        assert_eq!(config.admin_token.as_deref(), Some("secret"));
+
+        let request = native_payment_request();
+        assert!(params::address(&request.to).is_ok());
+        assert!(params::chain_id(&request.chain_id).is_ok());
+        assert!(params::hex_bytes(&request.data).is_ok());
+
+        let handle = RpcServer::new("127.0.0.1".to_string(), 0, storage, config)
+            .unwrap()
+            .start()
+            .await
+            .unwrap();
+        assert_ne!(handle.local_addr().port(), 0);
+        handle.shutdown().await;
+    }
+}
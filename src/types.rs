@@ -1,3 +1,4 @@
+use alloy::eips::eip4844::BlobTransactionSidecar;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -5,6 +6,8 @@ use uuid::Uuid;
 /// Request status enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum RequestStatus {
+    /// Held by the scheduler until its `execute_after` time; nothing broadcast yet
+    Scheduled,
     Pending,
     Processing,
     Completed,
@@ -28,6 +31,596 @@ pub struct RelayerRequest {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub error_message: Option<String>,
+    /// Bundle this request belongs to when it is one leg of a multichain submission
+    #[serde(default)]
+    pub bundle_id: Option<Uuid>,
+    /// Earliest time a scheduled request may be broadcast
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execute_after: Option<DateTime<Utc>>,
+    /// Lane the request was priced in; it also sets how hard the monitor bumps it
+    #[serde(default)]
+    pub speed: Speed,
+    /// When its first transaction was broadcast
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub broadcast_at: Option<DateTime<Utc>>,
+    /// When the monitor found its transaction mined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mined_at: Option<DateTime<Utc>>,
+    /// Blobs carried by a type-3 relay, kept so replacements carry them too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_sidecar: Option<BlobTransactionSidecar>,
+    /// Blob gas price cap of the latest broadcast, hex-encoded wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<String>,
+    /// Blob gas used by the mined transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<u64>,
+    /// Blob gas price the mined transaction paid, hex-encoded wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_price: Option<String>,
+    /// Signed transaction submitted through `relayer_sendRawTransaction`; the monitor
+    /// rebroadcasts it unchanged since the relayer cannot re-sign it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_transaction: Option<String>,
+    /// Broadcast through the chain's private mempool endpoint rather than its RPC
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private_mempool: bool,
+    /// Payment option chosen from several offered by the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<PaymentCapability>,
+    /// How the relay was paid for, recorded when it is accepted; raw transactions pay their
+    /// own gas and have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<RelayFee>,
+    /// Replacements broadcast so far, including those whose records were compacted away
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub resubmission_count: u32,
+    /// Tenant the request was submitted under; `None` when tenants are not configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+}
+
+fn is_zero(count: &u32) -> bool {
+    *count == 0
+}
+
+/// Payment of a relay, kept for accounting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayFee {
+    pub payment_type: String,
+    pub token: String,
+    /// Native fee the relay was priced at, in wei: its gas at the accepted price plus L1 data
+    /// and blob fees, without any forwarded value
+    pub priced_wei: String,
+}
+
+/// Prioritization lane trading cost for inclusion time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Speed {
+    Slow,
+    #[default]
+    Standard,
+    Fast,
+}
+
+/// How a lane prices gas on a chain
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeedLane {
+    /// Priority fee percentile over recent blocks, added to the next base fee; `None` uses
+    /// the node's `eth_gasPrice`
+    pub percentile: Option<f64>,
+    /// Gas price increase applied on each monitor rebroadcast, in percent
+    #[serde(rename = "bumpPercent")]
+    pub bump_percent: u64,
+}
+
+/// Where a chain's gas price comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GasPriceStrategy {
+    /// The node's `eth_gasPrice`
+    #[default]
+    Node,
+    /// Next base fee plus a priority fee percentile over recent blocks
+    FeeHistory,
+    /// The Polygon gas station's `standard` max fee
+    PolygonGasStation,
+}
+
+/// Call an ERC20 quote asks the wallet to make to pay the fee, set per chain under
+/// `feePayment`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeePayment {
+    /// `transfer(feeCollector, fee)`
+    #[default]
+    Transfer,
+    /// `approve(feeCollector, fee)`, for fee collectors that pull the fee themselves
+    Approve,
+}
+
+/// Transaction envelope the relayer builds on a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxType {
+    /// Type-0 transactions paying `gasPrice`
+    Legacy,
+    /// Type-2 transactions with a fee cap and a priority fee
+    Eip1559,
+}
+
+/// How a chain's gas price is sourced and bounded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasOracle {
+    pub strategy: GasPriceStrategy,
+    /// Priority fee percentile used by the `feeHistory` strategy
+    pub percentile: f64,
+    /// Gas station endpoint used by the `polygonGasStation` strategy
+    #[serde(rename = "gasStationUrl")]
+    pub gas_station_url: Option<String>,
+    /// Lowest gas price quoted or paid, in wei
+    #[serde(rename = "floorWei")]
+    pub floor_wei: Option<u128>,
+    /// Highest gas price quoted or paid, in wei
+    #[serde(rename = "ceilingWei")]
+    pub ceiling_wei: Option<u128>,
+}
+
+impl GasOracle {
+    /// Bound a gas price by the configured floor and ceiling
+    pub fn clamp(&self, wei: u128) -> u128 {
+        let wei = self.floor_wei.map_or(wei, |floor| wei.max(floor));
+        self.ceiling_wei.map_or(wei, |ceiling| wei.min(ceiling))
+    }
+}
+
+/// Compression RocksDB applies to newly written SST files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageCompression {
+    None,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+/// RocksDB tuning knobs, set under `rocksdb` in config.json
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageTuning {
+    /// Capacity of the LRU cache holding uncompressed data blocks
+    pub block_cache_bytes: usize,
+    /// Size a memtable grows to before it is flushed to an SST file
+    pub write_buffer_bytes: usize,
+    pub compression: StorageCompression,
+    /// Open SST file handles kept around; -1 keeps every file open
+    pub max_open_files: i32,
+    /// Collect the tickers behind `block_cache_hit_rate`, at a small cost per read
+    pub statistics: bool,
+}
+
+impl Default for StorageTuning {
+    fn default() -> Self {
+        Self {
+            block_cache_bytes: 32 * 1024 * 1024,
+            write_buffer_bytes: 64 * 1024 * 1024,
+            compression: StorageCompression::Snappy,
+            max_open_files: 10000,
+            statistics: true,
+        }
+    }
+}
+
+/// Limits on gas-bumped rebroadcasts of a request, set under `resubmission` in config.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResubmissionPolicy {
+    /// Resubmissions after which an unmined request is failed instead of bumped again
+    pub max_attempts: u32,
+    /// Latest resubmission records kept per request besides the first; older ones are
+    /// dropped and only counted
+    pub keep_last: usize,
+}
+
+impl Default for ResubmissionPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 50,
+            keep_last: 10,
+        }
+    }
+}
+
+/// Where a request stands as `relayer_getStatus` sees it, reported by name as `statusName`
+/// next to the numeric code [`StatusCodes`] maps it to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StatusKind {
+    /// Held by the scheduler until its `executeAfter` time
+    Scheduled,
+    /// Accepted and not mined yet
+    Pending,
+    /// Mined and executed
+    Confirmed,
+    /// Mined but reverted
+    Reverted,
+    /// Never mined: the broadcast failed or the request was given up on
+    Failed,
+    /// No request with this id or transaction hash
+    NotFound,
+    /// Neither a request id nor a transaction hash
+    InvalidId,
+    /// The relayer could not read the request
+    Error,
+}
+
+impl StatusKind {
+    pub const ALL: [StatusKind; 8] = [
+        StatusKind::Scheduled,
+        StatusKind::Pending,
+        StatusKind::Confirmed,
+        StatusKind::Reverted,
+        StatusKind::Failed,
+        StatusKind::NotFound,
+        StatusKind::InvalidId,
+        StatusKind::Error,
+    ];
+
+    /// Name used for `statusName` and as the key under `statusCodes` in config.json
+    pub fn name(self) -> &'static str {
+        match self {
+            StatusKind::Scheduled => "scheduled",
+            StatusKind::Pending => "pending",
+            StatusKind::Confirmed => "confirmed",
+            StatusKind::Reverted => "reverted",
+            StatusKind::Failed => "failed",
+            StatusKind::NotFound => "notFound",
+            StatusKind::InvalidId => "invalidId",
+            StatusKind::Error => "error",
+        }
+    }
+}
+
+/// Numeric status codes reported for each [`StatusKind`], set under `statusCodes` in
+/// config.json. The default is the HTTP-style scheme of relay spec 2.0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusCodes {
+    codes: [u16; StatusKind::ALL.len()],
+}
+
+impl Default for StatusCodes {
+    fn default() -> Self {
+        Self::http()
+    }
+}
+
+impl StatusCodes {
+    /// 202 scheduled, 201 pending, 200 confirmed, 500 reverted or failed, 404 unknown,
+    /// 400 malformed id, 500 read error
+    pub fn http() -> Self {
+        Self {
+            codes: [202, 201, 200, 500, 500, 404, 400, 500],
+        }
+    }
+
+    /// The `wallet_getCallsStatus` codes of EIP-5792: 100 pending (scheduled included),
+    /// 200 confirmed, 400 never included, 500 reverted
+    pub fn eip5792() -> Self {
+        Self {
+            codes: [100, 100, 200, 500, 400, 404, 400, 500],
+        }
+    }
+
+    /// Scheme called `name` in config.json
+    pub fn scheme(name: &str) -> Option<Self> {
+        match name {
+            "http" => Some(Self::http()),
+            "eip5792" => Some(Self::eip5792()),
+            _ => None,
+        }
+    }
+
+    fn index(kind: StatusKind) -> usize {
+        StatusKind::ALL
+            .iter()
+            .position(|k| *k == kind)
+            .unwrap_or_default()
+    }
+
+    pub fn code(&self, kind: StatusKind) -> u16 {
+        self.codes[Self::index(kind)]
+    }
+
+    pub fn set(&mut self, kind: StatusKind, code: u16) {
+        self.codes[Self::index(kind)] = code;
+    }
+}
+
+/// When finished requests move to the archive, set under `archive` in config.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchivePolicy {
+    /// Days after completing or failing that a request is archived
+    pub after_days: u32,
+    /// Seconds between archive passes
+    pub interval_seconds: u64,
+}
+
+impl Default for ArchivePolicy {
+    fn default() -> Self {
+        Self {
+            after_days: 30,
+            interval_seconds: 3600,
+        }
+    }
+}
+
+/// How repeated submissions of the same relay are handled, set under `duplicates` in
+/// config.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicatePolicy {
+    /// Seconds after a submission during which the same relay counts as a duplicate
+    pub window_seconds: u64,
+    /// Answer a duplicate with the earlier request's id instead of rejecting it
+    pub return_existing: bool,
+}
+
+impl Default for DuplicatePolicy {
+    fn default() -> Self {
+        Self {
+            window_seconds: 10,
+            return_existing: false,
+        }
+    }
+}
+
+/// How a sandboxed chain settles a relay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptOutcome {
+    /// Mined and executed
+    #[default]
+    Success,
+    /// Mined but reverted
+    Reverted,
+    /// Never mined, so the relay stays pending until it is resubmitted or expires
+    Pending,
+}
+
+/// Error a sandboxed method answers every call with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxFailure {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Chain-free sandbox mode, set under `sandbox` in config.json. Chain calls are answered in
+/// memory, transaction hashes are derived from the transaction itself and receipts follow
+/// the scripted outcomes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SandboxSettings {
+    /// Delay added to every simulated chain call
+    pub chain_latency: std::time::Duration,
+    /// Delay added to every RPC method call, unless `method_latency` names the method
+    pub default_latency: std::time::Duration,
+    pub method_latency: std::collections::BTreeMap<String, std::time::Duration>,
+    /// Methods that always fail, by name
+    pub failures: std::collections::BTreeMap<String, SandboxFailure>,
+    /// Outcome of relays to any address not in `receipts`
+    pub default_receipt: ReceiptOutcome,
+    /// Outcome of relays by target address
+    pub receipts: std::collections::HashMap<alloy::primitives::Address, ReceiptOutcome>,
+}
+
+impl SandboxSettings {
+    /// Delay added to calls of `method`
+    pub fn latency(&self, method: &str) -> std::time::Duration {
+        self.method_latency
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_latency)
+    }
+}
+
+/// Whether upstream chain traffic is captured or played back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingMode {
+    /// Forward calls to the chain and append each exchange to the fixture
+    Record,
+    /// Answer calls from the fixture only
+    Replay,
+}
+
+/// Record/replay of upstream chain traffic, set under `rpcRecording` in config.json
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcRecording {
+    pub mode: RecordingMode,
+    /// Fixture file, one JSON exchange per line
+    pub path: std::path::PathBuf,
+}
+
+/// Account state seeded into the local EVM, overriding whatever the chain holds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalAccount {
+    pub balance: alloy::primitives::U256,
+    pub nonce: u64,
+    pub code: alloy::primitives::Bytes,
+    pub storage: std::collections::BTreeMap<alloy::primitives::U256, alloy::primitives::U256>,
+}
+
+/// In-process simulation, set under `localSimulation` in config.json. `eth_call` and
+/// `eth_estimateGas` run in an embedded EVM instead of on the node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalEvmSettings {
+    /// Read missing state from the chain's RPC; without it the EVM starts empty and only
+    /// `accounts` exist
+    pub fork: bool,
+    /// Gas available to each simulated call
+    pub gas_limit: u64,
+    pub accounts: std::collections::BTreeMap<alloy::primitives::Address, LocalAccount>,
+}
+
+impl Default for LocalEvmSettings {
+    fn default() -> Self {
+        Self {
+            fork: true,
+            gas_limit: 30_000_000,
+            accounts: std::collections::BTreeMap::new(),
+        }
+    }
+}
+
+/// Sentry client settings, set under `sentry` in config.json
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentrySettings {
+    /// Environment events are tagged with, such as `production`
+    pub environment: Option<String>,
+    /// Share of error events sent, from 0 to 1
+    pub sample_rate: f32,
+    /// Share of performance traces sent, from 0 to 1
+    pub traces_sample_rate: f32,
+    /// Attach the failing call's method and params to error events
+    pub attach_request_context: bool,
+    /// Keys whose values are masked in events before they leave the process
+    pub scrub_fields: Vec<String>,
+}
+
+impl Default for SentrySettings {
+    fn default() -> Self {
+        Self {
+            environment: None,
+            sample_rate: 1.0,
+            traces_sample_rate: 0.0,
+            attach_request_context: false,
+            scrub_fields: ["apiKey", "authorizationList", "signature", "callbackUrl"]
+                .map(str::to_string)
+                .to_vec(),
+        }
+    }
+}
+
+/// What a relay does when its simulation fails, set under `simulation.onFailure` in
+/// config.json
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SimulationFailureMode {
+    /// Reject the relay with `-4211`
+    #[default]
+    Reject,
+    /// Relay it anyway with the chain's fallback gas limit
+    Fallback,
+    /// Fall back for at most this many relays per chain per hour, then reject
+    FallbackWithCap(u32),
+}
+
+/// Limit of a payment token's fee bounds that a quoted fee hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeBound {
+    Min,
+    Max,
+}
+
+/// Range quoted fees in one payment token are held to, set under `feeBounds` in config.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeBounds {
+    /// Lowest fee quoted, in the token's smallest unit
+    pub min: Option<u64>,
+    /// Highest fee quoted, in the token's smallest unit
+    pub max: Option<u64>,
+    /// Reject a fee outside the range instead of clamping it
+    pub reject: bool,
+}
+
+impl FeeBounds {
+    /// The limit `fee` falls outside of, if any
+    pub fn exceeded(&self, fee: u64) -> Option<(FeeBound, u64)> {
+        match (self.min, self.max) {
+            (Some(min), _) if fee < min => Some((FeeBound::Min, min)),
+            (_, Some(max)) if fee > max => Some((FeeBound::Max, max)),
+            _ => None,
+        }
+    }
+}
+
+/// Native balance targets the rebalancer keeps a chain's relayer keys at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebalancePolicy {
+    /// Environment variables holding the chain's keys besides its relayer key
+    #[serde(rename = "keyRefs")]
+    pub key_refs: Vec<String>,
+    /// A key below this balance, in wei, is topped up
+    #[serde(rename = "minBalance")]
+    pub min_balance: u128,
+    /// Balance, in wei, a topped-up key is brought to and donors never go below
+    #[serde(rename = "targetBalance")]
+    pub target_balance: u128,
+}
+
+/// Web3Signer instance holding one relayer key, set under `remoteSigners` in config.json
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RemoteSignerSettings {
+    /// Base URL of the signer, e.g. `https://web3signer.internal:9000`
+    pub url: String,
+    /// Uncompressed secp256k1 public key of the key, hex-encoded, as the signer's
+    /// `eth1/publicKeys` lists it
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    /// PEM client certificate presented to the signer; set together with `clientKey`
+    #[serde(
+        rename = "clientCert",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub client_cert: Option<std::path::PathBuf>,
+    /// PEM private key of the client certificate
+    #[serde(rename = "clientKey", default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<std::path::PathBuf>,
+    /// PEM CA certificate the signer's server certificate is checked against, besides the
+    /// system roots
+    #[serde(rename = "caCert", default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<std::path::PathBuf>,
+    /// How long a signing request may take
+    #[serde(rename = "timeoutMs", default = "default_remote_signer_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_remote_signer_timeout_ms() -> u64 {
+    5000
+}
+
+/// Uniswap v3 QuoterV2 used to price fee tokens that have no Chainlink feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DexQuoter {
+    /// QuoterV2 contract address
+    pub quoter: String,
+    /// Wrapped native token the fee is swapped from
+    #[serde(rename = "wrappedNative")]
+    pub wrapped_native: String,
+    /// Pool fee tier in hundredths of a bip (3000 = 0.3%)
+    #[serde(rename = "feeTier", default = "default_dex_fee_tier")]
+    pub fee_tier: u32,
+    /// Native amount, in wei, quoted to read the price
+    #[serde(rename = "probeAmount", default = "default_dex_probe_amount")]
+    pub probe_amount: u64,
+    /// Largest accepted price impact of the probe, in basis points, against a probe 100
+    /// times smaller; thinner pools are rejected
+    #[serde(
+        rename = "maxPriceImpactBps",
+        default = "default_dex_max_price_impact_bps"
+    )]
+    pub max_price_impact_bps: u32,
+}
+
+fn default_dex_fee_tier() -> u32 {
+    3000
+}
+
+fn default_dex_probe_amount() -> u64 {
+    100_000_000_000_000_000
+}
+
+fn default_dex_max_price_impact_bps() -> u32 {
+    100
 }
 
 /// Relayer response structure
@@ -76,6 +669,76 @@ pub struct HealthResponse {
     pub pending_requests: u64,
     pub completed_requests: u64,
     pub failed_requests: u64,
+    pub storage: StorageStats,
+    pub lifetime: LifetimeStats,
+}
+
+/// Figures covering every run of the relayer on its database, kept in storage so they
+/// survive restarts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    /// When the relayer first started on this database
+    pub first_started_at: DateTime<Utc>,
+    /// Times the relayer has been started, the current run included
+    pub starts: u64,
+    /// Relayed transactions mined, reverted ones included
+    pub total_relayed: u64,
+    /// Gas used by those transactions, not counting raw transactions paid for by their sender
+    pub total_gas_used: u64,
+    /// Wei spent on that gas and on blob gas, in decimal
+    pub total_gas_spent_wei: String,
+}
+
+impl Default for LifetimeStats {
+    fn default() -> Self {
+        Self {
+            first_started_at: Utc::now(),
+            starts: 0,
+            total_relayed: 0,
+            total_gas_used: 0,
+            total_gas_spent_wei: "0".to_string(),
+        }
+    }
+}
+
+/// RocksDB figures reported by `health_check`; figures RocksDB cannot provide are omitted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StorageStats {
+    /// Total size of all SST files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sst_files_bytes: Option<u64>,
+    /// Data held in memtables, not yet flushed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memtable_bytes: Option<u64>,
+    /// Bytes compaction must rewrite to bring every level under its target size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_compaction_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running_compactions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_cache_usage_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_cache_capacity_bytes: Option<u64>,
+    /// Share of block reads served from the cache since startup; needs `statistics`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_cache_hit_rate: Option<f64>,
+}
+
+/// `health_live`: the process is up and serving requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivenessResponse {
+    pub status: String,
+    pub uptime_seconds: u64,
+}
+
+/// `health_ready`: the dependencies needed to accept relays are available
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub storage_writable: bool,
+    pub chains_configured: usize,
+    pub chains_reachable: usize,
+    pub signer_loaded: bool,
 }
 
 // ===== New endpoint shared types =====
@@ -104,27 +767,252 @@ pub struct PaymentCapability {
     pub data: String,
 }
 
+/// `payment` is a single option, or an ordered list of options of which the relayer uses the
+/// first the wallet can afford
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    try_from = "SendTransactionCapabilitiesWire",
+    into = "SendTransactionCapabilitiesWire"
+)]
 pub struct SendTransactionCapabilities {
+    /// Preferred payment option, or the only one
     pub payment: PaymentCapability,
+    /// Options to fall back to, in order, when the wallet cannot afford `payment`
+    pub fallback_payments: Vec<PaymentCapability>,
+    /// Validate, simulate and price the transaction without persisting or broadcasting it
+    pub dry_run: bool,
+}
+
+impl SendTransactionCapabilities {
+    /// Every payment option, most preferred first
+    pub fn payment_options(&self) -> impl Iterator<Item = &PaymentCapability> {
+        std::iter::once(&self.payment).chain(&self.fallback_payments)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum PaymentOptions {
+    Single(PaymentCapability),
+    Ordered(Vec<PaymentCapability>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SendTransactionCapabilitiesWire {
+    payment: PaymentOptions,
+    #[serde(rename = "dryRun", default, skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+}
+
+impl TryFrom<SendTransactionCapabilitiesWire> for SendTransactionCapabilities {
+    type Error = String;
+
+    fn try_from(wire: SendTransactionCapabilitiesWire) -> Result<Self, Self::Error> {
+        let (payment, fallback_payments) = match wire.payment {
+            PaymentOptions::Single(payment) => (payment, Vec::new()),
+            PaymentOptions::Ordered(mut options) => {
+                if options.is_empty() {
+                    return Err("payment must list at least one option".to_string());
+                }
+                let payment = options.remove(0);
+                (payment, options)
+            }
+        };
+        Ok(Self {
+            payment,
+            fallback_payments,
+            dry_run: wire.dry_run,
+        })
+    }
+}
+
+impl From<SendTransactionCapabilities> for SendTransactionCapabilitiesWire {
+    fn from(capabilities: SendTransactionCapabilities) -> Self {
+        let payment = if capabilities.fallback_payments.is_empty() {
+            PaymentOptions::Single(capabilities.payment)
+        } else {
+            let mut options = vec![capabilities.payment];
+            options.extend(capabilities.fallback_payments);
+            PaymentOptions::Ordered(options)
+        };
+        Self {
+            payment,
+            dry_run: capabilities.dry_run,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendTransactionRequest {
     pub to: String,
     pub data: String,
+    /// Native currency sent with the call, as a hex quantity in wei; defaults to zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
     pub capabilities: SendTransactionCapabilities,
     #[serde(rename = "chainId")]
     pub chain_id: String,
     #[serde(rename = "authorizationList")]
     pub authorization_list: String,
+    /// URL to POST the final status and receipt to once the request completes or fails
+    #[serde(
+        rename = "callbackUrl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub callback_url: Option<String>,
+    /// Hold the request until this time, then simulate, price and broadcast it
+    #[serde(
+        rename = "executeAfter",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub execute_after: Option<DateTime<Utc>>,
+    /// Hold the request until this on-chain check passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<ExecutionCondition>,
+    /// Gas pricing lane; defaults to `standard`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<Speed>,
+    /// EIP-4844 blobs, commitments and proofs; relays as a type-3 blob transaction
+    #[serde(
+        rename = "blobSidecar",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_sidecar: Option<BlobTransactionSidecar>,
+    /// Safe transaction to relay through `execTransaction` on the Safe at `to`; `data` must
+    /// then be empty, as the calldata is built by the relayer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe: Option<SafeTransaction>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// `quoteId` of the relayer_getFeeData or relayer_estimateFee answer the fee was priced
+    /// from; the relay is rejected once that quote has expired
+    #[serde(rename = "quoteId", default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<String>,
+}
+
+/// SafeTx fields and owner signatures for a Safe relay. Quantities are hex strings and,
+/// like the addresses, default to zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeTransaction {
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub data: String,
+    /// 0 for a call, 1 for a delegatecall
+    #[serde(default)]
+    pub operation: u8,
+    #[serde(rename = "safeTxGas", default, skip_serializing_if = "Option::is_none")]
+    pub safe_tx_gas: Option<String>,
+    #[serde(rename = "baseGas", default, skip_serializing_if = "Option::is_none")]
+    pub base_gas: Option<String>,
+    #[serde(rename = "gasPrice", default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
+    #[serde(rename = "gasToken", default, skip_serializing_if = "Option::is_none")]
+    pub gas_token: Option<String>,
+    #[serde(
+        rename = "refundReceiver",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub refund_receiver: Option<String>,
+    /// Safe nonce the owners signed; defaults to, and must equal, the Safe's current nonce
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Owner signatures over the SafeTx hash, concatenated in ascending owner order
+    pub signatures: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConditionType {
+    /// Chainlink aggregator answer compared with a threshold
+    PriceFeed,
+    /// View call whose first return word must be non-zero (`true`)
+    Call,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConditionOperator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+/// On-chain check a relay waits for before it is broadcast
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionCondition {
+    #[serde(rename = "type")]
+    pub condition_type: ConditionType,
+    /// Aggregator for `priceFeed`, contract for `call`
+    pub address: String,
+    /// Calldata of the view, for `call`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// How the answer compares with `value`, for `priceFeed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<ConditionOperator>,
+    /// Threshold in the feed's own units (decimal integer, `decimals()` not applied)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The request fails if the condition has not held by this time
+    #[serde(rename = "expiresAt", default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendTransactionResult {
     #[serde(rename = "chainId")]
     pub chain_id: String,
+    /// Request id; empty for dry runs since nothing is stored
     pub id: String,
+    #[serde(rename = "dryRun", default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<DryRunResult>,
+    /// Payment option the relay was charged with, when the request listed several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<PaymentCapability>,
+    /// Rough time until the relay is mined; omitted for dry runs and scheduled requests
+    #[serde(
+        rename = "estimatedConfirmationSeconds",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimated_confirmation_seconds: Option<u64>,
+}
+
+/// Outcome of a `dryRun` submission: what the relayer would have done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResult {
+    #[serde(rename = "wouldSucceed")]
+    pub would_succeed: bool,
+    #[serde(rename = "gasLimit")]
+    pub gas_limit: u64,
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    /// L2 data fee included in the fee, hex-encoded wei
+    #[serde(rename = "l1DataFee")]
+    pub l1_data_fee: String,
+    /// Blob gas fee included in the fee for blob relays, hex-encoded wei
+    #[serde(rename = "blobFee", default, skip_serializing_if = "Option::is_none")]
+    pub blob_fee: Option<String>,
+    /// Fee in the payment token's smallest unit as a decimal integer string; absent when no
+    /// rate is available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<String>,
+    pub token: TokenInfo,
+    #[serde(
+        rename = "failureReason",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub failure_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -132,12 +1020,92 @@ pub struct SendTransactionResponse {
     pub result: Vec<SendTransactionResult>,
 }
 
+// ===== relayer_sendRawTransaction =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendRawTransactionRequest {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Signed EIP-2718 transaction envelope, hex-encoded
+    #[serde(rename = "rawTransaction")]
+    pub raw_transaction: String,
+    /// Broadcast through the chain's private mempool endpoint, keeping it out of the public
+    /// mempool
+    #[serde(
+        rename = "privateMempool",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub private_mempool: bool,
+    /// URL to POST the final status and receipt to once the request completes or fails
+    #[serde(
+        rename = "callbackUrl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub callback_url: Option<String>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+// ===== relayer_sendCalls =====
+
+/// One call of a `relayer_sendCalls` batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Call {
+    pub to: String,
+    pub data: String,
+    /// Hex wei sent with the call; only zero is accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendCallsRequest {
+    pub calls: Vec<Call>,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    pub capabilities: SendTransactionCapabilities,
+    /// URL to POST the final status and receipt to once the request completes or fails
+    #[serde(
+        rename = "callbackUrl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub callback_url: Option<String>,
+    /// Gas pricing lane; defaults to `standard`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<Speed>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Outcome of one call of a batch, in request order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallResult {
+    pub success: bool,
+    #[serde(rename = "returnData")]
+    pub return_data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendCallsResponse {
+    pub result: Vec<SendTransactionResult>,
+    /// Per-call outcomes from simulating the batch; empty when simulation is disabled
+    pub calls: Vec<CallResult>,
+}
+
 // ===== relayer_sendTransactionMultichain =====
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultichainTransaction {
     pub to: String,
     pub data: String,
+    /// Native currency sent with the call, as a hex quantity in wei; defaults to zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
     #[serde(rename = "chainId")]
     pub chain_id: String,
     #[serde(rename = "authorizationList")]
@@ -150,18 +1118,104 @@ pub struct SendTransactionMultichainRequest {
     pub capabilities: SendTransactionCapabilities,
     #[serde(rename = "paymentChainId")]
     pub payment_chain_id: String,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Outcome of one leg of a multichain submission
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MultichainLegStatus {
+    /// Broadcast; track it by its `id`
+    #[default]
+    Accepted,
+    /// Rejected in validation or simulation, or refused by the node
+    Failed,
+}
+
+/// Why a multichain leg failed, in the shape of the JSON-RPC error a single relay would get
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultichainLegError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultichainTransactionResult {
     #[serde(rename = "chainId")]
     pub chain_id: String,
+    /// Request id; empty for a leg rejected before a request was recorded
     pub id: String,
+    #[serde(default)]
+    pub status: MultichainLegStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<MultichainLegError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendTransactionMultichainResponse {
     pub result: Vec<MultichainTransactionResult>,
+    #[serde(rename = "bundleId")]
+    pub bundle_id: String,
+}
+
+/// Groups the per-chain requests created by a single multichain submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    pub id: Uuid,
+    pub payment_chain_id: u64,
+    pub request_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A `relayer_sendTransaction` call held until `execute_after`, kept whole so it can be
+/// simulated, priced and paid for at execution time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTransaction {
+    pub request_id: Uuid,
+    pub execute_after: DateTime<Utc>,
+    pub request: SendTransactionRequest,
+}
+
+// ===== relayer_getBundleStatus =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBundleStatusRequest {
+    #[serde(rename = "bundleId")]
+    pub bundle_id: String,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleStatusResult {
+    pub version: String,
+    #[serde(rename = "bundleId")]
+    pub bundle_id: String,
+    /// Aggregate status: confirmed when all legs completed, pending while legs are still
+    /// pending, failed once at least one leg failed, notFound for an unknown bundle and
+    /// invalidId for a malformed bundle id (200, 201, 500, 404 and 400 by default)
+    pub status: u16,
+    #[serde(
+        rename = "statusName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_name: Option<StatusKind>,
+    #[serde(rename = "allMined")]
+    pub all_mined: bool,
+    #[serde(rename = "anyFailed")]
+    pub any_failed: bool,
+    pub legs: Vec<StatusResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBundleStatusResponse {
+    pub result: BundleStatusResult,
 }
 
 // ===== relayer_getStatus =====
@@ -169,6 +1223,9 @@ pub struct SendTransactionMultichainResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetStatusRequest {
     pub ids: Vec<String>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -176,6 +1233,22 @@ pub struct Log {
     pub address: String,
     pub topics: Vec<String>,
     pub data: String,
+    /// The log decoded against an event ABI registered for its contract under `eventAbis`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedLog>,
+}
+
+/// A log decoded against a registered event ABI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedLog {
+    /// Event name, e.g. `Transfer`
+    pub name: String,
+    /// Canonical signature whose hash is the log's first topic
+    pub signature: String,
+    /// Arguments by parameter name (position for unnamed parameters). Integers are decimal
+    /// strings, addresses checksummed, byte values hex; indexed strings, bytes and arrays are
+    /// only logged as their keccak256 hash, which is given instead.
+    pub args: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -188,10 +1261,31 @@ pub struct Receipt {
     pub block_number: String,
     #[serde(rename = "gasUsed")]
     pub gas_used: String,
+    /// Price paid per gas, hex-encoded wei
+    #[serde(
+        rename = "effectiveGasPrice",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub effective_gas_price: Option<String>,
     #[serde(rename = "transactionHash")]
     pub transaction_hash: String,
     #[serde(rename = "chainId")]
     pub chain_id: String,
+    /// Blob gas used by a blob transaction, hex-encoded
+    #[serde(
+        rename = "blobGasUsed",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_used: Option<String>,
+    /// Price per blob gas a blob transaction paid, hex-encoded wei
+    #[serde(
+        rename = "blobGasPrice",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_price: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -222,13 +1316,95 @@ pub struct OnchainFailure {
 pub struct StatusResult {
     pub version: String,
     pub id: String,
+    /// Numeric code of `status_name` in the deployment's [`StatusCodes`] scheme
     pub status: u16,
+    #[serde(
+        rename = "statusName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_name: Option<StatusKind>,
     pub receipts: Vec<Receipt>,
     pub resubmissions: Vec<Resubmission>,
     #[serde(rename = "offchainFailure")]
     pub offchain_failure: Vec<OffchainFailure>,
     #[serde(rename = "onchainFailure")]
     pub onchain_failure: Vec<OnchainFailure>,
+    #[serde(rename = "bundleId", default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    /// Rough time until a pending request is mined; only set while status is 201
+    #[serde(
+        rename = "estimatedConfirmationSeconds",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub estimated_confirmation_seconds: Option<u64>,
+    /// Delivery state of the request's callback, if it registered one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback: Option<CallbackDelivery>,
+    /// When a scheduled request (status 202) will be broadcast
+    #[serde(
+        rename = "executeAfter",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub execute_after: Option<DateTime<Utc>>,
+    /// Per-call outcomes of a `relayer_sendCalls` batch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calls: Option<Vec<CallResult>>,
+    /// Payment option the relay was charged with, when the request listed several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<PaymentCapability>,
+    /// Replacements broadcast for the request; `resubmissions` lists only the first and the
+    /// most recent ones
+    #[serde(
+        rename = "resubmissionCount",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resubmission_count: Option<u32>,
+}
+
+impl StatusResult {
+    /// Report `kind` as the status, coded by `codes`
+    pub fn with_status(mut self, kind: StatusKind, codes: &StatusCodes) -> Self {
+        self.status = codes.code(kind);
+        self.status_name = Some(kind);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallbackStatus {
+    /// The request has not finished, or delivery is still being retried
+    Pending,
+    Delivered,
+    /// Every attempt failed
+    Failed,
+}
+
+/// Callback registered with `relayer_sendTransaction` and the outcome of delivering it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallbackDelivery {
+    pub url: String,
+    pub status: CallbackStatus,
+    pub attempts: u32,
+    #[serde(
+        rename = "lastAttemptAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status or transport error of the last failed attempt
+    #[serde(rename = "lastError", default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(
+        rename = "deliveredAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub delivered_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -236,6 +1412,66 @@ pub struct GetStatusResponse {
     pub result: Vec<StatusResult>,
 }
 
+// ===== relayer_getTransactionsByWallet =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTransactionsByWalletRequest {
+    /// Wallet the relayed calls were sent to
+    pub wallet: String,
+    /// Only return requests with this status code (as reported by `relayer_getStatus`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Page size; defaults to 50, at most 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// `nextCursor` from the previous page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Summary of one relayed request; use `relayer_getStatus` for receipts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletTransaction {
+    pub id: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    pub status: u16,
+    #[serde(
+        rename = "transactionHash",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transaction_hash: Option<String>,
+    #[serde(rename = "bundleId", default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    #[serde(
+        rename = "failureReason",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub failure_reason: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Newest requests first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTransactionsByWalletResponse {
+    pub result: Vec<WalletTransaction>,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page
+    #[serde(
+        rename = "nextCursor",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_cursor: Option<String>,
+}
+
 // ===== relayer_getExchangeRate =====
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -263,6 +1499,87 @@ pub struct ExchangeRateSuccess {
     #[serde(rename = "feeCollector")]
     pub fee_collector: String,
     pub expiry: u64,
+    /// Id a relay priced from this rate passes as `quoteId`; absent when the quote was not
+    /// recorded
+    #[serde(rename = "quoteId", default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<String>,
+    /// L2 data fee model for the chain ("optimism" / "arbitrum"), if any
+    #[serde(
+        rename = "l1FeeModel",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub l1_fee_model: Option<String>,
+    /// Current L1 base fee reported by the L2 gas oracle, hex-encoded wei
+    #[serde(rename = "l1BaseFee", default, skip_serializing_if = "Option::is_none")]
+    pub l1_base_fee: Option<String>,
+    /// How the rate was derived; filled in by relayer_getFeeData
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<FeeBreakdown>,
+}
+
+/// How a fee is derived from chain prices and the oracle rate, so integrators and auditors can
+/// recompute it: the wei cost components are summed and converted at `oracleRate`. Amounts are
+/// hex quantities in wei; the cost components are absent when no call was priced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    #[serde(rename = "gasLimit", default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// `gasLimit` times `gasPrice`
+    #[serde(
+        rename = "executionCost",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub execution_cost: Option<String>,
+    #[serde(rename = "l1DataFee", default, skip_serializing_if = "Option::is_none")]
+    pub l1_data_fee: Option<String>,
+    /// Blob gas the relay carries; absent without blobs
+    #[serde(rename = "blobFee", default, skip_serializing_if = "Option::is_none")]
+    pub blob_fee: Option<String>,
+    /// Native value the relayer fronts; absent when zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Gas price the fee is priced at, in the requested lane
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    /// Gas price the node suggests, which `oracleRate` was read against
+    #[serde(rename = "networkGasPrice")]
+    pub network_gas_price: String,
+    /// Median priority fee over recent blocks; absent without fee history
+    #[serde(
+        rename = "priorityFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority_fee_per_gas: Option<String>,
+    /// Whole payment tokens per whole native token; 1 for the native token
+    #[serde(rename = "oracleRate")]
+    pub oracle_rate: f64,
+    /// Premium of `gasPrice` over `networkGasPrice` in basis points; negative for lanes priced
+    /// below the network
+    #[serde(rename = "markupBps")]
+    pub markup_bps: i64,
+}
+
+/// Quote handed out by relayer_getFeeData, relayer_getExchangeRate or relayer_estimateFee,
+/// kept until it expires so relays priced from it can be held to its expiry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssuedQuote {
+    pub id: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Payment token address; the zero address is the native token
+    pub token: String,
+    pub rate: f64,
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    /// Fee in the token's smallest unit as a decimal integer string, for quotes priced for a
+    /// specific call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<String>,
+    /// Unix time the quote stops being honored
+    pub expiry: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -279,7 +1596,7 @@ pub struct ExchangeRateError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ExchangeRateResultItem {
-    Success(ExchangeRateSuccess),
+    Success(Box<ExchangeRateSuccess>),
     Error(ExchangeRateError),
 }
 
@@ -298,19 +1615,61 @@ pub struct QuoteRequestCapabilities {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuoteRequest {
+    /// Wallet to call; unused when `transactions` is given
+    #[serde(default)]
     pub to: String,
+    #[serde(default)]
     pub data: String,
+    /// Native currency the call will send; it is included in the fee, as a hex quantity in wei; defaults to zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
     #[serde(default)]
     pub capabilities: Option<QuoteRequestCapabilities>,
     #[serde(rename = "chainId")]
     pub chain_id: Option<String>,
     #[serde(rename = "authorizationList")]
     pub authorization_list: Option<String>,
+    /// Gas pricing lane to quote for; defaults to `standard`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<Speed>,
+    /// Blobs the relay will carry; their blob gas is included in the fee
+    #[serde(rename = "blobCount", default, skip_serializing_if = "Option::is_none")]
+    pub blob_count: Option<u32>,
+    /// Legs of a `relayer_sendTransactionMultichain` submission to quote together instead of
+    /// `to`/`data` on `chainId`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transactions: Vec<MultichainTransaction>,
+    /// Chain the multichain fee is paid on; required with `transactions`
+    #[serde(
+        rename = "paymentChainId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub payment_chain_id: Option<String>,
+}
+
+/// Gas estimate of one leg of a multichain quote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteLeg {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    #[serde(rename = "gasLimit")]
+    pub gas_limit: u64,
+    /// Gas price the leg is charged at, as a hex quantity in wei
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    /// `gasLimit` times `gasPrice` in the leg chain's native currency, as a hex quantity
+    #[serde(rename = "feeWei")]
+    pub fee_wei: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuoteInner {
-    pub fee: u64,
+    /// Fee in the payment token's smallest unit, as a decimal integer string
+    pub fee: String,
+    /// Decimals of the payment token, for scaling `fee` to whole tokens
+    pub decimals: u8,
+    /// Whole tokens per gas unit; informational, `fee` is what gets charged
     pub rate: f64,
     pub token: TokenInfo,
 }
@@ -324,6 +1683,125 @@ pub struct QuoteResponse {
     pub fee_collector: String,
     #[serde(rename = "revertReason")]
     pub revert_reason: String,
+    /// Lane the quote was priced in
+    pub speed: Speed,
+    /// `feeBounds` limit the fee was clamped to; absent when the fee was within bounds
+    #[serde(rename = "feeBound", default, skip_serializing_if = "Option::is_none")]
+    pub fee_bound: Option<FeeBound>,
+    /// Unix time the quoted fee is attested until
+    #[serde(default)]
+    pub expiry: u64,
+    /// Relayer's EIP-712 attestation of the fee, token, expiry, wallet and chain; absent
+    /// without a relayer key for the chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Per-leg gas estimates of a multichain quote, in request order; empty otherwise
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legs: Vec<QuoteLeg>,
+    /// How `quote.fee` was derived; absent for multichain quotes, whose `legs` carry the costs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<FeeBreakdown>,
+}
+
+// ===== relayer_estimateFee =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateFeeRequest {
+    pub to: String,
+    pub data: String,
+    /// Native currency the call will send; it is included in the fee, as a hex quantity in wei; defaults to zero
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Payment token address; the zero address pays in the native token
+    pub token: String,
+    /// Gas pricing lane to estimate for; defaults to `standard`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<Speed>,
+}
+
+/// Everything needed to price a relay, combining `relayer_getFeeData` and `relayer_getQuote`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateFeeResponse {
+    #[serde(rename = "gasLimit")]
+    pub gas_limit: u64,
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    /// EIP-1559 fee cap (twice the next base fee plus the tip); absent without fee history
+    #[serde(
+        rename = "maxFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_fee_per_gas: Option<String>,
+    /// Median tip paid over recent blocks; absent without fee history
+    #[serde(
+        rename = "maxPriorityFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_priority_fee_per_gas: Option<String>,
+    /// L2 data fee included in the fee, hex-encoded wei
+    #[serde(rename = "l1DataFee")]
+    pub l1_data_fee: String,
+    /// Whole payment tokens per gas unit at `gasPrice`
+    pub rate: f64,
+    pub token: TokenInfo,
+    /// Fee in the payment token's smallest unit, as a decimal integer string
+    pub fee: String,
+    #[serde(rename = "feeCollector")]
+    pub fee_collector: String,
+    pub expiry: u64,
+    /// Id a relay priced from this estimate passes as `quoteId`; absent when the quote was not
+    /// recorded
+    #[serde(rename = "quoteId", default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<String>,
+    /// Lane `gasPrice` was picked for
+    pub speed: Speed,
+    /// `feeBounds` limit the fee was clamped to; absent when the fee was within bounds
+    #[serde(rename = "feeBound", default, skip_serializing_if = "Option::is_none")]
+    pub fee_bound: Option<FeeBound>,
+    /// Relayer's EIP-712 attestation of the fee, token, expiry, wallet and chain; absent
+    /// without a relayer key for the chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+// ===== relayer_simulate =====
+
+/// `relayer_simulate` takes the same parameters as `relayer_estimateFee`
+pub type SimulateRequest = EstimateFeeRequest;
+
+/// Pre-flight result of a relay call: how it executes and what relaying it costs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateResponse {
+    /// `false` when the call reverts
+    pub success: bool,
+    #[serde(rename = "gasUsed")]
+    pub gas_used: u64,
+    /// Gas limit the relayer would send the call with; absent when it reverts
+    #[serde(rename = "gasLimit", default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// Decoded `Error(string)` message, `Panic` meaning or custom error signature
+    #[serde(
+        rename = "revertReason",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub revert_reason: Option<String>,
+    /// Raw revert data, hex-encoded
+    #[serde(
+        rename = "revertData",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub revert_data: Option<String>,
+    /// Logs the call would emit
+    pub logs: Vec<Log>,
+    /// Fee quote as `relayer_estimateFee` returns it; absent when the call reverts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<EstimateFeeResponse>,
 }
 
 // ===== relayer_getCapabilities =====
@@ -370,9 +1848,303 @@ pub struct Capabilities {
     pub payment: Vec<Payment>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetCapabilitiesRequest {
+    #[serde(rename = "chainId", default)]
+    pub chain_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainCapabilities {
+    pub payment: Vec<Payment>,
+    /// Smart account implementations (delegation targets) accepted on this chain
+    #[serde(rename = "walletImplementations")]
+    pub wallet_implementations: Vec<String>,
+    /// Whether the calls of a single relayed transaction execute atomically
+    pub atomic: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetCapabilitiesResponse {
+    /// Payment options across all returned chains (kept for spec compatibility)
     pub capabilities: Capabilities,
+    /// Capabilities keyed by chain ID
+    pub chains: std::collections::BTreeMap<String, ChainCapabilities>,
+    pub limits: RelayLimits,
+    pub policies: RelayPolicies,
+    /// Methods this relayer serves; those in `disabledMethods` are left out
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+/// Limits enforced on every relayed transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayLimits {
+    /// Largest accepted `data`, in bytes
+    #[serde(rename = "maxCalldataBytes")]
+    pub max_calldata_bytes: u64,
+    /// Most gas a single relayed transaction may use
+    #[serde(rename = "maxGas")]
+    pub max_gas: u64,
+    /// Seconds a quote or exchange rate stays valid after it is issued
+    #[serde(rename = "quoteExpirySeconds")]
+    pub quote_expiry_seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayPolicies {
+    /// Sponsored relays must carry an accepted API key in `capabilities.payment.data`
+    #[serde(rename = "sponsorshipRequiresApiKey")]
+    pub sponsorship_requires_api_key: bool,
+    /// Chain IDs accepting each payment type (`native`, `erc20`, `permit`, `sponsored`,
+    /// `gasTank`)
+    #[serde(rename = "paymentChains")]
+    pub payment_chains: std::collections::BTreeMap<String, Vec<String>>,
+}
+
+// ===== relayer_getSupportedChains =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportedChain {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    #[serde(rename = "relayerAddress")]
+    pub relayer_address: Option<String>,
+    #[serde(rename = "paymentTokens")]
+    pub payment_tokens: Vec<String>,
+    pub confirmations: u64,
+    pub available: bool,
+    #[serde(rename = "blockNumber")]
+    pub block_number: Option<u64>,
+    /// Chain name from the chain registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Block explorer from the chain registry
+    #[serde(
+        rename = "explorerUrl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub explorer_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSupportedChainsResponse {
+    pub chains: Vec<SupportedChain>,
+}
+
+// ===== admin_addChain / admin_removeChain =====
+
+/// A chain registered at runtime through the admin API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainRegistration {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    #[serde(rename = "rpcUrl")]
+    pub rpc_url: String,
+    /// ERC20 payment token address -> Chainlink token/USD feed address
+    #[serde(default)]
+    pub tokens: std::collections::BTreeMap<String, String>,
+    #[serde(rename = "nativeUsdFeed", default)]
+    pub native_usd_feed: Option<String>,
+    /// Name of the environment variable holding the relayer key for this chain
+    #[serde(rename = "relayerKeyRef", default)]
+    pub relayer_key_ref: Option<String>,
+    /// DEX quoter pricing fee tokens without a Chainlink feed
+    #[serde(rename = "dexQuoter", default)]
+    pub dex_quoter: Option<DexQuoter>,
+    /// Transaction type to build, overriding the startup probe
+    #[serde(rename = "txType", default)]
+    pub tx_type: Option<TxType>,
+    /// Endpoint that keeps transactions out of the public mempool, such as Flashbots Protect
+    #[serde(rename = "privateRpcUrl", default)]
+    pub private_rpc_url: Option<String>,
+    /// Contract address -> event ABI items its receipt logs are decoded with
+    #[serde(
+        rename = "eventAbis",
+        default,
+        skip_serializing_if = "std::collections::BTreeMap::is_empty"
+    )]
+    pub event_abis: std::collections::BTreeMap<String, Vec<alloy::json_abi::Event>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddChainRequest {
+    #[serde(rename = "adminToken")]
+    pub admin_token: String,
+    #[serde(flatten)]
+    pub chain: ChainRegistration,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveChainRequest {
+    #[serde(rename = "adminToken")]
+    pub admin_token: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminChainResponse {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    pub status: String,
+}
+
+/// `admin_exportAccounting` params: the completion-time range to export, as RFC 3339 times
+/// or `YYYY-MM-DD` dates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportAccountingRequest {
+    #[serde(rename = "adminToken")]
+    pub admin_token: String,
+    /// Inclusive lower bound
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Exclusive upper bound
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Only `csv` is supported; defaults to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportAccountingResponse {
+    pub format: String,
+    /// Number of exported requests, excluding the header row
+    pub rows: usize,
+    /// The export itself
+    pub data: String,
+}
+
+// ===== admin_getRefundReport =====
+
+/// What a mined relay was charged beyond its gas cost, recorded when the gas came in at least
+/// `refunds.thresholdPercent` below the price it was charged at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Overpayment {
+    #[serde(rename = "requestId")]
+    pub request_id: Uuid,
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    pub wallet: String,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    #[serde(rename = "paymentType")]
+    pub payment_type: String,
+    pub token: String,
+    /// Native fee the relay was charged at, in wei
+    #[serde(rename = "pricedWei")]
+    pub priced_wei: String,
+    /// What its gas and blob gas actually cost, in wei
+    #[serde(rename = "spentWei")]
+    pub spent_wei: String,
+    /// `pricedWei` less `spentWei`
+    #[serde(rename = "overpaidWei")]
+    pub overpaid_wei: String,
+    /// Refund due in the payment token's smallest unit: the token fee collected in the receipt
+    /// scaled by `overpaidWei` / `pricedWei`, or `overpaidWei` for native and gas tank fees
+    #[serde(rename = "refundAmount")]
+    pub refund_amount: String,
+    #[serde(rename = "recordedAt")]
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// `admin_getRefundReport` params: the recording-time range to report, as RFC 3339 times or
+/// `YYYY-MM-DD` dates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRefundReportRequest {
+    #[serde(rename = "adminToken")]
+    pub admin_token: String,
+    /// Inclusive lower bound
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Exclusive upper bound
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(rename = "chainId", default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    /// Also build the calls that pay the refunds back
+    #[serde(rename = "includeCalls", default)]
+    pub include_calls: bool,
+}
+
+/// Call paying back one overpayment, for the operator to send from the fee collector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundCall {
+    #[serde(rename = "requestId")]
+    pub request_id: Uuid,
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    pub to: String,
+    pub data: String,
+    /// Native value to send, as a hex quantity in wei
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRefundReportResponse {
+    /// Overpayments in the range, oldest first
+    pub refunds: Vec<Overpayment>,
+    /// Refund calls when `includeCalls` is set; gas tank refunds have none and are credited
+    /// back by the operator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<RefundCall>,
+}
+
+// ===== admin_listRequests / admin_cancelRequest =====
+
+/// `admin_listRequests` params, the filters of `relayx list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRequestsRequest {
+    #[serde(rename = "adminToken")]
+    pub admin_token: String,
+    /// `scheduled`, `pending`, `processing`, `completed` or `failed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Target address, looked up through the index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// Defaults to 50
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRequestsResponse {
+    /// Stored requests, newest first
+    pub requests: Vec<RelayerRequest>,
+}
+
+/// `admin_cancelRequest` params
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRequestRequest {
+    #[serde(rename = "adminToken")]
+    pub admin_token: String,
+    /// Request id returned by `relayer_sendTransaction`
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelRequestResponse {
+    pub id: String,
+    /// Self-transfer broadcast at the nonce of the request's pending transaction so its call
+    /// cannot be mined
+    #[serde(
+        rename = "replacementHash",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub replacement_hash: Option<String>,
+    /// Wei returned to the wallet's gas tank
+    #[serde(
+        rename = "refundedWei",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub refunded_wei: Option<String>,
 }
 
 pub type FeeDataRequest = ExchangeRateRequest;
@@ -381,3 +2153,395 @@ pub type FeeDataSuccess = ExchangeRateSuccess;
 pub type FeeDataError = ExchangeRateError;
 pub type FeeDataResultItem = ExchangeRateResultItem;
 pub type FeeDataResponse = ExchangeRateResponse;
+
+// ===== relayer_getFeeHistory =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetFeeHistoryRequest {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Payment token; defaults to the zero address (native token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Most recent samples to return; defaults to all that are kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// A chain's gas price and a payment token's rate as sampled by the relayer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeSample {
+    /// Unix seconds
+    pub timestamp: u64,
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    /// Whole tokens per gas unit at `gasPrice`, as in `relayer_getFeeData`
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetFeeHistoryResponse {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    pub token: String,
+    /// Oldest first
+    pub samples: Vec<FeeSample>,
+}
+
+// ===== relayer_getQueueStats =====
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetQueueStatsRequest {
+    /// Only report this chain; defaults to every chain with a configured RPC or requests
+    #[serde(rename = "chainId", default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    /// How far back, in seconds, broadcasts count towards the averages; defaults to 3600
+    #[serde(
+        rename = "windowSeconds",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub window_seconds: Option<u64>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+/// Queue depth and throughput of one chain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChainQueueStats {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Accepted but not yet broadcast: scheduled, or waiting on a condition
+    pub queued: u64,
+    /// Broadcast and waiting to be mined
+    #[serde(rename = "inFlight")]
+    pub in_flight: u64,
+    /// Requests first broadcast within the window
+    pub broadcast: u64,
+    /// Mean time from acceptance (or `executeAfter`) to first broadcast, in milliseconds
+    #[serde(rename = "avgBroadcastLatencyMs")]
+    pub avg_broadcast_latency_ms: Option<u64>,
+    /// Mean time from first broadcast to being found mined, in milliseconds
+    #[serde(rename = "avgInclusionMs")]
+    pub avg_inclusion_ms: Option<u64>,
+    /// Gas-bumped rebroadcasts per request broadcast within the window
+    #[serde(rename = "resubmissionRate")]
+    pub resubmission_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetQueueStatsResponse {
+    #[serde(rename = "windowSeconds")]
+    pub window_seconds: u64,
+    /// Ordered by chain id
+    pub chains: Vec<ChainQueueStats>,
+}
+
+// ===== relayer_resendTransaction =====
+
+/// Positional params `[id, options?]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResendTransactionRequest(
+    pub String,
+    #[serde(default)] pub Option<ResendTransactionOptions>,
+);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResendTransactionOptions {
+    /// Factor applied to the higher of the current network gas price and the price of the
+    /// pending transaction; defaults to 1.2
+    #[serde(
+        rename = "gasMultiplier",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub gas_multiplier: Option<f64>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResendTransactionResponse {
+    pub id: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Hash of the replacement transaction
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    /// Gas price the replacement was sent at
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+}
+
+// ===== relayer_getVersion =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetVersionResponse {
+    /// Crate version
+    pub version: String,
+    #[serde(rename = "gitCommit")]
+    pub git_commit: String,
+    #[serde(rename = "buildTimestamp")]
+    pub build_timestamp: DateTime<Utc>,
+    /// Relay API spec versions this relayer implements (the `version` in status results)
+    #[serde(rename = "specVersions")]
+    pub spec_versions: Vec<String>,
+    pub features: VersionFeatures,
+    /// Methods this relayer serves; those in `disabledMethods` are left out
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+/// Optional protocol features, so clients can feature-detect instead of probing endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionFeatures {
+    /// EIP-7702 `authorizationList` on `relayer_sendTransaction`
+    pub eip7702: bool,
+    /// ERC-4337 user operations
+    pub erc4337: bool,
+    /// `relayer_sendTransactionMultichain`, unless disabled
+    pub multichain: bool,
+}
+
+// ===== relayer_getReceipt =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReceiptRequest {
+    /// Request id, or any transaction hash broadcast for it
+    pub id: String,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReceiptResponse {
+    pub id: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Same code as `relayer_getStatus`
+    pub status: u16,
+    /// Every hash broadcast for the request, original first, then each replacement
+    pub transactions: Vec<BroadcastTransaction>,
+    /// Hash of the transaction that was mined, once one has been
+    #[serde(rename = "minedTransactionHash")]
+    pub mined_transaction_hash: Option<String>,
+    /// Receipts of the mined transaction; empty while none has been mined
+    pub receipts: Vec<Receipt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BroadcastTransaction {
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    pub mined: bool,
+    /// `true` once a later broadcast superseded this one
+    pub replaced: bool,
+}
+
+// ===== Gas tank =====
+
+/// Native-token deposit credited to a wallet's prepaid gas balance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasTankDeposit {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Wallet credited: the sender of the deposit transaction
+    pub wallet: String,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    /// Amount credited, in wei
+    pub amount: String,
+    #[serde(rename = "creditedAt")]
+    pub credited_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitGasTankDepositRequest {
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Mined transfer of native tokens to the relayer's deposit address
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitGasTankDepositResponse {
+    pub deposit: GasTankDeposit,
+    /// Wallet balance on the chain after the credit, in wei
+    pub balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGasTankBalanceRequest {
+    pub wallet: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGasTankBalanceResponse {
+    pub wallet: String,
+    #[serde(rename = "chainId")]
+    pub chain_id: String,
+    /// Prepaid balance, in wei
+    pub balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGasTankDepositsRequest {
+    pub wallet: String,
+    /// Only return deposits made on this chain
+    #[serde(rename = "chainId", default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    /// Page size; defaults to 50, at most 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGasTankDepositsResponse {
+    /// Newest first
+    pub deposits: Vec<GasTankDeposit>,
+}
+
+// ===== Tenants =====
+
+/// A customer from the `tenants` config, identified by its API key. Each tenant only sees
+/// the requests submitted with its key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tenant {
+    pub id: String,
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+}
+
+// ===== Sponsorship projects =====
+
+/// A project from the `sponsorship.projects` config, identified by its API key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SponsorshipProject {
+    pub id: String,
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    pub tier: String,
+    /// Sponsored gas the project may use per calendar month (UTC); `None` is unlimited
+    #[serde(rename = "monthlyGas")]
+    pub monthly_gas: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSponsorshipAllowanceRequest {
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSponsorshipAllowanceResponse {
+    pub project: String,
+    pub tier: String,
+    /// Billing period, `YYYY-MM` in UTC
+    pub period: String,
+    /// Monthly allowance in gas; `null` when the tier is unlimited
+    pub allowance: Option<u64>,
+    /// Sponsored gas charged this period
+    pub used: u64,
+    /// `null` when the tier is unlimited
+    pub remaining: Option<u64>,
+    #[serde(rename = "resetsAt")]
+    pub resets_at: DateTime<Utc>,
+}
+
+// ===== Recurring jobs =====
+
+/// A `relayer_sendTransaction` template relayed again every `intervalSeconds`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringJob {
+    pub id: Uuid,
+    /// Relayed as-is on every run, under the same policy and payment checks as a direct call
+    pub request: SendTransactionRequest,
+    #[serde(rename = "intervalSeconds")]
+    pub interval_seconds: u64,
+    /// Paused jobs keep their schedule but are skipped until resumed
+    pub paused: bool,
+    #[serde(rename = "nextRunAt")]
+    pub next_run_at: DateTime<Utc>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    /// Runs attempted so far, failed ones included
+    pub runs: u64,
+    #[serde(rename = "lastRunAt", default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Request id of the last successful run, for `relayer_getStatus`
+    #[serde(
+        rename = "lastRequestId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_request_id: Option<Uuid>,
+    /// Why the last run was rejected; cleared by the next successful run
+    #[serde(rename = "lastError", default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Tenant the job was created under; its runs are relayed with the key in `request`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRecurringJobRequest {
+    pub request: SendTransactionRequest,
+    #[serde(rename = "intervalSeconds")]
+    pub interval_seconds: u64,
+    /// First run; defaults to now
+    #[serde(rename = "startAt", default, skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PauseRecurringJobRequest {
+    pub id: String,
+    /// `false` resumes a paused job; defaults to `true`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRecurringJobsRequest {
+    /// Target wallet (`request.to`) of the jobs
+    pub wallet: String,
+    /// Key of the tenant the call is made for; required once tenants are configured
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRecurringJobsResponse {
+    /// Oldest first
+    pub jobs: Vec<RecurringJob>,
+}
+
+// ===== Nonce healing =====
+
+/// Self-transfer the monitor broadcast to fill a nonce gap left by a dropped relayer
+/// transaction, unblocking every later nonce
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonceHealing {
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    /// Relayer key whose nonce sequence was blocked
+    pub relayer: String,
+    /// The missing nonce, filled by the self-transfer
+    pub nonce: u64,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: String,
+    /// Gas price of the self-transfer, in wei as 0x hex
+    #[serde(rename = "gasPrice")]
+    pub gas_price: String,
+    #[serde(rename = "healedAt")]
+    pub healed_at: DateTime<Utc>,
+}
@@ -0,0 +1,722 @@
+//! WebSocket transport for upstream nodes.
+//!
+//! A chain whose RPC URL is `ws://` or `wss://` is reached over one persistent connection
+//! per endpoint instead of an HTTP request per call. [`WsTransport`] plugs into alloy as a
+//! transport: it matches responses to requests by JSON-RPC id, and when the connection drops
+//! it fails the calls in flight and re-dials with exponential backoff. It can also hold a
+//! `newHeads` subscription, which lets the monitor look for receipts as soon as a block
+//! arrives instead of on its next poll.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{TransportError, TransportErrorKind, TransportFut},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ring::{
+    digest::{digest, SHA1_FOR_LEGACY_USE_ONLY},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde_json::Value;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    sync::{mpsc, oneshot, watch},
+};
+use tokio_rustls::{
+    rustls::{pki_types::ServerName, ClientConfig, RootCertStore},
+    TlsConnector,
+};
+use url::Url;
+
+/// Appended to the handshake key before hashing it into `Sec-WebSocket-Accept` (RFC 6455)
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// How long dialing and the upgrade handshake may take
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the first re-dial, doubled after each failure up to [`RECONNECT_MAX`]
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(30);
+
+/// Idle connections are pinged this often and dropped after two intervals without traffic
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Largest message accepted from the node
+const MAX_MESSAGE_BYTES: usize = 64 << 20;
+
+/// JSON-RPC id of the relayer's own `eth_subscribe` for new heads
+const HEADS_REQUEST_ID: &str = "relayx-newHeads";
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xa;
+
+/// A persistent JSON-RPC connection to one WebSocket endpoint. Clones share the connection,
+/// which is closed once every clone is dropped.
+#[derive(Clone)]
+pub struct WsTransport {
+    commands: mpsc::UnboundedSender<Command>,
+    heads: Arc<watch::Sender<u64>>,
+    /// Endpoint host for error messages; the full URL may carry an API key
+    host: Arc<str>,
+}
+
+enum Command {
+    Request {
+        key: String,
+        payload: String,
+        reply: oneshot::Sender<Result<String, String>>,
+    },
+    WatchHeads,
+}
+
+impl WsTransport {
+    /// Start the connection task for `url`; must be called within a Tokio runtime. Calls
+    /// made before the first dial completes wait for it.
+    pub fn connect(url: Url) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let heads = Arc::new(watch::channel(0).0);
+        let host: Arc<str> = url.host_str().unwrap_or_default().into();
+        tokio::spawn(run(url, receiver, heads.clone(), host.clone()));
+        Self {
+            commands,
+            heads,
+            host,
+        }
+    }
+
+    /// Send one serialized JSON-RPC request or batch and return the raw response
+    async fn send(&self, payload: String) -> Result<String, String> {
+        let key = serde_json::from_str(&payload)
+            .ok()
+            .and_then(|value| message_key(&value))
+            .ok_or_else(|| "JSON-RPC request without an id".to_string())?;
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(Command::Request {
+                key,
+                payload,
+                reply,
+            })
+            .map_err(|_| self.closed())?;
+        response.await.map_err(|_| self.closed())?
+    }
+
+    fn closed(&self) -> String {
+        format!("WebSocket connection to {} closed", self.host)
+    }
+
+    /// Wait for the next block the endpoint announces and return its number. Subscribes to
+    /// `newHeads` on first use and again after every reconnect.
+    pub async fn next_head(&self) -> u64 {
+        let mut heads = self.heads.subscribe();
+        let _ = self.commands.send(Command::WatchHeads);
+        match heads.changed().await {
+            Ok(()) => *heads.borrow(),
+            Err(_) => std::future::pending().await,
+        }
+    }
+}
+
+impl tower::Service<RequestPacket> for WsTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let transport = self.clone();
+        Box::pin(async move {
+            let payload = serde_json::to_string(&request).map_err(TransportError::ser_err)?;
+            let response = transport
+                .send(payload)
+                .await
+                .map_err(|e| TransportErrorKind::custom_str(&e))?;
+            serde_json::from_str(&response).map_err(|e| TransportError::deser_err(e, &response))
+        })
+    }
+}
+
+/// Key matching a response to its request: the id, or for a batch every id in sorted order,
+/// as servers may answer a batch in any order
+fn message_key(message: &Value) -> Option<String> {
+    let mut ids: Vec<String> = match message {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.get("id"))
+            .filter(|id| !id.is_null())
+            .map(Value::to_string)
+            .collect(),
+        single => vec![single.get("id").filter(|id| !id.is_null())?.to_string()],
+    };
+    ids.sort();
+    (!ids.is_empty()).then(|| ids.join(","))
+}
+
+/// How a connection ended
+enum Ended {
+    /// Every transport was dropped; the task stops
+    Closed,
+    /// The connection failed and is re-dialed
+    Lost(String),
+}
+
+/// Connection task: dial, serve until the connection drops, back off and dial again
+async fn run(
+    url: Url,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    heads: Arc<watch::Sender<u64>>,
+    host: Arc<str>,
+) {
+    let mut watch_heads = false;
+    let mut backoff = RECONNECT_MIN;
+    loop {
+        let dialed = tokio::time::timeout(CONNECT_TIMEOUT, dial(&url))
+            .await
+            .unwrap_or_else(|_| Err(format!("no answer within {:?}", CONNECT_TIMEOUT)));
+        match dialed {
+            Ok(stream) => {
+                tracing::debug!("WebSocket connected to {}", host);
+                backoff = RECONNECT_MIN;
+                match serve(stream, &mut commands, &mut watch_heads, &heads).await {
+                    Ended::Closed => return,
+                    Ended::Lost(reason) => {
+                        tracing::warn!("WebSocket connection to {} lost: {}", host, reason)
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(
+                "WebSocket connection to {} failed: {}; retrying in {:?}",
+                host,
+                e,
+                backoff
+            ),
+        }
+
+        // Calls made while disconnected fail instead of waiting for the next dial
+        let retry = tokio::time::sleep(backoff);
+        tokio::pin!(retry);
+        loop {
+            tokio::select! {
+                _ = &mut retry => break,
+                command = commands.recv() => match command {
+                    Some(Command::Request { reply, .. }) => {
+                        let _ = reply.send(Err(format!("WebSocket connection to {} is down", host)));
+                    }
+                    Some(Command::WatchHeads) => watch_heads = true,
+                    None => return,
+                },
+            }
+        }
+        backoff = (backoff * 2).min(RECONNECT_MAX);
+    }
+}
+
+/// Serve requests over one established connection. Requests still waiting for a response
+/// when it ends fail as their reply channel is dropped.
+async fn serve(
+    stream: Box<dyn Io>,
+    commands: &mut mpsc::UnboundedReceiver<Command>,
+    watch_heads: &mut bool,
+    heads: &watch::Sender<u64>,
+) -> Ended {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let (messages_tx, mut messages) = mpsc::channel(64);
+    let reader = tokio::spawn(read_messages(reader, messages_tx));
+
+    let mut pending: HashMap<String, oneshot::Sender<Result<String, String>>> = HashMap::new();
+    let mut heads_subscription: Option<String> = None;
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    ping.tick().await;
+    let mut last_seen = Instant::now();
+
+    let ended = async {
+        if *watch_heads {
+            write_frame(&mut writer, OP_TEXT, subscribe_heads().as_bytes()).await?;
+        }
+        loop {
+            tokio::select! {
+                command = commands.recv() => match command {
+                    Some(Command::Request { key, payload, reply }) => {
+                        write_frame(&mut writer, OP_TEXT, payload.as_bytes()).await?;
+                        pending.insert(key, reply);
+                    }
+                    Some(Command::WatchHeads) if !*watch_heads => {
+                        *watch_heads = true;
+                        write_frame(&mut writer, OP_TEXT, subscribe_heads().as_bytes()).await?;
+                    }
+                    Some(Command::WatchHeads) => {}
+                    None => {
+                        let _ = write_frame(&mut writer, OP_CLOSE, &[]).await;
+                        return Ok(Ended::Closed);
+                    }
+                },
+                message = messages.recv() => {
+                    last_seen = Instant::now();
+                    match message {
+                        Some(Ok(Message::Text(text))) => {
+                            dispatch(text, &mut pending, &mut heads_subscription, heads)
+                        }
+                        Some(Ok(Message::Ping(data))) => write_frame(&mut writer, OP_PONG, &data).await?,
+                        Some(Ok(Message::Pong)) => {}
+                        Some(Ok(Message::Close)) | None => return Err("closed by the node".to_string()),
+                        Some(Err(e)) => return Err(e),
+                    }
+                }
+                _ = ping.tick() => {
+                    if last_seen.elapsed() > PING_INTERVAL * 2 {
+                        return Err(format!("no traffic for {:?}", PING_INTERVAL * 2));
+                    }
+                    write_frame(&mut writer, OP_PING, &[]).await?;
+                }
+            }
+        }
+    }
+    .await;
+    reader.abort();
+    ended.unwrap_or_else(Ended::Lost)
+}
+
+fn subscribe_heads() -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": HEADS_REQUEST_ID,
+        "method": "eth_subscribe",
+        "params": ["newHeads"],
+    })
+    .to_string()
+}
+
+/// Route a message from the node to the request waiting for it, or record a new head
+fn dispatch(
+    text: String,
+    pending: &mut HashMap<String, oneshot::Sender<Result<String, String>>>,
+    heads_subscription: &mut Option<String>,
+    heads: &watch::Sender<u64>,
+) {
+    let Ok(message) = serde_json::from_str::<Value>(&text) else {
+        tracing::warn!("Ignoring WebSocket message that is not JSON");
+        return;
+    };
+    if message.get("method").and_then(Value::as_str) == Some("eth_subscription") {
+        let params = &message["params"];
+        if heads_subscription.is_some()
+            && params["subscription"].as_str() == heads_subscription.as_deref()
+        {
+            if let Some(number) = params["result"]["number"]
+                .as_str()
+                .and_then(|n| u64::from_str_radix(n.trim_start_matches("0x"), 16).ok())
+            {
+                heads.send_replace(number);
+            }
+        }
+        return;
+    }
+    if message.get("id").and_then(Value::as_str) == Some(HEADS_REQUEST_ID) {
+        *heads_subscription = message["result"].as_str().map(str::to_string);
+        if heads_subscription.is_none() {
+            tracing::warn!(
+                "Node refused the newHeads subscription: {}",
+                message["error"]
+            );
+        }
+        return;
+    }
+    if let Some(reply) = message_key(&message).and_then(|key| pending.remove(&key)) {
+        let _ = reply.send(Ok(text));
+    }
+}
+
+/// Byte stream of a connection, plain or TLS
+trait Io: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Io for T {}
+
+async fn dial(url: &Url) -> Result<Box<dyn Io>, String> {
+    let host = url.host_str().ok_or("URL has no host")?;
+    let port = url.port_or_known_default().ok_or("URL has no port")?;
+    let tcp = TcpStream::connect((host.trim_start_matches('[').trim_end_matches(']'), port))
+        .await
+        .map_err(|e| e.to_string())?;
+    let _ = tcp.set_nodelay(true);
+    let mut stream: Box<dyn Io> = match url.scheme() {
+        "wss" => {
+            let name = ServerName::try_from(host.to_string()).map_err(|e| e.to_string())?;
+            let tls = TlsConnector::from(tls_config())
+                .connect(name, tcp)
+                .await
+                .map_err(|e| format!("TLS handshake failed: {}", e))?;
+            Box::new(tls)
+        }
+        _ => Box::new(tcp),
+    };
+    handshake(&mut stream, url).await?;
+    Ok(stream)
+}
+
+/// Client TLS settings trusting the Mozilla root certificates
+fn tls_config() -> Arc<ClientConfig> {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    CONFIG
+        .get_or_init(|| {
+            let roots = RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            };
+            let provider = tokio_rustls::rustls::crypto::ring::default_provider();
+            Arc::new(
+                ClientConfig::builder_with_provider(Arc::new(provider))
+                    .with_safe_default_protocol_versions()
+                    .expect("ring supports the default TLS versions")
+                    .with_root_certificates(roots)
+                    .with_no_client_auth(),
+            )
+        })
+        .clone()
+}
+
+/// HTTP/1.1 upgrade to the WebSocket protocol. Credentials in the URL are sent as basic
+/// authentication.
+async fn handshake(stream: &mut Box<dyn Io>, url: &Url) -> Result<(), String> {
+    let mut nonce = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "failed to generate a handshake key".to_string())?;
+    let key = BASE64.encode(nonce);
+
+    let mut target = url.path().to_string();
+    if let Some(query) = url.query() {
+        target.push('?');
+        target.push_str(query);
+    }
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    let mut request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n",
+        target, host, key
+    );
+    if !url.username().is_empty() {
+        let credentials = format!("{}:{}", url.username(), url.password().unwrap_or_default());
+        request.push_str(&format!(
+            "Authorization: Basic {}\r\n",
+            BASE64.encode(credentials)
+        ));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Read byte by byte so no frame data following the headers is consumed
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > 16 * 1024 {
+            return Err("upgrade response headers too large".to_string());
+        }
+        response.push(stream.read_u8().await.map_err(|e| e.to_string())?);
+    }
+    let response = String::from_utf8_lossy(&response);
+    let mut lines = response.split("\r\n");
+    let status = lines.next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("101") {
+        return Err(format!("upgrade refused: {}", status));
+    }
+    let accept = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("sec-websocket-accept"))
+        .map(|(_, value)| value.trim());
+    if accept != Some(accept_key(&key).as_str()) {
+        return Err("upgrade response has a wrong Sec-WebSocket-Accept".to_string());
+    }
+    Ok(())
+}
+
+/// `Sec-WebSocket-Accept` value a server must answer handshake `key` with
+fn accept_key(key: &str) -> String {
+    BASE64.encode(digest(
+        &SHA1_FOR_LEGACY_USE_ONLY,
+        format!("{}{}", key, WS_GUID).as_bytes(),
+    ))
+}
+
+/// One final client frame, masked with `mask` as clients must
+fn encode_frame(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 14);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len if len < 126 => frame.push(0x80 | len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(&mask);
+    frame.extend(
+        payload
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ mask[i % 4]),
+    );
+    frame
+}
+
+async fn write_frame(
+    writer: &mut (impl AsyncWrite + Unpin),
+    opcode: u8,
+    payload: &[u8],
+) -> Result<(), String> {
+    let mut mask = [0u8; 4];
+    SystemRandom::new()
+        .fill(&mut mask)
+        .map_err(|_| "failed to generate a frame mask".to_string())?;
+    writer
+        .write_all(&encode_frame(opcode, payload, mask))
+        .await
+        .map_err(|e| e.to_string())?;
+    writer.flush().await.map_err(|e| e.to_string())
+}
+
+struct Frame {
+    fin: bool,
+    opcode: u8,
+    payload: Vec<u8>,
+}
+
+async fn read_frame(reader: &mut (impl AsyncRead + Unpin)) -> Result<Frame, String> {
+    let io = |e: std::io::Error| e.to_string();
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).await.map_err(io)?;
+    let len = match head[1] & 0x7f {
+        126 => u64::from(reader.read_u16().await.map_err(io)?),
+        127 => reader.read_u64().await.map_err(io)?,
+        len => u64::from(len),
+    };
+    if len > MAX_MESSAGE_BYTES as u64 {
+        return Err(format!("frame of {} bytes is too large", len));
+    }
+    let mut mask = None;
+    if head[1] & 0x80 != 0 {
+        let mut key = [0u8; 4];
+        reader.read_exact(&mut key).await.map_err(io)?;
+        mask = Some(key);
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await.map_err(io)?;
+    if let Some(mask) = mask {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    Ok(Frame {
+        fin: head[0] & 0x80 != 0,
+        opcode: head[0] & 0x0f,
+        payload,
+    })
+}
+
+enum Message {
+    Text(String),
+    Ping(Vec<u8>),
+    Pong,
+    Close,
+}
+
+/// Reader task: reassemble frames into messages until the connection ends
+async fn read_messages(
+    mut reader: impl AsyncRead + Unpin,
+    messages: mpsc::Sender<Result<Message, String>>,
+) {
+    let mut partial: Option<Vec<u8>> = None;
+    loop {
+        let message = match read_frame(&mut reader).await {
+            Ok(frame) => match frame.opcode {
+                OP_TEXT | OP_BINARY | OP_CONTINUATION => match assemble(&mut partial, frame) {
+                    Ok(Some(data)) => String::from_utf8(data)
+                        .map(Message::Text)
+                        .map_err(|_| "message is not UTF-8".to_string()),
+                    Ok(None) => continue,
+                    Err(e) => Err(e),
+                },
+                OP_PING => Ok(Message::Ping(frame.payload)),
+                OP_PONG => Ok(Message::Pong),
+                OP_CLOSE => Ok(Message::Close),
+                opcode => Err(format!("unknown frame opcode {}", opcode)),
+            },
+            Err(e) => Err(e),
+        };
+        let last = !matches!(
+            message,
+            Ok(Message::Text(_) | Message::Ping(_) | Message::Pong)
+        );
+        if messages.send(message).await.is_err() || last {
+            return;
+        }
+    }
+}
+
+/// Add a data frame to the message being received; returns the message once complete
+fn assemble(partial: &mut Option<Vec<u8>>, frame: Frame) -> Result<Option<Vec<u8>>, String> {
+    let mut data = match (frame.opcode, partial.take()) {
+        (OP_CONTINUATION, Some(data)) => data,
+        (OP_CONTINUATION, None) => return Err("continuation frame without a message".into()),
+        (_, Some(_)) => return Err("new message before the previous one ended".into()),
+        (_, None) => Vec::new(),
+    };
+    data.extend_from_slice(&frame.payload);
+    if data.len() > MAX_MESSAGE_BYTES {
+        return Err(format!("message of {} bytes is too large", data.len()));
+    }
+    if frame.fin {
+        Ok(Some(data))
+    } else {
+        *partial = Some(data);
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        providers::{Provider, ProviderBuilder},
+        rpc::client::RpcClient,
+    };
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[tokio::test]
+    async fn test_frames_round_trip_at_every_length_encoding() {
+        for len in [5, 300, 70_000] {
+            let payload = vec![b'x'; len];
+            let encoded = encode_frame(OP_TEXT, &payload, [1, 2, 3, 4]);
+            assert!(!encoded.windows(8).any(|w| w == b"xxxxxxxx"));
+            let frame = read_frame(&mut encoded.as_slice()).await.unwrap();
+            assert!(frame.fin);
+            assert_eq!(frame.opcode, OP_TEXT);
+            assert_eq!(frame.payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_batch_responses_match_in_any_order() {
+        let request = serde_json::json!([{"id": 1, "method": "a"}, {"id": 2, "method": "b"}]);
+        let response = serde_json::json!([{"id": 2, "result": "0x2"}, {"id": 1, "result": "0x1"}]);
+        assert_eq!(message_key(&request), message_key(&response));
+        assert_eq!(
+            message_key(&serde_json::json!({"id": "7"})).unwrap(),
+            "\"7\""
+        );
+        assert_eq!(
+            message_key(&serde_json::json!({"method": "eth_subscription"})),
+            None
+        );
+    }
+
+    /// Unmasked server frame
+    fn server_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = encode_frame(opcode, payload, [0; 4]);
+        let len_byte = frame[1] & 0x7f;
+        frame[1] = len_byte;
+        let mask_at = match len_byte {
+            126 => 4,
+            127 => 10,
+            _ => 2,
+        };
+        frame.drain(mask_at..mask_at + 4);
+        frame
+    }
+
+    /// Accept one connection and answer `eth_chainId` and `eth_subscribe`, announcing block
+    /// 0x10 after a subscription. The connection is dropped after `requests` answers.
+    async fn serve_node(listener: &TcpListener, requests: usize) {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(socket.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        let key = head
+            .lines()
+            .find_map(|line| line.strip_prefix("Sec-WebSocket-Key: "))
+            .unwrap();
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+
+        for _ in 0..requests {
+            let frame = read_frame(&mut socket).await.unwrap();
+            let request: Value = serde_json::from_slice(&frame.payload).unwrap();
+            let result = match request["method"].as_str() {
+                Some("eth_subscribe") => "0xsub",
+                _ => "0x5",
+            };
+            let reply =
+                serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": result});
+            socket
+                .write_all(&server_frame(OP_TEXT, reply.to_string().as_bytes()))
+                .await
+                .unwrap();
+            if result == "0xsub" {
+                let head = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_subscription",
+                    "params": {"subscription": "0xsub", "result": {"number": "0x10"}},
+                });
+                socket
+                    .write_all(&server_frame(OP_TEXT, head.to_string().as_bytes()))
+                    .await
+                    .unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_answers_calls_and_reconnects() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: Url = format!("ws://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let transport = WsTransport::connect(url);
+        let provider = ProviderBuilder::new().on_client(RpcClient::new(transport.clone(), false));
+
+        // Served by the first connection, which the node then drops
+        let (chain_id, _) = tokio::join!(provider.get_chain_id(), serve_node(&listener, 1));
+        assert_eq!(chain_id.unwrap(), 5);
+
+        // The transport re-dials, subscribes to new heads and keeps answering calls
+        let node = tokio::spawn(async move { serve_node(&listener, 2).await });
+        assert_eq!(transport.next_head().await, 16);
+        assert_eq!(provider.get_chain_id().await.unwrap(), 5);
+        node.await.unwrap();
+    }
+}
@@ -0,0 +1,371 @@
+//! End-to-end tests against a local anvil node.
+//!
+//! Enabled with `cargo test --features e2e --test anvil_e2e`; requires the `anvil`
+//! binary from Foundry on `PATH` (or set `ANVIL_BIN`). Each test starts its own anvil,
+//! deploys a minimal wallet that implements `executeWithRelayer`, and drives a real
+//! relayer through sendTransaction → receipt → Completed.
+#![cfg(feature = "e2e")]
+
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use alloy::{
+    json_abi::JsonAbi,
+    network::{EthereumWallet, TransactionBuilder},
+    primitives::{Address, Bytes, U256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    signers::{local::PrivateKeySigner, SignerSync},
+};
+use alloy_eip7702::Authorization;
+use relayx::{
+    client::RelayxClient,
+    config::Config,
+    rpc::{RpcServer, ServerHandle},
+    storage::Storage,
+    types::{
+        ChainRegistration, ExchangeRateRequest, ExchangeRateResultItem, PaymentCapability,
+        SendTransactionCapabilities, SendTransactionRequest,
+    },
+};
+use tempfile::TempDir;
+use url::Url;
+
+/// anvil account 0, used as the relayer
+const RELAYER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcab78d7ff2f84ae28";
+/// anvil account 1, used to deploy fixtures
+const DEPLOYER_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+/// anvil account 2, the EOA delegating to the wallet in the EIP-7702 path
+const DELEGATOR_KEY: &str = "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a";
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Running anvil process, killed on drop
+struct Anvil {
+    child: Child,
+    url: String,
+    chain_id: u64,
+}
+
+impl Anvil {
+    /// Start anvil with its own chain id; tests run concurrently and the relayer's
+    /// runtime chain registry is process-wide, so each test needs a distinct chain
+    async fn spawn(chain_id: u64) -> Self {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .expect("free port")
+            .port();
+        let bin = std::env::var("ANVIL_BIN").unwrap_or_else(|_| "anvil".to_string());
+        let child = Command::new(&bin)
+            .args([
+                "--port",
+                &port.to_string(),
+                "--chain-id",
+                &chain_id.to_string(),
+                "--silent",
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap_or_else(|e| panic!("failed to start {} (is Foundry installed?): {}", bin, e));
+        let anvil = Anvil {
+            child,
+            url: format!("http://127.0.0.1:{}", port),
+            chain_id,
+        };
+
+        let provider = ProviderBuilder::new().on_hyper_http(anvil.endpoint());
+        for _ in 0..100 {
+            if provider.get_chain_id().await.is_ok() {
+                return anvil;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("anvil did not become ready at {}", anvil.url);
+    }
+
+    fn endpoint(&self) -> Url {
+        Url::parse(&self.url).unwrap()
+    }
+
+    /// Deploy `runtime` behind a constructor that returns it verbatim
+    async fn deploy(&self, runtime: &[u8]) -> Address {
+        let len = u8::try_from(runtime.len()).expect("runtime fits a PUSH1 length");
+        // PUSH1 len, DUP1, PUSH1 11, PUSH1 0, CODECOPY, PUSH1 0, RETURN
+        let mut init = vec![
+            0x60, len, 0x80, 0x60, 0x0b, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3,
+        ];
+        init.extend_from_slice(runtime);
+
+        let signer: PrivateKeySigner = DEPLOYER_KEY.parse().unwrap();
+        let provider = ProviderBuilder::new()
+            .with_recommended_fillers()
+            .wallet(EthereumWallet::from(signer))
+            .on_hyper_http(self.endpoint());
+        let tx = TransactionRequest::default().with_deploy_code(Bytes::from(init));
+        provider
+            .send_transaction(tx)
+            .await
+            .expect("deploy sent")
+            .get_receipt()
+            .await
+            .expect("deploy mined")
+            .contract_address
+            .expect("contract address")
+    }
+
+    /// Number of successful executeWithRelayer calls recorded by the sample wallet
+    async fn relay_count(&self, wallet: Address) -> U256 {
+        ProviderBuilder::new()
+            .on_hyper_http(self.endpoint())
+            .get_storage_at(wallet, U256::ZERO)
+            .await
+            .unwrap()
+    }
+}
+
+impl Drop for Anvil {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn execute_with_relayer_selector() -> [u8; 4] {
+    let abi: serde_json::Value =
+        serde_json::from_str(include_str!("../resources/abi.json")).unwrap();
+    let abi: JsonAbi = serde_json::from_value(abi["abi"].clone()).unwrap();
+    abi.function("executeWithRelayer").unwrap()[0].selector().0
+}
+
+/// Runtime of the sample wallet: `executeWithRelayer` increments storage slot 0, any
+/// other selector reverts
+fn sample_wallet_runtime() -> Vec<u8> {
+    let mut code = vec![0x60, 0x00, 0x35, 0x60, 0xe0, 0x1c, 0x63];
+    code.extend_from_slice(&execute_with_relayer_selector());
+    code.extend_from_slice(&[
+        0x14, 0x60, 0x13, 0x57, // EQ, PUSH1 dest, JUMPI
+        0x60, 0x00, 0x80, 0xfd, // revert(0, 0)
+        0x5b, // JUMPDEST
+        0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x60, 0x00, 0x55, // slot0 += 1
+        0x00, // STOP
+    ]);
+    code
+}
+
+/// Runtime answering every call with the same word: 8 as Chainlink `decimals()`,
+/// `latestAnswer()` and ERC20 `decimals()`, which is enough for a token priced 1:1
+/// against the native asset
+fn constant_feed_runtime() -> Vec<u8> {
+    let mut code = vec![0x7f];
+    code.extend_from_slice(&U256::from(8).to_be_bytes::<32>());
+    code.extend_from_slice(&[0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+    code
+}
+
+fn calldata() -> String {
+    format!(
+        "0x{}{}",
+        alloy::hex::encode(execute_with_relayer_selector()),
+        "00".repeat(64)
+    )
+}
+
+fn send_request(
+    anvil: &Anvil,
+    wallet: Address,
+    payment_type: &str,
+    token: &str,
+) -> SendTransactionRequest {
+    SendTransactionRequest {
+        to: format!("{:#x}", wallet),
+        data: calldata(),
+        capabilities: SendTransactionCapabilities {
+            payment: PaymentCapability {
+                payment_type: payment_type.to_string(),
+                token: token.to_string(),
+                data: "".to_string(),
+            },
+            dry_run: false,
+            fallback_payments: Vec::new(),
+        },
+        chain_id: anvil.chain_id.to_string(),
+        authorization_list: "".to_string(),
+        callback_url: None,
+        execute_after: None,
+        condition: None,
+        speed: None,
+        blob_sidecar: None,
+        safe: None,
+        value: None,
+        api_key: None,
+        quote_id: None,
+    }
+}
+
+/// Relayer serving the anvil chain, with `tokens` accepted as ERC20 payment
+async fn start_relayer(
+    temp_dir: &TempDir,
+    anvil: &Anvil,
+    tokens: &[(Address, Address)],
+) -> (ServerHandle, RelayxClient) {
+    let config = Config {
+        rpc_host: "127.0.0.1".to_string(),
+        rpc_port: 0,
+        db_path: temp_dir.path().join("db"),
+        relayers: String::new(),
+        max_concurrent_requests: 100,
+        request_timeout: 30,
+        config_path: None,
+        http_address: "127.0.0.1".to_string(),
+        http_port: 0,
+        http_cors: "*".to_string(),
+        log_level: "info".to_string(),
+        relayer_private_key: Some(RELAYER_KEY.to_string()),
+        relayer_private_key_file: None,
+        disable_simulation: false,
+        sentry_dsn: None,
+        admin_token: None,
+        callback_secret: None,
+        callback_allow_private_hosts: false,
+        gas_price_ttl_ms: 3000,
+        chain: Default::default(),
+        encryption: Default::default(),
+        relayer_key_from_file: Default::default(),
+    };
+    config.register_runtime_chain(ChainRegistration {
+        chain_id: anvil.chain_id.to_string(),
+        rpc_url: anvil.url.clone(),
+        tokens: tokens
+            .iter()
+            .map(|(token, feed)| (format!("{:#x}", token), format!("{:#x}", feed)))
+            .collect(),
+        native_usd_feed: tokens.first().map(|(_, feed)| format!("{:#x}", feed)),
+        relayer_key_ref: None,
+        dex_quoter: None,
+        tx_type: None,
+        private_rpc_url: None,
+        event_abis: Default::default(),
+    });
+
+    let storage = Storage::new(temp_dir.path().join("storage")).unwrap();
+    let handle = RpcServer::new("127.0.0.1".to_string(), 0, storage, config)
+        .unwrap()
+        .start()
+        .await
+        .unwrap();
+    let client = RelayxClient::new(format!("http://{}", handle.local_addr()));
+    (handle, client)
+}
+
+/// Submit `request` and wait for the background monitor to pick up its receipt
+async fn relay_to_completion(client: &RelayxClient, request: &SendTransactionRequest) {
+    let response = client.send_transaction(request).await.unwrap();
+    let id = response.result[0].id.clone();
+    let status = client
+        .wait_for_completion(&id, Duration::from_secs(1), Duration::from_secs(45))
+        .await
+        .unwrap();
+    assert_eq!(status.status, 200, "request {} did not complete", id);
+    assert_eq!(status.receipts.len(), 1);
+}
+
+#[tokio::test]
+async fn test_native_payment_relays_to_completion() {
+    let anvil = Anvil::spawn(31337).await;
+    let wallet = anvil.deploy(&sample_wallet_runtime()).await;
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, client) = start_relayer(&temp_dir, &anvil, &[]).await;
+
+    // Fund the wallet so it can cover the native fee check
+    let funder: PrivateKeySigner = DEPLOYER_KEY.parse().unwrap();
+    ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(EthereumWallet::from(funder))
+        .on_hyper_http(anvil.endpoint())
+        .send_transaction(
+            TransactionRequest::default()
+                .to(wallet)
+                .value(U256::from(10u128.pow(18))),
+        )
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    relay_to_completion(
+        &client,
+        &send_request(&anvil, wallet, "native", ZERO_ADDRESS),
+    )
+    .await;
+    assert_eq!(anvil.relay_count(wallet).await, U256::from(1));
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_erc20_payment_relays_to_completion() {
+    let anvil = Anvil::spawn(31338).await;
+    let wallet = anvil.deploy(&sample_wallet_runtime()).await;
+    let token = anvil.deploy(&constant_feed_runtime()).await;
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, client) = start_relayer(&temp_dir, &anvil, &[(token, token)]).await;
+
+    let rate = client
+        .get_exchange_rate(&ExchangeRateRequest {
+            token: format!("{:#x}", token),
+            chain_id: anvil.chain_id.to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(
+        rate.result.first(),
+        Some(ExchangeRateResultItem::Success(_))
+    ));
+
+    let request = send_request(&anvil, wallet, "erc20", &format!("{:#x}", token));
+    relay_to_completion(&client, &request).await;
+    assert_eq!(anvil.relay_count(wallet).await, U256::from(1));
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_eip7702_authorization_relays_to_completion() {
+    let anvil = Anvil::spawn(31339).await;
+    let wallet = anvil.deploy(&sample_wallet_runtime()).await;
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, client) = start_relayer(&temp_dir, &anvil, &[]).await;
+
+    let delegator: PrivateKeySigner = DELEGATOR_KEY.parse().unwrap();
+    let authorization = Authorization {
+        chain_id: anvil.chain_id,
+        address: wallet,
+        nonce: 0,
+    };
+    let signature = delegator
+        .sign_hash_sync(&authorization.signature_hash())
+        .unwrap();
+    let signed = vec![authorization.into_signed(signature)];
+
+    let mut request = send_request(&anvil, wallet, "sponsored", ZERO_ADDRESS);
+    request.authorization_list = format!("0x{}", alloy::hex::encode(alloy_rlp::encode(&signed)));
+    relay_to_completion(&client, &request).await;
+    assert_eq!(anvil.relay_count(wallet).await, U256::from(1));
+
+    // A list signed for another chain is rejected before anything is broadcast
+    let foreign = Authorization {
+        chain_id: anvil.chain_id + 1,
+        address: wallet,
+        nonce: 0,
+    };
+    let signature = delegator.sign_hash_sync(&foreign.signature_hash()).unwrap();
+    request.authorization_list = format!(
+        "0x{}",
+        alloy::hex::encode(alloy_rlp::encode(vec![foreign.into_signed(signature)]))
+    );
+    assert!(client.send_transaction(&request).await.is_err());
+
+    handle.shutdown().await;
+}
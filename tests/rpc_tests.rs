@@ -4,7 +4,7 @@ use relayx::{
     types::{
         ExchangeRateRequest, GetStatusRequest, MultichainTransaction, PaymentCapability,
         QuoteRequest, SendTransactionCapabilities, SendTransactionMultichainRequest,
-        SendTransactionRequest,
+        SendTransactionRequest, Speed,
     },
 };
 use serde_json::json;
@@ -27,8 +27,16 @@ fn create_test_config(temp_dir: &TempDir) -> Config {
         http_cors: "*".to_string(),
         log_level: "info".to_string(),
         relayer_private_key: None,
+        relayer_private_key_file: None,
         disable_simulation: false,
         sentry_dsn: None,
+        admin_token: None,
+        callback_secret: None,
+        callback_allow_private_hosts: false,
+        gas_price_ttl_ms: 3000,
+        chain: Default::default(),
+        encryption: Default::default(),
+        relayer_key_from_file: Default::default(),
     }
 }
 
@@ -54,9 +62,20 @@ mod send_transaction_tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         // This should fail validation
@@ -74,9 +93,20 @@ mod send_transaction_tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         assert!(request.data.is_empty());
@@ -93,9 +123,20 @@ mod send_transaction_tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         assert!(request.chain_id.is_empty());
@@ -112,9 +153,20 @@ mod send_transaction_tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "invalid".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         let result: Result<u64, _> = request.chain_id.parse();
@@ -132,9 +184,20 @@ mod send_transaction_tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         assert_eq!(request.capabilities.payment.payment_type, "native");
@@ -155,9 +218,20 @@ mod send_transaction_tests {
                     token: "0x742d35Cc6C3C3f4b4C1b3cd6c0d1b6C2B3d4e5f6".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         // Native payment should have zero address
@@ -178,9 +252,20 @@ mod send_transaction_tests {
                     token: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         assert_eq!(request.capabilities.payment.payment_type, "erc20");
@@ -199,9 +284,20 @@ mod send_transaction_tests {
                     token: "0xInvalid".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         // Should be invalid length
@@ -219,13 +315,97 @@ mod send_transaction_tests {
                     token: String::new(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             chain_id: "1".to_string(),
             authorization_list: String::new(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
         };
 
         assert_eq!(request.capabilities.payment.payment_type, "sponsored");
     }
+
+    #[test]
+    fn test_send_transaction_dry_run_capability() {
+        let payload = json!({
+            "to": "0x742d35Cc6C3C3f4b4C1b3cd6c0d1b6C2B3d4e5f6",
+            "data": "0x1234",
+            "capabilities": {
+                "payment": { "type": "sponsored", "token": "", "data": "" },
+                "dryRun": true
+            },
+            "chainId": "1",
+            "authorizationList": ""
+        });
+        let request: SendTransactionRequest = serde_json::from_value(payload.clone()).unwrap();
+        assert!(request.capabilities.dry_run);
+
+        // dryRun defaults to false and is omitted when serializing regular submissions
+        let mut payload = payload;
+        payload["capabilities"]
+            .as_object_mut()
+            .unwrap()
+            .remove("dryRun");
+        let request: SendTransactionRequest = serde_json::from_value(payload).unwrap();
+        assert!(!request.capabilities.dry_run);
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert!(serialized["capabilities"].get("dryRun").is_none());
+    }
+
+    #[test]
+    fn test_payment_options_round_trip() {
+        let usdc = json!({
+            "type": "erc20",
+            "token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "data": ""
+        });
+        let native = json!({
+            "type": "native",
+            "token": "0x0000000000000000000000000000000000000000",
+            "data": ""
+        });
+        let payload = |payment: serde_json::Value| {
+            json!({
+                "to": "0x742d35Cc6C3C3f4b4C1b3cd6c0d1b6C2B3d4e5f6",
+                "data": "0x1234",
+                "capabilities": { "payment": payment },
+                "chainId": "1",
+                "authorizationList": ""
+            })
+        };
+
+        let request: SendTransactionRequest =
+            serde_json::from_value(payload(json!([usdc, native]))).unwrap();
+        let options: Vec<_> = request
+            .capabilities
+            .payment_options()
+            .map(|payment| payment.payment_type.as_str())
+            .collect();
+        assert_eq!(options, ["erc20", "native"]);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["capabilities"]["payment"],
+            json!([usdc, native])
+        );
+
+        // A single option keeps its object form
+        let request: SendTransactionRequest =
+            serde_json::from_value(payload(json!([native]))).unwrap();
+        assert!(request.capabilities.fallback_payments.is_empty());
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["capabilities"]["payment"],
+            native
+        );
+        assert!(serde_json::from_value::<SendTransactionRequest>(payload(json!([]))).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -239,6 +419,7 @@ mod get_status_tests {
                 "550e8400-e29b-41d4-a716-446655440000".to_string(),
                 "6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string(),
             ],
+            api_key: None,
         };
 
         assert_eq!(request.ids.len(), 2);
@@ -246,7 +427,10 @@ mod get_status_tests {
 
     #[test]
     fn test_get_status_with_empty_ids() {
-        let request = GetStatusRequest { ids: vec![] };
+        let request = GetStatusRequest {
+            ids: vec![],
+            api_key: None,
+        };
 
         assert!(request.ids.is_empty());
     }
@@ -255,6 +439,7 @@ mod get_status_tests {
     fn test_get_status_with_invalid_uuid() {
         let request = GetStatusRequest {
             ids: vec!["invalid-uuid".to_string()],
+            api_key: None,
         };
 
         use uuid::Uuid;
@@ -316,6 +501,11 @@ mod quote_tests {
             capabilities: None,
             chain_id: Some("1".to_string()),
             authorization_list: None,
+            speed: None,
+            blob_count: None,
+            value: None,
+            transactions: Vec::new(),
+            payment_chain_id: None,
         };
 
         assert!(!request.to.is_empty());
@@ -335,6 +525,11 @@ mod quote_tests {
             }),
             chain_id: Some("1".to_string()),
             authorization_list: None,
+            speed: None,
+            blob_count: None,
+            value: None,
+            transactions: Vec::new(),
+            payment_chain_id: None,
         };
 
         assert!(request.capabilities.is_some());
@@ -354,12 +549,14 @@ mod send_transaction_multichain_tests {
                     data: "0x1234".to_string(),
                     chain_id: "1".to_string(),
                     authorization_list: String::new(),
+                    value: None,
                 },
                 MultichainTransaction {
                     to: "0x8922b54716264130634d6ff183747a8ead91a40c".to_string(),
                     data: "0x5678".to_string(),
                     chain_id: "137".to_string(),
                     authorization_list: String::new(),
+                    value: None,
                 },
             ],
             capabilities: SendTransactionCapabilities {
@@ -368,8 +565,11 @@ mod send_transaction_multichain_tests {
                     token: String::new(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             payment_chain_id: "1".to_string(),
+            api_key: None,
         };
 
         assert_eq!(request.transactions.len(), 2);
@@ -386,8 +586,11 @@ mod send_transaction_multichain_tests {
                     token: String::new(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             payment_chain_id: "1".to_string(),
+            api_key: None,
         };
 
         // Should fail validation
@@ -405,6 +608,7 @@ mod send_transaction_multichain_tests {
                 data: "0x1234".to_string(),
                 chain_id: chain.to_string(),
                 authorization_list: String::new(),
+                value: None,
             });
         }
 
@@ -416,8 +620,11 @@ mod send_transaction_multichain_tests {
                     token: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             payment_chain_id: "1".to_string(),
+            api_key: None,
         };
 
         assert_eq!(request.transactions.len(), 5);
@@ -433,12 +640,14 @@ mod send_transaction_multichain_tests {
                     data: "0x1234".to_string(),
                     chain_id: "10".to_string(),
                     authorization_list: String::new(),
+                    value: None,
                 },
                 MultichainTransaction {
                     to: "0x8922b54716264130634d6ff183747a8ead91a40c".to_string(),
                     data: "0x5678".to_string(),
                     chain_id: "8453".to_string(),
                     authorization_list: String::new(),
+                    value: None,
                 },
             ],
             capabilities: SendTransactionCapabilities {
@@ -447,8 +656,11 @@ mod send_transaction_multichain_tests {
                     token: "0x0000000000000000000000000000000000000000".to_string(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             payment_chain_id: "1".to_string(),
+            api_key: None,
         };
 
         // Payment chain (1) is different from transaction chains (10, 8453)
@@ -465,12 +677,14 @@ mod send_transaction_multichain_tests {
                     data: "0x1234".to_string(),
                     chain_id: "1".to_string(),
                     authorization_list: String::new(),
+                    value: None,
                 },
                 MultichainTransaction {
                     to: "0x8922b54716264130634d6ff183747a8ead91a40c".to_string(),
                     data: "0x5678".to_string(),
                     chain_id: "1".to_string(),
                     authorization_list: String::new(),
+                    value: None,
                 },
             ],
             capabilities: SendTransactionCapabilities {
@@ -479,8 +693,11 @@ mod send_transaction_multichain_tests {
                     token: String::new(),
                     data: String::new(),
                 },
+                dry_run: false,
+                fallback_payments: Vec::new(),
             },
             payment_chain_id: "1".to_string(),
+            api_key: None,
         };
 
         // Multiple transactions on the same chain is valid
@@ -494,7 +711,10 @@ mod send_transaction_multichain_tests {
 #[cfg(test)]
 mod storage_tests {
     use chrono::Utc;
-    use relayx::types::{RelayerRequest, RequestStatus};
+    use relayx::{
+        storage::{StorageBatch, TenantScope},
+        types::{FeeSample, RelayerRequest, RequestStatus, Resubmission, ScheduledTransaction},
+    };
     use uuid::Uuid;
 
     use super::*;
@@ -520,13 +740,31 @@ mod storage_tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
         };
 
         // Create request
         storage.create_request(request.clone()).await.unwrap();
 
         // Retrieve request
-        let retrieved = storage.get_request(request_id).await.unwrap();
+        let retrieved = storage
+            .get_request(TenantScope::All, request_id)
+            .await
+            .unwrap();
         assert!(retrieved.is_some());
 
         let retrieved_request = retrieved.unwrap();
@@ -557,6 +795,21 @@ mod storage_tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
         };
 
         // Create request
@@ -569,7 +822,11 @@ mod storage_tests {
             .unwrap();
 
         // Verify update
-        let updated = storage.get_request(request_id).await.unwrap().unwrap();
+        let updated = storage
+            .get_request(TenantScope::All, request_id)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(updated.status, RequestStatus::Completed);
     }
 
@@ -603,6 +860,21 @@ mod storage_tests {
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 error_message: None,
+                bundle_id: None,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
             };
 
             storage.create_request(request).await.unwrap();
@@ -649,6 +921,21 @@ mod storage_tests {
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 error_message: None,
+                bundle_id: None,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
             };
 
             storage.create_request(request).await.unwrap();
@@ -680,20 +967,389 @@ mod storage_tests {
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 error_message: None,
+                bundle_id: None,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
             };
 
             storage.create_request(request).await.unwrap();
         }
 
         // Get with limit
-        let requests = storage.get_requests(Some(3)).await.unwrap();
+        let requests = storage
+            .get_requests(TenantScope::All, Some(3))
+            .await
+            .unwrap();
         assert_eq!(requests.len(), 3);
 
         // Get all
-        let all_requests = storage.get_requests(None).await.unwrap();
+        let all_requests = storage.get_requests(TenantScope::All, None).await.unwrap();
         assert_eq!(all_requests.len(), 5);
     }
 
+    #[tokio::test]
+    async fn test_get_requests_by_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+
+        let bundle_id = Uuid::new_v4();
+        // Two legs in the bundle and one unrelated request
+        for bundle in [Some(bundle_id), Some(bundle_id), None] {
+            let request = RelayerRequest {
+                id: Uuid::new_v4(),
+                from_address: "0x1234567890123456789012345678901234567890".to_string(),
+                to_address: "0x0987654321098765432109876543210987654321".to_string(),
+                amount: "0".to_string(),
+                gas_limit: 21000,
+                gas_price: "0x4a817c800".to_string(),
+                data: Some("0x".to_string()),
+                nonce: 0,
+                chain_id: 1,
+                transaction_hash: None,
+                status: RequestStatus::Pending,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                error_message: None,
+                bundle_id: bundle,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
+            };
+
+            storage.create_request(request).await.unwrap();
+        }
+
+        let legs = storage
+            .get_requests_by_bundle(TenantScope::All, bundle_id)
+            .await
+            .unwrap();
+        assert_eq!(legs.len(), 2);
+        assert!(legs.iter().all(|r| r.bundle_id == Some(bundle_id)));
+
+        // The index must not leak into plain request listing
+        let all_requests = storage.get_requests(TenantScope::All, None).await.unwrap();
+        assert_eq!(all_requests.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_requests_by_wallet_pages_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+
+        let wallet = "0x00000000000000000000000000000000000000Aa";
+        let mut ids = Vec::new();
+        for (age, to, status) in [
+            (3, wallet, RequestStatus::Completed),
+            (2, wallet, RequestStatus::Failed),
+            (1, wallet, RequestStatus::Completed),
+            (
+                0,
+                "0x00000000000000000000000000000000000000bb",
+                RequestStatus::Completed,
+            ),
+        ] {
+            let created_at = Utc::now() - chrono::Duration::seconds(age);
+            let request = RelayerRequest {
+                id: Uuid::new_v4(),
+                from_address: "0x1234567890123456789012345678901234567890".to_string(),
+                to_address: to.to_string(),
+                amount: "0".to_string(),
+                gas_limit: 21000,
+                gas_price: "0x4a817c800".to_string(),
+                data: Some("0x".to_string()),
+                nonce: 0,
+                chain_id: 1,
+                transaction_hash: None,
+                status,
+                created_at,
+                updated_at: created_at,
+                error_message: None,
+                bundle_id: None,
+                execute_after: None,
+                speed: Speed::Standard,
+                broadcast_at: None,
+                mined_at: None,
+                blob_sidecar: None,
+                max_fee_per_blob_gas: None,
+                blob_gas_used: None,
+                blob_gas_price: None,
+                raw_transaction: None,
+                private_mempool: false,
+                payment: None,
+                fee: None,
+                resubmission_count: 0,
+                tenant: None,
+            };
+            ids.push(request.id);
+            storage.create_request(request).await.unwrap();
+        }
+
+        // Lookups ignore address case
+        let (page, cursor) = storage
+            .get_requests_by_wallet(None, &wallet.to_lowercase(), None, 2, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![ids[2], ids[1]]
+        );
+        let (page, cursor) = storage
+            .get_requests_by_wallet(None, wallet, cursor.as_deref(), 2, |_| true)
+            .await
+            .unwrap();
+        assert_eq!(page.iter().map(|r| r.id).collect::<Vec<_>>(), vec![ids[0]]);
+        assert!(cursor.is_none());
+
+        let (completed, _) = storage
+            .get_requests_by_wallet(None, wallet, None, 10, |r| {
+                r.status == RequestStatus::Completed
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            completed.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![ids[2], ids[0]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fee_samples_return_most_recent_oldest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+        let token = "0x00000000000000000000000000000000000000Aa";
+
+        for timestamp in 1..=3 {
+            let sample = FeeSample {
+                timestamp,
+                gas_price: format!("0x{:x}", timestamp),
+                rate: timestamp as f64,
+            };
+            storage.record_fee_sample(1, token, &sample).await.unwrap();
+        }
+
+        let recent = storage.get_fee_samples(1, token, 2).await.unwrap();
+        assert_eq!(
+            recent.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        // Tokens are matched case-insensitively and chains are kept apart
+        let all = storage
+            .get_fee_samples(1, &token.to_lowercase(), 10)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(storage
+            .get_fee_samples(2, token, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_commits_request_with_indexes() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+
+        let request = RelayerRequest {
+            id: Uuid::new_v4(),
+            from_address: "0x1234567890123456789012345678901234567890".to_string(),
+            to_address: "0x0987654321098765432109876543210987654321".to_string(),
+            amount: "0".to_string(),
+            gas_limit: 21000,
+            gas_price: "0x4a817c800".to_string(),
+            data: Some("0x".to_string()),
+            nonce: 0,
+            chain_id: 1,
+            transaction_hash: Some("0xABCDEF".to_string()),
+            status: RequestStatus::Processing,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
+        };
+        let resubmission = Resubmission {
+            status: 201,
+            transaction_hash: "0xabcdef".to_string(),
+            chain_id: "1".to_string(),
+        };
+
+        let mut batch = StorageBatch::new();
+        batch.put_request(&request).unwrap();
+        batch.add_resubmission(request.id, &resubmission).unwrap();
+        // Nothing is visible until the batch is committed
+        assert!(storage
+            .get_request(TenantScope::All, request.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        storage.commit(batch).await.unwrap();
+
+        let stored = storage
+            .get_request(TenantScope::All, request.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, RequestStatus::Processing);
+        assert_eq!(
+            storage.get_request_id_by_tx_hash("0xabcdef").await.unwrap(),
+            Some(request.id)
+        );
+        assert_eq!(
+            storage.get_resubmissions(request.id).await.unwrap().len(),
+            1
+        );
+        // Hashes are listed once per request, however often they were indexed
+        assert_eq!(
+            storage.get_transaction_hashes(request.id).await.unwrap(),
+            vec!["0xabcdef".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_due_schedules_come_out_earliest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+        let now = Utc::now();
+        let scheduled = |offset_secs: i64| ScheduledTransaction {
+            request_id: Uuid::new_v4(),
+            execute_after: now + chrono::Duration::seconds(offset_secs),
+            request: SendTransactionRequest {
+                to: "0x0000000000000000000000000000000000000002".to_string(),
+                data: "0x".to_string(),
+                capabilities: SendTransactionCapabilities {
+                    payment: PaymentCapability {
+                        payment_type: "sponsored".to_string(),
+                        token: "0x0000000000000000000000000000000000000000".to_string(),
+                        data: "".to_string(),
+                    },
+                    dry_run: false,
+                    fallback_payments: Vec::new(),
+                },
+                chain_id: "1".to_string(),
+                authorization_list: "".to_string(),
+                callback_url: None,
+                execute_after: None,
+                condition: None,
+                speed: None,
+                blob_sidecar: None,
+                safe: None,
+                value: None,
+                api_key: None,
+                quote_id: None,
+            },
+        };
+        let later = scheduled(-10);
+        let earliest = scheduled(-60);
+        let future = scheduled(60);
+
+        let mut batch = StorageBatch::new();
+        for entry in [&later, &earliest, &future] {
+            batch.put_schedule(entry).unwrap();
+        }
+        storage.commit(batch).await.unwrap();
+
+        let due = storage.get_due_schedules(now, 10).await.unwrap();
+        let ids: Vec<Uuid> = due.iter().map(|s| s.request_id).collect();
+        assert_eq!(ids, vec![earliest.request_id, later.request_id]);
+        assert_eq!(storage.get_due_schedules(now, 1).await.unwrap().len(), 1);
+
+        let mut batch = StorageBatch::new();
+        batch.remove_schedule(&earliest);
+        storage.commit(batch).await.unwrap();
+        let due = storage.get_due_schedules(now, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].request_id, later.request_id);
+    }
+
+    #[tokio::test]
+    async fn test_sponsored_gas_is_capped_per_project_and_period() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+
+        assert_eq!(
+            storage
+                .reserve_sponsored_gas("acme", "2024-01", 600_000, 1_000_000)
+                .await
+                .unwrap(),
+            Some(600_000)
+        );
+        // Over the allowance: rejected and not charged
+        assert_eq!(
+            storage
+                .reserve_sponsored_gas("acme", "2024-01", 500_000, 1_000_000)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            storage
+                .get_sponsored_gas_used("acme", "2024-01")
+                .await
+                .unwrap(),
+            600_000
+        );
+        // Each project and month starts from zero
+        assert_eq!(
+            storage
+                .get_sponsored_gas_used("acme", "2024-02")
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            storage
+                .release_sponsored_gas("acme", "2024-01", 100_000)
+                .await
+                .unwrap(),
+            500_000
+        );
+        assert_eq!(
+            storage
+                .reserve_sponsored_gas("acme", "2024-01", 500_000, 1_000_000)
+                .await
+                .unwrap(),
+            Some(1_000_000)
+        );
+    }
+
     #[test]
     fn test_storage_uptime() {
         let temp_dir = TempDir::new().unwrap();
@@ -718,6 +1374,7 @@ mod config_tests {
         assert_eq!(config.log_level, "info");
         assert_eq!(config.max_concurrent_requests, 100);
         assert_eq!(config.request_timeout, 30);
+        assert_eq!(config.gas_price_ttl_ms, 3000);
     }
 
     #[test]
@@ -733,3 +1390,2769 @@ mod config_tests {
         }
     }
 }
+
+#[cfg(all(test, feature = "client"))]
+mod server_tests {
+    use super::*;
+    use alloy::{
+        primitives::{Address, B256, U256},
+        rpc::types::FeeHistory,
+    };
+    use relayx::{
+        callback,
+        chain::{ChainBackend, MockChainClient, TxLog, TxTransfer},
+        client::{ClientError, RelayxClient},
+        rpc::RpcServer,
+        storage::StorageBatch,
+        types::{
+            Call, CallResult, CallbackDelivery, CallbackStatus, ChainRegistration, ConditionType,
+            CreateRecurringJobRequest, DexQuoter, EstimateFeeRequest, ExecutionCondition,
+            GetFeeHistoryRequest, GetGasTankDepositsRequest, GetQueueStatsRequest,
+            GetTransactionsByWalletRequest, MultichainLegStatus, QuoteRequestCapabilities,
+            ResendTransactionOptions, SafeTransaction, SendCallsRequest, SendRawTransactionRequest,
+            TxType,
+        },
+    };
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{mpsc, Arc},
+        thread,
+        time::Duration,
+    };
+
+    /// Well-known development key (anvil/hardhat account 0)
+    const DEV_PRIVATE_KEY: &str =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcab78d7ff2f84ae28";
+    const WALLET: &str = "0x00000000000000000000000000000000000000aa";
+    /// Default fee collector, which also receives gas tank deposits
+    const FEE_COLLECTOR: &str = "0x55f3a93f544e01ce4378d25e927d7c493b863bd6";
+
+    /// `executeWithRelayer` calldata; the mock chain does not decode past the selector
+    fn execute_with_relayer_calldata() -> String {
+        let abi: serde_json::Value =
+            serde_json::from_str(include_str!("../resources/abi.json")).expect("valid ABI file");
+        let abi: alloy::json_abi::JsonAbi = serde_json::from_value(abi["abi"].clone()).unwrap();
+        let selector = abi.function("executeWithRelayer").unwrap()[0].selector();
+        format!("0x{}{}", alloy::hex::encode(selector), "00".repeat(64))
+    }
+
+    /// Start a relayer whose only chain is served by `mock`
+    async fn start_with_mock(
+        temp_dir: &TempDir,
+        chain_id: &str,
+        mock: Arc<MockChainClient>,
+    ) -> (relayx::ServerHandle, RelayxClient) {
+        start_with_mock_config(create_test_config(temp_dir), temp_dir, chain_id, mock).await
+    }
+
+    async fn start_with_mock_config(
+        mut config: Config,
+        temp_dir: &TempDir,
+        chain_id: &str,
+        mock: Arc<MockChainClient>,
+    ) -> (relayx::ServerHandle, RelayxClient) {
+        config.relayer_private_key = Some(DEV_PRIVATE_KEY.to_string());
+        config.chain = ChainBackend::from_arc(mock);
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: chain_id.to_string(),
+            rpc_url: format!("http://chain-{}.invalid", chain_id),
+            tokens: Default::default(),
+            native_usd_feed: None,
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+
+        let storage = create_test_storage(temp_dir);
+        let server = RpcServer::new("127.0.0.1".to_string(), 0, storage, config).unwrap();
+        let handle = server.start().await.unwrap();
+        let client = RelayxClient::new(format!("http://{}", handle.local_addr()));
+        (handle, client)
+    }
+
+    fn send_request(chain_id: &str, payment_type: &str) -> SendTransactionRequest {
+        SendTransactionRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            capabilities: SendTransactionCapabilities {
+                payment: PaymentCapability {
+                    payment_type: payment_type.to_string(),
+                    token: "0x0000000000000000000000000000000000000000".to_string(),
+                    data: "".to_string(),
+                },
+                dry_run: false,
+                fallback_payments: Vec::new(),
+            },
+            chain_id: chain_id.to_string(),
+            authorization_list: "".to_string(),
+            callback_url: None,
+            execute_after: None,
+            condition: None,
+            speed: None,
+            blob_sidecar: None,
+            safe: None,
+            value: None,
+            api_key: None,
+            quote_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_broadcasts_through_chain_client() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_gas_estimate(90_000));
+        let (handle, client) = start_with_mock(&temp_dir, "900001", mock.clone()).await;
+
+        let response = client
+            .send_transaction(&send_request("900001", "sponsored"))
+            .await
+            .unwrap();
+
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0].to,
+            Some(WALLET.parse::<alloy::primitives::Address>().unwrap().into())
+        );
+        assert_eq!(sent[0].nonce, Some(0));
+        assert_eq!(sent[0].chain_id, Some(900001));
+
+        let status = client
+            .get_status(&[response.result[0].id.clone()])
+            .await
+            .unwrap();
+        assert_eq!(status.result[0].status, 201);
+        // No fee history from the mock: median inclusion (2 blocks) at the default 12s
+        assert_eq!(response.result[0].estimated_confirmation_seconds, Some(24));
+        assert_eq!(status.result[0].estimated_confirmation_seconds, Some(24));
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_multichain_reports_failed_legs_alongside_accepted_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_gas_estimate(90_000));
+        let (handle, client) = start_with_mock(&temp_dir, "900037", mock.clone()).await;
+
+        let leg = |chain_id: &str| MultichainTransaction {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            chain_id: chain_id.to_string(),
+            authorization_list: String::new(),
+            value: None,
+        };
+        let request = SendTransactionMultichainRequest {
+            transactions: vec![leg("900037"), leg("999999999")],
+            capabilities: send_request("900037", "sponsored").capabilities,
+            payment_chain_id: "900037".to_string(),
+            api_key: None,
+        };
+        let response = client.send_transaction_multichain(&request).await.unwrap();
+
+        assert_eq!(response.result.len(), 2);
+        let accepted = &response.result[0];
+        assert_eq!(accepted.status, MultichainLegStatus::Accepted);
+        assert!(accepted.error.is_none());
+        assert!(!accepted.id.is_empty());
+        assert_eq!(mock.sent_transactions().len(), 1);
+
+        let failed = &response.result[1];
+        assert_eq!(failed.chain_id, "999999999");
+        assert_eq!(failed.status, MultichainLegStatus::Failed);
+        assert!(failed.id.is_empty());
+        let error = failed.error.as_ref().unwrap();
+        assert_eq!(error.code, -32602);
+        assert_eq!(error.data.as_ref().unwrap()["reason"], "unsupported_chain");
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_estimate_tracks_tip_percentile() {
+        let gwei = 1_000_000_000u128;
+        let rewards = vec![5 * gwei, 4 * gwei, 3 * gwei, 2 * gwei, gwei];
+        let history = FeeHistory {
+            base_fee_per_gas: vec![18 * gwei; 3],
+            reward: Some(vec![rewards.clone(), rewards]),
+            ..Default::default()
+        };
+        // 20 gwei over an 18 gwei base fee tips 2 gwei, the 25th percentile: 3 blocks
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_price(20 * gwei)
+                .with_fee_history(history),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900004", mock).await;
+
+        let response = client
+            .send_transaction(&send_request("900004", "sponsored"))
+            .await
+            .unwrap();
+        assert_eq!(response.result[0].estimated_confirmation_seconds, Some(36));
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_reports_chain_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let reverting = Arc::new(MockChainClient::new().with_revert("nope"));
+        let (handle, client) = start_with_mock(&temp_dir, "900002", reverting.clone()).await;
+        match client
+            .send_transaction(&send_request("900002", "sponsored"))
+            .await
+        {
+            Err(ClientError::Rpc { code, data, .. }) => {
+                assert_eq!(code, relayx::errors::SIMULATION_FAILED);
+                assert_eq!(data.unwrap()["reason"], "simulation_failed");
+            }
+            other => panic!("expected simulation failure, got {:?}", other),
+        }
+        assert!(reverting.sent_transactions().is_empty());
+        handle.shutdown().await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let broke = Arc::new(MockChainClient::new().with_balance(alloy::primitives::U256::ZERO));
+        let (handle, client) = start_with_mock(&temp_dir, "900003", broke.clone()).await;
+        match client
+            .send_transaction(&send_request("900003", "native"))
+            .await
+        {
+            Err(ClientError::Rpc { data, .. }) => {
+                assert_eq!(data.unwrap()["reason"], "insufficient_balance");
+            }
+            other => panic!("expected insufficient balance, got {:?}", other),
+        }
+        assert!(broke.sent_transactions().is_empty());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_combines_gas_rate_and_eip1559_fields() {
+        let gwei = 1_000_000_000u128;
+        let history = FeeHistory {
+            base_fee_per_gas: vec![18 * gwei; 3],
+            reward: Some(vec![vec![gwei], vec![3 * gwei], vec![2 * gwei]]),
+            ..Default::default()
+        };
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_price(20 * gwei)
+                .with_gas_estimate(90_000)
+                .with_fee_history(history),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900007", mock).await;
+
+        let mut request = EstimateFeeRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            chain_id: "900007".to_string(),
+            token: "0x0000000000000000000000000000000000000000".to_string(),
+            speed: None,
+            value: None,
+        };
+        let estimate = client.estimate_fee(&request).await.unwrap();
+        assert_eq!(estimate.gas_limit, 90_000);
+        assert_eq!(estimate.gas_price, format!("0x{:x}", 20 * gwei));
+        assert_eq!(estimate.fee, (90_000 * 20 * gwei).to_string());
+        // Median tip of 2 gwei on top of twice the 18 gwei base fee
+        assert_eq!(
+            estimate.max_priority_fee_per_gas,
+            Some(format!("0x{:x}", 2 * gwei))
+        );
+        assert_eq!(estimate.max_fee_per_gas, Some(format!("0x{:x}", 38 * gwei)));
+        assert!(estimate.expiry > 0);
+
+        request.token = "0x00000000000000000000000000000000000000bb".to_string();
+        match client.estimate_fee(&request).await {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::UNSUPPORTED_PAYMENT_TOKEN);
+            }
+            other => panic!("expected unsupported token, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_beyond_u64_is_quoted_and_attested_in_full() {
+        use alloy::{primitives::PrimitiveSignature, signers::local::PrivateKeySigner};
+        use relayx::attestation::FeeQuote;
+
+        // 90000 gas at 1000000 gwei is 90 ETH, past u64::MAX wei
+        let wei_per_gas = 1_000_000_000_000_000u128;
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_price(wei_per_gas)
+                .with_gas_estimate(90_000),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900053", mock).await;
+
+        let request = EstimateFeeRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            chain_id: "900053".to_string(),
+            token: "0x0000000000000000000000000000000000000000".to_string(),
+            speed: None,
+            value: None,
+        };
+        let estimate = client.estimate_fee(&request).await.unwrap();
+        let fee = U256::from(90_000 * wei_per_gas);
+        assert!(fee > U256::from(u64::MAX));
+        assert_eq!(estimate.fee, fee.to_string());
+
+        let quote = FeeQuote {
+            wallet: WALLET.parse().unwrap(),
+            token: Address::ZERO,
+            fee,
+            expiry: estimate.expiry,
+            chain_id: 900053,
+        };
+        let signature = estimate.signature.expect("signed quote");
+        let bytes = alloy::hex::decode(signature.trim_start_matches("0x")).unwrap();
+        let relayer: PrivateKeySigner = DEV_PRIVATE_KEY.parse().unwrap();
+        assert_eq!(
+            PrimitiveSignature::from_raw(&bytes)
+                .unwrap()
+                .recover_address_from_prehash(&quote.hash())
+                .unwrap(),
+            relayer.address()
+        );
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_speed_lanes_price_and_broadcast_at_lane_gas_price() {
+        let gwei = 1_000_000_000u128;
+        let history = FeeHistory {
+            base_fee_per_gas: vec![18 * gwei; 3],
+            reward: Some(vec![vec![gwei], vec![3 * gwei], vec![2 * gwei]]),
+            ..Default::default()
+        };
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_price(5 * gwei)
+                .with_gas_estimate(90_000)
+                .with_fee_history(history),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900022", mock.clone()).await;
+
+        let mut request = EstimateFeeRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            chain_id: "900022".to_string(),
+            token: "0x0000000000000000000000000000000000000000".to_string(),
+            speed: None,
+            value: None,
+        };
+        let standard = client.estimate_fee(&request).await.unwrap();
+        assert_eq!(standard.speed, Speed::Standard);
+        assert_eq!(standard.gas_price, format!("0x{:x}", 5 * gwei));
+
+        // Fast lane: latest 18 gwei base fee plus the 2 gwei median tip
+        request.speed = Some(Speed::Fast);
+        let fast = client.estimate_fee(&request).await.unwrap();
+        assert_eq!(fast.speed, Speed::Fast);
+        assert_eq!(fast.gas_price, format!("0x{:x}", 20 * gwei));
+        assert!(fast.fee.parse::<u128>().unwrap() > standard.fee.parse::<u128>().unwrap());
+
+        let mut send = send_request("900022", "sponsored");
+        send.speed = Some(Speed::Fast);
+        client.send_transaction(&send).await.unwrap();
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].gas_price, Some(20 * gwei));
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_quote_is_priced_in_requested_payment_token() {
+        const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+        const TOKEN_FEED: &str = "0x00000000000000000000000000000000000000dd";
+        const NATIVE_FEED: &str = "0x00000000000000000000000000000000000000ee";
+        let word = |value: u64| {
+            alloy::primitives::Bytes::from(alloy::primitives::U256::from(value).to_be_bytes::<32>())
+        };
+        // Feeds answer with zero-decimal prices: native at $2048, the token at $256
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_call_result(NATIVE_FEED.parse().unwrap(), word(2048))
+                .with_call_result(TOKEN_FEED.parse().unwrap(), word(256))
+                .with_call_result(TOKEN.parse().unwrap(), word(6)),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900011", mock).await;
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900011".to_string(),
+            rpc_url: "http://chain-900011.invalid".to_string(),
+            tokens: [(TOKEN.to_string(), TOKEN_FEED.to_string())].into(),
+            native_usd_feed: Some(NATIVE_FEED.to_string()),
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+
+        let quote_for = |payment: serde_json::Value| QuoteRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            capabilities: Some(QuoteRequestCapabilities {
+                payment: Some(payment),
+            }),
+            chain_id: Some("900011".to_string()),
+            authorization_list: None,
+            speed: None,
+            blob_count: None,
+            value: None,
+            transactions: Vec::new(),
+            payment_chain_id: None,
+        };
+
+        let native = client
+            .get_quote(&quote_for(json!({ "type": "native" })))
+            .await
+            .unwrap();
+        assert_eq!(native.quote.fee, (90_000u64 * 20_000_000_000).to_string());
+        assert_eq!(native.quote.decimals, 18);
+        assert_eq!(native.quote.token.decimals, 18);
+        let breakdown = native.breakdown.as_ref().unwrap();
+        assert_eq!(breakdown.gas_limit, Some(90_000));
+        assert_eq!(
+            breakdown.execution_cost,
+            Some(format!("0x{:x}", 90_000u64 * 20_000_000_000))
+        );
+        assert_eq!(breakdown.oracle_rate, 1.0);
+        assert_eq!(breakdown.markup_bps, 0);
+        assert_eq!(native.relayer_calls.len(), 1);
+
+        let sponsored = client
+            .get_quote(&quote_for(json!({ "type": "sponsored" })))
+            .await
+            .unwrap();
+        assert_eq!(sponsored.quote.fee, "0");
+
+        // 0.0018 ETH at 8 tokens per ETH, in 6-decimal token units
+        let erc20 = client
+            .get_quote(&quote_for(json!({ "type": "erc20", "token": TOKEN })))
+            .await
+            .unwrap();
+        let erc20_fee: u64 = erc20.quote.fee.parse().unwrap();
+        assert!((14_400..=14_401).contains(&erc20_fee));
+        assert_eq!(erc20.quote.decimals, 6);
+        let breakdown = erc20.breakdown.as_ref().unwrap();
+        assert!((breakdown.oracle_rate - 8.0).abs() < 1e-9);
+        assert_eq!(breakdown.gas_price, breakdown.network_gas_price);
+        assert_eq!(erc20.quote.token.address, TOKEN);
+        assert_eq!(erc20.quote.token.decimals, 6);
+        assert_eq!(erc20.relayer_calls.len(), 2);
+        assert_eq!(erc20.relayer_calls[1].to, TOKEN);
+        assert_eq!(
+            erc20.relayer_calls[1].data,
+            format!(
+                "0xa9059cbb{:0>64}{:064x}",
+                erc20.fee_collector.trim_start_matches("0x").to_lowercase(),
+                erc20_fee
+            )
+        );
+
+        let unknown_token = "0x00000000000000000000000000000000000000ff";
+        match client
+            .get_quote(&quote_for(
+                json!({ "type": "erc20", "token": unknown_token }),
+            ))
+            .await
+        {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::UNSUPPORTED_PAYMENT_TOKEN);
+            }
+            other => panic!("expected unsupported token, got {:?}", other),
+        }
+        match client
+            .get_quote(&quote_for(json!({ "type": "permit2" })))
+            .await
+        {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::UNSUPPORTED_CAPABILITY);
+            }
+            other => panic!("expected unsupported capability, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_erc20_relay_balance_must_cover_l1_data_fee() {
+        const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+        const TOKEN_FEED: &str = "0x00000000000000000000000000000000000000dd";
+        const NATIVE_FEED: &str = "0x00000000000000000000000000000000000000ee";
+        const OP_GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000F";
+        let word = |value: u64| {
+            alloy::primitives::Bytes::from(alloy::primitives::U256::from(value).to_be_bytes::<32>())
+        };
+        // balanceOf(WALLET)
+        let balance_of = alloy::primitives::Bytes::from(
+            alloy::hex::decode(format!("70a08231{:0>64}", WALLET.trim_start_matches("0x")))
+                .unwrap(),
+        );
+        // 20_000 token units pay for execution (14_400) but not the 0.002 ETH L1 data fee
+        // (another 16_000) Base charges on top, at 8 tokens per ETH
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_call_result(NATIVE_FEED.parse().unwrap(), word(2048))
+                .with_call_result(TOKEN_FEED.parse().unwrap(), word(256))
+                .with_call_result(TOKEN.parse().unwrap(), word(6))
+                .with_call_result_for_input(TOKEN.parse().unwrap(), balance_of, word(20_000))
+                .with_call_result(
+                    OP_GAS_PRICE_ORACLE.parse().unwrap(),
+                    word(2_000_000_000_000_000),
+                ),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "8453", mock.clone()).await;
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "8453".to_string(),
+            rpc_url: "http://chain-8453.invalid".to_string(),
+            tokens: [(TOKEN.to_string(), TOKEN_FEED.to_string())].into(),
+            native_usd_feed: Some(NATIVE_FEED.to_string()),
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+
+        let mut request = send_request("8453", "erc20");
+        request.capabilities.payment.token = TOKEN.to_string();
+        match client.send_transaction(&request).await {
+            Err(ClientError::Rpc { data, .. }) => {
+                let data = data.unwrap();
+                assert_eq!(data["reason"], "insufficient_balance");
+                assert_eq!(data["available"], "20000");
+                let required: u64 = data["required"].as_str().unwrap().parse().unwrap();
+                assert!((30_400..=30_401).contains(&required), "{}", required);
+            }
+            other => panic!("expected insufficient balance, got {:?}", other),
+        }
+
+        request.capabilities.dry_run = true;
+        let result = client.send_transaction(&request).await.unwrap();
+        let report = result.result[0].dry_run.as_ref().unwrap();
+        assert!(!report.would_succeed);
+        assert!(report
+            .failure_reason
+            .as_ref()
+            .unwrap()
+            .starts_with("insufficient token balance"));
+        assert!(mock.sent_transactions().is_empty());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_multichain_quote_sums_leg_costs() {
+        let mock = Arc::new(MockChainClient::new().with_gas_estimate(90_000));
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900039", mock).await;
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900040".to_string(),
+            rpc_url: "http://chain-900040.invalid".to_string(),
+            tokens: Default::default(),
+            native_usd_feed: None,
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+
+        let leg = |chain_id: &str| MultichainTransaction {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            value: None,
+            chain_id: chain_id.to_string(),
+            authorization_list: String::new(),
+        };
+        let mut request = QuoteRequest {
+            to: String::new(),
+            data: String::new(),
+            capabilities: Some(QuoteRequestCapabilities {
+                payment: Some(json!({ "type": "native" })),
+            }),
+            chain_id: None,
+            authorization_list: None,
+            speed: None,
+            blob_count: None,
+            value: None,
+            transactions: vec![leg("900039"), leg("900040")],
+            payment_chain_id: Some("900039".to_string()),
+        };
+
+        // Both legs at 90000 gas and 20 gwei, paid in one fee on the payment chain
+        let quote = client.get_quote(&request).await.unwrap();
+        assert_eq!(quote.legs.len(), 2);
+        assert_eq!(quote.legs[1].chain_id, "900040");
+        assert_eq!(quote.legs[1].gas_limit, 90_000);
+        assert_eq!(
+            quote.legs[1].fee_wei,
+            format!("0x{:x}", 90_000u64 * 20_000_000_000)
+        );
+        assert_eq!(
+            quote.quote.fee,
+            (2 * 90_000u64 * 20_000_000_000).to_string()
+        );
+        assert!(quote.relayer_calls.is_empty());
+        assert!(quote.signature.is_some());
+
+        request.payment_chain_id = None;
+        match client.get_quote(&request).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_dex_quoter_prices_tokens_without_oracle_feed() {
+        const QUOTER: &str = "0x00000000000000000000000000000000000000a1";
+        const WRAPPED_NATIVE: &str = "0x00000000000000000000000000000000000000a2";
+        const DEEP_TOKEN: &str = "0x00000000000000000000000000000000000000a3";
+        const THIN_TOKEN: &str = "0x00000000000000000000000000000000000000a4";
+        let word = |value: u64| {
+            alloy::primitives::Bytes::from(alloy::primitives::U256::from(value).to_be_bytes::<32>())
+        };
+        let quote_input = |token: &str, amount_in: u64| -> alloy::primitives::Bytes {
+            format!(
+                "0xc6a5026a{:0>64}{:0>64}{:064x}{:064x}{:064x}",
+                WRAPPED_NATIVE.trim_start_matches("0x"),
+                token.trim_start_matches("0x"),
+                amount_in,
+                3000,
+                0
+            )
+            .parse()
+            .unwrap()
+        };
+        // 2000 tokens per ETH; the thin pool pays 5% less for the 0.1 ETH probe than for
+        // 0.001 ETH
+        let probe = 100_000_000_000_000_000;
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_call_result(DEEP_TOKEN.parse().unwrap(), word(6))
+                .with_call_result(THIN_TOKEN.parse().unwrap(), word(6))
+                .with_call_result_for_input(
+                    QUOTER.parse().unwrap(),
+                    quote_input(DEEP_TOKEN, probe),
+                    word(200_000_000),
+                )
+                .with_call_result_for_input(
+                    QUOTER.parse().unwrap(),
+                    quote_input(DEEP_TOKEN, probe / 100),
+                    word(2_000_000),
+                )
+                .with_call_result_for_input(
+                    QUOTER.parse().unwrap(),
+                    quote_input(THIN_TOKEN, probe),
+                    word(190_000_000),
+                )
+                .with_call_result_for_input(
+                    QUOTER.parse().unwrap(),
+                    quote_input(THIN_TOKEN, probe / 100),
+                    word(2_000_000),
+                ),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900025", mock).await;
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900025".to_string(),
+            rpc_url: "http://chain-900025.invalid".to_string(),
+            tokens: Default::default(),
+            native_usd_feed: None,
+            relayer_key_ref: None,
+            dex_quoter: Some(DexQuoter {
+                quoter: QUOTER.to_string(),
+                wrapped_native: WRAPPED_NATIVE.to_string(),
+                fee_tier: 3000,
+                probe_amount: probe,
+                max_price_impact_bps: 100,
+            }),
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+
+        let quote_for = |token: &str| QuoteRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            capabilities: Some(QuoteRequestCapabilities {
+                payment: Some(json!({ "type": "erc20", "token": token })),
+            }),
+            chain_id: Some("900025".to_string()),
+            authorization_list: None,
+            speed: None,
+            blob_count: None,
+            value: None,
+            transactions: Vec::new(),
+            payment_chain_id: None,
+        };
+
+        // 0.0018 ETH at 2000 tokens per ETH, in 6-decimal token units
+        let quote = client.get_quote(&quote_for(DEEP_TOKEN)).await.unwrap();
+        let fee: u64 = quote.quote.fee.parse().unwrap();
+        assert!((3_600_000..=3_600_001).contains(&fee));
+        assert_eq!(quote.quote.token.decimals, 6);
+        assert_eq!(quote.relayer_calls.len(), 2);
+
+        match client.get_quote(&quote_for(THIN_TOKEN)).await {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::UNSUPPORTED_PAYMENT_TOKEN);
+            }
+            other => panic!("expected unsupported token, got {:?}", other),
+        }
+
+        let caps = client.get_capabilities(Some("900025")).await.unwrap();
+        assert_eq!(caps.policies.payment_chains["erc20"], vec!["900025"]);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_blob_sidecar_relays_as_type_3_transaction() {
+        use alloy::eips::eip4844::{Blob, BlobTransactionSidecar, Bytes48};
+        let gwei = 1_000_000_000u64;
+        let sidecar = |blobs: usize, commitments: usize| BlobTransactionSidecar {
+            blobs: vec![Blob::default(); blobs],
+            commitments: vec![Bytes48::default(); commitments],
+            proofs: vec![Bytes48::default(); blobs],
+        };
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_blob_base_fee(u128::from(gwei)),
+        );
+        let (handle, client) = start_with_mock(&temp_dir, "900026", mock.clone()).await;
+
+        let mut request = send_request("900026", "sponsored");
+        request.blob_sidecar = Some(sidecar(1, 1));
+        let response = client.send_transaction(&request).await.unwrap();
+
+        // Blob gas is capped at twice the 1 gwei blob base fee
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].max_fee_per_blob_gas, Some(2 * u128::from(gwei)));
+        assert_eq!(
+            sent[0].blob_versioned_hashes.as_ref().map(Vec::len),
+            Some(1)
+        );
+        assert!(sent[0].sidecar.is_some());
+
+        let receipt = client.get_receipt(&response.result[0].id).await.unwrap();
+        assert_eq!(receipt.receipts.len(), 1);
+        assert_eq!(
+            receipt.receipts[0].blob_gas_used.as_deref(),
+            Some("0x20000")
+        );
+        assert_eq!(
+            receipt.receipts[0].blob_gas_price.as_deref(),
+            Some("0x3b9aca00")
+        );
+
+        let mut mismatched = send_request("900026", "sponsored");
+        mismatched.blob_sidecar = Some(sidecar(2, 1));
+        match client.send_transaction(&mismatched).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        // Quotes price the blob gas on top of execution gas
+        let quote = client
+            .get_quote(&QuoteRequest {
+                to: WALLET.to_string(),
+                data: execute_with_relayer_calldata(),
+                capabilities: None,
+                chain_id: Some("900026".to_string()),
+                authorization_list: None,
+                speed: None,
+                blob_count: Some(2),
+                value: None,
+                transactions: Vec::new(),
+                payment_chain_id: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            quote.quote.fee,
+            (90_000 * 20_000_000_000 + 2 * 131_072 * 2 * gwei).to_string()
+        );
+        handle.shutdown().await;
+
+        // A chain without blob support rejects sidecars outright
+        let temp_dir = TempDir::new().unwrap();
+        let legacy = Arc::new(MockChainClient::new().with_gas_estimate(90_000));
+        let (handle, client) = start_with_mock(&temp_dir, "900027", legacy.clone()).await;
+        let mut request = send_request("900027", "sponsored");
+        request.blob_sidecar = Some(sidecar(1, 1));
+        match client.send_transaction(&request).await {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::UNSUPPORTED_CAPABILITY);
+            }
+            other => panic!("expected unsupported capability, got {:?}", other),
+        }
+        assert!(legacy.sent_transactions().is_empty());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_tx_type_follows_base_fee_probe_and_override() {
+        let gwei = 1_000_000_000u128;
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_base_fee(18 * gwei)
+                .with_unmined_transactions(),
+        );
+        let (handle, client) = start_with_mock(&temp_dir, "900028", mock.clone()).await;
+
+        // London chain: capped at the 20 gwei price, tipping what the 18 gwei base fee leaves
+        let id = client
+            .send_transaction(&send_request("900028", "sponsored"))
+            .await
+            .unwrap()
+            .result[0]
+            .id
+            .clone();
+        let sent = mock.sent_transactions();
+        assert_eq!(sent[0].gas_price, None);
+        assert_eq!(sent[0].max_fee_per_gas, Some(20 * gwei));
+        assert_eq!(sent[0].max_priority_fee_per_gas, Some(2 * gwei));
+
+        // Replacements tip the whole cap so both fees rise
+        client.resend_transaction(&id, None).await.unwrap();
+        let sent = mock.sent_transactions();
+        assert_eq!(sent[1].max_fee_per_gas, Some(24 * gwei));
+        assert_eq!(sent[1].max_priority_fee_per_gas, Some(24 * gwei));
+        handle.shutdown().await;
+
+        // The same chain forced to legacy by its registration
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let forced = Arc::new(MockChainClient::new().with_base_fee(18 * gwei));
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900029", forced.clone()).await;
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900029".to_string(),
+            rpc_url: "http://chain-900029.invalid".to_string(),
+            tokens: Default::default(),
+            native_usd_feed: None,
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: Some(TxType::Legacy),
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+        client
+            .send_transaction(&send_request("900029", "sponsored"))
+            .await
+            .unwrap();
+        let sent = forced.sent_transactions();
+        assert_eq!(sent[0].gas_price, Some(20 * gwei));
+        assert_eq!(sent[0].max_fee_per_gas, None);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_safe_transaction_relays_exec_transaction() {
+        use alloy::signers::{local::PrivateKeySigner, SignerSync};
+        use relayx::safe::{self, SafeTx};
+
+        const SAFE: &str = "0x00000000000000000000000000000000000005af";
+        let safe_address: Address = SAFE.parse().unwrap();
+        let word = |value: U256| alloy::primitives::Bytes::from(value.to_be_bytes::<32>());
+        let mut keys = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        keys.sort_by_key(|key| key.address());
+        let mut owners = U256::from(32).to_be_bytes::<32>().to_vec();
+        owners.extend_from_slice(&U256::from(2).to_be_bytes::<32>());
+        for key in &keys {
+            owners.extend_from_slice(key.address().into_word().as_slice());
+        }
+        let read = |selector: [u8; 4]| alloy::primitives::Bytes::from(selector.to_vec());
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_call_result_for_input(
+                    safe_address,
+                    read(safe::GET_THRESHOLD_SELECTOR),
+                    word(U256::from(2)),
+                )
+                .with_call_result_for_input(
+                    safe_address,
+                    read(safe::GET_OWNERS_SELECTOR),
+                    owners.into(),
+                )
+                .with_call_result_for_input(
+                    safe_address,
+                    read(safe::NONCE_SELECTOR),
+                    word(U256::from(3)),
+                ),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900030", mock.clone()).await;
+
+        let safe_tx = SafeTx {
+            to: WALLET.parse().unwrap(),
+            value: U256::from(1_000),
+            data: alloy::primitives::Bytes::from(vec![0xab; 4]),
+            operation: 0,
+            safe_tx_gas: U256::ZERO,
+            base_gas: U256::ZERO,
+            gas_price: U256::ZERO,
+            gas_token: Address::ZERO,
+            refund_receiver: Address::ZERO,
+            nonce: U256::from(3),
+        };
+        let hash = safe_tx.hash(900030, safe_address);
+        let sign = |signers: [&PrivateKeySigner; 2]| -> Vec<u8> {
+            signers
+                .iter()
+                .flat_map(|key| key.sign_hash_sync(&hash).unwrap().as_bytes())
+                .collect()
+        };
+        let request_with = |signatures: &[u8], nonce: Option<&str>| {
+            let mut request = send_request("900030", "sponsored");
+            request.to = SAFE.to_string();
+            request.data = String::new();
+            request.safe = Some(SafeTransaction {
+                to: WALLET.to_string(),
+                value: Some("0x3e8".to_string()),
+                data: "0xabababab".to_string(),
+                operation: 0,
+                safe_tx_gas: None,
+                base_gas: None,
+                gas_price: None,
+                gas_token: None,
+                refund_receiver: None,
+                nonce: nonce.map(str::to_string),
+                signatures: format!("0x{}", alloy::hex::encode(signatures)),
+            });
+            request
+        };
+
+        let signatures = sign([&keys[0], &keys[1]]);
+        client
+            .send_transaction(&request_with(&signatures, None))
+            .await
+            .unwrap();
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, Some(safe_address.into()));
+        assert_eq!(
+            sent[0].input.input(),
+            Some(&safe_tx.exec_calldata(&signatures))
+        );
+
+        let reversed = sign([&keys[1], &keys[0]]);
+        match client
+            .send_transaction(&request_with(&reversed, None))
+            .await
+        {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::INVALID_SIGNATURE)
+            }
+            other => panic!("expected invalid signature, got {:?}", other),
+        }
+        match client
+            .send_transaction(&request_with(&signatures, Some("0x2")))
+            .await
+        {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+        // Calldata is built by the relayer, and only for a Safe
+        let mut with_data = request_with(&signatures, None);
+        with_data.data = execute_with_relayer_calldata();
+        let mut not_a_safe = request_with(&signatures, None);
+        not_a_safe.to = WALLET.to_string();
+        for request in [with_data, not_a_safe] {
+            match client.send_transaction(&request).await {
+                Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+                other => panic!("expected invalid params, got {:?}", other),
+            }
+        }
+        assert_eq!(mock.sent_transactions().len(), 1);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_calls_relays_aggregate3_with_call_results() {
+        use relayx::multicall;
+
+        const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+        let multicall_address: Address = multicall::MULTICALL3_ADDRESS.parse().unwrap();
+        let batch = vec![
+            (
+                WALLET.parse::<Address>().unwrap(),
+                alloy::primitives::Bytes::from(vec![0xab; 4]),
+            ),
+            (TOKEN.parse().unwrap(), alloy::primitives::Bytes::new()),
+        ];
+        let calldata = multicall::aggregate3_calldata(&batch);
+        // (bool,bytes)[] of a call returning the word 7 and a call that failed without data
+        let word = |value: usize| U256::from(value).to_be_bytes::<32>().to_vec();
+        let results = [
+            word(32),
+            word(2),
+            word(64),
+            word(64 + 4 * 32),
+            word(1),
+            word(64),
+            word(32),
+            word(7),
+            word(0),
+            word(64),
+            word(0),
+        ]
+        .concat();
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_call_result_for_input(multicall_address, calldata.clone(), results.into()),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900031", mock.clone()).await;
+
+        let request_with = |value: Option<&str>, payment_type: &str| SendCallsRequest {
+            calls: vec![
+                Call {
+                    to: WALLET.to_string(),
+                    data: "0xabababab".to_string(),
+                    value: value.map(str::to_string),
+                },
+                Call {
+                    to: TOKEN.to_string(),
+                    data: String::new(),
+                    value: None,
+                },
+            ],
+            chain_id: "900031".to_string(),
+            capabilities: send_request("900031", payment_type).capabilities,
+            callback_url: None,
+            speed: None,
+            api_key: None,
+        };
+        let expected = vec![
+            CallResult {
+                success: true,
+                return_data: format!("0x{:064x}", 7),
+            },
+            CallResult {
+                success: false,
+                return_data: "0x".to_string(),
+            },
+        ];
+
+        let response = client
+            .send_calls(&request_with(Some("0x0"), "sponsored"))
+            .await
+            .unwrap();
+        assert_eq!(response.calls, expected);
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, Some(multicall_address.into()));
+        assert_eq!(sent[0].input.input(), Some(&calldata));
+        let status = client
+            .get_status(&[response.result[0].id.clone()])
+            .await
+            .unwrap();
+        assert_eq!(status.result[0].calls, Some(expected));
+
+        // The relayer neither forwards value nor collects fees from Multicall3
+        for request in [
+            request_with(Some("0x1"), "sponsored"),
+            request_with(None, "native"),
+        ] {
+            match client.send_calls(&request).await {
+                Err(ClientError::Rpc { code, .. }) => {
+                    assert_eq!(code, relayx::errors::UNSUPPORTED_CAPABILITY)
+                }
+                other => panic!("expected unsupported capability, got {:?}", other),
+            }
+        }
+        let mut empty = request_with(None, "sponsored");
+        empty.calls.clear();
+        match client.send_calls(&empty).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+        assert_eq!(mock.sent_transactions().len(), 1);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_tracks_and_replaces() {
+        use alloy::{
+            consensus::{SignableTransaction, TxEnvelope, TxLegacy},
+            eips::eip2718::Encodable2718,
+            signers::{local::PrivateKeySigner, SignerSync},
+        };
+
+        let signer = PrivateKeySigner::random();
+        let sign = |chain_id: u64, nonce: u64, gas_price: u128| -> String {
+            let tx = TxLegacy {
+                chain_id: Some(chain_id),
+                nonce,
+                gas_price,
+                gas_limit: 21_000,
+                to: WALLET.parse::<Address>().unwrap().into(),
+                value: U256::from(1_000),
+                input: Default::default(),
+            };
+            let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+            let raw = TxEnvelope::from(tx.into_signed(signature)).encoded_2718();
+            format!("0x{}", alloy::hex::encode(raw))
+        };
+        let mock = Arc::new(MockChainClient::new().with_unmined_transactions());
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900032", mock.clone()).await;
+        let request_with = |raw: String, private_mempool: bool| SendRawTransactionRequest {
+            chain_id: "900032".to_string(),
+            raw_transaction: raw,
+            private_mempool,
+            callback_url: None,
+            api_key: None,
+        };
+        let expect_code = |result: Result<(), ClientError>, expected: i64| match result {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, expected),
+            other => panic!("expected error {}, got {:?}", expected, other),
+        };
+
+        let response = client
+            .send_raw_transaction(&request_with(sign(900032, 0, 1_000_000_000), false))
+            .await
+            .unwrap();
+        let id = response.result[0].id.clone();
+        let sent = mock.sent_raw_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "http://chain-900032.invalid");
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert_eq!(status.result[0].status, 201);
+
+        // Private submission needs a private endpoint for the chain
+        let replacement = sign(900032, 0, 2_000_000_000);
+        expect_code(
+            client
+                .send_raw_transaction(&request_with(replacement.clone(), true))
+                .await
+                .map(drop),
+            relayx::errors::UNSUPPORTED_CAPABILITY,
+        );
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900032".to_string(),
+            rpc_url: "http://chain-900032.invalid".to_string(),
+            tokens: Default::default(),
+            native_usd_feed: None,
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: Some("http://private-900032.invalid".to_string()),
+            event_abis: Default::default(),
+        });
+
+        // A replacement for the same sender and nonce is tracked as a resubmission
+        let response = client
+            .send_raw_transaction(&request_with(replacement, true))
+            .await
+            .unwrap();
+        assert_eq!(response.result[0].id, id);
+        let sent = mock.sent_raw_transactions();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1].0, "http://private-900032.invalid");
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert_eq!(status.result[0].resubmissions.len(), 1);
+
+        expect_code(
+            client
+                .send_raw_transaction(&request_with(sign(1, 1, 1_000_000_000), false))
+                .await
+                .map(drop),
+            -32602,
+        );
+        mock.set_transaction_count(signer.address(), 1);
+        expect_code(
+            client
+                .send_raw_transaction(&request_with(sign(900032, 0, 3_000_000_000), false))
+                .await
+                .map(drop),
+            -32602,
+        );
+        expect_code(client.resend_transaction(&id, None).await.map(drop), -32602);
+        assert_eq!(mock.sent_raw_transactions().len(), 2);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_forwards_value() {
+        // 0.5 ETH on top of 90k gas at 20 gwei, against a wallet holding 0.502 ETH
+        const VALUE: u64 = 500_000_000_000_000_000;
+        const GAS_FEE: u64 = 90_000 * 20_000_000_000;
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_balance(U256::from(VALUE + 2_000_000_000_000_000))
+                .with_unmined_transactions(),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900033", mock.clone()).await;
+        let request_with = |value: u64, payment_type: &str| SendTransactionRequest {
+            value: Some(format!("0x{:x}", value)),
+            ..send_request("900033", payment_type)
+        };
+        let wallet: Address = WALLET.parse().unwrap();
+
+        let response = client
+            .send_transaction(&request_with(VALUE, "native"))
+            .await
+            .unwrap();
+        assert_eq!(
+            mock.gas_estimates().last(),
+            Some(&(wallet, U256::from(VALUE)))
+        );
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].value, Some(U256::from(VALUE)));
+
+        // Replacements carry the same value
+        let id = response.result[0].id.clone();
+        client.resend_transaction(&id, None).await.unwrap();
+        assert_eq!(mock.sent_transactions()[1].value, Some(U256::from(VALUE)));
+
+        // The wallet must cover the value as well as the gas
+        for (request, expected) in [
+            (request_with(2 * VALUE, "native"), -32602),
+            (
+                request_with(VALUE, "sponsored"),
+                relayx::errors::UNSUPPORTED_CAPABILITY,
+            ),
+            (
+                SendTransactionRequest {
+                    value: Some("0xvalue".to_string()),
+                    ..send_request("900033", "native")
+                },
+                -32602,
+            ),
+        ] {
+            match client.send_transaction(&request).await {
+                Err(ClientError::Rpc { code, .. }) => assert_eq!(code, expected),
+                other => panic!("expected error {}, got {:?}", expected, other),
+            }
+        }
+        assert_eq!(mock.sent_transactions().len(), 2);
+
+        let quote_for = |value: Option<String>| QuoteRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            value,
+            capabilities: None,
+            chain_id: Some("900033".to_string()),
+            authorization_list: None,
+            speed: None,
+            blob_count: None,
+            transactions: Vec::new(),
+            payment_chain_id: None,
+        };
+        let quote = client
+            .get_quote(&quote_for(Some(format!("0x{:x}", VALUE))))
+            .await
+            .unwrap();
+        assert_eq!(quote.quote.fee, (GAS_FEE + VALUE).to_string());
+        assert_eq!(
+            client.get_quote(&quote_for(None)).await.unwrap().quote.fee,
+            GAS_FEE.to_string()
+        );
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_payment_options_fall_back_to_affordable_option() {
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_balance(U256::ZERO),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900034", mock.clone()).await;
+        let request_with = |options: &[&str]| {
+            let mut request = send_request("900034", options[0]);
+            request.capabilities.fallback_payments = options[1..]
+                .iter()
+                .map(|payment_type| send_request("900034", payment_type).capabilities.payment)
+                .collect();
+            request
+        };
+
+        // The wallet holds no native balance, so the relay falls back to sponsorship
+        let response = client
+            .send_transaction(&request_with(&["native", "sponsored"]))
+            .await
+            .unwrap();
+        let chosen = response.result[0].payment.as_ref().unwrap();
+        assert_eq!(chosen.payment_type, "sponsored");
+        let status = client
+            .get_status(std::slice::from_ref(&response.result[0].id))
+            .await
+            .unwrap();
+        assert_eq!(
+            status.result[0].payment.as_ref().unwrap().payment_type,
+            "sponsored"
+        );
+
+        // A single option is charged as before and not echoed back
+        let response = client
+            .send_transaction(&request_with(&["sponsored"]))
+            .await
+            .unwrap();
+        assert!(response.result[0].payment.is_none());
+
+        // When no option is affordable the preferred one reports why
+        match client
+            .send_transaction(&request_with(&["native", "gasTank"]))
+            .await
+        {
+            Err(ClientError::Rpc { code, message, .. }) => {
+                assert_eq!(code, -32602);
+                assert_eq!(message, "Insufficient balance");
+            }
+            other => panic!("expected insufficient balance, got {:?}", other),
+        }
+        assert_eq!(mock.sent_transactions().len(), 2);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_receipt_logs_decoded_with_registered_event_abis() {
+        use alloy::primitives::keccak256;
+
+        let token = Address::repeat_byte(0x70);
+        let other = Address::repeat_byte(0x71);
+        let recipient = Address::repeat_byte(0x22);
+        let wallet: Address = WALLET.parse().unwrap();
+        let transfer = TxLog {
+            address: token,
+            topics: vec![
+                keccak256("Transfer(address,address,uint256)"),
+                wallet.into_word(),
+                recipient.into_word(),
+            ],
+            data: U256::from(1_000_000).to_be_bytes::<32>().to_vec().into(),
+        };
+        // Same event, but from a contract without registered ABIs
+        let unregistered = TxLog {
+            address: other,
+            ..transfer.clone()
+        };
+        let mock = Arc::new(MockChainClient::new().with_receipt_logs(vec![transfer, unregistered]));
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900035", mock.clone()).await;
+        let events = serde_json::from_value(json!([{
+            "type": "event",
+            "name": "Transfer",
+            "anonymous": false,
+            "inputs": [
+                { "name": "from", "type": "address", "indexed": true },
+                { "name": "to", "type": "address", "indexed": true },
+                { "name": "value", "type": "uint256", "indexed": false }
+            ]
+        }]))
+        .unwrap();
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900035".to_string(),
+            rpc_url: "http://chain-900035.invalid".to_string(),
+            tokens: Default::default(),
+            native_usd_feed: None,
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: [(token.to_string(), events)].into_iter().collect(),
+        });
+
+        let response = client
+            .send_transaction(&send_request("900035", "sponsored"))
+            .await
+            .unwrap();
+        let id = response.result[0].id.clone();
+
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert!(status.result[0].receipts.is_empty());
+
+        // Finalizing the mined transaction stores its decoded receipt
+        match client.resend_transaction(&id, None).await {
+            Err(ClientError::Rpc { code, message, .. }) => {
+                assert_eq!(code, -32602);
+                assert_eq!(message, "Transaction is already mined");
+            }
+            other => panic!("expected already mined, got {:?}", other.map(drop)),
+        }
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert_eq!(status.result[0].status, 200);
+        let receipt = client.get_receipt(&id).await.unwrap();
+        for logs in [
+            &status.result[0].receipts[0].logs,
+            &receipt.receipts[0].logs,
+        ] {
+            assert_eq!(logs.len(), 2);
+            let decoded = logs[0].decoded.as_ref().unwrap();
+            assert_eq!(decoded.name, "Transfer");
+            assert_eq!(decoded.signature, "Transfer(address,address,uint256)");
+            assert_eq!(decoded.args["from"], wallet.to_checksum(None));
+            assert_eq!(decoded.args["to"], recipient.to_checksum(None));
+            assert_eq!(decoded.args["value"], "1000000");
+            assert!(logs[1].decoded.is_none());
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_reverted_transaction_reports_decoded_onchain_failure() {
+        let reason = "ERC20: transfer amount exceeds balance";
+        let mut revert = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        revert.extend_from_slice(&U256::from(reason.len()).to_be_bytes::<32>());
+        revert.extend_from_slice(reason.as_bytes());
+        revert.resize(68 + reason.len().next_multiple_of(32), 0);
+        let revert = alloy::primitives::Bytes::from(revert);
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_failed_receipts()
+                .with_replay_revert(revert.clone()),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) = start_with_mock(&temp_dir, "900038", mock).await;
+
+        let response = client
+            .send_transaction(&send_request("900038", "sponsored"))
+            .await
+            .unwrap();
+        let id = response.result[0].id.clone();
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert!(status.result[0].onchain_failure.is_empty());
+
+        // Finalizing the reverted transaction replays it for its revert data
+        assert!(client.resend_transaction(&id, None).await.is_err());
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert_eq!(status.result[0].status, 500);
+        let failure = &status.result[0].onchain_failure[0];
+        assert_eq!(failure.chain_id, "900038");
+        assert_eq!(failure.transaction_hash.len(), 66);
+        assert_eq!(failure.message, reason);
+        assert_eq!(failure.data, revert.to_string());
+
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_permit_payment_bundles_permit_and_transfer_from() {
+        const TOKEN: &str = "0x00000000000000000000000000000000000000cc";
+        const TOKEN_FEED: &str = "0x00000000000000000000000000000000000000dd";
+        const NATIVE_FEED: &str = "0x00000000000000000000000000000000000000ee";
+        const OWNER: &str = "00000000000000000000000000000000000000aa";
+        let word = |value: u64| {
+            alloy::primitives::Bytes::from(alloy::primitives::U256::from(value).to_be_bytes::<32>())
+        };
+        let permit_data = |value: u64, deadline: u64| {
+            format!(
+                "0x{:0>64}{:064x}{:064x}{:064x}{}{}",
+                OWNER,
+                value,
+                deadline,
+                27,
+                "11".repeat(32),
+                "22".repeat(32)
+            )
+        };
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_gas_estimate(90_000)
+                .with_call_result(NATIVE_FEED.parse().unwrap(), word(2048))
+                .with_call_result(TOKEN_FEED.parse().unwrap(), word(256))
+                .with_call_result(TOKEN.parse().unwrap(), word(6)),
+        );
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let (handle, client) =
+            start_with_mock_config(config.clone(), &temp_dir, "900024", mock).await;
+        config.register_runtime_chain(ChainRegistration {
+            chain_id: "900024".to_string(),
+            rpc_url: "http://chain-900024.invalid".to_string(),
+            tokens: [(TOKEN.to_string(), TOKEN_FEED.to_string())].into(),
+            native_usd_feed: Some(NATIVE_FEED.to_string()),
+            relayer_key_ref: None,
+            dex_quoter: None,
+            tx_type: None,
+            private_rpc_url: None,
+            event_abis: Default::default(),
+        });
+
+        let quote_for = |data: String| QuoteRequest {
+            to: WALLET.to_string(),
+            data: execute_with_relayer_calldata(),
+            capabilities: Some(QuoteRequestCapabilities {
+                payment: Some(json!({ "type": "permit", "token": TOKEN, "data": data })),
+            }),
+            chain_id: Some("900024".to_string()),
+            authorization_list: None,
+            speed: None,
+            blob_count: None,
+            value: None,
+            transactions: Vec::new(),
+            payment_chain_id: None,
+        };
+        let deadline = chrono::Utc::now().timestamp() as u64 + 3600;
+
+        let quote = client
+            .get_quote(&quote_for(permit_data(1_000_000, deadline)))
+            .await
+            .unwrap();
+        let fee: u64 = quote.quote.fee.parse().unwrap();
+        assert!((14_400..=14_401).contains(&fee));
+        assert_eq!(quote.relayer_calls.len(), 3);
+        assert_eq!(quote.relayer_calls[1].to, TOKEN);
+        assert_eq!(
+            quote.relayer_calls[1].data,
+            format!(
+                "0xd505accf{:0>64}{:0>64}{:064x}{:064x}{:064x}{}{}",
+                OWNER,
+                WALLET.trim_start_matches("0x").to_lowercase(),
+                1_000_000,
+                deadline,
+                27,
+                "11".repeat(32),
+                "22".repeat(32)
+            )
+        );
+        assert_eq!(
+            quote.relayer_calls[2].data,
+            format!(
+                "0x23b872dd{:0>64}{:0>64}{:064x}",
+                OWNER,
+                quote.fee_collector.trim_start_matches("0x").to_lowercase(),
+                fee
+            )
+        );
+
+        // A permit for less than the fee, an expired permit and malformed data are rejected
+        for (data, expected) in [
+            (
+                permit_data(1_000, deadline),
+                relayx::errors::INSUFFICIENT_FEE,
+            ),
+            (permit_data(1_000_000, 1), -32602),
+            ("0x1234".to_string(), -32602),
+        ] {
+            match client.get_quote(&quote_for(data)).await {
+                Err(ClientError::Rpc { code, .. }) => assert_eq!(code, expected),
+                other => panic!("expected error {}, got {:?}", expected, other),
+            }
+        }
+
+        let mut expired = send_request("900024", "permit");
+        expired.capabilities.payment.token = TOKEN.to_string();
+        expired.capabilities.payment.data = permit_data(1_000_000, 1);
+        match client.send_transaction(&expired).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        let caps = client.get_capabilities(Some("900024")).await.unwrap();
+        assert_eq!(caps.policies.payment_chains["permit"], vec!["900024"]);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_readiness_probe_over_json_rpc_and_http_get() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new());
+        let (handle, client) = start_with_mock(&temp_dir, "900012", mock.clone()).await;
+
+        let live = client.health_live().await.unwrap();
+        assert_eq!(live.status, "alive");
+        let ready = client.health_ready().await.unwrap();
+        assert_eq!(ready.status, "ready");
+        assert!(ready.storage_writable && ready.signer_loaded);
+        assert!(ready.chains_reachable >= 1);
+
+        let base = format!("http://{}", handle.local_addr());
+        let response = reqwest::get(format!("{}/health/live", base)).await.unwrap();
+        assert_eq!(response.status(), 200);
+        let response = reqwest::get(format!("{}/health/ready", base))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["status"], "ready");
+        handle.shutdown().await;
+
+        // Without a relayer key the relayer is alive but not ready
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config(&temp_dir);
+        config.chain = ChainBackend::from_arc(mock);
+        let storage = create_test_storage(&temp_dir);
+        let server = RpcServer::new("127.0.0.1".to_string(), 0, storage, config).unwrap();
+        let handle = server.start().await.unwrap();
+        let client = RelayxClient::new(format!("http://{}", handle.local_addr()));
+
+        assert_eq!(client.health_live().await.unwrap().status, "alive");
+        match client.health_ready().await {
+            Err(ClientError::Rpc { code, data, .. }) => {
+                assert_eq!(code, relayx::errors::NOT_READY);
+                let failing = data.unwrap()["failing"].clone();
+                assert!(failing.as_array().unwrap().contains(&json!("signer")));
+            }
+            other => panic!("expected not ready, got {:?}", other),
+        }
+        let response = reqwest::get(format!("http://{}/health/ready", handle.local_addr()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_version_reports_build_and_features() {
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) =
+            start_with_mock(&temp_dir, "900013", Arc::new(MockChainClient::new())).await;
+
+        let version = client.get_version().await.unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        assert!(!version.git_commit.is_empty());
+        assert!(version.build_timestamp.timestamp() > 0);
+        assert_eq!(version.spec_versions, vec!["2.0.0".to_string()]);
+        assert!(version.features.eip7702 && version.features.multichain);
+        assert!(!version.features.erc4337);
+        handle.shutdown().await;
+    }
+
+    /// Serve one HTTP request per entry of `statuses`, answering with that status. The
+    /// lowercased head and the body of each request are sent back over the channel.
+    fn callback_receiver(statuses: Vec<u16>) -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line.to_ascii_lowercase());
+                }
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .map_or(0, |value| value.trim().parse().unwrap());
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+                tx.send((head, body)).unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    fn header<'a>(head: &'a str, name: &str) -> &'a str {
+        head.lines()
+            .find_map(|line| line.strip_prefix(&format!("{}:", name.to_ascii_lowercase())))
+            .unwrap()
+            .trim()
+    }
+
+    #[tokio::test]
+    async fn test_callback_is_signed_and_retried_until_accepted() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = create_test_storage(&temp_dir);
+        let (url, deliveries) = callback_receiver(vec![500, 200]);
+        let private = callback::HostPolicy::AllowPrivate;
+        let request_id = uuid::Uuid::new_v4();
+        let mut batch = StorageBatch::new();
+        batch
+            .put_callback(
+                request_id,
+                &CallbackDelivery {
+                    url,
+                    status: CallbackStatus::Pending,
+                    attempts: 0,
+                    last_attempt_at: None,
+                    last_error: None,
+                    delivered_at: None,
+                },
+            )
+            .unwrap();
+        storage.commit(batch).await.unwrap();
+
+        let payload = json!({ "id": request_id.to_string(), "status": 200 });
+        callback::deliver(&storage, request_id, "hook-secret", private, &payload).await;
+
+        for _ in 0..2 {
+            let (head, body) = deliveries.recv_timeout(Duration::from_secs(5)).unwrap();
+            let timestamp: i64 = header(&head, callback::TIMESTAMP_HEADER).parse().unwrap();
+            assert_eq!(
+                header(&head, callback::SIGNATURE_HEADER),
+                callback::sign("hook-secret", timestamp, &body)
+            );
+            assert_eq!(
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+                payload
+            );
+        }
+        let delivered = storage.get_callback(request_id).await.unwrap().unwrap();
+        assert_eq!(delivered.status, CallbackStatus::Delivered);
+        assert_eq!(delivered.attempts, 2);
+        assert!(delivered.delivered_at.is_some() && delivered.last_error.is_none());
+
+        // A delivered callback is never sent again
+        callback::deliver(&storage, request_id, "hook-secret", private, &payload).await;
+        assert_eq!(
+            storage
+                .get_callback(request_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .attempts,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pending_callback_is_resumed_after_restart() {
+        use relayx::types::{RelayerRequest, RequestStatus};
+
+        let temp_dir = TempDir::new().unwrap();
+        let (url, deliveries) = callback_receiver(vec![200]);
+        let request = RelayerRequest {
+            id: uuid::Uuid::new_v4(),
+            from_address: "0x1234567890123456789012345678901234567890".to_string(),
+            to_address: WALLET.to_string(),
+            amount: "0".to_string(),
+            gas_limit: 21000,
+            gas_price: "0x4a817c800".to_string(),
+            data: Some("0x".to_string()),
+            nonce: 0,
+            chain_id: 900054,
+            transaction_hash: Some(format!("0x{}", "ab".repeat(32))),
+            status: RequestStatus::Completed,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            error_message: None,
+            bundle_id: None,
+            execute_after: None,
+            speed: Speed::Standard,
+            broadcast_at: None,
+            mined_at: None,
+            blob_sidecar: None,
+            max_fee_per_blob_gas: None,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            raw_transaction: None,
+            private_mempool: false,
+            payment: None,
+            fee: None,
+            resubmission_count: 0,
+            tenant: None,
+        };
+        // The previous process completed the request and failed one delivery attempt before
+        // it stopped
+        {
+            let storage = create_test_storage(&temp_dir);
+            let mut batch = StorageBatch::new();
+            batch.put_request(&request).unwrap();
+            batch
+                .put_callback(
+                    request.id,
+                    &CallbackDelivery {
+                        url,
+                        status: CallbackStatus::Pending,
+                        attempts: 1,
+                        last_attempt_at: Some(chrono::Utc::now()),
+                        last_error: Some("HTTP 503".to_string()),
+                        delivered_at: None,
+                    },
+                )
+                .unwrap();
+            storage.commit(batch).await.unwrap();
+        }
+
+        let mut config = create_test_config(&temp_dir);
+        config.callback_secret = Some("hook-secret".to_string());
+        config.callback_allow_private_hosts = true;
+        let (handle, client) = start_with_mock_config(
+            config,
+            &temp_dir,
+            "900054",
+            Arc::new(MockChainClient::new()),
+        )
+        .await;
+
+        let (_, body) = deliveries.recv_timeout(Duration::from_secs(10)).unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["id"], request.id.to_string());
+        let mut callback = None;
+        for _ in 0..50 {
+            let status = client.get_status(&[request.id.to_string()]).await.unwrap();
+            callback = status.result[0].callback.clone();
+            if callback.as_ref().unwrap().status != CallbackStatus::Pending {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        let callback = callback.unwrap();
+        assert_eq!(callback.status, CallbackStatus::Delivered);
+        assert_eq!(callback.attempts, 2);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_registers_callback_in_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config(&temp_dir);
+        config.callback_secret = Some("hook-secret".to_string());
+        let (handle, client) = start_with_mock_config(
+            config,
+            &temp_dir,
+            "900018",
+            Arc::new(MockChainClient::new()),
+        )
+        .await;
+
+        let mut request = send_request("900018", "sponsored");
+        request.callback_url = Some("ftp://example.com/hook".to_string());
+        match client.send_transaction(&request).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        // Hosts inside the relayer's network are refused
+        request.callback_url = Some("http://169.254.169.254/latest/meta-data".to_string());
+        match client.send_transaction(&request).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        request.callback_url = Some("https://example.com/hook".to_string());
+        let sent = client.send_transaction(&request).await.unwrap();
+        let status = client
+            .get_status(&[sent.result[0].id.clone()])
+            .await
+            .unwrap();
+        let callback = status.result[0].callback.as_ref().unwrap();
+        assert_eq!(callback.url, "https://example.com/hook");
+        assert_eq!(callback.status, CallbackStatus::Pending);
+        assert_eq!(callback.attempts, 0);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_transaction_is_broadcast_once_due() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new());
+        let (handle, client) = start_with_mock(&temp_dir, "900019", mock.clone()).await;
+
+        let mut request = send_request("900019", "sponsored");
+        let execute_after = chrono::Utc::now() + chrono::Duration::seconds(2);
+        request.execute_after = Some(execute_after);
+        let sent = client.send_transaction(&request).await.unwrap();
+        let id = sent.result[0].id.clone();
+        assert!(sent.result[0].estimated_confirmation_seconds.is_none());
+
+        // Held by the scheduler: nothing simulated or broadcast yet
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert_eq!(status.result[0].status, 202);
+        assert_eq!(status.result[0].execute_after, Some(execute_after));
+        assert!(mock.sent_transactions().is_empty());
+
+        let mut status = status.result[0].status;
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            status = client
+                .get_status(std::slice::from_ref(&id))
+                .await
+                .unwrap()
+                .result[0]
+                .status;
+            if status != 202 {
+                break;
+            }
+        }
+        assert_eq!(status, 201);
+        assert_eq!(mock.sent_transactions().len(), 1);
+        let result = client.get_status(&[id]).await.unwrap();
+        assert!(result.result[0].execute_after.is_none());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_conditional_transaction_waits_for_condition() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new());
+        let (handle, client) = start_with_mock(&temp_dir, "900021", mock.clone()).await;
+        let oracle = "0x00000000000000000000000000000000000000cc";
+
+        let mut request = send_request("900021", "sponsored");
+        request.condition = Some(ExecutionCondition {
+            condition_type: ConditionType::PriceFeed,
+            address: oracle.to_string(),
+            data: None,
+            operator: None,
+            value: Some("100".to_string()),
+            expires_at: None,
+        });
+        match client.send_transaction(&request).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        // The view returns nothing (false) until the mock is told otherwise
+        request.condition = Some(ExecutionCondition {
+            condition_type: ConditionType::Call,
+            address: oracle.to_string(),
+            data: Some("0x12345678".to_string()),
+            operator: None,
+            value: None,
+            expires_at: None,
+        });
+        let sent = client.send_transaction(&request).await.unwrap();
+        let id = sent.result[0].id.clone();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        assert_eq!(status.result[0].status, 202);
+        assert!(mock.sent_transactions().is_empty());
+
+        mock.set_call_result(
+            oracle.parse().unwrap(),
+            U256::from(1).to_be_bytes::<32>().to_vec().into(),
+        );
+        let mut status = 202;
+        for _ in 0..80 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            status = client
+                .get_status(std::slice::from_ref(&id))
+                .await
+                .unwrap()
+                .result[0]
+                .status;
+            if status != 202 {
+                break;
+            }
+        }
+        assert_eq!(status, 201);
+        assert_eq!(mock.sent_transactions().len(), 1);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_recurring_job_runs_on_schedule_until_paused() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new());
+        let (handle, client) = start_with_mock(&temp_dir, "900020", mock.clone()).await;
+
+        let mut create = CreateRecurringJobRequest {
+            request: send_request("900020", "sponsored"),
+            interval_seconds: 10,
+            start_at: None,
+        };
+        match client.create_recurring_job(&create).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        // Due right away; the next run is a day later
+        create.interval_seconds = 86_400;
+        let job = client.create_recurring_job(&create).await.unwrap();
+        assert!(!job.paused);
+        assert_eq!(job.runs, 0);
+
+        let mut listed = Vec::new();
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            listed = client.list_recurring_jobs(WALLET).await.unwrap().jobs;
+            if listed[0].runs > 0 {
+                break;
+            }
+        }
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, job.id);
+        assert_eq!(listed[0].runs, 1);
+        assert!(listed[0].last_error.is_none());
+        assert!(listed[0].next_run_at >= job.next_run_at + chrono::Duration::seconds(86_400));
+        assert_eq!(mock.sent_transactions().len(), 1);
+        let request_id = listed[0].last_request_id.unwrap().to_string();
+        let status = client.get_status(&[request_id]).await.unwrap();
+        assert_eq!(status.result[0].status, 201);
+
+        let paused = client
+            .pause_recurring_job(&job.id.to_string(), true)
+            .await
+            .unwrap();
+        assert!(paused.paused);
+        assert!(client.list_recurring_jobs(WALLET).await.unwrap().jobs[0].paused);
+        let resumed = client
+            .pause_recurring_job(&job.id.to_string(), false)
+            .await
+            .unwrap();
+        assert!(!resumed.paused);
+        assert_eq!(resumed.next_run_at, listed[0].next_run_at);
+
+        let other = "0x00000000000000000000000000000000000000bb";
+        assert!(client
+            .list_recurring_jobs(other)
+            .await
+            .unwrap()
+            .jobs
+            .is_empty());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_sponsorship_allowance_requires_a_project_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let (handle, client) =
+            start_with_mock(&temp_dir, "900017", Arc::new(MockChainClient::new())).await;
+
+        match client.get_sponsorship_allowance("not-a-project").await {
+            Err(ClientError::Rpc { code, data, .. }) => {
+                assert_eq!(code, -4100);
+                assert_eq!(data.unwrap()["reason"], "unauthorized");
+            }
+            other => panic!("expected unauthorized, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_wallet_history_lists_relayed_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new());
+        let (handle, client) = start_with_mock(&temp_dir, "900008", mock).await;
+
+        let sent = client
+            .send_transaction(&send_request("900008", "sponsored"))
+            .await
+            .unwrap();
+
+        let mut request = GetTransactionsByWalletRequest {
+            wallet: WALLET.to_string(),
+            status: None,
+            limit: None,
+            cursor: None,
+            api_key: None,
+        };
+        let history = client.get_transactions_by_wallet(&request).await.unwrap();
+        assert_eq!(history.result.len(), 1);
+        assert_eq!(history.result[0].id, sent.result[0].id);
+        assert_eq!(history.result[0].chain_id, "900008");
+        assert_eq!(history.result[0].status, 201);
+        assert!(history.result[0].transaction_hash.is_some());
+        assert!(history.next_cursor.is_none());
+
+        request.status = Some(200);
+        let history = client.get_transactions_by_wallet(&request).await.unwrap();
+        assert!(history.result.is_empty());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_exposes_sampled_gas_prices() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_gas_price(7_000_000_000));
+        let (handle, client) = start_with_mock(&temp_dir, "900009", mock).await;
+
+        // The sampler records its first sample as soon as the server starts
+        let request = GetFeeHistoryRequest {
+            chain_id: "900009".to_string(),
+            token: None,
+            limit: None,
+        };
+        let mut history = client.get_fee_history(&request).await.unwrap();
+        for _ in 0..50 {
+            if !history.samples.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            history = client.get_fee_history(&request).await.unwrap();
+        }
+
+        assert_eq!(history.token, "0x0000000000000000000000000000000000000000");
+        assert_eq!(history.samples.len(), 1);
+        assert_eq!(history.samples[0].gas_price, "0x1a13b8600");
+        assert!((history.samples[0].rate - 7e-9).abs() < 1e-18);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_resend_transaction_outbids_pending_transaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_unmined_transactions());
+        let (handle, client) = start_with_mock(&temp_dir, "900010", mock.clone()).await;
+
+        let sent = client
+            .send_transaction(&send_request("900010", "sponsored"))
+            .await
+            .unwrap();
+        let id = sent.result[0].id.clone();
+
+        match client
+            .resend_transaction(
+                &id,
+                Some(ResendTransactionOptions {
+                    gas_multiplier: Some(1.05),
+                    api_key: None,
+                }),
+            )
+            .await
+        {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        // Default bump of 20% over the 20 gwei network price
+        let first = client.resend_transaction(&id, None).await.unwrap();
+        assert_eq!(first.id, id);
+        assert_eq!(first.chain_id, "900010");
+        assert_eq!(first.gas_price, "0x59682f000");
+
+        // The network price dropped, so the bump applies to the pending replacement instead
+        mock.set_gas_price(1_000_000_000);
+        let second = client
+            .resend_transaction(
+                &id,
+                Some(ResendTransactionOptions {
+                    gas_multiplier: Some(1.5),
+                    api_key: None,
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.gas_price, "0x861c46800");
+        assert_ne!(second.transaction_hash, first.transaction_hash);
+        assert_eq!(mock.sent_transactions().len(), 3);
+
+        let status = client.get_status(&[id]).await.unwrap();
+        let resubmissions = &status.result[0].resubmissions;
+        assert_eq!(resubmissions.len(), 2);
+        assert_eq!(resubmissions[1].transaction_hash, second.transaction_hash);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_resend_transaction_reuses_the_request_nonce() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_unmined_transactions());
+        let (handle, client) = start_with_mock(&temp_dir, "900052", mock.clone()).await;
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let sent = client
+                .send_transaction(&send_request("900052", "sponsored"))
+                .await
+                .unwrap();
+            ids.push(sent.result[0].id.clone());
+        }
+
+        // Replacing the first relay reuses its nonce, not the next one the node would assign
+        client.resend_transaction(&ids[0], None).await.unwrap();
+        client.resend_transaction(&ids[1], None).await.unwrap();
+        let nonces: Vec<Option<u64>> = mock.sent_transactions().iter().map(|tx| tx.nonce).collect();
+        assert_eq!(nonces, vec![Some(0), Some(1), Some(0), Some(1)]);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_resubmission_history_compacted_and_capped() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_unmined_transactions());
+        let (handle, client) = start_with_mock(&temp_dir, "900036", mock.clone()).await;
+
+        let id = client
+            .send_transaction(&send_request("900036", "sponsored"))
+            .await
+            .unwrap()
+            .result[0]
+            .id
+            .clone();
+        let mut hashes = Vec::new();
+        for _ in 0..13 {
+            let resent = client.resend_transaction(&id, None).await.unwrap();
+            hashes.push(resent.transaction_hash);
+        }
+
+        // The first and the 10 latest records are kept, and every one is counted
+        let status = client.get_status(std::slice::from_ref(&id)).await.unwrap();
+        let result = &status.result[0];
+        assert_eq!(result.resubmission_count, Some(13));
+        let kept: Vec<&str> = result
+            .resubmissions
+            .iter()
+            .map(|resub| resub.transaction_hash.as_str())
+            .collect();
+        let expected: Vec<&str> = hashes[..1]
+            .iter()
+            .chain(&hashes[3..])
+            .map(String::as_str)
+            .collect();
+        assert_eq!(kept, expected);
+
+        // A dropped hash still finds its request
+        let by_hash = client.get_status(&[hashes[1].clone()]).await.unwrap();
+        assert_eq!(by_hash.result[0].id, id);
+
+        // Resubmissions stop at the default limit of 50
+        for _ in 13..50 {
+            client.resend_transaction(&id, None).await.unwrap();
+        }
+        match client.resend_transaction(&id, None).await {
+            Err(ClientError::Rpc { code, message, .. }) => {
+                assert_eq!(code, -32602);
+                assert!(message.contains("limit of 50 resubmissions"));
+            }
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+        let status = client.get_status(&[id]).await.unwrap();
+        assert_eq!(status.result[0].resubmission_count, Some(50));
+        assert_eq!(status.result[0].resubmissions.len(), 11);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_queue_stats_report_depth_latency_and_resubmissions() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_unmined_transactions());
+        let (handle, client) = start_with_mock(&temp_dir, "900023", mock.clone()).await;
+
+        // Mined after one resubmission
+        let mined = client
+            .send_transaction(&send_request("900023", "sponsored"))
+            .await
+            .unwrap()
+            .result[0]
+            .id
+            .clone();
+        let resent = client.resend_transaction(&mined, None).await.unwrap();
+        mock.mine(resent.transaction_hash.parse::<B256>().unwrap());
+        assert!(client.resend_transaction(&mined, None).await.is_err());
+
+        // Still in flight, and one scheduled for later
+        client
+            .send_transaction(&send_request("900023", "sponsored"))
+            .await
+            .unwrap();
+        let mut later = send_request("900023", "sponsored");
+        later.execute_after = Some(chrono::Utc::now() + chrono::Duration::hours(1));
+        client.send_transaction(&later).await.unwrap();
+
+        let request = GetQueueStatsRequest {
+            chain_id: Some("900023".to_string()),
+            window_seconds: None,
+            api_key: None,
+        };
+        let stats = client.get_queue_stats(&request).await.unwrap();
+        assert_eq!(stats.window_seconds, 3600);
+        assert_eq!(stats.chains.len(), 1);
+        let chain = &stats.chains[0];
+        assert_eq!(chain.chain_id, "900023");
+        assert_eq!(chain.queued, 1);
+        assert_eq!(chain.in_flight, 1);
+        assert_eq!(chain.broadcast, 2);
+        assert_eq!(chain.resubmission_rate, Some(0.5));
+        assert!(chain.avg_broadcast_latency_ms.is_some());
+        assert!(chain.avg_inclusion_ms.is_some());
+
+        let all = client
+            .get_queue_stats(&GetQueueStatsRequest::default())
+            .await
+            .unwrap();
+        assert!(all.chains.iter().any(|c| c == chain));
+
+        let zero_window = GetQueueStatsRequest {
+            window_seconds: Some(0),
+            ..request
+        };
+        match client.get_queue_stats(&zero_window).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_receipt_reports_replaced_hashes_and_the_one_mined() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_unmined_transactions());
+        let (handle, client) = start_with_mock(&temp_dir, "900014", mock.clone()).await;
+
+        let sent = client
+            .send_transaction(&send_request("900014", "sponsored"))
+            .await
+            .unwrap();
+        let id = sent.result[0].id.clone();
+
+        let pending = client.get_receipt(&id).await.unwrap();
+        assert_eq!(pending.status, 201);
+        assert_eq!(pending.transactions.len(), 1);
+        assert!(!pending.transactions[0].mined);
+        assert!(pending.mined_transaction_hash.is_none());
+        assert!(pending.receipts.is_empty());
+        let original = pending.transactions[0].transaction_hash.clone();
+
+        let replacement = client.resend_transaction(&id, None).await.unwrap();
+        // The original still lands first, outrunning its replacement
+        mock.mine(original.parse().unwrap());
+
+        let receipt = client
+            .get_receipt(&replacement.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(receipt.id, id);
+        assert_eq!(receipt.chain_id, "900014");
+        let hashes: Vec<_> = receipt
+            .transactions
+            .iter()
+            .map(|tx| (tx.transaction_hash.as_str(), tx.mined, tx.replaced))
+            .collect();
+        assert_eq!(
+            hashes,
+            vec![
+                (original.as_str(), true, true),
+                (replacement.transaction_hash.as_str(), false, false),
+            ]
+        );
+        assert_eq!(receipt.mined_transaction_hash, Some(original.clone()));
+        assert_eq!(receipt.receipts.len(), 1);
+        assert_eq!(receipt.receipts[0].transaction_hash, original);
+        assert_eq!(receipt.receipts[0].status, "0x1");
+        assert_eq!(receipt.receipts[0].block_number, "0x1");
+        assert_eq!(receipt.receipts[0].gas_used, "0x249f0");
+        assert_eq!(receipt.receipts[0].chain_id, "900014");
+
+        match client.get_receipt(&uuid::Uuid::new_v4().to_string()).await {
+            Err(ClientError::Rpc { code, .. }) => {
+                assert_eq!(code, relayx::errors::UNKNOWN_REQUEST)
+            }
+            other => panic!("expected unknown request, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_report_limits_that_relays_enforce() {
+        let temp_dir = TempDir::new().unwrap();
+        let mock = Arc::new(MockChainClient::new().with_gas_estimate(20_000_000));
+        let (handle, client) = start_with_mock(&temp_dir, "900015", mock.clone()).await;
+
+        let caps = client.get_capabilities(Some("900015")).await.unwrap();
+        assert_eq!(caps.limits.max_calldata_bytes, 131_072);
+        assert_eq!(caps.limits.max_gas, 15_000_000);
+        assert_eq!(caps.limits.quote_expiry_seconds, 600);
+        assert!(!caps.policies.sponsorship_requires_api_key);
+        assert_eq!(caps.policies.payment_chains["native"], vec!["900015"]);
+        assert_eq!(caps.policies.payment_chains["sponsored"], vec!["900015"]);
+        assert!(!caps.policies.payment_chains.contains_key("erc20"));
+
+        // Over the gas limit: rejected outright, reported by a dry run
+        match client
+            .send_transaction(&send_request("900015", "sponsored"))
+            .await
+        {
+            Err(ClientError::Rpc { code, data, .. }) => {
+                assert_eq!(code, relayx::errors::TRANSACTION_TOO_LARGE);
+                let data = data.unwrap();
+                assert_eq!(data["unit"], "gas");
+                assert_eq!(data["limit"], 15_000_000);
+            }
+            other => panic!("expected transaction too large, got {:?}", other),
+        }
+        let mut dry_run = send_request("900015", "sponsored");
+        dry_run.capabilities.dry_run = true;
+        let result = client.send_transaction(&dry_run).await.unwrap();
+        let report = result.result[0].dry_run.as_ref().unwrap();
+        assert!(!report.would_succeed);
+        assert!(report.failure_reason.as_ref().unwrap().contains("gas"));
+
+        let mut oversized = send_request("900015", "sponsored");
+        oversized.data = format!("0x{}", "00".repeat(131_073));
+        match client.send_transaction(&oversized).await {
+            Err(ClientError::Rpc { code, data, .. }) => {
+                assert_eq!(code, relayx::errors::TRANSACTION_TOO_LARGE);
+                let data = data.unwrap();
+                assert_eq!(data["unit"], "bytes");
+                assert_eq!(data["size"], 131_073);
+            }
+            other => panic!("expected transaction too large, got {:?}", other),
+        }
+        assert!(mock.sent_transactions().is_empty());
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_gas_tank_deposit_is_credited_once_and_debited_by_relays() {
+        use alloy::{
+            primitives::keccak256,
+            signers::{local::PrivateKeySigner, SignerSync},
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let key = PrivateKeySigner::random();
+        let wallet = format!("{:#x}", key.address());
+        // The wallet signs each debit of its tank, capped at `max_fee`
+        let authorize = |request: &mut SendTransactionRequest,
+                         key: &PrivateKeySigner,
+                         max_fee: u64| {
+            let deadline = U256::from(4_102_444_800u64);
+            let mut message = b"relayx gas tank debit".to_vec();
+            message.extend_from_slice(&U256::from(900016u64).to_be_bytes::<32>());
+            let wallet: Address = request.to.parse().unwrap();
+            message.extend_from_slice(wallet.into_word().as_slice());
+            let calldata = alloy::hex::decode(&request.data).unwrap();
+            message.extend_from_slice(keccak256(calldata).as_slice());
+            message.extend_from_slice(&U256::from(max_fee).to_be_bytes::<32>());
+            message.extend_from_slice(&deadline.to_be_bytes::<32>());
+            let signature = key
+                .sign_message_sync(keccak256(message).as_slice())
+                .unwrap();
+            let mut data = U256::from(max_fee).to_be_bytes::<32>().to_vec();
+            data.extend_from_slice(&deadline.to_be_bytes::<32>());
+            data.extend_from_slice(&U256::from(27 + u8::from(signature.v())).to_be_bytes::<32>());
+            data.extend_from_slice(&signature.r().to_be_bytes::<32>());
+            data.extend_from_slice(&signature.s().to_be_bytes::<32>());
+            request.capabilities.payment.data = format!("0x{}", alloy::hex::encode(data));
+        };
+        let deposit_hash = B256::repeat_byte(0xd1);
+        let stray_hash = B256::repeat_byte(0xd2);
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_transfer(
+                    deposit_hash,
+                    TxTransfer {
+                        from: key.address(),
+                        to: Some(FEE_COLLECTOR.parse().unwrap()),
+                        value: U256::from(10u128.pow(18)),
+                    },
+                )
+                .with_transfer(
+                    stray_hash,
+                    TxTransfer {
+                        from: WALLET.parse().unwrap(),
+                        to: Some(Address::repeat_byte(0x01)),
+                        value: U256::from(1u64),
+                    },
+                ),
+        );
+        let (handle, client) = start_with_mock(&temp_dir, "900016", mock.clone()).await;
+
+        let credited = client
+            .submit_gas_tank_deposit("900016", &deposit_hash.to_string())
+            .await
+            .unwrap();
+        assert_eq!(credited.balance, "1000000000000000000");
+        assert_eq!(credited.deposit.amount, "1000000000000000000");
+        assert!(credited.deposit.wallet.eq_ignore_ascii_case(&wallet));
+
+        for hash in [deposit_hash, stray_hash] {
+            match client
+                .submit_gas_tank_deposit("900016", &hash.to_string())
+                .await
+            {
+                Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+                other => panic!("expected invalid params, got {:?}", other),
+            }
+        }
+
+        // Naming the wallet is not enough to spend its tank
+        let mut relay = send_request("900016", "gasTank");
+        relay.to = wallet.clone();
+        for data in ["", "00".repeat(160).as_str()] {
+            let mut unsigned = relay.clone();
+            unsigned.capabilities.payment.data = data.to_string();
+            match client.send_transaction(&unsigned).await {
+                Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+                other => panic!("expected invalid params, got {:?}", other),
+            }
+        }
+        let mut forged = relay.clone();
+        authorize(&mut forged, &key, 10u64.pow(17));
+        forged.data = format!("{}00", forged.data);
+        match client.send_transaction(&forged).await {
+            Err(ClientError::Rpc { data, .. }) => {
+                assert_eq!(data.unwrap()["reason"], "invalid_signature")
+            }
+            other => panic!("expected invalid signature, got {:?}", other),
+        }
+
+        // A fee above the signed maximum is refused
+        let mut capped = relay.clone();
+        authorize(&mut capped, &key, 1);
+        match client.send_transaction(&capped).await {
+            Err(ClientError::Rpc { data, .. }) => {
+                assert_eq!(data.unwrap()["reason"], "insufficient_fee")
+            }
+            other => panic!("expected insufficient fee, got {:?}", other),
+        }
+        assert!(mock.sent_transactions().is_empty());
+
+        authorize(&mut relay, &key, 10u64.pow(17));
+        client.send_transaction(&relay).await.unwrap();
+        let sent = &mock.sent_transactions()[0];
+        let fee = u128::from(sent.gas.unwrap()) * sent.gas_price.unwrap();
+        let balance = client
+            .get_gas_tank_balance(&wallet, "900016")
+            .await
+            .unwrap();
+        assert_eq!(balance.balance, (10u128.pow(18) - fee).to_string());
+
+        // An authorization pays for one relay only
+        match client.send_transaction(&relay).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+
+        let deposits = client
+            .get_gas_tank_deposits(&GetGasTankDepositsRequest {
+                wallet: wallet.clone(),
+                chain_id: Some("900016".to_string()),
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(deposits.deposits, vec![credited.deposit]);
+
+        // A wallet without deposits cannot relay from the tank
+        let mut unfunded = send_request("900016", "gasTank");
+        let stranger = PrivateKeySigner::random();
+        unfunded.to = format!("{:#x}", stranger.address());
+        authorize(&mut unfunded, &stranger, 10u64.pow(17));
+        match client.send_transaction(&unfunded).await {
+            Err(ClientError::Rpc { data, .. }) => {
+                assert_eq!(data.unwrap()["reason"], "insufficient_balance")
+            }
+            other => panic!("expected insufficient balance, got {:?}", other),
+        }
+        assert_eq!(mock.sent_transactions().len(), 1);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_admin_cancel_withdraws_transaction_and_refunds_gas_tank() {
+        use alloy::{
+            primitives::{keccak256, TxKind},
+            signers::{local::PrivateKeySigner, SignerSync},
+        };
+        use relayx::types::{CancelRequestRequest, ListRequestsRequest};
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config(&temp_dir);
+        config.admin_token = Some("secret".to_string());
+        let key = PrivateKeySigner::random();
+        let wallet = format!("{:#x}", key.address());
+        let deposit_hash = B256::repeat_byte(0xd3);
+        let mock = Arc::new(
+            MockChainClient::new()
+                .with_unmined_transactions()
+                .with_transfer(
+                    deposit_hash,
+                    TxTransfer {
+                        from: key.address(),
+                        to: Some(FEE_COLLECTOR.parse().unwrap()),
+                        value: U256::from(10u128.pow(18)),
+                    },
+                ),
+        );
+        let (handle, client) =
+            start_with_mock_config(config, &temp_dir, "900055", mock.clone()).await;
+        client
+            .submit_gas_tank_deposit("900055", &deposit_hash.to_string())
+            .await
+            .unwrap();
+
+        let mut relay = send_request("900055", "gasTank");
+        relay.to = wallet.clone();
+        let max_fee = U256::from(10u64.pow(17));
+        let deadline = U256::from(4_102_444_800u64);
+        let mut message = b"relayx gas tank debit".to_vec();
+        message.extend_from_slice(&U256::from(900055u64).to_be_bytes::<32>());
+        message.extend_from_slice(key.address().into_word().as_slice());
+        message.extend_from_slice(keccak256(alloy::hex::decode(&relay.data).unwrap()).as_slice());
+        message.extend_from_slice(&max_fee.to_be_bytes::<32>());
+        message.extend_from_slice(&deadline.to_be_bytes::<32>());
+        let signature = key
+            .sign_message_sync(keccak256(message).as_slice())
+            .unwrap();
+        let mut data = max_fee.to_be_bytes::<32>().to_vec();
+        data.extend_from_slice(&deadline.to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(27 + u8::from(signature.v())).to_be_bytes::<32>());
+        data.extend_from_slice(&signature.r().to_be_bytes::<32>());
+        data.extend_from_slice(&signature.s().to_be_bytes::<32>());
+        relay.capabilities.payment.data = format!("0x{}", alloy::hex::encode(data));
+        let id = client.send_transaction(&relay).await.unwrap().result[0]
+            .id
+            .clone();
+
+        let cancel = CancelRequestRequest {
+            admin_token: "wrong".to_string(),
+            id: id.clone(),
+        };
+        match client.cancel_request(&cancel).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -4100),
+            other => panic!("expected unauthorized, got {:?}", other),
+        }
+        let cancel = CancelRequestRequest {
+            admin_token: "secret".to_string(),
+            ..cancel
+        };
+        let cancelled = client.cancel_request(&cancel).await.unwrap();
+
+        // The relay's nonce is taken by a self-transfer priced above it
+        let sent = mock.sent_transactions();
+        assert_eq!(sent.len(), 2);
+        let relayer: PrivateKeySigner = DEV_PRIVATE_KEY.parse().unwrap();
+        assert_eq!(sent[1].to, Some(TxKind::Call(relayer.address())));
+        assert_eq!(sent[1].value, Some(U256::ZERO));
+        assert_eq!(sent[1].nonce, sent[0].nonce);
+        assert!(sent[1].gas_price.unwrap() > sent[0].gas_price.unwrap());
+        assert!(cancelled.replacement_hash.is_some());
+
+        let fee = u128::from(sent[0].gas.unwrap()) * sent[0].gas_price.unwrap();
+        assert_eq!(cancelled.refunded_wei, Some(fee.to_string()));
+        let balance = client
+            .get_gas_tank_balance(&wallet, "900055")
+            .await
+            .unwrap();
+        assert_eq!(balance.balance, 10u128.pow(18).to_string());
+
+        let listed = client
+            .list_requests(&ListRequestsRequest {
+                admin_token: "secret".to_string(),
+                status: Some("failed".to_string()),
+                to: Some(wallet.clone()),
+                tenant: None,
+                limit: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed.requests.len(), 1);
+        assert_eq!(listed.requests[0].id.to_string(), id);
+        assert_eq!(
+            listed.requests[0].error_message.as_deref(),
+            Some(relayx::cli::CANCELLED_BY_OPERATOR)
+        );
+
+        // A cancelled request is no longer tracked and cannot be cancelled again
+        match client.cancel_request(&cancel).await {
+            Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32602),
+            other => panic!("expected invalid params, got {:?}", other),
+        }
+        assert_eq!(mock.sent_transactions().len(), 2);
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_slow_chain_times_out_with_retriable_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config(&temp_dir);
+        config.request_timeout = 1;
+        // Each call beats the per-call limit, but the handler as a whole does not
+        let slow = Arc::new(MockChainClient::new().with_latency(Duration::from_millis(400)));
+        let (handle, client) =
+            start_with_mock_config(config, &temp_dir, "900005", slow.clone()).await;
+
+        match client
+            .send_transaction(&send_request("900005", "sponsored"))
+            .await
+        {
+            Err(ClientError::Rpc { code, data, .. }) => {
+                assert_eq!(code, relayx::errors::TIMEOUT);
+                let data = data.unwrap();
+                assert_eq!(data["reason"], "timeout");
+                assert_eq!(data["timeoutSeconds"], 1);
+            }
+            other => panic!("expected timeout, got {:?}", other),
+        }
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_requests_over_concurrency_limit_are_busy() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = create_test_config(&temp_dir);
+        config.max_concurrent_requests = 1;
+        let slow = Arc::new(MockChainClient::new().with_latency(Duration::from_millis(300)));
+        let (handle, client) =
+            start_with_mock_config(config, &temp_dir, "900006", slow.clone()).await;
+
+        let request = send_request("900006", "sponsored");
+        let (first, second) = tokio::join!(
+            client.send_transaction(&request),
+            client.send_transaction(&request)
+        );
+        let busy = [&first, &second]
+            .into_iter()
+            .filter(|result| {
+                matches!(result, Err(ClientError::Rpc { code, .. }) if *code == relayx::errors::BUSY)
+            })
+            .count();
+        assert_eq!(busy, 1, "{:?} / {:?}", first, second);
+        assert!(first.is_ok() || second.is_ok());
+
+        // Health checks are never held back
+        client.health_check().await.unwrap();
+        handle.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_server_binds_ephemeral_port_and_shuts_down() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(&temp_dir);
+        let storage = create_test_storage(&temp_dir);
+
+        let server = RpcServer::new("127.0.0.1".to_string(), 0, storage, config).unwrap();
+        let handle = server.start().await.unwrap();
+        let addr = handle.local_addr();
+        assert_ne!(addr.port(), 0);
+
+        let client = RelayxClient::new(format!("http://{}", addr));
+        let health = client.health_check().await.unwrap();
+        assert_eq!(health.status, "healthy");
+        assert!(health.storage.sst_files_bytes.is_some());
+        assert!(health.storage.pending_compaction_bytes.is_some());
+
+        handle.shutdown().await;
+        assert!(client.health_check().await.is_err());
+    }
+}